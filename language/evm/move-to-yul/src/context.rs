@@ -767,6 +767,14 @@ impl<'a> Context<'a> {
                 Bool | U8 => 1,
                 U64 => 8,
                 U128 => 16,
+                U16 | U32 | U256 => {
+                    self.env.error(
+                        &self.env.unknown_loc(),
+                        "u16, u32 and u256 are not supported by the EVM backend",
+                    );
+                    // Placeholder size so that generation can continue after the error.
+                    32
+                }
                 // TODO: optimize for 20 bytes? Then we need primitives like LoadU160 etc.
                 Address | Signer => 32,
                 Num | Range | EventStore => {
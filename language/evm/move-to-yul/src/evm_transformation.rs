@@ -68,7 +68,7 @@ impl FunctionTargetProcessor for EvmTransformationProcessor {
                                 data.code[code_offset] = Bytecode::Call(
                                     *attr_id,
                                     dst.clone(),
-                                    Operation::CastU256,
+                                    Operation::CastU256FromWords,
                                     src.clone(),
                                     abort_condition.clone(),
                                 );
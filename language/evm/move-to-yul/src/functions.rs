@@ -270,6 +270,10 @@ impl<'a> FunctionGenerator<'a> {
                 Primitive(U8) => builtin(yul_fun_u8, dest, srcs),
                 Primitive(U64) => builtin(yul_fun_u64, dest, srcs),
                 Primitive(U128) => builtin(yul_fun_u128, dest, srcs),
+                Primitive(U16) | Primitive(U32) | Primitive(U256) => ctx.env.error(
+                    &target.get_bytecode_loc(bc.get_attr_id()),
+                    "u16, u32 and u256 are not supported by the EVM backend",
+                ),
                 Struct(mid, sid, _) => {
                     if ctx.is_u256(mid.qualified(sid)) {
                         builtin(yul_fun_u256, dest, srcs)
@@ -375,6 +379,12 @@ impl<'a> FunctionGenerator<'a> {
                             local(&srcs[0]),
                         )
                     }
+                    PackVariant(..) | UnpackVariant(..) | TestVariant(..) => {
+                        ctx.env.error(
+                            &target.get_bytecode_loc(bc.get_attr_id()),
+                            "enums are not supported by the EVM backend",
+                        );
+                    }
                     Destroy => {
                         print_loc();
                         self.destroy(ctx, &get_local_type(srcs[0]), local(&srcs[0]))
@@ -467,7 +477,13 @@ impl<'a> FunctionGenerator<'a> {
                     CastU8 => builtin(YulFunction::CastU8, dest, srcs),
                     CastU64 => builtin(YulFunction::CastU64, dest, srcs),
                     CastU128 => builtin(YulFunction::CastU128, dest, srcs),
-                    CastU256 => builtin(YulFunction::CastU256, dest, srcs),
+                    CastU256FromWords => builtin(YulFunction::CastU256, dest, srcs),
+                    CastU16 | CastU32 | CastU256 => {
+                        ctx.env.error(
+                            &target.get_bytecode_loc(bc.get_attr_id()),
+                            "u16, u32 and u256 are not supported by the EVM backend",
+                        );
+                    }
                     Not => builtin(YulFunction::LogicalNot, dest, srcs),
                     Add => builtin_typed(
                         YulFunction::AddU8,
@@ -599,6 +615,12 @@ impl<'a> FunctionGenerator<'a> {
             Constant::U8(v) => {
                 format!("{}", v)
             }
+            Constant::U16(v) => {
+                format!("{}", v)
+            }
+            Constant::U32(v) => {
+                format!("{}", v)
+            }
             Constant::U64(v) => {
                 format!("{}", v)
             }
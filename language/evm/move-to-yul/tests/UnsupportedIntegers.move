@@ -0,0 +1,11 @@
+// Tests that u16, u32 and u256 are reported as not supported instead of crashing the backend.
+#[evm_contract]
+module 0x2::M {
+    #[callable]
+    fun narrow_and_widen(x: u64): u64 {
+        let a = (x as u16) + 1;
+        let b = (x as u32);
+        let c = (x as u256);
+        (a as u64) + (b as u64) + (c as u64)
+    }
+}
@@ -178,7 +178,7 @@ impl<'r, 'l, S: MoveResolver> AsyncSession<'r, 'l, S> {
             .map_err(vm_error_to_async)?;
 
         // Check whether the actor state already exists.
-        let state = self
+        let (state, _) = self
             .vm_session
             .get_data_store()
             .load_resource(actor_addr, &state_type)
@@ -271,7 +271,7 @@ impl<'r, 'l, S: MoveResolver> AsyncSession<'r, 'l, S> {
             .load_type(&state_type_tag)
             .map_err(vm_error_to_async)?;
 
-        let actor_state_global = self
+        let (actor_state_global, _) = self
             .vm_session
             .get_data_store()
             .load_resource(actor_addr, &state_type)
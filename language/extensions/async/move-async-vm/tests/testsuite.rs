@@ -20,6 +20,7 @@ use move_core_types::{
     identifier::{IdentStr, Identifier},
     language_storage::{ModuleId, StructTag},
     resolver::{ModuleResolver, ResourceResolver},
+    resource_group::apply_resource_group_changes,
 };
 use move_prover_test_utils::{baseline_test::verify_or_update_baseline, extract_test_directives};
 use move_vm_types::gas_schedule::GasStatus;
@@ -181,7 +182,22 @@ impl Harness {
 
     fn commit_changeset(&self, changeset: ChangeSet) {
         for (addr, change) in changeset.into_inner() {
-            for (struct_tag, val) in change.into_inner().1 {
+            let (_, resources, resource_groups) = change.into_inner_with_groups();
+            // Resource groups are stored like resources, under the tag of the group
+            let groups = resource_groups
+                .into_iter()
+                .map(|(group, changes)| {
+                    let blob = self
+                        .resource_store
+                        .borrow()
+                        .get(&(addr, group.clone()))
+                        .cloned();
+                    let blob = apply_resource_group_changes(blob.as_deref(), changes)
+                        .expect("valid resource group changes");
+                    (group, blob)
+                })
+                .collect::<Vec<_>>();
+            for (struct_tag, val) in resources.into_iter().chain(groups) {
                 self.log(format!(
                     "  commit 0x{}::{}::{}[0x{}] := {}",
                     struct_tag.address.short_str_lossless(),
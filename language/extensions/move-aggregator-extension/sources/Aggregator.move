@@ -0,0 +1,50 @@
+/// Type of conflict-free counters.
+///
+/// An aggregator is an integer with an upper bound whose updates do not need to know its
+/// current value. Additions and subtractions are recorded as deltas, which the environment
+/// applies to the value in storage when the transaction is committed. Two transactions which
+/// only update the same aggregator therefore do not conflict. Reading the value of an
+/// aggregator resolves the deltas recorded so far, and gives up this property.
+module extensions::aggregator {
+    // native code raises this with errors::limit_exceeded()
+    const EAGGREGATOR_OVERFLOW: u64 = 100;
+    // native code raises this with errors::limit_exceeded()
+    const EAGGREGATOR_UNDERFLOW: u64 = 101;
+
+    /// Type of aggregators
+    struct Aggregator has store {
+        handle: u128,
+        limit: u128,
+    }
+
+    /// Create a new aggregator with value zero, which cannot exceed `limit`.
+    public fun create(limit: u128): Aggregator {
+        Aggregator{
+            handle: new_aggregator_handle(),
+            limit,
+        }
+    }
+
+    /// Returns the upper bound of the aggregator.
+    public fun limit(aggregator: &Aggregator): u128 {
+        aggregator.limit
+    }
+
+    /// Add `value` to the aggregator. Aborts if the result exceeds the limit.
+    public native fun add(aggregator: &mut Aggregator, value: u128);
+
+    /// Subtract `value` from the aggregator. Aborts if the result is below zero.
+    public native fun sub(aggregator: &mut Aggregator, value: u128);
+
+    /// Returns the value of the aggregator. Unlike `add` and `sub`, this depends on the
+    /// value in storage.
+    public native fun read(aggregator: &Aggregator): u128;
+
+    /// Destroy an aggregator, removing it from storage.
+    public native fun destroy(aggregator: Aggregator);
+
+    // ======================================================================================================
+    // Internal API
+
+    native fun new_aggregator_handle(): u128;
+}
@@ -0,0 +1,600 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A crate which extends Move by aggregators, counters which can be updated concurrently.
+//!
+//! See [`Aggregator.move`](../sources/Aggregator.move) for language use.
+//! See [`README.md`](../README.md) for integration into an adapter.
+
+use better_any::{Tid, TidAble};
+use move_binary_format::errors::{PartialVMError, PartialVMResult};
+use move_core_types::{
+    account_address::AccountAddress,
+    gas_schedule::{GasCarrier, InternalGasUnits},
+    vm_status::StatusCode,
+};
+use move_vm_runtime::{
+    native_functions,
+    native_functions::{NativeContext, NativeFunctionTable},
+};
+use move_vm_types::{
+    loaded_data::runtime_types::Type,
+    natives::function::NativeResult,
+    pop_arg,
+    values::{Reference, Struct, StructRef, Value},
+};
+use sha3::{Digest, Sha3_256};
+use smallvec::smallvec;
+use std::{
+    cell::RefCell,
+    cmp::max,
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    convert::TryInto,
+    fmt::Display,
+};
+
+// ===========================================================================================
+// Public Data Structures and Constants
+
+/// The representation of an aggregator handle. This is created from truncating a sha3-256
+/// based hash over a transaction hash provided by the environment and an aggregator creation
+/// counter local to the transaction.
+#[derive(Copy, Clone, Debug, PartialOrd, Ord, PartialEq, Eq)]
+pub struct AggregatorHandle(pub u128);
+
+impl Display for AggregatorHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "A-{:X}", self.0)
+    }
+}
+
+/// The accumulated change to the value of an aggregator.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DeltaUpdate {
+    Plus(u128),
+    Minus(u128),
+}
+
+/// A change to the value of an aggregator which does not depend on its value in storage.
+///
+/// Besides the accumulated change, this records the extremes reached while the updates were
+/// applied one by one, so that applying the delta fails exactly when one of the updates would
+/// have failed on the value in storage.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct DeltaOp {
+    pub update: DeltaUpdate,
+    /// The largest positive change reached by the updates.
+    pub max_positive: u128,
+    /// The largest negative change reached by the updates.
+    pub min_negative: u128,
+    /// The upper bound of the aggregator.
+    pub limit: u128,
+}
+
+impl DeltaOp {
+    fn new(limit: u128) -> Self {
+        Self {
+            update: DeltaUpdate::Plus(0),
+            max_positive: 0,
+            min_negative: 0,
+            limit,
+        }
+    }
+
+    fn is_noop(&self) -> bool {
+        self.update == DeltaUpdate::Plus(0) && self.max_positive == 0 && self.min_negative == 0
+    }
+
+    /// Records the addition of `value`. Fails if the addition overflows whatever the value in
+    /// storage is.
+    fn add(&mut self, value: u128) -> PartialVMResult<()> {
+        let update = match self.update {
+            DeltaUpdate::Plus(plus) => {
+                DeltaUpdate::Plus(plus.checked_add(value).ok_or_else(overflow_error)?)
+            }
+            DeltaUpdate::Minus(minus) if minus > value => DeltaUpdate::Minus(minus - value),
+            DeltaUpdate::Minus(minus) => DeltaUpdate::Plus(value - minus),
+        };
+        if let DeltaUpdate::Plus(plus) = update {
+            if plus > self.limit {
+                return Err(overflow_error());
+            }
+            self.max_positive = max(self.max_positive, plus);
+        }
+        self.update = update;
+        Ok(())
+    }
+
+    /// Records the subtraction of `value`. Fails if the subtraction underflows whatever the
+    /// value in storage is.
+    fn sub(&mut self, value: u128) -> PartialVMResult<()> {
+        let update = match self.update {
+            DeltaUpdate::Minus(minus) => {
+                DeltaUpdate::Minus(minus.checked_add(value).ok_or_else(underflow_error)?)
+            }
+            DeltaUpdate::Plus(plus) if plus >= value => DeltaUpdate::Plus(plus - value),
+            DeltaUpdate::Plus(plus) => DeltaUpdate::Minus(value - plus),
+        };
+        if let DeltaUpdate::Minus(minus) = update {
+            if minus > self.limit {
+                return Err(underflow_error());
+            }
+            self.min_negative = max(self.min_negative, minus);
+        }
+        self.update = update;
+        Ok(())
+    }
+
+    /// Applies the delta to the value of the aggregator in storage. Fails with the abort the
+    /// first failing update would have raised, if any.
+    pub fn apply_to(&self, base: u128) -> PartialVMResult<u128> {
+        if base
+            .checked_add(self.max_positive)
+            .map_or(true, |value| value > self.limit)
+        {
+            return Err(overflow_error());
+        }
+        if base < self.min_negative {
+            return Err(underflow_error());
+        }
+        Ok(match self.update {
+            DeltaUpdate::Plus(plus) => base + plus,
+            DeltaUpdate::Minus(minus) => base - minus,
+        })
+    }
+}
+
+/// A change of a single aggregator.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AggregatorChange {
+    /// The aggregator has been created or its value has been read; the value is overwritten.
+    Write(u128),
+    /// The delta has to be applied to the value in storage when the change set is committed.
+    Merge(DeltaOp),
+    /// The aggregator has been destroyed.
+    Delete,
+}
+
+/// An aggregator change set.
+#[derive(Default)]
+pub struct AggregatorChangeSet {
+    pub changes: BTreeMap<AggregatorHandle, AggregatorChange>,
+}
+
+/// An aggregator resolver which needs to be provided by the environment. This allows to lookup
+/// the values of aggregators in remote storage, as well as retrieve cost of aggregator
+/// operations.
+pub trait AggregatorResolver {
+    fn resolve_aggregator_value(
+        &self,
+        handle: &AggregatorHandle,
+    ) -> Result<Option<u128>, anyhow::Error>;
+
+    fn operation_cost(&self, op: AggregatorOperation) -> InternalGasUnits<GasCarrier>;
+}
+
+/// An aggregator operation, for supporting cost calculation.
+pub enum AggregatorOperation {
+    NewHandle,
+    Add,
+    Sub,
+    Read,
+    Destroy,
+}
+
+/// The native aggregator context extension. This needs to be attached to the
+/// NativeContextExtensions value which is passed into session functions, so its accessible
+/// from natives of this extension.
+#[derive(Tid)]
+pub struct NativeAggregatorContext<'a> {
+    resolver: &'a dyn AggregatorResolver,
+    txn_hash: u128,
+    aggregator_data: RefCell<AggregatorData>,
+}
+
+// See stdlib/Error.move
+const ECATEGORY_LIMIT_EXCEEDED: u8 = 8;
+
+const EAGGREGATOR_OVERFLOW: u64 = (100 << 8) + ECATEGORY_LIMIT_EXCEEDED as u64;
+const EAGGREGATOR_UNDERFLOW: u64 = (101 << 8) + ECATEGORY_LIMIT_EXCEEDED as u64;
+
+// ===========================================================================================
+// Private Data Structures and Constants
+
+/// A structure representing mutable data of the NativeAggregatorContext. This is in a RefCell
+/// of the overall context so we can mutate while still accessing the overall context.
+#[derive(Default)]
+struct AggregatorData {
+    new_aggregators: BTreeSet<AggregatorHandle>,
+    destroyed_aggregators: BTreeSet<AggregatorHandle>,
+    aggregators: BTreeMap<AggregatorHandle, Aggregator>,
+}
+
+/// What is known about the value of an aggregator.
+#[derive(Copy, Clone)]
+enum AggregatorState {
+    /// The value is known, because the aggregator has been created or read.
+    Data(u128),
+    /// Only the change to the value in storage is known.
+    Delta(DeltaOp),
+}
+
+/// A structure representing a single aggregator.
+struct Aggregator {
+    handle: AggregatorHandle,
+    limit: u128,
+    state: AggregatorState,
+    /// The value fetched from the resolver, if any.
+    base: Option<u128>,
+}
+
+/// The field index of the `handle` field in the `Aggregator` Move struct.
+const HANDLE_FIELD_INDEX: usize = 0;
+/// The field index of the `limit` field in the `Aggregator` Move struct.
+const LIMIT_FIELD_INDEX: usize = 1;
+
+// =========================================================================================
+// Implementation of Native Aggregator Context
+
+impl<'a> NativeAggregatorContext<'a> {
+    /// Create a new instance of a native aggregator context. This must be passed in via an
+    /// extension into VM session functions.
+    pub fn new(txn_hash: u128, resolver: &'a dyn AggregatorResolver) -> Self {
+        Self {
+            resolver,
+            txn_hash,
+            aggregator_data: Default::default(),
+        }
+    }
+
+    /// Computes the change set from a NativeAggregatorContext.
+    pub fn into_change_set(self) -> AggregatorChangeSet {
+        let NativeAggregatorContext {
+            aggregator_data, ..
+        } = self;
+        let AggregatorData {
+            new_aggregators,
+            destroyed_aggregators,
+            aggregators,
+        } = aggregator_data.into_inner();
+        let mut changes = BTreeMap::new();
+        for handle in &new_aggregators {
+            if !destroyed_aggregators.contains(handle) {
+                changes.insert(*handle, AggregatorChange::Write(0));
+            }
+        }
+        for (handle, aggregator) in aggregators {
+            match aggregator.state {
+                AggregatorState::Data(value) => {
+                    if aggregator.base != Some(value) {
+                        changes.insert(handle, AggregatorChange::Write(value));
+                    }
+                }
+                AggregatorState::Delta(delta) => {
+                    if !delta.is_noop() {
+                        changes.insert(handle, AggregatorChange::Merge(delta));
+                    }
+                }
+            }
+        }
+        for handle in destroyed_aggregators {
+            if !new_aggregators.contains(&handle) {
+                changes.insert(handle, AggregatorChange::Delete);
+            }
+        }
+        AggregatorChangeSet { changes }
+    }
+}
+
+impl AggregatorData {
+    /// Gets or creates the aggregator with the given handle. An aggregator created in this
+    /// session starts at zero, any other aggregator starts with an empty delta.
+    fn get_or_create_aggregator(
+        &mut self,
+        handle: AggregatorHandle,
+        limit: u128,
+    ) -> &mut Aggregator {
+        let is_new = self.new_aggregators.contains(&handle);
+        self.aggregators
+            .entry(handle)
+            .or_insert_with(|| Aggregator {
+                handle,
+                limit,
+                state: if is_new {
+                    AggregatorState::Data(0)
+                } else {
+                    AggregatorState::Delta(DeltaOp::new(limit))
+                },
+                base: None,
+            })
+    }
+}
+
+impl Aggregator {
+    /// Adds a value to the aggregator.
+    fn add(&mut self, value: u128) -> PartialVMResult<()> {
+        let limit = self.limit;
+        match &mut self.state {
+            AggregatorState::Data(current) => {
+                *current = current
+                    .checked_add(value)
+                    .filter(|new_value| *new_value <= limit)
+                    .ok_or_else(overflow_error)?;
+                Ok(())
+            }
+            AggregatorState::Delta(delta) => delta.add(value),
+        }
+    }
+
+    /// Subtracts a value from the aggregator.
+    fn sub(&mut self, value: u128) -> PartialVMResult<()> {
+        match &mut self.state {
+            AggregatorState::Data(current) => {
+                *current = current.checked_sub(value).ok_or_else(underflow_error)?;
+                Ok(())
+            }
+            AggregatorState::Delta(delta) => delta.sub(value),
+        }
+    }
+
+    /// Reads the value of the aggregator. If only a delta is known, the value is fetched from
+    /// the resolver and the delta is applied to it.
+    fn read(&mut self, context: &NativeAggregatorContext) -> PartialVMResult<u128> {
+        let value = match self.state {
+            AggregatorState::Data(value) => value,
+            AggregatorState::Delta(delta) => {
+                let base = context
+                    .resolver
+                    .resolve_aggregator_value(&self.handle)
+                    .map_err(|err| {
+                        partial_extension_error(format!(
+                            "remote aggregator resolver failure: {}",
+                            err
+                        ))
+                    })?
+                    .ok_or_else(|| partial_extension_error("undefined aggregator"))?;
+                self.base = Some(base);
+                delta.apply_to(base)?
+            }
+        };
+        self.state = AggregatorState::Data(value);
+        Ok(value)
+    }
+}
+
+// =========================================================================================
+// Native Function Implementations
+
+/// Returns all natives for aggregators.
+pub fn aggregator_natives(aggregator_addr: AccountAddress) -> NativeFunctionTable {
+    native_functions::make_table(
+        aggregator_addr,
+        &[
+            (
+                "aggregator",
+                "new_aggregator_handle",
+                native_new_aggregator_handle,
+            ),
+            ("aggregator", "add", native_add),
+            ("aggregator", "sub", native_sub),
+            ("aggregator", "read", native_read),
+            ("aggregator", "destroy", native_destroy),
+        ],
+    )
+}
+
+fn native_new_aggregator_handle(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    assert!(ty_args.is_empty());
+    assert!(args.is_empty());
+
+    let aggregator_context = context.extensions().get::<NativeAggregatorContext>();
+    let mut aggregator_data = aggregator_context.aggregator_data.borrow_mut();
+
+    // Take the transaction hash provided by the environment, combine it with the # of
+    // aggregators produced so far, sha256 this and select 16 bytes from the result. The
+    // domain separator keeps the handles apart from table handles.
+    let mut digest = Sha3_256::new();
+    Digest::update(&mut digest, b"aggregator");
+    Digest::update(&mut digest, aggregator_context.txn_hash.to_be_bytes());
+    Digest::update(
+        &mut digest,
+        aggregator_data.new_aggregators.len().to_be_bytes(),
+    );
+    let bytes: [u8; 16] = digest.finalize()[0..16].try_into().unwrap();
+    let id = u128::from_be_bytes(bytes);
+    assert!(aggregator_data.new_aggregators.insert(AggregatorHandle(id)));
+
+    Ok(NativeResult::ok(
+        aggregator_context
+            .resolver
+            .operation_cost(AggregatorOperation::NewHandle),
+        smallvec![Value::u128(id)],
+    ))
+}
+
+fn native_add(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    assert!(ty_args.is_empty());
+    assert_eq!(args.len(), 2);
+
+    let aggregator_context = context.extensions().get::<NativeAggregatorContext>();
+    let mut aggregator_data = aggregator_context.aggregator_data.borrow_mut();
+
+    let value = pop_arg!(args, u128);
+    let (handle, limit) = get_handle_and_limit(&pop_arg!(args, StructRef))?;
+    aggregator_data
+        .get_or_create_aggregator(handle, limit)
+        .add(value)?;
+
+    Ok(NativeResult::ok(
+        aggregator_context
+            .resolver
+            .operation_cost(AggregatorOperation::Add),
+        smallvec![],
+    ))
+}
+
+fn native_sub(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    assert!(ty_args.is_empty());
+    assert_eq!(args.len(), 2);
+
+    let aggregator_context = context.extensions().get::<NativeAggregatorContext>();
+    let mut aggregator_data = aggregator_context.aggregator_data.borrow_mut();
+
+    let value = pop_arg!(args, u128);
+    let (handle, limit) = get_handle_and_limit(&pop_arg!(args, StructRef))?;
+    aggregator_data
+        .get_or_create_aggregator(handle, limit)
+        .sub(value)?;
+
+    Ok(NativeResult::ok(
+        aggregator_context
+            .resolver
+            .operation_cost(AggregatorOperation::Sub),
+        smallvec![],
+    ))
+}
+
+fn native_read(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    assert!(ty_args.is_empty());
+    assert_eq!(args.len(), 1);
+
+    let aggregator_context = context.extensions().get::<NativeAggregatorContext>();
+    let mut aggregator_data = aggregator_context.aggregator_data.borrow_mut();
+
+    let (handle, limit) = get_handle_and_limit(&pop_arg!(args, StructRef))?;
+    let value = aggregator_data
+        .get_or_create_aggregator(handle, limit)
+        .read(aggregator_context)?;
+
+    Ok(NativeResult::ok(
+        aggregator_context
+            .resolver
+            .operation_cost(AggregatorOperation::Read),
+        smallvec![Value::u128(value)],
+    ))
+}
+
+fn native_destroy(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    assert!(ty_args.is_empty());
+    assert_eq!(args.len(), 1);
+
+    let aggregator_context = context.extensions().get::<NativeAggregatorContext>();
+    let mut aggregator_data = aggregator_context.aggregator_data.borrow_mut();
+
+    let mut fields = pop_arg!(args, Struct).unpack()?;
+    let handle = AggregatorHandle(fields.next().unwrap().value_as::<u128>()?);
+    aggregator_data.aggregators.remove(&handle);
+    assert!(aggregator_data.destroyed_aggregators.insert(handle));
+
+    Ok(NativeResult::ok(
+        aggregator_context
+            .resolver
+            .operation_cost(AggregatorOperation::Destroy),
+        smallvec![],
+    ))
+}
+
+// =========================================================================================
+// Helpers
+
+fn get_handle_and_limit(aggregator: &StructRef) -> PartialVMResult<(AggregatorHandle, u128)> {
+    let handle = read_u128_field(aggregator, HANDLE_FIELD_INDEX)?;
+    let limit = read_u128_field(aggregator, LIMIT_FIELD_INDEX)?;
+    Ok((AggregatorHandle(handle), limit))
+}
+
+fn read_u128_field(aggregator: &StructRef, index: usize) -> PartialVMResult<u128> {
+    aggregator
+        .borrow_field(index)?
+        .value_as::<Reference>()?
+        .read_ref()?
+        .value_as::<u128>()
+}
+
+fn partial_extension_error(msg: impl ToString) -> PartialVMError {
+    PartialVMError::new(StatusCode::VM_EXTENSION_ERROR).with_message(msg.to_string())
+}
+
+fn partial_abort_error(msg: impl ToString, code: u64) -> PartialVMError {
+    PartialVMError::new(StatusCode::ABORTED)
+        .with_message(msg.to_string())
+        .with_sub_status(code)
+}
+
+fn overflow_error() -> PartialVMError {
+    partial_abort_error("aggregator overflow", EAGGREGATOR_OVERFLOW)
+}
+
+fn underflow_error() -> PartialVMError {
+    partial_abort_error("aggregator underflow", EAGGREGATOR_UNDERFLOW)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn abort_code(err: PartialVMError) -> Option<u64> {
+        err.finish(move_binary_format::errors::Location::Undefined)
+            .sub_status()
+    }
+
+    #[test]
+    fn delta_apply() {
+        let mut delta = DeltaOp::new(100);
+        delta.add(30).unwrap();
+        delta.sub(50).unwrap();
+        delta.add(10).unwrap();
+        assert_eq!(delta.update, DeltaUpdate::Minus(10));
+        assert_eq!(delta.max_positive, 30);
+        assert_eq!(delta.min_negative, 20);
+
+        assert_eq!(delta.apply_to(20).unwrap(), 10);
+        assert_eq!(delta.apply_to(70).unwrap(), 60);
+        // The subtraction of 50 would have failed on a value of 19.
+        assert_eq!(
+            abort_code(delta.apply_to(19).unwrap_err()),
+            Some(EAGGREGATOR_UNDERFLOW)
+        );
+        // The addition of 30 would have failed on a value of 71.
+        assert_eq!(
+            abort_code(delta.apply_to(71).unwrap_err()),
+            Some(EAGGREGATOR_OVERFLOW)
+        );
+    }
+
+    #[test]
+    fn delta_out_of_bounds() {
+        let mut delta = DeltaOp::new(100);
+        delta.add(100).unwrap();
+        assert_eq!(
+            abort_code(delta.add(1).unwrap_err()),
+            Some(EAGGREGATOR_OVERFLOW)
+        );
+        delta.sub(200).unwrap();
+        assert_eq!(
+            abort_code(delta.sub(1).unwrap_err()),
+            Some(EAGGREGATOR_UNDERFLOW)
+        );
+        assert_eq!(delta.update, DeltaUpdate::Minus(100));
+    }
+}
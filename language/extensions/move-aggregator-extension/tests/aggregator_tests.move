@@ -0,0 +1,56 @@
+#[test_only]
+module extensions::aggregator_tests {
+    use extensions::aggregator as A;
+
+    struct Supply has key {
+        total: A::Aggregator
+    }
+
+    #[test]
+    fun create_add_sub_read() {
+        let a = A::create(100);
+        A::add(&mut a, 12);
+        A::add(&mut a, 30);
+        A::sub(&mut a, 2);
+        assert!(A::read(&a) == 40, 1);
+        assert!(A::limit(&a) == 100, 2);
+        A::destroy(a)
+    }
+
+    #[test]
+    fun add_up_to_limit() {
+        let a = A::create(100);
+        A::add(&mut a, 100);
+        assert!(A::read(&a) == 100, 1);
+        A::sub(&mut a, 100);
+        assert!(A::read(&a) == 0, 2);
+        A::destroy(a)
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 25608)]
+    fun overflow() {
+        let a = A::create(100);
+        A::add(&mut a, 60);
+        A::add(&mut a, 41);
+        A::destroy(a)
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 25864)]
+    fun underflow() {
+        let a = A::create(100);
+        A::add(&mut a, 10);
+        A::sub(&mut a, 11);
+        A::destroy(a)
+    }
+
+    #[test(s = @0x42)]
+    fun aggregator_in_resource(s: signer) acquires Supply {
+        move_to(&s, Supply{ total: A::create(1000) });
+        let supply = borrow_global_mut<Supply>(@0x42);
+        A::add(&mut supply.total, 500);
+        A::sub(&mut supply.total, 200);
+        assert!(A::read(&supply.total) == 300, 1);
+    }
+}
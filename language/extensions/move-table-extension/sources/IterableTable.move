@@ -0,0 +1,142 @@
+/// Type of tables which can be iterated.
+///
+/// An iterable table remembers the order in which its entries have been added. The entries
+/// are linked into a doubly linked list, which can be traversed in both directions starting at
+/// `head_key` or `tail_key`.
+module extensions::iterable_table {
+    use std::option::{Self, Option};
+    use extensions::table::{Self, Table};
+
+    /// Wrapper for values, linking them to the keys of the neighbouring entries.
+    struct IterableValue<K: copy + store + drop, V: store> has store {
+        val: V,
+        prev: Option<K>,
+        next: Option<K>,
+    }
+
+    /// Type of iterable tables
+    struct IterableTable<K: copy + store + drop, V: store> has store {
+        inner: Table<K, IterableValue<K, V>>,
+        head: Option<K>,
+        tail: Option<K>,
+    }
+
+    /// Create a new IterableTable.
+    public fun new<K: copy + store + drop, V: store>(): IterableTable<K, V> {
+        IterableTable{
+            inner: table::new(),
+            head: option::none(),
+            tail: option::none(),
+        }
+    }
+
+    /// Destroy a table. The table must be empty to succeed.
+    public fun destroy_empty<K: copy + store + drop, V: store>(table: IterableTable<K, V>) {
+        let IterableTable{ inner, head: _, tail: _ } = table;
+        table::destroy_empty(inner)
+    }
+
+    /// Add a new entry to the end of the table. Aborts if an entry for this key already exists.
+    public fun add<K: copy + store + drop, V: store>(table: &mut IterableTable<K, V>, key: K, val: V) {
+        let wrapped = IterableValue{
+            val,
+            prev: table.tail,
+            next: option::none(),
+        };
+        table::add(&mut table.inner, key, wrapped);
+        if (option::is_some(&table.tail)) {
+            let tail = *option::borrow(&table.tail);
+            table::borrow_mut(&mut table.inner, tail).next = option::some(key);
+        } else {
+            table.head = option::some(key);
+        };
+        table.tail = option::some(key);
+    }
+
+    /// Acquire an immutable reference to the value which `key` maps to.
+    /// Aborts if there is no entry for `key`.
+    public fun borrow<K: copy + store + drop, V: store>(table: &IterableTable<K, V>, key: K): &V {
+        &table::borrow(&table.inner, key).val
+    }
+
+    /// Acquire a mutable reference to the value which `key` maps to.
+    /// Aborts if there is no entry for `key`.
+    public fun borrow_mut<K: copy + store + drop, V: store>(table: &mut IterableTable<K, V>, key: K): &mut V {
+        &mut table::borrow_mut(&mut table.inner, key).val
+    }
+
+    /// Remove from `table` and return the value which `key` maps to.
+    /// Aborts if there is no entry for `key`.
+    public fun remove<K: copy + store + drop, V: store>(table: &mut IterableTable<K, V>, key: K): V {
+        let (val, _, _) = remove_iter(table, key);
+        val
+    }
+
+    /// Returns true iff `table` contains an entry for `key`.
+    public fun contains<K: copy + store + drop, V: store>(table: &IterableTable<K, V>, key: K): bool {
+        table::contains(&table.inner, key)
+    }
+
+    /// Returns the length of the table, i.e. the number of entries.
+    public fun length<K: copy + store + drop, V: store>(table: &IterableTable<K, V>): u64 {
+        table::length(&table.inner)
+    }
+
+    /// Returns true if this table is empty.
+    public fun empty<K: copy + store + drop, V: store>(table: &IterableTable<K, V>): bool {
+        table::empty(&table.inner)
+    }
+
+    // ======================================================================================================
+    // Iteration
+
+    /// Returns the key of the first entry, if any.
+    public fun head_key<K: copy + store + drop, V: store>(table: &IterableTable<K, V>): Option<K> {
+        table.head
+    }
+
+    /// Returns the key of the last entry, if any.
+    public fun tail_key<K: copy + store + drop, V: store>(table: &IterableTable<K, V>): Option<K> {
+        table.tail
+    }
+
+    /// Acquire an immutable reference to the value which `key` maps to, together with the keys
+    /// of the previous and the next entry. Aborts if there is no entry for `key`.
+    public fun borrow_iter<K: copy + store + drop, V: store>(table: &IterableTable<K, V>, key: K): (&V, Option<K>, Option<K>) {
+        let v = table::borrow(&table.inner, key);
+        (&v.val, v.prev, v.next)
+    }
+
+    /// Acquire a mutable reference to the value which `key` maps to, together with the keys
+    /// of the previous and the next entry. Aborts if there is no entry for `key`.
+    public fun borrow_iter_mut<K: copy + store + drop, V: store>(table: &mut IterableTable<K, V>, key: K): (&mut V, Option<K>, Option<K>) {
+        let v = table::borrow_mut(&mut table.inner, key);
+        (&mut v.val, v.prev, v.next)
+    }
+
+    /// Remove from `table` and return the value which `key` maps to, together with the keys of
+    /// the previous and the next entry. Aborts if there is no entry for `key`.
+    public fun remove_iter<K: copy + store + drop, V: store>(table: &mut IterableTable<K, V>, key: K): (V, Option<K>, Option<K>) {
+        let IterableValue{ val, prev, next } = table::remove(&mut table.inner, key);
+        if (option::is_some(&prev)) {
+            let prev_key = *option::borrow(&prev);
+            table::borrow_mut(&mut table.inner, prev_key).next = next;
+        } else {
+            table.head = next;
+        };
+        if (option::is_some(&next)) {
+            let next_key = *option::borrow(&next);
+            table::borrow_mut(&mut table.inner, next_key).prev = prev;
+        } else {
+            table.tail = prev;
+        };
+        (val, prev, next)
+    }
+
+    #[test_only]
+    /// Testing only: allows to drop a table even if it is not empty.
+    public fun drop_unchecked<K: copy + store + drop, V: store>(table: IterableTable<K, V>) {
+        let IterableTable{ inner, head: _, tail: _ } = table;
+        table::drop_unchecked(inner)
+    }
+}
@@ -32,6 +32,7 @@ use std::{
     collections::{btree_map::Entry, BTreeMap, BTreeSet, VecDeque},
     convert::TryInto,
     fmt::Display,
+    ops::Bound,
 };
 
 // ===========================================================================================
@@ -83,6 +84,32 @@ pub struct TableChange {
     pub entries: BTreeMap<Vec<u8>, Option<Vec<u8>>>,
 }
 
+impl TableChange {
+    /// Returns the changed entries whose serialized keys are in the range from `start`
+    /// (inclusive) to `end` (exclusive, unbounded if `None`), in the order of the serialized
+    /// keys. This allows to merge the changes with the keys listed by
+    /// `TableResolver::resolve_table_keys`.
+    pub fn entries_in_range<'a>(
+        &'a self,
+        start: &[u8],
+        end: Option<&[u8]>,
+    ) -> impl Iterator<Item = (&'a [u8], Option<&'a [u8]>)> {
+        let end = match end {
+            Some(end) => Bound::Excluded(end.max(start)),
+            None => Bound::Unbounded,
+        };
+        self.entries
+            .range::<[u8], _>((Bound::Included(start), end))
+            .map(|(key, val)| (key.as_slice(), val.as_deref()))
+    }
+}
+
+/// The table entries fetched from the resolver, with the values observed.
+#[derive(Default)]
+pub struct TableReadSet {
+    pub reads: BTreeMap<TableHandle, BTreeMap<Vec<u8>, Option<Vec<u8>>>>,
+}
+
 /// A table resolver which needs to be provided by the environment. This allows to lookup
 /// data in remote storage, as well as retrieve cost of table operations.
 pub trait TableResolver {
@@ -98,6 +125,22 @@ pub trait TableResolver {
         key_size: usize,
         val_size: usize,
     ) -> InternalGasUnits<GasCarrier>;
+
+    /// Lists the serialized keys of the entries of a table, in their order, starting at
+    /// `start` (inclusive) and ending before `end` (unbounded if `None`). At most `limit` keys
+    /// are returned. This is used by off-chain tooling to enumerate tables; resolvers which
+    /// cannot list keys do not need to implement it.
+    fn resolve_table_keys(
+        &self,
+        _handle: &TableHandle,
+        _start: &[u8],
+        _end: Option<&[u8]>,
+        _limit: usize,
+    ) -> Result<Vec<Vec<u8>>, anyhow::Error> {
+        Err(anyhow::anyhow!(
+            "listing table keys is not supported by this resolver"
+        ))
+    }
 }
 
 /// A table operation, for supporting cost calculation.
@@ -140,6 +183,7 @@ struct TableData {
     new_tables: BTreeMap<TableHandle, TableInfo>,
     removed_tables: BTreeSet<TableHandle>,
     tables: BTreeMap<TableHandle, Table>,
+    record_reads: bool,
 }
 
 /// A structure representing a single table.
@@ -148,6 +192,7 @@ struct Table {
     key_layout: MoveTypeLayout,
     value_layout: MoveTypeLayout,
     content: BTreeMap<Vec<u8>, GlobalValue>,
+    reads: Option<BTreeMap<Vec<u8>, Option<Vec<u8>>>>,
 }
 
 /// The field index of the `handle` field in the `Table` Move struct.
@@ -167,21 +212,46 @@ impl<'a> NativeTableContext<'a> {
         }
     }
 
+    /// Record the table entries read from the resolver from now on, so that
+    /// `into_change_set_and_read_set` can return them.
+    pub fn record_reads(&mut self) {
+        let table_data = self.table_data.get_mut();
+        table_data.record_reads = true;
+        for table in table_data.tables.values_mut() {
+            table.reads.get_or_insert_with(BTreeMap::new);
+        }
+    }
+
     /// Computes the change set from a NativeTableContext.
     pub fn into_change_set(self) -> PartialVMResult<TableChangeSet> {
+        self.into_change_set_and_read_set()
+            .map(|(change_set, _)| change_set)
+    }
+
+    /// Computes the change set from a NativeTableContext, together with the entries read
+    /// since `record_reads` was called.
+    pub fn into_change_set_and_read_set(self) -> PartialVMResult<(TableChangeSet, TableReadSet)> {
         let NativeTableContext { table_data, .. } = self;
         let TableData {
             new_tables,
             removed_tables,
             tables,
+            ..
         } = table_data.into_inner();
         let mut changes = BTreeMap::new();
+        let mut read_set = TableReadSet::default();
         for (handle, table) in tables {
             let Table {
                 value_layout,
                 content,
+                reads,
                 ..
             } = table;
+            if let Some(reads) = reads {
+                if !reads.is_empty() {
+                    read_set.reads.insert(handle, reads);
+                }
+            }
             let mut entries = BTreeMap::new();
             for (key, gv) in content {
                 match gv.into_effect()? {
@@ -199,11 +269,12 @@ impl<'a> NativeTableContext<'a> {
                 changes.insert(handle, TableChange { entries });
             }
         }
-        Ok(TableChangeSet {
+        let change_set = TableChangeSet {
             new_tables,
             removed_tables,
             changes,
-        })
+        };
+        Ok((change_set, read_set))
     }
 }
 
@@ -225,6 +296,11 @@ impl TableData {
                 key_layout,
                 value_layout,
                 content: Default::default(),
+                reads: if self.record_reads {
+                    Some(BTreeMap::new())
+                } else {
+                    None
+                },
             };
             e.insert(table);
         }
@@ -310,12 +386,16 @@ impl Table {
         let mut val_size = 0;
         if !self.content.contains_key(&key_bytes) {
             // Try to retrieve a value from the remote resolver.
-            let gv = match context
+            let val_bytes_opt = context
                 .resolver
                 .resolve_table_entry(&self.handle, &key_bytes)
                 .map_err(|err| {
                     partial_extension_error(format!("remote table resolver failure: {}", err))
-                })? {
+                })?;
+            if let Some(reads) = &mut self.reads {
+                reads.insert(key_bytes.clone(), val_bytes_opt.clone());
+            }
+            let gv = match val_bytes_opt {
                 Some(val_bytes) => {
                     val_size = val_bytes.len();
                     let val = deserialize(&self.value_layout, &val_bytes)?;
@@ -0,0 +1,133 @@
+#[test_only]
+module extensions::iterable_table_tests {
+    use std::option;
+    use std::vector;
+    use extensions::iterable_table as T;
+
+    struct S<K: copy + store + drop, V: store> has key {
+        t: T::IterableTable<K, V>
+    }
+
+    /// Collects the keys of the table, walking from the head to the tail.
+    fun keys(t: &T::IterableTable<u64, u64>): vector<u64> {
+        let keys = vector::empty();
+        let key = T::head_key(t);
+        while (option::is_some(&key)) {
+            let k = *option::borrow(&key);
+            vector::push_back(&mut keys, k);
+            let (_, _, next) = T::borrow_iter(t, k);
+            key = next;
+        };
+        keys
+    }
+
+    /// Collects the keys of the table, walking from the tail to the head.
+    fun keys_reversed(t: &T::IterableTable<u64, u64>): vector<u64> {
+        let keys = vector::empty();
+        let key = T::tail_key(t);
+        while (option::is_some(&key)) {
+            let k = *option::borrow(&key);
+            vector::push_back(&mut keys, k);
+            let (_, prev, _) = T::borrow_iter(t, k);
+            key = prev;
+        };
+        keys
+    }
+
+    #[test]
+    fun iterate_in_insertion_order() {
+        let t = T::new<u64, u64>();
+        T::add(&mut t, 5, 50);
+        T::add(&mut t, 1, 10);
+        T::add(&mut t, 3, 30);
+        assert!(T::length(&t) == 3, 1);
+        assert!(keys(&t) == vector[5, 1, 3], 2);
+        assert!(keys_reversed(&t) == vector[3, 1, 5], 3);
+
+        let sum = 0;
+        let key = T::head_key(&t);
+        while (option::is_some(&key)) {
+            let (val, _, next) = T::borrow_iter(&t, *option::borrow(&key));
+            sum = sum + *val;
+            key = next;
+        };
+        assert!(sum == 90, 4);
+        T::drop_unchecked(t)
+    }
+
+    #[test]
+    fun remove_relinks_entries() {
+        let t = T::new<u64, u64>();
+        T::add(&mut t, 1, 10);
+        T::add(&mut t, 2, 20);
+        T::add(&mut t, 3, 30);
+        T::add(&mut t, 4, 40);
+
+        let (val, prev, next) = T::remove_iter(&mut t, 2);
+        assert!(val == 20, 1);
+        assert!(prev == option::some(1), 2);
+        assert!(next == option::some(3), 3);
+        assert!(keys(&t) == vector[1, 3, 4], 4);
+
+        assert!(T::remove(&mut t, 1) == 10, 5);
+        assert!(T::remove(&mut t, 4) == 40, 6);
+        assert!(T::head_key(&t) == option::some(3), 7);
+        assert!(T::tail_key(&t) == option::some(3), 8);
+        assert!(keys_reversed(&t) == vector[3], 9);
+
+        T::add(&mut t, 2, 21);
+        assert!(keys(&t) == vector[3, 2], 10);
+        T::remove(&mut t, 3);
+        T::remove(&mut t, 2);
+        assert!(T::empty(&t), 11);
+        assert!(option::is_none(&T::head_key(&t)), 12);
+        assert!(option::is_none(&T::tail_key(&t)), 13);
+        T::destroy_empty(t)
+    }
+
+    #[test]
+    fun update_while_iterating() {
+        let t = T::new<u64, u64>();
+        T::add(&mut t, 1, 10);
+        T::add(&mut t, 2, 20);
+        let key = T::head_key(&t);
+        while (option::is_some(&key)) {
+            let (val, _, next) = T::borrow_iter_mut(&mut t, *option::borrow(&key));
+            *val = *val + 1;
+            key = next;
+        };
+        assert!(*T::borrow(&t, 1) == 11, 1);
+        assert!(*T::borrow(&t, 2) == 21, 2);
+        *T::borrow_mut(&mut t, 2) = 0;
+        assert!(*T::borrow(&t, 2) == 0, 3);
+        T::drop_unchecked(t)
+    }
+
+    #[test(s = @0x42)]
+    fun iterable_table_in_resource(s: signer) acquires S {
+        let t = T::new<u64, u64>();
+        T::add(&mut t, 7, 70);
+        move_to(&s, S { t });
+        let t = &mut borrow_global_mut<S<u64, u64>>(@0x42).t;
+        T::add(t, 8, 80);
+        assert!(T::contains(t, 7), 1);
+        assert!(keys(t) == vector[7, 8], 2);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 26113)]
+    fun destroy_non_empty_fails() {
+        let t = T::new<u64, u64>();
+        T::add(&mut t, 1, 10);
+        T::destroy_empty(t)
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 25607)]
+    fun add_twice_fails() {
+        let t = T::new<u64, u64>();
+        T::add(&mut t, 1, 10);
+        T::add(&mut t, 1, 11);
+        T::drop_unchecked(t)
+    }
+}
@@ -0,0 +1,212 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use move_core_types::{
+    effects::ChangeSet,
+    gas_schedule::{GasAlgebra, GasCarrier, InternalGasUnits},
+    language_storage::TypeTag,
+};
+use move_table_extension::{
+    TableChange, TableChangeSet, TableHandle, TableInfo, TableOperation, TableResolver,
+};
+use move_vm_test_utils::InMemoryStorage;
+use std::collections::{BTreeMap, BTreeSet};
+
+const HANDLE: TableHandle = TableHandle(1);
+
+fn key(k: u8) -> Vec<u8> {
+    vec![k]
+}
+
+fn keys(ks: &[u8]) -> Vec<Vec<u8>> {
+    ks.iter().copied().map(key).collect()
+}
+
+fn change(adds: &[u8], removes: &[u8]) -> TableChange {
+    let mut entries = BTreeMap::new();
+    for k in adds {
+        entries.insert(key(*k), Some(vec![*k]));
+    }
+    for k in removes {
+        entries.insert(key(*k), None);
+    }
+    TableChange { entries }
+}
+
+fn apply(storage: &mut InMemoryStorage, new_table: bool, change: TableChange) {
+    let mut table_changes = TableChangeSet::default();
+    if new_table {
+        table_changes
+            .new_tables
+            .insert(HANDLE, TableInfo::new(TypeTag::U8, TypeTag::U8));
+    }
+    table_changes.changes.insert(HANDLE, change);
+    storage
+        .apply_extended(ChangeSet::new(), table_changes)
+        .unwrap();
+}
+
+/// A storage holding a table with the keys 1, 3, 5, 7 and 9.
+fn storage() -> InMemoryStorage {
+    let mut storage = InMemoryStorage::new();
+    apply(&mut storage, true, change(&[9, 3, 7, 1, 5], &[]));
+    storage
+}
+
+/// Lists the keys of the table as they would be after applying the pending `change`, by merging
+/// the changed entries into the keys listed by the resolver.
+fn merged_keys(
+    resolver: &impl TableResolver,
+    change: &TableChange,
+    start: &[u8],
+    end: Option<&[u8]>,
+    limit: usize,
+) -> Vec<Vec<u8>> {
+    // Entries removed by the change may hide keys of the resolver, so fetch enough of them.
+    let removed = change
+        .entries_in_range(start, end)
+        .filter(|(_, val)| val.is_none())
+        .count();
+    let mut merged = resolver
+        .resolve_table_keys(&HANDLE, start, end, limit.saturating_add(removed))
+        .unwrap()
+        .into_iter()
+        .collect::<BTreeSet<_>>();
+    for (key, val) in change.entries_in_range(start, end) {
+        match val {
+            Some(_) => merged.insert(key.to_vec()),
+            None => merged.remove(key),
+        };
+    }
+    merged.into_iter().take(limit).collect()
+}
+
+#[test]
+fn entries_in_range() {
+    let change = change(&[4, 2, 8], &[6, 0]);
+    let range = |start: &[u8], end: Option<&[u8]>| {
+        change
+            .entries_in_range(start, end)
+            .map(|(key, val)| (key.to_vec(), val.map(|v| v.to_vec())))
+            .collect::<Vec<_>>()
+    };
+
+    // Entries are ordered by their serialized keys, removals included.
+    assert_eq!(
+        range(&[], None),
+        vec![
+            (key(0), None),
+            (key(2), Some(vec![2])),
+            (key(4), Some(vec![4])),
+            (key(6), None),
+            (key(8), Some(vec![8])),
+        ]
+    );
+    // The start is inclusive and the end exclusive.
+    assert_eq!(
+        range(&key(2), Some(&key(6))),
+        vec![(key(2), Some(vec![2])), (key(4), Some(vec![4]))]
+    );
+    assert_eq!(range(&key(3), Some(&key(4))), vec![]);
+    assert_eq!(
+        range(&key(6), None),
+        vec![(key(6), None), (key(8), Some(vec![8]))]
+    );
+    // An end before the start is an empty range.
+    assert_eq!(range(&key(6), Some(&key(2))), vec![]);
+    assert_eq!(range(&key(6), Some(&key(6))), vec![]);
+}
+
+#[test]
+fn resolve_table_keys_in_memory() {
+    let storage = storage();
+    let list = |start: &[u8], end: Option<&[u8]>, limit: usize| {
+        storage
+            .resolve_table_keys(&HANDLE, start, end, limit)
+            .unwrap()
+    };
+
+    // Keys are ordered regardless of the order they were inserted in.
+    assert_eq!(list(&[], None, usize::MAX), keys(&[1, 3, 5, 7, 9]));
+    // The start is inclusive and the end exclusive.
+    assert_eq!(list(&key(3), Some(&key(7)), usize::MAX), keys(&[3, 5]));
+    assert_eq!(list(&key(4), Some(&key(5)), usize::MAX), keys(&[]));
+    assert_eq!(list(&key(9), Some(&key(3)), usize::MAX), keys(&[]));
+    // The limit applies after the start.
+    assert_eq!(list(&[], None, 2), keys(&[1, 3]));
+    assert_eq!(list(&key(4), None, 2), keys(&[5, 7]));
+    assert_eq!(list(&key(4), Some(&key(7)), 2), keys(&[5]));
+    assert_eq!(list(&[], None, 0), keys(&[]));
+    // Unknown tables have no keys.
+    assert_eq!(
+        storage
+            .resolve_table_keys(&TableHandle(2), &[], None, usize::MAX)
+            .unwrap(),
+        keys(&[])
+    );
+}
+
+#[test]
+fn resolve_table_keys_default() {
+    struct NoKeys;
+
+    impl TableResolver for NoKeys {
+        fn resolve_table_entry(
+            &self,
+            _handle: &TableHandle,
+            _key: &[u8],
+        ) -> Result<Option<Vec<u8>>, anyhow::Error> {
+            Ok(None)
+        }
+
+        fn operation_cost(
+            &self,
+            _op: TableOperation,
+            _key_size: usize,
+            _val_size: usize,
+        ) -> InternalGasUnits<GasCarrier> {
+            InternalGasUnits::new(1)
+        }
+    }
+
+    assert!(NoKeys
+        .resolve_table_keys(&HANDLE, &[], None, usize::MAX)
+        .is_err());
+}
+
+#[test]
+fn pending_changes_merged_over_storage() {
+    // Adds 2 and 4, overwrites 5, removes 3 and 7 and removes the absent 8.
+    let pending = || change(&[2, 4, 5], &[3, 7, 8]);
+    let base = storage();
+    let mut applied = storage();
+    apply(&mut applied, false, pending());
+    assert_eq!(
+        applied
+            .resolve_table_keys(&HANDLE, &[], None, usize::MAX)
+            .unwrap(),
+        keys(&[1, 2, 4, 5, 9])
+    );
+
+    let ranges: &[(&[u8], Option<&[u8]>)] = &[
+        (&[], None),
+        (&[2], Some(&[5])),
+        (&[3], Some(&[9])),
+        (&[5], None),
+        (&[8], Some(&[3])),
+    ];
+    for (start, end) in ranges {
+        for limit in 0..=6 {
+            assert_eq!(
+                merged_keys(&base, &pending(), start, *end, limit),
+                applied
+                    .resolve_table_keys(&HANDLE, start, *end, limit)
+                    .unwrap(),
+                "start {:?}, end {:?}, limit {}",
+                start,
+                end,
+                limit
+            );
+        }
+    }
+}
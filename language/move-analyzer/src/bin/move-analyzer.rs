@@ -20,6 +20,7 @@ use std::{
 use move_analyzer::{
     completion::on_completion_request,
     context::Context,
+    formatting::on_formatting_request,
     symbols,
     vfs::{on_text_document_sync_notification, VirtualFileSystem},
 };
@@ -105,6 +106,8 @@ fn main() {
             symbols::DEFS_AND_REFS_SUPPORT,
         )),
         references_provider: Some(OneOf::Left(symbols::DEFS_AND_REFS_SUPPORT)),
+        // The server formats whole files, but not ranges within them.
+        document_formatting_provider: Some(OneOf::Left(true)),
         ..Default::default()
     })
     .expect("could not serialize server capabilities");
@@ -234,6 +237,7 @@ fn on_request(context: &Context, request: &Request) {
         lsp_types::request::HoverRequest::METHOD => {
             symbols::on_hover_request(context, request, &context.symbols.lock().unwrap());
         }
+        lsp_types::request::Formatting::METHOD => on_formatting_request(context, request),
         _ => eprintln!("handle request '{}' from client", request.method),
     }
 }
@@ -0,0 +1,61 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::context::Context;
+use lsp_server::Request;
+use lsp_types::{DocumentFormattingParams, Position, Range, TextEdit};
+use move_command_line_common::files::FileHash;
+use move_compiler::formatter::{format_string, FormatterConfig};
+
+/// Returns the position just past the end of `text`.
+fn end_position(text: &str) -> Position {
+    let last_line = text.rsplit('\n').next().unwrap_or("");
+    Position {
+        line: text.matches('\n').count() as u32,
+        character: last_line.encode_utf16().count() as u32,
+    }
+}
+
+/// Sends the edits formatting the buffer the given request is about. The whole buffer is replaced
+/// by its formatted version in a single edit. No edits are sent if the buffer is already formatted,
+/// and no result at all if it cannot be formatted, e.g. because it does not parse.
+pub fn on_formatting_request(context: &Context, request: &Request) {
+    eprintln!("handling formatting request");
+    let parameters = serde_json::from_value::<DocumentFormattingParams>(request.params.clone())
+        .expect("could not deserialize formatting request");
+
+    let path = parameters.text_document.uri.to_file_path().unwrap();
+    let config = FormatterConfig {
+        indent_width: parameters.options.tab_size as usize,
+    };
+    let edits = match context.files.get(&path) {
+        Some(buffer) => match format_string(FileHash::new(buffer), buffer, &config) {
+            Ok(formatted) if formatted == buffer => Some(vec![]),
+            Ok(formatted) => Some(vec![TextEdit {
+                range: Range {
+                    start: Position::new(0, 0),
+                    end: end_position(buffer),
+                },
+                new_text: formatted,
+            }]),
+            Err(_) => None,
+        },
+        None => {
+            eprintln!(
+                "Could not read '{:?}' when handling formatting request",
+                path
+            );
+            None
+        }
+    };
+
+    let result = serde_json::to_value(edits).expect("could not serialize formatting response");
+    let response = lsp_server::Response::new_ok(request.id.clone(), result);
+    if let Err(err) = context
+        .connection
+        .sender
+        .send(lsp_server::Message::Response(response))
+    {
+        eprintln!("could not send formatting response: {:?}", err);
+    }
+}
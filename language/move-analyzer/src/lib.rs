@@ -7,6 +7,7 @@ extern crate move_ir_types;
 pub mod completion;
 pub mod context;
 pub mod diagnostics;
+pub mod formatting;
 pub mod symbols;
 pub mod utils;
 pub mod vfs;
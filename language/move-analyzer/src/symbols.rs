@@ -79,7 +79,7 @@ use move_compiler::{
     shared::Identifier,
     typing::ast::{
         BuiltinFunction_, Exp, ExpListItem, Function, FunctionBody_, LValue, LValueList, LValue_,
-        ModuleCall, ModuleDefinition, SequenceItem, SequenceItem_, UnannotatedExp_,
+        MatchPattern_, ModuleCall, ModuleDefinition, SequenceItem, SequenceItem_, UnannotatedExp_,
     },
     PASS_TYPING,
 };
@@ -298,6 +298,11 @@ fn type_to_ide_string(sp!(_, t): &Type) -> String {
                 )
             }
         },
+        Type_::Fun(args, result) => format!(
+            "|{}|{}",
+            type_list_to_ide_string(args),
+            type_to_ide_string(result)
+        ),
         Type_::Anything => "_".to_string(),
         Type_::Var(_) => "invalid type (var)".to_string(),
         Type_::UnresolvedError => "invalid type (unresolved)".to_string(),
@@ -902,6 +907,13 @@ impl Symbolicator {
                 );
             }
         }
+        if let StructFields::Variants(variants) = &struct_def.fields {
+            for (_, _, (_, (_, fields))) in variants {
+                for (_, _, (_, t)) in fields {
+                    self.add_type_id_use_def(t, references, use_defs);
+                }
+            }
+        }
     }
 
     /// Get symbols for function a definition
@@ -1169,6 +1181,17 @@ impl Symbolicator {
                 self.add_type_id_use_def(t, references, use_defs);
                 self.exp_symbols(exp, scope, references, use_defs);
             }
+            E::VarCall(v, args) => {
+                // the called local is a lambda parameter, whose type is rebuilt from the call
+                let arg_types = match &args.ty.value {
+                    Type_::Unit => vec![],
+                    Type_::Apply(_, sp!(_, TypeName_::Multiple(_)), tys) => tys.clone(),
+                    _ => vec![args.ty.clone()],
+                };
+                let fun_type = sp(v.loc(), Type_::Fun(arg_types, Box::new(exp.ty.clone())));
+                self.add_local_use_def(&v.value(), &v.loc(), references, scope, use_defs, fun_type);
+                self.exp_symbols(args, scope, references, use_defs);
+            }
             E::IfElse(cond, t, f) => {
                 self.exp_symbols(cond, scope, references, use_defs);
                 self.exp_symbols(t, scope, references, use_defs);
@@ -1188,6 +1211,12 @@ impl Symbolicator {
                     self.seq_item_symbols(&mut new_scope, seq_item, references, use_defs);
                 }
             }
+            E::Lambda(lvalues, _, body) => {
+                // a lambda is a new var scope
+                let mut new_scope = scope.clone();
+                self.lvalue_list_symbols(true, lvalues, &mut new_scope, references, use_defs);
+                self.exp_symbols(body, &mut new_scope, references, use_defs);
+            }
             E::Assign(lvalues, opt_types, e) => {
                 self.lvalue_list_symbols(false, lvalues, scope, references, use_defs);
                 for opt_t in opt_types {
@@ -1221,6 +1250,30 @@ impl Symbolicator {
             E::Pack(ident, name, tparams, fields) => {
                 self.pack_symbols(ident, name, tparams, fields, scope, references, use_defs);
             }
+            E::PackVariant(ident, name, _, tparams, fields) => {
+                self.pack_symbols(ident, name, tparams, fields, scope, references, use_defs);
+            }
+            E::Match(subject, arms) => {
+                self.exp_symbols(subject, scope, references, use_defs);
+                for sp!(_, (pattern, arm)) in arms {
+                    // an arm is a new var scope
+                    let mut new_scope = scope.clone();
+                    if let MatchPattern_::Variant(ident, name, _, tparams, fields) = &pattern.value
+                    {
+                        self.unpack_symbols(
+                            true,
+                            ident,
+                            name,
+                            tparams,
+                            fields,
+                            &mut new_scope,
+                            references,
+                            use_defs,
+                        );
+                    }
+                    self.exp_symbols(arm, &mut new_scope, references, use_defs);
+                }
+            }
             E::ExpList(list_items) => {
                 for item in list_items {
                     let exp = match item {
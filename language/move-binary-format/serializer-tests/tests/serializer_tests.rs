@@ -17,19 +17,28 @@ proptest! {
     }
 }
 
-proptest! {
-    // Generating arbitrary compiled modules is really slow, possibly because of
-    // https://github.com/AltSysrq/proptest/issues/143.
-    #![proptest_config(ProptestConfig::with_cases(16))]
-
-    /// Make sure that garbage inputs don't crash the serializer and deserializer.
-    #[test]
-    fn garbage_inputs(module in any_with::<CompiledModule>(16)) {
-        let mut serialized = Vec::with_capacity(65536);
-        module.serialize(&mut serialized).expect("serialization should work");
+/// Make sure that garbage inputs don't crash the serializer and deserializer.
+#[test]
+fn garbage_inputs() {
+    // Generating arbitrary compiled modules goes through the strategies of all bytecodes, which
+    // takes more than the default stack of a test thread.
+    std::thread::Builder::new()
+        .stack_size(8 * 1024 * 1024)
+        .spawn(|| {
+            // Generating arbitrary compiled modules is really slow, possibly because of
+            // https://github.com/AltSysrq/proptest/issues/143.
+            proptest!(ProptestConfig::with_cases(16), |(
+                module in any_with::<CompiledModule>(16)
+            )| {
+                let mut serialized = Vec::with_capacity(65536);
+                module.serialize(&mut serialized).expect("serialization should work");
 
-        let deserialized_module = CompiledModule::deserialize_no_check_bounds(&serialized)
-            .expect("deserialization should work");
-        prop_assert_eq!(module, deserialized_module);
-    }
+                let deserialized_module = CompiledModule::deserialize_no_check_bounds(&serialized)
+                    .expect("deserialization should work");
+                prop_assert_eq!(module, deserialized_module);
+            });
+        })
+        .unwrap()
+        .join()
+        .unwrap()
 }
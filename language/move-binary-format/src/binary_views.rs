@@ -256,7 +256,7 @@ impl<'a> BinaryIndexedView<'a> {
         use SignatureToken::*;
 
         match ty {
-            Bool | U8 | U64 | U128 | Address => Ok(AbilitySet::PRIMITIVES),
+            Bool | U8 | U16 | U32 | U64 | U128 | U256 | Address => Ok(AbilitySet::PRIMITIVES),
 
             Reference(_) | MutableReference(_) => Ok(AbilitySet::REFERENCES),
             Signer => Ok(AbilitySet::SIGNER),
@@ -11,7 +11,8 @@ use crate::{
         AbilitySet, Bytecode, CodeOffset, CodeUnit, CompiledModule, CompiledScript, Constant,
         FieldHandle, FieldInstantiation, FunctionDefinition, FunctionDefinitionIndex,
         FunctionHandle, FunctionInstantiation, ModuleHandle, Signature, SignatureToken,
-        StructDefInstantiation, StructDefinition, StructFieldInformation, StructHandle, TableIndex,
+        StructDefInstantiation, StructDefInstantiationIndex, StructDefinition,
+        StructDefinitionIndex, StructFieldInformation, StructHandle, TableIndex, VariantIndex,
     },
     internals::ModuleIndex,
     IndexKind,
@@ -233,7 +234,7 @@ impl<'a> BoundsChecker<'a> {
             .and_then(|d| d.get(field_handle.owner.into_index()))
         {
             let fields_count = match &struct_def.field_information {
-                StructFieldInformation::Native => 0,
+                StructFieldInformation::Native | StructFieldInformation::DeclaredVariants(_) => 0,
                 StructFieldInformation::Declared(fields) => fields.len(),
             };
             if field_handle.field as usize >= fields_count {
@@ -288,20 +289,23 @@ impl<'a> BoundsChecker<'a> {
 
     fn check_struct_def(&self, struct_def: &StructDefinition) -> PartialVMResult<()> {
         check_bounds_impl(self.view.struct_handles(), struct_def.struct_handle)?;
-        // check signature (type) and type parameter for the field type
-        if let StructFieldInformation::Declared(fields) = &struct_def.field_information {
-            let type_param_count = self
-                .view
-                .struct_handles()
-                .get(struct_def.struct_handle.into_index())
-                .map_or(0, |sh| sh.type_parameters.len());
-            // field signatures are inlined
-            for field in fields {
-                check_bounds_impl(self.view.identifiers(), field.name)?;
-                self.check_type(&field.signature.0)?;
-                self.check_type_parameter(&field.signature.0, type_param_count)?;
+        if let StructFieldInformation::DeclaredVariants(variants) = &struct_def.field_information {
+            for variant in variants {
+                check_bounds_impl(self.view.identifiers(), variant.name)?;
             }
         }
+        // check signature (type) and type parameter for the field type
+        let type_param_count = self
+            .view
+            .struct_handles()
+            .get(struct_def.struct_handle.into_index())
+            .map_or(0, |sh| sh.type_parameters.len());
+        // field signatures are inlined
+        for field in struct_def.all_fields() {
+            check_bounds_impl(self.view.identifiers(), field.name)?;
+            self.check_type(&field.signature.0)?;
+            self.check_type_parameter(&field.signature.0, type_param_count)?;
+        }
         Ok(())
     }
 
@@ -438,27 +442,35 @@ impl<'a> BoundsChecker<'a> {
                 | ImmBorrowGlobalGeneric(idx)
                 | MutBorrowGlobalGeneric(idx)
                 | MoveFromGeneric(idx)
-                | MoveToGeneric(idx) => {
+                | MoveToGeneric(idx) => self.check_struct_instantiation_in_code(
+                    *idx,
+                    type_param_count,
+                    bytecode_offset,
+                )?,
+                PackVariant(idx, variant)
+                | UnpackVariant(idx, variant)
+                | TestVariant(idx, variant) => {
                     self.check_code_unit_bounds_impl_opt(
-                        &self.view.struct_instantiations(),
+                        &self.view.struct_defs(),
+                        *idx,
+                        bytecode_offset,
+                    )?;
+                    self.check_variant_index(*idx, *variant, bytecode_offset)?
+                }
+                PackVariantGeneric(idx, variant)
+                | UnpackVariantGeneric(idx, variant)
+                | TestVariantGeneric(idx, variant) => {
+                    self.check_struct_instantiation_in_code(
                         *idx,
+                        type_param_count,
                         bytecode_offset,
                     )?;
-                    // check type parameters in type operations are bound to the function type parameters
                     if let Some(struct_inst) = self
                         .view
                         .struct_instantiations()
                         .and_then(|s| s.get(idx.into_index()))
                     {
-                        if let Some(sig) = self
-                            .view
-                            .signatures()
-                            .get(struct_inst.type_parameters.into_index())
-                        {
-                            for ty in &sig.0 {
-                                self.check_type_parameter(ty, type_param_count)?
-                            }
-                        }
+                        self.check_variant_index(struct_inst.def, *variant, bytecode_offset)?
                     }
                 }
                 // Instructions that refer to this code block.
@@ -512,10 +524,11 @@ impl<'a> BoundsChecker<'a> {
 
                 // List out the other options explicitly so there's a compile error if a new
                 // bytecode gets added.
-                FreezeRef | Pop | Ret | LdU8(_) | LdU64(_) | LdU128(_) | CastU8 | CastU64
-                | CastU128 | LdTrue | LdFalse | ReadRef | WriteRef | Add | Sub | Mul | Mod
-                | Div | BitOr | BitAnd | Xor | Shl | Shr | Or | And | Not | Eq | Neq | Lt | Gt
-                | Le | Ge | Abort | Nop => (),
+                FreezeRef | Pop | Ret | LdU8(_) | LdU16(_) | LdU32(_) | LdU64(_) | LdU128(_)
+                | LdU256(_) | CastU8 | CastU16 | CastU32 | CastU64 | CastU128 | CastU256
+                | LdTrue | LdFalse | ReadRef | WriteRef | Add | Sub | Mul | Mod | Div | BitOr
+                | BitAnd | Xor | Shl | Shr | Or | And | Not | Eq | Neq | Lt | Gt | Le | Ge
+                | Abort | Nop => (),
             }
         }
         Ok(())
@@ -526,8 +539,8 @@ impl<'a> BoundsChecker<'a> {
 
         for ty in ty.preorder_traversal() {
             match ty {
-                Bool | U8 | U64 | U128 | Address | Signer | TypeParameter(_) | Reference(_)
-                | MutableReference(_) | Vector(_) => (),
+                Bool | U8 | U16 | U32 | U64 | U128 | U256 | Address | Signer | TypeParameter(_)
+                | Reference(_) | MutableReference(_) | Vector(_) => (),
                 Struct(idx) => {
                     check_bounds_impl(self.view.struct_handles(), *idx)?;
                     if let Some(sh) = self.view.struct_handles().get(idx.into_index()) {
@@ -584,8 +597,11 @@ impl<'a> BoundsChecker<'a> {
 
                 Bool
                 | U8
+                | U16
+                | U32
                 | U64
                 | U128
+                | U256
                 | Address
                 | Signer
                 | Struct(_)
@@ -598,6 +614,66 @@ impl<'a> BoundsChecker<'a> {
         Ok(())
     }
 
+    fn check_struct_instantiation_in_code(
+        &self,
+        idx: StructDefInstantiationIndex,
+        type_param_count: usize,
+        bytecode_offset: usize,
+    ) -> PartialVMResult<()> {
+        self.check_code_unit_bounds_impl_opt(
+            &self.view.struct_instantiations(),
+            idx,
+            bytecode_offset,
+        )?;
+        // check type parameters in type operations are bound to the function type parameters
+        if let Some(struct_inst) = self
+            .view
+            .struct_instantiations()
+            .and_then(|s| s.get(idx.into_index()))
+        {
+            if let Some(sig) = self
+                .view
+                .signatures()
+                .get(struct_inst.type_parameters.into_index())
+            {
+                for ty in &sig.0 {
+                    self.check_type_parameter(ty, type_param_count)?
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that `variant` is a variant of the enum defined at `idx`. Other than an enum, a
+    /// struct has no variants.
+    fn check_variant_index(
+        &self,
+        idx: StructDefinitionIndex,
+        variant: VariantIndex,
+        bytecode_offset: usize,
+    ) -> PartialVMResult<()> {
+        if let Some(struct_def) = self
+            .view
+            .struct_defs()
+            .and_then(|d| d.get(idx.into_index()))
+        {
+            let variant_count = match &struct_def.field_information {
+                StructFieldInformation::Native | StructFieldInformation::Declared(_) => 0,
+                StructFieldInformation::DeclaredVariants(variants) => variants.len(),
+            };
+            if variant as usize >= variant_count {
+                return Err(self.offset_out_of_bounds(
+                    StatusCode::INDEX_OUT_OF_BOUNDS,
+                    IndexKind::VariantDefinition,
+                    variant as usize,
+                    variant_count,
+                    bytecode_offset as CodeOffset,
+                ));
+            }
+        }
+        Ok(())
+    }
+
     fn check_code_unit_bounds_impl_opt<T, I>(
         &self,
         pool: &Option<&[T]>,
@@ -84,6 +84,12 @@ impl Compatibility {
                 //     will be the same.
                 struct_layout = false
             }
+            if !new_struct.variants.starts_with(&old_struct.variants) {
+                // Variants of an enum changed. Adding variants after the existing ones is
+                // compatible, as previously published values are still decoded as the variant
+                // they were created with.
+                struct_layout = false
+            }
         }
 
         // The modules are considered as compatible function-wise when all the conditions are met:
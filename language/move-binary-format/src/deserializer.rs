@@ -3,7 +3,7 @@
 
 use crate::{check_bounds::BoundsChecker, errors::*, file_format::*, file_format_common::*};
 use move_core_types::{
-    account_address::AccountAddress, identifier::Identifier, metadata::Metadata,
+    account_address::AccountAddress, identifier::Identifier, metadata::Metadata, u256::U256,
     vm_status::StatusCode,
 };
 use std::{collections::HashSet, convert::TryInto, io::Read};
@@ -56,6 +56,22 @@ impl Table {
     }
 }
 
+fn read_u16_internal(cursor: &mut VersionedCursor) -> BinaryLoaderResult<u16> {
+    let mut u16_bytes = [0; 2];
+    cursor
+        .read_exact(&mut u16_bytes)
+        .map_err(|_| PartialVMError::new(StatusCode::BAD_U16))?;
+    Ok(u16::from_le_bytes(u16_bytes))
+}
+
+fn read_u32_internal(cursor: &mut VersionedCursor) -> BinaryLoaderResult<u32> {
+    let mut u32_bytes = [0; 4];
+    cursor
+        .read_exact(&mut u32_bytes)
+        .map_err(|_| PartialVMError::new(StatusCode::BAD_U32))?;
+    Ok(u32::from_le_bytes(u32_bytes))
+}
+
 fn read_u64_internal(cursor: &mut VersionedCursor) -> BinaryLoaderResult<u64> {
     let mut u64_bytes = [0; 8];
     cursor
@@ -72,6 +88,14 @@ fn read_u128_internal(cursor: &mut VersionedCursor) -> BinaryLoaderResult<u128>
     Ok(u128::from_le_bytes(u128_bytes))
 }
 
+fn read_u256_internal(cursor: &mut VersionedCursor) -> BinaryLoaderResult<U256> {
+    let mut u256_bytes = [0; 32];
+    cursor
+        .read_exact(&mut u256_bytes)
+        .map_err(|_| PartialVMError::new(StatusCode::BAD_U256))?;
+    Ok(U256::from_le_bytes(&u256_bytes))
+}
+
 //
 // Helpers to read all uleb128 encoded integers.
 //
@@ -238,6 +262,14 @@ fn load_field_offset(cursor: &mut VersionedCursor) -> BinaryLoaderResult<u16> {
     read_uleb_internal(cursor, FIELD_OFFSET_MAX)
 }
 
+fn load_variant_count(cursor: &mut VersionedCursor) -> BinaryLoaderResult<u64> {
+    read_uleb_internal(cursor, VARIANT_COUNT_MAX)
+}
+
+fn load_variant_index(cursor: &mut VersionedCursor) -> BinaryLoaderResult<VariantIndex> {
+    read_uleb_internal(cursor, VARIANT_INDEX_MAX)
+}
+
 fn load_table_count(cursor: &mut VersionedCursor) -> BinaryLoaderResult<u8> {
     read_uleb_internal(cursor, TABLE_COUNT_MAX)
 }
@@ -970,11 +1002,26 @@ fn load_signature_token(cursor: &mut VersionedCursor) -> BinaryLoaderResult<Sign
 
     let mut read_next = || {
         if let Ok(byte) = cursor.read_u8() {
-            Ok(match S::from_u8(byte)? {
+            let ser_type = S::from_u8(byte)?;
+            match ser_type {
+                S::U16 | S::U32 | S::U256 if cursor.version() < VERSION_6 => {
+                    return Err(
+                        PartialVMError::new(StatusCode::MALFORMED).with_message(format!(
+                            "u16, u32, u256 integers not supported in bytecode version {}",
+                            cursor.version()
+                        )),
+                    );
+                }
+                _ => (),
+            };
+            Ok(match ser_type {
                 S::BOOL => T::Saturated(SignatureToken::Bool),
                 S::U8 => T::Saturated(SignatureToken::U8),
+                S::U16 => T::Saturated(SignatureToken::U16),
+                S::U32 => T::Saturated(SignatureToken::U32),
                 S::U64 => T::Saturated(SignatureToken::U64),
                 S::U128 => T::Saturated(SignatureToken::U128),
+                S::U256 => T::Saturated(SignatureToken::U256),
                 S::ADDRESS => T::Saturated(SignatureToken::Address),
                 S::SIGNER => T::Saturated(SignatureToken::Signer),
                 S::VECTOR => T::Vector,
@@ -1160,6 +1207,18 @@ fn load_struct_defs(
                 let fields = load_field_defs(&mut cursor)?;
                 StructFieldInformation::Declared(fields)
             }
+            SerializedNativeStructFlag::DECLARED_VARIANTS => {
+                if cursor.version() < VERSION_7 {
+                    return Err(
+                        PartialVMError::new(StatusCode::MALFORMED).with_message(format!(
+                            "enums not supported in bytecode version {}",
+                            cursor.version()
+                        )),
+                    );
+                }
+                let variants = load_variant_defs(&mut cursor)?;
+                StructFieldInformation::DeclaredVariants(variants)
+            }
         };
         struct_defs.push(StructDefinition {
             struct_handle,
@@ -1178,6 +1237,17 @@ fn load_field_defs(cursor: &mut VersionedCursor) -> BinaryLoaderResult<Vec<Field
     Ok(fields)
 }
 
+fn load_variant_defs(cursor: &mut VersionedCursor) -> BinaryLoaderResult<Vec<VariantDefinition>> {
+    let mut variants = Vec::new();
+    let variant_count = load_variant_count(cursor)?;
+    for _ in 0..variant_count {
+        let name = load_identifier_index(cursor)?;
+        let fields = load_field_defs(cursor)?;
+        variants.push(VariantDefinition { name, fields });
+    }
+    Ok(variants)
+}
+
 fn load_field_def(cursor: &mut VersionedCursor) -> BinaryLoaderResult<FieldDefinition> {
     let name = load_identifier_index(cursor)?;
     let signature = load_signature_token(cursor)?;
@@ -1375,6 +1445,36 @@ fn load_code(cursor: &mut VersionedCursor, code: &mut Vec<Bytecode>) -> BinaryLo
                     );
                 }
             }
+            Opcodes::LD_U16
+            | Opcodes::LD_U32
+            | Opcodes::LD_U256
+            | Opcodes::CAST_U16
+            | Opcodes::CAST_U32
+            | Opcodes::CAST_U256 => {
+                if cursor.version() < VERSION_6 {
+                    return Err(
+                        PartialVMError::new(StatusCode::MALFORMED).with_message(format!(
+                            "u16, u32, u256 integers not supported in bytecode version {}",
+                            cursor.version()
+                        )),
+                    );
+                }
+            }
+            Opcodes::PACK_VARIANT
+            | Opcodes::PACK_VARIANT_GENERIC
+            | Opcodes::UNPACK_VARIANT
+            | Opcodes::UNPACK_VARIANT_GENERIC
+            | Opcodes::TEST_VARIANT
+            | Opcodes::TEST_VARIANT_GENERIC => {
+                if cursor.version() < VERSION_7 {
+                    return Err(
+                        PartialVMError::new(StatusCode::MALFORMED).with_message(format!(
+                            "enums not supported in bytecode version {}",
+                            cursor.version()
+                        )),
+                    );
+                }
+            }
             _ => {}
         };
         // conversion
@@ -1391,6 +1491,14 @@ fn load_code(cursor: &mut VersionedCursor, code: &mut Vec<Bytecode>) -> BinaryLo
                 })?;
                 Bytecode::LdU8(value)
             }
+            Opcodes::LD_U16 => {
+                let value = read_u16_internal(cursor)?;
+                Bytecode::LdU16(value)
+            }
+            Opcodes::LD_U32 => {
+                let value = read_u32_internal(cursor)?;
+                Bytecode::LdU32(value)
+            }
             Opcodes::LD_U64 => {
                 let value = read_u64_internal(cursor)?;
                 Bytecode::LdU64(value)
@@ -1399,9 +1507,16 @@ fn load_code(cursor: &mut VersionedCursor, code: &mut Vec<Bytecode>) -> BinaryLo
                 let value = read_u128_internal(cursor)?;
                 Bytecode::LdU128(value)
             }
+            Opcodes::LD_U256 => {
+                let value = read_u256_internal(cursor)?;
+                Bytecode::LdU256(value)
+            }
             Opcodes::CAST_U8 => Bytecode::CastU8,
+            Opcodes::CAST_U16 => Bytecode::CastU16,
+            Opcodes::CAST_U32 => Bytecode::CastU32,
             Opcodes::CAST_U64 => Bytecode::CastU64,
             Opcodes::CAST_U128 => Bytecode::CastU128,
+            Opcodes::CAST_U256 => Bytecode::CastU256,
             Opcodes::LD_CONST => Bytecode::LdConst(load_constant_pool_index(cursor)?),
             Opcodes::LD_TRUE => Bytecode::LdTrue,
             Opcodes::LD_FALSE => Bytecode::LdFalse,
@@ -1478,6 +1593,27 @@ fn load_code(cursor: &mut VersionedCursor, code: &mut Vec<Bytecode>) -> BinaryLo
                 Bytecode::VecUnpack(load_signature_index(cursor)?, read_u64_internal(cursor)?)
             }
             Opcodes::VEC_SWAP => Bytecode::VecSwap(load_signature_index(cursor)?),
+            Opcodes::PACK_VARIANT => {
+                Bytecode::PackVariant(load_struct_def_index(cursor)?, load_variant_index(cursor)?)
+            }
+            Opcodes::PACK_VARIANT_GENERIC => Bytecode::PackVariantGeneric(
+                load_struct_def_inst_index(cursor)?,
+                load_variant_index(cursor)?,
+            ),
+            Opcodes::UNPACK_VARIANT => {
+                Bytecode::UnpackVariant(load_struct_def_index(cursor)?, load_variant_index(cursor)?)
+            }
+            Opcodes::UNPACK_VARIANT_GENERIC => Bytecode::UnpackVariantGeneric(
+                load_struct_def_inst_index(cursor)?,
+                load_variant_index(cursor)?,
+            ),
+            Opcodes::TEST_VARIANT => {
+                Bytecode::TestVariant(load_struct_def_index(cursor)?, load_variant_index(cursor)?)
+            }
+            Opcodes::TEST_VARIANT_GENERIC => Bytecode::TestVariantGeneric(
+                load_struct_def_inst_index(cursor)?,
+                load_variant_index(cursor)?,
+            ),
         };
         code.push(bytecode);
     }
@@ -1522,6 +1658,9 @@ impl SerializedType {
             0xA => Ok(SerializedType::VECTOR),
             0xB => Ok(SerializedType::STRUCT_INST),
             0xC => Ok(SerializedType::SIGNER),
+            0xD => Ok(SerializedType::U16),
+            0xE => Ok(SerializedType::U32),
+            0xF => Ok(SerializedType::U256),
             _ => Err(PartialVMError::new(StatusCode::UNKNOWN_SERIALIZED_TYPE)),
         }
     }
@@ -1570,6 +1709,7 @@ impl SerializedNativeStructFlag {
         match value {
             0x1 => Ok(SerializedNativeStructFlag::NATIVE),
             0x2 => Ok(SerializedNativeStructFlag::DECLARED),
+            0x3 => Ok(SerializedNativeStructFlag::DECLARED_VARIANTS),
             _ => Err(PartialVMError::new(StatusCode::UNKNOWN_NATIVE_STRUCT_FLAG)),
         }
     }
@@ -1649,6 +1789,18 @@ impl Opcodes {
             0x45 => Ok(Opcodes::VEC_POP_BACK),
             0x46 => Ok(Opcodes::VEC_UNPACK),
             0x47 => Ok(Opcodes::VEC_SWAP),
+            0x48 => Ok(Opcodes::LD_U16),
+            0x49 => Ok(Opcodes::LD_U32),
+            0x4A => Ok(Opcodes::LD_U256),
+            0x4B => Ok(Opcodes::CAST_U16),
+            0x4C => Ok(Opcodes::CAST_U32),
+            0x4D => Ok(Opcodes::CAST_U256),
+            0x4E => Ok(Opcodes::PACK_VARIANT),
+            0x4F => Ok(Opcodes::PACK_VARIANT_GENERIC),
+            0x50 => Ok(Opcodes::UNPACK_VARIANT),
+            0x51 => Ok(Opcodes::UNPACK_VARIANT_GENERIC),
+            0x52 => Ok(Opcodes::TEST_VARIANT),
+            0x53 => Ok(Opcodes::TEST_VARIANT_GENERIC),
             _ => Err(PartialVMError::new(StatusCode::UNKNOWN_OPCODE)),
         }
     }
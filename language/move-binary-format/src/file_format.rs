@@ -38,6 +38,7 @@ use move_core_types::{
     identifier::{IdentStr, Identifier},
     language_storage::ModuleId,
     metadata::Metadata,
+    u256::U256,
     vm_status::StatusCode,
 };
 #[cfg(any(test, feature = "fuzzing"))]
@@ -166,6 +167,10 @@ define_index! {
 pub type LocalIndex = u8;
 /// Max number of fields in a `StructDefinition`.
 pub type MemberCount = u16;
+/// Index of a variant in the list of variants of an enum.
+///
+/// Bytecodes that operate on values of enums carry the index of the variant they operate on.
+pub type VariantIndex = u16;
 /// Index into the code stream for a jump. The offset is relative to the beginning of
 /// the instruction stream.
 pub type CodeOffset = u16;
@@ -305,13 +310,15 @@ pub struct FieldHandle {
 // DEFINITIONS:
 // Definitions are the module code. So the set of types and functions in the module.
 
-/// `StructFieldInformation` indicates whether a struct is native or has user-specified fields
+/// `StructFieldInformation` indicates whether a struct is native, has user-specified fields, or
+/// is an enum with user-specified variants
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
 #[cfg_attr(any(test, feature = "fuzzing"), proptest(no_params))]
 pub enum StructFieldInformation {
     Native,
     Declared(Vec<FieldDefinition>),
+    DeclaredVariants(Vec<VariantDefinition>),
 }
 
 //
@@ -366,6 +373,7 @@ pub struct StructDefinition {
     /// Contains either
     /// - Information indicating the struct is native and has no accessible fields
     /// - Information indicating the number of fields and the start `FieldDefinition`s
+    /// - Information indicating the type is an enum and the `VariantDefinition`s of its variants
     pub field_information: StructFieldInformation,
 }
 
@@ -376,15 +384,41 @@ impl StructDefinition {
             StructFieldInformation::Native => Err(PartialVMError::new(StatusCode::LINKER_ERROR)
                 .with_message("Looking for field in native structure".to_string())),
             StructFieldInformation::Declared(fields) => Ok(fields.len() as u16),
+            StructFieldInformation::DeclaredVariants(_) => {
+                Err(PartialVMError::new(StatusCode::LINKER_ERROR)
+                    .with_message("Looking for field in enum".to_string()))
+            }
         }
     }
 
     pub fn field(&self, offset: usize) -> Option<&FieldDefinition> {
         match &self.field_information {
-            StructFieldInformation::Native => None,
+            StructFieldInformation::Native | StructFieldInformation::DeclaredVariants(_) => None,
             StructFieldInformation::Declared(fields) => fields.get(offset),
         }
     }
+
+    /// Returns the definition of the variant at `variant` if this is an enum.
+    pub fn variant(&self, variant: VariantIndex) -> Option<&VariantDefinition> {
+        match &self.field_information {
+            StructFieldInformation::Native | StructFieldInformation::Declared(_) => None,
+            StructFieldInformation::DeclaredVariants(variants) => variants.get(variant as usize),
+        }
+    }
+
+    /// Returns the fields of the type: the fields of a struct, or the fields of all variants of
+    /// an enum.
+    pub fn all_fields(&self) -> impl Iterator<Item = &FieldDefinition> {
+        let (fields, variants): (&[FieldDefinition], &[VariantDefinition]) =
+            match &self.field_information {
+                StructFieldInformation::Native => (&[], &[]),
+                StructFieldInformation::Declared(fields) => (fields.as_slice(), &[]),
+                StructFieldInformation::DeclaredVariants(variants) => (&[], variants.as_slice()),
+            };
+        fields
+            .iter()
+            .chain(variants.iter().flat_map(|variant| variant.fields.iter()))
+    }
 }
 
 /// A `FieldDefinition` is the definition of a field: its name and the field type.
@@ -398,6 +432,17 @@ pub struct FieldDefinition {
     pub signature: TypeSignature,
 }
 
+/// A `VariantDefinition` is the definition of a variant of an enum: its name and its fields.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
+#[cfg_attr(any(test, feature = "fuzzing"), proptest(no_params))]
+pub struct VariantDefinition {
+    /// The name of the variant.
+    pub name: IdentifierIndex,
+    /// The fields of the variant, in the order of declaration.
+    pub fields: Vec<FieldDefinition>,
+}
+
 /// `Visibility` restricts the accessibility of the associated entity.
 /// - For function visibility, it restricts who may call into the associated function.
 #[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord, Serialize, Deserialize)]
@@ -613,7 +658,7 @@ pub struct AbilitySet(u8);
 impl AbilitySet {
     /// The empty ability set
     pub const EMPTY: Self = Self(0);
-    /// Abilities for `Bool`, `U8`, `U64`, `U128`, and `Address`
+    /// Abilities for `Bool`, `U8`, `U16`, `U32`, `U64`, `U128`, `U256` and `Address`
     pub const PRIMITIVES: AbilitySet =
         Self((Ability::Copy as u8) | (Ability::Drop as u8) | (Ability::Store as u8));
     /// Abilities for `Reference` and `MutableReference`
@@ -839,6 +884,12 @@ pub enum SignatureToken {
     MutableReference(Box<SignatureToken>),
     /// Type parameter.
     TypeParameter(TypeParameterIndex),
+    /// Unsigned integers, 16 bits length.
+    U16,
+    /// Unsigned integers, 32 bits length.
+    U32,
+    /// Unsigned integers, 256 bits length.
+    U256,
 }
 
 /// An iterator to help traverse the `SignatureToken` in a non-recursive fashion to avoid
@@ -866,7 +917,8 @@ impl<'a> Iterator for SignatureTokenPreorderTraversalIter<'a> {
                         self.stack.extend(inner_toks.iter().rev())
                     }
 
-                    Signer | Bool | Address | U8 | U64 | U128 | Struct(_) | TypeParameter(_) => (),
+                    Signer | Bool | Address | U8 | U16 | U32 | U64 | U128 | U256 | Struct(_)
+                    | TypeParameter(_) => (),
                 }
                 Some(tok)
             }
@@ -898,7 +950,8 @@ impl<'a> Iterator for SignatureTokenPreorderTraversalIterWithDepth<'a> {
                         .stack
                         .extend(inner_toks.iter().map(|tok| (tok, depth + 1)).rev()),
 
-                    Signer | Bool | Address | U8 | U64 | U128 | Struct(_) | TypeParameter(_) => (),
+                    Signer | Bool | Address | U8 | U16 | U32 | U64 | U128 | U256 | Struct(_)
+                    | TypeParameter(_) => (),
                 }
                 Some((tok, depth))
             }
@@ -919,8 +972,11 @@ impl Arbitrary for SignatureToken {
         let leaf = prop_oneof![
             Just(Bool),
             Just(U8),
+            Just(U16),
+            Just(U32),
             Just(U64),
             Just(U128),
+            Just(U256),
             Just(Address),
             any::<StructHandleIndex>().prop_map(Struct),
             any::<TypeParameterIndex>().prop_map(TypeParameter),
@@ -946,8 +1002,11 @@ impl std::fmt::Debug for SignatureToken {
         match self {
             SignatureToken::Bool => write!(f, "Bool"),
             SignatureToken::U8 => write!(f, "U8"),
+            SignatureToken::U16 => write!(f, "U16"),
+            SignatureToken::U32 => write!(f, "U32"),
             SignatureToken::U64 => write!(f, "U64"),
             SignatureToken::U128 => write!(f, "U128"),
+            SignatureToken::U256 => write!(f, "U256"),
             SignatureToken::Address => write!(f, "Address"),
             SignatureToken::Signer => write!(f, "Signer"),
             SignatureToken::Vector(boxed) => write!(f, "Vector({:?})", boxed),
@@ -975,8 +1034,11 @@ impl SignatureToken {
             MutableReference(_) => SignatureTokenKind::MutableReference,
             Bool
             | U8
+            | U16
+            | U32
             | U64
             | U128
+            | U256
             | Address
             | Signer
             | Struct(_)
@@ -992,7 +1054,7 @@ impl SignatureToken {
     pub fn is_integer(&self) -> bool {
         use SignatureToken::*;
         match self {
-            U8 | U64 | U128 => true,
+            U8 | U16 | U32 | U64 | U128 | U256 => true,
             Bool
             | Address
             | Signer
@@ -1032,7 +1094,7 @@ impl SignatureToken {
         use SignatureToken::*;
 
         match self {
-            Bool | U8 | U64 | U128 | Address => true,
+            Bool | U8 | U16 | U32 | U64 | U128 | U256 | Address => true,
             Vector(inner) => inner.is_valid_for_constant(),
             Signer
             | Struct(_)
@@ -1556,6 +1618,67 @@ pub enum Bytecode {
     ///
     /// ```..., vector_reference, u64_value(1), u64_value(2) -> ...```
     VecSwap(SignatureIndex),
+    /// Push a U16 constant onto the stack.
+    ///
+    /// Stack transition:
+    ///
+    /// ```... -> ..., u16_value```
+    LdU16(u16),
+    /// Push a U32 constant onto the stack.
+    ///
+    /// Stack transition:
+    ///
+    /// ```... -> ..., u32_value```
+    LdU32(u32),
+    /// Push a U256 constant onto the stack.
+    ///
+    /// Stack transition:
+    ///
+    /// ```... -> ..., u256_value```
+    LdU256(U256),
+    /// Convert the value at the top of the stack into u16.
+    ///
+    /// Stack transition:
+    ///
+    /// ```..., integer_value -> ..., u16_value```
+    CastU16,
+    /// Convert the value at the top of the stack into u32.
+    ///
+    /// Stack transition:
+    ///
+    /// ```..., integer_value -> ..., u32_value```
+    CastU32,
+    /// Convert the value at the top of the stack into u256.
+    ///
+    /// Stack transition:
+    ///
+    /// ```..., integer_value -> ..., u256_value```
+    CastU256,
+    /// Create a value of the variant `VariantIndex` of the enum specified via
+    /// `StructDefinitionIndex` and push it on the stack. The values of the fields of the variant,
+    /// in the order they appear in the variant declaration, must be pushed on the stack.
+    ///
+    /// Stack transition:
+    ///
+    /// ```..., field(1)_value, field(2)_value, ..., field(n)_value -> ..., enum_value```
+    PackVariant(StructDefinitionIndex, VariantIndex),
+    PackVariantGeneric(StructDefInstantiationIndex, VariantIndex),
+    /// Destroy a value of an enum and push the values bound to the fields of its variant on the
+    /// stack. Abort execution if the value is not of the variant `VariantIndex`.
+    ///
+    /// Stack transition:
+    ///
+    /// ```..., enum_value -> ..., field(1)_value, field(2)_value, ..., field(n)_value```
+    UnpackVariant(StructDefinitionIndex, VariantIndex),
+    UnpackVariantGeneric(StructDefInstantiationIndex, VariantIndex),
+    /// Test whether the value of an enum referenced by the reference at the top of the stack is
+    /// of the variant `VariantIndex`. The reference is consumed.
+    ///
+    /// Stack transition:
+    ///
+    /// ```..., reference_value -> ..., bool_value```
+    TestVariant(StructDefinitionIndex, VariantIndex),
+    TestVariantGeneric(StructDefInstantiationIndex, VariantIndex),
 }
 
 pub const NUMBER_OF_NATIVE_FUNCTIONS: usize = 18;
@@ -1569,11 +1692,17 @@ impl ::std::fmt::Debug for Bytecode {
             Bytecode::BrFalse(a) => write!(f, "BrFalse({})", a),
             Bytecode::Branch(a) => write!(f, "Branch({})", a),
             Bytecode::LdU8(a) => write!(f, "LdU8({})", a),
+            Bytecode::LdU16(a) => write!(f, "LdU16({})", a),
+            Bytecode::LdU32(a) => write!(f, "LdU32({})", a),
             Bytecode::LdU64(a) => write!(f, "LdU64({})", a),
             Bytecode::LdU128(a) => write!(f, "LdU128({})", a),
+            Bytecode::LdU256(a) => write!(f, "LdU256({})", a),
             Bytecode::CastU8 => write!(f, "CastU8"),
+            Bytecode::CastU16 => write!(f, "CastU16"),
+            Bytecode::CastU32 => write!(f, "CastU32"),
             Bytecode::CastU64 => write!(f, "CastU64"),
             Bytecode::CastU128 => write!(f, "CastU128"),
+            Bytecode::CastU256 => write!(f, "CastU256"),
             Bytecode::LdConst(a) => write!(f, "LdConst({})", a),
             Bytecode::LdTrue => write!(f, "LdTrue"),
             Bytecode::LdFalse => write!(f, "LdFalse"),
@@ -1634,6 +1763,14 @@ impl ::std::fmt::Debug for Bytecode {
             Bytecode::VecPopBack(a) => write!(f, "VecPopBack({})", a),
             Bytecode::VecUnpack(a, n) => write!(f, "VecUnpack({}, {})", a, n),
             Bytecode::VecSwap(a) => write!(f, "VecSwap({})", a),
+            Bytecode::PackVariant(a, v) => write!(f, "PackVariant({}, {})", a, v),
+            Bytecode::PackVariantGeneric(a, v) => write!(f, "PackVariantGeneric({}, {})", a, v),
+            Bytecode::UnpackVariant(a, v) => write!(f, "UnpackVariant({}, {})", a, v),
+            Bytecode::UnpackVariantGeneric(a, v) => {
+                write!(f, "UnpackVariantGeneric({}, {})", a, v)
+            }
+            Bytecode::TestVariant(a, v) => write!(f, "TestVariant({}, {})", a, v),
+            Bytecode::TestVariantGeneric(a, v) => write!(f, "TestVariantGeneric({}, {})", a, v),
         }
     }
 }
@@ -1914,6 +2051,7 @@ impl CompiledModule {
                 | IndexKind::FieldDefinition
                 | IndexKind::TypeParameter
                 | IndexKind::MemberCount
+                | IndexKind::VariantDefinition
         ));
         match kind {
             IndexKind::ModuleHandle => self.module_handles.len(),
@@ -1935,7 +2073,10 @@ impl CompiledModule {
             | other @ IndexKind::CodeDefinition
             | other @ IndexKind::FieldDefinition
             | other @ IndexKind::TypeParameter
-            | other @ IndexKind::MemberCount => unreachable!("invalid kind for count: {:?}", other),
+            | other @ IndexKind::MemberCount
+            | other @ IndexKind::VariantDefinition => {
+                unreachable!("invalid kind for count: {:?}", other)
+            }
         }
     }
 
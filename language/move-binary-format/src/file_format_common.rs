@@ -12,6 +12,7 @@
 //! It's used to compress mostly indexes into the main binary tables.
 use crate::file_format::Bytecode;
 use anyhow::{bail, Result};
+use move_core_types::u256::U256;
 use std::{
     io::{Cursor, Read},
     mem::size_of,
@@ -71,6 +72,9 @@ pub const ACQUIRES_COUNT_MAX: u64 = 255;
 pub const FIELD_COUNT_MAX: u64 = 255;
 pub const FIELD_OFFSET_MAX: u64 = 255;
 
+pub const VARIANT_COUNT_MAX: u64 = 255;
+pub const VARIANT_INDEX_MAX: u64 = 65535;
+
 pub const TYPE_PARAMETER_COUNT_MAX: u64 = 255;
 pub const TYPE_PARAMETER_INDEX_MAX: u64 = 65536;
 
@@ -120,6 +124,9 @@ pub enum SerializedType {
     VECTOR                  = 0xA,
     STRUCT_INST             = 0xB,
     SIGNER                  = 0xC,
+    U16                     = 0xD,
+    U32                     = 0xE,
+    U256                    = 0xF,
 }
 
 #[rustfmt::skip]
@@ -129,6 +136,7 @@ pub enum SerializedType {
 pub enum SerializedNativeStructFlag {
     NATIVE                  = 0x1,
     DECLARED                = 0x2,
+    DECLARED_VARIANTS       = 0x3,
 }
 
 /// List of opcodes constants.
@@ -208,6 +216,18 @@ pub enum Opcodes {
     VEC_POP_BACK                = 0x45,
     VEC_UNPACK                  = 0x46,
     VEC_SWAP                    = 0x47,
+    LD_U16                      = 0x48,
+    LD_U32                      = 0x49,
+    LD_U256                     = 0x4A,
+    CAST_U16                    = 0x4B,
+    CAST_U32                    = 0x4C,
+    CAST_U256                   = 0x4D,
+    PACK_VARIANT                = 0x4E,
+    PACK_VARIANT_GENERIC        = 0x4F,
+    UNPACK_VARIANT              = 0x50,
+    UNPACK_VARIANT_GENERIC      = 0x51,
+    TEST_VARIANT                = 0x52,
+    TEST_VARIANT_GENERIC        = 0x53,
 }
 
 /// Upper limit on the binary size
@@ -299,7 +319,6 @@ pub(crate) fn write_u64_as_uleb128(binary: &mut BinaryData, mut val: u64) -> Res
 }
 
 /// Write a `u16` in Little Endian format.
-#[allow(dead_code)]
 pub(crate) fn write_u16(binary: &mut BinaryData, value: u16) -> Result<()> {
     binary.extend(&value.to_le_bytes())
 }
@@ -319,6 +338,11 @@ pub(crate) fn write_u128(binary: &mut BinaryData, value: u128) -> Result<()> {
     binary.extend(&value.to_le_bytes())
 }
 
+/// Write a `u256` in Little Endian format.
+pub(crate) fn write_u256(binary: &mut BinaryData, value: U256) -> Result<()> {
+    binary.extend(&value.to_le_bytes())
+}
+
 pub fn read_u8(cursor: &mut Cursor<&[u8]>) -> Result<u8> {
     let mut buf = [0; 1];
     cursor.read_exact(&mut buf)?;
@@ -383,8 +407,16 @@ pub const VERSION_4: u32 = 4;
 ///  + metadata
 pub const VERSION_5: u32 = 5;
 
+/// Version 6: changes compared with version 5
+///  + u16, u32, u256 integers and corresponding Ld, Cast bytecodes
+pub const VERSION_6: u32 = 6;
+
+/// Version 7: changes compared with version 6
+///  + enums and corresponding PackVariant, UnpackVariant, TestVariant bytecodes
+pub const VERSION_7: u32 = 7;
+
 // Mark which version is the latest version
-pub const VERSION_MAX: u32 = VERSION_5;
+pub const VERSION_MAX: u32 = VERSION_7;
 
 // Mark which oldest version is supported.
 // TODO(#145): finish v4 compatibility; as of now, only metadata is implemented
@@ -532,11 +564,17 @@ pub fn instruction_key(instruction: &Bytecode) -> u8 {
         BrFalse(_) => Opcodes::BR_FALSE,
         Branch(_) => Opcodes::BRANCH,
         LdU8(_) => Opcodes::LD_U8,
+        LdU16(_) => Opcodes::LD_U16,
+        LdU32(_) => Opcodes::LD_U32,
         LdU64(_) => Opcodes::LD_U64,
         LdU128(_) => Opcodes::LD_U128,
+        LdU256(_) => Opcodes::LD_U256,
         CastU8 => Opcodes::CAST_U8,
+        CastU16 => Opcodes::CAST_U16,
+        CastU32 => Opcodes::CAST_U32,
         CastU64 => Opcodes::CAST_U64,
         CastU128 => Opcodes::CAST_U128,
+        CastU256 => Opcodes::CAST_U256,
         LdConst(_) => Opcodes::LD_CONST,
         LdTrue => Opcodes::LD_TRUE,
         LdFalse => Opcodes::LD_FALSE,
@@ -597,6 +635,12 @@ pub fn instruction_key(instruction: &Bytecode) -> u8 {
         VecPopBack(_) => Opcodes::VEC_POP_BACK,
         VecUnpack(..) => Opcodes::VEC_UNPACK,
         VecSwap(_) => Opcodes::VEC_SWAP,
+        PackVariant(..) => Opcodes::PACK_VARIANT,
+        PackVariantGeneric(..) => Opcodes::PACK_VARIANT_GENERIC,
+        UnpackVariant(..) => Opcodes::UNPACK_VARIANT,
+        UnpackVariantGeneric(..) => Opcodes::UNPACK_VARIANT_GENERIC,
+        TestVariant(..) => Opcodes::TEST_VARIANT,
+        TestVariantGeneric(..) => Opcodes::TEST_VARIANT_GENERIC,
     };
     opcode as u8
 }
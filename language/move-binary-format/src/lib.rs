@@ -50,6 +50,7 @@ pub enum IndexKind {
     CodeDefinition,
     TypeParameter,
     MemberCount,
+    VariantDefinition,
 }
 
 impl IndexKind {
@@ -76,6 +77,7 @@ impl IndexKind {
             CodeDefinition,
             TypeParameter,
             MemberCount,
+            VariantDefinition,
         ]
     }
 }
@@ -104,6 +106,7 @@ impl fmt::Display for IndexKind {
             CodeDefinition => "code definition pool",
             TypeParameter => "type parameter",
             MemberCount => "field offset",
+            VariantDefinition => "variant definition",
         };
 
         f.write_str(desc)
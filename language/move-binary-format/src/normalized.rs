@@ -52,6 +52,13 @@ pub enum Type {
     TypeParameter(TypeParameterIndex),
     Reference(Box<Type>),
     MutableReference(Box<Type>),
+    // NOTE: Added in bytecode version 6, do not reorder!
+    #[serde(rename = "u16")]
+    U16,
+    #[serde(rename = "u32")]
+    U32,
+    #[serde(rename = "u256")]
+    U256,
 }
 
 /// Normalized version of a `FieldDefinition`. The `name` is included even though it is
@@ -64,13 +71,22 @@ pub struct Field {
     pub type_: Type,
 }
 
+/// Normalized version of a `VariantDefinition`. Not safe to compare without an enclosing
+/// `Struct`.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Variant {
+    pub name: Identifier,
+    pub fields: Vec<Field>,
+}
+
 /// Normalized version of a `StructDefinition`. Not safe to compare without an associated
-/// `ModuleId` or `Module`.
+/// `ModuleId` or `Module`. An enum has no `fields` but a non-empty list of `variants`.
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct Struct {
     pub abilities: AbilitySet,
     pub type_parameters: Vec<StructTypeParameter>,
     pub fields: Vec<Field>,
+    pub variants: Vec<Variant>,
 }
 
 /// Normalized version of a `FunctionDefinition`. Not safe to compare without an associated
@@ -160,8 +176,11 @@ impl Type {
             }
             Bool => Type::Bool,
             U8 => Type::U8,
+            U16 => Type::U16,
+            U32 => Type::U32,
             U64 => Type::U64,
             U128 => Type::U128,
+            U256 => Type::U256,
             Address => Type::Address,
             Signer => Type::Signer,
             Vector(t) => Type::Vector(Box::new(Type::new(m, t))),
@@ -178,8 +197,11 @@ impl Type {
             TypeParameter(_) => false,
             Bool => true,
             U8 => true,
+            U16 => true,
+            U32 => true,
             U64 => true,
             U128 => true,
+            U256 => true,
             Address => true,
             Signer => true,
             Struct { type_arguments, .. } => type_arguments.iter().all(|t| t.is_closed()),
@@ -194,8 +216,11 @@ impl Type {
                 Reference(_) | MutableReference(_) => return None,
                 Bool => TypeTag::Bool,
                 U8 => TypeTag::U8,
+                U16 => TypeTag::U16,
+                U32 => TypeTag::U32,
                 U64 => TypeTag::U64,
                 U128 => TypeTag::U128,
+                U256 => TypeTag::U256,
                 Address => TypeTag::Address,
                 Signer => TypeTag::Signer,
                 Vector(t) => TypeTag::Vector(Box::new(
@@ -237,7 +262,7 @@ impl Type {
     pub fn subst(&self, type_args: &[Type]) -> Self {
         use Type::*;
         match self {
-            Bool | U8 | U64 | U128 | Address | Signer => self.clone(),
+            Bool | U8 | U16 | U32 | U64 | U128 | U256 | Address | Signer => self.clone(),
             Reference(ty) => Reference(Box::new(ty.subst(type_args))),
             MutableReference(ty) => MutableReference(Box::new(ty.subst(type_args))),
             Vector(t) => Vector(Box::new(t.subst(type_args))),
@@ -278,17 +303,28 @@ impl Struct {
     /// a native struct definition.
     pub fn new(m: &CompiledModule, def: &StructDefinition) -> (Identifier, Self) {
         let handle = m.struct_handle_at(def.struct_handle);
-        let fields = match &def.field_information {
+        let (fields, variants) = match &def.field_information {
             StructFieldInformation::Native => panic!("Can't extract for native struct"),
             StructFieldInformation::Declared(fields) => {
-                fields.iter().map(|f| Field::new(m, f)).collect()
+                (fields.iter().map(|f| Field::new(m, f)).collect(), vec![])
             }
+            StructFieldInformation::DeclaredVariants(variants) => (
+                vec![],
+                variants
+                    .iter()
+                    .map(|v| Variant {
+                        name: m.identifier_at(v.name).to_owned(),
+                        fields: v.fields.iter().map(|f| Field::new(m, f)).collect(),
+                    })
+                    .collect(),
+            ),
         };
         let name = m.identifier_at(handle.name).to_owned();
         let s = Struct {
             abilities: handle.abilities,
             type_parameters: handle.type_parameters.clone(),
             fields,
+            variants,
         };
         (name, s)
     }
@@ -340,8 +376,11 @@ impl From<TypeTag> for Type {
         match ty {
             TypeTag::Bool => Bool,
             TypeTag::U8 => U8,
+            TypeTag::U16 => U16,
+            TypeTag::U32 => U32,
             TypeTag::U64 => U64,
             TypeTag::U128 => U128,
+            TypeTag::U256 => U256,
             TypeTag::Address => Address,
             TypeTag::Signer => Signer,
             TypeTag::Vector(ty) => Vector(Box::new(Type::from(*ty))),
@@ -383,8 +422,11 @@ impl std::fmt::Display for Type {
             }
             Type::Vector(ty) => write!(f, "vector<{}>", ty),
             Type::U8 => write!(f, "u8"),
+            Type::U16 => write!(f, "u16"),
+            Type::U32 => write!(f, "u32"),
             Type::U64 => write!(f, "u64"),
             Type::U128 => write!(f, "u128"),
+            Type::U256 => write!(f, "u256"),
             Type::Address => write!(f, "address"),
             Type::Signer => write!(f, "signer"),
             Type::Bool => write!(f, "bool"),
@@ -171,6 +171,14 @@ fn serialize_field_offset(binary: &mut BinaryData, offset: u16) -> Result<()> {
     write_as_uleb128(binary, offset, FIELD_OFFSET_MAX)
 }
 
+fn serialize_variant_count(binary: &mut BinaryData, len: usize) -> Result<()> {
+    write_as_uleb128(binary, len as u64, VARIANT_COUNT_MAX)
+}
+
+fn serialize_variant_index(binary: &mut BinaryData, idx: VariantIndex) -> Result<()> {
+    write_as_uleb128(binary, idx, VARIANT_INDEX_MAX)
+}
+
 fn serialize_acquires_count(binary: &mut BinaryData, len: usize) -> Result<()> {
     write_as_uleb128(binary, len as u64, ACQUIRES_COUNT_MAX)
 }
@@ -547,6 +555,8 @@ fn serialize_byte_blob(
 /// - `StructDefinition.handle` as a ULEB128 (index into the `ModuleHandle` table)
 /// - `StructDefinition.field_count` as a ULEB128 (number of fields defined in the type)
 /// - `StructDefinition.fields` as a ULEB128 (index into the `FieldDefinition` table)
+///
+/// An enum gets serialized with the number of its variants followed by each `VariantDefinition`.
 fn serialize_struct_definition(
     binary: &mut BinaryData,
     struct_definition: &StructDefinition,
@@ -558,6 +568,14 @@ fn serialize_struct_definition(
             binary.push(SerializedNativeStructFlag::DECLARED as u8)?;
             serialize_field_definitions(binary, fields)
         }
+        StructFieldInformation::DeclaredVariants(variants) => {
+            binary.push(SerializedNativeStructFlag::DECLARED_VARIANTS as u8)?;
+            serialize_variant_count(binary, variants.len())?;
+            for variant in variants {
+                serialize_variant_definition(binary, variant)?;
+            }
+            Ok(())
+        }
     }
 }
 
@@ -593,6 +611,19 @@ fn serialize_field_definition(
     serialize_signature_token(binary, &field_definition.signature.0)
 }
 
+/// Serializes a `VariantDefinition`.
+///
+/// A `VariantDefinition` gets serialized as follows:
+/// - `VariantDefinition.name` as a ULEB128 (index into the `IdentifierPool` table)
+/// - `VariantDefinition.fields` as the fields of a struct
+fn serialize_variant_definition(
+    binary: &mut BinaryData,
+    variant_definition: &VariantDefinition,
+) -> Result<()> {
+    serialize_identifier_index(binary, &variant_definition.name)?;
+    serialize_field_definitions(binary, &variant_definition.fields)
+}
+
 fn serialize_field_handle(binary: &mut BinaryData, field_handle: &FieldHandle) -> Result<()> {
     serialize_struct_def_index(binary, &field_handle.owner)?;
     serialize_field_offset(binary, field_handle.field)?;
@@ -640,8 +671,11 @@ fn serialize_signature_token_single_node_impl(
     match token {
         SignatureToken::Bool => binary.push(SerializedType::BOOL as u8)?,
         SignatureToken::U8 => binary.push(SerializedType::U8 as u8)?,
+        SignatureToken::U16 => binary.push(SerializedType::U16 as u8)?,
+        SignatureToken::U32 => binary.push(SerializedType::U32 as u8)?,
         SignatureToken::U64 => binary.push(SerializedType::U64 as u8)?,
         SignatureToken::U128 => binary.push(SerializedType::U128 as u8)?,
+        SignatureToken::U256 => binary.push(SerializedType::U256 as u8)?,
         SignatureToken::Address => binary.push(SerializedType::ADDRESS as u8)?,
         SignatureToken::Signer => binary.push(SerializedType::SIGNER as u8)?,
         SignatureToken::Vector(_) => {
@@ -746,6 +780,14 @@ fn serialize_instruction_inner(binary: &mut BinaryData, opcode: &Bytecode) -> Re
             binary.push(Opcodes::LD_U8 as u8)?;
             binary.push(*value)
         }
+        Bytecode::LdU16(value) => {
+            binary.push(Opcodes::LD_U16 as u8)?;
+            write_u16(binary, *value)
+        }
+        Bytecode::LdU32(value) => {
+            binary.push(Opcodes::LD_U32 as u8)?;
+            write_u32(binary, *value)
+        }
         Bytecode::LdU64(value) => {
             binary.push(Opcodes::LD_U64 as u8)?;
             write_u64(binary, *value)
@@ -754,9 +796,16 @@ fn serialize_instruction_inner(binary: &mut BinaryData, opcode: &Bytecode) -> Re
             binary.push(Opcodes::LD_U128 as u8)?;
             write_u128(binary, *value)
         }
+        Bytecode::LdU256(value) => {
+            binary.push(Opcodes::LD_U256 as u8)?;
+            write_u256(binary, *value)
+        }
         Bytecode::CastU8 => binary.push(Opcodes::CAST_U8 as u8),
+        Bytecode::CastU16 => binary.push(Opcodes::CAST_U16 as u8),
+        Bytecode::CastU32 => binary.push(Opcodes::CAST_U32 as u8),
         Bytecode::CastU64 => binary.push(Opcodes::CAST_U64 as u8),
         Bytecode::CastU128 => binary.push(Opcodes::CAST_U128 as u8),
+        Bytecode::CastU256 => binary.push(Opcodes::CAST_U256 as u8),
         Bytecode::LdConst(const_idx) => {
             binary.push(Opcodes::LD_CONST as u8)?;
             serialize_constant_pool_index(binary, const_idx)
@@ -920,6 +969,36 @@ fn serialize_instruction_inner(binary: &mut BinaryData, opcode: &Bytecode) -> Re
             binary.push(Opcodes::VEC_SWAP as u8)?;
             serialize_signature_index(binary, sig_idx)
         }
+        Bytecode::PackVariant(class_idx, variant) => {
+            binary.push(Opcodes::PACK_VARIANT as u8)?;
+            serialize_struct_def_index(binary, class_idx)?;
+            serialize_variant_index(binary, *variant)
+        }
+        Bytecode::PackVariantGeneric(class_idx, variant) => {
+            binary.push(Opcodes::PACK_VARIANT_GENERIC as u8)?;
+            serialize_struct_def_inst_index(binary, class_idx)?;
+            serialize_variant_index(binary, *variant)
+        }
+        Bytecode::UnpackVariant(class_idx, variant) => {
+            binary.push(Opcodes::UNPACK_VARIANT as u8)?;
+            serialize_struct_def_index(binary, class_idx)?;
+            serialize_variant_index(binary, *variant)
+        }
+        Bytecode::UnpackVariantGeneric(class_idx, variant) => {
+            binary.push(Opcodes::UNPACK_VARIANT_GENERIC as u8)?;
+            serialize_struct_def_inst_index(binary, class_idx)?;
+            serialize_variant_index(binary, *variant)
+        }
+        Bytecode::TestVariant(class_idx, variant) => {
+            binary.push(Opcodes::TEST_VARIANT as u8)?;
+            serialize_struct_def_index(binary, class_idx)?;
+            serialize_variant_index(binary, *variant)
+        }
+        Bytecode::TestVariantGeneric(class_idx, variant) => {
+            binary.push(Opcodes::TEST_VARIANT_GENERIC as u8)?;
+            serialize_struct_def_inst_index(binary, class_idx)?;
+            serialize_variant_index(binary, *variant)
+        }
     };
     res?;
     Ok(())
@@ -934,6 +1013,62 @@ fn serialize_code(binary: &mut BinaryData, code: &[Bytecode]) -> Result<()> {
     Ok(())
 }
 
+/// Fails if `token` uses an integer type which is not supported by bytecode `version`.
+fn check_signature_token_version(version: u32, token: &SignatureToken) -> Result<()> {
+    if version < VERSION_6 {
+        for node in token.preorder_traversal() {
+            if matches!(
+                node,
+                SignatureToken::U16 | SignatureToken::U32 | SignatureToken::U256
+            ) {
+                bail!(
+                    "u16, u32, u256 integers not supported in bytecode version {}",
+                    version
+                )
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Fails if `code` uses an instruction which is not supported by bytecode `version`.
+fn check_code_version(version: u32, code: &CodeUnit) -> Result<()> {
+    if version < VERSION_7 {
+        for instr in &code.code {
+            if matches!(
+                instr,
+                Bytecode::PackVariant(..)
+                    | Bytecode::PackVariantGeneric(..)
+                    | Bytecode::UnpackVariant(..)
+                    | Bytecode::UnpackVariantGeneric(..)
+                    | Bytecode::TestVariant(..)
+                    | Bytecode::TestVariantGeneric(..)
+            ) {
+                bail!("enums not supported in bytecode version {}", version)
+            }
+        }
+    }
+    if version < VERSION_6 {
+        for instr in &code.code {
+            if matches!(
+                instr,
+                Bytecode::LdU16(_)
+                    | Bytecode::LdU32(_)
+                    | Bytecode::LdU256(_)
+                    | Bytecode::CastU16
+                    | Bytecode::CastU32
+                    | Bytecode::CastU256
+            ) {
+                bail!(
+                    "u16, u32, u256 integers not supported in bytecode version {}",
+                    version
+                )
+            }
+        }
+    }
+    Ok(())
+}
+
 /// Compute the table size with a check for underflow
 fn checked_calculate_table_size(binary: &mut BinaryData, start: u32) -> Result<u32> {
     let offset = check_index_in_binary(binary.len())?;
@@ -1165,6 +1300,7 @@ impl CommonSerializer {
             self.table_count += 1;
             self.constant_pool.0 = check_index_in_binary(binary.len())?;
             for constant in constants {
+                check_signature_token_version(self.major_version, &constant.type_)?;
                 serialize_constant(binary, constant)?;
             }
             self.constant_pool.1 = checked_calculate_table_size(binary, self.constant_pool.0)?;
@@ -1195,6 +1331,9 @@ impl CommonSerializer {
             self.table_count += 1;
             self.signatures.0 = check_index_in_binary(binary.len())?;
             for signature in signatures {
+                for token in &signature.0 {
+                    check_signature_token_version(self.major_version, token)?;
+                }
                 serialize_signature(binary, signature)?;
             }
             self.signatures.1 = checked_calculate_table_size(binary, self.signatures.0)?;
@@ -1277,6 +1416,19 @@ impl ModuleSerializer {
             self.common.table_count = self.common.table_count.wrapping_add(1); // the count will bound to a small number
             self.struct_defs.0 = check_index_in_binary(binary.len())?;
             for struct_definition in struct_definitions {
+                if let StructFieldInformation::DeclaredVariants(_) =
+                    &struct_definition.field_information
+                {
+                    if self.common.major_version < VERSION_7 {
+                        bail!(
+                            "enums not supported in bytecode version {}",
+                            self.common.major_version
+                        )
+                    }
+                }
+                for field in struct_definition.all_fields() {
+                    check_signature_token_version(self.common.major_version, &field.signature.0)?;
+                }
                 serialize_struct_definition(binary, struct_definition)?;
             }
             self.struct_defs.1 = checked_calculate_table_size(binary, self.struct_defs.0)?;
@@ -1391,6 +1543,7 @@ impl ModuleSerializer {
 
         serialize_acquires(binary, &function_definition.acquires_global_resources)?;
         if let Some(code) = &function_definition.code {
+            check_code_version(self.common.major_version, code)?;
             serialize_code_unit(binary, code)?;
         }
         Ok(())
@@ -1424,6 +1577,7 @@ impl ScriptSerializer {
     fn serialize_main(&mut self, binary: &mut BinaryData, script: &CompiledScript) -> Result<()> {
         serialize_ability_sets(binary, &script.type_parameters)?;
         serialize_signature_index(binary, &script.parameters)?;
+        check_code_version(self.common.major_version, &script.code)?;
         serialize_code_unit(binary, &script.code)?;
         Ok(())
     }
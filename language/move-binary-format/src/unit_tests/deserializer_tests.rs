@@ -2,7 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    file_format::{CompiledModule, CompiledScript},
+    file_format::{
+        basic_test_module, empty_module, Bytecode, CompiledModule, CompiledScript, Signature,
+        SignatureToken, StructDefinitionIndex, StructFieldInformation, VariantDefinition,
+    },
     file_format_common::*,
 };
 use move_core_types::vm_status::StatusCode;
@@ -241,3 +244,70 @@ fn deserialize_invalid_script_no_signature() {
         StatusCode::INDEX_OUT_OF_BOUNDS
     );
 }
+
+#[test]
+fn deserialize_new_integer_types() {
+    let mut module = empty_module();
+    module.signatures.push(Signature(vec![
+        SignatureToken::U16,
+        SignatureToken::U32,
+        SignatureToken::Vector(Box::new(SignatureToken::U256)),
+    ]));
+
+    let mut binary = vec![];
+    module.serialize(&mut binary).unwrap();
+    assert_eq!(CompiledModule::deserialize(&binary).unwrap(), module);
+
+    // The types cannot be written in an older bytecode version...
+    module
+        .serialize_for_version(Some(VERSION_5), &mut vec![])
+        .expect_err("Expected serialization to fail");
+
+    // ...nor read from one.
+    let version_offset = BinaryConstants::DIEM_MAGIC_SIZE;
+    binary[version_offset..version_offset + 4].copy_from_slice(&VERSION_5.to_le_bytes());
+    assert_eq!(
+        CompiledModule::deserialize(&binary)
+            .expect_err("Expected unsupported type")
+            .major_status(),
+        StatusCode::MALFORMED
+    );
+}
+
+#[test]
+fn deserialize_enums() {
+    let mut module = basic_test_module();
+    let fields = match &module.struct_defs[0].field_information {
+        StructFieldInformation::Declared(fields) => fields.clone(),
+        _ => unreachable!(),
+    };
+    let name = module.struct_handles[0].name;
+    module.struct_defs[0].field_information =
+        StructFieldInformation::DeclaredVariants(vec![VariantDefinition { name, fields }]);
+    module.function_defs[0].code.as_mut().unwrap().code = vec![
+        Bytecode::LdU64(0),
+        Bytecode::PackVariant(StructDefinitionIndex(0), 0),
+        Bytecode::UnpackVariant(StructDefinitionIndex(0), 0),
+        Bytecode::Pop,
+        Bytecode::Ret,
+    ];
+
+    let mut binary = vec![];
+    module.serialize(&mut binary).unwrap();
+    assert_eq!(CompiledModule::deserialize(&binary).unwrap(), module);
+
+    // Enums cannot be written in an older bytecode version...
+    module
+        .serialize_for_version(Some(VERSION_6), &mut vec![])
+        .expect_err("Expected serialization to fail");
+
+    // ...nor read from one.
+    let version_offset = BinaryConstants::DIEM_MAGIC_SIZE;
+    binary[version_offset..version_offset + 4].copy_from_slice(&VERSION_6.to_le_bytes());
+    assert_eq!(
+        CompiledModule::deserialize(&binary)
+            .expect_err("Expected unsupported enum")
+            .major_status(),
+        StatusCode::MALFORMED
+    );
+}
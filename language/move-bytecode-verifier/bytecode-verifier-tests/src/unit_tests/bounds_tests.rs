@@ -450,14 +450,23 @@ proptest! {
     }
 }
 
-proptest! {
-    // Generating arbitrary compiled modules is really slow, possibly because of
-    // https://github.com/AltSysrq/proptest/issues/143.
-    #![proptest_config(ProptestConfig::with_cases(16))]
-
-    /// Make sure that garbage inputs don't crash the bounds checker.
-    #[test]
-    fn garbage_inputs(module in any_with::<CompiledModule>(16)) {
-        let _ = BoundsChecker::verify_module(&module);
-    }
+/// Make sure that garbage inputs don't crash the bounds checker.
+#[test]
+fn garbage_inputs() {
+    // Generating arbitrary compiled modules goes through the strategies of all bytecodes, which
+    // takes more than the default stack of a test thread.
+    std::thread::Builder::new()
+        .stack_size(8 * 1024 * 1024)
+        .spawn(|| {
+            // Generating arbitrary compiled modules is really slow, possibly because of
+            // https://github.com/AltSysrq/proptest/issues/143.
+            proptest!(ProptestConfig::with_cases(16), |(
+                module in any_with::<CompiledModule>(16)
+            )| {
+                let _ = BoundsChecker::verify_module(&module);
+            });
+        })
+        .unwrap()
+        .join()
+        .unwrap()
 }
@@ -9,7 +9,7 @@ use move_core_types::vm_status::StatusCode;
 #[test]
 fn invalid_fallthrough_br_true() {
     let module = dummy_procedure_module(vec![Bytecode::LdFalse, Bytecode::BrTrue(1)]);
-    let result = CodeUnitVerifier::verify_module(&module);
+    let result = CodeUnitVerifier::verify_module(&Default::default(), &module);
     assert_eq!(
         result.unwrap_err().major_status(),
         StatusCode::INVALID_FALL_THROUGH
@@ -19,7 +19,7 @@ fn invalid_fallthrough_br_true() {
 #[test]
 fn invalid_fallthrough_br_false() {
     let module = dummy_procedure_module(vec![Bytecode::LdTrue, Bytecode::BrFalse(1)]);
-    let result = CodeUnitVerifier::verify_module(&module);
+    let result = CodeUnitVerifier::verify_module(&Default::default(), &module);
     assert_eq!(
         result.unwrap_err().major_status(),
         StatusCode::INVALID_FALL_THROUGH
@@ -30,7 +30,7 @@ fn invalid_fallthrough_br_false() {
 #[test]
 fn invalid_fallthrough_non_branch() {
     let module = dummy_procedure_module(vec![Bytecode::LdTrue, Bytecode::Pop]);
-    let result = CodeUnitVerifier::verify_module(&module);
+    let result = CodeUnitVerifier::verify_module(&Default::default(), &module);
     assert_eq!(
         result.unwrap_err().major_status(),
         StatusCode::INVALID_FALL_THROUGH
@@ -40,20 +40,20 @@ fn invalid_fallthrough_non_branch() {
 #[test]
 fn valid_fallthrough_branch() {
     let module = dummy_procedure_module(vec![Bytecode::Branch(0)]);
-    let result = CodeUnitVerifier::verify_module(&module);
+    let result = CodeUnitVerifier::verify_module(&Default::default(), &module);
     assert!(result.is_ok());
 }
 
 #[test]
 fn valid_fallthrough_ret() {
     let module = dummy_procedure_module(vec![Bytecode::Ret]);
-    let result = CodeUnitVerifier::verify_module(&module);
+    let result = CodeUnitVerifier::verify_module(&Default::default(), &module);
     assert!(result.is_ok());
 }
 
 #[test]
 fn valid_fallthrough_abort() {
     let module = dummy_procedure_module(vec![Bytecode::LdU64(7), Bytecode::Abort]);
-    let result = CodeUnitVerifier::verify_module(&module);
+    let result = CodeUnitVerifier::verify_module(&Default::default(), &module);
     assert!(result.is_ok());
 }
@@ -24,6 +24,14 @@ fn valid_primitives() {
             type_: SignatureToken::U8,
             data: vec![0],
         },
+        Constant {
+            type_: SignatureToken::U16,
+            data: vec![0, 0],
+        },
+        Constant {
+            type_: SignatureToken::U32,
+            data: vec![0, 0, 0, 0],
+        },
         Constant {
             type_: SignatureToken::U64,
             data: vec![0, 0, 0, 0, 0, 0, 0, 0],
@@ -32,6 +40,10 @@ fn valid_primitives() {
             type_: SignatureToken::U128,
             data: vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
         },
+        Constant {
+            type_: SignatureToken::U256,
+            data: vec![0; 32],
+        },
         Constant {
             type_: SignatureToken::Address,
             data: vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
@@ -43,8 +55,11 @@ fn valid_primitives() {
 #[test]
 fn invalid_primitives() {
     malformed(SignatureToken::U8, vec![0, 0]);
+    malformed(SignatureToken::U16, vec![0]);
+    malformed(SignatureToken::U32, vec![0]);
     malformed(SignatureToken::U64, vec![0]);
     malformed(SignatureToken::U128, vec![0]);
+    malformed(SignatureToken::U256, vec![0]);
     let data = vec![
         0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
         0, 0,
@@ -7,10 +7,17 @@ use move_binary_format::{
     errors::PartialVMResult,
     file_format::{Bytecode, CompiledModule, FunctionDefinitionIndex, TableIndex},
 };
-use move_bytecode_verifier::control_flow;
+use move_bytecode_verifier::{control_flow, VerifierConfig};
 use move_core_types::vm_status::StatusCode;
 
 fn verify_module(module: &CompiledModule) -> PartialVMResult<()> {
+    verify_module_with_config(&VerifierConfig::default(), module)
+}
+
+fn verify_module_with_config(
+    verifier_config: &VerifierConfig,
+    module: &CompiledModule,
+) -> PartialVMResult<()> {
     for (idx, function_definition) in module
         .function_defs()
         .iter()
@@ -18,6 +25,7 @@ fn verify_module(module: &CompiledModule) -> PartialVMResult<()> {
         .filter(|(_, def)| !def.is_native())
     {
         control_flow::verify(
+            verifier_config,
             Some(FunctionDefinitionIndex(idx as TableIndex)),
             function_definition
                 .code
@@ -83,3 +91,47 @@ fn valid_fallthrough_abort() {
     let result = verify_module(&module);
     assert!(result.is_ok());
 }
+
+//**************************************************************************************************
+// Limits
+//**************************************************************************************************
+
+fn nested_loops_module() -> CompiledModule {
+    dummy_procedure_module(vec![
+        Bytecode::Nop,
+        Bytecode::Nop,
+        Bytecode::LdTrue,
+        Bytecode::BrTrue(1),
+        Bytecode::Branch(0),
+    ])
+}
+
+#[test]
+fn loop_max_depth() {
+    let module = nested_loops_module();
+    let verifier_config = |max_loop_depth| VerifierConfig {
+        max_loop_depth: Some(max_loop_depth),
+        ..VerifierConfig::default()
+    };
+    assert!(verify_module_with_config(&verifier_config(2), &module).is_ok());
+    let result = verify_module_with_config(&verifier_config(1), &module);
+    assert_eq!(
+        result.unwrap_err().major_status(),
+        StatusCode::LOOP_MAX_DEPTH_REACHED
+    );
+}
+
+#[test]
+fn too_many_back_edges() {
+    let module = nested_loops_module();
+    let verifier_config = |max_back_edges| VerifierConfig {
+        max_back_edges_per_function: Some(max_back_edges),
+        ..VerifierConfig::default()
+    };
+    assert!(verify_module_with_config(&verifier_config(2), &module).is_ok());
+    let result = verify_module_with_config(&verifier_config(1), &module);
+    assert_eq!(
+        result.unwrap_err().major_status(),
+        StatusCode::TOO_MANY_BACK_EDGES
+    );
+}
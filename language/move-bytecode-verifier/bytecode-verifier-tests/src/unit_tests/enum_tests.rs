@@ -0,0 +1,126 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use move_binary_format::file_format::*;
+use move_bytecode_verifier::verify_module;
+use move_core_types::{identifier::Identifier, vm_status::StatusCode};
+
+// Make the module of `basic_test_module` with `Bar` turned into the enum
+// `Bar { A { x: u64 }, B }` and the given code as the body of `foo`, which has a local of type
+// `Bar`.
+fn enum_module(code: Vec<Bytecode>) -> CompiledModule {
+    let mut m = basic_test_module();
+    let x = m.struct_defs[0].field(0).unwrap().clone();
+    let a = IdentifierIndex(m.identifiers.len() as u16);
+    m.identifiers.push(Identifier::new("A").unwrap());
+    let b = IdentifierIndex(m.identifiers.len() as u16);
+    m.identifiers.push(Identifier::new("B").unwrap());
+    m.struct_defs[0].field_information = StructFieldInformation::DeclaredVariants(vec![
+        VariantDefinition {
+            name: a,
+            fields: vec![x],
+        },
+        VariantDefinition {
+            name: b,
+            fields: vec![],
+        },
+    ]);
+
+    m.signatures
+        .push(Signature(vec![SignatureToken::Struct(StructHandleIndex(
+            0,
+        ))]));
+    let foo = m.function_defs[0].code.as_mut().unwrap();
+    foo.locals = SignatureIndex(1);
+    foo.code = code;
+    m
+}
+
+fn expect_status(module: &CompiledModule, status: StatusCode) {
+    assert_eq!(verify_module(module).unwrap_err().major_status(), status);
+}
+
+#[test]
+fn valid_variant_ops() {
+    let module = enum_module(vec![
+        Bytecode::LdU64(0),
+        Bytecode::PackVariant(StructDefinitionIndex(0), 0),
+        Bytecode::UnpackVariant(StructDefinitionIndex(0), 0),
+        Bytecode::Pop,
+        Bytecode::PackVariant(StructDefinitionIndex(0), 1),
+        Bytecode::StLoc(0),
+        Bytecode::ImmBorrowLoc(0),
+        Bytecode::TestVariant(StructDefinitionIndex(0), 0),
+        Bytecode::Pop,
+        Bytecode::MoveLoc(0),
+        Bytecode::UnpackVariant(StructDefinitionIndex(0), 1),
+        Bytecode::Ret,
+    ]);
+    verify_module(&module).unwrap();
+}
+
+#[test]
+fn variant_out_of_bounds() {
+    let module = enum_module(vec![
+        Bytecode::PackVariant(StructDefinitionIndex(0), 2),
+        Bytecode::Ret,
+    ]);
+    expect_status(&module, StatusCode::INDEX_OUT_OF_BOUNDS);
+
+    // A struct has no variants
+    let mut module = basic_test_module();
+    module.function_defs[0].code.as_mut().unwrap().code = vec![
+        Bytecode::PackVariant(StructDefinitionIndex(0), 0),
+        Bytecode::Ret,
+    ];
+    expect_status(&module, StatusCode::INDEX_OUT_OF_BOUNDS);
+}
+
+#[test]
+fn struct_op_on_enum() {
+    let module = enum_module(vec![
+        Bytecode::LdU64(0),
+        Bytecode::Pack(StructDefinitionIndex(0)),
+        Bytecode::Pop,
+        Bytecode::Ret,
+    ]);
+    expect_status(&module, StatusCode::STRUCT_ENUM_OPCODE_MISMATCH);
+}
+
+#[test]
+fn variant_field_type_mismatch() {
+    let module = enum_module(vec![
+        Bytecode::LdTrue,
+        Bytecode::PackVariant(StructDefinitionIndex(0), 0),
+        Bytecode::UnpackVariant(StructDefinitionIndex(0), 0),
+        Bytecode::Pop,
+        Bytecode::Ret,
+    ]);
+    expect_status(&module, StatusCode::PACK_TYPE_MISMATCH_ERROR);
+}
+
+#[test]
+fn test_variant_of_value() {
+    let module = enum_module(vec![
+        Bytecode::PackVariant(StructDefinitionIndex(0), 1),
+        Bytecode::TestVariant(StructDefinitionIndex(0), 1),
+        Bytecode::Pop,
+        Bytecode::Ret,
+    ]);
+    expect_status(&module, StatusCode::TEST_VARIANT_TYPE_MISMATCH_ERROR);
+}
+
+#[test]
+fn invalid_variant_definitions() {
+    let mut module = enum_module(vec![Bytecode::Ret]);
+    module.struct_defs[0].field_information = StructFieldInformation::DeclaredVariants(vec![]);
+    expect_status(&module, StatusCode::ZERO_SIZED_STRUCT);
+
+    let mut module = enum_module(vec![Bytecode::Ret]);
+    if let StructFieldInformation::DeclaredVariants(variants) =
+        &mut module.struct_defs[0].field_information
+    {
+        variants[1].name = variants[0].name;
+    }
+    expect_status(&module, StatusCode::DUPLICATE_ELEMENT);
+}
@@ -0,0 +1,81 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::support::dummy_procedure_module;
+use move_binary_format::file_format::{Bytecode, Signature, SignatureIndex, SignatureToken};
+use move_bytecode_verifier::{limits::LimitsVerifier, CodeUnitVerifier, VerifierConfig};
+use move_core_types::vm_status::StatusCode;
+
+#[test]
+fn too_many_type_nodes() {
+    let mut module = dummy_procedure_module(vec![Bytecode::Ret]);
+    module
+        .signatures
+        .push(Signature(vec![SignatureToken::Vector(Box::new(
+            SignatureToken::Vector(Box::new(SignatureToken::U8)),
+        ))]));
+    let verifier_config = |max_type_nodes| VerifierConfig {
+        max_type_nodes: Some(max_type_nodes),
+        ..VerifierConfig::default()
+    };
+
+    assert!(LimitsVerifier::verify_module(&verifier_config(3), &module).is_ok());
+    let result = LimitsVerifier::verify_module(&verifier_config(2), &module);
+    assert_eq!(
+        result.unwrap_err().major_status(),
+        StatusCode::TOO_MANY_TYPE_NODES
+    );
+}
+
+#[test]
+fn too_many_locals() {
+    let mut module = dummy_procedure_module(vec![Bytecode::Ret]);
+    module
+        .signatures
+        .push(Signature(vec![SignatureToken::U64; 3]));
+    module.function_defs[0].code.as_mut().unwrap().locals =
+        SignatureIndex((module.signatures.len() - 1) as u16);
+    let verifier_config = |max_locals| VerifierConfig {
+        max_locals: Some(max_locals),
+        ..VerifierConfig::default()
+    };
+
+    assert!(LimitsVerifier::verify_module(&verifier_config(3), &module).is_ok());
+    let result = LimitsVerifier::verify_module(&verifier_config(2), &module);
+    assert_eq!(
+        result.unwrap_err().major_status(),
+        StatusCode::TOO_MANY_LOCALS
+    );
+}
+
+#[test]
+fn too_many_basic_blocks() {
+    let module = dummy_procedure_module(vec![Bytecode::Branch(1), Bytecode::Ret]);
+    let verifier_config = |max_basic_blocks| VerifierConfig {
+        max_basic_blocks: Some(max_basic_blocks),
+        ..VerifierConfig::default()
+    };
+
+    assert!(CodeUnitVerifier::verify_module(&verifier_config(2), &module).is_ok());
+    let result = CodeUnitVerifier::verify_module(&verifier_config(1), &module);
+    assert_eq!(
+        result.unwrap_err().major_status(),
+        StatusCode::TOO_MANY_BASIC_BLOCKS
+    );
+}
+
+#[test]
+fn program_too_complex() {
+    let module = dummy_procedure_module(vec![Bytecode::Branch(0)]);
+    let verifier_config = |max_meter_units| VerifierConfig {
+        max_per_fun_meter_units: Some(max_meter_units),
+        ..VerifierConfig::default()
+    };
+
+    assert!(CodeUnitVerifier::verify_module(&verifier_config(1000), &module).is_ok());
+    let result = CodeUnitVerifier::verify_module(&verifier_config(5), &module);
+    assert_eq!(
+        result.unwrap_err().major_status(),
+        StatusCode::PROGRAM_TOO_COMPLEX
+    );
+}
@@ -9,7 +9,7 @@ use move_core_types::vm_status::StatusCode;
 #[test]
 fn one_pop_no_push() {
     let module = dummy_procedure_module(vec![Bytecode::Pop, Bytecode::Ret]);
-    let result = CodeUnitVerifier::verify_module(&module);
+    let result = CodeUnitVerifier::verify_module(&Default::default(), &module);
     assert_eq!(
         result.unwrap_err().major_status(),
         StatusCode::NEGATIVE_STACK_SIZE_WITHIN_BLOCK
@@ -20,7 +20,7 @@ fn one_pop_no_push() {
 fn one_pop_one_push() {
     // Height: 0 + (-1 + 1) = 0 would have passed original usage verifier
     let module = dummy_procedure_module(vec![Bytecode::ReadRef, Bytecode::Ret]);
-    let result = CodeUnitVerifier::verify_module(&module);
+    let result = CodeUnitVerifier::verify_module(&Default::default(), &module);
     assert_eq!(
         result.unwrap_err().major_status(),
         StatusCode::NEGATIVE_STACK_SIZE_WITHIN_BLOCK
@@ -31,7 +31,7 @@ fn one_pop_one_push() {
 fn two_pop_one_push() {
     // Height: 0 + 1 + (-2 + 1) = 0 would have passed original usage verifier
     let module = dummy_procedure_module(vec![Bytecode::LdU64(0), Bytecode::Add, Bytecode::Ret]);
-    let result = CodeUnitVerifier::verify_module(&module);
+    let result = CodeUnitVerifier::verify_module(&Default::default(), &module);
     assert_eq!(
         result.unwrap_err().major_status(),
         StatusCode::NEGATIVE_STACK_SIZE_WITHIN_BLOCK
@@ -41,7 +41,7 @@ fn two_pop_one_push() {
 #[test]
 fn two_pop_no_push() {
     let module = dummy_procedure_module(vec![Bytecode::WriteRef, Bytecode::Ret]);
-    let result = CodeUnitVerifier::verify_module(&module);
+    let result = CodeUnitVerifier::verify_module(&Default::default(), &module);
     assert_eq!(
         result.unwrap_err().major_status(),
         StatusCode::NEGATIVE_STACK_SIZE_WITHIN_BLOCK
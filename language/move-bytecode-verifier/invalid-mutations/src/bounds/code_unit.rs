@@ -296,6 +296,60 @@ impl<'a> ApplyCodeUnitBoundsContext<'a> {
                         StructDefInstantiationIndex,
                         UnpackGeneric
                     ),
+                    PackVariant(_, variant) => new_bytecode!(
+                        struct_defs_len,
+                        current_fdef,
+                        bytecode_idx,
+                        offset,
+                        StructDefinitionIndex,
+                        PackVariant,
+                        variant
+                    ),
+                    PackVariantGeneric(_, variant) => new_bytecode!(
+                        struct_inst_len,
+                        current_fdef,
+                        bytecode_idx,
+                        offset,
+                        StructDefInstantiationIndex,
+                        PackVariantGeneric,
+                        variant
+                    ),
+                    UnpackVariant(_, variant) => new_bytecode!(
+                        struct_defs_len,
+                        current_fdef,
+                        bytecode_idx,
+                        offset,
+                        StructDefinitionIndex,
+                        UnpackVariant,
+                        variant
+                    ),
+                    UnpackVariantGeneric(_, variant) => new_bytecode!(
+                        struct_inst_len,
+                        current_fdef,
+                        bytecode_idx,
+                        offset,
+                        StructDefInstantiationIndex,
+                        UnpackVariantGeneric,
+                        variant
+                    ),
+                    TestVariant(_, variant) => new_bytecode!(
+                        struct_defs_len,
+                        current_fdef,
+                        bytecode_idx,
+                        offset,
+                        StructDefinitionIndex,
+                        TestVariant,
+                        variant
+                    ),
+                    TestVariantGeneric(_, variant) => new_bytecode!(
+                        struct_inst_len,
+                        current_fdef,
+                        bytecode_idx,
+                        offset,
+                        StructDefInstantiationIndex,
+                        TestVariantGeneric,
+                        variant
+                    ),
                     Exists(_) => struct_bytecode!(
                         struct_defs_len,
                         current_fdef,
@@ -477,8 +531,9 @@ impl<'a> ApplyCodeUnitBoundsContext<'a> {
 
                     // List out the other options explicitly so there's a compile error if a new
                     // bytecode gets added.
-                    FreezeRef | Pop | Ret | LdU8(_) | LdU64(_) | LdU128(_) | CastU8 | CastU64
-                    | CastU128 | LdTrue | LdFalse | ReadRef | WriteRef | Add | Sub | Mul | Mod
+                    FreezeRef | Pop | Ret | LdU8(_) | LdU16(_) | LdU32(_) | LdU64(_)
+                    | LdU128(_) | LdU256(_) | CastU8 | CastU16 | CastU32 | CastU64 | CastU128
+                    | CastU256 | LdTrue | LdFalse | ReadRef | WriteRef | Add | Sub | Mul | Mod
                     | Div | BitOr | BitAnd | Xor | Shl | Shr | Or | And | Not | Eq | Neq | Lt
                     | Gt | Le | Ge | Abort | Nop => {
                         panic!("Bytecode has no internal index: {:?}", code[bytecode_idx])
@@ -508,6 +563,12 @@ fn is_interesting(bytecode: &Bytecode) -> bool {
         | PackGeneric(_)
         | Unpack(_)
         | UnpackGeneric(_)
+        | PackVariant(..)
+        | PackVariantGeneric(..)
+        | UnpackVariant(..)
+        | UnpackVariantGeneric(..)
+        | TestVariant(..)
+        | TestVariantGeneric(..)
         | Exists(_)
         | ExistsGeneric(_)
         | MutBorrowGlobal(_)
@@ -537,8 +598,9 @@ fn is_interesting(bytecode: &Bytecode) -> bool {
 
         // List out the other options explicitly so there's a compile error if a new
         // bytecode gets added.
-        FreezeRef | Pop | Ret | LdU8(_) | LdU64(_) | LdU128(_) | CastU8 | CastU64 | CastU128
-        | LdTrue | LdFalse | ReadRef | WriteRef | Add | Sub | Mul | Mod | Div | BitOr | BitAnd
-        | Xor | Shl | Shr | Or | And | Not | Eq | Neq | Lt | Gt | Le | Ge | Abort | Nop => false,
+        FreezeRef | Pop | Ret | LdU8(_) | LdU16(_) | LdU32(_) | LdU64(_) | LdU128(_)
+        | LdU256(_) | CastU8 | CastU16 | CastU32 | CastU64 | CastU128 | CastU256 | LdTrue
+        | LdFalse | ReadRef | WriteRef | Add | Sub | Mul | Mod | Div | BitOr | BitAnd | Xor
+        | Shl | Shr | Or | And | Not | Eq | Neq | Lt | Gt | Le | Ge | Abort | Nop => false,
     }
 }
@@ -5,16 +5,16 @@
 //! distinct values. Successful verification implies that an index in vector can be used to
 //! uniquely name the entry at that index. Additionally, the checker also verifies the
 //! following:
-//! - struct and field definitions are consistent
+//! - struct, variant and field definitions are consistent
 //! - the handles in struct and function definitions point to the self module index
 //! - all struct and function handles pointing to the self module index have a definition
 use move_binary_format::{
     access::{ModuleAccess, ScriptAccess},
     errors::{verification_error, Location, PartialVMResult, VMResult},
     file_format::{
-        CompiledModule, CompiledScript, Constant, FunctionHandle, FunctionHandleIndex,
-        FunctionInstantiation, ModuleHandle, Signature, StructFieldInformation, StructHandle,
-        StructHandleIndex, TableIndex,
+        CompiledModule, CompiledScript, Constant, FieldDefinition, FunctionHandle,
+        FunctionHandleIndex, FunctionInstantiation, ModuleHandle, Signature,
+        StructFieldInformation, StructHandle, StructHandleIndex, TableIndex, VariantDefinition,
     },
     IndexKind,
 };
@@ -195,6 +195,40 @@ impl<'a> DuplicationChecker<'a> {
         Ok(())
     }
 
+    fn check_fields(fields: &[FieldDefinition]) -> PartialVMResult<()> {
+        if let Some(idx) = Self::first_duplicate_element(fields.iter().map(|x| x.name)) {
+            return Err(verification_error(
+                StatusCode::DUPLICATE_ELEMENT,
+                IndexKind::FieldDefinition,
+                idx,
+            ));
+        }
+        Ok(())
+    }
+
+    // An enum has at least one variant, variant names are unique, and field names are unique
+    // within each variant. Unlike a struct, a variant may have no fields.
+    fn check_variants(struct_idx: usize, variants: &[VariantDefinition]) -> PartialVMResult<()> {
+        if variants.is_empty() {
+            return Err(verification_error(
+                StatusCode::ZERO_SIZED_STRUCT,
+                IndexKind::StructDefinition,
+                struct_idx as TableIndex,
+            ));
+        }
+        if let Some(idx) = Self::first_duplicate_element(variants.iter().map(|x| x.name)) {
+            return Err(verification_error(
+                StatusCode::DUPLICATE_ELEMENT,
+                IndexKind::VariantDefinition,
+                idx,
+            ));
+        }
+        for variant in variants {
+            Self::check_fields(&variant.fields)?;
+        }
+        Ok(())
+    }
+
     fn check_struct_definitions(&self) -> PartialVMResult<()> {
         // StructDefinition - contained StructHandle defines uniqueness
         if let Some(idx) =
@@ -211,6 +245,10 @@ impl<'a> DuplicationChecker<'a> {
             let fields = match &struct_def.field_information {
                 StructFieldInformation::Native => continue,
                 StructFieldInformation::Declared(fields) => fields,
+                StructFieldInformation::DeclaredVariants(variants) => {
+                    Self::check_variants(struct_idx, variants)?;
+                    continue;
+                }
             };
             if fields.is_empty() {
                 return Err(verification_error(
@@ -219,13 +257,7 @@ impl<'a> DuplicationChecker<'a> {
                     struct_idx as TableIndex,
                 ));
             }
-            if let Some(idx) = Self::first_duplicate_element(fields.iter().map(|x| x.name)) {
-                return Err(verification_error(
-                    StatusCode::DUPLICATE_ELEMENT,
-                    IndexKind::FieldDefinition,
-                    idx,
-                ));
-            }
+            Self::check_fields(fields)?;
         }
         // Check that each struct definition is pointing to the self module
         if let Some(idx) = self.module.struct_defs().iter().position(|x| {
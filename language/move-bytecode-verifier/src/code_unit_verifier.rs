@@ -4,73 +4,110 @@
 //! This module implements the checker for verifying correctness of function bodies.
 //! The overall verification is split between stack_usage_verifier.rs and
 //! abstract_interpreter.rs. CodeUnitVerifier simply orchestrates calls into these two files.
+//! It also enforces the limits of the `VerifierConfig` on the number of basic blocks and back
+//! edges, and meters the type safety check and the abstract interpretation.
 use crate::{
-    acquires_list_verifier::AcquiresVerifier, control_flow, locals_safety, reference_safety,
-    stack_usage_verifier::StackUsageVerifier, type_safety,
+    acquires_list_verifier::AcquiresVerifier, control_flow, locals_safety, meter::Meter,
+    reference_safety, stack_usage_verifier::StackUsageVerifier, type_safety,
+    verifier::VerifierConfig,
 };
 use move_binary_format::{
     access::ModuleAccess,
     binary_views::{BinaryIndexedView, FunctionView},
-    errors::{Location, PartialVMResult, VMResult},
+    control_flow_graph::ControlFlowGraph,
+    errors::{Location, PartialVMError, PartialVMResult, VMResult},
     file_format::{
         CompiledModule, CompiledScript, FunctionDefinition, FunctionDefinitionIndex,
         IdentifierIndex, TableIndex,
     },
     IndexKind,
 };
+use move_core_types::vm_status::StatusCode;
 use std::collections::HashMap;
 
 pub struct CodeUnitVerifier<'a> {
+    verifier_config: &'a VerifierConfig,
     resolver: BinaryIndexedView<'a>,
     function_view: FunctionView<'a>,
     name_def_map: HashMap<IdentifierIndex, FunctionDefinitionIndex>,
 }
 
 impl<'a> CodeUnitVerifier<'a> {
-    pub fn verify_module(module: &'a CompiledModule) -> VMResult<()> {
-        Self::verify_module_impl(module).map_err(|e| e.finish(Location::Module(module.self_id())))
+    pub fn verify_module(
+        verifier_config: &'a VerifierConfig,
+        module: &'a CompiledModule,
+    ) -> VMResult<()> {
+        Self::verify_module_impl(verifier_config, module)
+            .map_err(|e| e.finish(Location::Module(module.self_id())))
     }
 
-    fn verify_module_impl(module: &'a CompiledModule) -> PartialVMResult<()> {
+    fn verify_module_impl(
+        verifier_config: &'a VerifierConfig,
+        module: &'a CompiledModule,
+    ) -> PartialVMResult<()> {
+        let mut meter = Meter::new(verifier_config);
+        let mut total_back_edges = 0;
         for (idx, function_definition) in module.function_defs().iter().enumerate() {
             let index = FunctionDefinitionIndex(idx as TableIndex);
-            Self::verify_function(index, function_definition, module)
-                .map_err(|err| err.at_index(IndexKind::FunctionDefinition, index.0))?
+            let num_back_edges = Self::verify_function(
+                verifier_config,
+                index,
+                function_definition,
+                module,
+                &mut meter,
+            )
+            .map_err(|err| err.at_index(IndexKind::FunctionDefinition, index.0))?;
+            total_back_edges += num_back_edges;
+        }
+        if let Some(max_back_edges) = verifier_config.max_back_edges_per_module {
+            if total_back_edges > max_back_edges {
+                return Err(PartialVMError::new(StatusCode::TOO_MANY_BACK_EDGES));
+            }
         }
         Ok(())
     }
 
-    pub fn verify_script(module: &'a CompiledScript) -> VMResult<()> {
-        Self::verify_script_impl(module).map_err(|e| e.finish(Location::Script))
+    pub fn verify_script(
+        verifier_config: &'a VerifierConfig,
+        module: &'a CompiledScript,
+    ) -> VMResult<()> {
+        Self::verify_script_impl(verifier_config, module).map_err(|e| e.finish(Location::Script))
     }
 
-    fn verify_script_impl(script: &'a CompiledScript) -> PartialVMResult<()> {
+    fn verify_script_impl(
+        verifier_config: &'a VerifierConfig,
+        script: &'a CompiledScript,
+    ) -> PartialVMResult<()> {
         // create `FunctionView` and `BinaryIndexedView`
-        control_flow::verify(None, &script.code)?;
+        control_flow::verify(verifier_config, None, &script.code)?;
         let function_view = FunctionView::script(script);
         let resolver = BinaryIndexedView::Script(script);
         //verify
         let code_unit_verifier = CodeUnitVerifier {
+            verifier_config,
             resolver,
             function_view,
             name_def_map: HashMap::new(),
         };
-        code_unit_verifier.verify_common()
+        code_unit_verifier.verify_common(&mut Meter::new(verifier_config))
     }
 
+    // Returns the number of back edges of the function, counted towards the limit of the module
     fn verify_function(
+        verifier_config: &'a VerifierConfig,
         index: FunctionDefinitionIndex,
         function_definition: &'a FunctionDefinition,
         module: &'a CompiledModule,
-    ) -> PartialVMResult<()> {
+        meter: &mut Meter,
+    ) -> PartialVMResult<usize> {
         // nothing to verify for native function
         let code = match &function_definition.code {
             Some(code) => code,
-            None => return Ok(()),
+            None => return Ok(0),
         };
         // create `FunctionView` and `BinaryIndexedView`
         let function_handle = module.function_handle_at(function_definition.function);
-        control_flow::verify(Some(index), code)?;
+        control_flow::verify(verifier_config, Some(index), code)?;
         let function_view = FunctionView::function(module, index, code, function_handle);
         let resolver = BinaryIndexedView::Module(module);
         let mut name_def_map = HashMap::new();
@@ -80,18 +117,36 @@ impl<'a> CodeUnitVerifier<'a> {
         }
         // verify
         let code_unit_verifier = CodeUnitVerifier {
+            verifier_config,
             resolver,
             function_view,
             name_def_map,
         };
-        code_unit_verifier.verify_common()?;
-        AcquiresVerifier::verify(module, index, function_definition)
+        meter.enter_function();
+        code_unit_verifier.verify_common(meter)?;
+        AcquiresVerifier::verify(module, index, function_definition)?;
+        Ok(control_flow::num_back_edges(code))
     }
 
-    fn verify_common(&self) -> PartialVMResult<()> {
+    fn verify_common(&self, meter: &mut Meter) -> PartialVMResult<()> {
+        self.verify_basic_blocks()?;
         StackUsageVerifier::verify(&self.resolver, &self.function_view)?;
-        type_safety::verify(&self.resolver, &self.function_view)?;
-        locals_safety::verify(&self.resolver, &self.function_view)?;
-        reference_safety::verify(&self.resolver, &self.function_view, &self.name_def_map)
+        type_safety::verify(&self.resolver, &self.function_view, meter)?;
+        locals_safety::verify(&self.resolver, &self.function_view, meter)?;
+        reference_safety::verify(
+            &self.resolver,
+            &self.function_view,
+            &self.name_def_map,
+            meter,
+        )
+    }
+
+    fn verify_basic_blocks(&self) -> PartialVMResult<()> {
+        if let Some(max_basic_blocks) = self.verifier_config.max_basic_blocks {
+            if self.function_view.cfg().num_blocks() as usize > max_basic_blocks {
+                return Err(PartialVMError::new(StatusCode::TOO_MANY_BASIC_BLOCKS));
+            }
+        }
+        Ok(())
     }
 }
@@ -6,6 +6,9 @@
 //! - All forward jumps do not enter into the middle of a loop
 //! - All "breaks" (forward, loop-exiting jumps) go to the "end" of the loop
 //! - All "continues" (back jumps in a loop) are only to the current loop
+//! It also enforces the limits of the `VerifierConfig` on the loop depth and the number of back
+//! edges.
+use crate::verifier::VerifierConfig;
 use move_binary_format::{
     errors::{PartialVMError, PartialVMResult},
     file_format::{Bytecode, CodeOffset, CodeUnit, FunctionDefinitionIndex},
@@ -14,6 +17,7 @@ use move_core_types::vm_status::StatusCode;
 use std::{collections::HashSet, convert::TryInto};
 
 pub fn verify(
+    verifier_config: &VerifierConfig,
     current_function_opt: Option<FunctionDefinitionIndex>,
     code: &CodeUnit,
 ) -> PartialVMResult<()> {
@@ -35,7 +39,28 @@ pub fn verify(
         code: &code.code,
     };
     let labels = instruction_labels(context);
-    check_jumps(context, labels)
+    check_jumps(context, &labels)?;
+    check_limits(verifier_config, context, &labels)
+}
+
+/// The number of back jumps ("continues") in `code`.
+pub(crate) fn num_back_edges(code: &CodeUnit) -> usize {
+    back_edges(&code.code).count()
+}
+
+// The offsets of the back jumps in `code`
+fn back_edges(code: &[Bytecode]) -> impl Iterator<Item = CodeOffset> + '_ {
+    code.iter().enumerate().filter_map(|(i, instr)| {
+        let i = i as CodeOffset;
+        match instr {
+            Bytecode::Branch(target) | Bytecode::BrTrue(target) | Bytecode::BrFalse(target)
+                if is_back_edge(i, *target) =>
+            {
+                Some(i)
+            }
+            _ => None,
+        }
+    })
 }
 
 #[derive(Clone, Copy)]
@@ -94,13 +119,33 @@ fn instruction_labels(context: &ControlFlowVerifier) -> Vec<Label> {
 //   - All forward jumps do not enter into the middle of a loop
 //   - All "breaks" go to the "end" of the loop
 //   - All back jumps are only to the current loop
-fn check_jumps(context: &ControlFlowVerifier, labels: Vec<Label>) -> PartialVMResult<()> {
+fn check_jumps(context: &ControlFlowVerifier, labels: &[Label]) -> PartialVMResult<()> {
     // All back jumps are only to the current loop
-    check_continues(context, &labels)?;
+    check_continues(context, labels)?;
     // All "breaks" go to the "end" of the loop
-    check_breaks(context, &labels)?;
+    check_breaks(context, labels)?;
     // All forward jumps do not enter into the middle of a loop
-    check_no_loop_splits(context, &labels)
+    check_no_loop_splits(context, labels)
+}
+
+// Only called after jumps are verified, so we can assume that loops are well nested
+fn check_limits(
+    verifier_config: &VerifierConfig,
+    context: &ControlFlowVerifier,
+    labels: &[Label],
+) -> PartialVMResult<()> {
+    if let Some(max_loop_depth) = verifier_config.max_loop_depth {
+        let loop_depth = count_loop_depth(labels);
+        if let Some(offset) = loop_depth.iter().position(|depth| *depth > max_loop_depth) {
+            return Err(context.error(StatusCode::LOOP_MAX_DEPTH_REACHED, offset as CodeOffset));
+        }
+    }
+    if let Some(max_back_edges) = verifier_config.max_back_edges_per_function {
+        if let Some(offset) = back_edges(context.code).nth(max_back_edges) {
+            return Err(context.error(StatusCode::TOO_MANY_BACK_EDGES, offset));
+        }
+    }
+    Ok(())
 }
 
 fn check_code<
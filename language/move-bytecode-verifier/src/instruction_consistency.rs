@@ -11,7 +11,8 @@ use move_binary_format::{
     errors::{Location, PartialVMError, PartialVMResult, VMResult},
     file_format::{
         Bytecode, CodeOffset, CodeUnit, CompiledModule, CompiledScript, FieldHandleIndex,
-        FunctionDefinitionIndex, FunctionHandleIndex, StructDefinitionIndex, TableIndex,
+        FunctionDefinitionIndex, FunctionHandleIndex, StructDefinitionIndex,
+        StructFieldInformation, TableIndex,
     },
 };
 use move_core_types::vm_status::StatusCode;
@@ -83,16 +84,25 @@ impl<'a> InstructionConsistency<'a> {
                     self.check_function_op(offset, func_inst.handle, /* generic */ true)?;
                 }
                 Pack(idx) => {
-                    self.check_type_op(offset, *idx, /* generic */ false)?;
+                    self.check_struct_op(offset, *idx, /* generic */ false)?;
                 }
                 PackGeneric(idx) => {
                     let struct_inst = self.resolver.struct_instantiation_at(*idx)?;
-                    self.check_type_op(offset, struct_inst.def, /* generic */ true)?;
+                    self.check_struct_op(offset, struct_inst.def, /* generic */ true)?;
                 }
                 Unpack(idx) => {
-                    self.check_type_op(offset, *idx, /* generic */ false)?;
+                    self.check_struct_op(offset, *idx, /* generic */ false)?;
                 }
                 UnpackGeneric(idx) => {
+                    let struct_inst = self.resolver.struct_instantiation_at(*idx)?;
+                    self.check_struct_op(offset, struct_inst.def, /* generic */ true)?;
+                }
+                PackVariant(idx, _) | UnpackVariant(idx, _) | TestVariant(idx, _) => {
+                    self.check_type_op(offset, *idx, /* generic */ false)?;
+                }
+                PackVariantGeneric(idx, _)
+                | UnpackVariantGeneric(idx, _)
+                | TestVariantGeneric(idx, _) => {
                     let struct_inst = self.resolver.struct_instantiation_at(*idx)?;
                     self.check_type_op(offset, struct_inst.def, /* generic */ true)?;
                 }
@@ -134,10 +144,11 @@ impl<'a> InstructionConsistency<'a> {
 
                 // List out the other options explicitly so there's a compile error if a new
                 // bytecode gets added.
-                FreezeRef | Pop | Ret | Branch(_) | BrTrue(_) | BrFalse(_) | LdU8(_) | LdU64(_)
-                | LdU128(_) | LdConst(_) | CastU8 | CastU64 | CastU128 | LdTrue | LdFalse
-                | ReadRef | WriteRef | Add | Sub | Mul | Mod | Div | BitOr | BitAnd | Xor | Shl
-                | Shr | Or | And | Not | Eq | Neq | Lt | Gt | Le | Ge | CopyLoc(_) | MoveLoc(_)
+                FreezeRef | Pop | Ret | Branch(_) | BrTrue(_) | BrFalse(_) | LdU8(_) | LdU16(_)
+                | LdU32(_) | LdU64(_) | LdU128(_) | LdU256(_) | LdConst(_) | CastU8 | CastU16
+                | CastU32 | CastU64 | CastU128 | CastU256 | LdTrue | LdFalse | ReadRef
+                | WriteRef | Add | Sub | Mul | Mod | Div | BitOr | BitAnd | Xor | Shl | Shr
+                | Or | And | Not | Eq | Neq | Lt | Gt | Le | Ge | CopyLoc(_) | MoveLoc(_)
                 | StLoc(_) | MutBorrowLoc(_) | ImmBorrowLoc(_) | VecPack(..) | VecLen(_)
                 | VecImmBorrow(_) | VecMutBorrow(_) | VecPushBack(_) | VecPopBack(_)
                 | VecUnpack(..) | VecSwap(_) | Abort | Nop => (),
@@ -159,7 +170,7 @@ impl<'a> InstructionConsistency<'a> {
         generic: bool,
     ) -> PartialVMResult<()> {
         let field_handle = self.resolver.field_handle_at(field_handle_index)?;
-        self.check_type_op(offset, field_handle.owner, generic)
+        self.check_struct_op(offset, field_handle.owner, generic)
     }
 
     fn current_function(&self) -> FunctionDefinitionIndex {
@@ -183,6 +194,22 @@ impl<'a> InstructionConsistency<'a> {
         Ok(())
     }
 
+    /// Like `check_type_op`, for instructions operating on the fields of a struct, which do not
+    /// apply to enums.
+    fn check_struct_op(
+        &self,
+        offset: usize,
+        struct_def_index: StructDefinitionIndex,
+        generic: bool,
+    ) -> PartialVMResult<()> {
+        let struct_def = self.resolver.struct_def_at(struct_def_index)?;
+        if let StructFieldInformation::DeclaredVariants(_) = &struct_def.field_information {
+            return Err(PartialVMError::new(StatusCode::STRUCT_ENUM_OPCODE_MISMATCH)
+                .at_code_offset(self.current_function(), offset as CodeOffset));
+        }
+        self.check_type_op(offset, struct_def_index, generic)
+    }
+
     fn check_function_op(
         &self,
         offset: usize,
@@ -0,0 +1,96 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module implements a checker for the limits of a `VerifierConfig` which do not depend on
+//! the code of functions: the number of type nodes in signatures and the number of locals.
+//! The limits on the code itself are checked by the control flow and code unit verifiers.
+use crate::verifier::VerifierConfig;
+use move_binary_format::{
+    access::{ModuleAccess, ScriptAccess},
+    errors::{Location, PartialVMError, PartialVMResult, VMResult},
+    file_format::{CompiledModule, CompiledScript, Signature, SignatureToken, TableIndex},
+    IndexKind,
+};
+use move_core_types::vm_status::StatusCode;
+
+pub struct LimitsVerifier<'a> {
+    config: &'a VerifierConfig,
+}
+
+impl<'a> LimitsVerifier<'a> {
+    pub fn verify_module(config: &'a VerifierConfig, module: &CompiledModule) -> VMResult<()> {
+        Self::verify_module_impl(config, module)
+            .map_err(|e| e.finish(Location::Module(module.self_id())))
+    }
+
+    fn verify_module_impl(
+        config: &'a VerifierConfig,
+        module: &CompiledModule,
+    ) -> PartialVMResult<()> {
+        let limits = Self { config };
+        limits.verify_signature_pool(module.signatures())?;
+        for (idx, struct_def) in module.struct_defs().iter().enumerate() {
+            for field in struct_def.all_fields() {
+                limits
+                    .verify_type_nodes(&field.signature.0)
+                    .map_err(|err| err.at_index(IndexKind::StructDefinition, idx as TableIndex))?;
+            }
+        }
+        for (idx, function_def) in module.function_defs().iter().enumerate() {
+            let code = match &function_def.code {
+                Some(code) => code,
+                None => continue,
+            };
+            let function_handle = module.function_handle_at(function_def.function);
+            let num_locals = module.signature_at(function_handle.parameters).len()
+                + module.signature_at(code.locals).len();
+            limits
+                .verify_num_locals(num_locals)
+                .map_err(|err| err.at_index(IndexKind::FunctionDefinition, idx as TableIndex))?;
+        }
+        Ok(())
+    }
+
+    pub fn verify_script(config: &'a VerifierConfig, script: &CompiledScript) -> VMResult<()> {
+        Self::verify_script_impl(config, script).map_err(|e| e.finish(Location::Script))
+    }
+
+    fn verify_script_impl(
+        config: &'a VerifierConfig,
+        script: &CompiledScript,
+    ) -> PartialVMResult<()> {
+        let limits = Self { config };
+        limits.verify_signature_pool(script.signatures())?;
+        let num_locals = script.signature_at(script.parameters).len()
+            + script.signature_at(script.code().locals).len();
+        limits.verify_num_locals(num_locals)
+    }
+
+    fn verify_signature_pool(&self, signatures: &[Signature]) -> PartialVMResult<()> {
+        for (idx, signature) in signatures.iter().enumerate() {
+            for ty in &signature.0 {
+                self.verify_type_nodes(ty)
+                    .map_err(|err| err.at_index(IndexKind::Signature, idx as TableIndex))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn verify_type_nodes(&self, ty: &SignatureToken) -> PartialVMResult<()> {
+        if let Some(max_type_nodes) = self.config.max_type_nodes {
+            if ty.preorder_traversal().count() > max_type_nodes {
+                return Err(PartialVMError::new(StatusCode::TOO_MANY_TYPE_NODES));
+            }
+        }
+        Ok(())
+    }
+
+    fn verify_num_locals(&self, num_locals: usize) -> PartialVMResult<()> {
+        if let Some(max_locals) = self.config.max_locals {
+            if num_locals > max_locals {
+                return Err(PartialVMError::new(StatusCode::TOO_MANY_LOCALS));
+            }
+        }
+        Ok(())
+    }
+}
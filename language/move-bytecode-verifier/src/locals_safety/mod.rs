@@ -7,7 +7,10 @@
 
 mod abstract_state;
 
-use crate::absint::{AbstractInterpreter, BlockInvariant, BlockPostcondition, TransferFunctions};
+use crate::{
+    absint::{AbstractInterpreter, BlockInvariant, BlockPostcondition, TransferFunctions},
+    meter::Meter,
+};
 use abstract_state::{AbstractState, LocalState};
 use move_binary_format::{
     binary_views::{BinaryIndexedView, FunctionView},
@@ -19,9 +22,10 @@ use move_core_types::vm_status::StatusCode;
 pub(crate) fn verify<'a>(
     resolver: &BinaryIndexedView,
     function_view: &'a FunctionView<'a>,
+    meter: &mut Meter,
 ) -> PartialVMResult<()> {
     let initial_state = AbstractState::new(resolver, function_view)?;
-    let inv_map = LocalsSafetyAnalysis().analyze_function(initial_state, function_view);
+    let inv_map = LocalsSafetyAnalysis().analyze_function(initial_state, function_view, meter)?;
     // Report all the join failures
     for (_block_id, BlockInvariant { post, .. }) in inv_map {
         match post {
@@ -101,8 +105,11 @@ fn execute_inner(
         | Bytecode::ImmBorrowField(_)
         | Bytecode::ImmBorrowFieldGeneric(_)
         | Bytecode::LdU8(_)
+        | Bytecode::LdU16(_)
+        | Bytecode::LdU32(_)
         | Bytecode::LdU64(_)
         | Bytecode::LdU128(_)
+        | Bytecode::LdU256(_)
         | Bytecode::LdConst(_)
         | Bytecode::LdTrue
         | Bytecode::LdFalse
@@ -112,11 +119,20 @@ fn execute_inner(
         | Bytecode::PackGeneric(_)
         | Bytecode::Unpack(_)
         | Bytecode::UnpackGeneric(_)
+        | Bytecode::PackVariant(..)
+        | Bytecode::PackVariantGeneric(..)
+        | Bytecode::UnpackVariant(..)
+        | Bytecode::UnpackVariantGeneric(..)
+        | Bytecode::TestVariant(..)
+        | Bytecode::TestVariantGeneric(..)
         | Bytecode::ReadRef
         | Bytecode::WriteRef
         | Bytecode::CastU8
+        | Bytecode::CastU16
+        | Bytecode::CastU32
         | Bytecode::CastU64
         | Bytecode::CastU128
+        | Bytecode::CastU256
         | Bytecode::Add
         | Bytecode::Sub
         | Bytecode::Mul
@@ -0,0 +1,56 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module implements the meter bounding the work done by the type safety check and the
+//! abstract interpretation of function bodies. Units are charged for every instruction checked or
+//! interpreted and every join of abstract states, so the number of units consumed grows with the
+//! number of times the analyses revisit loops, which is not bounded by the size of the code alone.
+//! The type safety check visits every instruction once, but is metered so that the budget covers
+//! all passes over the code.
+use crate::verifier::VerifierConfig;
+use move_binary_format::errors::{PartialVMError, PartialVMResult};
+use move_core_types::vm_status::StatusCode;
+
+/// The units charged for interpreting one instruction.
+pub(crate) const INSTRUCTION_UNITS: u128 = 1;
+/// The units charged for joining the abstract state at the start of a block.
+pub(crate) const JOIN_UNITS: u128 = 10;
+
+/// Counts the units consumed verifying a module or script against the budgets of a
+/// `VerifierConfig`.
+pub struct Meter {
+    max_per_fun_units: Option<u128>,
+    max_per_mod_units: Option<u128>,
+    fun_units: u128,
+    mod_units: u128,
+}
+
+impl Meter {
+    pub fn new(config: &VerifierConfig) -> Self {
+        Self {
+            max_per_fun_units: config.max_per_fun_meter_units,
+            max_per_mod_units: config.max_per_mod_meter_units,
+            fun_units: 0,
+            mod_units: 0,
+        }
+    }
+
+    /// Start metering a new function, resetting the units consumed by the function.
+    pub fn enter_function(&mut self) {
+        self.fun_units = 0;
+    }
+
+    /// Charge `units` to the current function and the module, failing with `PROGRAM_TOO_COMPLEX`
+    /// if either exceeds its budget.
+    pub fn add(&mut self, units: u128) -> PartialVMResult<()> {
+        self.fun_units = self.fun_units.saturating_add(units);
+        self.mod_units = self.mod_units.saturating_add(units);
+        let exceeds = |consumed: u128, max: Option<u128>| max.map_or(false, |max| consumed > max);
+        if exceeds(self.fun_units, self.max_per_fun_units)
+            || exceeds(self.mod_units, self.max_per_mod_units)
+        {
+            return Err(PartialVMError::new(StatusCode::PROGRAM_TOO_COMPLEX));
+        }
+        Ok(())
+    }
+}
@@ -9,14 +9,17 @@
 
 mod abstract_state;
 
-use crate::absint::{AbstractInterpreter, BlockInvariant, BlockPostcondition, TransferFunctions};
+use crate::{
+    absint::{AbstractInterpreter, BlockInvariant, BlockPostcondition, TransferFunctions},
+    meter::Meter,
+};
 use abstract_state::{AbstractState, AbstractValue};
 use move_binary_format::{
     binary_views::{BinaryIndexedView, FunctionView},
     errors::{PartialVMError, PartialVMResult},
     file_format::{
         Bytecode, CodeOffset, FunctionDefinitionIndex, FunctionHandle, IdentifierIndex,
-        SignatureIndex, SignatureToken, StructDefinition, StructFieldInformation,
+        SignatureIndex, SignatureToken, StructDefinition, StructFieldInformation, VariantIndex,
     },
 };
 use move_core_types::vm_status::StatusCode;
@@ -48,11 +51,12 @@ pub(crate) fn verify<'a>(
     resolver: &'a BinaryIndexedView<'a>,
     function_view: &FunctionView,
     name_def_map: &'a HashMap<IdentifierIndex, FunctionDefinitionIndex>,
+    meter: &mut Meter,
 ) -> PartialVMResult<()> {
     let initial_state = AbstractState::new(function_view);
 
     let mut verifier = ReferenceSafetyAnalysis::new(resolver, function_view, name_def_map);
-    let inv_map = verifier.analyze_function(initial_state, function_view);
+    let inv_map = verifier.analyze_function(initial_state, function_view, meter)?;
     // Report all the join failures
     for (_block_id, BlockInvariant { post, .. }) in inv_map {
         match post {
@@ -100,23 +104,29 @@ fn call(
 
 fn num_fields(struct_def: &StructDefinition) -> usize {
     match &struct_def.field_information {
-        StructFieldInformation::Native => 0,
+        StructFieldInformation::Native | StructFieldInformation::DeclaredVariants(_) => 0,
         StructFieldInformation::Declared(fields) => fields.len(),
     }
 }
 
-fn pack(verifier: &mut ReferenceSafetyAnalysis, struct_def: &StructDefinition) {
-    for _ in 0..num_fields(struct_def) {
+fn num_variant_fields(struct_def: &StructDefinition, variant: VariantIndex) -> usize {
+    struct_def
+        .variant(variant)
+        .map_or(0, |variant| variant.fields.len())
+}
+
+fn pack(verifier: &mut ReferenceSafetyAnalysis, num_fields: usize) {
+    for _ in 0..num_fields {
         assert!(verifier.stack.pop().unwrap().is_value())
     }
     // TODO maybe call state.value_for
     verifier.stack.push(AbstractValue::NonReference)
 }
 
-fn unpack(verifier: &mut ReferenceSafetyAnalysis, struct_def: &StructDefinition) {
+fn unpack(verifier: &mut ReferenceSafetyAnalysis, num_fields: usize) {
     assert!(verifier.stack.pop().unwrap().is_value());
     // TODO maybe call state.value_for
-    for _ in 0..num_fields(struct_def) {
+    for _ in 0..num_fields {
         verifier.stack.push(AbstractValue::NonReference)
     }
 }
@@ -267,8 +277,11 @@ fn execute_inner(
         Bytecode::Branch(_)
         | Bytecode::Nop
         | Bytecode::CastU8
+        | Bytecode::CastU16
+        | Bytecode::CastU32
         | Bytecode::CastU64
         | Bytecode::CastU128
+        | Bytecode::CastU256
         | Bytecode::Not
         | Bytecode::Exists(_)
         | Bytecode::ExistsGeneric(_) => (),
@@ -287,8 +300,11 @@ fn execute_inner(
             verifier.stack.push(state.value_for(&SignatureToken::Bool))
         }
         Bytecode::LdU8(_) => verifier.stack.push(state.value_for(&SignatureToken::U8)),
+        Bytecode::LdU16(_) => verifier.stack.push(state.value_for(&SignatureToken::U16)),
+        Bytecode::LdU32(_) => verifier.stack.push(state.value_for(&SignatureToken::U32)),
         Bytecode::LdU64(_) => verifier.stack.push(state.value_for(&SignatureToken::U64)),
         Bytecode::LdU128(_) => verifier.stack.push(state.value_for(&SignatureToken::U128)),
+        Bytecode::LdU256(_) => verifier.stack.push(state.value_for(&SignatureToken::U256)),
         Bytecode::LdConst(idx) => {
             let signature = &verifier.resolver.constant_at(*idx).type_;
             verifier.stack.push(state.value_for(signature))
@@ -318,21 +334,45 @@ fn execute_inner(
 
         Bytecode::Pack(idx) => {
             let struct_def = verifier.resolver.struct_def_at(*idx)?;
-            pack(verifier, struct_def)
+            pack(verifier, num_fields(struct_def))
         }
         Bytecode::PackGeneric(idx) => {
             let struct_inst = verifier.resolver.struct_instantiation_at(*idx)?;
             let struct_def = verifier.resolver.struct_def_at(struct_inst.def)?;
-            pack(verifier, struct_def)
+            pack(verifier, num_fields(struct_def))
         }
         Bytecode::Unpack(idx) => {
             let struct_def = verifier.resolver.struct_def_at(*idx)?;
-            unpack(verifier, struct_def)
+            unpack(verifier, num_fields(struct_def))
         }
         Bytecode::UnpackGeneric(idx) => {
             let struct_inst = verifier.resolver.struct_instantiation_at(*idx)?;
             let struct_def = verifier.resolver.struct_def_at(struct_inst.def)?;
-            unpack(verifier, struct_def)
+            unpack(verifier, num_fields(struct_def))
+        }
+        Bytecode::PackVariant(idx, variant) => {
+            let struct_def = verifier.resolver.struct_def_at(*idx)?;
+            pack(verifier, num_variant_fields(struct_def, *variant))
+        }
+        Bytecode::PackVariantGeneric(idx, variant) => {
+            let struct_inst = verifier.resolver.struct_instantiation_at(*idx)?;
+            let struct_def = verifier.resolver.struct_def_at(struct_inst.def)?;
+            pack(verifier, num_variant_fields(struct_def, *variant))
+        }
+        Bytecode::UnpackVariant(idx, variant) => {
+            let struct_def = verifier.resolver.struct_def_at(*idx)?;
+            unpack(verifier, num_variant_fields(struct_def, *variant))
+        }
+        Bytecode::UnpackVariantGeneric(idx, variant) => {
+            let struct_inst = verifier.resolver.struct_instantiation_at(*idx)?;
+            let struct_def = verifier.resolver.struct_def_at(struct_inst.def)?;
+            unpack(verifier, num_variant_fields(struct_def, *variant))
+        }
+        // Testing the variant reads the tag of the value behind the reference
+        Bytecode::TestVariant(..) | Bytecode::TestVariantGeneric(..) => {
+            let id = verifier.stack.pop().unwrap().ref_id().unwrap();
+            let value = state.read_ref(offset, id)?;
+            verifier.stack.push(value)
         }
 
         Bytecode::VecPack(idx, num) => {
@@ -82,16 +82,16 @@ impl<'a> SignatureChecker<'a> {
 
     fn verify_fields(&self, struct_defs: &[StructDefinition]) -> PartialVMResult<()> {
         for (struct_def_idx, struct_def) in struct_defs.iter().enumerate() {
-            let fields = match &struct_def.field_information {
-                StructFieldInformation::Native => continue,
-                StructFieldInformation::Declared(fields) => fields,
-            };
+            if struct_def.field_information == StructFieldInformation::Native {
+                continue;
+            }
             let struct_handle = self.resolver.struct_handle_at(struct_def.struct_handle);
             let err_handler = |err: PartialVMError, idx| {
                 err.at_index(IndexKind::FieldDefinition, idx as TableIndex)
                     .at_index(IndexKind::StructDefinition, struct_def_idx as TableIndex)
             };
-            for (field_offset, field_def) in fields.iter().enumerate() {
+            // the fields of an enum are numbered across all of its variants
+            for (field_offset, field_def) in struct_def.all_fields().enumerate() {
                 self.check_signature_token(&field_def.signature.0)
                     .map_err(|err| err_handler(err, field_offset))?;
                 let type_param_constraints: Vec<_> =
@@ -148,6 +148,9 @@ impl<'a> SignatureChecker<'a> {
                 }
                 PackGeneric(idx)
                 | UnpackGeneric(idx)
+                | PackVariantGeneric(idx, _)
+                | UnpackVariantGeneric(idx, _)
+                | TestVariantGeneric(idx, _)
                 | ExistsGeneric(idx)
                 | MoveFromGeneric(idx)
                 | MoveToGeneric(idx)
@@ -200,13 +203,15 @@ impl<'a> SignatureChecker<'a> {
 
                 // List out the other options explicitly so there's a compile error if a new
                 // bytecode gets added.
-                Pop | Ret | Branch(_) | BrTrue(_) | BrFalse(_) | LdU8(_) | LdU64(_) | LdU128(_)
-                | LdConst(_) | CastU8 | CastU64 | CastU128 | LdTrue | LdFalse | Call(_)
-                | Pack(_) | Unpack(_) | ReadRef | WriteRef | FreezeRef | Add | Sub | Mul | Mod
-                | Div | BitOr | BitAnd | Xor | Shl | Shr | Or | And | Not | Eq | Neq | Lt | Gt
-                | Le | Ge | CopyLoc(_) | MoveLoc(_) | StLoc(_) | MutBorrowLoc(_)
-                | ImmBorrowLoc(_) | MutBorrowField(_) | ImmBorrowField(_) | MutBorrowGlobal(_)
-                | ImmBorrowGlobal(_) | Exists(_) | MoveTo(_) | MoveFrom(_) | Abort | Nop => Ok(()),
+                Pop | Ret | Branch(_) | BrTrue(_) | BrFalse(_) | LdU8(_) | LdU16(_) | LdU32(_)
+                | LdU64(_) | LdU128(_) | LdU256(_) | LdConst(_) | CastU8 | CastU16 | CastU32
+                | CastU64 | CastU128 | CastU256 | LdTrue | LdFalse | Call(_) | Pack(_)
+                | Unpack(_) | ReadRef | WriteRef | FreezeRef | Add | Sub | Mul | Mod | Div
+                | BitOr | BitAnd | Xor | Shl | Shr | Or | And | Not | Eq | Neq | Lt | Gt | Le
+                | Ge | CopyLoc(_) | MoveLoc(_) | StLoc(_) | MutBorrowLoc(_) | ImmBorrowLoc(_)
+                | MutBorrowField(_) | ImmBorrowField(_) | MutBorrowGlobal(_)
+                | ImmBorrowGlobal(_) | Exists(_) | MoveTo(_) | MoveFrom(_) | PackVariant(..)
+                | UnpackVariant(..) | TestVariant(..) | Abort | Nop => Ok(()),
             };
             result.map_err(|err| {
                 err.append_message_with_separator(' ', format!("at offset {} ", offset))
@@ -250,8 +255,11 @@ impl<'a> SignatureChecker<'a> {
             | SignatureToken::MutableReference(_)
             | SignatureToken::Bool
             | SignatureToken::U8
+            | SignatureToken::U16
+            | SignatureToken::U32
             | SignatureToken::U64
             | SignatureToken::U128
+            | SignatureToken::U256
             | SignatureToken::Address
             | SignatureToken::Signer => {}
         }
@@ -286,7 +294,8 @@ impl<'a> SignatureChecker<'a> {
     fn check_signature_token(&self, ty: &SignatureToken) -> PartialVMResult<()> {
         use SignatureToken::*;
         match ty {
-            U8 | U64 | U128 | Bool | Address | Signer | Struct(_) | TypeParameter(_) => Ok(()),
+            U8 | U16 | U32 | U64 | U128 | U256 | Bool | Address | Signer | Struct(_)
+            | TypeParameter(_) => Ok(()),
             Reference(_) | MutableReference(_) => {
                 // TODO: Prop tests expect us to NOT check the inner types.
                 // Revisit this once we rework prop tests.
@@ -12,7 +12,10 @@ use move_binary_format::{
     binary_views::{BinaryIndexedView, FunctionView},
     control_flow_graph::{BlockId, ControlFlowGraph},
     errors::{PartialVMError, PartialVMResult},
-    file_format::{Bytecode, CodeUnit, FunctionDefinitionIndex, Signature, StructFieldInformation},
+    file_format::{
+        Bytecode, CodeUnit, FunctionDefinitionIndex, Signature, StructDefinition,
+        StructFieldInformation, VariantIndex,
+    },
 };
 use move_core_types::vm_status::StatusCode;
 
@@ -83,8 +86,11 @@ impl<'a> StackUsageVerifier<'a> {
 
             // Instructions that push, but don't pop
             Bytecode::LdU8(_)
+            | Bytecode::LdU16(_)
+            | Bytecode::LdU32(_)
             | Bytecode::LdU64(_)
             | Bytecode::LdU128(_)
+            | Bytecode::LdU256(_)
             | Bytecode::LdTrue
             | Bytecode::LdFalse
             | Bytecode::LdConst(_)
@@ -110,8 +116,11 @@ impl<'a> StackUsageVerifier<'a> {
             | Bytecode::MoveFrom(_)
             | Bytecode::MoveFromGeneric(_)
             | Bytecode::CastU8
+            | Bytecode::CastU16
+            | Bytecode::CastU32
             | Bytecode::CastU64
             | Bytecode::CastU128
+            | Bytecode::CastU256
             | Bytecode::VecLen(_)
             | Bytecode::VecPopBack(_) => (1, 1),
 
@@ -179,8 +188,10 @@ impl<'a> StackUsageVerifier<'a> {
             Bytecode::Pack(idx) => {
                 let struct_definition = self.resolver.struct_def_at(*idx)?;
                 let field_count = match &struct_definition.field_information {
-                    // 'Native' here is an error that will be caught by the bytecode verifier later
-                    StructFieldInformation::Native => 0,
+                    // 'Native' or an enum here is an error that will be caught by the bytecode
+                    // verifier later
+                    StructFieldInformation::Native
+                    | StructFieldInformation::DeclaredVariants(_) => 0,
                     StructFieldInformation::Declared(fields) => fields.len(),
                 };
                 (field_count as u64, 1)
@@ -189,8 +200,10 @@ impl<'a> StackUsageVerifier<'a> {
                 let struct_inst = self.resolver.struct_instantiation_at(*idx)?;
                 let struct_definition = self.resolver.struct_def_at(struct_inst.def)?;
                 let field_count = match &struct_definition.field_information {
-                    // 'Native' here is an error that will be caught by the bytecode verifier later
-                    StructFieldInformation::Native => 0,
+                    // 'Native' or an enum here is an error that will be caught by the bytecode
+                    // verifier later
+                    StructFieldInformation::Native
+                    | StructFieldInformation::DeclaredVariants(_) => 0,
                     StructFieldInformation::Declared(fields) => fields.len(),
                 };
                 (field_count as u64, 1)
@@ -200,8 +213,10 @@ impl<'a> StackUsageVerifier<'a> {
             Bytecode::Unpack(idx) => {
                 let struct_definition = self.resolver.struct_def_at(*idx)?;
                 let field_count = match &struct_definition.field_information {
-                    // 'Native' here is an error that will be caught by the bytecode verifier later
-                    StructFieldInformation::Native => 0,
+                    // 'Native' or an enum here is an error that will be caught by the bytecode
+                    // verifier later
+                    StructFieldInformation::Native
+                    | StructFieldInformation::DeclaredVariants(_) => 0,
                     StructFieldInformation::Declared(fields) => fields.len(),
                 };
                 (1, field_count as u64)
@@ -210,12 +225,39 @@ impl<'a> StackUsageVerifier<'a> {
                 let struct_inst = self.resolver.struct_instantiation_at(*idx)?;
                 let struct_definition = self.resolver.struct_def_at(struct_inst.def)?;
                 let field_count = match &struct_definition.field_information {
-                    // 'Native' here is an error that will be caught by the bytecode verifier later
-                    StructFieldInformation::Native => 0,
+                    // 'Native' or an enum here is an error that will be caught by the bytecode
+                    // verifier later
+                    StructFieldInformation::Native
+                    | StructFieldInformation::DeclaredVariants(_) => 0,
                     StructFieldInformation::Declared(fields) => fields.len(),
                 };
                 (1, field_count as u64)
             }
+
+            // PackVariant performs `num_fields` of the variant pops and one push
+            Bytecode::PackVariant(idx, variant) => {
+                let struct_definition = self.resolver.struct_def_at(*idx)?;
+                (variant_field_count(struct_definition, *variant), 1)
+            }
+            Bytecode::PackVariantGeneric(idx, variant) => {
+                let struct_inst = self.resolver.struct_instantiation_at(*idx)?;
+                let struct_definition = self.resolver.struct_def_at(struct_inst.def)?;
+                (variant_field_count(struct_definition, *variant), 1)
+            }
+
+            // UnpackVariant performs one pop and `num_fields` of the variant pushes
+            Bytecode::UnpackVariant(idx, variant) => {
+                let struct_definition = self.resolver.struct_def_at(*idx)?;
+                (1, variant_field_count(struct_definition, *variant))
+            }
+            Bytecode::UnpackVariantGeneric(idx, variant) => {
+                let struct_inst = self.resolver.struct_instantiation_at(*idx)?;
+                let struct_definition = self.resolver.struct_def_at(struct_inst.def)?;
+                (1, variant_field_count(struct_definition, *variant))
+            }
+
+            // TestVariant pops the reference and pushes the result
+            Bytecode::TestVariant(_, _) | Bytecode::TestVariantGeneric(_, _) => (1, 1),
         })
     }
 
@@ -223,3 +265,10 @@ impl<'a> StackUsageVerifier<'a> {
         self.current_function.unwrap_or(FunctionDefinitionIndex(0))
     }
 }
+
+fn variant_field_count(struct_definition: &StructDefinition, variant: VariantIndex) -> u64 {
+    // A missing variant here has been caught by the bounds checker
+    struct_definition
+        .variant(variant)
+        .map_or(0, |variant| variant.fields.len() as u64)
+}
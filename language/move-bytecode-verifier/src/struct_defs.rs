@@ -11,7 +11,6 @@ use move_binary_format::{
         CompiledModule, SignatureToken, StructDefinitionIndex, StructHandleIndex, TableIndex,
     },
     internals::ModuleIndex,
-    views::StructDefinitionView,
     IndexKind,
 };
 use move_core_types::vm_status::StatusCode;
@@ -87,11 +86,9 @@ impl<'a> StructDefGraphBuilder<'a> {
         idx: StructDefinitionIndex,
     ) -> PartialVMResult<()> {
         let struct_def = self.module.struct_def_at(idx);
-        let struct_def = StructDefinitionView::new(self.module, struct_def);
-        // The fields iterator is an option in the case of native structs. Flatten makes an empty
-        // iterator for that case
-        for field in struct_def.fields().into_iter().flatten() {
-            self.add_signature_token(neighbors, idx, field.signature_token())?
+        // A native struct has no fields, and an enum contains the fields of all of its variants
+        for field in struct_def.all_fields() {
+            self.add_signature_token(neighbors, idx, &field.signature.0)?
         }
         Ok(())
     }
@@ -104,7 +101,16 @@ impl<'a> StructDefGraphBuilder<'a> {
     ) -> PartialVMResult<()> {
         use SignatureToken as T;
         Ok(match token {
-            T::Bool | T::U8 | T::U64 | T::U128 | T::Address | T::Signer | T::TypeParameter(_) => (),
+            T::Bool
+            | T::U8
+            | T::U16
+            | T::U32
+            | T::U64
+            | T::U128
+            | T::U256
+            | T::Address
+            | T::Signer
+            | T::TypeParameter(_) => (),
             T::Reference(_) | T::MutableReference(_) => {
                 return Err(
                     PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
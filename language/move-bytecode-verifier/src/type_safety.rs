@@ -4,6 +4,7 @@
 //! This module defines the transfer functions for verifying type safety of a procedure body.
 //! It does not utilize control flow, but does check each block independently
 
+use crate::meter::{Meter, INSTRUCTION_UNITS};
 use move_binary_format::{
     binary_views::{BinaryIndexedView, FunctionView},
     control_flow_graph::ControlFlowGraph,
@@ -12,6 +13,7 @@ use move_binary_format::{
         AbilitySet, Bytecode, CodeOffset, FieldHandleIndex, FunctionDefinitionIndex,
         FunctionHandle, LocalIndex, Signature, SignatureToken, SignatureToken as ST,
         StructDefinition, StructDefinitionIndex, StructFieldInformation, StructHandleIndex,
+        VariantIndex,
     },
 };
 use move_core_types::vm_status::StatusCode;
@@ -81,10 +83,13 @@ impl<'a> TypeSafetyChecker<'a> {
 pub(crate) fn verify<'a>(
     resolver: &'a BinaryIndexedView<'a>,
     function_view: &'a FunctionView<'a>,
+    meter: &mut Meter,
 ) -> PartialVMResult<()> {
     let verifier = &mut TypeSafetyChecker::new(resolver, function_view);
 
     for block_id in function_view.cfg().blocks() {
+        let block_size = function_view.cfg().instr_indexes(block_id).count() as u128;
+        meter.add(INSTRUCTION_UNITS * block_size)?;
         for offset in function_view.cfg().instr_indexes(block_id) {
             let instr = &verifier.function_view.code().code[offset as usize];
             verify_instr(verifier, instr, offset)?
@@ -120,7 +125,7 @@ fn borrow_field(
     }
 
     let field_def = match &struct_def.field_information {
-        StructFieldInformation::Native => {
+        StructFieldInformation::Native | StructFieldInformation::DeclaredVariants(_) => {
             return Err(verifier.error(StatusCode::BORROWFIELD_BAD_FIELD_ERROR, offset));
         }
         StructFieldInformation::Declared(fields) => {
@@ -207,35 +212,42 @@ fn call(
     Ok(())
 }
 
+// The fields of a struct if `variant` is `None`, or the fields of the variant `variant` of an enum
 fn type_fields_signature(
     verifier: &mut TypeSafetyChecker,
     offset: CodeOffset,
     struct_def: &StructDefinition,
+    variant: Option<VariantIndex>,
     type_args: &Signature,
 ) -> PartialVMResult<Signature> {
-    match &struct_def.field_information {
-        StructFieldInformation::Native => {
+    let fields = match (&struct_def.field_information, variant) {
+        (StructFieldInformation::Declared(fields), None) => fields,
+        (StructFieldInformation::DeclaredVariants(variants), Some(variant))
+            if (variant as usize) < variants.len() =>
+        {
+            &variants[variant as usize].fields
+        }
+        _ => {
             // TODO: this is more of "unreachable"
-            Err(verifier.error(StatusCode::PACK_TYPE_MISMATCH_ERROR, offset))
-        }
-        StructFieldInformation::Declared(fields) => {
-            let mut field_sig = vec![];
-            for field_def in fields.iter() {
-                field_sig.push(instantiate(&field_def.signature.0, type_args));
-            }
-            Ok(Signature(field_sig))
+            return Err(verifier.error(StatusCode::PACK_TYPE_MISMATCH_ERROR, offset));
         }
+    };
+    let mut field_sig = vec![];
+    for field_def in fields.iter() {
+        field_sig.push(instantiate(&field_def.signature.0, type_args));
     }
+    Ok(Signature(field_sig))
 }
 
 fn pack(
     verifier: &mut TypeSafetyChecker,
     offset: CodeOffset,
     struct_def: &StructDefinition,
+    variant: Option<VariantIndex>,
     type_args: &Signature,
 ) -> PartialVMResult<()> {
     let struct_type = materialize_type(struct_def.struct_handle, type_args);
-    let field_sig = type_fields_signature(verifier, offset, struct_def, type_args)?;
+    let field_sig = type_fields_signature(verifier, offset, struct_def, variant, type_args)?;
     for sig in field_sig.0.iter().rev() {
         let arg = verifier.stack.pop().unwrap();
         if &arg != sig {
@@ -251,6 +263,7 @@ fn unpack(
     verifier: &mut TypeSafetyChecker,
     offset: CodeOffset,
     struct_def: &StructDefinition,
+    variant: Option<VariantIndex>,
     type_args: &Signature,
 ) -> PartialVMResult<()> {
     let struct_type = materialize_type(struct_def.struct_handle, type_args);
@@ -262,13 +275,31 @@ fn unpack(
         return Err(verifier.error(StatusCode::UNPACK_TYPE_MISMATCH_ERROR, offset));
     }
 
-    let field_sig = type_fields_signature(verifier, offset, struct_def, type_args)?;
+    let field_sig = type_fields_signature(verifier, offset, struct_def, variant, type_args)?;
     for sig in field_sig.0 {
         verifier.stack.push(sig)
     }
     Ok(())
 }
 
+fn test_variant(
+    verifier: &mut TypeSafetyChecker,
+    offset: CodeOffset,
+    struct_def: &StructDefinition,
+    type_args: &Signature,
+) -> PartialVMResult<()> {
+    let struct_type = materialize_type(struct_def.struct_handle, type_args);
+
+    // The operand is a reference to a value of the enum, and is consumed.
+    let operand = verifier.stack.pop().unwrap();
+    match operand {
+        ST::Reference(inner) | ST::MutableReference(inner) if struct_type == *inner => (),
+        _ => return Err(verifier.error(StatusCode::TEST_VARIANT_TYPE_MISMATCH_ERROR, offset)),
+    }
+    verifier.stack.push(ST::Bool);
+    Ok(())
+}
+
 fn exists(
     verifier: &mut TypeSafetyChecker,
     offset: CodeOffset,
@@ -465,6 +496,14 @@ fn verify_instr(
             verifier.stack.push(ST::U8);
         }
 
+        Bytecode::LdU16(_) => {
+            verifier.stack.push(ST::U16);
+        }
+
+        Bytecode::LdU32(_) => {
+            verifier.stack.push(ST::U32);
+        }
+
         Bytecode::LdU64(_) => {
             verifier.stack.push(ST::U64);
         }
@@ -473,6 +512,10 @@ fn verify_instr(
             verifier.stack.push(ST::U128);
         }
 
+        Bytecode::LdU256(_) => {
+            verifier.stack.push(ST::U256);
+        }
+
         Bytecode::LdConst(idx) => {
             let signature = verifier.resolver.constant_at(*idx).type_.clone();
             verifier.stack.push(signature);
@@ -517,26 +560,88 @@ fn verify_instr(
 
         Bytecode::Pack(idx) => {
             let struct_definition = verifier.resolver.struct_def_at(*idx)?;
-            pack(verifier, offset, struct_definition, &Signature(vec![]))?
+            pack(
+                verifier,
+                offset,
+                struct_definition,
+                None,
+                &Signature(vec![]),
+            )?
         }
 
         Bytecode::PackGeneric(idx) => {
             let struct_inst = verifier.resolver.struct_instantiation_at(*idx)?;
             let struct_def = verifier.resolver.struct_def_at(struct_inst.def)?;
             let type_args = verifier.resolver.signature_at(struct_inst.type_parameters);
-            pack(verifier, offset, struct_def, type_args)?
+            pack(verifier, offset, struct_def, None, type_args)?
         }
 
         Bytecode::Unpack(idx) => {
             let struct_definition = verifier.resolver.struct_def_at(*idx)?;
-            unpack(verifier, offset, struct_definition, &Signature(vec![]))?
+            unpack(
+                verifier,
+                offset,
+                struct_definition,
+                None,
+                &Signature(vec![]),
+            )?
         }
 
         Bytecode::UnpackGeneric(idx) => {
             let struct_inst = verifier.resolver.struct_instantiation_at(*idx)?;
             let struct_def = verifier.resolver.struct_def_at(struct_inst.def)?;
             let type_args = verifier.resolver.signature_at(struct_inst.type_parameters);
-            unpack(verifier, offset, struct_def, type_args)?
+            unpack(verifier, offset, struct_def, None, type_args)?
+        }
+
+        Bytecode::PackVariant(idx, variant) => {
+            let struct_definition = verifier.resolver.struct_def_at(*idx)?;
+            let type_args = &Signature(vec![]);
+            pack(
+                verifier,
+                offset,
+                struct_definition,
+                Some(*variant),
+                type_args,
+            )?
+        }
+
+        Bytecode::PackVariantGeneric(idx, variant) => {
+            let struct_inst = verifier.resolver.struct_instantiation_at(*idx)?;
+            let struct_def = verifier.resolver.struct_def_at(struct_inst.def)?;
+            let type_args = verifier.resolver.signature_at(struct_inst.type_parameters);
+            pack(verifier, offset, struct_def, Some(*variant), type_args)?
+        }
+
+        Bytecode::UnpackVariant(idx, variant) => {
+            let struct_definition = verifier.resolver.struct_def_at(*idx)?;
+            let type_args = &Signature(vec![]);
+            unpack(
+                verifier,
+                offset,
+                struct_definition,
+                Some(*variant),
+                type_args,
+            )?
+        }
+
+        Bytecode::UnpackVariantGeneric(idx, variant) => {
+            let struct_inst = verifier.resolver.struct_instantiation_at(*idx)?;
+            let struct_def = verifier.resolver.struct_def_at(struct_inst.def)?;
+            let type_args = verifier.resolver.signature_at(struct_inst.type_parameters);
+            unpack(verifier, offset, struct_def, Some(*variant), type_args)?
+        }
+
+        Bytecode::TestVariant(idx, _) => {
+            let struct_definition = verifier.resolver.struct_def_at(*idx)?;
+            test_variant(verifier, offset, struct_definition, &Signature(vec![]))?
+        }
+
+        Bytecode::TestVariantGeneric(idx, _) => {
+            let struct_inst = verifier.resolver.struct_instantiation_at(*idx)?;
+            let struct_def = verifier.resolver.struct_def_at(struct_inst.def)?;
+            let type_args = verifier.resolver.signature_at(struct_inst.type_parameters);
+            test_variant(verifier, offset, struct_def, type_args)?
         }
 
         Bytecode::ReadRef => {
@@ -581,6 +686,20 @@ fn verify_instr(
             }
             verifier.stack.push(ST::U8);
         }
+        Bytecode::CastU16 => {
+            let operand = verifier.stack.pop().unwrap();
+            if !operand.is_integer() {
+                return Err(verifier.error(StatusCode::INTEGER_OP_TYPE_MISMATCH_ERROR, offset));
+            }
+            verifier.stack.push(ST::U16);
+        }
+        Bytecode::CastU32 => {
+            let operand = verifier.stack.pop().unwrap();
+            if !operand.is_integer() {
+                return Err(verifier.error(StatusCode::INTEGER_OP_TYPE_MISMATCH_ERROR, offset));
+            }
+            verifier.stack.push(ST::U32);
+        }
         Bytecode::CastU64 => {
             let operand = verifier.stack.pop().unwrap();
             if !operand.is_integer() {
@@ -595,6 +714,13 @@ fn verify_instr(
             }
             verifier.stack.push(ST::U128);
         }
+        Bytecode::CastU256 => {
+            let operand = verifier.stack.pop().unwrap();
+            if !operand.is_integer() {
+                return Err(verifier.error(StatusCode::INTEGER_OP_TYPE_MISMATCH_ERROR, offset));
+            }
+            verifier.stack.push(ST::U256);
+        }
 
         Bytecode::Add
         | Bytecode::Sub
@@ -818,8 +944,11 @@ fn instantiate(token: &SignatureToken, subst: &Signature) -> SignatureToken {
     match token {
         Bool => Bool,
         U8 => U8,
+        U16 => U16,
+        U32 => U32,
         U64 => U64,
         U128 => U128,
+        U256 => U256,
         Address => Address,
         Signer => Signer,
         Vector(ty) => Vector(Box::new(instantiate(ty, subst))),
@@ -6,8 +6,9 @@ use crate::{
     ability_field_requirements, check_duplication::DuplicationChecker,
     code_unit_verifier::CodeUnitVerifier, constants, friends,
     instantiation_loops::InstantiationLoopChecker, instruction_consistency::InstructionConsistency,
-    script_signature, script_signature::no_additional_script_signature_checks,
-    signature::SignatureChecker, struct_defs::RecursiveStructDefChecker,
+    limits::LimitsVerifier, script_signature,
+    script_signature::no_additional_script_signature_checks, signature::SignatureChecker,
+    struct_defs::RecursiveStructDefChecker,
 };
 use move_binary_format::{
     check_bounds::BoundsChecker,
@@ -15,6 +16,31 @@ use move_binary_format::{
     file_format::{CompiledModule, CompiledScript},
 };
 
+/// Limits on the modules and scripts the verifier accepts, bounding the work verification does.
+///
+/// A limit of `None` means no limit. Exceeding a limit fails verification with a dedicated
+/// `StatusCode`, so that every verifier configured the same way accepts the same code.
+#[derive(Clone, Debug, Default)]
+pub struct VerifierConfig {
+    /// The maximum number of basic blocks in a function or script.
+    pub max_basic_blocks: Option<usize>,
+    /// The maximum number of locals, including parameters, of a function or script.
+    pub max_locals: Option<usize>,
+    /// The maximum number of type nodes in a signature, e.g. 3 for `vector<vector<u8>>`.
+    pub max_type_nodes: Option<usize>,
+    /// The maximum nesting depth of loops in a function or script.
+    pub max_loop_depth: Option<usize>,
+    /// The maximum number of back edges in a function or script.
+    pub max_back_edges_per_function: Option<usize>,
+    /// The maximum number of back edges in all the functions of a module.
+    pub max_back_edges_per_module: Option<usize>,
+    /// The maximum number of meter units verifying the code of a function or script may consume.
+    pub max_per_fun_meter_units: Option<u128>,
+    /// The maximum number of meter units verifying the code of all the functions of a module may
+    /// consume.
+    pub max_per_mod_meter_units: Option<u128>,
+}
+
 /// Helper for a "canonical" verification of a module.
 ///
 /// Clients that rely on verification should call the proper passes
@@ -26,11 +52,17 @@ use move_binary_format::{
 /// minimize the code locations that need to be updated should a new checker
 /// is introduced.
 pub fn verify_module(module: &CompiledModule) -> VMResult<()> {
+    verify_module_with_config(&VerifierConfig::default(), module)
+}
+
+/// Verify a module as `verify_module` does, enforcing the limits of `config`.
+pub fn verify_module_with_config(config: &VerifierConfig, module: &CompiledModule) -> VMResult<()> {
     BoundsChecker::verify_module(module).map_err(|e| {
         // We can't point the error at the module, because if bounds-checking
         // failed, we cannot safely index into module's handle to itself.
         e.finish(Location::Undefined)
     })?;
+    LimitsVerifier::verify_module(config, module)?;
     DuplicationChecker::verify_module(module)?;
     SignatureChecker::verify_module(module)?;
     InstructionConsistency::verify_module(module)?;
@@ -39,7 +71,7 @@ pub fn verify_module(module: &CompiledModule) -> VMResult<()> {
     ability_field_requirements::verify_module(module)?;
     RecursiveStructDefChecker::verify_module(module)?;
     InstantiationLoopChecker::verify_module(module)?;
-    CodeUnitVerifier::verify_module(module)?;
+    CodeUnitVerifier::verify_module(config, module)?;
     script_signature::verify_module(module, no_additional_script_signature_checks)
 }
 
@@ -54,11 +86,17 @@ pub fn verify_module(module: &CompiledModule) -> VMResult<()> {
 /// minimize the code locations that need to be updated should a new checker
 /// is introduced.
 pub fn verify_script(script: &CompiledScript) -> VMResult<()> {
+    verify_script_with_config(&VerifierConfig::default(), script)
+}
+
+/// Verify a script as `verify_script` does, enforcing the limits of `config`.
+pub fn verify_script_with_config(config: &VerifierConfig, script: &CompiledScript) -> VMResult<()> {
     BoundsChecker::verify_script(script).map_err(|e| e.finish(Location::Script))?;
+    LimitsVerifier::verify_script(config, script)?;
     DuplicationChecker::verify_script(script)?;
     SignatureChecker::verify_script(script)?;
     InstructionConsistency::verify_script(script)?;
     constants::verify_script(script)?;
-    CodeUnitVerifier::verify_script(script)?;
+    CodeUnitVerifier::verify_script(config, script)?;
     script_signature::verify_script(script, no_additional_script_signature_checks)
 }
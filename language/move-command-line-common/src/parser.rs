@@ -2,7 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use anyhow::{anyhow, bail, Result};
-use move_core_types::account_address::AccountAddress;
+use move_core_types::{
+    account_address::AccountAddress,
+    u256::{U256FromStrError, U256},
+};
 use num_bigint::BigUint;
 use std::{collections::BTreeMap, fmt::Display, iter::Peekable, num::ParseIntError};
 
@@ -133,8 +136,11 @@ impl<'a, I: Iterator<Item = (TypeToken, &'a str)>> Parser<'a, TypeToken, I> {
         let (tok, contents) = self.advance_any()?;
         Ok(match (tok, contents) {
             (TypeToken::Ident, "u8") => ParsedType::U8,
+            (TypeToken::Ident, "u16") => ParsedType::U16,
+            (TypeToken::Ident, "u32") => ParsedType::U32,
             (TypeToken::Ident, "u64") => ParsedType::U64,
             (TypeToken::Ident, "u128") => ParsedType::U128,
+            (TypeToken::Ident, "u256") => ParsedType::U256,
             (TypeToken::Ident, "bool") => ParsedType::Bool,
             (TypeToken::Ident, "address") => ParsedType::Address,
             (TypeToken::Ident, "signer") => ParsedType::Signer,
@@ -189,19 +195,28 @@ impl<'a, I: Iterator<Item = (ValueToken, &'a str)>> Parser<'a, ValueToken, I> {
         let (tok, contents) = self.advance_any()?;
         Ok(match tok {
             ValueToken::Number if !matches!(self.peek_tok(), Some(ValueToken::ColonColon)) => {
-                let (u, _) = parse_u128(contents)?;
+                let (u, _) = parse_u256(contents)?;
                 ParsedValue::InferredNum(u)
             }
             ValueToken::NumberTyped => {
                 if let Some(s) = contents.strip_suffix("u8") {
                     let (u, _) = parse_u8(s)?;
                     ParsedValue::U8(u)
+                } else if let Some(s) = contents.strip_suffix("u16") {
+                    let (u, _) = parse_u16(s)?;
+                    ParsedValue::U16(u)
+                } else if let Some(s) = contents.strip_suffix("u32") {
+                    let (u, _) = parse_u32(s)?;
+                    ParsedValue::U32(u)
                 } else if let Some(s) = contents.strip_suffix("u64") {
                     let (u, _) = parse_u64(s)?;
                     ParsedValue::U64(u)
-                } else {
-                    let (u, _) = parse_u128(contents.strip_suffix("u128").unwrap())?;
+                } else if let Some(s) = contents.strip_suffix("u128") {
+                    let (u, _) = parse_u128(s)?;
                     ParsedValue::U128(u)
+                } else {
+                    let (u, _) = parse_u256(contents.strip_suffix("u256").unwrap())?;
+                    ParsedValue::U256(u)
                 }
             }
             ValueToken::True => ParsedValue::Bool(true),
@@ -329,6 +344,18 @@ pub fn parse_u8(s: &str) -> Result<(u8, NumberFormat), ParseIntError> {
     Ok((u8::from_str_radix(txt, base as u32)?, base))
 }
 
+// Parse a u16 from a decimal or hex encoding
+pub fn parse_u16(s: &str) -> Result<(u16, NumberFormat), ParseIntError> {
+    let (txt, base) = determine_num_text_and_base(s);
+    Ok((u16::from_str_radix(txt, base as u32)?, base))
+}
+
+// Parse a u32 from a decimal or hex encoding
+pub fn parse_u32(s: &str) -> Result<(u32, NumberFormat), ParseIntError> {
+    let (txt, base) = determine_num_text_and_base(s);
+    Ok((u32::from_str_radix(txt, base as u32)?, base))
+}
+
 // Parse a u64 from a decimal or hex encoding
 pub fn parse_u64(s: &str) -> Result<(u64, NumberFormat), ParseIntError> {
     let (txt, base) = determine_num_text_and_base(s);
@@ -341,6 +368,12 @@ pub fn parse_u128(s: &str) -> Result<(u128, NumberFormat), ParseIntError> {
     Ok((u128::from_str_radix(txt, base as u32)?, base))
 }
 
+// Parse a u256 from a decimal or hex encoding
+pub fn parse_u256(s: &str) -> Result<(U256, NumberFormat), U256FromStrError> {
+    let (txt, base) = determine_num_text_and_base(s);
+    Ok((U256::from_str_radix(txt, base as u32)?, base))
+}
+
 // Parse an address from a decimal or hex encoding
 pub fn parse_address_number(s: &str) -> Option<([u8; AccountAddress::LENGTH], NumberFormat)> {
     let (txt, base) = determine_num_text_and_base(s);
@@ -367,7 +400,7 @@ mod tests {
         types::{ParsedStructType, ParsedType},
         values::ParsedValue,
     };
-    use move_core_types::account_address::AccountAddress;
+    use move_core_types::{account_address::AccountAddress, u256::U256};
 
     #[allow(clippy::unreadable_literal)]
     #[test]
@@ -377,18 +410,34 @@ mod tests {
             ("  0u8", V::U8(0)),
             ("0u8", V::U8(0)),
             ("255u8", V::U8(255)),
-            ("0", V::InferredNum(0)),
-            ("0123", V::InferredNum(123)),
-            ("0xFF", V::InferredNum(0xFF)),
+            ("0", V::InferredNum(U256::from(0u8))),
+            ("0123", V::InferredNum(U256::from(123u8))),
+            ("0xFF", V::InferredNum(U256::from(0xFFu8))),
+            ("0u16", V::U16(0)),
+            ("65535u16", V::U16(65535)),
+            ("0u32", V::U32(0)),
+            ("0xFFFFFFFFu32", V::U32(0xFFFFFFFF)),
             ("0u64", V::U64(0)),
             ("0x0u64", V::U64(0)),
-            ("18446744073709551615", V::InferredNum(18446744073709551615)),
+            (
+                "18446744073709551615",
+                V::InferredNum(U256::from(18446744073709551615u64)),
+            ),
             ("18446744073709551615u64", V::U64(18446744073709551615)),
             ("0u128", V::U128(0)),
             (
                 "340282366920938463463374607431768211455u128",
                 V::U128(340282366920938463463374607431768211455),
             ),
+            ("0u256", V::U256(U256::zero())),
+            (
+                "115792089237316195423570985008687907853269984665640564039457584007913129639935u256",
+                V::U256(U256::max_value()),
+            ),
+            (
+                "115792089237316195423570985008687907853269984665640564039457584007913129639935",
+                V::InferredNum(U256::max_value()),
+            ),
             ("true", V::Bool(true)),
             ("false", V::Bool(false)),
             (
@@ -448,6 +497,10 @@ mod tests {
             "256u8",
             "18446744073709551616u64",
             "340282366920938463463374607431768211456u128",
+            "65536u16",
+            "4294967296u32",
+            "115792089237316195423570985008687907853269984665640564039457584007913129639936u256",
+            "115792089237316195423570985008687907853269984665640564039457584007913129639936",
             "0xg",
             "0x00g0",
             "0x",
@@ -479,7 +532,10 @@ mod tests {
     #[test]
     fn test_type_type() {
         for s in &[
+            "u16",
+            "u32",
             "u64",
+            "u256",
             "bool",
             "vector<u8>",
             "vector<vector<u64>>",
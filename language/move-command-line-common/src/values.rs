@@ -9,10 +9,12 @@ use anyhow::bail;
 use move_core_types::{
     account_address::AccountAddress,
     identifier::{self, Identifier},
+    u256::U256,
     value::{MoveStruct, MoveValue},
 };
 use std::{
     collections::BTreeMap,
+    convert::TryFrom,
     fmt::{self, Display},
 };
 
@@ -41,10 +43,13 @@ pub enum ValueToken {
 #[derive(Eq, PartialEq, Debug, Clone)]
 pub enum ParsedValue<Extra: ParsableValue = ()> {
     Address(ParsedAddress),
-    InferredNum(u128),
+    InferredNum(U256),
     U8(u8),
+    U16(u16),
+    U32(u32),
     U64(u64),
     U128(u128),
+    U256(U256),
     Bool(bool),
     Vector(Vec<ParsedValue<Extra>>),
     Struct(
@@ -148,9 +153,10 @@ impl Token for ValueToken {
             let rest = &text[num_text_len..];
             if rest.starts_with("u8") {
                 (ValueToken::NumberTyped, num_text_len + 2)
-            } else if rest.starts_with("u64") {
+            } else if rest.starts_with("u16") || rest.starts_with("u32") || rest.starts_with("u64")
+            {
                 (ValueToken::NumberTyped, num_text_len + 3)
-            } else if rest.starts_with("u128") {
+            } else if rest.starts_with("u128") || rest.starts_with("u256") {
                 (ValueToken::NumberTyped, num_text_len + 4)
             } else {
                 // No typed suffix
@@ -275,12 +281,21 @@ impl<Extra: ParsableValue> ParsedValue<Extra> {
                 a.into_account_address(mapping)?,
             )),
             ParsedValue::U8(u) => Extra::move_value_into_concrete(MoveValue::U8(u)),
+            ParsedValue::U16(u) => Extra::move_value_into_concrete(MoveValue::U16(u)),
+            ParsedValue::U32(u) => Extra::move_value_into_concrete(MoveValue::U32(u)),
             ParsedValue::U64(u) => Extra::move_value_into_concrete(MoveValue::U64(u)),
-            ParsedValue::InferredNum(u) if u <= (u64::MAX as u128) => {
-                Extra::move_value_into_concrete(MoveValue::U64(u as u64))
-            }
-            ParsedValue::InferredNum(u) | ParsedValue::U128(u) => {
-                Extra::move_value_into_concrete(MoveValue::U128(u))
+            ParsedValue::U128(u) => Extra::move_value_into_concrete(MoveValue::U128(u)),
+            ParsedValue::U256(u) => Extra::move_value_into_concrete(MoveValue::U256(u)),
+            ParsedValue::InferredNum(u) => {
+                // Pick the smallest of u64, u128 and u256 which can represent the number
+                let value = if let Ok(u) = u64::try_from(u) {
+                    MoveValue::U64(u)
+                } else if let Ok(u) = u128::try_from(u) {
+                    MoveValue::U128(u)
+                } else {
+                    MoveValue::U256(u)
+                };
+                Extra::move_value_into_concrete(value)
             }
             ParsedValue::Bool(b) => Extra::move_value_into_concrete(MoveValue::Bool(b)),
             ParsedValue::Vector(values) => Extra::concrete_vector(
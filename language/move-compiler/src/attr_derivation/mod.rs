@@ -103,6 +103,7 @@ pub fn new_native_fun(
         loc,
         visibility,
         entry,
+        inline: false,
         signature,
         acquires: vec![],
         name,
@@ -125,6 +126,7 @@ pub fn new_fun(
         loc,
         visibility,
         entry,
+        inline: false,
         signature,
         acquires: vec![],
         name,
@@ -154,7 +154,7 @@ fn lvalue(context: &mut Context, sp!(loc, l_): &LValue, value: Value) {
             let diags = context.borrow_state.assign_local(*loc, v, value);
             context.add_diags(diags)
         }
-        L::Unpack(_, _, fields) => {
+        L::Unpack(_, _, fields) | L::UnpackVariant(_, _, _, fields) => {
             assert!(!value.is_ref());
             fields
                 .iter()
@@ -197,6 +197,12 @@ fn exp(context: &mut Context, parent_e: &Exp) -> Values {
             context.add_diags(errors);
             vec![value]
         }
+        E::TestVariant(_, _, _, e) => {
+            let evalue = assert_single_value(exp(context, e));
+            let (diags, value) = context.borrow_state.test_variant(*eloc, evalue);
+            context.add_diags(diags);
+            vec![value]
+        }
         E::Borrow(mut_, e, f) => {
             let evalue = assert_single_value(exp(context, e));
             let (diags, value) = context.borrow_state.borrow_field(*eloc, *mut_, evalue, f);
@@ -252,7 +258,7 @@ fn exp(context: &mut Context, parent_e: &Exp) -> Values {
         }
 
         E::Unit { .. } => vec![],
-        E::Value(_) | E::Constant(_) | E::Spec(_, _) | E::UnresolvedError => svalue(),
+        E::Value(_) | E::Constant(_, _) | E::Spec(_, _) | E::UnresolvedError => svalue(),
 
         E::Cast(e, _) | E::UnaryExp(_, e) => {
             let v = exp(context, e);
@@ -280,7 +286,7 @@ fn exp(context: &mut Context, parent_e: &Exp) -> Values {
             assert!(!v2.is_ref());
             svalue()
         }
-        E::Pack(_, _, fields) => {
+        E::Pack(_, _, fields) | E::PackVariant(_, _, _, fields) => {
             fields.iter().for_each(|(_, _, e)| {
                 let arg = exp(context, e);
                 assert!(!assert_single_value(arg).is_ref());
@@ -671,6 +671,30 @@ impl BorrowState {
         (diags, Value::NonRef)
     }
 
+    pub fn test_variant(&mut self, loc: Loc, rvalue: Value) -> (Diagnostics, Value) {
+        let id = match rvalue {
+            Value::NonRef => {
+                assert!(
+                    self.prev_had_errors,
+                    "ICE borrow checking failed {:#?}",
+                    loc
+                );
+                return (Diagnostics::new(), Value::NonRef);
+            }
+            Value::Ref(id) => id,
+        };
+
+        let diags = self.readable(
+            loc,
+            ReferenceSafety::MutOwns,
+            || "Invalid test of the variant of an enum.".into(),
+            id,
+            None,
+        );
+        self.release(id);
+        (diags, Value::NonRef)
+    }
+
     pub fn borrow_field(
         &mut self,
         loc: Loc,
@@ -273,7 +273,7 @@ fn unreachable_loc_exp(parent_e: &Exp) -> Option<Loc> {
         E::Unreachable => Some(parent_e.exp.loc),
         E::Unit { .. }
         | E::Value(_)
-        | E::Constant(_)
+        | E::Constant(_, _)
         | E::Spec(_, _)
         | E::UnresolvedError
         | E::BorrowLocal(_, _)
@@ -286,11 +286,14 @@ fn unreachable_loc_exp(parent_e: &Exp) -> Option<Loc> {
         | E::Dereference(e)
         | E::UnaryExp(_, e)
         | E::Borrow(_, e, _)
+        | E::TestVariant(_, _, _, e)
         | E::Cast(e, _) => unreachable_loc_exp(e),
 
         E::BinopExp(e1, _, e2) => unreachable_loc_exp(e1).or_else(|| unreachable_loc_exp(e2)),
 
-        E::Pack(_, _, fields) => fields.iter().find_map(|(_, _, e)| unreachable_loc_exp(e)),
+        E::Pack(_, _, fields) | E::PackVariant(_, _, _, fields) => {
+            fields.iter().find_map(|(_, _, e)| unreachable_loc_exp(e))
+        }
 
         E::ExpList(es) => es.iter().find_map(unreachable_loc_item),
     }
@@ -10,6 +10,7 @@ use crate::{
     naming::ast::{BuiltinTypeName, BuiltinTypeName_},
     parser::ast::{BinOp, BinOp_, UnaryOp, UnaryOp_},
 };
+use move_core_types::u256::U256;
 use move_ir_types::location::*;
 use std::convert::TryFrom;
 
@@ -73,7 +74,7 @@ fn optimize_exp(e: &mut Exp) -> bool {
         //************************************
         E::Unit { .. }
         | E::Value(_)
-        | E::Constant(_)
+        | E::Constant(_, _)
         | E::UnresolvedError
         | E::Spec(_, _)
         | E::BorrowLocal(_, _)
@@ -82,9 +83,13 @@ fn optimize_exp(e: &mut Exp) -> bool {
         | E::Unreachable => false,
 
         E::ModuleCall(mcall) => optimize_exp(&mut mcall.arguments),
-        E::Builtin(_, e) | E::Freeze(e) | E::Dereference(e) | E::Borrow(_, e, _) => optimize_exp(e),
+        E::Builtin(_, e)
+        | E::Freeze(e)
+        | E::Dereference(e)
+        | E::Borrow(_, e, _)
+        | E::TestVariant(_, _, _, e) => optimize_exp(e),
 
-        E::Pack(_, _, fields) => fields
+        E::Pack(_, _, fields) | E::PackVariant(_, _, _, fields) => fields
             .iter_mut()
             .map(|(_, _, e)| optimize_exp(e))
             .any(|changed| changed),
@@ -195,7 +200,9 @@ fn is_valid_const_type_name(sp!(_, tn_): &TypeName) -> bool {
 fn is_valid_const_builtin_type(sp!(_, bt_): &BuiltinTypeName) -> bool {
     use BuiltinTypeName_ as N;
     match bt_ {
-        N::Address | N::U8 | N::U64 | N::U128 | N::Vector | N::Bool => true,
+        N::Address | N::U8 | N::U16 | N::U32 | N::U64 | N::U128 | N::U256 | N::Vector | N::Bool => {
+            true
+        }
         N::Signer => false,
     }
 }
@@ -227,47 +234,77 @@ fn fold_binary_op(
         // Checked arith
         //************************************
         (B::Add, V::U8(u1), V::U8(u2)) => V::U8(u1.checked_add(u2)?),
+        (B::Add, V::U16(u1), V::U16(u2)) => V::U16(u1.checked_add(u2)?),
+        (B::Add, V::U32(u1), V::U32(u2)) => V::U32(u1.checked_add(u2)?),
         (B::Add, V::U64(u1), V::U64(u2)) => V::U64(u1.checked_add(u2)?),
         (B::Add, V::U128(u1), V::U128(u2)) => V::U128(u1.checked_add(u2)?),
+        (B::Add, V::U256(u1), V::U256(u2)) => V::U256(u1.checked_add(u2)?),
 
         (B::Sub, V::U8(u1), V::U8(u2)) => V::U8(u1.checked_sub(u2)?),
+        (B::Sub, V::U16(u1), V::U16(u2)) => V::U16(u1.checked_sub(u2)?),
+        (B::Sub, V::U32(u1), V::U32(u2)) => V::U32(u1.checked_sub(u2)?),
         (B::Sub, V::U64(u1), V::U64(u2)) => V::U64(u1.checked_sub(u2)?),
         (B::Sub, V::U128(u1), V::U128(u2)) => V::U128(u1.checked_sub(u2)?),
+        (B::Sub, V::U256(u1), V::U256(u2)) => V::U256(u1.checked_sub(u2)?),
 
         (B::Mul, V::U8(u1), V::U8(u2)) => V::U8(u1.checked_mul(u2)?),
+        (B::Mul, V::U16(u1), V::U16(u2)) => V::U16(u1.checked_mul(u2)?),
+        (B::Mul, V::U32(u1), V::U32(u2)) => V::U32(u1.checked_mul(u2)?),
         (B::Mul, V::U64(u1), V::U64(u2)) => V::U64(u1.checked_mul(u2)?),
         (B::Mul, V::U128(u1), V::U128(u2)) => V::U128(u1.checked_mul(u2)?),
+        (B::Mul, V::U256(u1), V::U256(u2)) => V::U256(u1.checked_mul(u2)?),
 
         (B::Mod, V::U8(u1), V::U8(u2)) => V::U8(u1.checked_rem(u2)?),
+        (B::Mod, V::U16(u1), V::U16(u2)) => V::U16(u1.checked_rem(u2)?),
+        (B::Mod, V::U32(u1), V::U32(u2)) => V::U32(u1.checked_rem(u2)?),
         (B::Mod, V::U64(u1), V::U64(u2)) => V::U64(u1.checked_rem(u2)?),
         (B::Mod, V::U128(u1), V::U128(u2)) => V::U128(u1.checked_rem(u2)?),
+        (B::Mod, V::U256(u1), V::U256(u2)) => V::U256(u1.checked_rem(u2)?),
 
         (B::Div, V::U8(u1), V::U8(u2)) => V::U8(u1.checked_div(u2)?),
+        (B::Div, V::U16(u1), V::U16(u2)) => V::U16(u1.checked_div(u2)?),
+        (B::Div, V::U32(u1), V::U32(u2)) => V::U32(u1.checked_div(u2)?),
         (B::Div, V::U64(u1), V::U64(u2)) => V::U64(u1.checked_div(u2)?),
         (B::Div, V::U128(u1), V::U128(u2)) => V::U128(u1.checked_div(u2)?),
+        (B::Div, V::U256(u1), V::U256(u2)) => V::U256(u1.checked_div(u2)?),
 
         (B::Shl, V::U8(u1), V::U8(u2)) => V::U8(u1.checked_shl(u2 as u32)?),
+        (B::Shl, V::U16(u1), V::U8(u2)) => V::U16(u1.checked_shl(u2 as u32)?),
+        (B::Shl, V::U32(u1), V::U8(u2)) => V::U32(u1.checked_shl(u2 as u32)?),
         (B::Shl, V::U64(u1), V::U8(u2)) => V::U64(u1.checked_shl(u2 as u32)?),
         (B::Shl, V::U128(u1), V::U8(u2)) => V::U128(u1.checked_shl(u2 as u32)?),
+        (B::Shl, V::U256(u1), V::U8(u2)) => V::U256(u1.shl_u8(u2)),
 
         (B::Shr, V::U8(u1), V::U8(u2)) => V::U8(u1.checked_shr(u2 as u32)?),
+        (B::Shr, V::U16(u1), V::U8(u2)) => V::U16(u1.checked_shr(u2 as u32)?),
+        (B::Shr, V::U32(u1), V::U8(u2)) => V::U32(u1.checked_shr(u2 as u32)?),
         (B::Shr, V::U64(u1), V::U8(u2)) => V::U64(u1.checked_shr(u2 as u32)?),
         (B::Shr, V::U128(u1), V::U8(u2)) => V::U128(u1.checked_shr(u2 as u32)?),
+        (B::Shr, V::U256(u1), V::U8(u2)) => V::U256(u1.shr_u8(u2)),
 
         //************************************
         // Pure arith
         //************************************
         (B::BitOr, V::U8(u1), V::U8(u2)) => V::U8(u1 | u2),
+        (B::BitOr, V::U16(u1), V::U16(u2)) => V::U16(u1 | u2),
+        (B::BitOr, V::U32(u1), V::U32(u2)) => V::U32(u1 | u2),
         (B::BitOr, V::U64(u1), V::U64(u2)) => V::U64(u1 | u2),
         (B::BitOr, V::U128(u1), V::U128(u2)) => V::U128(u1 | u2),
+        (B::BitOr, V::U256(u1), V::U256(u2)) => V::U256(u1 | u2),
 
         (B::BitAnd, V::U8(u1), V::U8(u2)) => V::U8(u1 & u2),
+        (B::BitAnd, V::U16(u1), V::U16(u2)) => V::U16(u1 & u2),
+        (B::BitAnd, V::U32(u1), V::U32(u2)) => V::U32(u1 & u2),
         (B::BitAnd, V::U64(u1), V::U64(u2)) => V::U64(u1 & u2),
         (B::BitAnd, V::U128(u1), V::U128(u2)) => V::U128(u1 & u2),
+        (B::BitAnd, V::U256(u1), V::U256(u2)) => V::U256(u1 & u2),
 
         (B::Xor, V::U8(u1), V::U8(u2)) => V::U8(u1 ^ u2),
+        (B::Xor, V::U16(u1), V::U16(u2)) => V::U16(u1 ^ u2),
+        (B::Xor, V::U32(u1), V::U32(u2)) => V::U32(u1 ^ u2),
         (B::Xor, V::U64(u1), V::U64(u2)) => V::U64(u1 ^ u2),
         (B::Xor, V::U128(u1), V::U128(u2)) => V::U128(u1 ^ u2),
+        (B::Xor, V::U256(u1), V::U256(u2)) => V::U256(u1 ^ u2),
 
         //************************************
         // Logical
@@ -279,20 +316,32 @@ fn fold_binary_op(
         // Comparisons
         //************************************
         (B::Lt, V::U8(u1), V::U8(u2)) => V::Bool(u1 < u2),
+        (B::Lt, V::U16(u1), V::U16(u2)) => V::Bool(u1 < u2),
+        (B::Lt, V::U32(u1), V::U32(u2)) => V::Bool(u1 < u2),
         (B::Lt, V::U64(u1), V::U64(u2)) => V::Bool(u1 < u2),
         (B::Lt, V::U128(u1), V::U128(u2)) => V::Bool(u1 < u2),
+        (B::Lt, V::U256(u1), V::U256(u2)) => V::Bool(u1 < u2),
 
         (B::Gt, V::U8(u1), V::U8(u2)) => V::Bool(u1 > u2),
+        (B::Gt, V::U16(u1), V::U16(u2)) => V::Bool(u1 > u2),
+        (B::Gt, V::U32(u1), V::U32(u2)) => V::Bool(u1 > u2),
         (B::Gt, V::U64(u1), V::U64(u2)) => V::Bool(u1 > u2),
         (B::Gt, V::U128(u1), V::U128(u2)) => V::Bool(u1 > u2),
+        (B::Gt, V::U256(u1), V::U256(u2)) => V::Bool(u1 > u2),
 
         (B::Le, V::U8(u1), V::U8(u2)) => V::Bool(u1 <= u2),
+        (B::Le, V::U16(u1), V::U16(u2)) => V::Bool(u1 <= u2),
+        (B::Le, V::U32(u1), V::U32(u2)) => V::Bool(u1 <= u2),
         (B::Le, V::U64(u1), V::U64(u2)) => V::Bool(u1 <= u2),
         (B::Le, V::U128(u1), V::U128(u2)) => V::Bool(u1 <= u2),
+        (B::Le, V::U256(u1), V::U256(u2)) => V::Bool(u1 <= u2),
 
         (B::Ge, V::U8(u1), V::U8(u2)) => V::Bool(u1 >= u2),
+        (B::Ge, V::U16(u1), V::U16(u2)) => V::Bool(u1 >= u2),
+        (B::Ge, V::U32(u1), V::U32(u2)) => V::Bool(u1 >= u2),
         (B::Ge, V::U64(u1), V::U64(u2)) => V::Bool(u1 >= u2),
         (B::Ge, V::U128(u1), V::U128(u2)) => V::Bool(u1 >= u2),
+        (B::Ge, V::U256(u1), V::U256(u2)) => V::Bool(u1 >= u2),
 
         (B::Eq, v1, v2) => V::Bool(v1 == v2),
         (B::Neq, v1, v2) => V::Bool(v1 != v2),
@@ -308,20 +357,24 @@ fn fold_binary_op(
 fn fold_cast(loc: Loc, sp!(_, bt_): &BuiltinTypeName, v: Value_) -> Option<UnannotatedExp_> {
     use BuiltinTypeName_ as BT;
     use Value_ as V;
-    let cast = match (bt_, v) {
-        (BT::U8, V::U8(u)) => V::U8(u),
-        (BT::U8, V::U64(u)) => V::U8(u8::try_from(u).ok()?),
-        (BT::U8, V::U128(u)) => V::U8(u8::try_from(u).ok()?),
-
-        (BT::U64, V::U8(u)) => V::U64(u as u64),
-        (BT::U64, V::U64(u)) => V::U64(u),
-        (BT::U64, V::U128(u)) => V::U64(u64::try_from(u).ok()?),
-
-        (BT::U128, V::U8(u)) => V::U128(u as u128),
-        (BT::U128, V::U64(u)) => V::U128(u as u128),
-        (BT::U128, V::U128(u)) => V::U128(u),
-
-        (_, v) => panic!("ICE unexpected cast while folding: {:?} as {:?}", v, bt_),
+    // Widen the value to u256, then narrow it to the target type
+    let u = match v {
+        V::U8(u) => U256::from(u),
+        V::U16(u) => U256::from(u),
+        V::U32(u) => U256::from(u),
+        V::U64(u) => U256::from(u),
+        V::U128(u) => U256::from(u),
+        V::U256(u) => u,
+        v => panic!("ICE unexpected cast while folding: {:?} as {:?}", v, bt_),
+    };
+    let cast = match bt_ {
+        BT::U8 => V::U8(u8::try_from(u).ok()?),
+        BT::U16 => V::U16(u16::try_from(u).ok()?),
+        BT::U32 => V::U32(u32::try_from(u).ok()?),
+        BT::U64 => V::U64(u64::try_from(u).ok()?),
+        BT::U128 => V::U128(u128::try_from(u).ok()?),
+        BT::U256 => V::U256(u),
+        _ => panic!("ICE unexpected cast while folding: {:?} as {:?}", u, bt_),
     };
     Some(evalue_(loc, cast))
 }
@@ -132,7 +132,7 @@ mod count {
     fn lvalue(context: &mut Context, sp!(_, l_): &LValue, substitutable: bool) {
         use LValue_ as L;
         match l_ {
-            L::Ignore | L::Unpack(_, _, _) => (),
+            L::Ignore | L::Unpack(_, _, _) | L::UnpackVariant(_, _, _, _) => (),
             L::Var(v, _) => context.assign(v, substitutable),
         }
     }
@@ -140,7 +140,7 @@ mod count {
     fn exp(context: &mut Context, parent_e: &Exp) {
         use UnannotatedExp_ as E;
         match &parent_e.exp.value {
-            E::Unit { .. } | E::Value(_) | E::Constant(_) | E::UnresolvedError => (),
+            E::Unit { .. } | E::Value(_) | E::Constant(_, _) | E::UnresolvedError => (),
             E::Spec(_, used_locals) => {
                 used_locals.keys().for_each(|var| context.used(var, false));
             }
@@ -156,6 +156,7 @@ mod count {
             | E::Dereference(e)
             | E::UnaryExp(_, e)
             | E::Borrow(_, e, _)
+            | E::TestVariant(_, _, _, e)
             | E::Cast(e, _) => exp(context, e),
 
             E::BinopExp(e1, _, e2) => {
@@ -163,7 +164,9 @@ mod count {
                 exp(context, e2)
             }
 
-            E::Pack(_, _, fields) => fields.iter().for_each(|(_, _, e)| exp(context, e)),
+            E::Pack(_, _, fields) | E::PackVariant(_, _, _, fields) => {
+                fields.iter().for_each(|(_, _, e)| exp(context, e))
+            }
 
             E::ExpList(es) => es.iter().for_each(|item| exp_list_item(context, item)),
 
@@ -212,9 +215,10 @@ mod count {
             | E::Dereference(_)
             | E::ModuleCall(_)
             | E::Move { .. }
-            | E::Borrow(_, _, _) => false,
+            | E::Borrow(_, _, _)
+            | E::TestVariant(_, _, _, _) => false,
 
-            E::Unit { .. } | E::Value(_) | E::Constant(_) => true,
+            E::Unit { .. } | E::Value(_) | E::Constant(_, _) => true,
 
             E::Cast(e, _) => can_subst_exp_single(e),
             E::UnaryExp(op, e) => can_subst_exp_unary(op) && can_subst_exp_single(e),
@@ -222,7 +226,9 @@ mod count {
                 can_subst_exp_binary(op) && can_subst_exp_single(e1) && can_subst_exp_single(e2)
             }
             E::ExpList(es) => es.iter().all(can_subst_exp_item),
-            E::Pack(_, _, fields) => fields.iter().all(|(_, _, e)| can_subst_exp_single(e)),
+            E::Pack(_, _, fields) | E::PackVariant(_, _, _, fields) => {
+                fields.iter().all(|(_, _, e)| can_subst_exp_single(e))
+            }
             E::Vector(_, _, _, eargs) => can_subst_exp_single(eargs),
 
             E::Unreachable => panic!("ICE should not analyze dead code"),
@@ -333,7 +339,9 @@ mod eliminate {
     fn lvalue(context: &mut Context, sp!(loc, l_): LValue) -> LRes {
         use LValue_ as L;
         match l_ {
-            l_ @ L::Ignore | l_ @ L::Unpack(_, _, _) => LRes::Same(sp(loc, l_)),
+            l_ @ L::Ignore | l_ @ L::Unpack(_, _, _) | l_ @ L::UnpackVariant(_, _, _, _) => {
+                LRes::Same(sp(loc, l_))
+            }
             L::Var(v, t) => {
                 let contained = context.ssa_temps.remove(&v);
                 if contained {
@@ -356,7 +364,7 @@ mod eliminate {
 
             E::Unit { .. }
             | E::Value(_)
-            | E::Constant(_)
+            | E::Constant(_, _)
             | E::Spec(_, _)
             | E::UnresolvedError
             | E::BorrowLocal(_, _) => (),
@@ -368,6 +376,7 @@ mod eliminate {
             | E::Dereference(e)
             | E::UnaryExp(_, e)
             | E::Borrow(_, e, _)
+            | E::TestVariant(_, _, _, e)
             | E::Cast(e, _) => exp(context, e),
 
             E::BinopExp(e1, _, e2) => {
@@ -375,7 +384,9 @@ mod eliminate {
                 exp(context, e2)
             }
 
-            E::Pack(_, _, fields) => fields.iter_mut().for_each(|(_, _, e)| exp(context, e)),
+            E::Pack(_, _, fields) | E::PackVariant(_, _, _, fields) => {
+                fields.iter_mut().for_each(|(_, _, e)| exp(context, e))
+            }
 
             E::ExpList(es) => es.iter_mut().for_each(|item| exp_list_item(context, item)),
 
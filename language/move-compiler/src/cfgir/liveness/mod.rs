@@ -112,14 +112,16 @@ fn lvalue(state: &mut LivenessState, sp!(_, l_): &LValue) {
         L::Var(v, _) => {
             state.0.remove(v);
         }
-        L::Unpack(_, _, fields) => fields.iter().for_each(|(_, l)| lvalue(state, l)),
+        L::Unpack(_, _, fields) | L::UnpackVariant(_, _, _, fields) => {
+            fields.iter().for_each(|(_, l)| lvalue(state, l))
+        }
     }
 }
 
 fn exp(state: &mut LivenessState, parent_e: &Exp) {
     use UnannotatedExp_ as E;
     match &parent_e.exp.value {
-        E::Unit { .. } | E::Value(_) | E::Constant(_) | E::UnresolvedError => (),
+        E::Unit { .. } | E::Value(_) | E::Constant(_, _) | E::UnresolvedError => (),
 
         E::BorrowLocal(_, var) | E::Copy { var, .. } | E::Move { var, .. } => {
             state.0.insert(*var);
@@ -136,6 +138,7 @@ fn exp(state: &mut LivenessState, parent_e: &Exp) {
         | E::Dereference(e)
         | E::UnaryExp(_, e)
         | E::Borrow(_, e, _)
+        | E::TestVariant(_, _, _, e)
         | E::Cast(e, _) => exp(state, e),
 
         E::BinopExp(e1, _, e2) => {
@@ -143,7 +146,9 @@ fn exp(state: &mut LivenessState, parent_e: &Exp) {
             exp(state, e2)
         }
 
-        E::Pack(_, _, fields) => fields.iter().for_each(|(_, _, e)| exp(state, e)),
+        E::Pack(_, _, fields) | E::PackVariant(_, _, _, fields) => {
+            fields.iter().for_each(|(_, _, e)| exp(state, e))
+        }
 
         E::ExpList(es) => es.iter().for_each(|item| exp_list_item(state, item)),
 
@@ -315,14 +320,16 @@ mod last_usage {
                     }
                 }
             }
-            L::Unpack(_, _, fields) => fields.iter_mut().for_each(|(_, l)| lvalue(context, l)),
+            L::Unpack(_, _, fields) | L::UnpackVariant(_, _, _, fields) => {
+                fields.iter_mut().for_each(|(_, l)| lvalue(context, l))
+            }
         }
     }
 
     fn exp(context: &mut Context, parent_e: &mut Exp) {
         use UnannotatedExp_ as E;
         match &mut parent_e.exp.value {
-            E::Unit { .. } | E::Value(_) | E::Constant(_) | E::UnresolvedError => (),
+            E::Unit { .. } | E::Value(_) | E::Constant(_, _) | E::UnresolvedError => (),
 
             E::BorrowLocal(_, var) | E::Move { var, .. } => {
                 // remove it from context to prevent accidental dropping in previous usages
@@ -357,6 +364,7 @@ mod last_usage {
             | E::Dereference(e)
             | E::UnaryExp(_, e)
             | E::Borrow(_, e, _)
+            | E::TestVariant(_, _, _, e)
             | E::Cast(e, _) => exp(context, e),
 
             E::BinopExp(e1, _, e2) => {
@@ -364,7 +372,7 @@ mod last_usage {
                 exp(context, e1)
             }
 
-            E::Pack(_, _, fields) => fields
+            E::Pack(_, _, fields) | E::PackVariant(_, _, _, fields) => fields
                 .iter_mut()
                 .rev()
                 .for_each(|(_, _, e)| exp(context, e)),
@@ -238,7 +238,9 @@ fn lvalue(context: &mut Context, sp!(loc, l_): &LValue) {
             }
             context.set_state(*v, LocalState::Available(*loc))
         }
-        L::Unpack(_, _, fields) => fields.iter().for_each(|(_, l)| lvalue(context, l)),
+        L::Unpack(_, _, fields) | L::UnpackVariant(_, _, _, fields) => {
+            fields.iter().for_each(|(_, l)| lvalue(context, l))
+        }
     }
 }
 
@@ -246,7 +248,7 @@ fn exp(context: &mut Context, parent_e: &Exp) {
     use UnannotatedExp_ as E;
     let eloc = &parent_e.exp.loc;
     match &parent_e.exp.value {
-        E::Unit { .. } | E::Value(_) | E::Constant(_) | E::Spec(_, _) | E::UnresolvedError => (),
+        E::Unit { .. } | E::Value(_) | E::Constant(_, _) | E::Spec(_, _) | E::UnresolvedError => (),
 
         E::BorrowLocal(_, var) | E::Copy { var, .. } => use_local(context, eloc, var),
 
@@ -265,6 +267,7 @@ fn exp(context: &mut Context, parent_e: &Exp) {
         | E::Dereference(e)
         | E::UnaryExp(_, e)
         | E::Borrow(_, e, _)
+        | E::TestVariant(_, _, _, e)
         | E::Cast(e, _) => exp(context, e),
 
         E::BinopExp(e1, _, e2) => {
@@ -272,7 +275,9 @@ fn exp(context: &mut Context, parent_e: &Exp) {
             exp(context, e2)
         }
 
-        E::Pack(_, _, fields) => fields.iter().for_each(|(_, _, e)| exp(context, e)),
+        E::Pack(_, _, fields) | E::PackVariant(_, _, _, fields) => {
+            fields.iter().for_each(|(_, _, e)| exp(context, e))
+        }
 
         E::ExpList(es) => es.iter().for_each(|item| exp_list_item(context, item)),
 
@@ -406,7 +411,7 @@ fn add_drop_ability_tip(context: &Context, diag: &mut Diagnostic, st: SingleType
                 T::Param(TParam { abilities, .. }) | T::Apply(Some(abilities), _, _) => {
                     abilities.clone()
                 }
-                T::Var(_) | T::Apply(None, _, _) => panic!("ICE expansion failed"),
+                T::Var(_) | T::Apply(None, _, _) | T::Fun(_, _) => panic!("ICE expansion failed"),
             };
             (ty_arg, abilities)
         }),
@@ -27,9 +27,21 @@ use std::{
 // Context
 //**************************************************************************************************
 
+// The constants of a script are not in a module
+type ConstantKey = (Option<ModuleIdent>, ConstantName);
+
+enum ConstantValue {
+    Unfolded(H::Constant),
+    Folding,
+    Folded(Option<Value>),
+}
+
 struct Context<'env> {
     env: &'env mut CompilationEnv,
     struct_declared_abilities: UniqueMap<ModuleIdent, UniqueMap<StructName, AbilitySet>>,
+    // Constants are folded on demand, as they can refer to the constants of any other module
+    constants: BTreeMap<ConstantKey, ConstantValue>,
+    current_module: Option<ModuleIdent>,
     start: Option<Label>,
     loop_begin: Option<Label>,
     loop_end: Option<Label>,
@@ -57,14 +69,20 @@ impl<'env> Context<'env> {
                     .key_cloned_iter()
                     .filter(|(mident, _m)| !modules.contains_key(mident))
             }));
-        let struct_declared_abilities = UniqueMap::maybe_from_iter(
-            all_modules
-                .map(|(m, mdef)| (m, mdef.structs.ref_map(|_s, sdef| sdef.abilities.clone()))),
-        )
-        .unwrap();
+        let mut struct_declared_abilities = UniqueMap::new();
+        let mut constants = BTreeMap::new();
+        for (m, mdef) in all_modules {
+            let abilities = mdef.structs.ref_map(|_s, sdef| sdef.abilities.clone());
+            struct_declared_abilities.add(m, abilities).unwrap();
+            for (c, cdef) in mdef.constants.key_cloned_iter() {
+                constants.insert((Some(m), c), ConstantValue::Unfolded(cdef.clone()));
+            }
+        }
         Context {
             env,
             struct_declared_abilities,
+            constants,
+            current_module: None,
             next_label: None,
             loop_begin: None,
             loop_end: None,
@@ -192,8 +210,10 @@ fn module(
         constants: hconstants,
     } = mdef;
 
+    context.current_module = Some(module_ident);
     let constants = hconstants.map(|name, c| constant(context, name, c));
     let functions = hfunctions.map(|name, f| function(context, name, f));
+    context.current_module = None;
     (
         module_ident,
         G::ModuleDefinition {
@@ -228,6 +248,11 @@ fn script(context: &mut Context, hscript: H::Script) -> G::Script {
         function_name,
         function: hfunction,
     } = hscript;
+    context.constants.retain(|(m, _), _| m.is_some());
+    for (name, c) in hconstants.key_cloned_iter() {
+        let value = ConstantValue::Unfolded(c.clone());
+        context.constants.insert((None, name), value);
+    }
     let constants = hconstants.map(|name, c| constant(context, name, c));
     let function = function(context, function_name, hfunction);
     G::Script {
@@ -241,19 +266,18 @@ fn script(context: &mut Context, hscript: H::Script) -> G::Script {
 }
 
 //**************************************************************************************************
-// Functions
+// Constants
 //**************************************************************************************************
 
-fn constant(context: &mut Context, _name: ConstantName, c: H::Constant) -> G::Constant {
+fn constant(context: &mut Context, name: ConstantName, c: H::Constant) -> G::Constant {
     let H::Constant {
         attributes,
         loc,
         signature,
-        value: (locals, block),
+        value: _,
     } = c;
 
-    let final_value = constant_(context, loc, signature.clone(), locals, block);
-    let value = final_value.and_then(move_value_from_exp);
+    let value = constant_value(context, context.current_module, name).map(move_value_from_value);
 
     G::Constant {
         attributes,
@@ -266,6 +290,37 @@ fn constant(context: &mut Context, _name: ConstantName, c: H::Constant) -> G::Co
 const CANNOT_FOLD: &str =
     "Invalid expression in 'const'. This expression could not be evaluated to a value";
 
+// Returns the value of the constant, folding it first if it was not folded yet. Returns `None` if
+// the constant could not be folded (or refers to itself), which has been reported already
+fn constant_value(
+    context: &mut Context,
+    m: Option<ModuleIdent>,
+    name: ConstantName,
+) -> Option<Value> {
+    let key = (m, name);
+    let c = match context.constants.insert(key, ConstantValue::Folding) {
+        Some(ConstantValue::Unfolded(c)) => c,
+        Some(ConstantValue::Folded(value)) => {
+            context
+                .constants
+                .insert(key, ConstantValue::Folded(value.clone()));
+            return value;
+        }
+        Some(ConstantValue::Folding) | None => return None,
+    };
+    let H::Constant {
+        loc,
+        signature,
+        value: (locals, block),
+        ..
+    } = c;
+    let value = constant_(context, loc, signature, locals, block).and_then(value_from_exp);
+    context
+        .constants
+        .insert(key, ConstantValue::Folded(value.clone()));
+    value
+}
+
 fn constant_(
     context: &mut Context,
     full_loc: Loc,
@@ -278,6 +333,7 @@ fn constant_(
 
     initial_block(context, block);
     let (start, mut blocks, block_info) = context.finish_blocks();
+    inline_constants(context, &mut blocks, true);
 
     let (mut cfg, infinite_loop_starts, errors) = BlockCFG::new(start, &mut blocks, &block_info);
     assert!(infinite_loop_starts.is_empty(), "{}", ICE_MSG);
@@ -349,10 +405,84 @@ fn check_constant_value(context: &mut Context, e: &H::Exp) {
     }
 }
 
-fn move_value_from_exp(e: H::Exp) -> Option<MoveValue> {
+// Replaces the references to constants with their values. In functions, only the constants of other
+// modules are replaced, as the constants of the current module are loaded from its constant pool
+fn inline_constants(context: &mut Context, blocks: &mut BasicBlocks, all_constants: bool) {
+    for block in blocks.values_mut() {
+        for cmd in block.iter_mut() {
+            inline_constants_cmd(context, all_constants, cmd)
+        }
+    }
+}
+
+fn inline_constants_cmd(context: &mut Context, all_constants: bool, sp!(_, cmd_): &mut H::Command) {
+    use H::Command_ as C;
+    match cmd_ {
+        C::Assign(_, e) => inline_constants_exp(context, all_constants, e),
+        C::Mutate(el, er) => {
+            inline_constants_exp(context, all_constants, el);
+            inline_constants_exp(context, all_constants, er)
+        }
+        C::Abort(e)
+        | C::Return { exp: e, .. }
+        | C::IgnoreAndPop { exp: e, .. }
+        | C::JumpIf { cond: e, .. } => inline_constants_exp(context, all_constants, e),
+        C::Break | C::Continue | C::Jump { .. } => (),
+    }
+}
+
+fn inline_constants_exp(context: &mut Context, all_constants: bool, e: &mut H::Exp) {
+    use H::UnannotatedExp_ as E;
+    match &mut e.exp.value {
+        E::Constant(m, c) if all_constants || *m != context.current_module => {
+            if let Some(v) = constant_value(context, *m, *c) {
+                e.exp.value = E::Value(v)
+            }
+        }
+        E::Unit { .. }
+        | E::Value(_)
+        | E::Move { .. }
+        | E::Copy { .. }
+        | E::Constant(_, _)
+        | E::BorrowLocal(_, _)
+        | E::Unreachable
+        | E::Spec(_, _)
+        | E::UnresolvedError => (),
+
+        E::ModuleCall(call) => inline_constants_exp(context, all_constants, &mut call.arguments),
+        E::Builtin(_, e)
+        | E::Freeze(e)
+        | E::Vector(_, _, _, e)
+        | E::Dereference(e)
+        | E::UnaryExp(_, e)
+        | E::TestVariant(_, _, _, e)
+        | E::Borrow(_, e, _)
+        | E::Cast(e, _) => inline_constants_exp(context, all_constants, e),
+        E::BinopExp(el, _, er) => {
+            inline_constants_exp(context, all_constants, el);
+            inline_constants_exp(context, all_constants, er)
+        }
+        E::Pack(_, _, fields) | E::PackVariant(_, _, _, fields) => {
+            for (_, _, e) in fields {
+                inline_constants_exp(context, all_constants, e)
+            }
+        }
+        E::ExpList(items) => {
+            for item in items {
+                match item {
+                    H::ExpListItem::Single(e, _) | H::ExpListItem::Splat(_, e, _) => {
+                        inline_constants_exp(context, all_constants, e)
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn value_from_exp(e: H::Exp) -> Option<Value> {
     use H::UnannotatedExp_ as E;
     match e.exp.value {
-        E::Value(v) => Some(move_value_from_value(v)),
+        E::Value(v) => Some(v),
         _ => None,
     }
 }
@@ -367,8 +497,11 @@ pub(crate) fn move_value_from_value_(v_: Value_) -> MoveValue {
     match v_ {
         V::Address(a) => MV::Address(MoveAddress::new(a.into_bytes())),
         V::U8(u) => MV::U8(u),
+        V::U16(u) => MV::U16(u),
+        V::U32(u) => MV::U32(u),
         V::U64(u) => MV::U64(u),
         V::U128(u) => MV::U128(u),
+        V::U256(u) => MV::U256(u),
         V::Bool(b) => MV::Bool(b),
         V::Vector(_, vs) => MV::Vector(vs.into_iter().map(move_value_from_value).collect()),
     }
@@ -419,6 +552,7 @@ fn function_body(
         HB::Defined { locals, body } => {
             initial_block(context, body);
             let (start, mut blocks, block_info) = context.finish_blocks();
+            inline_constants(context, &mut blocks, false);
 
             let (mut cfg, infinite_loop_starts, diags) =
                 BlockCFG::new(start, &mut blocks, &block_info);
@@ -6,8 +6,8 @@ use crate::{
     command_line::{DEFAULT_OUTPUT_DIR, MOVE_COMPILED_INTERFACES_DIR},
     compiled_unit,
     compiled_unit::AnnotatedCompiledUnit,
-    diagnostics::{codes::Severity, *},
-    expansion, hlir, interface_generator, naming, parser,
+    diagnostics::{codes::Severity, structured::StructuredDiagnostic, *},
+    expansion, hlir, interface_generator, linters, naming, parser,
     parser::{comments::*, *},
     shared::{
         CompilationEnv, Flags, IndexedPackagePath, NamedAddressMap, NamedAddressMaps,
@@ -229,11 +229,15 @@ impl<'a> Compiler<'a> {
     }
 
     pub fn check_and_report(self) -> anyhow::Result<FilesSourceText> {
+        let format = self.flags.diagnostics_format();
         let (files, res) = self.check()?;
-        unwrap_or_report_diagnostics(&files, res);
+        unwrap_or_report_diagnostics_with_format(&files, res, format);
         Ok(files)
     }
 
+    /// Compiles the program, returning the source files along with either the compiled units and
+    /// the warnings, or the errors. See `build_structured` for the diagnostics in a
+    /// machine-readable form.
     pub fn build(
         self,
     ) -> anyhow::Result<(
@@ -247,10 +251,28 @@ impl<'a> Compiler<'a> {
         ))
     }
 
+    /// Compiles the program like `build`, with the diagnostics in their machine-readable form.
+    /// Their labels carry the file, line and column they point to, so the source files are not
+    /// needed to interpret them.
+    pub fn build_structured(
+        self,
+    ) -> anyhow::Result<(
+        FilesSourceText,
+        Result<(Vec<AnnotatedCompiledUnit>, Vec<StructuredDiagnostic>), Vec<StructuredDiagnostic>>,
+    )> {
+        let (files, res) = self.build()?;
+        let res = match res {
+            Ok((units, warnings)) => Ok((units, warnings.to_structured(&files))),
+            Err(errors) => Err(errors.to_structured(&files)),
+        };
+        Ok((files, res))
+    }
+
     pub fn build_and_report(self) -> anyhow::Result<(FilesSourceText, Vec<AnnotatedCompiledUnit>)> {
+        let format = self.flags.diagnostics_format();
         let (files, units_res) = self.build()?;
-        let (units, warnings) = unwrap_or_report_diagnostics(&files, units_res);
-        report_warnings(&files, warnings);
+        let (units, warnings) = unwrap_or_report_diagnostics_with_format(&files, units_res, format);
+        report_warnings_with_format(&files, warnings, format);
         Ok((files, units))
     }
 }
@@ -364,17 +386,20 @@ macro_rules! ast_stepped_compilers {
                 }
 
                 pub fn check_and_report(self, files: &FilesSourceText)  {
+                    let format = self.compilation_env.flags().diagnostics_format();
                     let errors_result = self.check();
-                    unwrap_or_report_diagnostics(&files, errors_result);
+                    unwrap_or_report_diagnostics_with_format(&files, errors_result, format);
                 }
 
                 pub fn build_and_report(
                     self,
                     files: &FilesSourceText,
                 ) -> Vec<AnnotatedCompiledUnit> {
+                    let format = self.compilation_env.flags().diagnostics_format();
                     let units_result = self.build();
-                    let (units, warnings) = unwrap_or_report_diagnostics(&files, units_result);
-                    report_warnings(&files, warnings);
+                    let (units, warnings) =
+                        unwrap_or_report_diagnostics_with_format(&files, units_result, format);
+                    report_warnings_with_format(&files, warnings, format);
                     units
                 }
             }
@@ -785,6 +810,7 @@ fn run(
         PassResult::Naming(nprog) => {
             let tprog = typing::translate::program(compilation_env, pre_compiled_lib, nprog);
             compilation_env.check_diags_at_or_above_severity(Severity::BlockingError)?;
+            linters::typing(compilation_env, &tprog);
             run(
                 compilation_env,
                 pre_compiled_lib,
@@ -806,6 +832,7 @@ fn run(
         }
         PassResult::HLIR(hprog) => {
             let cprog = cfgir::translate::program(compilation_env, pre_compiled_lib, hprog);
+            linters::cfgir(compilation_env, &cprog);
             compilation_env.check_diags_at_or_above_severity(Severity::NonblockingError)?;
             run(
                 compilation_env,
@@ -26,6 +26,16 @@ pub const FLAVOR: &str = "flavor";
 
 pub const BYTECODE_VERSION: &str = "bytecode-version";
 
+pub const LINT: &str = "lint";
+
+pub const WARNINGS_ARE_ERRORS: &str = "warnings-are-errors";
+
+pub const ALLOW: &str = "allow";
+
+pub const DENY: &str = "deny";
+
+pub const DIAGNOSTICS_FORMAT: &str = "diagnostics-format";
+
 pub const COLOR_MODE_ENV_VAR: &str = "COLOR_MODE";
 
 pub const MOVE_COMPILED_INTERFACES_DIR: &str = "mv_interfaces";
@@ -1,6 +1,8 @@
 // Copyright (c) The Diem Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use std::{fmt, str::FromStr};
+
 //**************************************************************************************************
 // Main types
 //**************************************************************************************************
@@ -49,12 +51,23 @@ macro_rules! codes {
     ($($cat:ident: [
         $($code:ident: { msg: $code_msg:literal, severity:$sev:ident $(,)? }),* $(,)?
     ]),* $(,)?) => {
-        #[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
+        #[derive(PartialEq, Eq, Clone, Copy, Debug, Hash, PartialOrd, Ord)]
         #[repr(u8)]
         pub enum Category {
             $($cat,)*
         }
 
+        impl Category {
+            const ALL: &'static [Category] = &[$(Category::$cat,)*];
+
+            /// The names of the codes of the category, starting with the code `1`
+            fn code_names(self) -> &'static [&'static str] {
+                match self {
+                    $(Category::$cat => &[$(stringify!($code),)*],)*
+                }
+            }
+        }
+
         $(
             #[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
             #[repr(u8)]
@@ -150,6 +163,8 @@ codes!(
         UnboundField: { msg: "unbound field", severity: BlockingError },
         ReservedName: { msg: "invalid use of reserved name", severity: BlockingError },
         UnboundMacro: { msg: "unbound macro", severity: BlockingError },
+        UnboundVariant: { msg: "unbound variant", severity: BlockingError },
+        DeprecatedUsage: { msg: "use of deprecated item", severity: Warning },
     ],
     // errors for typing rules. mostly typing/translate
     TypeSafety: [
@@ -181,6 +196,12 @@ codes!(
                 (NOTE: this may become an error in the future)",
             severity: Warning
         },
+        NonExhaustiveMatch: { msg: "non-exhaustive match", severity: BlockingError },
+        UnreachableMatchArm: { msg: "unreachable match arm", severity: Warning },
+        InvalidEnumUsage: { msg: "invalid use of enum", severity: BlockingError },
+        InvalidLambda: { msg: "invalid use of lambda", severity: BlockingError },
+        InvalidInlineFunction: { msg: "invalid inline function", severity: BlockingError },
+        CyclicConstant: { msg: "cyclic constant definition", severity: BlockingError },
     ],
     // errors for ability rules. mostly typing/translate
     AbilitySafety: [
@@ -225,6 +246,8 @@ codes!(
         InvalidTest: { msg: "unable to generate test", severity: NonblockingError },
         InvalidBytecodeInst:
             { msg: "unknown bytecode instruction function", severity: NonblockingError },
+        UnknownLint: { msg: "unknown lint", severity: Warning },
+        UnknownWarningFilter: { msg: "unknown warning filter", severity: Warning },
     ],
     Tests: [
         TestFailed: { msg: "test failure", severity: BlockingError },
@@ -232,9 +255,20 @@ codes!(
     Bug: [
         BytecodeGeneration: { msg: "BYTECODE GENERATION FAILED", severity: Bug },
         BytecodeVerification: { msg: "BYTECODE VERIFICATION FAILED", severity: Bug },
+        FormattingFailed: { msg: "FORMATTING FAILED", severity: Bug },
     ],
     Derivation: [
         DeriveFailed: { msg: "attribute derivation failed", severity: BlockingError }
+    ],
+    // warnings for code that is valid but likely a mistake or needlessly complicated. only
+    // reported when linting. see the linters module
+    Lint: [
+        SelfTransfer: { msg: "self transfer", severity: Warning },
+        UnnecessaryCopy: { msg: "unnecessary 'copy'", severity: Warning },
+        WhileTrue: { msg: "'while (true)' instead of 'loop'", severity: Warning },
+        RedundantRefDeref: { msg: "redundant reference and dereference", severity: Warning },
+        OptionEquality: { msg: "'Option' compared with '=='", severity: Warning },
+        ShadowedConstant: { msg: "local shadowing a constant", severity: Warning },
     ]
 );
 
@@ -265,9 +299,21 @@ impl DiagnosticInfo {
         self.message
     }
 
+    pub fn category(&self) -> Category {
+        self.category
+    }
+
+    pub fn code(&self) -> u8 {
+        self.code
+    }
+
     pub fn severity(&self) -> Severity {
         self.severity
     }
+
+    pub fn set_severity(&mut self, severity: Severity) {
+        self.severity = severity
+    }
 }
 
 impl Severity {
@@ -289,3 +335,179 @@ impl Default for Severity {
         Self::MIN
     }
 }
+
+//**************************************************************************************************
+// Filters
+//**************************************************************************************************
+
+/// Selects warnings, to silence them or to report them as errors. A filter is written either as
+/// the name of a category, e.g. `unused_item`, as the name of a code qualified by its category,
+/// e.g. `unused_item::variable`, or as the short name of a code, e.g. `unused_variable`.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash, PartialOrd, Ord)]
+pub enum WarningFilter {
+    /// All warnings of a category
+    Category(Category),
+    /// The warnings with a code of a category
+    Code(Category, u8),
+}
+
+/// The short names of the codes which are reported as warnings
+const WARNING_NAMES: &[(&str, Category, u8)] = &[
+    (
+        "invalid_doc_comment",
+        Category::Syntax,
+        Syntax::InvalidDocComment as u8,
+    ),
+    (
+        "non_phantom_use",
+        Category::Declarations,
+        Declarations::InvalidNonPhantomUse as u8,
+    ),
+    (
+        "deprecated_usage",
+        Category::NameResolution,
+        NameResolution::DeprecatedUsage as u8,
+    ),
+    (
+        "non_invocable_script",
+        Category::TypeSafety,
+        TypeSafety::NonInvocablePublicScript as u8,
+    ),
+    (
+        "unreachable_match_arm",
+        Category::TypeSafety,
+        TypeSafety::UnreachableMatchArm as u8,
+    ),
+    (
+        "unused_alias",
+        Category::UnusedItem,
+        UnusedItem::Alias as u8,
+    ),
+    (
+        "unused_variable",
+        Category::UnusedItem,
+        UnusedItem::Variable as u8,
+    ),
+    (
+        "unused_assignment",
+        Category::UnusedItem,
+        UnusedItem::Assignment as u8,
+    ),
+    (
+        "unused_trailing_semi",
+        Category::UnusedItem,
+        UnusedItem::TrailingSemi as u8,
+    ),
+    (
+        "dead_code",
+        Category::UnusedItem,
+        UnusedItem::DeadCode as u8,
+    ),
+    (
+        "unused_type_parameter",
+        Category::UnusedItem,
+        UnusedItem::StructTypeParam as u8,
+    ),
+    (
+        "unknown_lint",
+        Category::Attributes,
+        Attributes::UnknownLint as u8,
+    ),
+    (
+        "unknown_warning_filter",
+        Category::Attributes,
+        Attributes::UnknownWarningFilter as u8,
+    ),
+];
+
+impl WarningFilter {
+    pub fn matches(&self, info: &DiagnosticInfo) -> bool {
+        match self {
+            Self::Category(category) => info.category == *category,
+            Self::Code(category, code) => info.category == *category && info.code == *code,
+        }
+    }
+}
+
+impl Category {
+    /// The name of the category in snake case, e.g. `unused_item`
+    pub fn name(self) -> String {
+        snake_case(&format!("{:?}", self))
+    }
+}
+
+fn snake_case(name: &str) -> String {
+    let mut s = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i > 0 {
+                s.push('_');
+            }
+            s.push(c.to_ascii_lowercase());
+        } else {
+            s.push(c);
+        }
+    }
+    s
+}
+
+impl FromStr for WarningFilter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some((_, category, code)) = WARNING_NAMES.iter().find(|(name, _, _)| *name == s) {
+            return Ok(Self::Code(*category, *code));
+        }
+        let (category_name, code_name) = match s.split_once("::") {
+            Some((category_name, code_name)) => (category_name, Some(code_name)),
+            None => (s, None),
+        };
+        let category = Category::ALL
+            .iter()
+            .copied()
+            .find(|category| category.name() == category_name)
+            .ok_or_else(|| match code_name {
+                None => format!(
+                    "Unknown warning '{}'. Expected the name of a warning or of a warning category",
+                    s
+                ),
+                Some(_) => format!("Unknown warning category '{}'", category_name),
+            })?;
+        match code_name {
+            None => Ok(Self::Category(category)),
+            Some(code_name) => category
+                .code_names()
+                .iter()
+                .position(|name| snake_case(name) == code_name)
+                .map(|index| Self::Code(category, index as u8 + 1))
+                .ok_or_else(|| {
+                    format!(
+                        "Unknown code '{}' in warning category '{}'",
+                        code_name, category_name
+                    )
+                }),
+        }
+    }
+}
+
+impl fmt::Display for WarningFilter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Category(category) => write!(f, "{}", category.name()),
+            Self::Code(category, code) => {
+                match WARNING_NAMES
+                    .iter()
+                    .find(|(_, c, n)| c == category && n == code)
+                {
+                    Some((name, _, _)) => write!(f, "{}", name),
+                    None => write!(
+                        f,
+                        "{}::{}",
+                        category.name(),
+                        snake_case(category.code_names()[*code as usize - 1])
+                    ),
+                }
+            }
+        }
+    }
+}
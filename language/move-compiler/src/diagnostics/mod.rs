@@ -2,10 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod codes;
+pub mod structured;
 
 use crate::{
     command_line::COLOR_MODE_ENV_VAR,
-    diagnostics::codes::{DiagnosticCode, DiagnosticInfo, Severity},
+    diagnostics::{
+        codes::{DiagnosticCode, DiagnosticInfo, Severity},
+        structured::DiagnosticsFormat,
+    },
 };
 use codespan_reporting::{
     self as csr,
@@ -55,40 +59,99 @@ pub struct Diagnostics {
 //**************************************************************************************************
 
 pub fn report_diagnostics(files: &FilesSourceText, diags: Diagnostics) -> ! {
+    report_diagnostics_with_format(files, diags, DiagnosticsFormat::Human)
+}
+
+pub fn report_diagnostics_with_format(
+    files: &FilesSourceText,
+    diags: Diagnostics,
+    format: DiagnosticsFormat,
+) -> ! {
     let should_exit = true;
-    report_diagnostics_impl(files, diags, should_exit);
+    report_diagnostics_impl(files, diags, format, should_exit);
     std::process::exit(1)
 }
 
 pub fn report_warnings(files: &FilesSourceText, warnings: Diagnostics) {
-    if warnings.is_empty() {
+    report_warnings_with_format(files, warnings, DiagnosticsFormat::Human)
+}
+
+/// Reports `warnings` in `format`. Unlike the human format, the machine-readable formats are
+/// written to stdout, and also when there are no warnings, so that there is always a document to
+/// read.
+pub fn report_warnings_with_format(
+    files: &FilesSourceText,
+    warnings: Diagnostics,
+    format: DiagnosticsFormat,
+) {
+    if warnings.is_empty() && format == DiagnosticsFormat::Human {
         return;
     }
-    debug_assert!(warnings.max_severity().unwrap() == Severity::Warning);
-    report_diagnostics_impl(files, warnings, false)
+    debug_assert!(matches!(
+        warnings.max_severity(),
+        None | Some(Severity::Warning)
+    ));
+    report_diagnostics_impl(files, warnings, format, false)
 }
 
-fn report_diagnostics_impl(files: &FilesSourceText, diags: Diagnostics, should_exit: bool) {
-    let color_choice = match read_env_var(COLOR_MODE_ENV_VAR).as_str() {
-        "NONE" => ColorChoice::Never,
-        "ANSI" => ColorChoice::AlwaysAnsi,
-        "ALWAYS" => ColorChoice::Always,
-        _ => ColorChoice::Auto,
-    };
-    let mut writer = StandardStream::stderr(color_choice);
-    output_diagnostics(&mut writer, files, diags);
+fn report_diagnostics_impl(
+    files: &FilesSourceText,
+    diags: Diagnostics,
+    format: DiagnosticsFormat,
+    should_exit: bool,
+) {
+    match format {
+        DiagnosticsFormat::Human => {
+            let color_choice = match read_env_var(COLOR_MODE_ENV_VAR).as_str() {
+                "NONE" => ColorChoice::Never,
+                "ANSI" => ColorChoice::AlwaysAnsi,
+                "ALWAYS" => ColorChoice::Always,
+                _ => ColorChoice::Auto,
+            };
+            let mut writer = StandardStream::stderr(color_choice);
+            output_diagnostics(&mut writer, files, diags);
+        }
+        // Written to stdout, as tools usually report their progress on stderr
+        DiagnosticsFormat::Json | DiagnosticsFormat::Sarif => {
+            println!("{}", format_diagnostics(files, diags, format))
+        }
+    }
     if should_exit {
         std::process::exit(1);
     }
 }
 
 pub fn unwrap_or_report_diagnostics<T>(files: &FilesSourceText, res: Result<T, Diagnostics>) -> T {
+    unwrap_or_report_diagnostics_with_format(files, res, DiagnosticsFormat::Human)
+}
+
+pub fn unwrap_or_report_diagnostics_with_format<T>(
+    files: &FilesSourceText,
+    res: Result<T, Diagnostics>,
+    format: DiagnosticsFormat,
+) -> T {
     match res {
         Ok(t) => t,
         Err(diags) => {
             assert!(!diags.is_empty());
-            report_diagnostics(files, diags)
+            report_diagnostics_with_format(files, diags, format)
+        }
+    }
+}
+
+/// Formats `diags` as a JSON or SARIF document, or renders them without colors for the human
+/// format
+pub fn format_diagnostics(
+    files: &FilesSourceText,
+    diags: Diagnostics,
+    format: DiagnosticsFormat,
+) -> String {
+    match format {
+        DiagnosticsFormat::Human => {
+            String::from_utf8(report_diagnostics_to_buffer(files, diags)).unwrap()
         }
+        DiagnosticsFormat::Json => structured::to_json(diags.to_structured(files)),
+        DiagnosticsFormat::Sarif => structured::to_sarif(diags.to_structured(files)),
     }
 }
 
@@ -284,6 +347,19 @@ impl Diagnostic {
         self
     }
 
+    pub fn set_severity(mut self, severity: Severity) -> Self {
+        self.info.set_severity(severity);
+        self
+    }
+
+    pub fn info(&self) -> &DiagnosticInfo {
+        &self.info
+    }
+
+    pub fn primary_loc(&self) -> Loc {
+        self.primary_label.0
+    }
+
     #[allow(unused)]
     pub fn add_secondary_labels(
         &mut self,
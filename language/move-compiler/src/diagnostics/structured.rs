@@ -0,0 +1,288 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Machine-readable forms of diagnostics, for tools that consume them instead of a person. The
+//! JSON form is versioned, and a field is only added or changed along with a new
+//! `JSON_FORMAT_VERSION`. Lines and columns start at 1, and columns count characters.
+
+use crate::diagnostics::{
+    codes::{DiagnosticInfo, Severity},
+    Diagnostic, Diagnostics, FileMapping, FilesSourceText,
+};
+use codespan_reporting::files::{Files, SimpleFiles};
+use move_ir_types::location::Loc;
+use move_symbol_pool::Symbol;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::{collections::BTreeMap, fmt, str::FromStr};
+
+pub const JSON_FORMAT_VERSION: u64 = 1;
+
+const SARIF_VERSION: &str = "2.1.0";
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const TOOL_NAME: &str = "move-compiler";
+
+/// How diagnostics are reported
+#[derive(PartialEq, Eq, Clone, Copy, Debug, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum DiagnosticsFormat {
+    /// Rendered with their source, for a terminal
+    Human,
+    /// A JSON document, see `JsonDiagnostics`
+    Json,
+    /// A SARIF 2.1.0 log
+    Sarif,
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiagnosticSeverity {
+    Warning,
+    Error,
+    Bug,
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+#[derive(PartialEq, Eq, Clone, Debug, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Location {
+    pub file: String,
+    pub start: Position,
+    pub end: Position,
+}
+
+#[derive(PartialEq, Eq, Clone, Debug, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Label {
+    pub location: Location,
+    pub message: String,
+}
+
+#[derive(PartialEq, Eq, Clone, Debug, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct StructuredDiagnostic {
+    /// The code as printed by the compiler, e.g. `E03001`
+    pub code: String,
+    pub severity: DiagnosticSeverity,
+    /// The name of the category of the code, e.g. `unused_item`
+    pub category: String,
+    /// The message of the code
+    pub message: String,
+    pub primary_label: Label,
+    pub secondary_labels: Vec<Label>,
+    pub notes: Vec<String>,
+}
+
+/// The document written for `DiagnosticsFormat::Json`
+#[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize)]
+pub struct JsonDiagnostics {
+    pub version: u64,
+    pub diagnostics: Vec<StructuredDiagnostic>,
+}
+
+//**************************************************************************************************
+// Conversion
+//**************************************************************************************************
+
+impl Diagnostics {
+    /// Returns the diagnostics in a structured form, ordered by file and position, without
+    /// duplicates. `files` must contain the source of every location, as returned along with the
+    /// diagnostics by the compiler.
+    pub fn to_structured(&self, sources: &FilesSourceText) -> Vec<StructuredDiagnostic> {
+        let mut files = SimpleFiles::new();
+        let mut file_mapping = FileMapping::new();
+        for (fhash, (fname, source)) in sources {
+            let id = files.add(*fname, source.as_str());
+            file_mapping.insert(*fhash, id);
+        }
+        let mut structured = self
+            .diagnostics
+            .iter()
+            .map(|diag| structured_diagnostic(&files, &file_mapping, diag))
+            .collect::<Vec<_>>();
+        structured.sort_by(|d1, d2| {
+            let loc1 = &d1.primary_label.location;
+            let loc2 = &d2.primary_label.location;
+            loc1.cmp(loc2).then_with(|| d1.cmp(d2))
+        });
+        structured.dedup();
+        structured
+    }
+}
+
+fn structured_diagnostic(
+    files: &SimpleFiles<Symbol, &str>,
+    file_mapping: &FileMapping,
+    diag: &Diagnostic,
+) -> StructuredDiagnostic {
+    let Diagnostic {
+        info,
+        primary_label,
+        secondary_labels,
+        notes,
+    } = diag;
+    let (code, message) = info.clone().render();
+    StructuredDiagnostic {
+        code,
+        severity: structured_severity(info),
+        category: info.category().name(),
+        message: message.to_string(),
+        primary_label: label(files, file_mapping, primary_label),
+        secondary_labels: secondary_labels
+            .iter()
+            .map(|lbl| label(files, file_mapping, lbl))
+            .collect(),
+        notes: notes.clone(),
+    }
+}
+
+fn structured_severity(info: &DiagnosticInfo) -> DiagnosticSeverity {
+    match info.severity() {
+        Severity::Warning => DiagnosticSeverity::Warning,
+        Severity::NonblockingError | Severity::BlockingError => DiagnosticSeverity::Error,
+        Severity::Bug => DiagnosticSeverity::Bug,
+    }
+}
+
+fn label(
+    files: &SimpleFiles<Symbol, &str>,
+    file_mapping: &FileMapping,
+    (loc, message): &(Loc, String),
+) -> Label {
+    let id = file_mapping[&loc.file_hash()];
+    let position = |byte_index: u32| {
+        let location = files.location(id, byte_index as usize).unwrap();
+        Position {
+            line: location.line_number,
+            column: location.column_number,
+        }
+    };
+    Label {
+        location: Location {
+            file: files.name(id).unwrap().to_string(),
+            start: position(loc.start()),
+            end: position(loc.end()),
+        },
+        message: message.clone(),
+    }
+}
+
+//**************************************************************************************************
+// Output
+//**************************************************************************************************
+
+pub(crate) fn to_json(diagnostics: Vec<StructuredDiagnostic>) -> String {
+    let document = JsonDiagnostics {
+        version: JSON_FORMAT_VERSION,
+        diagnostics,
+    };
+    serde_json::to_string_pretty(&document).unwrap()
+}
+
+pub(crate) fn to_sarif(diagnostics: Vec<StructuredDiagnostic>) -> String {
+    let sarif_location = |location: &Location| {
+        json!({
+            "physicalLocation": {
+                "artifactLocation": { "uri": location.file },
+                "region": {
+                    "startLine": location.start.line,
+                    "startColumn": location.start.column,
+                    "endLine": location.end.line,
+                    "endColumn": location.end.column,
+                },
+            },
+        })
+    };
+    let rules = diagnostics
+        .iter()
+        .map(|diag| {
+            let rule = json!({
+                "id": diag.code,
+                "shortDescription": { "text": diag.message },
+                "properties": { "category": diag.category },
+            });
+            (diag.code.clone(), rule)
+        })
+        .collect::<BTreeMap<_, _>>();
+    let results = diagnostics
+        .iter()
+        .map(|diag| {
+            let level = match diag.severity {
+                DiagnosticSeverity::Warning => "warning",
+                DiagnosticSeverity::Error | DiagnosticSeverity::Bug => "error",
+            };
+            let mut text = format!("{}: {}", diag.message, diag.primary_label.message);
+            for note in &diag.notes {
+                text.push('\n');
+                text.push_str(note);
+            }
+            let related_locations = diag
+                .secondary_labels
+                .iter()
+                .map(|lbl| {
+                    let mut location = sarif_location(&lbl.location);
+                    location["message"] = json!({ "text": lbl.message });
+                    location
+                })
+                .collect::<Vec<_>>();
+            json!({
+                "ruleId": diag.code,
+                "level": level,
+                "message": { "text": text },
+                "locations": [sarif_location(&diag.primary_label.location)],
+                "relatedLocations": related_locations,
+            })
+        })
+        .collect::<Vec<_>>();
+    let log = json!({
+        "$schema": SARIF_SCHEMA,
+        "version": SARIF_VERSION,
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": TOOL_NAME,
+                    "rules": rules.into_values().collect::<Vec<_>>(),
+                },
+            },
+            "results": results,
+        }],
+    });
+    serde_json::to_string_pretty(&log).unwrap()
+}
+
+//**************************************************************************************************
+// traits
+//**************************************************************************************************
+
+impl Default for DiagnosticsFormat {
+    fn default() -> Self {
+        Self::Human
+    }
+}
+
+impl FromStr for DiagnosticsFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(Self::Human),
+            "json" => Ok(Self::Json),
+            "sarif" => Ok(Self::Sarif),
+            _ => Err(format!(
+                "Unknown diagnostics format '{}'. Expected one of 'human', 'json' or 'sarif'",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for DiagnosticsFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Human => write!(f, "human"),
+            Self::Json => write!(f, "json"),
+            Self::Sarif => write!(f, "sarif"),
+        }
+    }
+}
@@ -4,13 +4,15 @@
 use crate::{
     parser::ast::{
         self as P, Ability, Ability_, BinOp, ConstantName, Field, FunctionName, ModuleName,
-        QuantKind, SpecApplyPattern, StructName, UnaryOp, Var, ENTRY_MODIFIER,
+        QuantKind, SpecApplyPattern, StructName, UnaryOp, Var, VariantName, ENTRY_MODIFIER,
+        INLINE_MODIFIER,
     },
     shared::{
         ast_debug::*, known_attributes::KnownAttribute, unique_map::UniqueMap,
         unique_set::UniqueSet, *,
     },
 };
+use move_core_types::u256::U256;
 use move_ir_types::location::*;
 use move_symbol_pool::Symbol;
 use std::{
@@ -37,6 +39,7 @@ pub struct Program {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum AttributeValue_ {
     Value(Value),
+    Vector(Vec<AttributeValue>),
     ModuleAccess(ModuleAccess),
 }
 pub type AttributeValue = Spanned<AttributeValue_>;
@@ -142,6 +145,7 @@ pub enum Neighbor {
 //**************************************************************************************************
 
 pub type Fields<T> = UniqueMap<Field, (usize, T)>;
+pub type Variants<T> = UniqueMap<VariantName, (usize, Fields<T>)>;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct StructTypeParameter {
@@ -163,6 +167,7 @@ pub struct StructDefinition {
 pub enum StructFields {
     Defined(Fields<Type>),
     Native(Loc),
+    Variants(Variants<Type>),
 }
 
 //**************************************************************************************************
@@ -199,6 +204,7 @@ pub struct Function {
     pub loc: Loc,
     pub visibility: Visibility,
     pub entry: Option<Loc>,
+    pub inline: bool,
     pub signature: FunctionSignature,
     pub acquires: Vec<ModuleAccess>,
     pub body: FunctionBody,
@@ -213,6 +219,7 @@ pub struct Function {
 pub struct Constant {
     pub attributes: Attributes,
     pub loc: Loc,
+    pub visibility: Visibility,
     pub signature: Type,
     pub value: Exp,
 }
@@ -355,6 +362,14 @@ pub type LValue = Spanned<LValue_>;
 pub type LValueList_ = Vec<LValue>;
 pub type LValueList = Spanned<LValueList_>;
 
+#[derive(Debug, Clone, PartialEq)]
+pub enum MatchPattern_ {
+    Wildcard,
+    Variant(ModuleAccess, VariantName, Option<Vec<Type>>, Fields<LValue>),
+}
+pub type MatchPattern = Spanned<MatchPattern_>;
+pub type MatchArm = Spanned<(MatchPattern, Exp)>;
+
 pub type LValueWithRange_ = (LValue, Exp);
 pub type LValueWithRange = Spanned<LValueWithRange_>;
 pub type LValueWithRangeList_ = Vec<LValueWithRange>;
@@ -373,13 +388,19 @@ pub enum Value_ {
     // 0x<hex representation up to 64 digits with padding 0s>
     Address(Address),
     // <num>
-    InferredNum(u128),
+    InferredNum(U256),
     // <num>u8
     U8(u8),
+    // <num>u16
+    U16(u16),
+    // <num>u32
+    U32(u32),
     // <num>u64
     U64(u64),
     // <num>u128
     U128(u128),
+    // <num>u256
+    U256(U256),
     // true
     // false
     Bool(bool),
@@ -402,13 +423,15 @@ pub enum Exp_ {
         Spanned<Vec<Exp>>,
     ),
     Pack(ModuleAccess, Option<Vec<Type>>, Fields<Exp>),
+    PackVariant(ModuleAccess, VariantName, Option<Vec<Type>>, Fields<Exp>),
     Vector(Loc, Option<Vec<Type>>, Spanned<Vec<Exp>>),
 
     IfElse(Box<Exp>, Box<Exp>, Box<Exp>),
+    Match(Box<Exp>, Vec<MatchArm>),
     While(Box<Exp>, Box<Exp>),
     Loop(Box<Exp>),
     Block(Sequence),
-    Lambda(LValueList, Box<Exp>), // spec only
+    Lambda(LValueList, Box<Exp>),
     Quant(
         QuantKind,
         LValueWithRangeList,
@@ -585,7 +608,7 @@ impl AbilitySet {
         Ability_::Store,
         Ability_::Key,
     ];
-    /// Abilities for bool, u8, u64, u128, and address
+    /// Abilities for bool, u8, u16, u32, u64, u128, u256, and address
     pub const PRIMITIVES: [Ability_; 3] = [Ability_::Copy, Ability_::Drop, Ability_::Store];
     /// Abilities for &_ and &mut _
     pub const REFERENCES: [Ability_; 2] = [Ability_::Copy, Ability_::Drop];
@@ -852,6 +875,11 @@ impl AstDebug for AttributeValue_ {
     fn ast_debug(&self, w: &mut AstWriter) {
         match self {
             AttributeValue_::Value(v) => v.ast_debug(w),
+            AttributeValue_::Vector(vs) => {
+                w.write("vector[");
+                w.comma(vs, |w, v| v.ast_debug(w));
+                w.write("]");
+            }
             AttributeValue_::ModuleAccess(n) => n.ast_debug(w),
         }
     }
@@ -1009,26 +1037,37 @@ impl AstDebug for (StructName, &StructDefinition) {
 
         attributes.ast_debug(w);
 
-        if let StructFields::Native(_) = fields {
-            w.write("native ");
+        match fields {
+            StructFields::Native(_) => w.write(&format!("native struct {}", name)),
+            StructFields::Defined(_) => w.write(&format!("struct {}", name)),
+            StructFields::Variants(_) => w.write(&format!("enum {}", name)),
         }
-
-        w.write(&format!("struct {}", name));
         type_parameters.ast_debug(w);
         ability_modifiers_ast_debug(w, abilities);
-        if let StructFields::Defined(fields) = fields {
-            w.block(|w| {
-                w.list(fields, ",", |w, (_, f, idx_st)| {
-                    let (idx, st) = idx_st;
-                    w.write(&format!("{}#{}: ", idx, f));
-                    st.ast_debug(w);
+        match fields {
+            StructFields::Defined(fields) => w.block(|w| fields_ast_debug(w, fields)),
+            StructFields::Variants(variants) => w.block(|w| {
+                w.list(variants, ",", |w, (_, v, idx_fields)| {
+                    let (idx, fields) = idx_fields;
+                    w.write(&format!("{}#{}", idx, v));
+                    w.block(|w| fields_ast_debug(w, fields));
                     true
                 });
-            })
+            }),
+            StructFields::Native(_) => (),
         }
     }
 }
 
+fn fields_ast_debug(w: &mut AstWriter, fields: &Fields<Type>) {
+    w.list(fields, ",", |w, (_, f, idx_st)| {
+        let (idx, st) = idx_st;
+        w.write(&format!("{}#{}: ", idx, f));
+        st.ast_debug(w);
+        true
+    });
+}
+
 impl AstDebug for SpecBlock_ {
     fn ast_debug(&self, w: &mut AstWriter) {
         w.write(" spec ");
@@ -1224,6 +1263,7 @@ impl AstDebug for (FunctionName, &Function) {
                 loc: _loc,
                 visibility,
                 entry,
+                inline,
                 signature,
                 acquires,
                 body,
@@ -1235,6 +1275,9 @@ impl AstDebug for (FunctionName, &Function) {
         if entry.is_some() {
             w.write(&format!("{} ", ENTRY_MODIFIER));
         }
+        if *inline {
+            w.write(&format!("{} ", INLINE_MODIFIER));
+        }
         if let FunctionBody_::Native = &body.value {
             w.write("native ");
         }
@@ -1283,11 +1326,13 @@ impl AstDebug for (ConstantName, &Constant) {
             Constant {
                 attributes,
                 loc: _loc,
+                visibility,
                 signature,
                 value,
             },
         ) = self;
         attributes.ast_debug(w);
+        visibility.ast_debug(w);
         w.write(&format!("const {}:", name));
         signature.ast_debug(w);
         w.write(" = ");
@@ -1434,8 +1479,11 @@ impl AstDebug for Value_ {
             V::Address(addr) => w.write(&format!("@{}", addr)),
             V::InferredNum(u) => w.write(&format!("{}", u)),
             V::U8(u) => w.write(&format!("{}u8", u)),
+            V::U16(u) => w.write(&format!("{}u16", u)),
+            V::U32(u) => w.write(&format!("{}u32", u)),
             V::U64(u) => w.write(&format!("{}u64", u)),
             V::U128(u) => w.write(&format!("{}u128", u)),
+            V::U256(u) => w.write(&format!("{}u256", u)),
             V::Bool(b) => w.write(&format!("{}", b)),
             V::Bytearray(v) => w.write(&format!("{:?}", v)),
         }
@@ -1490,6 +1538,22 @@ impl AstDebug for Exp_ {
                 });
                 w.write("}");
             }
+            E::PackVariant(ma, v, tys_opt, fields) => {
+                ma.ast_debug(w);
+                w.write(&format!("::{}", v));
+                if let Some(ss) = tys_opt {
+                    w.write("<");
+                    ss.ast_debug(w);
+                    w.write(">");
+                }
+                w.write("{");
+                w.comma(fields, |w, (_, f, idx_e)| {
+                    let (idx, e) = idx_e;
+                    w.write(&format!("{}#{}: ", idx, f));
+                    e.ast_debug(w);
+                });
+                w.write("}");
+            }
             E::Vector(_loc, tys_opt, sp!(_, elems)) => {
                 w.write("vector");
                 if let Some(ss) = tys_opt {
@@ -1509,6 +1573,18 @@ impl AstDebug for Exp_ {
                 w.write(" else ");
                 f.ast_debug(w);
             }
+            E::Match(e, arms) => {
+                w.write("match (");
+                e.ast_debug(w);
+                w.write(") ");
+                w.block(|w| {
+                    w.comma(arms, |w, sp!(_, (pattern, rhs))| {
+                        pattern.ast_debug(w);
+                        w.write(" => ");
+                        rhs.ast_debug(w);
+                    })
+                });
+            }
             E::While(b, e) => {
                 w.write("while (");
                 b.ast_debug(w);
@@ -1654,6 +1730,30 @@ impl AstDebug for Vec<LValue> {
     }
 }
 
+impl AstDebug for MatchPattern_ {
+    fn ast_debug(&self, w: &mut AstWriter) {
+        match self {
+            MatchPattern_::Wildcard => w.write("_"),
+            MatchPattern_::Variant(ma, v, tys_opt, fields) => {
+                ma.ast_debug(w);
+                w.write(&format!("::{}", v));
+                if let Some(ss) = tys_opt {
+                    w.write("<");
+                    ss.ast_debug(w);
+                    w.write(">");
+                }
+                w.write("{");
+                w.comma(fields, |w, (_, f, idx_b)| {
+                    let (idx, b) = idx_b;
+                    w.write(&format!("{}#{}: ", idx, f));
+                    b.ast_debug(w);
+                });
+                w.write("}");
+            }
+        }
+    }
+}
+
 impl AstDebug for LValue_ {
     fn ast_debug(&self, w: &mut AstWriter) {
         use LValue_ as L;
@@ -276,6 +276,9 @@ fn module(context: &mut Context, mident: ModuleIdent, mdef: &E::ModuleDefinition
     mdef.structs
         .iter()
         .for_each(|(_, _, sdef)| struct_def(context, sdef));
+    mdef.constants
+        .iter()
+        .for_each(|(_, _, cdef)| constant(context, cdef));
     mdef.functions
         .iter()
         .for_each(|(_, _, fdef)| function(context, fdef));
@@ -299,6 +302,9 @@ fn script_defs(context: &mut Context, scripts: &BTreeMap<Symbol, E::Script>) {
 
 fn script(context: &mut Context, sname: Symbol, sdef: &E::Script) {
     context.current_node = Some(NodeIdent::Script(sname));
+    sdef.constants
+        .iter()
+        .for_each(|(_, _, cdef)| constant(context, cdef));
     function(context, &sdef.function);
     sdef.specs
         .iter()
@@ -336,11 +342,28 @@ fn function_acquires(context: &mut Context, acqs: &[E::ModuleAccess]) {
 //**************************************************************************************************
 
 fn struct_def(context: &mut Context, sdef: &E::StructDefinition) {
-    if let E::StructFields::Defined(fields) = &sdef.fields {
-        fields.iter().for_each(|(_, _, (_, bt))| type_(context, bt));
+    match &sdef.fields {
+        E::StructFields::Defined(fields) => {
+            fields.iter().for_each(|(_, _, (_, bt))| type_(context, bt))
+        }
+        E::StructFields::Variants(variants) => {
+            for (_, _, (_, fields)) in variants {
+                fields.iter().for_each(|(_, _, (_, bt))| type_(context, bt))
+            }
+        }
+        E::StructFields::Native(_) => (),
     }
 }
 
+//**************************************************************************************************
+// Constants
+//**************************************************************************************************
+
+fn constant(context: &mut Context, cdef: &E::Constant) {
+    type_(context, &cdef.signature);
+    exp(context, &cdef.value)
+}
+
 //**************************************************************************************************
 // Types
 //**************************************************************************************************
@@ -423,7 +446,7 @@ fn lvalue(context: &mut Context, sp!(_loc, a_): &E::LValue) {
 }
 
 fn exp(context: &mut Context, sp!(_loc, e_): &E::Exp) {
-    use crate::expansion::ast::{Exp_ as E, Value_ as V};
+    use crate::expansion::ast::{Exp_ as E, MatchPattern_, Value_ as V};
     match e_ {
         E::Value(sp!(_, V::Address(a))) => context.add_address_usage(*a),
 
@@ -445,11 +468,22 @@ fn exp(context: &mut Context, sp!(_loc, e_): &E::Exp) {
             types_opt(context, tys_opt);
             args_.iter().for_each(|e| exp(context, e))
         }
-        E::Pack(ma, tys_opt, fields) => {
+        E::Pack(ma, tys_opt, fields) | E::PackVariant(ma, _, tys_opt, fields) => {
             module_access(context, ma);
             types_opt(context, tys_opt);
             fields.iter().for_each(|(_, _, (_, e))| exp(context, e))
         }
+        E::Match(e, arms) => {
+            exp(context, e);
+            for sp!(_, (pat, earm)) in arms {
+                if let sp!(_, MatchPattern_::Variant(ma, _, tys_opt, fields)) = pat {
+                    module_access(context, ma);
+                    types_opt(context, tys_opt);
+                    lvalues(context, fields.iter().map(|(_, _, (_, b))| b));
+                }
+                exp(context, earm)
+            }
+        }
         E::Vector(_vec_loc, tys_opt, sp!(_, args_)) => {
             types_opt(context, tys_opt);
             args_.iter().for_each(|e| exp(context, e))
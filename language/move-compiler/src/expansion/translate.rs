@@ -3,7 +3,7 @@
 
 use crate::{
     diag,
-    diagnostics::Diagnostic,
+    diagnostics::{codes::WarningFilter, Diagnostic},
     expansion::{
         aliases::{AliasMap, AliasSet},
         ast::{self as E, Address, Fields, ModuleIdent, ModuleIdent_, SpecId},
@@ -11,8 +11,15 @@ use crate::{
     },
     parser::ast::{
         self as P, Ability, ConstantName, Field, FunctionName, ModuleName, StructName, Var,
+        VariantName,
+    },
+    shared::{
+        known_attributes::{
+            AttributePosition, DeprecationAttribute, DiagnosticAttribute, KnownAttribute,
+        },
+        unique_map::UniqueMap,
+        *,
     },
-    shared::{known_attributes::AttributePosition, unique_map::UniqueMap, *},
     FullyCompiledProgram,
 };
 use move_ir_types::location::*;
@@ -398,6 +405,8 @@ fn module_(
         members,
     } = mdef;
     let attributes = flatten_attributes(context, AttributePosition::Module, attributes);
+    allowed_warnings(context, loc, &attributes);
+    check_deprecation(context, &attributes);
     assert!(context.address == None);
     assert!(address == None);
     set_sender_address(context, &name, module_address);
@@ -489,6 +498,7 @@ fn script_(context: &mut Context, package_name: Option<Symbol>, pscript: P::Scri
     } = pscript;
 
     let attributes = flatten_attributes(context, AttributePosition::Script, attributes);
+    allowed_warnings(context, loc, &attributes);
     let new_scope = uses(context, puses);
     let old_aliases = context.aliases.add_and_shadow_all(new_scope);
     assert!(
@@ -531,6 +541,16 @@ fn script_(context: &mut Context, package_name: Option<Symbol>, pscript: P::Scri
             ));
         }
     }
+    if function.inline {
+        let msg = format!(
+            "Invalid '{}' function. 'script' functions cannot be expanded at call sites",
+            P::INLINE_MODIFIER
+        );
+        context.env.add_diag(diag!(
+            Declarations::InvalidScript,
+            (function_name.loc(), msg)
+        ));
+    }
     let specs = specs(context, pspecs);
     context.set_to_outer_scope(old_aliases);
 
@@ -560,6 +580,108 @@ fn flatten_attributes(
     unique_attributes(context, attr_position, false, all_attrs)
 }
 
+/// Registers the warnings silenced by an `allow` attribute for the item at `loc`
+fn allowed_warnings(context: &mut Context, loc: Loc, attributes: &E::Attributes) {
+    let allow = E::AttributeName_::Known(KnownAttribute::Diagnostic(DiagnosticAttribute::Allow));
+    let sp!(attr_loc, attr_) = match attributes.get_(&allow) {
+        Some(attr) => attr,
+        None => return,
+    };
+    let names = match attr_ {
+        E::Attribute_::Parameterized(_, names) => names,
+        E::Attribute_::Name(_) | E::Attribute_::Assigned(_, _) => {
+            let msg = format!(
+                "Expected a list of warnings for attribute '{}', e.g. '{}(unused_variable)'",
+                DiagnosticAttribute::ALLOW,
+                DiagnosticAttribute::ALLOW,
+            );
+            context
+                .env
+                .add_diag(diag!(Attributes::InvalidValue, (*attr_loc, msg)));
+            return;
+        }
+    };
+    let mut filters = vec![];
+    for (_, sp!(name_loc, name_attr_)) in names.key_cloned_iter() {
+        let name = match name_attr_ {
+            E::Attribute_::Name(n) => n,
+            E::Attribute_::Assigned(n, _) | E::Attribute_::Parameterized(n, _) => {
+                let msg = format!("Expected the name of a warning, found '{}'", n);
+                context
+                    .env
+                    .add_diag(diag!(Attributes::InvalidValue, (*name_loc, msg)));
+                continue;
+            }
+        };
+        match name.value.as_str().parse::<WarningFilter>() {
+            Ok(filter) => filters.push(filter),
+            Err(msg) => context
+                .env
+                .add_diag(diag!(Attributes::UnknownWarningFilter, (name.loc, msg))),
+        }
+    }
+    context.env.add_allowed_warnings(loc, filters)
+}
+
+/// An item annotated with a `deprecated` attribute
+pub struct Deprecation {
+    /// The location of the attribute
+    pub loc: Loc,
+    /// The note given with `deprecated(note = b"...")`, if any
+    pub note: Option<String>,
+}
+
+/// Returns the deprecation of the item with `attributes`, or the location and message of the error
+/// if its `deprecated` attribute is malformed
+pub fn deprecation(attributes: &E::Attributes) -> Result<Option<Deprecation>, (Loc, String)> {
+    let deprecated = E::AttributeName_::Known(KnownAttribute::Deprecation(
+        DeprecationAttribute::Deprecated,
+    ));
+    let sp!(loc, attr_) = match attributes.get_(&deprecated) {
+        Some(attr) => attr,
+        None => return Ok(None),
+    };
+    let note = match attr_ {
+        E::Attribute_::Name(_) => None,
+        E::Attribute_::Parameterized(_, inner) if inner.len() == 1 => {
+            let note = E::AttributeName_::Unknown(DeprecationAttribute::NOTE.into());
+            match inner.get_(&note).map(|attr| &attr.value) {
+                Some(E::Attribute_::Assigned(_, value)) => match &value.value {
+                    E::AttributeValue_::Value(sp!(_, E::Value_::Bytearray(bytes))) => {
+                        Some(String::from_utf8_lossy(bytes).into_owned())
+                    }
+                    _ => {
+                        let msg = "Expected a byte string for the note, e.g. 'note = b\"...\"'";
+                        return Err((value.loc, msg.to_owned()));
+                    }
+                },
+                _ => return Err((*loc, deprecation_usage_msg())),
+            }
+        }
+        E::Attribute_::Parameterized(_, _) | E::Attribute_::Assigned(_, _) => {
+            return Err((*loc, deprecation_usage_msg()))
+        }
+    };
+    Ok(Some(Deprecation { loc: *loc, note }))
+}
+
+fn deprecation_usage_msg() -> String {
+    format!(
+        "Expected either '{}' or '{}({} = b\"...\")'",
+        DeprecationAttribute::DEPRECATED,
+        DeprecationAttribute::DEPRECATED,
+        DeprecationAttribute::NOTE,
+    )
+}
+
+fn check_deprecation(context: &mut Context, attributes: &E::Attributes) {
+    if let Err((loc, msg)) = deprecation(attributes) {
+        context
+            .env
+            .add_diag(diag!(Attributes::InvalidValue, (loc, msg)))
+    }
+}
+
 fn unique_attributes(
     context: &mut Context,
     attr_position: AttributePosition,
@@ -656,6 +778,11 @@ fn attribute_value(
         loc,
         match avalue_ {
             PV::Value(v) => EV::Value(value(context, v)?),
+            PV::Vector(vs) => EV::Vector(
+                vs.into_iter()
+                    .map(|v| attribute_value(context, v))
+                    .collect::<Option<_>>()?,
+            ),
             PV::ModuleAccess(ma) => EV::ModuleAccess(name_access_chain(context, Access::Type, ma)?),
         },
     ))
@@ -734,7 +861,13 @@ fn module_members(
                 cur_members.insert(c.name.0, ModuleMemberKind::Constant);
             }
             P::ModuleMember::Struct(s) => {
-                cur_members.insert(s.name.0, ModuleMemberKind::Struct);
+                let kind = match &s.fields {
+                    P::StructFields::Variants(_) => ModuleMemberKind::Enum,
+                    P::StructFields::Defined(_) | P::StructFields::Native(_) => {
+                        ModuleMemberKind::Struct
+                    }
+                };
+                cur_members.insert(s.name.0, kind);
             }
             P::ModuleMember::Spec(
                 sp!(
@@ -810,7 +943,11 @@ fn aliases_from_member(
         }
         P::ModuleMember::Struct(s) => {
             let n = s.name.0;
-            check_name_and_add_implicit_alias!(ModuleMemberKind::Struct, n);
+            let kind = match &s.fields {
+                P::StructFields::Variants(_) => ModuleMemberKind::Enum,
+                _ => ModuleMemberKind::Struct,
+            };
+            check_name_and_add_implicit_alias!(kind, n);
             Some(P::ModuleMember::Struct(s))
         }
         P::ModuleMember::Spec(s) => {
@@ -1008,12 +1145,14 @@ fn struct_def_(
         fields: pfields,
     } = pstruct;
     let attributes = flatten_attributes(context, AttributePosition::Struct, attributes);
+    allowed_warnings(context, loc, &attributes);
+    check_deprecation(context, &attributes);
     let type_parameters = struct_type_parameters(context, pty_params);
     let old_aliases = context
         .aliases
         .shadow_for_type_parameters(type_parameters.iter().map(|tp| &tp.name));
     let abilities = ability_set(context, "modifier", abilities_vec);
-    let fields = struct_fields(context, &name, pfields);
+    let fields = struct_fields(context, loc, &name, pfields);
     let sdef = E::StructDefinition {
         attributes,
         loc,
@@ -1027,13 +1166,67 @@ fn struct_def_(
 
 fn struct_fields(
     context: &mut Context,
+    loc: Loc,
     sname: &StructName,
     pfields: P::StructFields,
 ) -> E::StructFields {
-    let pfields_vec = match pfields {
-        P::StructFields::Native(loc) => return E::StructFields::Native(loc),
-        P::StructFields::Defined(v) => v,
-    };
+    match pfields {
+        P::StructFields::Native(loc) => E::StructFields::Native(loc),
+        P::StructFields::Defined(pfields_vec) => E::StructFields::Defined(struct_field_map(
+            context,
+            &format!("struct '{}'", sname),
+            pfields_vec,
+        )),
+        P::StructFields::Variants(pvariants) => {
+            if pvariants.is_empty() {
+                context.env.add_diag(diag!(
+                    Declarations::InvalidStruct,
+                    (
+                        loc,
+                        format!(
+                            "Invalid enum declaration '{}'. An enum must have at least one \
+                             variant",
+                            sname
+                        ),
+                    ),
+                ));
+            }
+            let mut variant_map = UniqueMap::new();
+            for (idx, pvariant) in pvariants.into_iter().enumerate() {
+                let P::VariantDefinition {
+                    loc: _,
+                    name,
+                    fields: pfields_vec,
+                } = pvariant;
+                let field_map = struct_field_map(
+                    context,
+                    &format!("variant '{}::{}'", sname, name),
+                    pfields_vec,
+                );
+                if let Err((name, old_loc)) = variant_map.add(name, (idx, field_map)) {
+                    context.env.add_diag(diag!(
+                        Declarations::DuplicateItem,
+                        (
+                            name.loc(),
+                            format!(
+                                "Duplicate definition for variant '{}' in enum '{}'",
+                                name, sname
+                            ),
+                        ),
+                        (old_loc, "Variant previously defined here"),
+                    ));
+                }
+            }
+            E::StructFields::Variants(variant_map)
+        }
+    }
+}
+
+fn struct_field_map(
+    context: &mut Context,
+    owner: &str,
+    pfields_vec: Vec<(Field, P::Type)>,
+) -> E::Fields<E::Type> {
     let mut field_map = UniqueMap::new();
     for (idx, (field, pt)) in pfields_vec.into_iter().enumerate() {
         let t = type_(context, pt);
@@ -1042,16 +1235,13 @@ fn struct_fields(
                 Declarations::DuplicateItem,
                 (
                     field.loc(),
-                    format!(
-                        "Duplicate definition for field '{}' in struct '{}'",
-                        field, sname
-                    ),
+                    format!("Duplicate definition for field '{}' in {}", field, owner),
                 ),
                 (old_loc, "Field previously defined here"),
             ));
         }
     }
-    E::StructFields::Defined(field_map)
+    field_map
 }
 
 //**************************************************************************************************
@@ -1115,17 +1305,22 @@ fn constant_(context: &mut Context, pconstant: P::Constant) -> (ConstantName, E:
     let P::Constant {
         attributes: pattributes,
         loc,
+        visibility: pvisibility,
         name,
         signature: psignature,
         value: pvalue,
     } = pconstant;
     let attributes = flatten_attributes(context, AttributePosition::Constant, pattributes);
+    allowed_warnings(context, loc, &attributes);
+    check_deprecation(context, &attributes);
+    let visibility = visibility(context, pvisibility);
     let signature = type_(context, psignature);
     let value = exp_(context, pvalue);
     let _specs = context.extract_exp_specs();
     let constant = E::Constant {
         attributes,
         loc,
+        visibility,
         signature,
         value,
     };
@@ -1154,14 +1349,17 @@ fn function_(context: &mut Context, pfunction: P::Function) -> (FunctionName, E:
         name,
         visibility: pvisibility,
         entry,
+        inline,
         signature: psignature,
         body: pbody,
         acquires,
     } = pfunction;
     assert!(context.exp_specs.is_empty());
     let attributes = flatten_attributes(context, AttributePosition::Function, pattributes);
+    allowed_warnings(context, loc, &attributes);
+    check_deprecation(context, &attributes);
     let visibility = visibility(context, pvisibility);
-    let (old_aliases, signature) = function_signature(context, psignature);
+    let (old_aliases, signature) = function_signature(context, inline, psignature);
     let acquires = acquires
         .into_iter()
         .flat_map(|a| name_access_chain(context, Access::Type, a))
@@ -1173,6 +1371,7 @@ fn function_(context: &mut Context, pfunction: P::Function) -> (FunctionName, E:
         loc,
         visibility,
         entry,
+        inline,
         signature,
         acquires,
         body,
@@ -1196,6 +1395,7 @@ fn visibility(context: &mut Context, pvisibility: P::Visibility) -> E::Visibilit
 
 fn function_signature(
     context: &mut Context,
+    inline: bool,
     psignature: P::FunctionSignature,
 ) -> (OldAliasMap, E::FunctionSignature) {
     let P::FunctionSignature {
@@ -1209,7 +1409,7 @@ fn function_signature(
         .shadow_for_type_parameters(type_parameters.iter().map(|(name, _)| name));
     let parameters = pparams
         .into_iter()
-        .map(|(v, t)| (v, type_(context, t)))
+        .map(|(v, t)| (v, parameter_type(context, inline, t)))
         .collect::<Vec<_>>();
     for (v, _) in &parameters {
         check_valid_local_name(context, v)
@@ -1223,6 +1423,19 @@ fn function_signature(
     (old_aliases, signature)
 }
 
+// The parameters of inline functions can have function types, which take the lambdas given at
+// the call sites
+fn parameter_type(context: &mut Context, inline: bool, sp!(loc, pt_): P::Type) -> E::Type {
+    match pt_ {
+        P::Type_::Fun(args, result) if inline => {
+            let args = types(context, args);
+            let result = type_(context, *result);
+            sp(loc, E::Type_::Fun(args, Box::new(result)))
+        }
+        pt_ => type_(context, sp(loc, pt_)),
+    }
+}
+
 fn function_body(context: &mut Context, sp!(loc, pbody_): P::FunctionBody) -> E::FunctionBody {
     use E::FunctionBody_ as EF;
     use P::FunctionBody_ as PF;
@@ -1282,7 +1495,7 @@ fn spec_target(context: &mut Context, sp!(loc, pt): P::SpecBlockTarget) -> E::Sp
         PT::Member(name, signature_opt) => ET::Member(
             name,
             signature_opt.map(|s| {
-                let (old_aliases, signature) = function_signature(context, *s);
+                let (old_aliases, signature) = function_signature(context, false, *s);
                 context.set_to_outer_scope(old_aliases);
                 Box::new(signature)
             }),
@@ -1374,7 +1587,7 @@ fn spec_member(context: &mut Context, sp!(loc, pm): P::SpecBlockMember) -> E::Sp
             signature,
             body,
         } => {
-            let (old_aliases, signature) = function_signature(context, signature);
+            let (old_aliases, signature) = function_signature(context, false, signature);
             let body = function_body(context, body);
             context.set_to_outer_scope(old_aliases);
             EM::Function {
@@ -1548,7 +1761,11 @@ fn type_(context: &mut Context, sp!(loc, pt_): P::Type) -> E::Type {
             } else {
                 context.env.add_diag(diag!(
                     Syntax::SpecContextRestricted,
-                    (loc, "`|_|_` function type only allowed in specifications")
+                    (
+                        loc,
+                        "`|_|_` function type only allowed in specifications and for the \
+                         parameters of 'inline' functions"
+                    )
                 ));
                 ET::UnresolvedError
             }
@@ -1621,6 +1838,29 @@ fn name_access_chain(
     Some(sp(loc, tn_))
 }
 
+/// Resolves `E::V` to the variant `V` of the enum `E`, if `E` is not a module alias but an alias
+/// for an enum. Returns `None` otherwise, leaving the access to be resolved as usual.
+fn variant_name_access_chain(
+    context: &mut Context,
+    sp!(_, ptn_): &P::NameAccessChain,
+) -> Option<(E::ModuleAccess, VariantName)> {
+    use P::{LeadingNameAccess_ as LN, NameAccessChain_ as PN};
+    let (n1, n2) = match ptn_ {
+        PN::Two(sp!(_, LN::Name(n1)), n2) => (n1, n2),
+        _ => return None,
+    };
+    if context.aliases.module_alias_get(n1).is_some() {
+        return None;
+    }
+    let (mident, mem) = context.aliases.member_alias_get(n1)?;
+    let kind = context.module_members.get(&mident)?.get(&mem)?;
+    if *kind != ModuleMemberKind::Enum {
+        return None;
+    }
+    let en = sp(n1.loc, E::ModuleAccess_::ModuleAccess(mident, mem));
+    Some((en, VariantName(*n2)))
+}
+
 fn name_access_chain_to_module_ident(
     context: &mut Context,
     sp!(loc, pn_): P::NameAccessChain,
@@ -1767,6 +2007,11 @@ fn exp_(context: &mut Context, sp!(loc, pe_): P::Exp) -> E::Exp {
         },
         PE::Move(v) => EE::Move(v),
         PE::Copy(v) => EE::Copy(v),
+        PE::Name(pn, ptys_opt) if variant_name_access_chain(context, &pn).is_some() => {
+            let (en, v) = variant_name_access_chain(context, &pn).unwrap();
+            let tys_opt = optional_types(context, ptys_opt);
+            EE::PackVariant(en, v, tys_opt, UniqueMap::new())
+        }
         PE::Name(_, Some(_)) if !context.in_spec_context => {
             context.env.add_diag(diag!(
                 Syntax::SpecContextRestricted,
@@ -1801,22 +2046,7 @@ fn exp_(context: &mut Context, sp!(loc, pe_): P::Exp) -> E::Exp {
                 }
             }
         }
-        PE::Pack(pn, ptys_opt, pfields) => {
-            let en_opt = name_access_chain(context, Access::ApplyNamed, pn);
-            let tys_opt = optional_types(context, ptys_opt);
-            let efields_vec = pfields
-                .into_iter()
-                .map(|(f, pe)| (f, exp_(context, pe)))
-                .collect();
-            let efields = fields(context, loc, "construction", "argument", efields_vec);
-            match en_opt {
-                Some(en) => EE::Pack(en, tys_opt, efields),
-                None => {
-                    assert!(context.env.has_diags());
-                    EE::UnresolvedError
-                }
-            }
-        }
+        PE::Pack(pn, ptys_opt, pfields) => pack(context, loc, pn, ptys_opt, pfields),
         PE::Vector(vec_loc, ptys_opt, sp!(args_loc, pargs_)) => {
             let tys_opt = optional_types(context, ptys_opt);
             let args = sp(args_loc, exps(context, pargs_));
@@ -1834,22 +2064,17 @@ fn exp_(context: &mut Context, sp!(loc, pe_): P::Exp) -> E::Exp {
         PE::While(pb, ploop) => EE::While(exp(context, *pb), exp(context, *ploop)),
         PE::Loop(ploop) => EE::Loop(exp(context, *ploop)),
         PE::Block(seq) => EE::Block(sequence(context, loc, seq)),
+        PE::Match(pe, parms) => match_(context, *pe, parms),
+        // Outside of specifications, whether the lambda is an argument of an inline function is
+        // checked during typing
         PE::Lambda(pbs, pe) => {
-            if !context.in_spec_context {
-                context.env.add_diag(diag!(
-                    Syntax::SpecContextRestricted,
-                    (loc, "lambda expression only allowed in specifications"),
-                ));
-                EE::UnresolvedError
-            } else {
-                let bs_opt = bind_list(context, pbs);
-                let e = exp_(context, *pe);
-                match bs_opt {
-                    Some(bs) => EE::Lambda(bs, Box::new(e)),
-                    None => {
-                        assert!(context.env.has_diags());
-                        EE::UnresolvedError
-                    }
+            let bs_opt = bind_list(context, pbs);
+            let e = exp_(context, *pe);
+            match bs_opt {
+                Some(bs) => EE::Lambda(bs, Box::new(e)),
+                None => {
+                    assert!(context.env.has_diags());
+                    EE::UnresolvedError
                 }
             }
         }
@@ -1986,6 +2211,20 @@ fn value(context: &mut Context, sp!(loc, pvalue_): P::Value) -> Option<E::Value>
                 return None;
             }
         },
+        PV::Num(s) if s.ends_with("u16") => match parse_u16(&s[..s.len() - 3]) {
+            Ok((u, _format)) => EV::U16(u),
+            Err(_) => {
+                context.env.add_diag(num_too_big_error(loc, "'u16'"));
+                return None;
+            }
+        },
+        PV::Num(s) if s.ends_with("u32") => match parse_u32(&s[..s.len() - 3]) {
+            Ok((u, _format)) => EV::U32(u),
+            Err(_) => {
+                context.env.add_diag(num_too_big_error(loc, "'u32'"));
+                return None;
+            }
+        },
         PV::Num(s) if s.ends_with("u64") => match parse_u64(&s[..s.len() - 3]) {
             Ok((u, _format)) => EV::U64(u),
             Err(_) => {
@@ -2000,12 +2239,19 @@ fn value(context: &mut Context, sp!(loc, pvalue_): P::Value) -> Option<E::Value>
                 return None;
             }
         },
-        PV::Num(s) => match parse_u128(&s) {
+        PV::Num(s) if s.ends_with("u256") => match parse_u256(&s[..s.len() - 4]) {
+            Ok((u, _format)) => EV::U256(u),
+            Err(_) => {
+                context.env.add_diag(num_too_big_error(loc, "'u256'"));
+                return None;
+            }
+        },
+        PV::Num(s) => match parse_u256(&s) {
             Ok((u, _format)) => EV::InferredNum(u),
             Err(_) => {
                 context.env.add_diag(num_too_big_error(
                     loc,
-                    "the largest possible integer type, 'u128'",
+                    "the largest possible integer type, 'u256'",
                 ));
                 return None;
             }
@@ -2044,6 +2290,94 @@ fn num_too_big_error(loc: Loc, type_description: &'static str) -> Diagnostic {
     )
 }
 
+// Kept out of `exp_`, as every arm there adds to the stack frame of each nested expression
+fn pack(
+    context: &mut Context,
+    loc: Loc,
+    pn: P::NameAccessChain,
+    ptys_opt: Option<Vec<P::Type>>,
+    pfields: Vec<(Field, P::Exp)>,
+) -> E::Exp_ {
+    let variant_opt = variant_name_access_chain(context, &pn);
+    let en_opt = match &variant_opt {
+        Some(_) => None,
+        None => name_access_chain(context, Access::ApplyNamed, pn),
+    };
+    let tys_opt = optional_types(context, ptys_opt);
+    let efields_vec = pfields
+        .into_iter()
+        .map(|(f, pe)| (f, exp_(context, pe)))
+        .collect();
+    let efields = fields(context, loc, "construction", "argument", efields_vec);
+    match (variant_opt, en_opt) {
+        (Some((en, v)), _) => E::Exp_::PackVariant(en, v, tys_opt, efields),
+        (None, Some(en)) => E::Exp_::Pack(en, tys_opt, efields),
+        (None, None) => {
+            assert!(context.env.has_diags());
+            E::Exp_::UnresolvedError
+        }
+    }
+}
+
+fn match_(context: &mut Context, pe: P::Exp, parms: Vec<P::MatchArm>) -> E::Exp_ {
+    let e = exp(context, pe);
+    let arms_opt: Option<Vec<E::MatchArm>> = parms
+        .into_iter()
+        .map(|parm| match_arm(context, parm))
+        .collect();
+    match arms_opt {
+        Some(arms) => E::Exp_::Match(e, arms),
+        None => {
+            assert!(context.env.has_diags());
+            E::Exp_::UnresolvedError
+        }
+    }
+}
+
+fn match_arm(context: &mut Context, sp!(loc, (ppat, pe)): P::MatchArm) -> Option<E::MatchArm> {
+    let pat_opt = match_pattern(context, ppat);
+    let e = exp_(context, pe);
+    Some(sp(loc, (pat_opt?, e)))
+}
+
+fn match_pattern(
+    context: &mut Context,
+    sp!(loc, ppat_): P::MatchPattern,
+) -> Option<E::MatchPattern> {
+    use E::MatchPattern_ as EP;
+    use P::MatchPattern_ as PP;
+    let pat_ = match ppat_ {
+        PP::Wildcard => EP::Wildcard,
+        PP::Variant(pn, ptys_opt, pfields) => {
+            let (en, v) = match variant_name_access_chain(context, &pn) {
+                Some(variant) => variant,
+                None => {
+                    context.env.add_diag(diag!(
+                        NameResolution::UnboundVariant,
+                        (
+                            pn.loc,
+                            format!(
+                                "Unbound variant '{}'. Expected a variant of an enum, e.g. \
+                                 'Enum::Variant'",
+                                pn
+                            )
+                        )
+                    ));
+                    return None;
+                }
+            };
+            let tys_opt = optional_types(context, ptys_opt);
+            let vfields: Option<Vec<(Field, E::LValue)>> = pfields
+                .into_iter()
+                .map(|(f, pb)| Some((f, bind(context, pb)?)))
+                .collect();
+            let fields = fields(context, loc, "match pattern", "binding", vfields?);
+            EP::Variant(en, v, tys_opt, fields)
+        }
+    };
+    Some(sp(loc, pat_))
+}
+
 //**************************************************************************************************
 // Fields
 //**************************************************************************************************
@@ -2104,6 +2438,10 @@ fn bind(context: &mut Context, sp!(loc, pb_): P::Bind) -> Option<E::LValue> {
             check_valid_local_name(context, &v);
             EL::Var(sp(loc, E::ModuleAccess_::Name(v.0)), None)
         }
+        PB::Unpack(ptn, _, _) if variant_name_access_chain(context, &ptn).is_some() => {
+            invalid_variant_lvalue(context, loc);
+            return None;
+        }
         PB::Unpack(ptn, ptys_opt, pfields) => {
             let tn = name_access_chain(context, Access::ApplyNamed, *ptn)?;
             let tys_opt = optional_types(context, ptys_opt);
@@ -2151,6 +2489,12 @@ fn assign(context: &mut Context, sp!(loc, e_): P::Exp) -> Option<E::LValue> {
     use E::LValue_ as EL;
     use P::Exp_ as PE;
     let a_ = match e_ {
+        PE::Name(pn, _) | PE::Pack(pn, _, _)
+            if variant_name_access_chain(context, &pn).is_some() =>
+        {
+            invalid_variant_lvalue(context, loc);
+            return None;
+        }
         PE::Name(n @ sp!(_, P::NameAccessChain_::Two(_, _)), _)
         | PE::Name(n @ sp!(_, P::NameAccessChain_::Three(_, _)), _)
             if !context.in_spec_context =>
@@ -2226,6 +2570,17 @@ fn assign(context: &mut Context, sp!(loc, e_): P::Exp) -> Option<E::LValue> {
     Some(sp(loc, a_))
 }
 
+fn invalid_variant_lvalue(context: &mut Context, loc: Loc) {
+    context.env.add_diag(diag!(
+        Syntax::InvalidLValue,
+        (
+            loc,
+            "Invalid deconstruction of an enum variant. Enum values can only be deconstructed \
+             by a 'match'"
+        )
+    ));
+}
+
 fn assign_unpack_fields(
     context: &mut Context,
     loc: Loc,
@@ -2297,7 +2652,9 @@ fn unbound_names_exp(unbound: &mut BTreeSet<Name>, sp!(_, e_): &E::Exp) {
         EE::Call(_, _, _, sp!(_, es_)) | EE::Vector(_, _, sp!(_, es_)) => {
             unbound_names_exps(unbound, es_)
         }
-        EE::Pack(_, _, es) => unbound_names_exps(unbound, es.iter().map(|(_, _, (_, e))| e)),
+        EE::Pack(_, _, es) | EE::PackVariant(_, _, _, es) => {
+            unbound_names_exps(unbound, es.iter().map(|(_, _, (_, e))| e))
+        }
         EE::IfElse(econd, et, ef) => {
             unbound_names_exp(unbound, ef);
             unbound_names_exp(unbound, et);
@@ -2308,6 +2665,18 @@ fn unbound_names_exp(unbound: &mut BTreeSet<Name>, sp!(_, e_): &E::Exp) {
             unbound_names_exp(unbound, econd)
         }
         EE::Loop(eloop) => unbound_names_exp(unbound, eloop),
+        EE::Match(esubject, arms) => {
+            for sp!(_, (pat, earm)) in arms {
+                unbound_names_exp(unbound, earm);
+                // remove anything bound by the pattern
+                if let sp!(_, E::MatchPattern_::Variant(_, _, _, lfields)) = pat {
+                    lfields
+                        .iter()
+                        .for_each(|(_, _, (_, l))| unbound_names_bind(unbound, l))
+                }
+            }
+            unbound_names_exp(unbound, esubject)
+        }
 
         EE::Block(seq) => unbound_names_sequence(unbound, seq),
         EE::Lambda(ls, er) => {
@@ -2472,11 +2841,12 @@ fn check_valid_local_name(context: &mut Context, v: &Var) {
     let _ = check_restricted_name_all_cases(context, NameCase::Variable, &v.0);
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 enum ModuleMemberKind {
     Constant,
     Function,
     Struct,
+    Enum,
     Schema,
 }
 
@@ -2485,7 +2855,7 @@ impl ModuleMemberKind {
         match self {
             ModuleMemberKind::Constant => NameCase::Constant,
             ModuleMemberKind::Function => NameCase::Function,
-            ModuleMemberKind::Struct => NameCase::Struct,
+            ModuleMemberKind::Struct | ModuleMemberKind::Enum => NameCase::Struct,
             ModuleMemberKind::Schema => NameCase::Schema,
         }
     }
@@ -2515,6 +2885,7 @@ impl NameCase {
             NameCase::ModuleMemberAlias(ModuleMemberKind::Function) => "function alias",
             NameCase::ModuleMemberAlias(ModuleMemberKind::Constant) => "constant alias",
             NameCase::ModuleMemberAlias(ModuleMemberKind::Struct) => "struct alias",
+            NameCase::ModuleMemberAlias(ModuleMemberKind::Enum) => "enum alias",
             NameCase::ModuleMemberAlias(ModuleMemberKind::Schema) => "schema alias",
             NameCase::ModuleAlias => "module alias",
             NameCase::Variable => "variable",
@@ -2579,7 +2950,7 @@ fn check_valid_module_member_name_impl(
                 return Err(());
             }
         }
-        M::Constant | M::Struct | M::Schema => {
+        M::Constant | M::Struct | M::Enum | M::Schema => {
             if !is_valid_struct_constant_or_schema_name(&n.value) {
                 let msg = format!(
                     "Invalid {} name '{}'. {} names must start with 'A'..'Z'",
@@ -0,0 +1,652 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! The Move source formatter.
+//!
+//! Formatting works on the token stream rather than on the AST, so that every comment in a file
+//! (including the doc comments collected in `parser::comments`) survives formatting unchanged. Line
+//! breaks chosen by the author are kept, while indentation, blank lines and the spacing between
+//! the tokens of a line are normalized. The spacing of tokens whose role depends on the syntax,
+//! like `*` multiplying or dereferencing and `<` comparing or opening type arguments, is decided
+//! from the parsed file rather than from the input. Only files that parse are formatted, and the
+//! output is checked to lex into the same tokens and comments as the input before it is returned.
+
+use crate::{
+    diag,
+    diagnostics::{codes::Severity, Diagnostics},
+    parser::{
+        ast::{
+            Definition, Exp, Exp_, FunctionBody, FunctionBody_, ModuleDefinition, ModuleMember,
+            Sequence, SequenceItem_, SpecBlock, SpecBlockMember_,
+        },
+        comments::verify_string,
+        lexer::{Lexer, Tok},
+        syntax::parse_file_string,
+    },
+    shared::{CompilationEnv, Flags},
+};
+use move_command_line_common::files::FileHash;
+use move_ir_types::location::Loc;
+use std::collections::BTreeSet;
+
+/// The number of spaces used for one level of indentation by default.
+pub const DEFAULT_INDENT_WIDTH: usize = 4;
+
+/// Options controlling the layout of formatted sources.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FormatterConfig {
+    /// The number of spaces used for one level of indentation.
+    pub indent_width: usize,
+}
+
+impl Default for FormatterConfig {
+    fn default() -> Self {
+        Self {
+            indent_width: DEFAULT_INDENT_WIDTH,
+        }
+    }
+}
+
+/// Formats the Move source `input`, whose diagnostics are reported against `file_hash`. Fails with
+/// the parser's diagnostics if `input` is not a syntactically valid Move file.
+pub fn format_string(
+    file_hash: FileHash,
+    input: &str,
+    config: &FormatterConfig,
+) -> Result<String, Diagnostics> {
+    verify_string(file_hash, input)?;
+    let mut env = CompilationEnv::new(Flags::empty());
+    let (defs, _) = parse_file_string(&mut env, file_hash, input)?;
+    env.check_diags_at_or_above_severity(Severity::NonblockingError)?;
+
+    let elements = lex(file_hash, input)?;
+    let roles = Syntax::new(&defs).roles(&elements);
+    let output = Formatter::new(config).format(&elements, &roles);
+
+    // Formatting only ever changes the whitespace between elements. Anything else is a bug in the
+    // formatter, and the input is better left untouched than silently changed.
+    let unchanged = match lex(FileHash::new(&output), &output) {
+        Ok(formatted) => {
+            formatted.len() == elements.len()
+                && formatted
+                    .iter()
+                    .zip(&elements)
+                    .all(|(f, e)| f.element.same_as(&e.element))
+        }
+        Err(_) => false,
+    };
+    if !unchanged {
+        let loc = Loc::new(file_hash, 0, 0);
+        let msg = "Formatting changed the tokens or comments of the file";
+        return Err(Diagnostics::from(vec![diag!(
+            Bug::FormattingFailed,
+            (loc, msg)
+        )]));
+    }
+    Ok(output)
+}
+
+//**************************************************************************************************
+// Lexing
+//**************************************************************************************************
+
+/// A piece of source text as seen by the formatter.
+#[derive(Debug)]
+enum Element<'a> {
+    Token(Tok, &'a str),
+    /// A `//` comment, without the line break ending it
+    LineComment(&'a str),
+    /// A `/* .. */` comment, which can span multiple lines
+    BlockComment(&'a str),
+}
+
+/// An element, together with the layout of the source between it and the previous element.
+#[derive(Debug)]
+struct Spaced<'a> {
+    element: Element<'a>,
+    /// The number of line breaks before the element
+    newlines: usize,
+    /// Whether any whitespace came before the element
+    spaced: bool,
+    /// The byte offset of the element in the source
+    start: usize,
+}
+
+impl<'a> Element<'a> {
+    fn same_as(&self, other: &Element) -> bool {
+        use Element::*;
+        match (self, other) {
+            (Token(t1, s1), Token(t2, s2)) => t1 == t2 && s1 == s2,
+            (LineComment(s1), LineComment(s2)) => s1 == s2,
+            // Trailing whitespace is removed from the lines a block comment spans
+            (BlockComment(s1), BlockComment(s2)) => s1
+                .lines()
+                .map(str::trim_end)
+                .eq(s2.lines().map(str::trim_end)),
+            _ => false,
+        }
+    }
+}
+
+fn lex(file_hash: FileHash, input: &str) -> Result<Vec<Spaced<'_>>, Diagnostics> {
+    let mut lexer = Lexer::new(input, file_hash);
+    let mut elements = vec![];
+    loop {
+        lexer
+            .advance()
+            .map_err(|diag| Diagnostics::from(vec![diag]))?;
+        // The lexer skips whitespace and comments between tokens, so recover the comments from
+        // the text in between
+        let gap = &input[lexer.previous_end_loc()..lexer.start_loc()];
+        let (newlines, spaced) = lex_gap(gap, lexer.previous_end_loc(), &mut elements);
+        if lexer.peek() == Tok::EOF {
+            break;
+        }
+        elements.push(Spaced {
+            // `&mut` is lexed together with the space following it
+            element: Element::Token(lexer.peek(), lexer.content().trim_end()),
+            newlines,
+            spaced,
+            start: lexer.start_loc(),
+        });
+    }
+    Ok(elements)
+}
+
+/// Collects the comments in `gap`, the text between two tokens starting at the offset `start`, and
+/// returns the layout of the text after the last comment.
+fn lex_gap<'a>(
+    mut gap: &'a str,
+    mut start: usize,
+    elements: &mut Vec<Spaced<'a>>,
+) -> (usize, bool) {
+    let mut newlines = 0;
+    let mut spaced = false;
+    loop {
+        let rest = gap.trim_start_matches(|c: char| c.is_ascii_whitespace());
+        let whitespace = &gap[..gap.len() - rest.len()];
+        start += whitespace.len();
+        newlines += whitespace.matches('\n').count();
+        spaced |= !whitespace.is_empty();
+        gap = rest;
+
+        let (element, len) = if gap.starts_with("//") {
+            let len = gap.find('\n').unwrap_or(gap.len());
+            (Element::LineComment(gap[..len].trim_end()), len)
+        } else if gap.starts_with("/*") {
+            let len = block_comment_len(gap);
+            (Element::BlockComment(&gap[..len]), len)
+        } else {
+            break;
+        };
+        elements.push(Spaced {
+            element,
+            newlines,
+            spaced,
+            start,
+        });
+        newlines = 0;
+        spaced = false;
+        gap = &gap[len..];
+        start += len;
+    }
+    (newlines, spaced)
+}
+
+/// The length of the (possibly nested) block comment `text` starts with.
+fn block_comment_len(text: &str) -> usize {
+    let mut depth = 0;
+    let mut pos = 0;
+    while pos < text.len() {
+        let rest = &text[pos..];
+        if rest.starts_with("/*") {
+            depth += 1;
+            pos += 2;
+        } else if rest.starts_with("*/") {
+            depth -= 1;
+            pos += 2;
+            if depth == 0 {
+                return pos;
+            }
+        } else {
+            pos += 1;
+        }
+    }
+    text.len()
+}
+
+//**************************************************************************************************
+// Syntax
+//**************************************************************************************************
+
+/// The role of an element in the syntax of the file, as far as it matters for the spacing around
+/// the element.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Role {
+    /// A binary operator, e.g. the `*` of `x * y`
+    Binary,
+    /// A `&` or `*` applied to the expression or type following it, e.g. the `*` of `*x`
+    Prefix,
+    /// A `<` opening type parameters or arguments
+    TypeOpen,
+    /// A `>` or `>>` closing type parameters or arguments
+    TypeClose,
+    /// A `|` opening the parameters of a lambda or function type
+    PipeOpen,
+    /// A `|` closing the parameters of a lambda or function type
+    PipeClose,
+    /// A `(` starting an expression, rather than the arguments of a call
+    ExpParen,
+    /// Any other element
+    Other,
+}
+
+/// The offsets of the tokens whose role cannot be told from the tokens around them, collected
+/// from the parsed file.
+#[derive(Default)]
+struct Syntax {
+    /// The operators of binary expressions
+    binary_ops: BTreeSet<usize>,
+    /// The `*` of dereferences
+    derefs: BTreeSet<usize>,
+    /// The starts of all expressions
+    exp_starts: BTreeSet<usize>,
+}
+
+impl Syntax {
+    fn new(defs: &[Definition]) -> Self {
+        let mut syntax = Self::default();
+        for def in defs {
+            match def {
+                Definition::Module(m) => syntax.module(m),
+                Definition::Address(a) => a.modules.iter().for_each(|m| syntax.module(m)),
+                Definition::Script(s) => {
+                    s.constants.iter().for_each(|c| syntax.exp(&c.value));
+                    syntax.function_body(&s.function.body);
+                    s.specs.iter().for_each(|spec| syntax.spec_block(spec));
+                }
+            }
+        }
+        syntax
+    }
+
+    /// The role of each of the `elements` lexed from the file.
+    fn roles(&self, elements: &[Spaced]) -> Vec<Role> {
+        let mut in_pipes = false;
+        elements
+            .iter()
+            .map(|item| {
+                let tok = match item.element {
+                    Element::Token(tok, _) => tok,
+                    Element::LineComment(_) | Element::BlockComment(_) => return Role::Other,
+                };
+                if self.binary_ops.contains(&item.start) {
+                    return Role::Binary;
+                }
+                match tok {
+                    Tok::Amp => Role::Prefix,
+                    Tok::Star if self.derefs.contains(&item.start) => Role::Prefix,
+                    Tok::Less => Role::TypeOpen,
+                    Tok::Greater | Tok::GreaterGreater => Role::TypeClose,
+                    Tok::Pipe => {
+                        in_pipes = !in_pipes;
+                        if in_pipes {
+                            Role::PipeOpen
+                        } else {
+                            Role::PipeClose
+                        }
+                    }
+                    Tok::LParen if self.exp_starts.contains(&item.start) => Role::ExpParen,
+                    _ => Role::Other,
+                }
+            })
+            .collect()
+    }
+
+    fn module(&mut self, module: &ModuleDefinition) {
+        for member in &module.members {
+            match member {
+                ModuleMember::Function(f) => self.function_body(&f.body),
+                ModuleMember::Constant(c) => self.exp(&c.value),
+                ModuleMember::Spec(spec) => self.spec_block(spec),
+                ModuleMember::Struct(_) | ModuleMember::Use(_) | ModuleMember::Friend(_) => (),
+            }
+        }
+    }
+
+    fn function_body(&mut self, body: &FunctionBody) {
+        match &body.value {
+            FunctionBody_::Defined(seq) => self.sequence(seq),
+            FunctionBody_::Native => (),
+        }
+    }
+
+    fn sequence(&mut self, (_, items, _, last): &Sequence) {
+        for item in items {
+            match &item.value {
+                SequenceItem_::Seq(e) | SequenceItem_::Bind(_, _, e) => self.exp(e),
+                SequenceItem_::Declare(_, _) => (),
+            }
+        }
+        if let Some(e) = &**last {
+            self.exp(e)
+        }
+    }
+
+    fn spec_block(&mut self, spec: &SpecBlock) {
+        use SpecBlockMember_ as M;
+        for member in &spec.value.members {
+            match &member.value {
+                M::Condition {
+                    exp,
+                    additional_exps,
+                    ..
+                } => {
+                    self.exp(exp);
+                    additional_exps.iter().for_each(|e| self.exp(e));
+                }
+                M::Function { body, .. } => self.function_body(body),
+                M::Variable { init, .. } => init.iter().for_each(|e| self.exp(e)),
+                M::Let { def: e, .. } | M::Include { exp: e, .. } | M::Apply { exp: e, .. } => {
+                    self.exp(e)
+                }
+                M::Update { lhs, rhs } => {
+                    self.exp(lhs);
+                    self.exp(rhs);
+                }
+                M::Pragma { .. } => (),
+            }
+        }
+    }
+
+    fn exp(&mut self, exp: &Exp) {
+        use Exp_ as E;
+        let start = exp.loc.start() as usize;
+        self.exp_starts.insert(start);
+        match &exp.value {
+            E::Value(_)
+            | E::Move(_)
+            | E::Copy(_)
+            | E::Name(_, _)
+            | E::Unit
+            | E::Return(None)
+            | E::Break
+            | E::Continue
+            | E::UnresolvedError => (),
+            E::Call(_, _, _, args) | E::Vector(_, _, args) => {
+                args.value.iter().for_each(|e| self.exp(e))
+            }
+            E::Pack(_, _, fields) => fields.iter().for_each(|(_, e)| self.exp(e)),
+            E::IfElse(cond, if_true, if_false) => {
+                self.exp(cond);
+                self.exp(if_true);
+                if_false.iter().for_each(|e| self.exp(e));
+            }
+            E::Match(e, arms) => {
+                self.exp(e);
+                arms.iter().for_each(|arm| self.exp(&arm.value.1));
+            }
+            E::Block(seq) => self.sequence(seq),
+            E::Quant(_, ranges, triggers, cond, body) => {
+                ranges
+                    .value
+                    .iter()
+                    .for_each(|range| self.exp(&range.value.1));
+                triggers.iter().flatten().for_each(|e| self.exp(e));
+                cond.iter().for_each(|e| self.exp(e));
+                self.exp(body);
+            }
+            E::ExpList(es) => es.iter().for_each(|e| self.exp(e)),
+            E::While(e1, e2) | E::Assign(e1, e2) | E::Index(e1, e2) => {
+                self.exp(e1);
+                self.exp(e2);
+            }
+            E::BinopExp(lhs, op, rhs) => {
+                self.binary_ops.insert(op.loc.start() as usize);
+                self.exp(lhs);
+                self.exp(rhs);
+            }
+            E::Dereference(e) => {
+                self.derefs.insert(start);
+                self.exp(e);
+            }
+            E::Loop(e)
+            | E::Lambda(_, e)
+            | E::Return(Some(e))
+            | E::Abort(e)
+            | E::UnaryExp(_, e)
+            | E::Borrow(_, e)
+            | E::Dot(e, _)
+            | E::Cast(e, _)
+            | E::Annotate(e, _) => self.exp(e),
+            E::Spec(spec) => self.spec_block(spec),
+        }
+    }
+}
+
+//**************************************************************************************************
+// Layout
+//**************************************************************************************************
+
+struct Formatter<'c> {
+    config: &'c FormatterConfig,
+    output: String,
+    /// The line being built, without its indentation
+    line: String,
+    /// The indentation of the line being built
+    indent: usize,
+    /// For each bracket that is still open, the indentation of the line it was opened on, and
+    /// whether it is a brace opening a list of `use` members
+    brackets: Vec<(usize, bool)>,
+    /// Whether the last element emitted is an opening bracket
+    after_opener: bool,
+}
+
+impl<'c> Formatter<'c> {
+    fn new(config: &'c FormatterConfig) -> Self {
+        Self {
+            config,
+            output: String::new(),
+            line: String::new(),
+            indent: 0,
+            brackets: vec![],
+            after_opener: false,
+        }
+    }
+
+    fn format(mut self, elements: &[Spaced], roles: &[Role]) -> String {
+        let mut prev: Option<(&Element, Role)> = None;
+        for (item, role) in elements.iter().zip(roles) {
+            let Spaced {
+                element,
+                newlines,
+                spaced,
+                ..
+            } = item;
+            let mut blank_line = false;
+            if *newlines > 0 && !self.line.is_empty() {
+                self.end_line();
+                // Runs of blank lines are collapsed into one, and dropped entirely at the start
+                // and end of a block
+                blank_line = *newlines > 1 && !self.after_opener;
+            }
+
+            let closer = matches!(
+                element,
+                Element::Token(Tok::RParen | Tok::RBracket | Tok::RBrace, _)
+            );
+            let closed = if closer { self.brackets.pop() } else { None };
+            let in_use_list = match closed {
+                Some((_, use_list)) => use_list,
+                None => self
+                    .brackets
+                    .last()
+                    .map_or(false, |(_, use_list)| *use_list),
+            };
+            if self.line.is_empty() {
+                self.indent = match closed {
+                    Some((indent, _)) => indent,
+                    None => self
+                        .brackets
+                        .last()
+                        .map_or(0, |(indent, _)| indent + self.config.indent_width),
+                };
+                if blank_line && !closer && !self.output.is_empty() {
+                    self.output.push('\n');
+                }
+            } else if needs_space(prev, (element, *role), *spaced, in_use_list) {
+                self.line.push(' ');
+            }
+
+            let opener = matches!(
+                element,
+                Element::Token(Tok::LParen | Tok::LBracket | Tok::LBrace, _)
+            );
+            if opener {
+                let use_list = matches!(
+                    (prev, element),
+                    (
+                        Some((Element::Token(Tok::ColonColon, _), _)),
+                        Element::Token(Tok::LBrace, _)
+                    )
+                );
+                self.brackets.push((self.indent, use_list));
+            }
+            self.after_opener = opener;
+            let text = match element {
+                Element::Token(_, text)
+                | Element::LineComment(text)
+                | Element::BlockComment(text) => text,
+            };
+            self.line.push_str(text);
+            prev = Some((element, *role));
+        }
+        if !self.line.is_empty() {
+            self.end_line();
+        }
+        self.output
+    }
+
+    fn end_line(&mut self) {
+        let line = std::mem::take(&mut self.line);
+        for _ in 0..self.indent {
+            self.output.push(' ');
+        }
+        // A block comment can continue on the following lines, which are kept as they are
+        for (i, l) in line.split('\n').enumerate() {
+            if i > 0 {
+                self.output.push('\n');
+            }
+            self.output.push_str(l.trim_end());
+        }
+        self.output.push('\n');
+    }
+}
+
+/// Whether a space separates `prev` from `next` on the same line, given the elements and their
+/// roles. The source's spacing, told by `spaced`, is only followed around comments and around the
+/// `*` wildcards of spec apply patterns, where it matters to the parser. The braces of a list of
+/// `use` members, told by `in_use_list`, are not padded with spaces.
+fn needs_space(
+    prev: Option<(&Element, Role)>,
+    next: (&Element, Role),
+    spaced: bool,
+    in_use_list: bool,
+) -> bool {
+    use Role::*;
+    use Tok::*;
+    let (prev, next) = match (prev, next) {
+        (Some((Element::Token(prev, _), prev_role)), (Element::Token(next, _), next_role)) => {
+            ((*prev, prev_role), (*next, next_role))
+        }
+        (None, _) => return false,
+        (_, (Element::LineComment(_), _)) => return true,
+        _ => return spaced,
+    };
+    match (prev, next) {
+        ((PeriodPeriod, Binary), _) | (_, (PeriodPeriod, Binary)) => false,
+        ((_, Binary), _) | (_, (_, Binary)) => true,
+        // Joining these would lex them as a different token
+        ((Amp, _), (Amp | AmpMut, _)) | ((Greater, _), (Greater | GreaterGreater, _)) => true,
+        ((Pipe, _), (Pipe, _)) => true,
+        ((AmpMut, _), _) => true,
+        ((Amp | Star, Prefix), _) => false,
+        ((ColonColon | Period | NumSign | AtSign | Exclaim, _), _)
+        | (_, (ColonColon | Period, _)) => false,
+        ((LParen | LBracket, _), _) | (_, (RParen | RBracket | Comma | Semicolon | Colon, _)) => {
+            false
+        }
+        ((_, TypeOpen), _) | (_, (_, TypeOpen | TypeClose)) => false,
+        ((_, TypeClose), (LParen | LBracket, _)) => false,
+        ((_, PipeOpen), _) | (_, (_, PipeClose)) => false,
+        ((LBrace, _), (RBrace, _)) => false,
+        ((LBrace, _), _) | (_, (RBrace, _)) => !in_use_list,
+        (_, (LBrace, _)) => true,
+        ((Public, _), (LParen, _)) => false,
+        ((Identifier, _), (Exclaim, _)) => false,
+        ((Identifier | RParen | RBracket, _), (LBracket, _)) => false,
+        ((Identifier | RParen | RBracket, _), (LParen, role)) => role == ExpParen,
+        ((Star | Minus | PeriodPeriod, Other), _) | (_, (Star | Minus | PeriodPeriod, Other)) => {
+            spaced
+        }
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_string, FormatterConfig};
+    use move_command_line_common::files::FileHash;
+
+    fn format(input: &str) -> String {
+        format_string(FileHash::new(input), input, &FormatterConfig::default()).unwrap()
+    }
+
+    #[test]
+    fn test_spacing_is_canonical() {
+        let tight = "\
+module 0x42::M {
+    fun f<T: drop>(x: &u64, v: vector<vector<u8>>): u64 {
+        let y = *x-1*2;
+        let b = &v;
+        let c = (y&3)|(y>>1);
+        assert!(1<y, 0);
+        if (y>c) g<T>(*x) else vector::length<vector<u8>>(b)
+    }
+    spec f {
+        ensures forall i in 0..len(v): v[i]==v[i];
+    }
+}
+";
+        let loose = "\
+module 0x42::M {
+    fun f < T : drop > ( x : & u64 , v : vector < vector < u8 >> ) : u64 {
+        let y = * x - 1 * 2;
+        let b = & v;
+        let c = ( y & 3 ) | ( y >> 1 );
+        assert ! ( 1 < y , 0 );
+        if ( y > c ) g< T > ( * x ) else vector :: length< vector < u8 >> ( b )
+    }
+    spec f {
+        ensures forall i in 0 .. len ( v ) : v [ i ] == v [ i ];
+    }
+}
+";
+        let expected = "\
+module 0x42::M {
+    fun f<T: drop>(x: &u64, v: vector<vector<u8>>): u64 {
+        let y = *x - 1 * 2;
+        let b = &v;
+        let c = (y & 3) | (y >> 1);
+        assert!(1 < y, 0);
+        if (y > c) g<T>(*x) else vector::length<vector<u8>>(b)
+    }
+    spec f {
+        ensures forall i in 0..len(v): v[i] == v[i];
+    }
+}
+";
+        assert_eq!(format(tight), expected);
+        assert_eq!(format(loose), expected);
+    }
+}
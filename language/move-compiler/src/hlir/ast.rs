@@ -8,10 +8,12 @@ use crate::{
     },
     naming::ast::{BuiltinTypeName, BuiltinTypeName_, StructTypeParameter, TParam},
     parser::ast::{
-        BinOp, ConstantName, Field, FunctionName, StructName, UnaryOp, Var, ENTRY_MODIFIER,
+        BinOp, ConstantName, Field, FunctionName, StructName, UnaryOp, Var, VariantName,
+        ENTRY_MODIFIER,
     },
     shared::{ast_debug::*, unique_map::UniqueMap, NumericalAddress},
 };
+use move_core_types::u256::U256;
 use move_ir_types::location::*;
 use move_symbol_pool::Symbol;
 use std::collections::{BTreeMap, BTreeSet, VecDeque};
@@ -76,6 +78,7 @@ pub struct StructDefinition {
 #[derive(Debug, PartialEq, Clone)]
 pub enum StructFields {
     Defined(Vec<(Field, BaseType)>),
+    Variants(Vec<(VariantName, Vec<(Field, BaseType)>)>),
     Native(Loc),
 }
 
@@ -230,6 +233,7 @@ pub enum LValue_ {
     Ignore,
     Var(Var, Box<SingleType>),
     Unpack(StructName, Vec<BaseType>, Vec<(Field, LValue)>),
+    UnpackVariant(StructName, VariantName, Vec<BaseType>, Vec<(Field, LValue)>),
 }
 pub type LValue = Spanned<LValue_>;
 
@@ -268,10 +272,16 @@ pub enum Value_ {
     Address(NumericalAddress),
     // <num>u8
     U8(u8),
+    // <num>u16
+    U16(u16),
+    // <num>u32
+    U32(u32),
     // <num>u64
     U64(u64),
     // <num>u128
     U128(u128),
+    // <num>u256
+    U256(U256),
     // true
     // false
     Bool(bool),
@@ -304,7 +314,7 @@ pub enum UnannotatedExp_ {
         from_user: bool,
         var: Var,
     },
-    Constant(ConstantName),
+    Constant(Option<ModuleIdent>, ConstantName),
 
     ModuleCall(Box<ModuleCall>),
     Builtin(Box<BuiltinFunction>, Box<Exp>),
@@ -316,6 +326,14 @@ pub enum UnannotatedExp_ {
     BinopExp(Box<Exp>, BinOp, Box<Exp>),
 
     Pack(StructName, Vec<BaseType>, Vec<(Field, BaseType, Exp)>),
+    PackVariant(
+        StructName,
+        VariantName,
+        Vec<BaseType>,
+        Vec<(Field, BaseType, Exp)>,
+    ),
+    // Tests if the enum value behind the reference is of the given variant
+    TestVariant(StructName, VariantName, Vec<BaseType>, Box<Exp>),
     ExpList(Vec<ExpListItem>),
 
     Borrow(bool, Box<Exp>, Field),
@@ -430,7 +448,7 @@ impl BaseType_ {
         use BuiltinTypeName_::*;
 
         let kind = match b_ {
-            U8 | U64 | U128 | Bool | Address => AbilitySet::primitives(loc),
+            U8 | U16 | U32 | U64 | U128 | U256 | Bool | Address => AbilitySet::primitives(loc),
             Signer => AbilitySet::signer(loc),
             Vector => {
                 let declared_abilities = AbilitySet::collection(loc);
@@ -683,21 +701,30 @@ impl AstDebug for (StructName, &StructDefinition) {
             },
         ) = self;
         attributes.ast_debug(w);
-        if let StructFields::Native(_) = fields {
-            w.write("native ");
+        match fields {
+            StructFields::Native(_) => w.write(&format!("native struct {}", name)),
+            StructFields::Defined(_) => w.write(&format!("struct {}", name)),
+            StructFields::Variants(_) => w.write(&format!("enum {}", name)),
         }
-
-        w.write(&format!("struct {}", name));
         type_parameters.ast_debug(w);
         ability_modifiers_ast_debug(w, abilities);
-        if let StructFields::Defined(fields) = fields {
-            w.block(|w| {
-                w.list(fields, ";", |w, (f, bt)| {
-                    w.write(&format!("{}: ", f));
-                    bt.ast_debug(w);
+        let fields_ast_debug = |w: &mut AstWriter, fields: &Vec<(Field, BaseType)>| {
+            w.list(fields, ";", |w, (f, bt)| {
+                w.write(&format!("{}: ", f));
+                bt.ast_debug(w);
+                true
+            })
+        };
+        match fields {
+            StructFields::Defined(fields) => w.block(|w| fields_ast_debug(w, fields)),
+            StructFields::Variants(variants) => w.block(|w| {
+                w.list(variants, ",", |w, (v, fields)| {
+                    w.write(&format!("{}", v));
+                    w.block(|w| fields_ast_debug(w, fields));
                     true
                 })
-            })
+            }),
+            StructFields::Native(_) => (),
         }
     }
 }
@@ -989,8 +1016,11 @@ impl AstDebug for Value_ {
         match self {
             V::Address(addr) => w.write(&format!("@{}", addr)),
             V::U8(u) => w.write(&format!("{}u8", u)),
+            V::U16(u) => w.write(&format!("{}u16", u)),
+            V::U32(u) => w.write(&format!("{}u32", u)),
             V::U64(u) => w.write(&format!("{}u64", u)),
             V::U128(u) => w.write(&format!("{}u128", u)),
+            V::U256(u) => w.write(&format!("{}u256", u)),
             V::Bool(b) => w.write(&format!("{}", b)),
             V::Vector(ty, elems) => {
                 w.write("vector#value");
@@ -1042,7 +1072,8 @@ impl AstDebug for UnannotatedExp_ {
                 from_user: true,
                 var: v,
             } => w.write(&format!("copy@{}", v)),
-            E::Constant(c) => w.write(&format!("{}", c)),
+            E::Constant(None, c) => w.write(&format!("{}", c)),
+            E::Constant(Some(m), c) => w.write(&format!("{}::{}", m, c)),
             E::ModuleCall(mcall) => {
                 mcall.ast_debug(w);
             }
@@ -1079,6 +1110,27 @@ impl AstDebug for UnannotatedExp_ {
                 });
                 w.write("}");
             }
+            E::PackVariant(s, v, tys, fields) => {
+                w.write(&format!("{}::{}", s, v));
+                w.write("<");
+                tys.ast_debug(w);
+                w.write(">");
+                w.write("{");
+                w.comma(fields, |w, (f, bt, e)| {
+                    w.annotate(|w| w.write(&format!("{}", f)), bt);
+                    w.write(": ");
+                    e.ast_debug(w);
+                });
+                w.write("}");
+            }
+            E::TestVariant(s, v, tys, e) => {
+                w.write(&format!("test_variant<{}::{}", s, v));
+                w.write("<");
+                tys.ast_debug(w);
+                w.write(">>(");
+                e.ast_debug(w);
+                w.write(")");
+            }
 
             E::ExpList(es) => {
                 w.write("(");
@@ -1229,6 +1281,18 @@ impl AstDebug for LValue_ {
                 });
                 w.write("}");
             }
+            L::UnpackVariant(s, v, tys, fields) => {
+                w.write(&format!("{}::{}", s, v));
+                w.write("<");
+                tys.ast_debug(w);
+                w.write(">");
+                w.write("{");
+                w.comma(fields, |w, (f, l)| {
+                    w.write(&format!("{}: ", f));
+                    l.ast_debug(w)
+                });
+                w.write("}");
+            }
         }
     }
 }
@@ -6,7 +6,7 @@ use crate::{
     expansion::ast::{self as E, AbilitySet, Fields, ModuleIdent},
     hlir::ast::{self as H, Block, MoveOpAnnotation},
     naming::ast as N,
-    parser::ast::{BinOp_, ConstantName, Field, FunctionName, StructName, Var},
+    parser::ast::{BinOp_, ConstantName, Field, FunctionName, StructName, Var, VariantName},
     shared::{unique_map::UniqueMap, *},
     typing::ast as T,
     FullyCompiledProgram,
@@ -62,6 +62,7 @@ pub fn display_var(s: Symbol) -> DisplayVar {
 struct Context<'env> {
     env: &'env mut CompilationEnv,
     structs: UniqueMap<StructName, UniqueMap<Field, usize>>,
+    variants: UniqueMap<StructName, UniqueMap<VariantName, UniqueMap<Field, usize>>>,
     function_locals: UniqueMap<Var, H::SingleType>,
     local_scope: UniqueMap<Var, Var>,
     used_locals: BTreeSet<Var>,
@@ -74,6 +75,7 @@ impl<'env> Context<'env> {
         Context {
             env,
             structs: UniqueMap::new(),
+            variants: UniqueMap::new(),
             function_locals: UniqueMap::new(),
             local_scope: UniqueMap::new(),
             used_locals: BTreeSet::new(),
@@ -122,16 +124,28 @@ impl<'env> Context<'env> {
 
     pub fn add_struct_fields(&mut self, structs: &UniqueMap<StructName, H::StructDefinition>) {
         assert!(self.structs.is_empty());
-        for (sname, sdef) in structs.key_cloned_iter() {
+        assert!(self.variants.is_empty());
+        fn field_indices(field_map: &[(Field, H::BaseType)]) -> UniqueMap<Field, usize> {
             let mut fields = UniqueMap::new();
-            let field_map = match &sdef.fields {
-                H::StructFields::Native(_) => continue,
-                H::StructFields::Defined(m) => m,
-            };
             for (idx, (field, _)) in field_map.iter().enumerate() {
                 fields.add(*field, idx).unwrap();
             }
-            self.structs.add(sname, fields).unwrap();
+            fields
+        }
+        for (sname, sdef) in structs.key_cloned_iter() {
+            match &sdef.fields {
+                H::StructFields::Native(_) => (),
+                H::StructFields::Defined(m) => self.structs.add(sname, field_indices(m)).unwrap(),
+                H::StructFields::Variants(variants) => {
+                    let mut variant_fields = UniqueMap::new();
+                    for (variant, field_map) in variants {
+                        variant_fields
+                            .add(*variant, field_indices(field_map))
+                            .unwrap();
+                    }
+                    self.variants.add(sname, variant_fields).unwrap();
+                }
+            }
         }
     }
 
@@ -143,6 +157,20 @@ impl<'env> Context<'env> {
         fields
     }
 
+    pub fn variant_fields(
+        &self,
+        struct_name: &StructName,
+        variant: &VariantName,
+    ) -> Option<&UniqueMap<Field, usize>> {
+        let fields = self
+            .variants
+            .get(struct_name)
+            .and_then(|variants| variants.get(variant));
+        // as with `fields`, if there are none, the enum must be defined in another module
+        assert!(fields.is_some() || self.env.has_diags());
+        fields
+    }
+
     fn counter_next(&mut self) -> usize {
         self.tmp_counter += 1;
         self.tmp_counter
@@ -199,9 +227,18 @@ fn module(
     context.add_struct_fields(&structs);
 
     let constants = tconstants.map(|name, c| constant(context, name, c));
-    let functions = tfunctions.map(|name, f| function(context, name, f));
+    // Inline functions were expanded at their call sites during typing, so no code is generated
+    // for them
+    let functions = tfunctions.filter_map(|name, f| {
+        if f.inline {
+            None
+        } else {
+            Some(function(context, name, f))
+        }
+    });
 
     context.structs = UniqueMap::new();
+    context.variants = UniqueMap::new();
     (
         module_ident,
         H::ModuleDefinition {
@@ -259,6 +296,7 @@ fn function(context: &mut Context, _name: FunctionName, f: T::Function) -> H::Fu
         attributes,
         visibility,
         entry,
+        inline: _,
         signature,
         acquires,
         body,
@@ -400,16 +438,30 @@ fn struct_def(
 }
 
 fn struct_fields(context: &mut Context, tfields: N::StructFields) -> H::StructFields {
-    let tfields_map = match tfields {
-        N::StructFields::Native(loc) => return H::StructFields::Native(loc),
-        N::StructFields::Defined(m) => m,
-    };
+    match tfields {
+        N::StructFields::Native(loc) => H::StructFields::Native(loc),
+        N::StructFields::Defined(m) => H::StructFields::Defined(struct_field_types(context, m)),
+        N::StructFields::Variants(variants) => {
+            let mut indexed_variants = variants
+                .into_iter()
+                .map(|(v, (idx, m))| (idx, (v, struct_field_types(context, m))))
+                .collect::<Vec<_>>();
+            indexed_variants.sort_by(|(idx1, _), (idx2, _)| idx1.cmp(idx2));
+            H::StructFields::Variants(indexed_variants.into_iter().map(|(_, v)| v).collect())
+        }
+    }
+}
+
+fn struct_field_types(
+    context: &mut Context,
+    tfields_map: Fields<N::Type>,
+) -> Vec<(Field, H::BaseType)> {
     let mut indexed_fields = tfields_map
         .into_iter()
         .map(|(f, (idx, t))| (idx, (f, base_type(context, t))))
         .collect::<Vec<_>>();
     indexed_fields.sort_by(|(idx1, _), (idx2, _)| idx1.cmp(idx2));
-    H::StructFields::Defined(indexed_fields.into_iter().map(|(_, f_ty)| f_ty).collect())
+    indexed_fields.into_iter().map(|(_, f_ty)| f_ty).collect()
 }
 
 //**************************************************************************************************
@@ -465,6 +517,12 @@ fn base_type(context: &Context, sp!(loc, nb_): N::Type) -> H::BaseType {
                 loc.end()
             )
         }
+        NT::Fun(_, _) => panic!(
+            "ICE function types should have been inlined {}:{}-{}",
+            loc.file_hash(),
+            loc.start(),
+            loc.end()
+        ),
     };
     sp(loc, b_)
 }
@@ -693,7 +751,7 @@ fn assign(
             let bs = base_types(context, tbs);
 
             let mut fields = vec![];
-            for (decl_idx, f, bt, tfa) in assign_fields(context, &s, tfields) {
+            for (decl_idx, f, bt, tfa) in assign_fields(context, &s, None, tfields) {
                 assert!(fields.len() == decl_idx);
                 let st = &H::SingleType_::base(bt);
                 let (fa, mut fafter) = assign(context, result, tfa, st);
@@ -711,7 +769,9 @@ fn assign(
                 };
                 H::exp(H::Type_::single(rvalue_ty.clone()), sp(loc, copy_tmp_))
             };
-            let fields = assign_fields(context, &s, tfields).into_iter().enumerate();
+            let fields = assign_fields(context, &s, None, tfields)
+                .into_iter()
+                .enumerate();
             for (idx, (decl_idx, f, bt, tfa)) in fields {
                 assert!(idx == decl_idx);
                 let floc = tfa.loc;
@@ -729,9 +789,13 @@ fn assign(
 fn assign_fields(
     context: &Context,
     s: &StructName,
+    variant_opt: Option<&VariantName>,
     tfields: Fields<(N::Type, T::LValue)>,
 ) -> Vec<(usize, Field, H::BaseType, T::LValue)> {
-    let decl_fields = context.fields(s);
+    let decl_fields = match variant_opt {
+        None => context.fields(s),
+        Some(v) => context.variant_fields(s, v),
+    };
     let mut count = 0;
     let mut decl_field = |f: &Field| -> usize {
         match decl_fields {
@@ -1130,10 +1194,7 @@ fn exp_impl(
             },
         },
         TE::Value(ev) => HE::Value(value(context, ev)),
-        TE::Constant(_m, c) => {
-            // Currently only private constants exist
-            HE::Constant(c)
-        }
+        TE::Constant(m, c) => HE::Constant(m, c),
         TE::Move { from_user, var } => {
             let annotation = if from_user {
                 MoveOpAnnotation::FromUser
@@ -1190,78 +1251,15 @@ fn exp_impl(
 
         TE::Pack(_, s, tbs, tfields) => {
             let bs = base_types(context, tbs);
-
-            let decl_fields = context.fields(&s);
-            let mut count = 0;
-            let mut decl_field = |f: &Field| -> usize {
-                match decl_fields {
-                    Some(m) => *m.get(f).unwrap(),
-                    None => {
-                        // none can occur with errors in typing
-                        let i = count;
-                        count += 1;
-                        i
-                    }
-                }
-            };
-
-            let mut texp_fields: Vec<(usize, Field, usize, N::Type, T::Exp)> = tfields
-                .into_iter()
-                .map(|(f, (exp_idx, (bt, tf)))| (decl_field(&f), f, exp_idx, bt, tf))
-                .collect();
-            texp_fields.sort_by(|(_, _, eidx1, _, _), (_, _, eidx2, _, _)| eidx1.cmp(eidx2));
-
-            let bind_all_fields = texp_fields
-                .iter()
-                .any(|(decl_idx, _, exp_idx, _, _)| decl_idx != exp_idx);
-            let fields = if !bind_all_fields {
-                let mut fs = vec![];
-                let tes = texp_fields
-                    .into_iter()
-                    .map(|(_, f, _, bt, te)| {
-                        let bt = base_type(context, bt);
-                        fs.push((f, bt.clone()));
-                        let t = H::Type_::base(bt);
-                        (te, Some(t))
-                    })
-                    .collect();
-                let es = exp_evaluation_order(context, result, tes);
-                assert!(
-                    fs.len() == es.len(),
-                    "ICE exp_evaluation_order changed arity"
-                );
-                es.into_iter()
-                    .zip(fs)
-                    .map(|(e, (f, bt))| (f, bt, e))
-                    .collect()
-            } else {
-                let num_fields = decl_fields.as_ref().map(|m| m.len()).unwrap_or(0);
-                let mut fields = (0..num_fields).map(|_| None).collect::<Vec<_>>();
-                for (decl_idx, f, _exp_idx, bt, tf) in texp_fields {
-                    // Might have too many arguments, there will be an error from typing
-                    if decl_idx > fields.len() {
-                        debug_assert!(context.env.has_diags());
-                        break;
-                    }
-                    let bt = base_type(context, bt);
-                    let t = H::Type_::base(bt.clone());
-                    let ef = exp_(context, result, Some(&t), tf);
-                    assert!(fields.get(decl_idx).unwrap().is_none());
-                    let move_tmp = bind_exp(context, result, ef);
-                    fields[decl_idx] = Some((f, bt, move_tmp))
-                }
-                // Might have too few arguments, there will be an error from typing if so
-                fields
-                    .into_iter()
-                    .filter_map(|o| {
-                        // if o is None, context should have errors
-                        debug_assert!(o.is_some() || context.env.has_diags());
-                        o
-                    })
-                    .collect()
-            };
+            let fields = pack_fields(context, result, &s, None, tfields);
             HE::Pack(s, bs, fields)
         }
+        TE::PackVariant(_, s, v, tbs, tfields) => {
+            let bs = base_types(context, tbs);
+            let fields = pack_fields(context, result, &s, Some(&v), tfields);
+            HE::PackVariant(s, v, bs, fields)
+        }
+        TE::Match(tsubject, tarms) => match_(context, result, &ty, eloc, *tsubject, tarms),
         TE::ExpList(titems) => {
             assert!(!titems.is_empty());
             let mut tmp_items = vec![];
@@ -1315,8 +1313,11 @@ fn exp_impl(
             let e = exp(context, result, None, *te);
             let bt = match rhs_ty.value.builtin_name() {
                 Some(bt @ sp!(_, BT::U8))
+                | Some(bt @ sp!(_, BT::U16))
+                | Some(bt @ sp!(_, BT::U32))
                 | Some(bt @ sp!(_, BT::U64))
-                | Some(bt @ sp!(_, BT::U128)) => bt.clone(),
+                | Some(bt @ sp!(_, BT::U128))
+                | Some(bt @ sp!(_, BT::U256)) => bt.clone(),
                 _ => panic!("ICE typing failed for cast"),
             };
             HE::Cast(e, bt)
@@ -1340,12 +1341,249 @@ fn exp_impl(
             assert!(context.env.has_diags());
             HE::UnresolvedError
         }
+        TE::VarCall(_, _) | TE::Lambda(_, _, _) => panic!("ICE lambdas should have been inlined"),
 
         TE::IfElse(..) | TE::BinopExp(..) => unreachable!(),
     };
     H::exp(ty, sp(eloc, res))
 }
 
+fn pack_fields(
+    context: &mut Context,
+    result: &mut Block,
+    s: &StructName,
+    variant_opt: Option<&VariantName>,
+    tfields: Fields<(N::Type, T::Exp)>,
+) -> Vec<(Field, H::BaseType, H::Exp)> {
+    let decl_fields = match variant_opt {
+        None => context.fields(s),
+        Some(v) => context.variant_fields(s, v),
+    };
+    let mut count = 0;
+    let mut decl_field = |f: &Field| -> usize {
+        match decl_fields {
+            Some(m) => *m.get(f).unwrap(),
+            None => {
+                // none can occur with errors in typing
+                let i = count;
+                count += 1;
+                i
+            }
+        }
+    };
+
+    let mut texp_fields: Vec<(usize, Field, usize, N::Type, T::Exp)> = tfields
+        .into_iter()
+        .map(|(f, (exp_idx, (bt, tf)))| (decl_field(&f), f, exp_idx, bt, tf))
+        .collect();
+    texp_fields.sort_by(|(_, _, eidx1, _, _), (_, _, eidx2, _, _)| eidx1.cmp(eidx2));
+
+    let bind_all_fields = texp_fields
+        .iter()
+        .any(|(decl_idx, _, exp_idx, _, _)| decl_idx != exp_idx);
+    if !bind_all_fields {
+        let mut fs = vec![];
+        let tes = texp_fields
+            .into_iter()
+            .map(|(_, f, _, bt, te)| {
+                let bt = base_type(context, bt);
+                fs.push((f, bt.clone()));
+                let t = H::Type_::base(bt);
+                (te, Some(t))
+            })
+            .collect();
+        let es = exp_evaluation_order(context, result, tes);
+        assert!(
+            fs.len() == es.len(),
+            "ICE exp_evaluation_order changed arity"
+        );
+        es.into_iter()
+            .zip(fs)
+            .map(|(e, (f, bt))| (f, bt, e))
+            .collect()
+    } else {
+        let num_fields = decl_fields.as_ref().map(|m| m.len()).unwrap_or(0);
+        let mut fields = (0..num_fields).map(|_| None).collect::<Vec<_>>();
+        for (decl_idx, f, _exp_idx, bt, tf) in texp_fields {
+            // Might have too many arguments, there will be an error from typing
+            if decl_idx > fields.len() {
+                debug_assert!(context.env.has_diags());
+                break;
+            }
+            let bt = base_type(context, bt);
+            let t = H::Type_::base(bt.clone());
+            let ef = exp_(context, result, Some(&t), tf);
+            assert!(fields.get(decl_idx).unwrap().is_none());
+            let move_tmp = bind_exp(context, result, ef);
+            fields[decl_idx] = Some((f, bt, move_tmp))
+        }
+        // Might have too few arguments, there will be an error from typing if so
+        fields
+            .into_iter()
+            .filter_map(|o| {
+                // if o is None, context should have errors
+                debug_assert!(o.is_some() || context.env.has_diags());
+                o
+            })
+            .collect()
+    }
+}
+
+// A match is lowered to a chain of if-else statements, one per arm, on a temporary holding the
+// subject. Each arm but the last tests the variant of the subject before running the arm, which
+// unpacks the subject if it is matched by value. The typing pass ensures the match is exhaustive,
+// so the last arm needs no test.
+fn match_(
+    context: &mut Context,
+    result: &mut Block,
+    ty: &H::Type,
+    eloc: Loc,
+    tsubject: T::Exp,
+    tarms: Vec<T::MatchArm>,
+) -> H::UnannotatedExp_ {
+    use H::{
+        Command_ as C, LValue_ as L, SingleType_ as ST, Statement_ as S, UnannotatedExp_ as HE,
+    };
+    use T::MatchPattern_ as P;
+
+    let sloc = tsubject.exp.loc;
+    let subject = exp_(context, result, None, tsubject);
+    if matches!(&subject.exp.value, HE::Unreachable) {
+        return HE::Unreachable;
+    }
+    // Only the variant of a reference is inspected, so a mutable reference is frozen
+    let subject = match &subject.ty.value {
+        H::Type_::Single(sp!(_, ST::Ref(true, _))) => {
+            let frozen_ty = freeze_ty(subject.ty.clone());
+            freeze(context, result, &frozen_ty, subject)
+        }
+        _ => subject,
+    };
+    let subject_st = match &subject.ty.value {
+        H::Type_::Single(st) => st.clone(),
+        _ => panic!("ICE type checking failed"),
+    };
+    let by_ref = matches!(&subject_st.value, ST::Ref(_, _));
+    let tmp = context.new_temp(sloc, subject_st.clone());
+    let tmp_lvalue = sp(sloc, L::Var(tmp, Box::new(subject_st.clone())));
+    let asgn = sp(sloc, C::Assign(vec![tmp_lvalue], Box::new(subject)));
+    result.push_back(sp(sloc, S::Command(asgn)));
+
+    let move_tmp = || {
+        let ty = H::Type_::single(subject_st.clone());
+        H::exp(ty, sp(sloc, use_tmp(tmp)))
+    };
+    let subject_ref = || match &subject_st.value {
+        ST::Ref(_, _) => {
+            let copy_tmp_ = HE::Copy {
+                from_user: false,
+                var: tmp,
+            };
+            H::exp(H::Type_::single(subject_st.clone()), sp(sloc, copy_tmp_))
+        }
+        ST::Base(bt) => {
+            let ref_ty = sp(sloc, ST::Ref(false, bt.clone()));
+            H::exp(
+                H::Type_::single(ref_ty),
+                sp(sloc, HE::BorrowLocal(false, tmp)),
+            )
+        }
+    };
+
+    // Arms after a wildcard are never reached
+    let num_arms = tarms
+        .iter()
+        .position(|sp!(_, (sp!(_, pat_), _))| matches!(pat_, P::Wildcard))
+        .map(|idx| idx + 1)
+        .unwrap_or(tarms.len());
+    let mut arms = vec![];
+    for (idx, sp!(aloc, (sp!(ploc, pat_), tarm))) in tarms.into_iter().take(num_arms).enumerate() {
+        let old_scope = context.local_scope.clone();
+        let mut arm_block = Block::new();
+        let mut cond_opt = None;
+        match pat_ {
+            P::Wildcard => {
+                if !by_ref {
+                    ignore_and_pop(&mut arm_block, move_tmp())
+                }
+            }
+            P::Variant(_m, s, v, tbs, tfields) => {
+                let bs: Vec<_> = base_types(context, tbs);
+                if idx + 1 < num_arms {
+                    let test_ = HE::TestVariant(s, v, bs.clone(), Box::new(subject_ref()));
+                    cond_opt = Some(Box::new(H::exp(H::Type_::bool(ploc), sp(ploc, test_))));
+                }
+                // When matching on a reference, the fields can only be ignored
+                if !by_ref {
+                    tfields
+                        .iter()
+                        .for_each(|(_, _, (_, (_, b)))| declare_bind(context, b));
+                    let mut after = Block::new();
+                    let mut fields = vec![];
+                    for (decl_idx, f, bt, tfa) in assign_fields(context, &s, Some(&v), tfields) {
+                        assert!(fields.len() == decl_idx);
+                        let st = &H::SingleType_::base(bt);
+                        let (fa, mut fafter) = assign(context, &mut arm_block, tfa, st);
+                        after.append(&mut fafter);
+                        fields.push((f, fa))
+                    }
+                    let unpack = sp(ploc, L::UnpackVariant(s, v, bs, fields));
+                    let asgn = sp(ploc, C::Assign(vec![unpack], Box::new(move_tmp())));
+                    arm_block.push_back(sp(ploc, S::Command(asgn)));
+                    arm_block.append(&mut after);
+                }
+            }
+        }
+        let earm = exp_(context, &mut arm_block, Some(ty), tarm);
+        context.local_scope = old_scope;
+        arms.push((aloc, cond_opt, arm_block, earm));
+    }
+
+    let all_unreachable = arms
+        .iter()
+        .all(|(_, _, _, earm)| matches!(&earm.exp.value, HE::Unreachable));
+    let tmps = if all_unreachable {
+        vec![]
+    } else {
+        make_temps(context, eloc, ty.clone())
+    };
+    let mut res = HE::Unreachable;
+    let mut else_block_opt: Option<Block> = None;
+    for (aloc, cond_opt, mut arm_block, earm) in arms.into_iter().rev() {
+        let arm_res = bind_exp_(&mut arm_block, aloc, tmps.clone(), earm);
+        let else_reachable = !matches!(res, HE::Unreachable);
+        let arm_reachable = !matches!(arm_res, HE::Unreachable);
+        if arm_reachable {
+            res = arm_res
+        }
+        let block = match (cond_opt, else_block_opt.take()) {
+            (Some(cond), Some(mut else_block)) => {
+                // A block cannot end with an if-else that falls through, as there would be no
+                // command left to jump to after it
+                if else_reachable && matches!(&else_block.back().unwrap().value, S::IfElse { .. }) {
+                    let unit = HE::Unit {
+                        case: H::UnitCase::Implicit,
+                    };
+                    let unit_ty = sp(aloc, H::Type_::Unit);
+                    ignore_and_pop(&mut else_block, H::exp(unit_ty, sp(aloc, unit)));
+                }
+                let s_ = S::IfElse {
+                    cond,
+                    if_block: arm_block,
+                    else_block,
+                };
+                let mut block = Block::new();
+                block.push_back(sp(aloc, s_));
+                block
+            }
+            (_, _) => arm_block,
+        };
+        else_block_opt = Some(block);
+    }
+    result.append(&mut else_block_opt.unwrap_or_default());
+    res
+}
+
 fn exp_evaluation_order(
     context: &mut Context,
     result: &mut Block,
@@ -1529,8 +1767,11 @@ fn value(_context: &mut Context, sp!(loc, ev_): E::Value) -> H::Value {
         EV::InferredNum(_) => panic!("ICE should have been expanded"),
         EV::Address(a) => HV::Address(a.into_addr_bytes()),
         EV::U8(u) => HV::U8(u),
+        EV::U16(u) => HV::U16(u),
+        EV::U32(u) => HV::U32(u),
         EV::U64(u) => HV::U64(u),
         EV::U128(u) => HV::U128(u),
+        EV::U256(u) => HV::U256(u),
         EV::Bool(u) => HV::Bool(u),
         EV::Bytearray(bytes) => HV::Vector(
             Box::new(H::BaseType_::u8(loc)),
@@ -1689,6 +1930,7 @@ fn bind_for_short_circuit(e: &T::Exp) -> bool {
         TE::Break
         | TE::Continue
         | TE::IfElse(_, _, _)
+        | TE::Match(_, _)
         | TE::While(_, _)
         | TE::Loop { .. }
         | TE::Return(_)
@@ -1705,9 +1947,12 @@ fn bind_for_short_circuit(e: &T::Exp) -> bool {
         | TE::Assign(_, _, _)
         | TE::Mutate(_, _)
         | TE::Pack(_, _, _, _)
+        | TE::PackVariant(_, _, _, _, _)
         | TE::Vector(_, _, _, _)
         | TE::BorrowLocal(_, _)
         | TE::ExpList(_)
+        | TE::VarCall(_, _)
+        | TE::Lambda(_, _, _)
         | TE::Cast(_, _) => panic!("ICE unexpected exp in short circuit check: {:?}", e),
     }
 }
@@ -1984,7 +2229,7 @@ fn remove_unused_bindings_lvalue(unused: &BTreeSet<Var>, sp!(_, l_): &mut H::LVa
     match l_ {
         HL::Var(v, _) if unused.contains(v) => *l_ = HL::Ignore,
         HL::Var(_, _) | HL::Ignore => (),
-        HL::Unpack(_, _, fields) => fields
+        HL::Unpack(_, _, fields) | HL::UnpackVariant(_, _, _, fields) => fields
             .iter_mut()
             .for_each(|(_, l)| remove_unused_bindings_lvalue(unused, l)),
     }
@@ -6,9 +6,9 @@ use anyhow::{anyhow, Result};
 use move_binary_format::{
     access::ModuleAccess,
     file_format::{
-        Ability, AbilitySet, CompiledModule, FunctionDefinition, ModuleHandle, SignatureToken,
-        StructDefinition, StructFieldInformation, StructHandleIndex, StructTypeParameter,
-        TypeParameterIndex, Visibility,
+        Ability, AbilitySet, CompiledModule, FieldDefinition, FunctionDefinition, ModuleHandle,
+        SignatureToken, StructDefinition, StructFieldInformation, StructHandleIndex,
+        StructTypeParameter, TypeParameterIndex, Visibility,
     },
 };
 use move_core_types::language_storage::ModuleId;
@@ -180,39 +180,61 @@ fn write_struct_def(ctx: &mut Context, sdef: &StructDefinition) -> String {
     let mut out = String::new();
 
     let shandle = ctx.module.struct_handle_at(sdef.struct_handle);
+    let keyword = match &sdef.field_information {
+        StructFieldInformation::DeclaredVariants(_) => "enum",
+        StructFieldInformation::Native | StructFieldInformation::Declared(_) => "struct",
+    };
 
     push_line!(
         out,
         format!(
-            "    struct {}{}{} {{",
+            "    {} {}{}{} {{",
+            keyword,
             ctx.module.identifier_at(shandle.name),
             write_struct_type_parameters(&shandle.type_parameters),
             write_ability_modifiers(shandle.abilities),
         )
     );
 
-    let fields = match &sdef.field_information {
-        StructFieldInformation::Native => {
-            push!(out, "    }");
-            return out;
+    match &sdef.field_information {
+        StructFieldInformation::Native => (),
+        StructFieldInformation::Declared(fields) => {
+            for field in fields {
+                push_line!(out, format!("        {},", write_field_def(ctx, field)))
+            }
+        }
+        StructFieldInformation::DeclaredVariants(variants) => {
+            for variant in variants {
+                let fields = variant
+                    .fields
+                    .iter()
+                    .map(|field| write_field_def(ctx, field))
+                    .collect::<Vec<_>>();
+                let name = ctx.module.identifier_at(variant.name);
+                if fields.is_empty() {
+                    push_line!(out, format!("        {},", name))
+                } else {
+                    push_line!(
+                        out,
+                        format!("        {} {{ {} }},", name, fields.join(", "))
+                    )
+                }
+            }
         }
-        StructFieldInformation::Declared(fields) => fields,
-    };
-    for field in fields {
-        push_line!(
-            out,
-            format!(
-                "        {}: {},",
-                ctx.module.identifier_at(field.name),
-                write_signature_token(ctx, &field.signature.0),
-            )
-        )
     }
 
     push!(out, "    }");
     out
 }
 
+fn write_field_def(ctx: &mut Context, field: &FieldDefinition) -> String {
+    format!(
+        "{}: {}",
+        ctx.module.identifier_at(field.name),
+        write_signature_token(ctx, &field.signature.0),
+    )
+}
+
 fn write_function_def(ctx: &mut Context, fdef: &FunctionDefinition) -> String {
     let fhandle = ctx.module.function_handle_at(fdef.function);
     let parameters = &ctx.module.signature_at(fhandle.parameters).0;
@@ -342,8 +364,11 @@ fn write_signature_token(ctx: &mut Context, t: &SignatureToken) -> String {
     match t {
         SignatureToken::Bool => "bool".to_string(),
         SignatureToken::U8 => "u8".to_string(),
+        SignatureToken::U16 => "u16".to_string(),
+        SignatureToken::U32 => "u32".to_string(),
         SignatureToken::U64 => "u64".to_string(),
         SignatureToken::U128 => "u128".to_string(),
+        SignatureToken::U256 => "u256".to_string(),
         SignatureToken::Address => "address".to_string(),
         SignatureToken::Signer => "signer".to_string(),
         SignatureToken::Vector(inner) => format!("vector<{}>", write_signature_token(ctx, inner)),
@@ -12,9 +12,11 @@ pub mod command_line;
 pub mod compiled_unit;
 pub mod diagnostics;
 pub mod expansion;
+pub mod formatter;
 pub mod hlir;
 pub mod interface_generator;
 pub mod ir_translation;
+pub mod linters;
 pub mod naming;
 pub mod parser;
 pub mod shared;
@@ -0,0 +1,490 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Lints report code which is valid, but is likely a mistake or is needlessly complicated. They
+//! only run when linting is enabled in the `Flags`, and only over source modules and scripts (of
+//! the package set in the `LintConfig`, if any).
+//!
+//! A lint is either a `TypingLint`, run over the typed AST after typing, or a `CFGIRLint`, run over
+//! the control flow graphs of CFGIR. Every lint is reported as a warning by default. The level of a
+//! lint can be set by name in the `LintConfig`, and overridden for a module, script or function by
+//! the `lint_allow`, `lint_warn` and `lint_deny` attributes, e.g. `#[lint_allow(while_true)]`.
+
+mod option_equality;
+mod redundant_ref_deref;
+mod self_transfer;
+mod shadowed_constant;
+mod unnecessary_copy;
+mod while_true;
+
+use crate::{
+    cfgir::ast as G,
+    diag,
+    diagnostics::{
+        codes::{Lint, Severity},
+        Diagnostic,
+    },
+    expansion::ast::{self as E, AttributeName_, Attribute_, ModuleIdent},
+    parser::ast::{ConstantName, Var},
+    shared::{
+        known_attributes::{KnownAttribute, LintAttribute},
+        unique_map::UniqueMap,
+        CompilationEnv,
+    },
+    typing::ast as T,
+};
+use move_symbol_pool::Symbol;
+use std::{collections::BTreeMap, fmt, str::FromStr};
+
+//**************************************************************************************************
+// Configuration
+//**************************************************************************************************
+
+/// The name of each lint, as used in attributes and in the `LintConfig`
+const LINTS: &[(Lint, &str)] = &[
+    (Lint::SelfTransfer, "self_transfer"),
+    (Lint::UnnecessaryCopy, "unnecessary_copy"),
+    (Lint::WhileTrue, "while_true"),
+    (Lint::RedundantRefDeref, "redundant_ref_deref"),
+    (Lint::OptionEquality, "option_equality"),
+    (Lint::ShadowedConstant, "shadowed_constant"),
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LintLevel {
+    /// The lint is not reported
+    Allow,
+    /// The lint is reported as a warning
+    Warn,
+    /// The lint is reported as an error, failing compilation
+    Deny,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LintConfig {
+    /// The levels of lints, by lint name. Lints not in the map are reported as warnings
+    pub levels: BTreeMap<Symbol, LintLevel>,
+    /// If set, only the modules and scripts of this package are linted
+    pub package: Option<Symbol>,
+}
+
+pub fn lint_names() -> impl Iterator<Item = &'static str> {
+    LINTS.iter().map(|(_, name)| *name)
+}
+
+pub fn is_known_lint(name: &str) -> bool {
+    lint_names().any(|known| known == name)
+}
+
+fn lint_name(lint: Lint) -> &'static str {
+    LINTS
+        .iter()
+        .find(|(known, _)| *known == lint)
+        .map(|(_, name)| *name)
+        .unwrap()
+}
+
+impl LintLevel {
+    pub const ALLOW: &'static str = "allow";
+    pub const WARN: &'static str = "warn";
+    pub const DENY: &'static str = "deny";
+}
+
+impl FromStr for LintLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            Self::ALLOW => Ok(Self::Allow),
+            Self::WARN => Ok(Self::Warn),
+            Self::DENY => Ok(Self::Deny),
+            _ => Err(format!(
+                "Invalid lint level '{}'. Expected one of '{}', '{}' or '{}'",
+                s,
+                Self::ALLOW,
+                Self::WARN,
+                Self::DENY
+            )),
+        }
+    }
+}
+
+impl fmt::Display for LintLevel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Allow => write!(f, "{}", Self::ALLOW),
+            Self::Warn => write!(f, "{}", Self::WARN),
+            Self::Deny => write!(f, "{}", Self::DENY),
+        }
+    }
+}
+
+//**************************************************************************************************
+// Lints
+//**************************************************************************************************
+
+/// A lint over the typed AST. Function bodies are visited in order, calling `visit_local` for each
+/// local introduced by a parameter, `let`, pattern or lambda, and `visit_exp` for each expression
+/// before its subexpressions.
+pub trait TypingLint {
+    /// Called before visiting each function, with the constants of its module or script
+    fn visit_function(
+        &mut self,
+        _context: &mut LintContext,
+        _constants: &UniqueMap<ConstantName, T::Constant>,
+        _fdef: &T::Function,
+    ) {
+    }
+
+    fn visit_local(&mut self, _context: &mut LintContext, _var: &Var) {}
+
+    fn visit_exp(&mut self, _context: &mut LintContext, _e: &T::Exp) {}
+}
+
+/// A lint over CFGIR, visiting each function with the module declaring it, if any
+pub trait CFGIRLint {
+    fn visit_function(
+        &mut self,
+        context: &mut LintContext,
+        mident: Option<&ModuleIdent>,
+        fdef: &G::Function,
+    );
+}
+
+fn typing_lints() -> Vec<Box<dyn TypingLint>> {
+    vec![
+        Box::new(self_transfer::SelfTransfer),
+        Box::new(while_true::WhileTrue),
+        Box::new(redundant_ref_deref::RedundantRefDeref),
+        Box::new(option_equality::OptionEquality),
+        Box::new(shadowed_constant::ShadowedConstant::default()),
+    ]
+}
+
+fn cfgir_lints() -> Vec<Box<dyn CFGIRLint>> {
+    vec![Box::new(unnecessary_copy::UnnecessaryCopy)]
+}
+
+//**************************************************************************************************
+// Context
+//**************************************************************************************************
+
+pub struct LintContext<'env> {
+    env: &'env mut CompilationEnv,
+    config: LintConfig,
+    // Invalid lint attributes are only reported by the first of the lint passes
+    report_attributes: bool,
+    // The levels set by the attributes of the current module or script
+    module_levels: BTreeMap<&'static str, LintLevel>,
+    // The levels set by the attributes of the current function
+    function_levels: BTreeMap<&'static str, LintLevel>,
+}
+
+impl<'env> LintContext<'env> {
+    fn new(env: &'env mut CompilationEnv, report_attributes: bool) -> Self {
+        let config = env.flags().lint_config().clone();
+        Self {
+            env,
+            config,
+            report_attributes,
+            module_levels: BTreeMap::new(),
+            function_levels: BTreeMap::new(),
+        }
+    }
+
+    fn is_linted(&self, is_source: bool, package_name: Option<Symbol>) -> bool {
+        is_source
+            && self
+                .config
+                .package
+                .map_or(true, |package| package_name == Some(package))
+    }
+
+    fn enter_module(&mut self, attributes: &E::Attributes) {
+        self.module_levels = self.attribute_levels(attributes);
+        self.function_levels = BTreeMap::new();
+    }
+
+    fn enter_function(&mut self, attributes: &E::Attributes) {
+        self.function_levels = self.attribute_levels(attributes);
+    }
+
+    fn level(&self, lint: Lint) -> LintLevel {
+        let name = lint_name(lint);
+        self.function_levels
+            .get(name)
+            .or_else(|| self.module_levels.get(name))
+            .or_else(|| self.config.levels.get(&Symbol::from(name)))
+            .copied()
+            .unwrap_or(LintLevel::Warn)
+    }
+
+    /// Reports the diagnostic of `lint` at the level set for it
+    pub fn add_diag(&mut self, lint: Lint, diag: Diagnostic) {
+        match self.level(lint) {
+            LintLevel::Allow => (),
+            LintLevel::Warn => self.env.add_diag(diag),
+            LintLevel::Deny => self
+                .env
+                .add_diag(diag.set_severity(Severity::NonblockingError)),
+        }
+    }
+
+    fn attribute_levels(
+        &mut self,
+        attributes: &E::Attributes,
+    ) -> BTreeMap<&'static str, LintLevel> {
+        let mut levels = BTreeMap::new();
+        for (sp!(_, name_), sp!(loc, attr_)) in attributes.key_cloned_iter() {
+            let level = match name_ {
+                AttributeName_::Known(KnownAttribute::Lint(LintAttribute::Allow)) => {
+                    LintLevel::Allow
+                }
+                AttributeName_::Known(KnownAttribute::Lint(LintAttribute::Warn)) => LintLevel::Warn,
+                AttributeName_::Known(KnownAttribute::Lint(LintAttribute::Deny)) => LintLevel::Deny,
+                _ => continue,
+            };
+            let lints = match attr_ {
+                Attribute_::Parameterized(_, lints) => lints,
+                Attribute_::Name(_) | Attribute_::Assigned(_, _) => {
+                    let msg = format!(
+                        "Expected a list of lints for attribute '{}', e.g. '{}({})'",
+                        name_,
+                        name_,
+                        lint_name(Lint::WhileTrue)
+                    );
+                    self.add_attribute_diag(diag!(Attributes::InvalidValue, (*loc, msg)));
+                    continue;
+                }
+            };
+            for (_, sp!(lint_loc, lint_attr_)) in lints.key_cloned_iter() {
+                let name = match lint_attr_ {
+                    Attribute_::Name(n) => n,
+                    Attribute_::Assigned(n, _) | Attribute_::Parameterized(n, _) => {
+                        let msg = format!("Expected the name of a lint, found '{}'", n);
+                        self.add_attribute_diag(diag!(Attributes::InvalidValue, (*lint_loc, msg)));
+                        continue;
+                    }
+                };
+                match LINTS
+                    .iter()
+                    .find(|(_, known)| *known == name.value.as_str())
+                {
+                    Some((_, known)) => {
+                        levels.insert(*known, level);
+                    }
+                    None => {
+                        let msg = format!(
+                            "Unknown lint '{}'. Known lints are: {}",
+                            name,
+                            lint_names().collect::<Vec<_>>().join(", ")
+                        );
+                        self.add_attribute_diag(diag!(Attributes::UnknownLint, (name.loc, msg)));
+                    }
+                }
+            }
+        }
+        levels
+    }
+
+    fn add_attribute_diag(&mut self, diag: Diagnostic) {
+        if self.report_attributes {
+            self.env.add_diag(diag)
+        }
+    }
+}
+
+//**************************************************************************************************
+// Typing
+//**************************************************************************************************
+
+pub fn typing(env: &mut CompilationEnv, prog: &T::Program) {
+    if !env.flags().is_linting() {
+        return;
+    }
+    let lints = &mut typing_lints();
+    let context = &mut LintContext::new(env, /* report_attributes */ true);
+    for (_, mdef) in prog.modules.key_cloned_iter() {
+        if !context.is_linted(mdef.is_source_module, mdef.package_name) {
+            continue;
+        }
+        context.enter_module(&mdef.attributes);
+        for (_, fdef) in mdef.functions.key_cloned_iter() {
+            typing_function(context, lints, &mdef.constants, fdef)
+        }
+    }
+    for script in prog.scripts.values() {
+        if !context.is_linted(true, script.package_name) {
+            continue;
+        }
+        context.enter_module(&script.attributes);
+        typing_function(context, lints, &script.constants, &script.function)
+    }
+}
+
+fn typing_function(
+    context: &mut LintContext,
+    lints: &mut [Box<dyn TypingLint>],
+    constants: &UniqueMap<ConstantName, T::Constant>,
+    fdef: &T::Function,
+) {
+    context.enter_function(&fdef.attributes);
+    for lint in lints.iter_mut() {
+        lint.visit_function(context, constants, fdef)
+    }
+    let mut visitor = TypingVisitor { context, lints };
+    for (var, _) in &fdef.signature.parameters {
+        visitor.local(var)
+    }
+    if let T::FunctionBody_::Defined(seq) = &fdef.body.value {
+        visitor.sequence(seq)
+    }
+}
+
+struct TypingVisitor<'a, 'env> {
+    context: &'a mut LintContext<'env>,
+    lints: &'a mut [Box<dyn TypingLint>],
+}
+
+impl<'a, 'env> TypingVisitor<'a, 'env> {
+    fn local(&mut self, var: &Var) {
+        for lint in self.lints.iter_mut() {
+            lint.visit_local(self.context, var)
+        }
+    }
+
+    fn lvalues(&mut self, sp!(_, lvalues): &T::LValueList) {
+        lvalues.iter().for_each(|l| self.lvalue(l))
+    }
+
+    fn lvalue(&mut self, sp!(_, l_): &T::LValue) {
+        use T::LValue_ as L;
+        match l_ {
+            L::Ignore => (),
+            L::Var(var, _) => self.local(var),
+            L::Unpack(_, _, _, fields) | L::BorrowUnpack(_, _, _, _, fields) => {
+                fields.iter().for_each(|(_, _, (_, (_, l)))| self.lvalue(l))
+            }
+        }
+    }
+
+    fn sequence(&mut self, seq: &T::Sequence) {
+        use T::SequenceItem_ as S;
+        for sp!(_, item_) in seq {
+            match item_ {
+                S::Seq(e) => self.exp(e),
+                S::Declare(lvalues) => self.lvalues(lvalues),
+                S::Bind(lvalues, _, e) => {
+                    self.exp(e);
+                    self.lvalues(lvalues)
+                }
+            }
+        }
+    }
+
+    fn exp(&mut self, e: &T::Exp) {
+        use T::UnannotatedExp_ as E;
+        for lint in self.lints.iter_mut() {
+            lint.visit_exp(self.context, e)
+        }
+        match &e.exp.value {
+            E::Unit { .. }
+            | E::Value(_)
+            | E::Move { .. }
+            | E::Copy { .. }
+            | E::Use(_)
+            | E::Constant(_, _)
+            | E::Break
+            | E::Continue
+            | E::BorrowLocal(_, _)
+            | E::Spec(_, _)
+            | E::UnresolvedError => (),
+
+            E::ModuleCall(call) => self.exp(&call.arguments),
+            E::Builtin(_, e)
+            | E::Vector(_, _, _, e)
+            | E::VarCall(_, e)
+            | E::Loop { body: e, .. }
+            | E::Return(e)
+            | E::Abort(e)
+            | E::Assign(_, _, e)
+            | E::Dereference(e)
+            | E::UnaryExp(_, e)
+            | E::Borrow(_, e, _)
+            | E::TempBorrow(_, e)
+            | E::Cast(e, _)
+            | E::Annotate(e, _) => self.exp(e),
+
+            E::IfElse(econd, etrue, efalse) => {
+                self.exp(econd);
+                self.exp(etrue);
+                self.exp(efalse)
+            }
+            E::Match(esubject, arms) => {
+                self.exp(esubject);
+                for sp!(_, (sp!(_, pattern_), earm)) in arms {
+                    if let T::MatchPattern_::Variant(_, _, _, _, fields) = pattern_ {
+                        fields.iter().for_each(|(_, _, (_, (_, l)))| self.lvalue(l))
+                    }
+                    self.exp(earm)
+                }
+            }
+            E::While(e1, e2) | E::Mutate(e1, e2) | E::BinopExp(e1, _, _, e2) => {
+                self.exp(e1);
+                self.exp(e2)
+            }
+            E::Block(seq) => self.sequence(seq),
+            E::Lambda(lvalues, _, body) => {
+                self.lvalues(lvalues);
+                self.exp(body)
+            }
+
+            E::Pack(_, _, _, fields) | E::PackVariant(_, _, _, _, fields) => {
+                fields.iter().for_each(|(_, _, (_, (_, e)))| self.exp(e))
+            }
+            E::ExpList(items) => {
+                for item in items {
+                    match item {
+                        T::ExpListItem::Single(e, _) | T::ExpListItem::Splat(_, e, _) => {
+                            self.exp(e)
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+//**************************************************************************************************
+// CFGIR
+//**************************************************************************************************
+
+pub fn cfgir(env: &mut CompilationEnv, prog: &G::Program) {
+    if !env.flags().is_linting() {
+        return;
+    }
+    let lints = &mut cfgir_lints();
+    let context = &mut LintContext::new(env, /* report_attributes */ false);
+    for (mident, mdef) in prog.modules.key_cloned_iter() {
+        if !context.is_linted(mdef.is_source_module, mdef.package_name) {
+            continue;
+        }
+        context.enter_module(&mdef.attributes);
+        for (_, fdef) in mdef.functions.key_cloned_iter() {
+            context.enter_function(&fdef.attributes);
+            for lint in lints.iter_mut() {
+                lint.visit_function(context, Some(&mident), fdef)
+            }
+        }
+    }
+    for script in prog.scripts.values() {
+        if !context.is_linted(true, script.package_name) {
+            continue;
+        }
+        context.enter_module(&script.attributes);
+        context.enter_function(&script.function.attributes);
+        for lint in lints.iter_mut() {
+            lint.visit_function(context, None, &script.function)
+        }
+    }
+}
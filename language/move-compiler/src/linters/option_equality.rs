@@ -0,0 +1,56 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Reports comparisons of `std::option::Option` values with `==` or `!=`. The functions of the
+//! `option` module, such as `option::is_none` and `option::contains`, state the intent more
+//! clearly, and only need a reference to the `Option`.
+
+use super::{LintContext, TypingLint};
+use crate::{
+    diag,
+    diagnostics::codes::Lint,
+    expansion::ast::ModuleIdent,
+    naming::ast::{Type, TypeName_, Type_},
+    parser::ast::BinOp_,
+    typing::ast as T,
+};
+use move_core_types::account_address::AccountAddress;
+
+const OPTION_MODULE: &str = "option";
+const OPTION_STRUCT: &str = "Option";
+
+pub struct OptionEquality;
+
+impl TypingLint for OptionEquality {
+    fn visit_exp(&mut self, context: &mut LintContext, e: &T::Exp) {
+        use T::UnannotatedExp_ as E;
+        if let E::BinopExp(_, sp!(_, op @ (BinOp_::Eq | BinOp_::Neq)), ty, _) = &e.exp.value {
+            if is_option(ty) {
+                let msg = format!(
+                    "Comparing an 'Option' with '{}'. Consider using the functions of the \
+                     '{}' module, such as '{}::is_none' or '{}::contains', instead",
+                    op, OPTION_MODULE, OPTION_MODULE, OPTION_MODULE
+                );
+                context.add_diag(
+                    Lint::OptionEquality,
+                    diag!(Lint::OptionEquality, (e.exp.loc, msg)),
+                )
+            }
+        }
+    }
+}
+
+fn is_option(sp!(_, ty_): &Type) -> bool {
+    match ty_ {
+        Type_::Ref(_, inner) => is_option(inner),
+        Type_::Apply(_, sp!(_, TypeName_::ModuleType(m, s)), _) => {
+            is_std_module(m, OPTION_MODULE) && s.0.value.as_str() == OPTION_STRUCT
+        }
+        _ => false,
+    }
+}
+
+fn is_std_module(sp!(_, m): &ModuleIdent, name: &str) -> bool {
+    m.address.into_addr_bytes().into_inner() == AccountAddress::ONE
+        && m.module.0.value.as_str() == name
+}
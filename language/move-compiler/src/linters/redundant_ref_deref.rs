@@ -0,0 +1,33 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Reports dereferences of borrows, `*&e`, which are copies of `e`, and borrows of dereferences,
+//! `&*e`, which borrow a copy of the value behind `e` where `e` itself (or `freeze(e)`) could be
+//! used.
+
+use super::{LintContext, TypingLint};
+use crate::{diag, diagnostics::codes::Lint, typing::ast as T};
+
+pub struct RedundantRefDeref;
+
+impl TypingLint for RedundantRefDeref {
+    fn visit_exp(&mut self, context: &mut LintContext, e: &T::Exp) {
+        use T::UnannotatedExp_ as E;
+        let msg = match &e.exp.value {
+            E::Dereference(inner)
+                if matches!(&inner.exp.value, E::BorrowLocal(_, _) | E::TempBorrow(_, _)) =>
+            {
+                "Redundant dereference of a borrow. Consider using the value directly"
+            }
+            E::TempBorrow(_, inner) if matches!(&inner.exp.value, E::Dereference(_)) => {
+                "Redundant borrow of a dereference. Consider using the reference directly, or \
+                 'freeze' to get an immutable reference from a mutable one"
+            }
+            _ => return,
+        };
+        context.add_diag(
+            Lint::RedundantRefDeref,
+            diag!(Lint::RedundantRefDeref, (e.exp.loc, msg)),
+        )
+    }
+}
@@ -0,0 +1,68 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Reports assignments of a local, or of a location behind a reference, to itself, e.g. `x = x`,
+//! `*r = *r` or `s.f = s.f`, which have no effect.
+
+use super::{LintContext, TypingLint};
+use crate::{
+    diag,
+    diagnostics::codes::Lint,
+    parser::ast::{Field, Var},
+    typing::ast as T,
+};
+
+pub struct SelfTransfer;
+
+impl TypingLint for SelfTransfer {
+    fn visit_exp(&mut self, context: &mut LintContext, e: &T::Exp) {
+        use T::UnannotatedExp_ as E;
+        let is_self_transfer = match &e.exp.value {
+            E::Assign(sp!(_, lvalues), _, rhs) => match (&lvalues[..], used_local(rhs)) {
+                ([sp!(_, T::LValue_::Var(lhs, _))], Some(rhs)) => lhs == rhs,
+                _ => false,
+            },
+            E::Mutate(lhs, rhs) => match &rhs.exp.value {
+                E::Dereference(rhs) => match (borrowed_place(lhs), borrowed_place(rhs)) {
+                    (Some(lhs), Some(rhs)) => lhs == rhs,
+                    _ => false,
+                },
+                _ => false,
+            },
+            _ => false,
+        };
+        if is_self_transfer {
+            let msg = "Unnecessary assignment of a location to itself. It has no effect";
+            context.add_diag(
+                Lint::SelfTransfer,
+                diag!(Lint::SelfTransfer, (e.exp.loc, msg)),
+            )
+        }
+    }
+}
+
+// The local read by `e`, if `e` only reads a local
+fn used_local(e: &T::Exp) -> Option<&Var> {
+    use T::UnannotatedExp_ as E;
+    match &e.exp.value {
+        E::Copy { var, .. } | E::Move { var, .. } | E::Use(var) => Some(var),
+        E::Annotate(e, _) => used_local(e),
+        _ => None,
+    }
+}
+
+// The local and the path of fields borrowed by the reference `e`, e.g. `s` and `[f, g]` for
+// `&mut s.f.g`. A local of a reference type is its own root
+fn borrowed_place(e: &T::Exp) -> Option<(&Var, Vec<&Field>)> {
+    use T::UnannotatedExp_ as E;
+    match &e.exp.value {
+        E::BorrowLocal(_, var) => Some((var, vec![])),
+        E::Borrow(_, e, field) => {
+            let (var, mut fields) = borrowed_place(e)?;
+            fields.push(field);
+            Some((var, fields))
+        }
+        E::Annotate(e, _) => borrowed_place(e),
+        _ => used_local(e).map(|var| (var, vec![])),
+    }
+}
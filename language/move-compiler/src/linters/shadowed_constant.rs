@@ -0,0 +1,53 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Reports locals named like a constant of their module or script apart from case, e.g. a local
+//! `max_value` next to a constant `MAX_VALUE`. As constant names start with an uppercase letter and
+//! local names do not, the local never refers to the constant, but the two are easily confused.
+
+use super::{LintContext, TypingLint};
+use crate::{
+    diag,
+    diagnostics::codes::Lint,
+    parser::ast::{ConstantName, Var},
+    shared::unique_map::UniqueMap,
+    typing::ast as T,
+};
+use std::collections::BTreeMap;
+
+#[derive(Default)]
+pub struct ShadowedConstant {
+    // The constants of the current module or script, by their lowercase name
+    constants: BTreeMap<String, ConstantName>,
+}
+
+impl TypingLint for ShadowedConstant {
+    fn visit_function(
+        &mut self,
+        _context: &mut LintContext,
+        constants: &UniqueMap<ConstantName, T::Constant>,
+        _fdef: &T::Function,
+    ) {
+        self.constants = constants
+            .key_cloned_iter()
+            .map(|(name, _)| (name.0.value.as_str().to_lowercase(), name))
+            .collect();
+    }
+
+    fn visit_local(&mut self, context: &mut LintContext, var: &Var) {
+        if let Some(constant) = self.constants.get(&var.0.value.as_str().to_lowercase()) {
+            let msg = format!(
+                "Local '{}' is named like the constant '{}'. Consider renaming it",
+                var, constant
+            );
+            context.add_diag(
+                Lint::ShadowedConstant,
+                diag!(
+                    Lint::ShadowedConstant,
+                    (var.0.loc, msg),
+                    (constant.0.loc, "Constant declared here"),
+                ),
+            )
+        }
+    }
+}
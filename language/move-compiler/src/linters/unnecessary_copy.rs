@@ -0,0 +1,197 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Reports explicit `copy`s of locals which are not used afterwards, where a `move` would avoid
+//! copying the value. Locals borrowed anywhere in the function are not reported, as they might
+//! still be borrowed when copied, in which case they cannot be moved.
+
+use super::{CFGIRLint, LintContext};
+use crate::{
+    cfgir::ast as G,
+    diag,
+    diagnostics::codes::Lint,
+    expansion::ast::ModuleIdent,
+    hlir::{
+        ast::{
+            BasicBlock, Command, Command_, Exp, ExpListItem, LValue, LValue_, Label,
+            UnannotatedExp_,
+        },
+        translate::{display_var, DisplayVar},
+    },
+    parser::ast::Var,
+};
+use move_ir_types::location::*;
+use std::collections::{BTreeMap, BTreeSet};
+
+pub struct UnnecessaryCopy;
+
+impl CFGIRLint for UnnecessaryCopy {
+    fn visit_function(
+        &mut self,
+        context: &mut LintContext,
+        _mident: Option<&ModuleIdent>,
+        fdef: &G::Function,
+    ) {
+        let blocks = match &fdef.body.value {
+            G::FunctionBody_::Defined { blocks, .. } => blocks,
+            G::FunctionBody_::Native => return,
+        };
+
+        // The locals live at the start of each block, computed until reaching a fixpoint
+        let mut live_in: BTreeMap<Label, BTreeSet<Var>> = BTreeMap::new();
+        loop {
+            let mut changed = false;
+            for (lbl, block) in blocks.iter().rev() {
+                let mut state = Liveness::at_end(&live_in, block);
+                for cmd in block.iter().rev() {
+                    state.command(cmd)
+                }
+                if live_in.get(lbl) != Some(&state.live) {
+                    live_in.insert(*lbl, state.live);
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        let mut borrowed = BTreeSet::new();
+        let mut last_copies = vec![];
+        for block in blocks.values() {
+            let mut state = Liveness::at_end(&live_in, block);
+            for cmd in block.iter().rev() {
+                state.command(cmd)
+            }
+            borrowed.extend(state.borrowed);
+            last_copies.extend(state.last_copies);
+        }
+        for (loc, var) in last_copies {
+            if borrowed.contains(&var) {
+                continue;
+            }
+            let var = match display_var(var.0.value) {
+                DisplayVar::Tmp => continue,
+                DisplayVar::Orig(var) => var,
+            };
+            let msg = format!(
+                "Unnecessary 'copy' of '{}'. It is not used afterwards, so it can be moved \
+                 instead",
+                var
+            );
+            context.add_diag(
+                Lint::UnnecessaryCopy,
+                diag!(Lint::UnnecessaryCopy, (loc, msg)),
+            )
+        }
+    }
+}
+
+// Walks a block backwards, from its last command to its first, tracking the live locals
+struct Liveness {
+    live: BTreeSet<Var>,
+    borrowed: BTreeSet<Var>,
+    // The explicit copies of locals which were not live after the copy
+    last_copies: Vec<(Loc, Var)>,
+}
+
+impl Liveness {
+    fn at_end(live_in: &BTreeMap<Label, BTreeSet<Var>>, block: &BasicBlock) -> Self {
+        let successors = block.back().unwrap().value.successors();
+        let live = successors
+            .iter()
+            .filter_map(|lbl| live_in.get(lbl))
+            .flatten()
+            .cloned()
+            .collect();
+        Self {
+            live,
+            borrowed: BTreeSet::new(),
+            last_copies: vec![],
+        }
+    }
+
+    fn command(&mut self, sp!(_, cmd_): &Command) {
+        use Command_ as C;
+        match cmd_ {
+            C::Assign(ls, e) => {
+                ls.iter().for_each(|l| self.lvalue(l));
+                self.exp(e)
+            }
+            C::Mutate(el, er) => {
+                self.exp(el);
+                self.exp(er)
+            }
+            C::Return { exp: e, .. }
+            | C::Abort(e)
+            | C::IgnoreAndPop { exp: e, .. }
+            | C::JumpIf { cond: e, .. } => self.exp(e),
+            C::Jump { .. } => (),
+            C::Break | C::Continue => panic!("ICE break/continue not translated to jumps"),
+        }
+    }
+
+    fn lvalue(&mut self, sp!(_, l_): &LValue) {
+        use LValue_ as L;
+        match l_ {
+            L::Ignore => (),
+            L::Var(var, _) => {
+                self.live.remove(var);
+            }
+            L::Unpack(_, _, fields) | L::UnpackVariant(_, _, _, fields) => {
+                fields.iter().for_each(|(_, l)| self.lvalue(l))
+            }
+        }
+    }
+
+    // Expressions are visited in the reverse of their evaluation order
+    fn exp(&mut self, e: &Exp) {
+        use UnannotatedExp_ as E;
+        match &e.exp.value {
+            E::Unit { .. }
+            | E::Value(_)
+            | E::Constant(_, _)
+            | E::Unreachable
+            | E::UnresolvedError => {}
+
+            E::Copy { var, from_user } => {
+                if *from_user && !self.live.contains(var) {
+                    self.last_copies.push((e.exp.loc, *var))
+                }
+                self.live.insert(*var);
+            }
+            E::Move { var, .. } => {
+                self.live.insert(*var);
+            }
+            E::BorrowLocal(_, var) => {
+                self.live.insert(*var);
+                self.borrowed.insert(*var);
+            }
+            E::Spec(_, used_locals) => self.live.extend(used_locals.keys().cloned()),
+
+            E::ModuleCall(call) => self.exp(&call.arguments),
+            E::Builtin(_, e)
+            | E::Vector(_, _, _, e)
+            | E::Freeze(e)
+            | E::Dereference(e)
+            | E::UnaryExp(_, e)
+            | E::Borrow(_, e, _)
+            | E::TestVariant(_, _, _, e)
+            | E::Cast(e, _) => self.exp(e),
+            E::BinopExp(e1, _, e2) => {
+                self.exp(e2);
+                self.exp(e1)
+            }
+            E::Pack(_, _, fields) | E::PackVariant(_, _, _, fields) => {
+                fields.iter().rev().for_each(|(_, _, e)| self.exp(e))
+            }
+            E::ExpList(items) => {
+                for item in items.iter().rev() {
+                    match item {
+                        ExpListItem::Single(e, _) | ExpListItem::Splat(_, e, _) => self.exp(e),
+                    }
+                }
+            }
+        }
+    }
+}
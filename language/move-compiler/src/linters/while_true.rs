@@ -0,0 +1,24 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Reports `while (true)` loops, which are more clearly written as `loop`.
+
+use super::{LintContext, TypingLint};
+use crate::{diag, diagnostics::codes::Lint, expansion::ast::Value_, typing::ast as T};
+
+pub struct WhileTrue;
+
+impl TypingLint for WhileTrue {
+    fn visit_exp(&mut self, context: &mut LintContext, e: &T::Exp) {
+        use T::UnannotatedExp_ as E;
+        if let E::While(econd, _) = &e.exp.value {
+            if let E::Value(sp!(_, Value_::Bool(true))) = &econd.exp.value {
+                let msg = "Unnecessary 'while (true)'. Replace it with 'loop'";
+                context.add_diag(
+                    Lint::WhileTrue,
+                    diag!(Lint::WhileTrue, (econd.exp.loc, msg)),
+                )
+            }
+        }
+    }
+}
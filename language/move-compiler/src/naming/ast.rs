@@ -4,10 +4,11 @@
 use crate::{
     expansion::ast::{
         ability_constraints_ast_debug, ability_modifiers_ast_debug, AbilitySet, Attributes, Fields,
-        Friend, ModuleIdent, SpecId, Value, Value_, Visibility,
+        Friend, ModuleIdent, SpecId, Value, Value_, Variants, Visibility,
     },
     parser::ast::{
-        BinOp, ConstantName, Field, FunctionName, StructName, UnaryOp, Var, ENTRY_MODIFIER,
+        BinOp, ConstantName, Field, FunctionName, StructName, UnaryOp, Var, VariantName,
+        ENTRY_MODIFIER, INLINE_MODIFIER,
     },
     shared::{ast_debug::*, unique_map::UniqueMap, *},
 };
@@ -84,6 +85,7 @@ pub struct StructTypeParameter {
 #[derive(Debug, PartialEq, Clone)]
 pub enum StructFields {
     Defined(Fields<Type>),
+    Variants(Variants<Type>),
     Native(Loc),
 }
 
@@ -110,6 +112,7 @@ pub struct Function {
     pub attributes: Attributes,
     pub visibility: Visibility,
     pub entry: Option<Loc>,
+    pub inline: bool,
     pub signature: FunctionSignature,
     pub acquires: BTreeMap<StructName, Loc>,
     pub body: FunctionBody,
@@ -123,6 +126,7 @@ pub struct Function {
 pub struct Constant {
     pub attributes: Attributes,
     pub loc: Loc,
+    pub visibility: Visibility,
     pub signature: Type,
     pub value: Exp,
 }
@@ -139,10 +143,16 @@ pub enum BuiltinTypeName_ {
     Signer,
     // u8
     U8,
+    // u16
+    U16,
+    // u32
+    U32,
     // u64
     U64,
     // u128
     U128,
+    // u256
+    U256,
     // Vector
     Vector,
     // bool
@@ -181,6 +191,8 @@ pub enum Type_ {
     Param(TParam),
     Apply(Option<AbilitySet>, TypeName, Vec<Type>),
     Var(TVar),
+    // The type of a parameter of an inline function, which takes a lambda
+    Fun(Vec<Type>, Box<Type>),
     Anything,
     UnresolvedError,
 }
@@ -201,6 +213,20 @@ pub type LValue = Spanned<LValue_>;
 pub type LValueList_ = Vec<LValue>;
 pub type LValueList = Spanned<LValueList_>;
 
+#[derive(Debug, PartialEq, Clone)]
+pub enum MatchPattern_ {
+    Wildcard,
+    Variant(
+        ModuleIdent,
+        StructName,
+        VariantName,
+        Option<Vec<Type>>,
+        Fields<LValue>,
+    ),
+}
+pub type MatchPattern = Spanned<MatchPattern_>;
+pub type MatchArm = Spanned<(MatchPattern, Exp)>;
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum ExpDotted_ {
     Exp(Box<Exp>),
@@ -237,11 +263,15 @@ pub enum Exp_ {
     ),
     Builtin(BuiltinFunction, Spanned<Vec<Exp>>),
     Vector(Loc, Option<Type>, Spanned<Vec<Exp>>),
+    // Call of a lambda parameter of an inline function
+    VarCall(Var, Spanned<Vec<Exp>>),
 
     IfElse(Box<Exp>, Box<Exp>, Box<Exp>),
+    Match(Box<Exp>, Vec<MatchArm>),
     While(Box<Exp>, Box<Exp>),
     Loop(Box<Exp>),
     Block(Sequence),
+    Lambda(LValueList, Box<Exp>),
 
     Assign(LValueList, Box<Exp>),
     FieldMutate(ExpDotted, Box<Exp>),
@@ -257,6 +287,13 @@ pub enum Exp_ {
     BinopExp(Box<Exp>, BinOp, Box<Exp>),
 
     Pack(ModuleIdent, StructName, Option<Vec<Type>>, Fields<Exp>),
+    PackVariant(
+        ModuleIdent,
+        StructName,
+        VariantName,
+        Option<Vec<Type>>,
+        Fields<Exp>,
+    ),
     ExpList(Vec<Exp>),
     Unit {
         trailing: bool,
@@ -292,8 +329,11 @@ static BUILTIN_TYPE_ALL_NAMES: Lazy<BTreeSet<Symbol>> = Lazy::new(|| {
         BuiltinTypeName_::ADDRESS,
         BuiltinTypeName_::SIGNER,
         BuiltinTypeName_::U_8,
+        BuiltinTypeName_::U_16,
+        BuiltinTypeName_::U_32,
         BuiltinTypeName_::U_64,
         BuiltinTypeName_::U_128,
+        BuiltinTypeName_::U_256,
         BuiltinTypeName_::BOOL,
         BuiltinTypeName_::VECTOR,
     ]
@@ -305,8 +345,11 @@ static BUILTIN_TYPE_ALL_NAMES: Lazy<BTreeSet<Symbol>> = Lazy::new(|| {
 static BUILTIN_TYPE_NUMERIC: Lazy<BTreeSet<BuiltinTypeName_>> = Lazy::new(|| {
     [
         BuiltinTypeName_::U8,
+        BuiltinTypeName_::U16,
+        BuiltinTypeName_::U32,
         BuiltinTypeName_::U64,
         BuiltinTypeName_::U128,
+        BuiltinTypeName_::U256,
     ]
     .iter()
     .cloned()
@@ -323,8 +366,11 @@ impl BuiltinTypeName_ {
     pub const ADDRESS: &'static str = "address";
     pub const SIGNER: &'static str = "signer";
     pub const U_8: &'static str = "u8";
+    pub const U_16: &'static str = "u16";
+    pub const U_32: &'static str = "u32";
     pub const U_64: &'static str = "u64";
     pub const U_128: &'static str = "u128";
+    pub const U_256: &'static str = "u256";
     pub const BOOL: &'static str = "bool";
     pub const VECTOR: &'static str = "vector";
 
@@ -354,8 +400,11 @@ impl BuiltinTypeName_ {
             BT::ADDRESS => Some(BT::Address),
             BT::SIGNER => Some(BT::Signer),
             BT::U_8 => Some(BT::U8),
+            BT::U_16 => Some(BT::U16),
+            BT::U_32 => Some(BT::U32),
             BT::U_64 => Some(BT::U64),
             BT::U_128 => Some(BT::U128),
+            BT::U_256 => Some(BT::U256),
             BT::BOOL => Some(BT::Bool),
             BT::VECTOR => Some(BT::Vector),
             _ => None,
@@ -366,7 +415,9 @@ impl BuiltinTypeName_ {
         use BuiltinTypeName_ as B;
         // Match here to make sure this function is fixed when collections are added
         match self {
-            B::Address | B::U8 | B::U64 | B::U128 | B::Bool => AbilitySet::primitives(loc),
+            B::Address | B::U8 | B::U16 | B::U32 | B::U64 | B::U128 | B::U256 | B::Bool => {
+                AbilitySet::primitives(loc)
+            }
             B::Signer => AbilitySet::signer(loc),
             B::Vector => AbilitySet::collection(loc),
         }
@@ -376,7 +427,15 @@ impl BuiltinTypeName_ {
         use BuiltinTypeName_ as B;
         // Match here to make sure this function is fixed when collections are added
         match self {
-            B::Address | B::Signer | B::U8 | B::U64 | B::U128 | B::Bool => vec![],
+            B::Address
+            | B::Signer
+            | B::U8
+            | B::U16
+            | B::U32
+            | B::U64
+            | B::U128
+            | B::U256
+            | B::Bool => vec![],
             B::Vector => vec![AbilitySet::empty()],
         }
     }
@@ -453,7 +512,9 @@ impl Type_ {
     pub fn builtin_(b: BuiltinTypeName, ty_args: Vec<Type>) -> Type_ {
         use BuiltinTypeName_ as B;
         let abilities = match &b.value {
-            B::Address | B::U8 | B::U64 | B::U128 | B::Bool => Some(AbilitySet::primitives(b.loc)),
+            B::Address | B::U8 | B::U16 | B::U32 | B::U64 | B::U128 | B::U256 | B::Bool => {
+                Some(AbilitySet::primitives(b.loc))
+            }
             B::Signer => Some(AbilitySet::signer(b.loc)),
             B::Vector => None,
         };
@@ -481,6 +542,14 @@ impl Type_ {
         Self::builtin(loc, sp(loc, BuiltinTypeName_::U8), vec![])
     }
 
+    pub fn u16(loc: Loc) -> Type {
+        Self::builtin(loc, sp(loc, BuiltinTypeName_::U16), vec![])
+    }
+
+    pub fn u32(loc: Loc) -> Type {
+        Self::builtin(loc, sp(loc, BuiltinTypeName_::U32), vec![])
+    }
+
     pub fn u64(loc: Loc) -> Type {
         Self::builtin(loc, sp(loc, BuiltinTypeName_::U64), vec![])
     }
@@ -489,6 +558,10 @@ impl Type_ {
         Self::builtin(loc, sp(loc, BuiltinTypeName_::U128), vec![])
     }
 
+    pub fn u256(loc: Loc) -> Type {
+        Self::builtin(loc, sp(loc, BuiltinTypeName_::U256), vec![])
+    }
+
     pub fn vector(loc: Loc, elem: Type) -> Type {
         Self::builtin(loc, sp(loc, BuiltinTypeName_::Vector), vec![elem])
     }
@@ -520,8 +593,11 @@ impl Value_ {
             Address(_) => Type_::address(loc),
             InferredNum(_) => return None,
             U8(_) => Type_::u8(loc),
+            U16(_) => Type_::u16(loc),
+            U32(_) => Type_::u32(loc),
             U64(_) => Type_::u64(loc),
             U128(_) => Type_::u128(loc),
+            U256(_) => Type_::u256(loc),
             Bool(_) => Type_::bool(loc),
             Bytearray(_) => Type_::vector(loc, Type_::u8(loc)),
         })
@@ -542,8 +618,11 @@ impl fmt::Display for BuiltinTypeName_ {
                 BT::Address => BT::ADDRESS,
                 BT::Signer => BT::SIGNER,
                 BT::U8 => BT::U_8,
+                BT::U16 => BT::U_16,
+                BT::U32 => BT::U_32,
                 BT::U64 => BT::U_64,
                 BT::U128 => BT::U_128,
+                BT::U256 => BT::U_256,
                 BT::Bool => BT::BOOL,
                 BT::Vector => BT::VECTOR,
             }
@@ -658,21 +737,32 @@ impl AstDebug for (StructName, &StructDefinition) {
             },
         ) = self;
         attributes.ast_debug(w);
-        if let StructFields::Native(_) = fields {
-            w.write("native ");
+        match fields {
+            StructFields::Native(_) => w.write(&format!("native struct {}", name)),
+            StructFields::Defined(_) => w.write(&format!("struct {}", name)),
+            StructFields::Variants(_) => w.write(&format!("enum {}", name)),
         }
-        w.write(&format!("struct {}", name));
         type_parameters.ast_debug(w);
         ability_modifiers_ast_debug(w, abilities);
-        if let StructFields::Defined(fields) = fields {
-            w.block(|w| {
-                w.list(fields, ",", |w, (_, f, idx_st)| {
-                    let (idx, st) = idx_st;
-                    w.write(&format!("{}#{}: ", idx, f));
-                    st.ast_debug(w);
-                    true
-                })
+        let fields_ast_debug = |w: &mut AstWriter, fields: &Fields<Type>| {
+            w.list(fields, ",", |w, (_, f, idx_st)| {
+                let (idx, st) = idx_st;
+                w.write(&format!("{}#{}: ", idx, f));
+                st.ast_debug(w);
+                true
             })
+        };
+        match fields {
+            StructFields::Defined(fields) => w.block(|w| fields_ast_debug(w, fields)),
+            StructFields::Variants(variants) => w.block(|w| {
+                w.list(variants, ",", |w, (_, v, idx_fields)| {
+                    let (idx, fields) = idx_fields;
+                    w.write(&format!("{}#{}", idx, v));
+                    w.block(|w| fields_ast_debug(w, fields));
+                    true
+                });
+            }),
+            StructFields::Native(_) => (),
         }
     }
 }
@@ -685,6 +775,7 @@ impl AstDebug for (FunctionName, &Function) {
                 attributes,
                 visibility,
                 entry,
+                inline,
                 signature,
                 acquires,
                 body,
@@ -695,6 +786,9 @@ impl AstDebug for (FunctionName, &Function) {
         if entry.is_some() {
             w.write(&format!("{} ", ENTRY_MODIFIER));
         }
+        if *inline {
+            w.write(&format!("{} ", INLINE_MODIFIER));
+        }
         if let FunctionBody_::Native = &body.value {
             w.write("native ");
         }
@@ -757,11 +851,13 @@ impl AstDebug for (ConstantName, &Constant) {
             Constant {
                 attributes,
                 loc: _loc,
+                visibility,
                 signature,
                 value,
             },
         ) = self;
         attributes.ast_debug(w);
+        visibility.ast_debug(w);
         w.write(&format!("const {}:", name));
         signature.ast_debug(w);
         w.write(" = ");
@@ -856,6 +952,12 @@ impl AstDebug for Type_ {
                 }
             }
             Type_::Var(tv) => w.write(&format!("#{}", tv.0)),
+            Type_::Fun(args, result) => {
+                w.write("|");
+                w.comma(args, |w, ty| ty.ast_debug(w));
+                w.write("|");
+                result.ast_debug(w);
+            }
             Type_::Anything => w.write("_"),
             Type_::UnresolvedError => w.write("_|_"),
         }
@@ -938,6 +1040,12 @@ impl AstDebug for Exp_ {
                 w.comma(elems, |w, e| e.ast_debug(w));
                 w.write("]");
             }
+            E::VarCall(v, sp!(_, rhs)) => {
+                w.write(&format!("{}", v));
+                w.write("(");
+                w.comma(rhs, |w, e| e.ast_debug(w));
+                w.write(")");
+            }
             E::Pack(m, s, tys_opt, fields) => {
                 w.write(&format!("{}::{}", m, s));
                 if let Some(ss) = tys_opt {
@@ -953,6 +1061,21 @@ impl AstDebug for Exp_ {
                 });
                 w.write("}");
             }
+            E::PackVariant(m, s, v, tys_opt, fields) => {
+                w.write(&format!("{}::{}::{}", m, s, v));
+                if let Some(ss) = tys_opt {
+                    w.write("<");
+                    ss.ast_debug(w);
+                    w.write(">");
+                }
+                w.write("{");
+                w.comma(fields, |w, (_, f, idx_e)| {
+                    let (idx, e) = idx_e;
+                    w.write(&format!("{}#{}: ", idx, f));
+                    e.ast_debug(w);
+                });
+                w.write("}");
+            }
             E::IfElse(b, t, f) => {
                 w.write("if (");
                 b.ast_debug(w);
@@ -961,6 +1084,18 @@ impl AstDebug for Exp_ {
                 w.write(" else ");
                 f.ast_debug(w);
             }
+            E::Match(e, arms) => {
+                w.write("match (");
+                e.ast_debug(w);
+                w.write(") ");
+                w.block(|w| {
+                    w.comma(arms, |w, sp!(_, (pattern, rhs))| {
+                        pattern.ast_debug(w);
+                        w.write(" => ");
+                        rhs.ast_debug(w);
+                    })
+                });
+            }
             E::While(b, e) => {
                 w.write("while (");
                 b.ast_debug(w);
@@ -972,6 +1107,12 @@ impl AstDebug for Exp_ {
                 e.ast_debug(w);
             }
             E::Block(seq) => w.block(|w| seq.ast_debug(w)),
+            E::Lambda(sp!(_, bs), e) => {
+                w.write("|");
+                w.comma(bs, |w, b| b.ast_debug(w));
+                w.write("| ");
+                e.ast_debug(w);
+            }
             E::ExpList(es) => {
                 w.write("(");
                 w.comma(es, |w, e| e.ast_debug(w));
@@ -1106,6 +1247,29 @@ impl AstDebug for Vec<LValue> {
     }
 }
 
+impl AstDebug for MatchPattern_ {
+    fn ast_debug(&self, w: &mut AstWriter) {
+        match self {
+            MatchPattern_::Wildcard => w.write("_"),
+            MatchPattern_::Variant(m, s, v, tys_opt, fields) => {
+                w.write(&format!("{}::{}::{}", m, s, v));
+                if let Some(ss) = tys_opt {
+                    w.write("<");
+                    ss.ast_debug(w);
+                    w.write(">");
+                }
+                w.write("{");
+                w.comma(fields, |w, (_, f, idx_b)| {
+                    let (idx, b) = idx_b;
+                    w.write(&format!("{}#{}: ", idx, f));
+                    b.ast_debug(w);
+                });
+                w.write("}");
+            }
+        }
+    }
+}
+
 impl AstDebug for LValue_ {
     fn ast_debug(&self, w: &mut AstWriter) {
         use LValue_ as L;
@@ -6,7 +6,9 @@ use crate::{
     diagnostics::codes::*,
     expansion::{
         ast::{self as E, AbilitySet, ModuleIdent},
-        translate::is_valid_struct_constant_or_schema_name as is_constant_name,
+        translate::{
+            deprecation, is_valid_struct_constant_or_schema_name as is_constant_name, Deprecation,
+        },
     },
     naming::ast as N,
     parser::ast::{Ability_, ConstantName, Field, FunctionName, StructName, Var},
@@ -15,7 +17,7 @@ use crate::{
 };
 use move_ir_types::location::*;
 use move_symbol_pool::Symbol;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use super::fake_natives;
 
@@ -44,11 +46,19 @@ impl ResolvedType {
 struct Context<'env> {
     env: &'env mut CompilationEnv,
     current_module: Option<ModuleIdent>,
+    // Uses of deprecated items are not reported in dependencies, as they cannot be fixed there
+    in_source_module: bool,
     scoped_types: BTreeMap<ModuleIdent, BTreeMap<Symbol, (Loc, ModuleIdent, AbilitySet, usize)>>,
     unscoped_types: BTreeMap<Symbol, ResolvedType>,
     scoped_functions: BTreeMap<ModuleIdent, BTreeMap<Symbol, Loc>>,
     unscoped_constants: BTreeMap<Symbol, Loc>,
     scoped_constants: BTreeMap<ModuleIdent, BTreeMap<Symbol, Loc>>,
+    // The parameters of the current inline function which take lambdas
+    lambda_params: BTreeSet<Symbol>,
+    // The modules and the module members annotated with `deprecated`. Module members share a
+    // single namespace, so structs, functions and constants are kept together
+    deprecated_modules: BTreeMap<ModuleIdent, Deprecation>,
+    deprecated_members: BTreeMap<(ModuleIdent, Symbol), Deprecation>,
 }
 
 impl<'env> Context<'env> {
@@ -104,6 +114,33 @@ impl<'env> Context<'env> {
                 (mident, mems)
             })
             .collect();
+        let mut deprecated_modules = BTreeMap::new();
+        let mut deprecated_members = BTreeMap::new();
+        for (mident, mdef) in all_modules() {
+            // Malformed attributes were reported in expansion
+            if let Ok(Some(d)) = deprecation(&mdef.attributes) {
+                deprecated_modules.insert(mident, d);
+            }
+            let member_attributes = mdef
+                .structs
+                .iter()
+                .map(|(_, n, sdef)| (*n, &sdef.attributes))
+                .chain(
+                    mdef.functions
+                        .iter()
+                        .map(|(_, n, fdef)| (*n, &fdef.attributes)),
+                )
+                .chain(
+                    mdef.constants
+                        .iter()
+                        .map(|(_, n, cdef)| (*n, &cdef.attributes)),
+                );
+            for (n, attributes) in member_attributes {
+                if let Ok(Some(d)) = deprecation(attributes) {
+                    deprecated_members.insert((mident, n), d);
+                }
+            }
+        }
         let unscoped_types = N::BuiltinTypeName_::all_names()
             .iter()
             .map(|s| (*s, RT::BuiltinType))
@@ -111,11 +148,15 @@ impl<'env> Context<'env> {
         Self {
             env: compilation_env,
             current_module: None,
+            in_source_module: true,
             scoped_types,
             scoped_functions,
             scoped_constants,
             unscoped_types,
             unscoped_constants: BTreeMap::new(),
+            lambda_params: BTreeSet::new(),
+            deprecated_modules,
+            deprecated_members,
         }
     }
 
@@ -160,7 +201,9 @@ impl<'env> Context<'env> {
                 None
             }
             Some((decl_loc, _, abilities, arity)) => {
-                Some((*decl_loc, StructName(*n), abilities.clone(), *arity))
+                let resolved = (*decl_loc, StructName(*n), abilities.clone(), *arity);
+                self.check_deprecated_usage(loc, m, n, "struct");
+                Some(resolved)
             }
         }
     }
@@ -191,7 +234,10 @@ impl<'env> Context<'env> {
                     .add_diag(diag!(NameResolution::UnboundModuleMember, (loc, msg)));
                 None
             }
-            Some(_) => Some(FunctionName(*n)),
+            Some(_) => {
+                self.check_deprecated_usage(loc, m, n, "function");
+                Some(FunctionName(*n))
+            }
         }
     }
 
@@ -221,10 +267,46 @@ impl<'env> Context<'env> {
                     .add_diag(diag!(NameResolution::UnboundModuleMember, (loc, msg)));
                 None
             }
-            Some(_) => Some(ConstantName(n)),
+            Some(_) => {
+                self.check_deprecated_usage(loc, m, &n, "constant");
+                Some(ConstantName(n))
+            }
         }
     }
 
+    // Warns about the use of `m::n` from another module, if either `n` or `m` is deprecated
+    fn check_deprecated_usage(&mut self, loc: Loc, m: &ModuleIdent, n: &Name, kind: &str) {
+        if !self.in_source_module || self.current_module.as_ref() == Some(m) {
+            return;
+        }
+        let (msg, deprecation) = match self.deprecated_members.get(&(*m, n.value)) {
+            Some(deprecation) => (
+                format!("Use of deprecated {} '{}::{}'", kind, m, n),
+                deprecation,
+            ),
+            None => match self.deprecated_modules.get(m) {
+                Some(deprecation) => (
+                    format!(
+                        "Use of {} '{}::{}' from deprecated module '{}'",
+                        kind, m, n, m
+                    ),
+                    deprecation,
+                ),
+                None => return,
+            },
+        };
+        let note_msg = match &deprecation.note {
+            Some(note) => format!("Deprecated: {}", note),
+            None => "Deprecated here".to_owned(),
+        };
+        let deprecated_loc = deprecation.loc;
+        self.env.add_diag(diag!(
+            NameResolution::DeprecatedUsage,
+            (loc, msg),
+            (deprecated_loc, note_msg),
+        ));
+    }
+
     fn resolve_unscoped_type(&mut self, n: &Name) -> Option<ResolvedType> {
         match self.unscoped_types.get(&n.value) {
             None => {
@@ -357,6 +439,7 @@ fn module(
     mdef: E::ModuleDefinition,
 ) -> N::ModuleDefinition {
     context.current_module = Some(ident);
+    context.in_source_module = mdef.is_source_module;
     let E::ModuleDefinition {
         package_name,
         attributes,
@@ -420,6 +503,8 @@ fn script(context: &mut Context, escript: E::Script) -> N::Script {
         function: efunction,
         specs: _specs,
     } = escript;
+    context.current_module = None;
+    context.in_source_module = true;
     let outer_unscoped = context.save_unscoped();
     for (loc, s, _) in &econstants {
         context.bind_constant(*s, loc)
@@ -489,6 +574,7 @@ fn function(
         loc: _,
         visibility,
         entry,
+        inline,
         signature,
         acquires,
         body,
@@ -496,11 +582,22 @@ fn function(
     } = ef;
     let signature = function_signature(context, signature);
     let acquires = function_acquires(context, acquires);
+    assert!(context.lambda_params.is_empty());
+    if inline {
+        context.lambda_params = signature
+            .parameters
+            .iter()
+            .filter(|(_, ty)| matches!(ty.value, N::Type_::Fun(_, _)))
+            .map(|(v, _)| v.value())
+            .collect();
+    }
     let body = function_body(context, body);
+    context.lambda_params.clear();
     let f = N::Function {
         attributes,
         visibility,
         entry,
+        inline,
         signature,
         acquires,
         body,
@@ -655,6 +752,9 @@ fn struct_fields(context: &mut Context, efields: E::StructFields) -> N::StructFi
         E::StructFields::Defined(em) => {
             N::StructFields::Defined(em.map(|_f, (idx, t)| (idx, type_(context, t))))
         }
+        E::StructFields::Variants(ev) => N::StructFields::Variants(
+            ev.map(|_v, (idx, em)| (idx, em.map(|_f, (idx, t)| (idx, type_(context, t))))),
+        ),
     }
 }
 
@@ -666,6 +766,7 @@ fn constant(context: &mut Context, _name: ConstantName, econstant: E::Constant)
     let E::Constant {
         attributes,
         loc,
+        visibility,
         signature: esignature,
         value: evalue,
     } = econstant;
@@ -674,6 +775,7 @@ fn constant(context: &mut Context, _name: ConstantName, econstant: E::Constant)
     N::Constant {
         attributes,
         loc,
+        visibility,
         signature,
         value,
     }
@@ -793,7 +895,7 @@ fn type_(context: &mut Context, sp!(loc, ety_): E::Type) -> N::Type {
                 }
             }
         }
-        ET::Fun(_, _) => panic!("ICE only allowed in spec context"),
+        ET::Fun(args, result) => NT::Fun(types(context, args), Box::new(type_(context, *result))),
     };
     sp(loc, ty_)
 }
@@ -905,9 +1007,11 @@ fn exp_(context: &mut Context, e: E::Exp) -> N::Exp {
         EE::IfElse(eb, et, ef) => {
             NE::IfElse(exp(context, *eb), exp(context, *et), exp(context, *ef))
         }
+        EE::Match(e, earms) => match_(context, *e, earms),
         EE::While(eb, el) => NE::While(exp(context, *eb), exp(context, *el)),
         EE::Loop(el) => NE::Loop(exp(context, *el)),
         EE::Block(seq) => NE::Block(sequence(context, seq)),
+        EE::Lambda(ebinds, e) => lambda(context, ebinds, *e),
 
         EE::Assign(a, e) => {
             let na_opt = assign_list(context, a);
@@ -960,6 +1064,21 @@ fn exp_(context: &mut Context, e: E::Exp) -> N::Exp {
                 ),
             }
         }
+        EE::PackVariant(tn, v, etys_opt, efields) => {
+            match context.resolve_struct_name(eloc, "construction", tn, etys_opt) {
+                None => {
+                    assert!(context.env.has_diags());
+                    NE::UnresolvedError
+                }
+                Some((m, sn, tys_opt)) => NE::PackVariant(
+                    m,
+                    sn,
+                    v,
+                    tys_opt,
+                    efields.map(|_, (idx, e)| (idx, exp_(context, e))),
+                ),
+            }
+        }
         EE::ExpList(es) => {
             assert!(es.len() > 1);
             NE::ExpList(exps(context, es))
@@ -1023,6 +1142,16 @@ fn exp_(context: &mut Context, e: E::Exp) -> N::Exp {
                     }
                 }
 
+                EA::Name(n) if context.lambda_params.contains(&n.value) => {
+                    if let Some(tys) = ty_args {
+                        let msg = "Lambda parameters cannot take type arguments";
+                        context.env.add_diag(diag!(
+                            NameResolution::NamePositionMismatch,
+                            (tys[0].loc, msg),
+                        ));
+                    }
+                    NE::VarCall(Var(n), nes)
+                }
                 EA::Name(n) => {
                     context.env.add_diag(diag!(
                         NameResolution::UnboundUnscopedName,
@@ -1067,7 +1196,7 @@ fn exp_(context: &mut Context, e: E::Exp) -> N::Exp {
             NE::UnresolvedError
         }
         // `Name` matches name variants only allowed in specs (we handle the allowed ones above)
-        EE::Index(..) | EE::Lambda(..) | EE::Quant(..) | EE::Name(_, Some(_)) => {
+        EE::Index(..) | EE::Quant(..) | EE::Name(_, Some(_)) => {
             panic!("ICE unexpected specification construct")
         }
     };
@@ -1099,6 +1228,62 @@ fn dotted(context: &mut Context, edot: E::ExpDotted) -> Option<N::ExpDotted> {
     Some(sp(loc, nedot_))
 }
 
+// Kept out of `exp_`, which is recursive, to keep its stack frame small
+fn match_(context: &mut Context, e: E::Exp, earms: Vec<E::MatchArm>) -> N::Exp_ {
+    let ne = exp(context, e);
+    let narms_opt: Option<Vec<N::MatchArm>> = earms
+        .into_iter()
+        .map(|earm| match_arm(context, earm))
+        .collect();
+    match narms_opt {
+        None => {
+            assert!(context.env.has_diags());
+            N::Exp_::UnresolvedError
+        }
+        Some(narms) => N::Exp_::Match(ne, narms),
+    }
+}
+
+fn lambda(context: &mut Context, ebinds: E::LValueList, e: E::Exp) -> N::Exp_ {
+    let bind_opt = bind_list(context, ebinds);
+    let ne = exp(context, e);
+    match bind_opt {
+        None => {
+            assert!(context.env.has_diags());
+            N::Exp_::UnresolvedError
+        }
+        Some(binds) => N::Exp_::Lambda(binds, ne),
+    }
+}
+
+fn match_arm(context: &mut Context, sp!(loc, (epat, e)): E::MatchArm) -> Option<N::MatchArm> {
+    use E::MatchPattern_ as EP;
+    use N::MatchPattern_ as NP;
+    let sp!(ploc, epat_) = epat;
+    let npat_opt = match epat_ {
+        EP::Wildcard => Some(NP::Wildcard),
+        EP::Variant(tn, v, etys_opt, efields) => {
+            let resolved = context.resolve_struct_name(ploc, "match pattern", tn, etys_opt);
+            let nfields =
+                UniqueMap::maybe_from_opt_iter(efields.into_iter().map(|(k, (idx, inner))| {
+                    Some((k, (idx, lvalue(context, LValueCase::Bind, inner)?)))
+                }));
+            match (resolved, nfields) {
+                (Some((m, sn, tys_opt)), Some(nfields)) => Some(NP::Variant(
+                    m,
+                    sn,
+                    v,
+                    tys_opt,
+                    nfields.expect("ICE fields were already unique"),
+                )),
+                _ => None,
+            }
+        }
+    };
+    let ne = exp_(context, e);
+    Some(sp(loc, (sp(ploc, npat_opt?), ne)))
+}
+
 #[derive(Clone, Copy)]
 enum LValueCase {
     Bind,
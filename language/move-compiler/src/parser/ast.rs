@@ -112,6 +112,7 @@ pub struct UseDecl {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum AttributeValue_ {
     Value(Value),
+    Vector(Vec<AttributeValue>),
     ModuleAccess(NameAccessChain),
 }
 pub type AttributeValue = Spanned<AttributeValue_>;
@@ -197,6 +198,7 @@ pub struct FriendDecl {
 
 new_name!(Field);
 new_name!(StructName);
+new_name!(VariantName);
 
 pub type ResourceLoc = Option<Loc>;
 
@@ -221,6 +223,15 @@ pub struct StructDefinition {
 pub enum StructFields {
     Defined(Vec<(Field, Type)>),
     Native(Loc),
+    // The variants of an enum
+    Variants(Vec<VariantDefinition>),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct VariantDefinition {
+    pub loc: Loc,
+    pub name: VariantName,
+    pub fields: Vec<(Field, Type)>,
 }
 
 //**************************************************************************************************
@@ -231,6 +242,7 @@ new_name!(FunctionName);
 
 pub const NATIVE_MODIFIER: &str = "native";
 pub const ENTRY_MODIFIER: &str = "entry";
+pub const INLINE_MODIFIER: &str = "inline";
 
 #[derive(PartialEq, Clone, Debug)]
 pub struct FunctionSignature {
@@ -264,6 +276,7 @@ pub struct Function {
     pub loc: Loc,
     pub visibility: Visibility,
     pub entry: Option<Loc>,
+    pub inline: bool,
     pub signature: FunctionSignature,
     pub acquires: Vec<NameAccessChain>,
     pub name: FunctionName,
@@ -280,6 +293,7 @@ new_name!(ConstantName);
 pub struct Constant {
     pub attributes: Vec<Attributes>,
     pub loc: Loc,
+    pub visibility: Visibility,
     pub signature: Type,
     pub name: ConstantName,
     pub value: Exp,
@@ -470,6 +484,19 @@ pub type Bind = Spanned<Bind_>;
 // b1, ..., bn
 pub type BindList = Spanned<Vec<Bind>>;
 
+#[derive(Debug, Clone, PartialEq)]
+pub enum MatchPattern_ {
+    // _
+    Wildcard,
+    // E::V { f1: b1, ... fn: bn }
+    // E::V<t1, ... , tn> { f1: b1, ... fn: bn }
+    // E::V
+    Variant(NameAccessChain, Option<Vec<Type>>, Vec<(Field, Bind)>),
+}
+pub type MatchPattern = Spanned<MatchPattern_>;
+// pattern => e
+pub type MatchArm = Spanned<(MatchPattern, Exp)>;
+
 pub type BindWithRange = Spanned<(Bind, Exp)>;
 pub type BindWithRangeList = Spanned<Vec<BindWithRange>>;
 
@@ -477,7 +504,7 @@ pub type BindWithRangeList = Spanned<Vec<BindWithRange>>;
 pub enum Value_ {
     // @<num>
     Address(LeadingNameAccess),
-    // <num>(u8|u64|u128)?
+    // <num>(u8|u16|u32|u64|u128|u256)?
     Num(Symbol),
     // false
     Bool(bool),
@@ -584,6 +611,8 @@ pub enum Exp_ {
 
     // if (eb) et else ef
     IfElse(Box<Exp>, Box<Exp>, Option<Box<Exp>>),
+    // match (e) { arm1, ..., armn }
+    Match(Box<Exp>, Vec<MatchArm>),
     // while (eb) eloop
     While(Box<Exp>, Box<Exp>),
     // loop eloop
@@ -592,7 +621,7 @@ pub enum Exp_ {
     // { seq }
     Block(Sequence),
     // fun (x1, ..., xn) e
-    Lambda(BindList, Box<Exp>), // spec only
+    Lambda(BindList, Box<Exp>), // spec only, or an argument of an inline function
     // forall/exists x1 : e1, ..., xn [{ t1, .., tk } *] [where cond]: en.
     Quant(
         QuantKind,
@@ -1049,6 +1078,11 @@ impl AstDebug for AttributeValue_ {
     fn ast_debug(&self, w: &mut AstWriter) {
         match self {
             AttributeValue_::Value(v) => v.ast_debug(w),
+            AttributeValue_::Vector(vs) => {
+                w.write("vector[");
+                w.comma(vs, |w, v| v.ast_debug(w));
+                w.write("]");
+            }
             AttributeValue_::ModuleAccess(n) => n.ast_debug(w),
         }
     }
@@ -1231,15 +1265,31 @@ impl AstDebug for StructDefinition {
             w.write("native ");
         }
 
-        w.write(&format!("struct {}", name));
+        match fields {
+            StructFields::Variants(_) => w.write(&format!("enum {}", name)),
+            StructFields::Defined(_) | StructFields::Native(_) => {
+                w.write(&format!("struct {}", name))
+            }
+        }
         type_parameters.ast_debug(w);
-        if let StructFields::Defined(fields) = fields {
-            w.block(|w| {
+        match fields {
+            StructFields::Defined(fields) => w.block(|w| {
                 w.semicolon(fields, |w, (f, st)| {
                     w.write(&format!("{}: ", f));
                     st.ast_debug(w);
                 });
-            })
+            }),
+            StructFields::Variants(variants) => w.block(|w| {
+                w.comma(variants, |w, variant| {
+                    w.write(&format!("{} {{ ", variant.name));
+                    w.comma(&variant.fields, |w, (f, st)| {
+                        w.write(&format!("{}: ", f));
+                        st.ast_debug(w);
+                    });
+                    w.write(" }");
+                });
+            }),
+            StructFields::Native(_) => (),
         }
     }
 }
@@ -1461,6 +1511,7 @@ impl AstDebug for Function {
             loc: _loc,
             visibility,
             entry,
+            inline,
             signature,
             acquires,
             name,
@@ -1471,6 +1522,9 @@ impl AstDebug for Function {
         if entry.is_some() {
             w.write(&format!("{} ", ENTRY_MODIFIER));
         }
+        if *inline {
+            w.write(&format!("{} ", INLINE_MODIFIER));
+        }
         if let FunctionBody_::Native = &body.value {
             w.write("native ");
         }
@@ -1518,11 +1572,13 @@ impl AstDebug for Constant {
         let Constant {
             attributes,
             loc: _loc,
+            visibility,
             name,
             signature,
             value,
         } = self;
         attributes.ast_debug(w);
+        visibility.ast_debug(w);
         w.write(&format!("const {}:", name));
         signature.ast_debug(w);
         w.write(" = ");
@@ -1750,6 +1806,18 @@ impl AstDebug for Exp_ {
                     f.ast_debug(w);
                 }
             }
+            E::Match(e, arms) => {
+                w.write("match (");
+                e.ast_debug(w);
+                w.write(") ");
+                w.block(|w| {
+                    w.comma(arms, |w, sp!(_, (pattern, rhs))| {
+                        pattern.ast_debug(w);
+                        w.write(" => ");
+                        rhs.ast_debug(w);
+                    })
+                });
+            }
             E::While(b, e) => {
                 w.write("while (");
                 b.ast_debug(w);
@@ -1939,6 +2007,29 @@ impl AstDebug for Vec<Vec<Exp>> {
     }
 }
 
+impl AstDebug for MatchPattern_ {
+    fn ast_debug(&self, w: &mut AstWriter) {
+        use MatchPattern_ as P;
+        match self {
+            P::Wildcard => w.write("_"),
+            P::Variant(ma, tys_opt, fields) => {
+                ma.ast_debug(w);
+                if let Some(ss) = tys_opt {
+                    w.write("<");
+                    ss.ast_debug(w);
+                    w.write(">");
+                }
+                w.write("{");
+                w.comma(fields, |w, (f, b)| {
+                    w.write(&format!("{}: ", f));
+                    b.ast_debug(w);
+                });
+                w.write("}");
+            }
+        }
+    }
+}
+
 impl AstDebug for Bind_ {
     fn ast_debug(&self, w: &mut AstWriter) {
         use Bind_ as B;
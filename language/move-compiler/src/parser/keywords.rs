@@ -42,12 +42,14 @@ pub const CONTEXTUAL_KEYWORDS: &[&str] = &[
     "decreases",
     "emits",
     "ensures",
+    "enum",
     "except",
     "forall",
     "global",
     "include",
     "internal",
     "local",
+    "match",
     "min",
     "modifies",
     "mut",
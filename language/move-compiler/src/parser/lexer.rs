@@ -42,6 +42,7 @@ pub enum Tok {
     Equal,
     EqualEqual,
     EqualEqualGreater,
+    EqualGreater,
     LessEqualEqualGreater,
     Greater,
     GreaterEqual,
@@ -116,6 +117,7 @@ impl fmt::Display for Tok {
             Equal => "=",
             EqualEqual => "==",
             EqualEqualGreater => "==>",
+            EqualGreater => "=>",
             LessEqualEqualGreater => "<==>",
             Greater => ">",
             GreaterEqual => ">=",
@@ -473,6 +475,8 @@ fn find_token(
                 (Tok::EqualEqualGreater, 3)
             } else if text.starts_with("==") {
                 (Tok::EqualEqual, 2)
+            } else if text.starts_with("=>") {
+                (Tok::EqualGreater, 2)
             } else {
                 (Tok::Equal, 1)
             }
@@ -578,9 +582,9 @@ fn get_number_maybe_with_suffix(text: &str, num_text_len: usize) -> (Tok, usize)
     let rest = &text[num_text_len..];
     if rest.starts_with("u8") {
         (Tok::NumTypedValue, num_text_len + 2)
-    } else if rest.starts_with("u64") {
+    } else if rest.starts_with("u16") || rest.starts_with("u32") || rest.starts_with("u64") {
         (Tok::NumTypedValue, num_text_len + 3)
-    } else if rest.starts_with("u128") {
+    } else if rest.starts_with("u128") || rest.starts_with("u256") {
         (Tok::NumTypedValue, num_text_len + 4)
     } else {
         // No typed suffix
@@ -425,6 +425,7 @@ struct Modifiers {
     visibility: Option<Visibility>,
     entry: Option<Loc>,
     native: Option<Loc>,
+    inline: Option<Loc>,
 }
 
 impl Modifiers {
@@ -433,14 +434,15 @@ impl Modifiers {
             visibility: None,
             entry: None,
             native: None,
+            inline: None,
         }
     }
 }
 
-// Parse module member modifiers: visiblility and native.
+// Parse module member modifiers: visiblility, native, entry and inline.
 // The modifiers are also used for script-functions
 //      ModuleMemberModifiers = <ModuleMemberModifier>*
-//      ModuleMemberModifier = <Visibility> | "native"
+//      ModuleMemberModifier = <Visibility> | "native" | "entry" | "inline"
 // ModuleMemberModifiers checks for uniqueness, meaning each individual ModuleMemberModifier can
 // appear only once
 fn parse_module_member_modifiers(context: &mut Context) -> Result<Modifiers, Diagnostic> {
@@ -488,6 +490,20 @@ fn parse_module_member_modifiers(context: &mut Context) -> Result<Modifiers, Dia
                 }
                 mods.entry = Some(loc)
             }
+            Tok::Identifier if context.tokens.content() == INLINE_MODIFIER => {
+                let loc = current_token_loc(context.tokens);
+                context.tokens.advance()?;
+                if let Some(prev_loc) = mods.inline {
+                    let msg = format!("Duplicate '{}' modifier", INLINE_MODIFIER);
+                    let prev_msg = format!("'{}' modifier previously given here", INLINE_MODIFIER);
+                    context.env.add_diag(diag!(
+                        Declarations::DuplicateItem,
+                        (loc, msg),
+                        (prev_loc, prev_msg)
+                    ))
+                }
+                mods.inline = Some(loc)
+            }
             _ => break,
         }
     }
@@ -526,15 +542,40 @@ fn parse_visibility(context: &mut Context) -> Result<Visibility, Diagnostic> {
         }
     })
 }
-// Parse an attribute value. Either a value literal or a module access
+// Parse an attribute value. Either a value literal, a vector of attribute values or a module access
 //      AttributeValue =
 //          <Value>
+//          | "vector" "[" Comma<AttributeValue> "]"
 //          | <NameAccessChain>
 fn parse_attribute_value(context: &mut Context) -> Result<AttributeValue, Diagnostic> {
+    const VECTOR_IDENT: &str = "vector";
+
     if let Some(v) = maybe_parse_value(context)? {
         return Ok(sp(v.loc, AttributeValue_::Value(v)));
     }
 
+    if context.tokens.peek() == Tok::Identifier
+        && context.tokens.content() == VECTOR_IDENT
+        && context.tokens.lookahead()? == Tok::LBracket
+    {
+        let start_loc = context.tokens.start_loc();
+        consume_identifier(context.tokens, VECTOR_IDENT)?;
+        let values = parse_comma_list(
+            context,
+            Tok::LBracket,
+            Tok::RBracket,
+            parse_attribute_value,
+            "a vector attribute value",
+        )?;
+        let end_loc = context.tokens.previous_end_loc();
+        return Ok(spanned(
+            context.tokens.file_hash(),
+            start_loc,
+            end_loc,
+            AttributeValue_::Vector(values),
+        ));
+    }
+
     let ma = parse_name_access_chain(context, || "attribute name value")?;
     Ok(sp(ma.loc, AttributeValue_::ModuleAccess(ma)))
 }
@@ -700,15 +741,20 @@ fn parse_bind_list(context: &mut Context) -> Result<BindList, Diagnostic> {
 // Parse a list of bindings for lambda.
 //      LambdaBindList =
 //          "|" Comma<Bind> "|"
+//          | "||"
 fn parse_lambda_bind_list(context: &mut Context) -> Result<BindList, Diagnostic> {
     let start_loc = context.tokens.start_loc();
-    let b = parse_comma_list(
-        context,
-        Tok::Pipe,
-        Tok::Pipe,
-        parse_bind,
-        "a variable or structure binding",
-    )?;
+    let b = if match_token(context.tokens, Tok::PipePipe)? {
+        vec![]
+    } else {
+        parse_comma_list(
+            context,
+            Tok::Pipe,
+            Tok::Pipe,
+            parse_bind,
+            "a variable or structure binding",
+        )?
+    };
     let end_loc = context.tokens.previous_end_loc();
     Ok(spanned(context.tokens.file_hash(), start_loc, end_loc, b))
 }
@@ -882,6 +928,7 @@ fn parse_sequence(context: &mut Context) -> Result<Sequence, Diagnostic> {
 //          | "(" <Exp> ":" <Type> ")"
 //          | "(" <Exp> "as" <Type> ")"
 //          | "{" <Sequence>
+//          | <Match>
 fn parse_term(context: &mut Context) -> Result<Exp, Diagnostic> {
     const VECTOR_IDENT: &str = "vector";
 
@@ -928,6 +975,13 @@ fn parse_term(context: &mut Context) -> Result<Exp, Diagnostic> {
             Exp_::Vector(vec_loc, tys_opt, args)
         }
 
+        Tok::Identifier
+            if context.tokens.content() == MATCH_IDENT
+                && context.tokens.lookahead()? == Tok::LParen =>
+        {
+            parse_match(context)?
+        }
+
         Tok::Identifier => parse_name_exp(context)?,
 
         Tok::NumValue => {
@@ -1009,6 +1063,68 @@ fn parse_term(context: &mut Context) -> Result<Exp, Diagnostic> {
     ))
 }
 
+const MATCH_IDENT: &str = "match";
+
+// Parse a match expression:
+//      Match = "match" "(" <Exp> ")" "{" Comma<MatchArm> "}"
+fn parse_match(context: &mut Context) -> Result<Exp_, Diagnostic> {
+    consume_identifier(context.tokens, MATCH_IDENT)?;
+    consume_token(context.tokens, Tok::LParen)?;
+    let e = parse_exp(context)?;
+    consume_token(context.tokens, Tok::RParen)?;
+    let arms = parse_comma_list(
+        context,
+        Tok::LBrace,
+        Tok::RBrace,
+        parse_match_arm,
+        "a match arm",
+    )?;
+    Ok(Exp_::Match(Box::new(e), arms))
+}
+
+// Parse an arm of a match expression:
+//      MatchArm = <MatchPattern> "=>" <Exp>
+//      MatchPattern =
+//          "_"
+//          | <NameAccessChain> <OptionalTypeArgs> ("{" Comma<BindField> "}")?
+fn parse_match_arm(context: &mut Context) -> Result<MatchArm, Diagnostic> {
+    let start_loc = context.tokens.start_loc();
+    let pattern_ = if context.tokens.peek() == Tok::Identifier && context.tokens.content() == "_" {
+        context.tokens.advance()?;
+        MatchPattern_::Wildcard
+    } else {
+        let n = parse_name_access_chain(context, || "a variant or '_'")?;
+        let tys = parse_optional_type_args(context)?;
+        let fields = if context.tokens.peek() == Tok::LBrace {
+            parse_comma_list(
+                context,
+                Tok::LBrace,
+                Tok::RBrace,
+                parse_bind_field,
+                "a field binding",
+            )?
+        } else {
+            vec![]
+        };
+        MatchPattern_::Variant(n, tys, fields)
+    };
+    let pattern = spanned(
+        context.tokens.file_hash(),
+        start_loc,
+        context.tokens.previous_end_loc(),
+        pattern_,
+    );
+    consume_token(context.tokens, Tok::EqualGreater)?;
+    let rhs = parse_exp(context)?;
+    let end_loc = context.tokens.previous_end_loc();
+    Ok(spanned(
+        context.tokens.file_hash(),
+        start_loc,
+        end_loc,
+        (pattern, rhs),
+    ))
+}
+
 // Parse a pack, call, or other reference to a name:
 //      NameExp =
 //          <NameAccessChain> <OptionalTypeArgs> "{" Comma<ExpField> "}"
@@ -1097,7 +1213,7 @@ fn at_end_of_exp(context: &mut Context) -> bool {
 
 // Parse an expression:
 //      Exp =
-//            <LambdaBindList> <Exp>        spec only
+//            <LambdaBindList> <Exp>        spec only, or an argument of an inline function
 //          | <Quantifier>                  spec only
 //          | "if" "(" <Exp> ")" <Exp> ("else" <Exp>)?
 //          | "while" "(" <Exp> ")" <Exp> (SpecBlock)?
@@ -1109,7 +1225,7 @@ fn at_end_of_exp(context: &mut Context) -> bool {
 fn parse_exp(context: &mut Context) -> Result<Exp, Diagnostic> {
     let start_loc = context.tokens.start_loc();
     let exp = match context.tokens.peek() {
-        Tok::Pipe => {
+        Tok::Pipe | Tok::PipePipe => {
             let bindings = parse_lambda_bind_list(context)?;
             let body = Box::new(parse_exp(context)?);
             Exp_::Lambda(bindings, body)
@@ -1583,7 +1699,8 @@ fn make_builtin_call(loc: Loc, name: Symbol, type_args: Option<Vec<Type>>, args:
 //          <NameAccessChain> ("<" Comma<Type> ">")?
 //          | "&" <Type>
 //          | "&mut" <Type>
-//          | "|" Comma<Type> "|" Type   (spec only)
+//          | "|" Comma<Type> "|" Type   (spec only, or a parameter of an inline function)
+//          | "||" Type                  (spec only, or a parameter of an inline function)
 //          | "(" Comma<Type> ")"
 fn parse_type(context: &mut Context) -> Result<Type, Diagnostic> {
     let start_loc = context.tokens.start_loc();
@@ -1606,8 +1723,12 @@ fn parse_type(context: &mut Context) -> Result<Type, Diagnostic> {
             let t = parse_type(context)?;
             Type_::Ref(true, Box::new(t))
         }
-        Tok::Pipe => {
-            let args = parse_comma_list(context, Tok::Pipe, Tok::Pipe, parse_type, "a type")?;
+        Tok::Pipe | Tok::PipePipe => {
+            let args = if match_token(context.tokens, Tok::PipePipe)? {
+                vec![]
+            } else {
+                parse_comma_list(context, Tok::Pipe, Tok::Pipe, parse_type, "a type")?
+            };
             let result = parse_type(context)?;
             return Ok(spanned(
                 context.tokens.file_hash(),
@@ -1792,6 +1913,7 @@ fn parse_function_decl(
         visibility,
         mut entry,
         native,
+        inline,
     } = modifiers;
 
     if let Some(Visibility::Script(vloc)) = visibility {
@@ -1810,6 +1932,29 @@ fn parse_function_decl(
         }
     }
 
+    if let Some(inline_loc) = inline {
+        let invalid_modifier = match (native, entry) {
+            (Some(loc), _) => Some((loc, NATIVE_MODIFIER)),
+            (None, Some(loc)) => Some((loc, ENTRY_MODIFIER)),
+            (None, None) => None,
+        };
+        if let Some((loc, modifier)) = invalid_modifier {
+            let msg = format!(
+                "Invalid '{}' modifier. '{}' functions are expanded at their call sites and \
+                 cannot be '{}'",
+                modifier, INLINE_MODIFIER, modifier
+            );
+            context.env.add_diag(diag!(
+                Syntax::InvalidModifier,
+                (loc, msg),
+                (
+                    inline_loc,
+                    format!("'{}' modifier given here", INLINE_MODIFIER)
+                ),
+            ));
+        }
+    }
+
     // "fun" <FunctionDefName>
     consume_token(context.tokens, Tok::Fun)?;
     let name = FunctionName(parse_identifier(context)?);
@@ -1882,6 +2027,7 @@ fn parse_function_decl(
         loc,
         visibility: visibility.unwrap_or(Visibility::Internal),
         entry,
+        inline: inline.is_some(),
         signature,
         acquires,
         name,
@@ -1902,10 +2048,12 @@ fn parse_parameter(context: &mut Context) -> Result<(Var, Type), Diagnostic> {
 // Structs
 //**************************************************************************************************
 
-// Parse a struct definition:
+// Parse a struct or enum definition:
 //      StructDecl =
 //          "struct" <StructDefName> ("has" <Ability> (, <Ability>)+)?
 //          ("{" Comma<FieldAnnot> "}" | ";")
+//          | "enum" <StructDefName> ("has" <Ability> (, <Ability>)+)?
+//          "{" Comma<VariantDecl> "}"
 //      StructDefName =
 //          <Identifier> <OptionalTypeParameters>
 fn parse_struct_decl(
@@ -1918,6 +2066,7 @@ fn parse_struct_decl(
         visibility,
         entry,
         native,
+        inline,
     } = modifiers;
     if let Some(vis) = visibility {
         let msg = format!(
@@ -1938,8 +2087,22 @@ fn parse_struct_decl(
             .env
             .add_diag(diag!(Syntax::InvalidModifier, (loc, msg)));
     }
+    if let Some(loc) = inline {
+        let msg = format!(
+            "Invalid struct declaration. '{}' is used only on functions",
+            INLINE_MODIFIER
+        );
+        context
+            .env
+            .add_diag(diag!(Syntax::InvalidModifier, (loc, msg)));
+    }
 
-    consume_token(context.tokens, Tok::Struct)?;
+    let is_enum = context.tokens.peek() == Tok::Identifier && context.tokens.content() == "enum";
+    if is_enum {
+        context.tokens.advance()?;
+    } else {
+        consume_token(context.tokens, Tok::Struct)?;
+    }
 
     // <StructDefName>
     let name = StructName(parse_identifier(context)?);
@@ -1973,10 +2136,26 @@ fn parse_struct_decl(
     };
 
     let fields = match native {
+        Some(loc) if is_enum => {
+            return Err(diag!(
+                Syntax::InvalidModifier,
+                (loc, "Invalid enum declaration. Enums cannot be 'native'")
+            ));
+        }
         Some(loc) => {
             consume_token(context.tokens, Tok::Semicolon)?;
             StructFields::Native(loc)
         }
+        _ if is_enum => {
+            let variants = parse_comma_list(
+                context,
+                Tok::LBrace,
+                Tok::RBrace,
+                parse_variant_decl,
+                "a variant",
+            )?;
+            StructFields::Variants(variants)
+        }
         _ => {
             let list = parse_comma_list(
                 context,
@@ -2004,6 +2183,31 @@ fn parse_struct_decl(
     })
 }
 
+// Parse a variant of an enum:
+//      VariantDecl = <DocComments> <Identifier> ("{" Comma<FieldAnnot> "}")?
+fn parse_variant_decl(context: &mut Context) -> Result<VariantDefinition, Diagnostic> {
+    context.tokens.match_doc_comments();
+    let start_loc = context.tokens.start_loc();
+    let name = VariantName(parse_identifier(context)?);
+    let fields = if context.tokens.peek() == Tok::LBrace {
+        parse_comma_list(
+            context,
+            Tok::LBrace,
+            Tok::RBrace,
+            parse_field_annot,
+            "a field",
+        )?
+    } else {
+        vec![]
+    };
+    let loc = make_loc(
+        context.tokens.file_hash(),
+        start_loc,
+        context.tokens.previous_end_loc(),
+    );
+    Ok(VariantDefinition { loc, name, fields })
+}
+
 // Parse a field annotated with a type:
 //      FieldAnnot = <DocComments> <Field> ":" <Type>
 fn parse_field_annot(context: &mut Context) -> Result<(Field, Type), Diagnostic> {
@@ -2030,14 +2234,22 @@ fn parse_constant_decl(
         visibility,
         entry,
         native,
+        inline,
     } = modifiers;
-    if let Some(vis) = visibility {
-        let msg = "Invalid constant declaration. Constants cannot have visibility modifiers as \
-                   they are always internal";
-        context
-            .env
-            .add_diag(diag!(Syntax::InvalidModifier, (vis.loc().unwrap(), msg)));
-    }
+    let visibility = match visibility {
+        Some(Visibility::Script(loc)) => {
+            let msg = format!(
+                "Invalid constant declaration. '{}' is used only on functions",
+                Visibility::SCRIPT
+            );
+            context
+                .env
+                .add_diag(diag!(Syntax::InvalidModifier, (loc, msg)));
+            Visibility::Internal
+        }
+        Some(vis) => vis,
+        None => Visibility::Internal,
+    };
     if let Some(loc) = entry {
         let msg = format!(
             "Invalid constant declaration. '{}' is used only on functions",
@@ -2053,6 +2265,15 @@ fn parse_constant_decl(
             .env
             .add_diag(diag!(Syntax::InvalidModifier, (loc, msg)));
     }
+    if let Some(loc) = inline {
+        let msg = format!(
+            "Invalid constant declaration. '{}' is used only on functions",
+            INLINE_MODIFIER
+        );
+        context
+            .env
+            .add_diag(diag!(Syntax::InvalidModifier, (loc, msg)));
+    }
     consume_token(context.tokens, Tok::Const)?;
     let name = ConstantName(parse_identifier(context)?);
     consume_token(context.tokens, Tok::Colon)?;
@@ -2068,6 +2289,7 @@ fn parse_constant_decl(
     Ok(Constant {
         attributes,
         loc,
+        visibility,
         signature,
         name,
         value,
@@ -2310,11 +2532,17 @@ fn parse_module(
                         Tok::Struct => ModuleMember::Struct(parse_struct_decl(
                             attributes, start_loc, modifiers, context,
                         )?),
+                        Tok::Identifier if context.tokens.content() == "enum" => {
+                            ModuleMember::Struct(parse_struct_decl(
+                                attributes, start_loc, modifiers, context,
+                            )?)
+                        }
                         _ => {
                             return Err(unexpected_token_error(
                                 context.tokens,
                                 &format!(
-                                    "a module member: '{}', '{}', '{}', '{}', '{}', or '{}'",
+                                    "a module member: '{}', '{}', '{}', '{}', '{}', '{}', or \
+                                     'enum'",
                                     Tok::Spec,
                                     Tok::Use,
                                     Tok::Friend,
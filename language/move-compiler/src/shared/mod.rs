@@ -3,7 +3,12 @@
 
 use crate::{
     command_line as cli,
-    diagnostics::{codes::Severity, Diagnostic, Diagnostics},
+    diagnostics::{
+        codes::{Severity, WarningFilter},
+        structured::DiagnosticsFormat,
+        Diagnostic, Diagnostics,
+    },
+    linters::LintConfig,
     naming::ast::ModuleDefinition,
 };
 use clap::*;
@@ -27,7 +32,8 @@ pub mod unique_set;
 //**************************************************************************************************
 
 pub use move_command_line_common::parser::{
-    parse_address_number as parse_address, parse_u128, parse_u64, parse_u8, NumberFormat,
+    parse_address_number as parse_address, parse_u128, parse_u16, parse_u256, parse_u32, parse_u64,
+    parse_u8, NumberFormat,
 };
 
 //**************************************************************************************************
@@ -172,6 +178,8 @@ pub type AttributeDeriver = dyn Fn(&mut CompilationEnv, &mut ModuleDefinition);
 pub struct CompilationEnv {
     flags: Flags,
     diags: Diagnostics,
+    // The warnings allowed by `allow` attributes, along with the location of the annotated item
+    allowed_warnings: Vec<(Loc, Vec<WarningFilter>)>,
     // TODO(tzakian): Remove the global counter and use this counter instead
     // pub counter: u64,
 }
@@ -181,15 +189,44 @@ impl CompilationEnv {
         Self {
             flags,
             diags: Diagnostics::new(),
+            allowed_warnings: vec![],
         }
     }
 
+    /// Adds `diag`, unless it is a warning silenced by the flags or by an `allow` attribute.
+    /// Warnings are reported as errors if the flags say so.
     pub fn add_diag(&mut self, diag: Diagnostic) {
-        self.diags.add(diag)
+        if diag.info().severity() != Severity::Warning {
+            self.diags.add(diag);
+            return;
+        }
+        let info = diag.info();
+        let loc = diag.primary_loc();
+        let is_denied = self.flags.deny.iter().any(|filter| filter.matches(info));
+        let is_allowed = self.flags.allow.iter().any(|filter| filter.matches(info))
+            || self.allowed_warnings.iter().any(|(scope, filters)| {
+                scope.file_hash() == loc.file_hash()
+                    && scope.start() <= loc.start()
+                    && loc.end() <= scope.end()
+                    && filters.iter().any(|filter| filter.matches(info))
+            });
+        if is_denied || (!is_allowed && self.flags.warnings_are_errors) {
+            self.diags
+                .add(diag.set_severity(Severity::NonblockingError))
+        } else if !is_allowed {
+            self.diags.add(diag)
+        }
     }
 
     pub fn add_diags(&mut self, diags: Diagnostics) {
-        self.diags.extend(diags)
+        for diag in diags.into_vec() {
+            self.add_diag(diag)
+        }
+    }
+
+    /// Silences the warnings selected by `filters` within the item at `loc`
+    pub fn add_allowed_warnings(&mut self, loc: Loc, filters: Vec<WarningFilter>) {
+        self.allowed_warnings.push((loc, filters))
     }
 
     pub fn has_diags(&self) -> bool {
@@ -290,6 +327,43 @@ pub struct Flags {
     )]
     shadow: bool,
 
+    /// Run the linters over the source modules and scripts
+    #[clap(
+        long = cli::LINT,
+    )]
+    lint: bool,
+
+    /// The lint levels of the package being linted, usually set from its manifest
+    #[clap(skip)]
+    lint_config: LintConfig,
+
+    /// Report all warnings as errors
+    #[clap(
+        long = cli::WARNINGS_ARE_ERRORS,
+    )]
+    warnings_are_errors: bool,
+
+    /// Do not report the selected warnings, e.g. 'unused_item::variable' or 'unused_item'
+    #[clap(
+        long = cli::ALLOW,
+        value_name = "FILTER",
+    )]
+    allow: Vec<WarningFilter>,
+
+    /// Report the selected warnings as errors, even if they are allowed
+    #[clap(
+        long = cli::DENY,
+        value_name = "FILTER",
+    )]
+    deny: Vec<WarningFilter>,
+
+    /// The format of the reported diagnostics: 'human', 'json' or 'sarif'
+    #[clap(
+        long = cli::DIAGNOSTICS_FORMAT,
+        default_value = "human",
+    )]
+    diagnostics_format: DiagnosticsFormat,
+
     /// Internal flag used by the model builder to maintain functions which would be otherwise
     /// included only in tests, without creating the unit test code regular tests do.
     #[clap(skip)]
@@ -303,6 +377,12 @@ impl Flags {
             shadow: false,
             flavor: "".to_string(),
             bytecode_version: None,
+            lint: false,
+            lint_config: LintConfig::default(),
+            warnings_are_errors: false,
+            allow: vec![],
+            deny: vec![],
+            diagnostics_format: DiagnosticsFormat::Human,
             keep_testing_functions: false,
         }
     }
@@ -313,6 +393,12 @@ impl Flags {
             shadow: false,
             flavor: "".to_string(),
             bytecode_version: None,
+            lint: false,
+            lint_config: LintConfig::default(),
+            warnings_are_errors: false,
+            allow: vec![],
+            deny: vec![],
+            diagnostics_format: DiagnosticsFormat::Human,
             keep_testing_functions: false,
         }
     }
@@ -338,6 +424,39 @@ impl Flags {
         }
     }
 
+    pub fn set_lint(self, lint: bool) -> Self {
+        Self { lint, ..self }
+    }
+
+    pub fn set_lint_config(self, lint_config: LintConfig) -> Self {
+        Self {
+            lint_config,
+            ..self
+        }
+    }
+
+    pub fn set_warnings_are_errors(self, warnings_are_errors: bool) -> Self {
+        Self {
+            warnings_are_errors,
+            ..self
+        }
+    }
+
+    pub fn set_allowed_warnings(self, allow: Vec<WarningFilter>) -> Self {
+        Self { allow, ..self }
+    }
+
+    pub fn set_denied_warnings(self, deny: Vec<WarningFilter>) -> Self {
+        Self { deny, ..self }
+    }
+
+    pub fn set_diagnostics_format(self, diagnostics_format: DiagnosticsFormat) -> Self {
+        Self {
+            diagnostics_format,
+            ..self
+        }
+    }
+
     pub fn is_empty(&self) -> bool {
         self == &Self::empty()
     }
@@ -361,6 +480,30 @@ impl Flags {
     pub fn bytecode_version(&self) -> Option<u32> {
         self.bytecode_version
     }
+
+    pub fn is_linting(&self) -> bool {
+        self.lint
+    }
+
+    pub fn lint_config(&self) -> &LintConfig {
+        &self.lint_config
+    }
+
+    pub fn warnings_are_errors(&self) -> bool {
+        self.warnings_are_errors
+    }
+
+    pub fn allowed_warnings(&self) -> &[WarningFilter] {
+        &self.allow
+    }
+
+    pub fn denied_warnings(&self) -> &[WarningFilter] {
+        &self.deny
+    }
+
+    pub fn diagnostics_format(&self) -> DiagnosticsFormat {
+        self.diagnostics_format
+    }
 }
 
 //**************************************************************************************************
@@ -388,6 +531,9 @@ pub mod known_attributes {
     pub enum KnownAttribute {
         Testing(TestingAttribute),
         Native(NativeAttribute),
+        Lint(LintAttribute),
+        Diagnostic(DiagnosticAttribute),
+        Deprecation(DeprecationAttribute),
     }
 
     #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -398,6 +544,10 @@ pub mod known_attributes {
         Test,
         // This test is expected to fail
         ExpectedFailure,
+        // Runs this test once for each of the listed cases
+        TestCases,
+        // Is a test that will be run with randomly generated arguments
+        RandomTest,
     }
 
     #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -406,6 +556,28 @@ pub mod known_attributes {
         BytecodeInstruction,
     }
 
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    pub enum LintAttribute {
+        // Sets the listed lints to be ignored
+        Allow,
+        // Sets the listed lints to be reported as warnings
+        Warn,
+        // Sets the listed lints to be reported as errors
+        Deny,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    pub enum DiagnosticAttribute {
+        // Silences the listed warnings within the annotated item
+        Allow,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    pub enum DeprecationAttribute {
+        // Reports a warning when the annotated item is used from another module
+        Deprecated,
+    }
+
     impl fmt::Display for AttributePosition {
         fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             match self {
@@ -430,9 +602,18 @@ pub mod known_attributes {
                 TestingAttribute::EXPECTED_FAILURE => {
                     Self::Testing(TestingAttribute::ExpectedFailure)
                 }
+                TestingAttribute::TEST_CASES => Self::Testing(TestingAttribute::TestCases),
+                TestingAttribute::RANDOM_TEST => Self::Testing(TestingAttribute::RandomTest),
                 NativeAttribute::BYTECODE_INSTRUCTION => {
                     Self::Native(NativeAttribute::BytecodeInstruction)
                 }
+                LintAttribute::ALLOW => Self::Lint(LintAttribute::Allow),
+                LintAttribute::WARN => Self::Lint(LintAttribute::Warn),
+                LintAttribute::DENY => Self::Lint(LintAttribute::Deny),
+                DiagnosticAttribute::ALLOW => Self::Diagnostic(DiagnosticAttribute::Allow),
+                DeprecationAttribute::DEPRECATED => {
+                    Self::Deprecation(DeprecationAttribute::Deprecated)
+                }
                 _ => return None,
             })
         }
@@ -441,6 +622,9 @@ pub mod known_attributes {
             match self {
                 Self::Testing(a) => a.name(),
                 Self::Native(a) => a.name(),
+                Self::Lint(a) => a.name(),
+                Self::Diagnostic(a) => a.name(),
+                Self::Deprecation(a) => a.name(),
             }
         }
 
@@ -448,6 +632,9 @@ pub mod known_attributes {
             match self {
                 Self::Testing(a) => a.expected_positions(),
                 Self::Native(a) => a.expected_positions(),
+                Self::Lint(a) => a.expected_positions(),
+                Self::Diagnostic(a) => a.expected_positions(),
+                Self::Deprecation(a) => a.expected_positions(),
             }
         }
     }
@@ -456,6 +643,8 @@ pub mod known_attributes {
         pub const TEST: &'static str = "test";
         pub const EXPECTED_FAILURE: &'static str = "expected_failure";
         pub const TEST_ONLY: &'static str = "test_only";
+        pub const TEST_CASES: &'static str = "test_cases";
+        pub const RANDOM_TEST: &'static str = "random_test";
         pub const CODE_ASSIGNMENT_NAME: &'static str = "abort_code";
 
         pub const fn name(&self) -> &str {
@@ -463,6 +652,8 @@ pub mod known_attributes {
                 Self::Test => Self::TEST,
                 Self::TestOnly => Self::TEST_ONLY,
                 Self::ExpectedFailure => Self::EXPECTED_FAILURE,
+                Self::TestCases => Self::TEST_CASES,
+                Self::RandomTest => Self::RANDOM_TEST,
             }
         }
 
@@ -483,10 +674,16 @@ pub mod known_attributes {
                 Lazy::new(|| IntoIterator::into_iter([AttributePosition::Function]).collect());
             static EXPECTED_FAILURE_POSITIONS: Lazy<BTreeSet<AttributePosition>> =
                 Lazy::new(|| IntoIterator::into_iter([AttributePosition::Function]).collect());
+            static TEST_CASES_POSITIONS: Lazy<BTreeSet<AttributePosition>> =
+                Lazy::new(|| IntoIterator::into_iter([AttributePosition::Function]).collect());
+            static RANDOM_TEST_POSITIONS: Lazy<BTreeSet<AttributePosition>> =
+                Lazy::new(|| IntoIterator::into_iter([AttributePosition::Function]).collect());
             match self {
                 TestingAttribute::TestOnly => &*TEST_ONLY_POSITIONS,
                 TestingAttribute::Test => &*TEST_POSITIONS,
                 TestingAttribute::ExpectedFailure => &*EXPECTED_FAILURE_POSITIONS,
+                TestingAttribute::TestCases => &*TEST_CASES_POSITIONS,
+                TestingAttribute::RandomTest => &*RANDOM_TEST_POSITIONS,
             }
         }
     }
@@ -508,4 +705,78 @@ pub mod known_attributes {
             }
         }
     }
+
+    impl LintAttribute {
+        pub const ALLOW: &'static str = "lint_allow";
+        pub const WARN: &'static str = "lint_warn";
+        pub const DENY: &'static str = "lint_deny";
+
+        pub const fn name(&self) -> &str {
+            match self {
+                LintAttribute::Allow => Self::ALLOW,
+                LintAttribute::Warn => Self::WARN,
+                LintAttribute::Deny => Self::DENY,
+            }
+        }
+
+        pub fn expected_positions(&self) -> &'static BTreeSet<AttributePosition> {
+            static LINT_POSITIONS: Lazy<BTreeSet<AttributePosition>> = Lazy::new(|| {
+                IntoIterator::into_iter([
+                    AttributePosition::Module,
+                    AttributePosition::Script,
+                    AttributePosition::Function,
+                ])
+                .collect()
+            });
+            &*LINT_POSITIONS
+        }
+    }
+
+    impl DiagnosticAttribute {
+        pub const ALLOW: &'static str = "allow";
+
+        pub const fn name(&self) -> &str {
+            match self {
+                DiagnosticAttribute::Allow => Self::ALLOW,
+            }
+        }
+
+        pub fn expected_positions(&self) -> &'static BTreeSet<AttributePosition> {
+            static ALLOW_POSITIONS: Lazy<BTreeSet<AttributePosition>> = Lazy::new(|| {
+                IntoIterator::into_iter([
+                    AttributePosition::Module,
+                    AttributePosition::Script,
+                    AttributePosition::Constant,
+                    AttributePosition::Struct,
+                    AttributePosition::Function,
+                ])
+                .collect()
+            });
+            &*ALLOW_POSITIONS
+        }
+    }
+
+    impl DeprecationAttribute {
+        pub const DEPRECATED: &'static str = "deprecated";
+        pub const NOTE: &'static str = "note";
+
+        pub const fn name(&self) -> &str {
+            match self {
+                DeprecationAttribute::Deprecated => Self::DEPRECATED,
+            }
+        }
+
+        pub fn expected_positions(&self) -> &'static BTreeSet<AttributePosition> {
+            static DEPRECATED_POSITIONS: Lazy<BTreeSet<AttributePosition>> = Lazy::new(|| {
+                IntoIterator::into_iter([
+                    AttributePosition::Module,
+                    AttributePosition::Constant,
+                    AttributePosition::Struct,
+                    AttributePosition::Function,
+                ])
+                .collect()
+            });
+            &*DEPRECATED_POSITIONS
+        }
+    }
 }
@@ -17,7 +17,7 @@ use crate::{
     },
     parser::ast::{
         Ability, Ability_, BinOp, BinOp_, ConstantName, Field, FunctionName, StructName, UnaryOp,
-        UnaryOp_, Var,
+        UnaryOp_, Var, VariantName,
     },
     shared::{unique_map::UniqueMap, *},
     FullyCompiledProgram,
@@ -509,6 +509,19 @@ fn struct_fields(
                 .collect();
             IRF::Move { fields }
         }
+        HF::Variants(variants) => {
+            let variants = variants
+                .into_iter()
+                .map(|(v, field_vec)| {
+                    let fields = field_vec
+                        .into_iter()
+                        .map(|(f, ty)| (field(f), base_type(context, ty)))
+                        .collect();
+                    (variant_name(v), fields)
+                })
+                .collect();
+            IRF::Variants { variants }
+        }
     }
 }
 
@@ -722,6 +735,10 @@ fn field(f: Field) -> IR::Field {
     sp(f.0.loc, IR::Field_(f.0.value))
 }
 
+fn variant_name(v: VariantName) -> IR::VariantName {
+    IR::VariantName(v.0.value)
+}
+
 fn struct_definition_name(
     context: &mut Context,
     sp!(_, t_): H::Type,
@@ -807,8 +824,11 @@ fn base_type(context: &mut Context, sp!(_, bt_): H::BaseType) -> IR::Type {
         B::Apply(_, sp!(_, TN::Builtin(sp!(_, BT::Address))), _) => IRT::Address,
         B::Apply(_, sp!(_, TN::Builtin(sp!(_, BT::Signer))), _) => IRT::Signer,
         B::Apply(_, sp!(_, TN::Builtin(sp!(_, BT::U8))), _) => IRT::U8,
+        B::Apply(_, sp!(_, TN::Builtin(sp!(_, BT::U16))), _) => IRT::U16,
+        B::Apply(_, sp!(_, TN::Builtin(sp!(_, BT::U32))), _) => IRT::U32,
         B::Apply(_, sp!(_, TN::Builtin(sp!(_, BT::U64))), _) => IRT::U64,
         B::Apply(_, sp!(_, TN::Builtin(sp!(_, BT::U128))), _) => IRT::U128,
+        B::Apply(_, sp!(_, TN::Builtin(sp!(_, BT::U256))), _) => IRT::U256,
 
         B::Apply(_, sp!(_, TN::Builtin(sp!(_, BT::Bool))), _) => IRT::Bool,
         B::Apply(_, sp!(_, TN::Builtin(sp!(_, BT::Vector))), mut args) => {
@@ -934,6 +954,14 @@ fn lvalue(context: &mut Context, code: &mut IR::BytecodeBlock, sp!(loc, l_): H::
 
             lvalues_(context, code, field_ls.into_iter().map(|(_, l)| l));
         }
+
+        L::UnpackVariant(s, v, tys, field_ls) => {
+            let n = context.struct_definition_name(context.current_module().unwrap(), s);
+            let tys = base_types(context, tys);
+            code.push(sp(loc, B::UnpackVariant(n, variant_name(v), tys)));
+
+            lvalues_(context, code, field_ls.into_iter().map(|(_, l)| l));
+        }
     }
 }
 
@@ -959,8 +987,11 @@ fn exp_(context: &mut Context, code: &mut IR::BytecodeBlock, e: H::Exp) {
         E::Value(sp!(_, v_)) => {
             let ld_value = match v_ {
                 V::U8(u) => B::LdU8(u),
+                V::U16(u) => B::LdU16(u),
+                V::U32(u) => B::LdU32(u),
                 V::U64(u) => B::LdU64(u),
                 V::U128(u) => B::LdU128(u),
+                V::U256(u) => B::LdU256(u),
                 V::Bool(b) => {
                     if b {
                         B::LdTrue
@@ -982,7 +1013,7 @@ fn exp_(context: &mut Context, code: &mut IR::BytecodeBlock, e: H::Exp) {
         }
         E::Copy { var: v, .. } => code.push(sp(loc, B::CopyLoc(var(v)))),
 
-        E::Constant(c) => code.push(sp(loc, B::LdNamedConst(context.constant_name(c)))),
+        E::Constant(_, c) => code.push(sp(loc, B::LdNamedConst(context.constant_name(c)))),
 
         E::ModuleCall(mcall) => {
             exp(context, code, mcall.arguments);
@@ -1041,6 +1072,22 @@ fn exp_(context: &mut Context, code: &mut IR::BytecodeBlock, e: H::Exp) {
             code.push(sp(loc, B::Pack(n, base_types(context, tys))))
         }
 
+        E::PackVariant(s, v, tys, field_args) => {
+            for (_, _, earg) in field_args {
+                exp_(context, code, earg);
+            }
+            let n = context.struct_definition_name(context.current_module().unwrap(), s);
+            let tys = base_types(context, tys);
+            code.push(sp(loc, B::PackVariant(n, variant_name(v), tys)))
+        }
+
+        E::TestVariant(s, v, tys, e) => {
+            exp(context, code, e);
+            let n = context.struct_definition_name(context.current_module().unwrap(), s);
+            let tys = base_types(context, tys);
+            code.push(sp(loc, B::TestVariant(n, variant_name(v), tys)))
+        }
+
         E::Vector(_, n, bt, args) => {
             let ty = base_type(context, *bt);
             exp(context, code, args);
@@ -1081,8 +1128,11 @@ fn exp_(context: &mut Context, code: &mut IR::BytecodeBlock, e: H::Exp) {
             exp(context, code, el);
             let instr = match bt_ {
                 BT::U8 => B::CastU8,
+                BT::U16 => B::CastU16,
+                BT::U32 => B::CastU32,
                 BT::U64 => B::CastU64,
                 BT::U128 => B::CastU128,
+                BT::U256 => B::CastU256,
                 _ => panic!("ICE type checking failed. unexpected cast"),
             };
             code.push(sp(loc, instr));
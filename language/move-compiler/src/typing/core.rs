@@ -4,7 +4,7 @@
 use crate::{
     diag,
     diagnostics::{codes::NameResolution, Diagnostic},
-    expansion::ast::{AbilitySet, ModuleIdent, Visibility},
+    expansion::ast::{AbilitySet, ModuleIdent, Variants, Visibility},
     naming::ast::{
         self as N, BuiltinTypeName_, FunctionSignature, StructDefinition, StructTypeParameter,
         TParam, TParamID, TVar, Type, TypeName, TypeName_, Type_,
@@ -41,10 +41,12 @@ pub struct FunctionInfo {
     pub visibility: Visibility,
     pub signature: FunctionSignature,
     pub acquires: BTreeMap<StructName, Loc>,
+    pub inline: bool,
 }
 
 pub struct ConstantInfo {
     pub defined_loc: Loc,
+    pub visibility: Visibility,
     pub signature: Type,
 }
 
@@ -70,6 +72,7 @@ pub struct Context<'env> {
     pub current_module: Option<ModuleIdent>,
     pub current_function: Option<FunctionName>,
     pub current_script_constants: Option<UniqueMap<ConstantName, ConstantInfo>>,
+    pub current_function_inline: bool,
     pub return_type: Option<Type>,
     locals: UniqueMap<Var, Type>,
 
@@ -77,6 +80,7 @@ pub struct Context<'env> {
     pub constraints: Constraints,
 
     loop_info: LoopInfo,
+    lambda_depth: usize,
 }
 
 impl<'env> Context<'env> {
@@ -102,9 +106,11 @@ impl<'env> Context<'env> {
                 visibility: fdef.visibility.clone(),
                 signature: fdef.signature.clone(),
                 acquires: fdef.acquires.clone(),
+                inline: fdef.inline,
             });
             let constants = mdef.constants.ref_map(|cname, cdef| ConstantInfo {
                 defined_loc: cname.loc(),
+                visibility: cdef.visibility.clone(),
                 signature: cdef.signature.clone(),
             });
             let minfo = ModuleInfo {
@@ -121,10 +127,12 @@ impl<'env> Context<'env> {
             current_module: None,
             current_function: None,
             current_script_constants: None,
+            current_function_inline: false,
             return_type: None,
             constraints: vec![],
             locals: UniqueMap::new(),
             loop_info: LoopInfo(LoopInfo_::NotInLoop),
+            lambda_depth: 0,
             modules,
             env,
         }
@@ -135,17 +143,23 @@ impl<'env> Context<'env> {
             matches!(&self.loop_info, LoopInfo(LoopInfo_::NotInLoop)),
             "ICE loop_info should be reset after the loop"
         );
+        assert!(
+            self.lambda_depth == 0,
+            "ICE lambda_depth should be reset after the lambda"
+        );
         self.return_type = None;
         self.locals = UniqueMap::new();
         self.subst = Subst::empty();
         self.constraints = Constraints::new();
         self.current_function = None;
+        self.current_function_inline = false;
     }
 
     pub fn bind_script_constants(&mut self, constants: &UniqueMap<ConstantName, N::Constant>) {
         assert!(self.current_script_constants.is_none());
         self.current_script_constants = Some(constants.ref_map(|cname, cdef| ConstantInfo {
             defined_loc: cname.loc(),
+            visibility: cdef.visibility.clone(),
             signature: cdef.signature.clone(),
         }));
     }
@@ -314,6 +328,18 @@ impl<'env> Context<'env> {
         &self.struct_definition(m, n).type_parameters
     }
 
+    /// The variants of `m::n` if it is an enum
+    pub fn struct_variants(&self, m: &ModuleIdent, n: &StructName) -> Option<&Variants<Type>> {
+        match &self.struct_definition(m, n).fields {
+            N::StructFields::Variants(vs) => Some(vs),
+            N::StructFields::Defined(_) | N::StructFields::Native(_) => None,
+        }
+    }
+
+    pub fn is_inline_function(&self, m: &ModuleIdent, n: &FunctionName) -> bool {
+        self.function_info(m, n).inline
+    }
+
     fn function_info(&self, m: &ModuleIdent, n: &FunctionName) -> &FunctionInfo {
         self.module_info(m)
             .functions
@@ -364,6 +390,25 @@ impl<'env> Context<'env> {
             LoopInfo_::BreakType(t) => Some(*t),
         }
     }
+
+    pub fn in_lambda(&self) -> bool {
+        self.lambda_depth > 0
+    }
+
+    // The body of a lambda is outside of any loop around the lambda, as it is not evaluated there
+    pub fn enter_lambda(&mut self) -> LoopInfo {
+        self.lambda_depth += 1;
+        std::mem::replace(&mut self.loop_info, LoopInfo(LoopInfo_::NotInLoop))
+    }
+
+    pub fn exit_lambda(&mut self, old_info: LoopInfo) {
+        assert!(
+            matches!(&self.loop_info, LoopInfo(LoopInfo_::NotInLoop)),
+            "ICE loop_info should be reset after the loop"
+        );
+        self.lambda_depth -= 1;
+        self.loop_info = old_info;
+    }
 }
 
 //**************************************************************************************************
@@ -492,6 +537,11 @@ fn error_format_impl_(b_: &Type_, subst: &Subst, nested: bool) -> String {
             if *mut_ { "mut " } else { "" },
             error_format_nested(ty, subst)
         ),
+        Fun(args, result) => format!(
+            "|{}|{}",
+            format_comma(args.iter().map(|t| error_format_nested(t, subst))),
+            error_format_nested(result, subst)
+        ),
     };
     if nested {
         res
@@ -511,6 +561,7 @@ pub fn infer_abilities(context: &Context, subst: &Subst, ty: Type) -> AbilitySet
         T::Unit => AbilitySet::collection(loc),
         T::Ref(_, _) => AbilitySet::references(loc),
         T::Var(_) => unreachable!("ICE unfold_type failed, which is impossible"),
+        T::Fun(_, _) => AbilitySet::empty(),
         T::UnresolvedError | T::Anything => AbilitySet::all(loc),
         T::Param(TParam { abilities, .. }) | T::Apply(Some(abilities), _, _) => abilities,
         T::Apply(None, n, ty_args) => {
@@ -553,6 +604,7 @@ fn debug_abilities_info(context: &Context, ty: &Type) -> (Option<Loc>, AbilitySe
     match &ty.value {
         T::Unit | T::Ref(_, _) => (None, AbilitySet::references(loc), vec![]),
         T::Var(_) => panic!("ICE call unfold_type before debug_abilities_info"),
+        T::Fun(_, _) => (None, AbilitySet::empty(), vec![]),
         T::UnresolvedError | T::Anything => (None, AbilitySet::all(loc), vec![]),
         T::Param(TParam {
             abilities,
@@ -659,6 +711,12 @@ pub fn make_field_types(
                 (*idx, subst_tparams(tparam_subst, field_ty.clone()))
             }))
         }
+        N::StructFields::Variants(vs) => N::StructFields::Variants(vs.ref_map(|_, (vidx, m)| {
+            let fields = m.ref_map(|_, (idx, field_ty)| {
+                (*idx, subst_tparams(tparam_subst, field_ty.clone()))
+            });
+            (*vidx, fields)
+        })),
     }
 }
 
@@ -683,6 +741,17 @@ pub fn make_field_type(
             ));
             return context.error_type(loc);
         }
+        N::StructFields::Variants(_) => {
+            let msg = format!(
+                "Unbound field '{}' for enum '{}::{}'. The fields of an enum's variants can only \
+                 be accessed by a 'match'",
+                field, m, n
+            );
+            context
+                .env
+                .add_diag(diag!(TypeSafety::InvalidEnumUsage, (loc, msg)));
+            return context.error_type(loc);
+        }
         N::StructFields::Defined(m) => m,
     };
     match fields_map.get(field).cloned() {
@@ -718,25 +787,46 @@ pub fn make_constant_type(
     c: &ConstantName,
 ) -> Type {
     let in_current_module = m == &context.current_module;
-    let (defined_loc, signature) = {
+    let (defined_loc, visibility, signature) = {
         let ConstantInfo {
             defined_loc,
+            visibility,
             signature,
         } = context.constant_info(m, c);
-        (*defined_loc, signature.clone())
+        (*defined_loc, visibility.clone(), signature.clone())
     };
-    if !in_current_module {
-        let msg = match m {
-            None => format!("Invalid access of '{}'", c),
-            Some(mident) => format!("Invalid access of '{}::{}'", mident, c),
-        };
-        let internal_msg = "Constants are internal to their module, and cannot can be accessed \
-                            outside of their module";
-        context.env.add_diag(diag!(
-            TypeSafety::Visibility,
-            (loc, msg),
-            (defined_loc, internal_msg)
-        ));
+    let msg = || match m {
+        None => format!("Invalid access of '{}'", c),
+        Some(mident) => format!("Invalid access of '{}::{}'", mident, c),
+    };
+    match (m, visibility) {
+        _ if in_current_module => (),
+        (Some(_), Visibility::Public(_)) => (),
+        (Some(mident), Visibility::Friend(_)) if context.current_module_is_a_friend_of(mident) => {}
+        (Some(mident), Visibility::Friend(vis_loc)) => {
+            let internal_msg = format!(
+                "This constant can only be accessed from a 'friend' of module '{}'",
+                mident
+            );
+            context.env.add_diag(diag!(
+                TypeSafety::Visibility,
+                (loc, msg()),
+                (vis_loc, internal_msg)
+            ));
+        }
+        _ => {
+            let internal_msg = format!(
+                "This constant is internal to its module. Only '{}' and '{}' constants can be \
+                 accessed outside of their module",
+                Visibility::PUBLIC,
+                Visibility::FRIEND
+            );
+            context.env.add_diag(diag!(
+                TypeSafety::Visibility,
+                (loc, msg()),
+                (defined_loc, internal_msg)
+            ));
+        }
     }
 
     signature
@@ -1035,7 +1125,7 @@ fn solve_base_type_constraint(context: &mut Context, loc: Loc, msg: String, ty:
     let sp!(tyloc, unfolded_) = unfold_type(&context.subst, ty.clone());
     match unfolded_ {
         Var(_) => unreachable!(),
-        Unit | Ref(_, _) | Apply(_, sp!(_, Multiple(_)), _) => {
+        Unit | Ref(_, _) | Fun(_, _) | Apply(_, sp!(_, Multiple(_)), _) => {
             let tystr = error_format(ty, &context.subst);
             let tmsg = format!("Expected a single non-reference type, but found: {}", tystr);
             context.env.add_diag(diag!(
@@ -1065,7 +1155,7 @@ fn solve_single_type_constraint(context: &mut Context, loc: Loc, msg: String, ty
                 (tyloc, tmsg)
             ))
         }
-        UnresolvedError | Anything | Ref(_, _) | Param(_) | Apply(_, _, _) => (),
+        UnresolvedError | Anything | Ref(_, _) | Fun(_, _) | Param(_) | Apply(_, _, _) => (),
     }
 }
 
@@ -1139,6 +1229,11 @@ pub fn subst_tparams(subst: &TParamSubst, sp!(loc, t_): Type) -> Type {
                 .collect();
             sp(loc, Apply(k, n, ftys))
         }
+        Fun(args, result) => {
+            let args = args.into_iter().map(|t| subst_tparams(subst, t)).collect();
+            let result = subst_tparams(subst, *result);
+            sp(loc, Fun(args, Box::new(result)))
+        }
     }
 }
 
@@ -1151,6 +1246,11 @@ pub fn ready_tvars(subst: &Subst, sp!(loc, t_): Type) -> Type {
             let tys = tys.into_iter().map(|t| ready_tvars(subst, t)).collect();
             sp(loc, Apply(k, n, tys))
         }
+        Fun(args, result) => {
+            let args = args.into_iter().map(|t| ready_tvars(subst, t)).collect();
+            let result = ready_tvars(subst, *result);
+            sp(loc, Fun(args, Box::new(result)))
+        }
         Var(i) => {
             let last_var = forward_tvar(subst, i);
             match subst.get(last_var) {
@@ -1180,6 +1280,11 @@ pub fn instantiate(context: &mut Context, sp!(loc, t_): Type) -> Type {
         Apply(abilities_opt, n, ty_args) => {
             instantiate_apply(context, loc, abilities_opt, n, ty_args)
         }
+        Fun(args, result) => {
+            let args = args.into_iter().map(|t| instantiate(context, t)).collect();
+            let result = instantiate(context, *result);
+            Fun(args, Box::new(result))
+        }
         x @ Param(_) => x,
         Var(_) => panic!("ICE instantiate type variable"),
     };
@@ -1408,6 +1513,19 @@ fn join_impl(
             let (subst, tys) = join_impl_types(subst, case, tys1, tys2)?;
             Ok((subst, sp(*loc, Apply(k2.clone(), n2.clone(), tys))))
         }
+        (sp!(_, Fun(args1, _)), sp!(_, Fun(args2, _))) if args1.len() != args2.len() => Err(
+            TypingError::Incompatible(Box::new(lhs.clone()), Box::new(rhs.clone())),
+        ),
+        (sp!(_, Fun(args1, result1)), sp!(loc, Fun(args2, result2))) => {
+            // The arguments are contravariant: a subtype takes arguments of any supertype of the
+            // arguments its supertype takes
+            let (subst, args) = match case {
+                Join => join_impl_types(subst, case, args1, args2)?,
+                Subtype => join_impl_types(subst, case, args2, args1)?,
+            };
+            let (subst, result) = join_impl(subst, case, result1, result2)?;
+            Ok((subst, sp(*loc, Fun(args, Box::new(result)))))
+        }
         (sp!(loc1, Var(id1)), sp!(loc2, Var(id2))) => {
             if *id1 == *id2 {
                 Ok((subst, sp(*loc2, Var(*id2))))
@@ -1554,6 +1672,10 @@ fn join_bind_tvar(subst: &mut Subst, loc: Loc, tvar: TVar, ty: Type) -> Result<b
                 .iter()
                 .rev()
                 .for_each(|inner| used_tvars(used, inner)),
+            T::Fun(args, result) => {
+                used_tvars(used, result);
+                args.iter().rev().for_each(|arg| used_tvars(used, arg))
+            }
             T::Unit | T::Param(_) | T::Anything | T::UnresolvedError => (),
         }
     }
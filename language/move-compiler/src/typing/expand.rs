@@ -9,6 +9,7 @@ use crate::{
     parser::ast::Ability_,
     typing::ast as T,
 };
+use move_core_types::u256::U256;
 use move_ir_types::location::*;
 use std::convert::TryInto;
 
@@ -51,6 +52,10 @@ pub fn type_(context: &mut Context, ty: &mut Type) {
     match &mut ty.value {
         Anything | UnresolvedError | Param(_) | Unit => (),
         Ref(_, b) => type_(context, b),
+        Fun(args, result) => {
+            types(context, args);
+            type_(context, result);
+        }
         Var(tvar) => {
             let ty_tvar = sp(ty.loc, Var(*tvar));
             let replacement = core::unfold_type(&context.subst, ty_tvar);
@@ -156,13 +161,19 @@ pub fn exp(context: &mut Context, e: &mut T::Exp) {
                 _ => panic!("ICE inferred num failed {:?}", &e.ty.value),
             };
             let v = *v;
-            let u8_max = std::u8::MAX as u128;
-            let u64_max = std::u64::MAX as u128;
-            let u128_max = std::u128::MAX;
+            let u8_max = U256::from(std::u8::MAX);
+            let u16_max = U256::from(std::u16::MAX);
+            let u32_max = U256::from(std::u32::MAX);
+            let u64_max = U256::from(std::u64::MAX);
+            let u128_max = U256::from(std::u128::MAX);
+            let u256_max = U256::max_value();
             let max = match bt {
                 BT::U8 => u8_max,
+                BT::U16 => u16_max,
+                BT::U32 => u32_max,
                 BT::U64 => u64_max,
                 BT::U128 => u128_max,
+                BT::U256 => u256_max,
                 _ => unreachable!(),
             };
             let new_exp = if v > max {
@@ -170,11 +181,17 @@ pub fn exp(context: &mut Context, e: &mut T::Exp) {
                     "Expected a literal of type '{}', but the value is too large.",
                     bt
                 );
-                let fix_bt = if v > u64_max {
+                let fix_bt = if v > u128_max {
+                    BT::U256
+                } else if v > u64_max {
                     BT::U128
+                } else if v > u32_max {
+                    BT::U64
+                } else if v > u16_max {
+                    BT::U32
                 } else {
                     assert!(v > u8_max);
-                    BT::U64
+                    BT::U16
                 };
                 let fix = format!(
                     "Annotating the literal might help inference: '{value}{type}'",
@@ -191,8 +208,11 @@ pub fn exp(context: &mut Context, e: &mut T::Exp) {
             } else {
                 let value_ = match bt {
                     BT::U8 => Value_::U8(v.try_into().unwrap()),
+                    BT::U16 => Value_::U16(v.try_into().unwrap()),
+                    BT::U32 => Value_::U32(v.try_into().unwrap()),
                     BT::U64 => Value_::U64(v.try_into().unwrap()),
-                    BT::U128 => Value_::U128(v),
+                    BT::U128 => Value_::U128(v.try_into().unwrap()),
+                    BT::U256 => Value_::U256(v),
                     _ => unreachable!(),
                 };
                 E::Value(sp(*vloc, value_))
@@ -217,6 +237,7 @@ pub fn exp(context: &mut Context, e: &mut T::Exp) {
             builtin_function(context, b);
             exp(context, args);
         }
+        E::VarCall(_, args) => exp(context, args),
         E::Vector(_vec_loc, _n, ty_arg, args) => {
             type_(context, ty_arg);
             exp(context, args);
@@ -227,12 +248,30 @@ pub fn exp(context: &mut Context, e: &mut T::Exp) {
             exp(context, et);
             exp(context, ef);
         }
+        E::Match(es, arms) => {
+            exp(context, es);
+            for sp!(_, (pat, earm)) in arms {
+                if let T::MatchPattern_::Variant(_, _, _, bs, fields) = &mut pat.value {
+                    types(context, bs);
+                    for (_, _, (_, (bt, innerb))) in fields.iter_mut() {
+                        type_(context, bt);
+                        lvalue(context, innerb)
+                    }
+                }
+                exp(context, earm)
+            }
+        }
         E::While(eb, eloop) => {
             exp(context, eb);
             exp(context, eloop);
         }
         E::Loop { body: eloop, .. } => exp(context, eloop),
         E::Block(seq) => sequence(context, seq),
+        E::Lambda(binds, tys, body) => {
+            lvalues(context, binds);
+            expected_types(context, tys);
+            exp(context, body);
+        }
         E::Assign(assigns, tys, er) => {
             lvalues(context, assigns);
             expected_types(context, tys);
@@ -255,7 +294,7 @@ pub fn exp(context: &mut Context, e: &mut T::Exp) {
             type_(context, operand_ty);
         }
 
-        E::Pack(_, _, bs, fields) => {
+        E::Pack(_, _, bs, fields) | E::PackVariant(_, _, _, bs, fields) => {
             types(context, bs);
             for (_, _, (_, (bt, fe))) in fields.iter_mut() {
                 type_(context, bt);
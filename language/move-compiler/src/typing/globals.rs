@@ -114,19 +114,28 @@ fn exp(
             builtin_function(context, annotated_acquires, seen, &e.exp.loc, b);
             exp(context, annotated_acquires, seen, args);
         }
-        E::Vector(_vec_loc, _n, _targ, args) => exp(context, annotated_acquires, seen, args),
+        E::Vector(_, _, _, args) | E::VarCall(_, args) => {
+            exp(context, annotated_acquires, seen, args)
+        }
 
         E::IfElse(eb, et, ef) => {
             exp(context, annotated_acquires, seen, eb);
             exp(context, annotated_acquires, seen, et);
             exp(context, annotated_acquires, seen, ef);
         }
+        E::Match(es, arms) => {
+            exp(context, annotated_acquires, seen, es);
+            for sp!(_, (_, earm)) in arms {
+                exp(context, annotated_acquires, seen, earm)
+            }
+        }
         E::While(eb, eloop) => {
             exp(context, annotated_acquires, seen, eb);
             exp(context, annotated_acquires, seen, eloop);
         }
         E::Loop { body: eloop, .. } => exp(context, annotated_acquires, seen, eloop),
         E::Block(seq) => sequence(context, annotated_acquires, seen, seq),
+        E::Lambda(_, _, body) => exp(context, annotated_acquires, seen, body),
         E::Assign(_, _, er) => {
             exp(context, annotated_acquires, seen, er);
         }
@@ -142,7 +151,7 @@ fn exp(
             exp(context, annotated_acquires, seen, er)
         }
 
-        E::Pack(_, _, _, fields) => {
+        E::Pack(_, _, _, fields) | E::PackVariant(_, _, _, _, fields) => {
             for (_, _, (_, (_, fe))) in fields {
                 exp(context, annotated_acquires, seen, fe)
             }
@@ -267,7 +276,7 @@ where
         T::Anything | T::UnresolvedError => {
             return None;
         }
-        T::Ref(_, _) | T::Unit => {
+        T::Ref(_, _) | T::Unit | T::Fun(_, _) => {
             // Key ability is checked by constraints, and these types do not have Key
             assert!(context.env.has_diags());
             return None;
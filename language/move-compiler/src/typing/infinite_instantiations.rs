@@ -77,7 +77,8 @@ impl<'a> Context<'a> {
         use N::Type_::*;
         match targ_ {
             Var(_) => panic!("ICE tvar after expansion"),
-            Unit | Anything | UnresolvedError => (),
+            // Function types are not valid type arguments, which is reported elsewhere
+            Unit | Anything | UnresolvedError | Fun(_, _) => (),
             Ref(_, t) => {
                 let info = EdgeInfo {
                     edge: Edge::Nested,
@@ -166,9 +167,11 @@ fn module<'a>(
     module: &T::ModuleDefinition,
 ) {
     let context = &mut Context::new(tparams, mname);
+    // Inline functions are checked through their expansions at their call sites
     module
         .functions
         .key_cloned_iter()
+        .filter(|(_fname, fdef)| !fdef.inline)
         .for_each(|(_fname, fdef)| function_body(context, &fdef.body));
     let graph = context.instantiation_graph();
     // - get the strongly connected components
@@ -233,6 +236,12 @@ fn exp(context: &mut Context, e: &T::Exp) {
             exp(context, et);
             exp(context, ef);
         }
+        E::Match(es, arms) => {
+            exp(context, es);
+            for sp!(_, (_, earm)) in arms {
+                exp(context, earm)
+            }
+        }
         E::While(eb, eloop) => {
             exp(context, eb);
             exp(context, eloop);
@@ -243,6 +252,8 @@ fn exp(context: &mut Context, e: &T::Exp) {
 
         E::Builtin(_, er)
         | E::Vector(_, _, _, er)
+        | E::VarCall(_, er)
+        | E::Lambda(_, _, er)
         | E::Return(er)
         | E::Abort(er)
         | E::Dereference(er)
@@ -254,7 +265,7 @@ fn exp(context: &mut Context, e: &T::Exp) {
             exp(context, er)
         }
 
-        E::Pack(_, _, _, fields) => {
+        E::Pack(_, _, _, fields) | E::PackVariant(_, _, _, _, fields) => {
             for (_, _, (_, (_, fe))) in fields.iter() {
                 exp(context, fe)
             }
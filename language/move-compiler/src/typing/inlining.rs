@@ -0,0 +1,628 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module expands the calls of inline functions. Each call is replaced by the body of the
+//! function, with its parameters bound to the arguments of the call and its lambda parameters
+//! replaced by the lambdas given for them. The expanded code keeps the locations of the inline
+//! function, and its locals are renamed apart from the locals of the caller.
+//! As the expanded code becomes part of the caller, it can only use the private items of the
+//! module of the inline function when the caller is in that module. Constants are the exception,
+//! as the references to the constants of other modules are replaced by their values in `cfgir`.
+
+use super::core::{self, TParamSubst};
+use crate::{
+    diag,
+    expansion::ast::{AbilitySet, ModuleIdent, Visibility},
+    naming::ast::{Type, TypeName_, Type_},
+    parser::ast::{FunctionName, StructName, Var, INLINE_MODIFIER},
+    shared::{unique_map::UniqueMap, CompilationEnv, Identifier},
+    typing::ast as T,
+    FullyCompiledProgram,
+};
+use move_ir_types::location::*;
+use move_symbol_pool::Symbol;
+use std::collections::{BTreeMap, BTreeSet};
+
+const INLINE_NAME_DELIM: &str = "#inline";
+
+//**************************************************************************************************
+// Context
+//**************************************************************************************************
+
+// The expansion of a call of an inline function
+struct Frame {
+    module: ModuleIdent,
+    function: FunctionName,
+    call_loc: Loc,
+    tparam_subst: TParamSubst,
+    suffix: usize,
+    lambdas: BTreeMap<Var, T::Exp>,
+}
+
+struct Context<'env> {
+    env: &'env mut CompilationEnv,
+    inline_functions: BTreeMap<(ModuleIdent, FunctionName), T::Function>,
+    visibilities: BTreeMap<(ModuleIdent, FunctionName), Visibility>,
+    friends: BTreeMap<ModuleIdent, BTreeSet<ModuleIdent>>,
+    current_module: Option<ModuleIdent>,
+    frames: Vec<Frame>,
+    next_suffix: usize,
+}
+
+impl<'env> Context<'env> {
+    fn new<'a>(
+        env: &'env mut CompilationEnv,
+        modules: impl Iterator<Item = (ModuleIdent, &'a T::ModuleDefinition)>,
+    ) -> Self {
+        let mut inline_functions = BTreeMap::new();
+        let mut visibilities = BTreeMap::new();
+        let mut friends = BTreeMap::new();
+        for (mident, mdef) in modules {
+            friends.insert(
+                mident,
+                mdef.friends.key_cloned_iter().map(|(f, _)| f).collect(),
+            );
+            for (fname, fdef) in mdef.functions.key_cloned_iter() {
+                visibilities.insert((mident, fname), fdef.visibility.clone());
+                if fdef.inline {
+                    inline_functions.insert((mident, fname), fdef.clone());
+                }
+            }
+        }
+        Context {
+            env,
+            inline_functions,
+            visibilities,
+            friends,
+            current_module: None,
+            frames: vec![],
+            next_suffix: 0,
+        }
+    }
+
+    fn is_inline_function(&self, m: &ModuleIdent, f: &FunctionName) -> bool {
+        self.inline_functions.contains_key(&(*m, *f))
+    }
+
+    fn lambda(&self, v: &Var) -> Option<T::Exp> {
+        self.frames
+            .last()
+            .and_then(|frame| frame.lambdas.get(v))
+            .cloned()
+    }
+
+    // Expanded code can only use the private items of module `m` if it is expanded into `m`
+    fn check_module_access(&mut self, loc: Loc, m: &ModuleIdent, msg: impl FnOnce() -> String) {
+        if self.frames.is_empty() || self.current_module.as_ref() == Some(m) {
+            return;
+        }
+        self.report_inaccessible(loc, msg())
+    }
+
+    fn report_inaccessible(&mut self, loc: Loc, msg: String) {
+        let frame = &self.frames[0];
+        let call_msg = format!(
+            "Invalid call of '{}' function '{}::{}'",
+            INLINE_MODIFIER, frame.module, frame.function
+        );
+        let call_loc = frame.call_loc;
+        self.env.add_diag(diag!(
+            TypeSafety::InvalidInlineFunction,
+            (call_loc, call_msg),
+            (loc, msg),
+        ))
+    }
+}
+
+//**************************************************************************************************
+// Entry
+//**************************************************************************************************
+
+pub fn program(
+    compilation_env: &mut CompilationEnv,
+    pre_compiled_lib: Option<&FullyCompiledProgram>,
+    modules: &mut UniqueMap<ModuleIdent, T::ModuleDefinition>,
+    scripts: &mut BTreeMap<Symbol, T::Script>,
+) {
+    let all_modules = modules
+        .key_cloned_iter()
+        .chain(pre_compiled_lib.iter().flat_map(|pre_compiled| {
+            pre_compiled
+                .typing
+                .modules
+                .key_cloned_iter()
+                .filter(|(mident, _m)| !modules.contains_key(mident))
+        }));
+    let mut context = Context::new(compilation_env, all_modules);
+    if context.inline_functions.is_empty() {
+        return;
+    }
+
+    for (mloc, mident_, mdef) in modules.iter_mut() {
+        context.current_module = Some(sp(mloc, *mident_));
+        for (_, _, fdef) in mdef.functions.iter_mut() {
+            // Inline functions are expanded at their call sites, so their bodies are left as is
+            if !fdef.inline {
+                function_body(&mut context, &mut fdef.body)
+            }
+        }
+    }
+    context.current_module = None;
+    for script in scripts.values_mut() {
+        function_body(&mut context, &mut script.function.body)
+    }
+}
+
+fn function_body(context: &mut Context, sp!(_, b_): &mut T::FunctionBody) {
+    match b_ {
+        T::FunctionBody_::Native => (),
+        T::FunctionBody_::Defined(seq) => sequence(context, seq),
+    }
+}
+
+//**************************************************************************************************
+// Types and locals
+//**************************************************************************************************
+
+fn type_(context: &Context, ty: &mut Type) {
+    if let Some(frame) = context.frames.last() {
+        *ty = core::subst_tparams(&frame.tparam_subst, ty.clone())
+    }
+}
+
+fn types(context: &Context, tys: &mut [Type]) {
+    for ty in tys {
+        type_(context, ty)
+    }
+}
+
+fn expected_types(context: &Context, tys: &mut [Option<Type>]) {
+    for ty in tys.iter_mut().flatten() {
+        type_(context, ty)
+    }
+}
+
+fn var(context: &Context, v: &mut Var) {
+    if let Some(frame) = context.frames.last() {
+        let name = format!("{}{}{}", v.value(), INLINE_NAME_DELIM, frame.suffix);
+        *v = Var(sp(v.loc(), name.into()))
+    }
+}
+
+//**************************************************************************************************
+// Expressions
+//**************************************************************************************************
+
+fn sequence(context: &mut Context, seq: &mut T::Sequence) {
+    for item in seq {
+        sequence_item(context, item)
+    }
+}
+
+fn sequence_item(context: &mut Context, item: &mut T::SequenceItem) {
+    use T::SequenceItem_ as S;
+    match &mut item.value {
+        S::Seq(e) => exp(context, e),
+        S::Declare(binds) => lvalues(context, binds),
+        S::Bind(binds, tys, e) => {
+            exp(context, e);
+            lvalues(context, binds);
+            expected_types(context, tys);
+        }
+    }
+}
+
+fn exp(context: &mut Context, e: &mut T::Exp) {
+    use T::UnannotatedExp_ as E;
+    type_(context, &mut e.ty);
+    let eloc = e.exp.loc;
+    match &mut e.exp.value {
+        E::ModuleCall(call) if context.is_inline_function(&call.module, &call.name) => {
+            types(context, &mut call.type_arguments);
+            exp(context, &mut call.arguments);
+            types(context, &mut call.parameter_types);
+            let call = match std::mem::replace(&mut e.exp.value, E::UnresolvedError) {
+                E::ModuleCall(call) => *call,
+                _ => unreachable!(),
+            };
+            *e = inline_call(context, eloc, e.ty.clone(), call)
+        }
+        E::ModuleCall(call) => {
+            check_call(context, eloc, call);
+            types(context, &mut call.type_arguments);
+            exp(context, &mut call.arguments);
+            types(context, &mut call.parameter_types);
+        }
+        E::VarCall(v, args) => {
+            exp(context, args);
+            match context.lambda(v) {
+                Some(lambda) => {
+                    let args = std::mem::replace(
+                        &mut **args,
+                        T::exp(e.ty.clone(), sp(eloc, E::UnresolvedError)),
+                    );
+                    *e = lambda_call(eloc, e.ty.clone(), lambda, args)
+                }
+                None => var(context, v),
+            }
+        }
+        E::Move { var: v, .. } | E::Copy { var: v, .. } | E::Use(v) => match context.lambda(v) {
+            Some(lambda) => *e = lambda,
+            None => var(context, v),
+        },
+        E::BorrowLocal(_, v) => var(context, v),
+        E::Spec(_, _) if !context.frames.is_empty() => {
+            // Specifications are not expanded with the rest of the code
+            e.exp.value = E::Unit { trailing: false }
+        }
+        E::Unit { .. }
+        | E::Value(_)
+        | E::Constant(_, _)
+        | E::Spec(_, _)
+        | E::Break
+        | E::Continue
+        | E::UnresolvedError => (),
+
+        E::Builtin(b, args) => {
+            builtin_function(context, eloc, b);
+            exp(context, args);
+        }
+        E::Vector(_, _, ty, args) => {
+            type_(context, ty);
+            exp(context, args);
+        }
+        E::IfElse(eb, et, ef) => {
+            exp(context, eb);
+            exp(context, et);
+            exp(context, ef);
+        }
+        E::Match(es, arms) => {
+            exp(context, es);
+            for sp!(_, (pat, earm)) in arms {
+                match_pattern(context, pat);
+                exp(context, earm)
+            }
+        }
+        E::While(eb, eloop) => {
+            exp(context, eb);
+            exp(context, eloop);
+        }
+        E::Loop { body: eloop, .. } => exp(context, eloop),
+        E::Block(seq) => sequence(context, seq),
+        E::Lambda(binds, tys, body) => {
+            lvalues(context, binds);
+            expected_types(context, tys);
+            exp(context, body);
+        }
+        E::Assign(binds, tys, er) => {
+            exp(context, er);
+            lvalues(context, binds);
+            expected_types(context, tys);
+        }
+        E::Mutate(el, er) => {
+            exp(context, el);
+            exp(context, er);
+        }
+        E::Return(er)
+        | E::Abort(er)
+        | E::Dereference(er)
+        | E::UnaryExp(_, er)
+        | E::TempBorrow(_, er) => exp(context, er),
+        E::BinopExp(el, _, operand_ty, er) => {
+            exp(context, el);
+            exp(context, er);
+            type_(context, operand_ty);
+        }
+        E::Pack(m, s, tys, fields) => {
+            let (m, s) = (*m, *s);
+            context.check_module_access(eloc, &m, || {
+                format!("Packing '{}::{}' is only allowed in module '{}'", m, s, m)
+            });
+            types(context, tys);
+            for (_, _, (_, (ty, fe))) in fields.iter_mut() {
+                type_(context, ty);
+                exp(context, fe)
+            }
+        }
+        E::PackVariant(m, s, v, tys, fields) => {
+            let (m, s, v) = (*m, *s, *v);
+            context.check_module_access(eloc, &m, || {
+                format!(
+                    "Packing '{}::{}::{}' is only allowed in module '{}'",
+                    m, s, v, m
+                )
+            });
+            types(context, tys);
+            for (_, _, (_, (ty, fe))) in fields.iter_mut() {
+                type_(context, ty);
+                exp(context, fe)
+            }
+        }
+        E::Borrow(_, er, f) => {
+            exp(context, er);
+            if let Some((m, s)) = struct_of_reference(&er.ty) {
+                let f = *f;
+                context.check_module_access(eloc, &m, || {
+                    format!(
+                        "Accessing the field '{}' of '{}::{}' is only allowed in module '{}'",
+                        f, m, s, m
+                    )
+                })
+            }
+        }
+        E::ExpList(items) => {
+            for item in items {
+                match item {
+                    T::ExpListItem::Single(e, ty) => {
+                        exp(context, e);
+                        type_(context, ty);
+                    }
+                    T::ExpListItem::Splat(_, e, tys) => {
+                        exp(context, e);
+                        types(context, tys);
+                    }
+                }
+            }
+        }
+        E::Cast(el, ty) | E::Annotate(el, ty) => {
+            exp(context, el);
+            type_(context, ty);
+        }
+    }
+}
+
+fn builtin_function(context: &mut Context, loc: Loc, b: &mut T::BuiltinFunction) {
+    use T::BuiltinFunction_ as B;
+    let name = b.value.display_name();
+    match &mut b.value {
+        B::MoveTo(ty) | B::MoveFrom(ty) | B::BorrowGlobal(_, ty) | B::Exists(ty) => {
+            type_(context, ty);
+            if let Type_::Apply(_, sp!(_, TypeName_::ModuleType(m, s)), _) = &ty.value {
+                let (m, s) = (*m, *s);
+                context.check_module_access(loc, &m, || {
+                    format!(
+                        "Calling '{}' on '{}::{}' is only allowed in module '{}'",
+                        name, m, s, m
+                    )
+                })
+            }
+        }
+        B::Freeze(ty) => type_(context, ty),
+        B::Assert(_) => (),
+    }
+}
+
+fn check_call(context: &mut Context, loc: Loc, call: &T::ModuleCall) {
+    let (m, f) = (call.module, call.name);
+    if context.frames.is_empty() || context.current_module == Some(m) {
+        return;
+    }
+    let is_friend = match &context.current_module {
+        Some(current) => context
+            .friends
+            .get(&m)
+            .map_or(false, |friends| friends.contains(current)),
+        None => false,
+    };
+    let msg = match context.visibilities.get(&(m, f)) {
+        None | Some(Visibility::Public(_)) => return,
+        Some(Visibility::Friend(_)) if is_friend => return,
+        Some(Visibility::Friend(_)) => format!(
+            "Calling '{}::{}' is only allowed in module '{}' and its friends",
+            m, f, m
+        ),
+        Some(Visibility::Internal) => {
+            format!("Calling '{}::{}' is only allowed in module '{}'", m, f, m)
+        }
+    };
+    context.report_inaccessible(loc, msg)
+}
+
+fn struct_of_reference(ty: &Type) -> Option<(ModuleIdent, StructName)> {
+    match &ty.value {
+        Type_::Ref(_, inner) => match &inner.value {
+            Type_::Apply(_, sp!(_, TypeName_::ModuleType(m, s)), _) => Some((*m, *s)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn lvalues(context: &mut Context, binds: &mut T::LValueList) {
+    for b in &mut binds.value {
+        lvalue(context, b)
+    }
+}
+
+fn lvalue(context: &mut Context, b: &mut T::LValue) {
+    use T::LValue_ as L;
+    let loc = b.loc;
+    match &mut b.value {
+        L::Ignore => (),
+        L::Var(v, ty) => {
+            var(context, v);
+            type_(context, ty);
+        }
+        L::BorrowUnpack(_, m, s, tys, fields) | L::Unpack(m, s, tys, fields) => {
+            let (m, s) = (*m, *s);
+            context.check_module_access(loc, &m, || {
+                format!("Unpacking '{}::{}' is only allowed in module '{}'", m, s, m)
+            });
+            types(context, tys);
+            for (_, _, (_, (ty, inner))) in fields.iter_mut() {
+                type_(context, ty);
+                lvalue(context, inner)
+            }
+        }
+    }
+}
+
+fn match_pattern(context: &mut Context, pat: &mut T::MatchPattern) {
+    let loc = pat.loc;
+    match &mut pat.value {
+        T::MatchPattern_::Wildcard => (),
+        T::MatchPattern_::Variant(m, s, v, tys, fields) => {
+            let (m, s, v) = (*m, *s, *v);
+            context.check_module_access(loc, &m, || {
+                format!(
+                    "Matching on '{}::{}::{}' is only allowed in module '{}'",
+                    m, s, v, m
+                )
+            });
+            types(context, tys);
+            for (_, _, (_, (ty, inner))) in fields.iter_mut() {
+                type_(context, ty);
+                lvalue(context, inner)
+            }
+        }
+    }
+}
+
+//**************************************************************************************************
+// Expansion
+//**************************************************************************************************
+
+// The arguments of a call of a function with `n` parameters, which are given as a single
+// expression
+fn call_arguments(arguments: T::Exp, n: usize) -> Vec<T::Exp> {
+    use T::UnannotatedExp_ as E;
+    if n == 1 {
+        return vec![arguments];
+    }
+    match arguments.exp.value {
+        E::Unit { .. } => vec![],
+        E::ExpList(items) => items
+            .into_iter()
+            .map(|item| match item {
+                T::ExpListItem::Single(e, _) | T::ExpListItem::Splat(_, e, _) => e,
+            })
+            .collect(),
+        _ => vec![arguments],
+    }
+}
+
+fn inline_call(context: &mut Context, loc: Loc, ty: Type, call: T::ModuleCall) -> T::Exp {
+    use T::{SequenceItem_ as S, UnannotatedExp_ as E};
+    let T::ModuleCall {
+        module,
+        name,
+        type_arguments,
+        arguments,
+        parameter_types,
+        acquires: _,
+    } = call;
+    if context
+        .frames
+        .iter()
+        .any(|frame| frame.module == module && frame.function == name)
+    {
+        let msg = format!(
+            "Invalid call of '{}' function '{}::{}'. '{}' functions cannot be recursive, as \
+             their bodies are expanded at their call sites",
+            INLINE_MODIFIER, module, name, INLINE_MODIFIER
+        );
+        context
+            .env
+            .add_diag(diag!(TypeSafety::InvalidInlineFunction, (loc, msg)));
+        return T::exp(ty, sp(loc, E::UnresolvedError));
+    }
+
+    let fdef = context.inline_functions[&(module, name)].clone();
+    let args = call_arguments(*arguments, fdef.signature.parameters.len());
+    let mut body = match fdef.body.value {
+        T::FunctionBody_::Defined(seq) if args.len() == fdef.signature.parameters.len() => seq,
+        _ => {
+            assert!(context.env.has_diags());
+            return T::exp(ty, sp(loc, E::UnresolvedError));
+        }
+    };
+
+    // Lambdas are not evaluated at the call. The other arguments are bound to the parameters
+    // before the body, in their original order
+    let mut lambdas = BTreeMap::new();
+    let mut binds = vec![];
+    let mut bind_tys = vec![];
+    let mut values = vec![];
+    let params = fdef.signature.parameters.into_iter().zip(parameter_types);
+    for (((param, param_ty), expected_ty), arg) in params.zip(args) {
+        match &param_ty.value {
+            Type_::Fun(_, _) => {
+                lambdas.insert(param, arg);
+            }
+            _ => {
+                binds.push(sp(param.loc(), T::LValue_::Var(param, Box::new(param_ty))));
+                bind_tys.push(Some(expected_ty));
+                values.push(arg);
+            }
+        }
+    }
+
+    let tparam_subst = core::make_tparam_subst(&fdef.signature.type_parameters, type_arguments);
+    let suffix = context.next_suffix;
+    context.next_suffix += 1;
+    context.frames.push(Frame {
+        module,
+        function: name,
+        call_loc: loc,
+        tparam_subst,
+        suffix,
+        lambdas,
+    });
+    sequence(context, &mut body);
+    let mut binds = sp(loc, binds);
+    lvalues(context, &mut binds);
+    context.frames.pop();
+
+    if !values.is_empty() {
+        let value = bound_value(loc, values);
+        body.push_front(sp(loc, S::Bind(binds, bind_tys, Box::new(value))));
+    }
+    expanded_block(loc, ty, body)
+}
+
+// The call of a lambda parameter, replaced by the body of the lambda with its parameters bound to
+// the arguments
+fn lambda_call(loc: Loc, ty: Type, lambda: T::Exp, args: T::Exp) -> T::Exp {
+    use T::{SequenceItem_ as S, UnannotatedExp_ as E};
+    match lambda.exp.value {
+        E::Lambda(binds, bind_tys, body) => {
+            let mut seq = T::Sequence::new();
+            if !binds.value.is_empty() {
+                seq.push_back(sp(loc, S::Bind(binds, bind_tys, Box::new(args))));
+            }
+            seq.push_back(sp(loc, S::Seq(body)));
+            expanded_block(loc, ty, seq)
+        }
+        _ => T::exp(ty, sp(loc, E::UnresolvedError)),
+    }
+}
+
+fn bound_value(loc: Loc, mut values: Vec<T::Exp>) -> T::Exp {
+    use T::UnannotatedExp_ as E;
+    if values.len() == 1 {
+        return values.pop().unwrap();
+    }
+    let tys = values.iter().map(|e| e.ty.clone()).collect::<Vec<_>>();
+    let n = tys.len();
+    let multiple = sp(loc, TypeName_::Multiple(n));
+    let ty = sp(
+        loc,
+        Type_::Apply(Some(AbilitySet::collection(loc)), multiple, tys),
+    );
+    let items = values.into_iter().map(T::single_item).collect();
+    T::exp(ty, sp(loc, E::ExpList(items)))
+}
+
+// The expanded body is annotated with the type of the call, so that its result is frozen as needed
+fn expanded_block(loc: Loc, ty: Type, seq: T::Sequence) -> T::Exp {
+    use T::{SequenceItem_ as S, UnannotatedExp_ as E};
+    let body_ty = match seq.back() {
+        Some(sp!(_, S::Seq(last))) => last.ty.clone(),
+        _ => ty.clone(),
+    };
+    let block = T::exp(body_ty, sp(loc, E::Block(seq)));
+    T::exp(
+        ty.clone(),
+        sp(loc, E::Annotate(Box::new(block), Box::new(ty))),
+    )
+}
@@ -6,5 +6,7 @@ pub(crate) mod core;
 mod expand;
 mod globals;
 mod infinite_instantiations;
+mod inlining;
+mod recursive_constants;
 mod recursive_structs;
 pub(crate) mod translate;
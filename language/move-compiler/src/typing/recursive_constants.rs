@@ -0,0 +1,151 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    diag,
+    diagnostics::Diagnostic,
+    expansion::ast::ModuleIdent,
+    parser::ast::ConstantName,
+    shared::{unique_map::UniqueMap, *},
+    typing::ast as T,
+};
+use move_ir_types::location::*;
+use move_symbol_pool::Symbol;
+use petgraph::{algo::tarjan_scc as petgraph_scc, graphmap::DiGraphMap};
+use std::collections::BTreeMap;
+
+// Only the constants of the same module can form a cycle, as the modules themselves cannot
+struct Context {
+    constant_neighbors: BTreeMap<ConstantName, BTreeMap<ConstantName, Loc>>,
+    current_module: Option<ModuleIdent>,
+    current_constant: Option<ConstantName>,
+}
+
+impl Context {
+    fn new(current_module: Option<ModuleIdent>) -> Self {
+        Context {
+            current_module,
+            constant_neighbors: BTreeMap::new(),
+            current_constant: None,
+        }
+    }
+
+    fn add_usage(&mut self, loc: Loc, module: &Option<ModuleIdent>, cname: &ConstantName) {
+        if &self.current_module != module {
+            return;
+        }
+        self.constant_neighbors
+            .entry(self.current_constant.unwrap())
+            .or_insert_with(BTreeMap::new)
+            .insert(*cname, loc);
+    }
+
+    fn constant_graph(&self) -> DiGraphMap<&ConstantName, ()> {
+        let edges = self
+            .constant_neighbors
+            .iter()
+            .flat_map(|(parent, children)| children.iter().map(move |(child, _)| (parent, child)));
+        DiGraphMap::from_edges(edges)
+    }
+}
+
+//**************************************************************************************************
+// Entry
+//**************************************************************************************************
+
+pub fn program(
+    compilation_env: &mut CompilationEnv,
+    modules: &UniqueMap<ModuleIdent, T::ModuleDefinition>,
+    scripts: &BTreeMap<Symbol, T::Script>,
+) {
+    for (mname, mdef) in modules.key_cloned_iter() {
+        constants(compilation_env, Some(mname), &mdef.constants)
+    }
+    for sdef in scripts.values() {
+        constants(compilation_env, None, &sdef.constants)
+    }
+}
+
+fn constants(
+    compilation_env: &mut CompilationEnv,
+    current_module: Option<ModuleIdent>,
+    constants: &UniqueMap<ConstantName, T::Constant>,
+) {
+    let context = &mut Context::new(current_module);
+    for (cname, cdef) in constants.key_cloned_iter() {
+        context.current_constant = Some(cname);
+        exp(context, &cdef.value);
+    }
+    let graph = context.constant_graph();
+    // - get the strongly connected components
+    // - filter out single nodes that do not connect to themselves
+    // - report those cycles
+    petgraph_scc(&graph)
+        .into_iter()
+        .filter(|scc| scc.len() > 1 || graph.contains_edge(scc[0], scc[0]))
+        .for_each(|scc| compilation_env.add_diag(cycle_error(context, &graph, scc[0])))
+}
+
+//**************************************************************************************************
+// Expressions
+//**************************************************************************************************
+
+fn exp(context: &mut Context, e: &T::Exp) {
+    use T::UnannotatedExp_ as E;
+    match &e.exp.value {
+        E::Constant(m, c) => context.add_usage(e.exp.loc, m, c),
+        E::Block(seq) => {
+            for sp!(_, item_) in seq {
+                match item_ {
+                    T::SequenceItem_::Seq(e) | T::SequenceItem_::Bind(_, _, e) => exp(context, e),
+                    T::SequenceItem_::Declare(_) => (),
+                }
+            }
+        }
+        E::UnaryExp(_, e) | E::Cast(e, _) | E::Annotate(e, _) | E::Vector(_, _, _, e) => {
+            exp(context, e)
+        }
+        E::BinopExp(el, _, _, er) => {
+            exp(context, el);
+            exp(context, er)
+        }
+        E::ExpList(items) => {
+            for item in items {
+                match item {
+                    T::ExpListItem::Single(e, _) | T::ExpListItem::Splat(_, e, _) => {
+                        exp(context, e)
+                    }
+                }
+            }
+        }
+        // The other expressions are not supported in constants, which is reported elsewhere
+        _ => (),
+    }
+}
+
+fn cycle_error(
+    context: &Context,
+    graph: &DiGraphMap<&ConstantName, ()>,
+    cycle_node: &ConstantName,
+) -> Diagnostic {
+    let cycle = shortest_cycle(graph, cycle_node);
+
+    let cycle_strings = cycle
+        .iter()
+        .map(|c| format!("'{}'", c))
+        .collect::<Vec<_>>()
+        .join(" refers to ");
+
+    let len = cycle.len();
+    let user = cycle[len - 2];
+    let used = cycle[len - 1];
+    let used_loc = context.constant_neighbors[user][used];
+
+    let use_msg = format!("Invalid reference to '{}' in constant '{}'", used, user);
+    let cycle_msg = format!("Using this constant creates a cycle: {}", cycle_strings);
+    diag!(
+        TypeSafety::CyclicConstant,
+        (used_loc, use_msg),
+        (used_loc, cycle_msg)
+    )
+}
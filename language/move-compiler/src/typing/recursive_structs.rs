@@ -85,6 +85,11 @@ fn struct_def(context: &mut Context, sname: StructName, sdef: &N::StructDefiniti
         N::StructFields::Defined(fields) => {
             fields.iter().for_each(|(_, _, (_, ty))| type_(context, ty))
         }
+        N::StructFields::Variants(variants) => {
+            for (_, _, (_, fields)) in variants {
+                fields.iter().for_each(|(_, _, (_, ty))| type_(context, ty))
+            }
+        }
     };
     context.current_struct = None;
 }
@@ -94,7 +99,7 @@ fn type_(context: &mut Context, sp!(loc, ty_): &N::Type) {
     match ty_ {
         Var(_) => panic!("ICE tvar in struct field type"),
         Unit | Anything | UnresolvedError | Param(_) => (),
-        Ref(_, t) => type_(context, t),
+        Ref(_, t) | Fun(_, t) => type_(context, t),
         Apply(_, sp!(_, tn_), tys) => {
             if let TypeName_::ModuleType(m, s) = tn_ {
                 context.add_usage(*loc, m, s)
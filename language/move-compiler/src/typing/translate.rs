@@ -3,14 +3,17 @@
 
 use super::{
     core::{self, Context, Subst},
-    expand, globals, infinite_instantiations, recursive_structs,
+    expand, globals, infinite_instantiations, inlining, recursive_constants, recursive_structs,
 };
 use crate::{
     diag,
     diagnostics::{codes::*, Diagnostic},
     expansion::ast::{Fields, ModuleIdent, Value_},
     naming::ast::{self as N, TParam, TParamID, Type, TypeName_, Type_},
-    parser::ast::{Ability_, BinOp_, ConstantName, Field, FunctionName, StructName, UnaryOp_, Var},
+    parser::ast::{
+        Ability_, BinOp_, ConstantName, Field, FunctionName, StructName, UnaryOp_, Var,
+        VariantName, INLINE_MODIFIER,
+    },
     shared::{unique_map::UniqueMap, *},
     typing::ast as T,
     FullyCompiledProgram,
@@ -33,11 +36,13 @@ pub fn program(
         modules: nmodules,
         scripts: nscripts,
     } = prog;
-    let modules = modules(&mut context, nmodules);
-    let scripts = scripts(&mut context, nscripts);
+    let mut modules = modules(&mut context, nmodules);
+    let mut scripts = scripts(&mut context, nscripts);
 
     assert!(context.constraints.is_empty());
+    inlining::program(context.env, pre_compiled_lib, &mut modules, &mut scripts);
     recursive_structs::modules(context.env, &modules);
+    recursive_constants::program(context.env, &modules, &scripts);
     infinite_instantiations::modules(context.env, &modules);
     T::Program { modules, scripts }
 }
@@ -134,6 +139,7 @@ fn function(
         attributes,
         visibility,
         entry,
+        inline,
         mut signature,
         body: n_body,
         acquires,
@@ -141,6 +147,7 @@ fn function(
     assert!(context.constraints.is_empty());
     context.reset_for_module_item();
     context.current_function = Some(name);
+    context.current_function_inline = inline;
     function_signature(context, &signature);
     if is_script {
         let mk_msg = || {
@@ -167,6 +174,7 @@ fn function(
         attributes,
         visibility,
         entry,
+        inline,
         signature,
         acquires,
         body,
@@ -239,6 +247,7 @@ fn constant(context: &mut Context, _name: ConstantName, nconstant: N::Constant)
     let N::Constant {
         attributes,
         loc,
+        visibility: _,
         signature,
         value: nvalue,
     } = nconstant;
@@ -303,8 +312,11 @@ mod check_valid_constant {
 
         let mut acceptable_types = vec![
             Type_::u8(loc),
+            Type_::u16(loc),
+            Type_::u32(loc),
             Type_::u64(loc),
             Type_::u128(loc),
+            Type_::u256(loc),
             Type_::bool(loc),
             Type_::address(loc),
         ];
@@ -360,7 +372,9 @@ mod check_valid_constant {
             //*****************************************
             // Valid cases
             //*****************************************
-            E::Unit { .. } | E::Value(_) | E::Move { .. } | E::Copy { .. } => return,
+            E::Unit { .. } | E::Value(_) | E::Move { .. } | E::Copy { .. } | E::Constant(_, _) => {
+                return
+            }
             E::Block(seq) => {
                 sequence(context, seq);
                 return;
@@ -401,6 +415,11 @@ mod check_valid_constant {
                 s = format!("'{}' is", b);
                 &s
             }
+            E::VarCall(_, args) => {
+                exp(context, args);
+                "Lambda calls are"
+            }
+            E::Lambda(_, _, _) => "Lambdas are",
             E::IfElse(eb, et, ef) => {
                 exp(context, eb);
                 exp(context, et);
@@ -443,7 +462,19 @@ mod check_valid_constant {
                 }
                 "Structs are"
             }
-            E::Constant(_, _) => "Other constants are",
+            E::PackVariant(_, _, _, _, fields) => {
+                for (_, _, (_, (_, fe))) in fields {
+                    exp(context, fe)
+                }
+                "Enums are"
+            }
+            E::Match(es, arms) => {
+                exp(context, es);
+                for sp!(_, (_, earm)) in arms {
+                    exp(context, earm)
+                }
+                "'match' expressions are"
+            }
         };
         context.env.add_diag(diag!(
             TypeSafety::UnsupportedConstant,
@@ -504,9 +535,10 @@ fn struct_def(context: &mut Context, s: &mut N::StructDefinition) {
     assert!(context.constraints.is_empty());
     context.reset_for_module_item();
 
-    let field_map = match &mut s.fields {
+    let mut field_maps = match &mut s.fields {
         N::StructFields::Native(_) => return,
-        N::StructFields::Defined(m) => m,
+        N::StructFields::Defined(m) => vec![m],
+        N::StructFields::Variants(vs) => vs.iter_mut().map(|(_, _, (_, m))| m).collect(),
     };
 
     let declared_abilities = &s.abilities;
@@ -516,9 +548,12 @@ fn struct_def(context: &mut Context, s: &mut N::StructDefinition) {
             .iter()
             .map(|tp| sp(tp.param.user_specified_name.loc, Type_::Anything)),
     );
-    for (_field_loc, _field, idx_ty) in field_map.iter() {
-        let loc = idx_ty.1.loc;
-        let subst_ty = core::subst_tparams(tparam_subst, idx_ty.1.clone());
+    let field_tys = field_maps
+        .iter()
+        .flat_map(|m| m.iter().map(|(_, _, (_, ty))| ty));
+    for ty in field_tys {
+        let loc = ty.loc;
+        let subst_ty = core::subst_tparams(tparam_subst, ty.clone());
         let inst_ty = core::instantiate(context, subst_ty);
         context.add_base_type_constraint(loc, "Invalid field type", inst_ty.clone());
         for declared_ability in declared_abilities {
@@ -533,21 +568,25 @@ fn struct_def(context: &mut Context, s: &mut N::StructDefinition) {
     }
     core::solve_constraints(context);
 
-    for (_field_loc, _field_, idx_ty) in field_map.iter_mut() {
-        expand::type_(context, &mut idx_ty.1);
+    for field_map in &mut field_maps {
+        for (_field_loc, _field_, idx_ty) in field_map.iter_mut() {
+            expand::type_(context, &mut idx_ty.1);
+        }
     }
 
-    check_type_params_usage(context, &s.type_parameters, field_map);
+    let field_tys = field_maps
+        .iter()
+        .flat_map(|m| m.iter().map(|(_, _, (_, ty))| ty))
+        .collect::<Vec<_>>();
+    check_type_params_usage(context, &s.type_parameters, &field_tys);
 }
 
 fn check_type_params_usage(
     context: &mut Context,
     type_parameters: &[N::StructTypeParameter],
-    field_map: &Fields<Type>,
+    field_tys: &[&Type],
 ) {
-    let has_unresolved = field_map
-        .iter()
-        .any(|(_, _, ty)| has_unresolved_error_type(&ty.1));
+    let has_unresolved = field_tys.iter().any(|ty| has_unresolved_error_type(ty));
 
     if has_unresolved {
         return;
@@ -562,10 +601,10 @@ fn check_type_params_usage(
         .filter(|ty_param| ty_param.is_phantom)
         .map(|param| param.param.id)
         .collect();
-    for (_, _, idx_ty) in field_map.iter() {
+    for ty in field_tys {
         visit_type_params(
             context,
-            &idx_ty.1,
+            ty,
             ParamPos::FIELD,
             &mut |context, loc, param, pos| {
                 let param_is_phantom = phantom_params.contains(&param.id);
@@ -661,6 +700,13 @@ fn visit_type_params(
                 }
             }
         },
+        // Function types cannot appear in structs, but we still report them as a non-phantom
+        // position for full information.
+        Type_::Fun(arg_tys, result_ty) => {
+            for ty in arg_tys.iter().chain(std::iter::once(&**result_ty)) {
+                visit_type_params(context, ty, ParamPos::NonPhantom(NonPhantomPos::TypeArg), f)
+            }
+        }
         Type_::Var(_) | Type_::Anything | Type_::UnresolvedError => {}
         Type_::Unit => {}
     }
@@ -721,6 +767,9 @@ fn has_unresolved_error_type(ty: &Type) -> bool {
         Type_::UnresolvedError => true,
         Type_::Ref(_, ty) => has_unresolved_error_type(ty),
         Type_::Apply(_, _, ty_args) => ty_args.iter().any(has_unresolved_error_type),
+        Type_::Fun(arg_tys, result_ty) => {
+            arg_tys.iter().any(has_unresolved_error_type) || has_unresolved_error_type(result_ty)
+        }
         Type_::Param(_) | Type_::Var(_) | Type_::Anything | Type_::Unit => false,
     }
 }
@@ -1184,6 +1233,19 @@ fn exp_(context: &mut Context, initial_ne: N::Exp) -> T::Exp {
 fn exp_inner(context: &mut Context, sp!(eloc, ne_): N::Exp) -> T::Exp {
     use N::Exp_ as NE;
     use T::UnannotatedExp_ as TE;
+    if let NE::Move(var) | NE::Copy(var) | NE::Use(var) = &ne_ {
+        if let Some(sp!(_, Type_::Fun(_, _))) = context.get_local_(var) {
+            let msg = format!(
+                "Invalid usage of lambda parameter '{}'. Lambda parameters can only be called \
+                 or passed to '{}' functions",
+                var, INLINE_MODIFIER
+            );
+            context
+                .env
+                .add_diag(diag!(TypeSafety::InvalidLambda, (eloc, msg)));
+            return T::exp(context.error_type(eloc), sp(eloc, TE::UnresolvedError));
+        }
+    }
     let (ty, e_) = match ne_ {
         NE::Unit { trailing } => (sp(eloc, Type_::Unit), TE::Unit { trailing }),
         NE::Value(sp!(vloc, Value_::InferredNum(v))) => (
@@ -1222,8 +1284,11 @@ fn exp_inner(context: &mut Context, sp!(eloc, ne_): N::Exp) -> T::Exp {
         }
 
         NE::ModuleCall(m, f, ty_args_opt, sp!(argloc, nargs_)) => {
+            module_call(context, eloc, m, f, ty_args_opt, argloc, nargs_)
+        }
+        NE::VarCall(var, sp!(argloc, nargs_)) => {
             let args = exp_vec(context, nargs_);
-            module_call(context, eloc, m, f, ty_args_opt, argloc, args)
+            var_call(context, eloc, var, argloc, args)
         }
         NE::Builtin(b, sp!(argloc, nargs_)) => {
             let args = exp_vec(context, nargs_);
@@ -1255,6 +1320,7 @@ fn exp_inner(context: &mut Context, sp!(eloc, ne_): N::Exp) -> T::Exp {
             );
             (ty, TE::IfElse(eb, et, ef))
         }
+        NE::Match(ns, narms) => match_(context, eloc, *ns, narms),
         NE::While(nb, nloop) => {
             let eb = exp(context, nb);
             let bloc = eb.exp.loc;
@@ -1277,6 +1343,16 @@ fn exp_inner(context: &mut Context, sp!(eloc, ne_): N::Exp) -> T::Exp {
             let seq = sequence(context, nseq);
             (sequence_type(&seq).clone(), TE::Block(seq))
         }
+        NE::Lambda(_, _) => {
+            let msg = format!(
+                "Invalid lambda. Lambdas are only allowed as arguments to '{}' functions",
+                INLINE_MODIFIER
+            );
+            context
+                .env
+                .add_diag(diag!(TypeSafety::InvalidLambda, (eloc, msg)));
+            (context.error_type(eloc), TE::UnresolvedError)
+        }
 
         NE::Assign(na, nr) => {
             let er = exp(context, nr);
@@ -1302,6 +1378,21 @@ fn exp_inner(context: &mut Context, sp!(eloc, ne_): N::Exp) -> T::Exp {
         }
 
         NE::Return(nret) => {
+            if context.in_lambda() {
+                let msg = "Invalid usage of 'return'. 'return' cannot be used inside a lambda";
+                context
+                    .env
+                    .add_diag(diag!(TypeSafety::InvalidLambda, (eloc, msg)))
+            } else if context.current_function_inline {
+                let msg = format!(
+                    "Invalid usage of 'return'. 'return' cannot be used in '{}' functions, as \
+                     their bodies are expanded at their call sites",
+                    INLINE_MODIFIER
+                );
+                context
+                    .env
+                    .add_diag(diag!(TypeSafety::InvalidInlineFunction, (eloc, msg)))
+            }
             let eret = exp(context, nret);
             let ret_ty = context.return_type.clone().unwrap();
             subtype(context, eloc, || "Invalid return", eret.ty.clone(), ret_ty);
@@ -1429,6 +1520,9 @@ fn exp_inner(context: &mut Context, sp!(eloc, ne_): N::Exp) -> T::Exp {
             }
             (bt, TE::Pack(m, n, targs, tfields))
         }
+        NE::PackVariant(m, n, v, ty_args_opt, nfields) => {
+            pack_variant(context, eloc, m, n, v, ty_args_opt, nfields)
+        }
 
         NE::Borrow(mut_, sp!(_, N::ExpDotted_::Exp(ner))) => {
             let er = exp_(context, *ner);
@@ -1497,6 +1591,233 @@ fn exp_inner(context: &mut Context, sp!(eloc, ne_): N::Exp) -> T::Exp {
     T::exp(ty, sp(eloc, e_))
 }
 
+fn pack_variant(
+    context: &mut Context,
+    eloc: Loc,
+    m: ModuleIdent,
+    n: StructName,
+    v: VariantName,
+    ty_args_opt: Option<Vec<Type>>,
+    nfields: Fields<N::Exp>,
+) -> (Type, T::UnannotatedExp_) {
+    let (bt, targs) = core::make_struct_type(context, eloc, &m, &n, ty_args_opt);
+    let typed_nfields = add_variant_field_types(
+        context,
+        eloc,
+        "argument",
+        (&m, &n, &v),
+        targs.clone(),
+        nfields,
+    );
+
+    let tfields = typed_nfields.map(|f, (idx, (fty, narg))| {
+        let arg = exp_(context, narg);
+        subtype(
+            context,
+            arg.exp.loc,
+            || {
+                format!(
+                    "Invalid argument for field '{}' for '{}::{}::{}'",
+                    f, &m, &n, &v
+                )
+            },
+            arg.ty.clone(),
+            fty.clone(),
+        );
+        (idx, (fty, arg))
+    });
+    if !context.is_current_module(&m) {
+        let msg = format!(
+            "Invalid instantiation of '{}::{}::{}'.\nAll enums can only be constructed in \
+             the module in which they are declared",
+            &m, &n, &v,
+        );
+        context
+            .env
+            .add_diag(diag!(TypeSafety::Visibility, (eloc, msg)));
+    }
+    (bt, T::UnannotatedExp_::PackVariant(m, n, v, targs, tfields))
+}
+
+fn match_(
+    context: &mut Context,
+    eloc: Loc,
+    nsubject: N::Exp,
+    narms: Vec<N::MatchArm>,
+) -> (Type, T::UnannotatedExp_) {
+    let subject = exp_(context, nsubject);
+    let (ref_mut, subject_inner) = match core::unfold_type(&context.subst, subject.ty.clone()).value
+    {
+        Type_::Ref(mut_, inner) => (Some(mut_), *inner),
+        _ => (None, subject.ty.clone()),
+    };
+
+    let mut ty_opt: Option<Type> = None;
+    let mut arms = vec![];
+    for sp!(aloc, (sp!(ploc, npat_), narm)) in narms {
+        let old_locals = context.save_locals_scope();
+        let (declared, pat_) = match npat_ {
+            N::MatchPattern_::Wildcard => {
+                if ref_mut.is_none() {
+                    context.add_ability_constraint(
+                        ploc,
+                        Some(format!(
+                            "Invalid wildcard pattern. The matched value is discarded, which \
+                             requires the '{}' ability",
+                            Ability_::Drop
+                        )),
+                        subject_inner.clone(),
+                        Ability_::Drop,
+                    );
+                }
+                (UniqueMap::new(), T::MatchPattern_::Wildcard)
+            }
+            N::MatchPattern_::Variant(m, n, v, ty_args_opt, nfields) => match_variant_pattern(
+                context,
+                ploc,
+                (ref_mut, &subject_inner),
+                (m, n, v),
+                ty_args_opt,
+                nfields,
+            ),
+        };
+        let arm = exp_(context, narm);
+        context.close_locals_scope(old_locals, declared);
+        ty_opt = Some(match ty_opt {
+            None => arm.ty.clone(),
+            Some(ty) => join(
+                context,
+                arm.exp.loc,
+                || "Incompatible match arms",
+                ty,
+                arm.ty.clone(),
+            ),
+        });
+        arms.push(sp(aloc, (sp(ploc, pat_), arm)));
+    }
+    check_match_arms(context, eloc, &arms);
+    let ty = ty_opt.unwrap_or_else(|| context.error_type(eloc));
+    (ty, T::UnannotatedExp_::Match(Box::new(subject), arms))
+}
+
+fn match_variant_pattern(
+    context: &mut Context,
+    loc: Loc,
+    (ref_mut, subject_inner): (Option<bool>, &Type),
+    (m, n, v): (ModuleIdent, StructName, VariantName),
+    ty_args_opt: Option<Vec<Type>>,
+    nfields: Fields<N::LValue>,
+) -> (UniqueMap<Var, ()>, T::MatchPattern_) {
+    let (bt, targs) = core::make_struct_type(context, loc, &m, &n, ty_args_opt);
+    subtype(
+        context,
+        loc,
+        || "Invalid match pattern",
+        bt,
+        subject_inner.clone(),
+    );
+    let typed_fields = add_variant_field_types(
+        context,
+        loc,
+        "binding",
+        (&m, &n, &v),
+        targs.clone(),
+        nfields,
+    );
+    let mut seen_locals = UniqueMap::new();
+    let tfields = typed_fields.map(|f, (idx, (fty, nl))| {
+        let nl_ty = match ref_mut {
+            None => fty.clone(),
+            Some(mut_) => {
+                if !matches!(nl.value, N::LValue_::Ignore) {
+                    let msg = format!(
+                        "Invalid binding of field '{}' when matching on a reference. The fields \
+                         of a variant can only be bound when matching on a value, use '_' to \
+                         ignore the field",
+                        f
+                    );
+                    context
+                        .env
+                        .add_diag(diag!(TypeSafety::InvalidEnumUsage, (nl.loc, msg)));
+                }
+                sp(f.loc(), Type_::Ref(mut_, Box::new(fty.clone())))
+            }
+        };
+        let tl = lvalue(context, LValueCase::Bind, &mut seen_locals, nl, nl_ty);
+        (idx, (fty, tl))
+    });
+    if !context.is_current_module(&m) {
+        let msg = format!(
+            "Invalid match on '{}::{}::{}'.\nAll enums can only be deconstructed in the module \
+             in which they are declared",
+            &m, &n, &v,
+        );
+        context
+            .env
+            .add_diag(diag!(TypeSafety::Visibility, (loc, msg)));
+    }
+    (
+        seen_locals,
+        T::MatchPattern_::Variant(m, n, v, targs, tfields),
+    )
+}
+
+/// Checks that the arms of a match cover every variant of the matched enum, and warns about
+/// arms which can never be reached because the arms before them already cover their variant.
+fn check_match_arms(context: &mut Context, loc: Loc, arms: &[T::MatchArm]) {
+    let mut enum_opt = None;
+    let mut covered: BTreeSet<Symbol> = BTreeSet::new();
+    let mut has_wildcard = false;
+    for sp!(_, (sp!(ploc, pat_), _)) in arms {
+        let is_new = match pat_ {
+            T::MatchPattern_::Wildcard => !std::mem::replace(&mut has_wildcard, true),
+            T::MatchPattern_::Variant(m, n, v, _, _) => {
+                enum_opt.get_or_insert((*m, *n));
+                !has_wildcard && covered.insert(v.value())
+            }
+        };
+        if !is_new {
+            context.env.add_diag(diag!(
+                TypeSafety::UnreachableMatchArm,
+                (
+                    *ploc,
+                    "Unreachable match arm. The arms before it already match every value it \
+                     matches"
+                ),
+            ));
+        }
+    }
+    if has_wildcard {
+        return;
+    }
+    let missing = match enum_opt {
+        None => vec![],
+        Some((m, n)) => match context.struct_variants(&m, &n) {
+            Some(vs) => vs
+                .key_cloned_iter()
+                .filter(|(v, _)| !covered.contains(&v.value()))
+                .map(|(v, _)| format!("'{}::{}'", n, v))
+                .collect(),
+            // Reported when typing the pattern
+            None => return,
+        },
+    };
+    if enum_opt.is_none() || !missing.is_empty() {
+        let msg = if missing.is_empty() {
+            "Invalid match. A match must have at least one arm".to_string()
+        } else {
+            format!(
+                "Invalid match. Missing arms for the variants {}. Add the arms or a wildcard \
+                 '_' arm",
+                format_comma(missing)
+            )
+        };
+        context
+            .env
+            .add_diag(diag!(TypeSafety::NonExhaustiveMatch, (loc, msg)));
+    }
+}
+
 fn loop_body(
     context: &mut Context,
     eloc: Loc,
@@ -1846,8 +2167,19 @@ fn add_field_types<T>(
     fields: Fields<T>,
 ) -> Fields<(Type, T)> {
     let maybe_fields_ty = core::make_field_types(context, loc, m, n, targs);
-    let mut fields_ty = match maybe_fields_ty {
+    let fields_ty = match maybe_fields_ty {
         N::StructFields::Defined(m) => m,
+        N::StructFields::Variants(_) => {
+            let msg = format!(
+                "Invalid {} usage for enum '{}::{}'. Enums are constructed and deconstructed \
+                 through their variants, e.g. '{}::Variant {{ .. }}'",
+                verb, m, n, n
+            );
+            context
+                .env
+                .add_diag(diag!(TypeSafety::InvalidEnumUsage, (loc, msg)));
+            return fields.map(|f, (idx, x)| (idx, (context.error_type(f.loc()), x)));
+        }
         N::StructFields::Native(nloc) => {
             let msg = format!(
                 "Invalid {} usage for native struct '{}::{}'. Native structs cannot be directly \
@@ -1862,9 +2194,63 @@ fn add_field_types<T>(
             return fields.map(|f, (idx, x)| (idx, (context.error_type(f.loc()), x)));
         }
     };
+    match_field_types(
+        context,
+        loc,
+        verb,
+        &format!("{}::{}", m, n),
+        fields_ty,
+        fields,
+    )
+}
+
+fn add_variant_field_types<T>(
+    context: &mut Context,
+    loc: Loc,
+    verb: &str,
+    (m, n, v): (&ModuleIdent, &StructName, &VariantName),
+    targs: Vec<Type>,
+    fields: Fields<T>,
+) -> Fields<(Type, T)> {
+    let maybe_fields_ty = match core::make_field_types(context, loc, m, n, targs) {
+        N::StructFields::Variants(mut vs) => vs.remove(v).map(|(_, fields_ty)| fields_ty),
+        N::StructFields::Defined(_) | N::StructFields::Native(_) => {
+            let msg = format!(
+                "Invalid {} usage for variant '{}'. '{}::{}' is not an enum",
+                verb, v, m, n
+            );
+            context
+                .env
+                .add_diag(diag!(TypeSafety::InvalidEnumUsage, (loc, msg)));
+            return fields.map(|f, (idx, x)| (idx, (context.error_type(f.loc()), x)));
+        }
+    };
+    match maybe_fields_ty {
+        Some(fields_ty) => {
+            let name = format!("{}::{}::{}", m, n, v);
+            match_field_types(context, loc, verb, &name, fields_ty, fields)
+        }
+        None => {
+            context.env.add_diag(diag!(
+                NameResolution::UnboundVariant,
+                (loc, format!("Unbound variant '{}' in '{}::{}'", v, m, n)),
+            ));
+            fields.map(|f, (idx, x)| (idx, (context.error_type(f.loc()), x)))
+        }
+    }
+}
+
+fn match_field_types<T>(
+    context: &mut Context,
+    loc: Loc,
+    verb: &str,
+    name: &str,
+    mut fields_ty: Fields<Type>,
+    fields: Fields<T>,
+) -> Fields<(Type, T)> {
     for (_, f_, _) in &fields_ty {
         if fields.get_(f_).is_none() {
-            let msg = format!("Missing {} for field '{}' in '{}::{}'", verb, f_, m, n);
+            let msg = format!("Missing {} for field '{}' in '{}'", verb, f_, name);
             context
                 .env
                 .add_diag(diag!(TypeSafety::TooFewArguments, (loc, msg)))
@@ -1875,7 +2261,7 @@ fn add_field_types<T>(
             None => {
                 context.env.add_diag(diag!(
                     NameResolution::UnboundField,
-                    (loc, format!("Unbound field '{}' in '{}::{}'", &f, m, n))
+                    (loc, format!("Unbound field '{}' in '{}'", &f, name))
                 ));
                 context.error_type(f.loc())
             }
@@ -2042,10 +2428,57 @@ fn module_call(
     f: FunctionName,
     ty_args_opt: Option<Vec<Type>>,
     argloc: Loc,
-    args: Vec<T::Exp>,
+    nargs: Vec<N::Exp>,
 ) -> (Type, T::UnannotatedExp_) {
+    use N::Exp_ as NE;
+    let inline = context.is_inline_function(&m, &f);
+    // The lambdas passed to an inline function are typed last, once the types of the parameters
+    // they are passed for are known
+    let args = nargs
+        .into_iter()
+        .map(|narg| match narg {
+            sp!(_, NE::Lambda(_, _)) if inline => Err(narg),
+            narg if inline => Ok(inline_call_arg(context, narg)),
+            narg => Ok(exp_(context, narg)),
+        })
+        .collect::<Vec<_>>();
     let (_, ty_args, parameters, acquires, ret_ty) =
         core::make_function_type(context, loc, &m, &f, ty_args_opt);
+    let arg_msg = |param: &Var| {
+        format!(
+            "Invalid call of '{}::{}'. Invalid argument for parameter '{}'",
+            &m, &f, param
+        )
+    };
+    // The other arguments are checked before typing the lambdas, so that the parameters of the
+    // lambdas get the types instantiated by the arguments
+    let mut checked = vec![false; parameters.len()];
+    if inline && args.len() == parameters.len() {
+        for (idx, (arg, (param, param_ty))) in args.iter().zip(&parameters).enumerate() {
+            if let Ok(e) = arg {
+                subtype(
+                    context,
+                    loc,
+                    || arg_msg(param),
+                    e.ty.clone(),
+                    param_ty.clone(),
+                );
+                checked[idx] = true;
+            }
+        }
+    }
+    let args = args
+        .into_iter()
+        .enumerate()
+        .map(|(idx, arg)| match arg {
+            Ok(e) => e,
+            Err(sp!(lloc, NE::Lambda(nbinds, nbody))) => {
+                let expected_ty = parameters.get(idx).map(|(_, ty)| ty.clone());
+                lambda(context, lloc, nbinds, *nbody, expected_ty)
+            }
+            Err(_) => unreachable!(),
+        })
+        .collect();
     let (arguments, arg_tys) = call_args(
         context,
         loc,
@@ -2055,14 +2488,11 @@ fn module_call(
         args,
     );
     assert!(arg_tys.len() == parameters.len());
-    for (arg_ty, (param, param_ty)) in arg_tys.into_iter().zip(parameters.clone()) {
-        let msg = || {
-            format!(
-                "Invalid call of '{}::{}'. Invalid argument for parameter '{}'",
-                &m, &f, param
-            )
-        };
-        subtype(context, loc, msg, arg_ty, param_ty);
+    let params = arg_tys.into_iter().zip(parameters.clone()).zip(checked);
+    for ((arg_ty, (param, param_ty)), checked) in params {
+        if !checked {
+            subtype(context, loc, || arg_msg(&param), arg_ty, param_ty);
+        }
     }
     let params_ty_list = parameters.into_iter().map(|(_, ty)| ty).collect();
     let call = T::ModuleCall {
@@ -2076,6 +2506,96 @@ fn module_call(
     (ret_ty, T::UnannotatedExp_::ModuleCall(Box::new(call)))
 }
 
+// A lambda parameter of an inline function can be passed on to another inline function
+fn inline_call_arg(context: &mut Context, narg: N::Exp) -> T::Exp {
+    use N::Exp_ as NE;
+    use T::UnannotatedExp_ as TE;
+    if let NE::Move(var) | NE::Copy(var) | NE::Use(var) = &narg.value {
+        if let Some(ty @ sp!(_, Type_::Fun(_, _))) = context.get_local_(var) {
+            return T::exp(ty, sp(narg.loc, TE::Use(*var)));
+        }
+    }
+    exp_(context, narg)
+}
+
+// The parameters of the lambda get the argument types of the function type it is expected to
+// have. They are inferred from the body if that type is unknown
+fn lambda(
+    context: &mut Context,
+    loc: Loc,
+    nbinds: N::LValueList,
+    nbody: N::Exp,
+    expected_ty: Option<Type>,
+) -> T::Exp {
+    use T::UnannotatedExp_ as TE;
+    let arity = nbinds.value.len();
+    let param_tys = match expected_ty.map(|ty| core::unfold_type(&context.subst, ty)) {
+        Some(sp!(_, Type_::Fun(param_tys, _))) if param_tys.len() == arity => param_tys,
+        _ => nbinds
+            .value
+            .iter()
+            .map(|sp!(bloc, _)| core::make_tvar(context, *bloc))
+            .collect(),
+    };
+    let bind_ty = match arity {
+        0 => sp(nbinds.loc, Type_::Unit),
+        1 => param_tys[0].clone(),
+        _ => Type_::multiple(nbinds.loc, param_tys.clone()),
+    };
+    let old_locals = context.save_locals_scope();
+    let (declared, binds) = bind_list(context, nbinds, Some(bind_ty));
+    let bind_tys = lvalues_expected_types(context, &binds);
+    let old_loop_info = context.enter_lambda();
+    let body = exp_(context, nbody);
+    context.exit_lambda(old_loop_info);
+    context.close_locals_scope(old_locals, declared);
+    let ty = sp(loc, Type_::Fun(param_tys, Box::new(body.ty.clone())));
+    T::exp(ty, sp(loc, TE::Lambda(binds, bind_tys, Box::new(body))))
+}
+
+fn var_call(
+    context: &mut Context,
+    loc: Loc,
+    var: Var,
+    argloc: Loc,
+    args: Vec<T::Exp>,
+) -> (Type, T::UnannotatedExp_) {
+    let ty = context.get_local(loc, "call", &var);
+    let (param_tys, ret_ty) = match core::unfold_type(&context.subst, ty) {
+        sp!(_, Type_::Fun(param_tys, ret_ty)) => (param_tys, *ret_ty),
+        sp!(_, Type_::UnresolvedError) => {
+            assert!(context.env.has_diags());
+            return (context.error_type(loc), T::UnannotatedExp_::UnresolvedError);
+        }
+        ty => {
+            let msg = format!("Invalid call of '{}'. It is not a lambda parameter", var);
+            let tmsg = format!("Found type: {}", core::error_format(&ty, &context.subst));
+            context
+                .env
+                .add_diag(diag!(TypeSafety::InvalidLambda, (loc, msg), (ty.loc, tmsg)));
+            return (context.error_type(loc), T::UnannotatedExp_::UnresolvedError);
+        }
+    };
+    let (arguments, arg_tys) = call_args(
+        context,
+        loc,
+        || format!("Invalid call of '{}'", &var),
+        param_tys.len(),
+        argloc,
+        args,
+    );
+    for (idx, (arg_ty, param_ty)) in arg_tys.into_iter().zip(param_tys).enumerate() {
+        let msg = || {
+            format!(
+                "Invalid call of '{}'. Invalid argument at position {}",
+                &var, idx
+            )
+        };
+        subtype(context, loc, msg, arg_ty, param_ty);
+    }
+    (ret_ty, T::UnannotatedExp_::VarCall(var, arguments))
+}
+
 fn builtin_call(
     context: &mut Context,
     loc: Loc,
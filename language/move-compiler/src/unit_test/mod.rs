@@ -7,8 +7,10 @@ use crate::{
     shared::NumericalAddress,
 };
 use move_core_types::{
-    account_address::AccountAddress, identifier::Identifier, language_storage::ModuleId,
-    value::MoveValue,
+    account_address::AccountAddress,
+    identifier::Identifier,
+    language_storage::ModuleId,
+    value::{MoveTypeLayout, MoveValue},
 };
 use std::collections::BTreeMap;
 
@@ -33,10 +35,22 @@ pub struct ModuleTestPlan {
 #[derive(Debug, Clone)]
 pub struct TestCase {
     pub test_name: TestName,
+    // The function to run. Differs from `test_name` for each case of a #[test_cases(...)] test
+    pub function_name: String,
     pub arguments: Vec<MoveValue>,
+    // Set for a #[random_test], whose arguments are generated by the test runner on each run
+    pub random_arguments: Option<Vec<TestArgument>>,
     pub expected_failure: Option<ExpectedFailure>,
 }
 
+#[derive(Debug, Clone)]
+pub enum TestArgument {
+    // A value assigned in the test attribute
+    Value(MoveValue),
+    // A randomly generated value for the named parameter
+    Random(String, MoveTypeLayout),
+}
+
 #[derive(Debug, Clone)]
 pub enum ExpectedFailure {
     // expected failure, but abort code not checked
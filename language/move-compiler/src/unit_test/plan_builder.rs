@@ -5,16 +5,23 @@ use crate::{
     cfgir::ast as G,
     diag,
     expansion::ast::{self as E, Address, ModuleIdent, ModuleIdent_},
+    hlir::ast as H,
+    naming::ast::BuiltinTypeName_,
+    parser::ast::Var,
     shared::{
         known_attributes::{KnownAttribute, TestingAttribute},
         CompilationEnv, Identifier, NumericalAddress,
     },
-    unit_test::{ExpectedFailure, ModuleTestPlan, TestCase},
+    unit_test::{ExpectedFailure, ModuleTestPlan, TestArgument, TestCase, TestName},
+};
+use move_core_types::{
+    account_address::AccountAddress as MoveAddress,
+    u256::U256,
+    value::{MoveTypeLayout, MoveValue},
 };
-use move_core_types::{account_address::AccountAddress as MoveAddress, value::MoveValue};
 use move_ir_types::location::Loc;
 use move_symbol_pool::Symbol;
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, convert::TryFrom};
 
 struct Context<'env> {
     env: &'env mut CompilationEnv,
@@ -69,10 +76,7 @@ fn construct_module_test_plan(
     let tests: BTreeMap<_, _> = module
         .functions
         .iter()
-        .filter_map(|(loc, fn_name, func)| {
-            build_test_info(context, loc, fn_name, func)
-                .map(|test_case| (fn_name.to_string(), test_case))
-        })
+        .flat_map(|(loc, fn_name, func)| build_test_info(context, loc, fn_name, func))
         .collect();
 
     if tests.is_empty() {
@@ -89,7 +93,7 @@ fn build_test_info<'func>(
     fn_loc: Loc,
     fn_name: &str,
     function: &'func G::Function,
-) -> Option<TestCase> {
+) -> Vec<(TestName, TestCase)> {
     let get_attrs = |attr: TestingAttribute| -> Option<&'func E::Attribute> {
         function
             .attributes
@@ -102,13 +106,30 @@ fn build_test_info<'func>(
     let test_attribute_opt = get_attrs(TestingAttribute::Test);
     let abort_attribute_opt = get_attrs(TestingAttribute::ExpectedFailure);
     let test_only_attribute_opt = get_attrs(TestingAttribute::TestOnly);
+    let test_cases_attribute_opt = get_attrs(TestingAttribute::TestCases);
+    let random_test_attribute_opt = get_attrs(TestingAttribute::RandomTest);
+
+    // A function cannot be annotated as both #[test] and #[random_test]
+    if let (Some(test_attribute), Some(random_test_attribute)) =
+        (test_attribute_opt, random_test_attribute_opt)
+    {
+        let msg = "Function annotated as both #[test(...)] and #[random_test(...)]. You need to \
+                   declare it as either one or the other";
+        context.env.add_diag(diag!(
+            Attributes::InvalidUsage,
+            (random_test_attribute.loc, msg),
+            (test_attribute.loc, PREVIOUSLY_ANNOTATED_MSG),
+            (fn_loc, IN_THIS_TEST_MSG),
+        ));
+        return vec![];
+    }
 
-    let test_attribute = match test_attribute_opt {
+    let test_attribute = match test_attribute_opt.or(random_test_attribute_opt) {
         None => {
             // expected failures cannot be annotated on non-#[test] functions
             if let Some(abort_attribute) = abort_attribute_opt {
-                let fn_msg = "Only functions defined as a test with #[test] can also have an \
-                              #[expected_failure] attribute";
+                let fn_msg = "Only functions defined as a test with #[test] or #[random_test] can \
+                              also have an #[expected_failure] attribute";
                 let abort_msg = "Attributed as #[expected_failure] here";
                 context.env.add_diag(diag!(
                     Attributes::InvalidUsage,
@@ -116,7 +137,18 @@ fn build_test_info<'func>(
                     (abort_attribute.loc, abort_msg),
                 ))
             }
-            return None;
+            // neither can test cases
+            if let Some(test_cases_attribute) = test_cases_attribute_opt {
+                let fn_msg = "Only functions defined as a test with #[test] can also have a \
+                              #[test_cases(...)] attribute";
+                let test_cases_msg = "Attributed as #[test_cases(...)] here";
+                context.env.add_diag(diag!(
+                    Attributes::InvalidUsage,
+                    (fn_loc, fn_msg),
+                    (test_cases_attribute.loc, test_cases_msg),
+                ))
+            }
+            return vec![];
         }
         Some(test_attribute) => test_attribute,
     };
@@ -133,45 +165,194 @@ fn build_test_info<'func>(
         ))
     }
 
-    let test_annotation_params = parse_test_attribute(context, test_attribute, 0);
-    let mut arguments = Vec::new();
-    for (var, _) in &function.signature.parameters {
-        match test_annotation_params.get(&var.value()) {
-            Some(value) => arguments.push(value.clone()),
-            None => {
-                let missing_param_msg = "Missing test parameter assignment in test. Expected a \
-                                         parameter to be assigned in this attribute";
-                context.env.add_diag(diag!(
-                    Attributes::InvalidTest,
-                    (test_attribute.loc, missing_param_msg),
-                    (var.loc(), "Corresponding to this parameter"),
-                    (fn_loc, IN_THIS_TEST_MSG),
-                ))
+    let expected_failure = match abort_attribute_opt {
+        None => None,
+        Some(abort_attribute) => parse_failure_attribute(context, abort_attribute),
+    };
+
+    let parameters = &function.signature.parameters;
+    // Arguments assigned in #[test(...)] are shared by all test cases
+    let shared_assignments = parse_test_attribute(context, test_attribute, 0);
+    let shared_arguments = convert_test_arguments(context, parameters, shared_assignments);
+
+    // The parameters of a #[random_test] that are not assigned are generated by the test runner
+    if random_test_attribute_opt.is_some() {
+        if let Some(test_cases_attribute) = test_cases_attribute_opt {
+            let msg = "A function annotated as #[random_test(...)] cannot also have a \
+                       #[test_cases(...)] attribute";
+            context.env.add_diag(diag!(
+                Attributes::InvalidUsage,
+                (test_cases_attribute.loc, msg),
+                (test_attribute.loc, PREVIOUSLY_ANNOTATED_MSG),
+                (fn_loc, IN_THIS_TEST_MSG),
+            ))
+        }
+        let mut random_arguments = Vec::new();
+        for (var, ty) in parameters {
+            match shared_arguments.get(&var.value()) {
+                Some((_, Some(value))) => random_arguments.push(TestArgument::Value(value.clone())),
+                // An invalid value was assigned, which has already been reported
+                Some((_, None)) => (),
+                None => match random_argument_layout(ty) {
+                    Some(layout) => {
+                        random_arguments.push(TestArgument::Random(var.value().to_string(), layout))
+                    }
+                    None => {
+                        let msg = "Unable to generate random values for this parameter. Only \
+                                   values of primitive types and vectors of them can be \
+                                   generated, other parameters must be assigned in the \
+                                   #[random_test(...)] attribute";
+                        context.env.add_diag(diag!(
+                            Attributes::InvalidTest,
+                            (var.loc(), msg),
+                            (ty.loc, "Unsupported type for a random argument"),
+                            (fn_loc, IN_THIS_TEST_MSG),
+                        ))
+                    }
+                },
             }
         }
+        let test_case = TestCase {
+            test_name: fn_name.to_string(),
+            function_name: fn_name.to_string(),
+            arguments: vec![],
+            random_arguments: Some(random_arguments),
+            expected_failure,
+        };
+        return vec![(fn_name.to_string(), test_case)];
     }
+    let cases = match test_cases_attribute_opt {
+        None => vec![(fn_name.to_string(), test_attribute.loc, shared_arguments)],
+        Some(test_cases_attribute) => parse_test_cases_attribute(context, test_cases_attribute)
+            .into_iter()
+            .map(|(case_name, case_loc, assignments)| {
+                let mut case_arguments = convert_test_arguments(context, parameters, assignments);
+                for (name, (shared_loc, value)) in &shared_arguments {
+                    if let Some((case_assign_loc, _)) = case_arguments.get(name) {
+                        let msg = format!(
+                            "Test parameter '{}' is assigned both in #[test(...)] and in this \
+                             test case",
+                            name
+                        );
+                        context.env.add_diag(diag!(
+                            Attributes::InvalidTest,
+                            (*case_assign_loc, msg),
+                            (*shared_loc, PREVIOUSLY_ANNOTATED_MSG),
+                        ));
+                    } else {
+                        case_arguments.insert(*name, (*shared_loc, value.clone()));
+                    }
+                }
+                (
+                    format!("{}[{}]", fn_name, case_name),
+                    case_loc,
+                    case_arguments,
+                )
+            })
+            .collect(),
+    };
 
-    let expected_failure = match abort_attribute_opt {
-        None => None,
-        Some(abort_attribute) => parse_failure_attribute(context, abort_attribute),
+    let mut tests = vec![];
+    for (test_name, case_loc, case_arguments) in cases {
+        let mut arguments = Vec::new();
+        for (var, _) in parameters {
+            match case_arguments.get(&var.value()) {
+                Some((_, Some(value))) => arguments.push(value.clone()),
+                // An invalid value was assigned, which has already been reported
+                Some((_, None)) => (),
+                None => {
+                    let missing_param_msg = "Missing test parameter assignment in test. Expected \
+                                             a parameter to be assigned in this attribute";
+                    context.env.add_diag(diag!(
+                        Attributes::InvalidTest,
+                        (case_loc, missing_param_msg),
+                        (var.loc(), "Corresponding to this parameter"),
+                        (fn_loc, IN_THIS_TEST_MSG),
+                    ))
+                }
+            }
+        }
+        let test_case = TestCase {
+            test_name: test_name.clone(),
+            function_name: fn_name.to_string(),
+            arguments,
+            random_arguments: None,
+            expected_failure: expected_failure.clone(),
+        };
+        tests.push((test_name, test_case))
+    }
+    tests
+}
+
+// Returns the layout of the values generated for a #[random_test] parameter of type `ty`, or `None`
+// if values of that type cannot be generated
+fn random_argument_layout(sp!(_, ty): &H::SingleType) -> Option<MoveTypeLayout> {
+    let base_ty = match ty {
+        H::SingleType_::Base(b) | H::SingleType_::Ref(_, b) => b,
     };
+    base_type_layout(base_ty)
+}
 
-    Some(TestCase {
-        test_name: fn_name.to_string(),
-        arguments,
-        expected_failure,
+fn base_type_layout(sp!(_, ty): &H::BaseType) -> Option<MoveTypeLayout> {
+    use BuiltinTypeName_ as B;
+    let (builtin, ty_args) = match ty {
+        H::BaseType_::Apply(_, sp!(_, H::TypeName_::Builtin(sp!(_, b))), ty_args) => (b, ty_args),
+        _ => return None,
+    };
+    Some(match builtin {
+        B::Address => MoveTypeLayout::Address,
+        B::Bool => MoveTypeLayout::Bool,
+        B::U8 => MoveTypeLayout::U8,
+        B::U16 => MoveTypeLayout::U16,
+        B::U32 => MoveTypeLayout::U32,
+        B::U64 => MoveTypeLayout::U64,
+        B::U128 => MoveTypeLayout::U128,
+        B::U256 => MoveTypeLayout::U256,
+        B::Vector => MoveTypeLayout::Vector(Box::new(base_type_layout(ty_args.first()?)?)),
+        // Signers cannot be made up, they must be assigned
+        B::Signer => return None,
     })
 }
 
+// Converts the values assigned to the parameters of a test into Move values of the parameters'
+// types. Invalid values are reported and mapped to `None`.
+fn convert_test_arguments(
+    context: &mut Context,
+    parameters: &[(Var, H::SingleType)],
+    assignments: BTreeMap<Symbol, (Loc, &E::AttributeValue)>,
+) -> BTreeMap<Symbol, (Loc, Option<MoveValue>)> {
+    let mut arguments = BTreeMap::new();
+    for (var, ty) in parameters {
+        let (assign_loc, value) = match assignments.get(&var.value()) {
+            Some(assignment) => *assignment,
+            None => continue,
+        };
+        let base_ty = match &ty.value {
+            H::SingleType_::Base(b) | H::SingleType_::Ref(_, b) => b,
+        };
+        let move_value = convert_attribute_value_to_move_value(context, value, base_ty);
+        if move_value.is_none() {
+            context.env.add_diag(diag!(
+                Attributes::InvalidValue,
+                (value.loc, "Unsupported attribute value"),
+                (assign_loc, "Assigned in this attribute"),
+                (ty.loc, "Expected a value of this parameter's type"),
+            ));
+        }
+        arguments.insert(var.value(), (assign_loc, move_value));
+    }
+    arguments
+}
+
 //***************************************************************************
 // Attribute parsers
 //***************************************************************************
 
-fn parse_test_attribute(
+fn parse_test_attribute<'a>(
     context: &mut Context,
-    sp!(aloc, test_attribute): &E::Attribute,
+    sp!(aloc, test_attribute): &'a E::Attribute,
     depth: usize,
-) -> BTreeMap<Symbol, MoveValue> {
+) -> BTreeMap<Symbol, (Loc, &'a E::AttributeValue)> {
     use E::Attribute_ as EA;
 
     match test_attribute {
@@ -184,7 +365,10 @@ fn parse_test_attribute(
         }
         EA::Name(nm) => {
             assert!(
-                nm.value.as_str() == TestingAttribute::Test.name() && depth == 0,
+                matches!(
+                    nm.value.as_str(),
+                    TestingAttribute::TEST | TestingAttribute::RANDOM_TEST
+                ) && depth == 0,
                 "ICE: We should only be parsing a raw test attribute"
             );
             BTreeMap::new()
@@ -197,26 +381,16 @@ fn parse_test_attribute(
                 ));
                 return BTreeMap::new();
             }
-            let sp!(assign_loc, attr_value) = &**attr_value;
-            let value = match convert_attribute_value_to_move_value(context, attr_value) {
-                Some(move_value) => move_value,
-                None => {
-                    context.env.add_diag(diag!(
-                        Attributes::InvalidValue,
-                        (*assign_loc, "Unsupported attribute value"),
-                        (*aloc, "Assigned in this attribute"),
-                    ));
-                    return BTreeMap::new();
-                }
-            };
-
             let mut args = BTreeMap::new();
-            args.insert(nm.value, value);
+            args.insert(nm.value, (*aloc, &**attr_value));
             args
         }
         EA::Parameterized(nm, attributes) => {
             assert!(
-                nm.value.as_str() == TestingAttribute::Test.name() && depth == 0,
+                matches!(
+                    nm.value.as_str(),
+                    TestingAttribute::TEST | TestingAttribute::RANDOM_TEST
+                ) && depth == 0,
                 "ICE: We should only be parsing a raw test attribute"
             );
             attributes
@@ -227,6 +401,52 @@ fn parse_test_attribute(
     }
 }
 
+// Parses the cases of a #[test_cases(...)] attribute, returning the name, location and parameter
+// assignments of each case
+fn parse_test_cases_attribute<'a>(
+    context: &mut Context,
+    sp!(aloc, test_cases_attribute): &'a E::Attribute,
+) -> Vec<(Symbol, Loc, BTreeMap<Symbol, (Loc, &'a E::AttributeValue)>)> {
+    use E::Attribute_ as EA;
+
+    let expected_msg = format!(
+        "Expected a list of named test cases, e.g. #[{}(case_name(x = 1))]",
+        TestingAttribute::TEST_CASES
+    );
+    let cases = match test_cases_attribute {
+        EA::Parameterized(_, cases) if !cases.is_empty() => cases,
+        _ => {
+            context.env.add_diag(diag!(
+                Attributes::InvalidTest,
+                (
+                    *aloc,
+                    format!("Invalid #[test_cases(...)] attribute. {}", expected_msg)
+                ),
+            ));
+            return vec![];
+        }
+    };
+    let mut parsed = vec![];
+    for (_, _, case) in cases {
+        match case {
+            sp!(case_loc, EA::Name(nm)) => parsed.push((nm.value, *case_loc, BTreeMap::new())),
+            sp!(case_loc, EA::Parameterized(nm, assignments)) => {
+                let assignments = assignments
+                    .iter()
+                    .flat_map(|(_, _, attr)| parse_test_attribute(context, attr, 1))
+                    .collect();
+                parsed.push((nm.value, *case_loc, assignments))
+            }
+            sp!(case_loc, EA::Assigned(_, _)) => context.env.add_diag(diag!(
+                Attributes::InvalidTest,
+                (*case_loc, "Invalid test case"),
+                (*aloc, expected_msg.clone()),
+            )),
+        }
+    }
+    parsed
+}
+
 fn parse_failure_attribute(
     context: &mut Context,
     sp!(aloc, expected_attr): &E::Attribute,
@@ -277,15 +497,20 @@ fn parse_failure_attribute(
                 {
                     match &**value {
                         sp!(_, EAV::Value(sp!(_, EV::InferredNum(u))))
-                            if *u <= std::u64::MAX as u128 =>
+                            if *u <= U256::from(std::u64::MAX) =>
                         {
-                            Some(ExpectedFailure::ExpectedWithCode(*u as u64))
+                            Some(ExpectedFailure::ExpectedWithCode(
+                                u64::try_from(*u).unwrap(),
+                            ))
                         }
                         sp!(_, EAV::Value(sp!(_, EV::U64(u)))) => {
                             Some(ExpectedFailure::ExpectedWithCode(*u))
                         }
                         sp!(vloc, EAV::Value(sp!(_, EV::U8(_))))
-                        | sp!(vloc, EAV::Value(sp!(_, EV::U128(_)))) => {
+                        | sp!(vloc, EAV::Value(sp!(_, EV::U16(_))))
+                        | sp!(vloc, EAV::Value(sp!(_, EV::U32(_))))
+                        | sp!(vloc, EAV::Value(sp!(_, EV::U128(_))))
+                        | sp!(vloc, EAV::Value(sp!(_, EV::U256(_)))) => {
                             let msg = "Invalid value in expected failure code assignment";
                             context.env.add_diag(diag!(
                                 Attributes::InvalidValue,
@@ -333,14 +558,56 @@ fn parse_failure_attribute(
 
 fn convert_attribute_value_to_move_value(
     context: &mut Context,
-    value: &E::AttributeValue_,
+    sp!(_, value): &E::AttributeValue,
+    sp!(_, ty): &H::BaseType,
 ) -> Option<MoveValue> {
+    use BuiltinTypeName_ as B;
     use E::{AttributeValue_ as EAV, Value_ as EV};
-    match value {
-        // Only addresses are allowed
-        EAV::Value(sp!(_, EV::Address(a))) => Some(MoveValue::Address(MoveAddress::new(
-            context.resolve_address(a).into_bytes(),
-        ))),
-        _ => None,
-    }
+    let (builtin, ty_args) = match ty {
+        H::BaseType_::Apply(_, sp!(_, H::TypeName_::Builtin(sp!(_, b))), ty_args) => (b, ty_args),
+        // Only values of builtin types are allowed
+        _ => return None,
+    };
+    Some(match (builtin, value) {
+        // Signers are passed as the address of the signer
+        (B::Address, EAV::Value(sp!(_, EV::Address(a))))
+        | (B::Signer, EAV::Value(sp!(_, EV::Address(a)))) => {
+            MoveValue::Address(MoveAddress::new(context.resolve_address(a).into_bytes()))
+        }
+        (B::Bool, EAV::Value(sp!(_, EV::Bool(b)))) => MoveValue::Bool(*b),
+        (B::U8, EAV::Value(sp!(_, EV::U8(u)))) => MoveValue::U8(*u),
+        (B::U16, EAV::Value(sp!(_, EV::U16(u)))) => MoveValue::U16(*u),
+        (B::U32, EAV::Value(sp!(_, EV::U32(u)))) => MoveValue::U32(*u),
+        (B::U64, EAV::Value(sp!(_, EV::U64(u)))) => MoveValue::U64(*u),
+        (B::U128, EAV::Value(sp!(_, EV::U128(u)))) => MoveValue::U128(*u),
+        (B::U256, EAV::Value(sp!(_, EV::U256(u)))) => MoveValue::U256(*u),
+        (B::U8, EAV::Value(sp!(_, EV::InferredNum(u)))) => MoveValue::U8(u8::try_from(*u).ok()?),
+        (B::U16, EAV::Value(sp!(_, EV::InferredNum(u)))) => MoveValue::U16(u16::try_from(*u).ok()?),
+        (B::U32, EAV::Value(sp!(_, EV::InferredNum(u)))) => MoveValue::U32(u32::try_from(*u).ok()?),
+        (B::U64, EAV::Value(sp!(_, EV::InferredNum(u)))) => MoveValue::U64(u64::try_from(*u).ok()?),
+        (B::U128, EAV::Value(sp!(_, EV::InferredNum(u)))) => {
+            MoveValue::U128(u128::try_from(*u).ok()?)
+        }
+        (B::U256, EAV::Value(sp!(_, EV::InferredNum(u)))) => MoveValue::U256(*u),
+        (B::Vector, EAV::Vector(values)) => {
+            let elem_ty = ty_args.first()?;
+            MoveValue::Vector(
+                values
+                    .iter()
+                    .map(|v| convert_attribute_value_to_move_value(context, v, elem_ty))
+                    .collect::<Option<_>>()?,
+            )
+        }
+        // Byte strings can be used for vector<u8> values
+        (B::Vector, EAV::Value(sp!(_, EV::Bytearray(bytes)))) => match ty_args.first() {
+            Some(
+                sp!(
+                    _,
+                    H::BaseType_::Apply(_, sp!(_, H::TypeName_::Builtin(sp!(_, B::U8))), _)
+                ),
+            ) => MoveValue::Vector(bytes.iter().map(|b| MoveValue::U8(*b)).collect()),
+            _ => return None,
+        },
+        _ => return None,
+    })
 }
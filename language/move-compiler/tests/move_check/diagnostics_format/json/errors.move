@@ -0,0 +1,3 @@
+module 0x42::M {
+    fun mismatch(): u64 { false }
+}
@@ -0,0 +1,7 @@
+module 0x42::M {
+    enum E {
+        A,
+        A,
+        B { x: u64 },
+    }
+}
@@ -0,0 +1,23 @@
+address 0x2 {
+
+#[deprecated(note)]
+module X {
+    #[deprecated = b"note"]
+    fun f() {}
+
+    #[deprecated(note = 0)]
+    struct S {}
+
+    #[deprecated(note = b"note", since = b"0.1")]
+    const C: u64 = 0;
+
+    #[deprecated(notes = b"note")]
+    fun g() {}
+}
+
+}
+
+#[deprecated]
+script {
+    fun main() {}
+}
@@ -0,0 +1,6 @@
+module 0x42::M {
+    fun foo() {
+        0x1FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF;
+        115792089237316195423570985008687907853269984665640564039457584007913129639936;
+    }
+}
@@ -0,0 +1,8 @@
+module 0x42::M {
+    fun long_u16() {
+        0x0FFFFu16;
+        0x1FFFFu16;
+        65535u16;
+        65536u16;
+    }
+}
@@ -0,0 +1,8 @@
+module 0x42::M {
+    fun long_u256() {
+        0x0FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFu256;
+        0x1FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFu256;
+        115792089237316195423570985008687907853269984665640564039457584007913129639935u256;
+        115792089237316195423570985008687907853269984665640564039457584007913129639936u256;
+    }
+}
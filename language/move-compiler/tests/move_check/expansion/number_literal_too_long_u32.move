@@ -0,0 +1,8 @@
+module 0x42::M {
+    fun long_u32() {
+        0x0FFFFFFFFu32;
+        0x1FFFFFFFFu32;
+        4294967295u32;
+        4294967296u32;
+    }
+}
@@ -0,0 +1,24 @@
+#[lint_allow(while_true)]
+module 0x42::M {
+    fun spin() {
+        while (true) {}
+    }
+
+    #[lint_warn(while_true)]
+    fun spin_warn() {
+        while (true) {}
+    }
+
+    #[lint_deny(while_true, self_transfer)]
+    fun spin_deny(x: u64): u64 {
+        while (true) { break };
+        x = x;
+        x
+    }
+
+    #[lint_allow(not_a_lint)]
+    fun unknown() {}
+
+    #[lint_allow]
+    fun missing_list() {}
+}
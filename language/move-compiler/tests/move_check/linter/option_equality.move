@@ -0,0 +1,19 @@
+module 0x42::M {
+    use std::option::{Self, Option};
+
+    fun is_empty(o: Option<u64>): bool {
+        o == option::none()
+    }
+
+    fun is_not(o: &Option<u64>, x: u64): bool {
+        o != &option::some(x)
+    }
+
+    fun is_empty_fixed(o: &Option<u64>): bool {
+        option::is_none(o)
+    }
+
+    fun other_equality(v: vector<u64>): bool {
+        v == vector[]
+    }
+}
@@ -0,0 +1,15 @@
+module 0x42::M {
+    fun deref_borrow(x: u64): u64 {
+        *&x
+    }
+
+    fun borrow_deref(r: &mut u64): u64 {
+        let r2 = &*r;
+        *r2
+    }
+
+    fun no_redundancy(x: &mut u64): u64 {
+        let r = freeze(x);
+        *r
+    }
+}
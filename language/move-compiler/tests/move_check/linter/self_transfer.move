@@ -0,0 +1,22 @@
+module 0x42::M {
+    struct S has drop { f: u64 }
+
+    fun assign_local(x: u64): u64 {
+        x = x;
+        x
+    }
+
+    fun assign_deref(r: &mut u64) {
+        *r = *r;
+    }
+
+    fun assign_field(s: &mut S) {
+        s.f = s.f;
+    }
+
+    fun assign_other(x: u64, y: u64, s: &mut S, r: &u64): u64 {
+        x = y;
+        s.f = *r;
+        x
+    }
+}
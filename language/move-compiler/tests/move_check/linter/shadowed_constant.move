@@ -0,0 +1,18 @@
+module 0x42::M {
+    const MAX_VALUE: u64 = 100;
+
+    const Zero: u64 = 0;
+
+    fun clamp(x: u64): u64 {
+        let max_value = 50;
+        if (x > max_value) max_value else x
+    }
+
+    fun is_zero(zero: u64): bool {
+        zero == Zero
+    }
+
+    fun clamp_fixed(x: u64): u64 {
+        if (x > MAX_VALUE) MAX_VALUE else x
+    }
+}
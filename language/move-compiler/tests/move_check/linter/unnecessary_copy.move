@@ -0,0 +1,26 @@
+module 0x42::M {
+    fun last_use(x: u64): u64 {
+        let y = copy x;
+        y
+    }
+
+    fun used_after(x: u64): u64 {
+        let y = copy x;
+        x + y
+    }
+
+    fun borrowed(x: u64): u64 {
+        let r = &x;
+        let y = copy x;
+        *r + y
+    }
+
+    fun in_loop(x: u64, n: u64): u64 {
+        let sum = 0;
+        while (n > 0) {
+            sum = sum + copy x;
+            n = n - 1;
+        };
+        sum
+    }
+}
@@ -0,0 +1,25 @@
+module 0x42::M {
+    fun count(): u64 {
+        let i = 0;
+        while (true) {
+            i = i + 1;
+            if (i == 10) break
+        };
+        i
+    }
+
+    fun count_loop(): u64 {
+        let i = 0;
+        loop {
+            i = i + 1;
+            if (i == 10) break
+        };
+        i
+    }
+
+    fun count_while(n: u64): u64 {
+        let i = 0;
+        while (i < n) i = i + 1;
+        i
+    }
+}
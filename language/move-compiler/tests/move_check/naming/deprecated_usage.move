@@ -0,0 +1,42 @@
+address 0x2 {
+
+module X {
+    #[deprecated(note = b"use 'g' instead")]
+    public fun f(): u64 { g() + C }
+
+    public fun g(): u64 { 0 }
+
+    #[deprecated]
+    struct S has drop {}
+
+    #[deprecated]
+    public const C: u64 = 0;
+
+    // Uses within the module itself are not reported
+    public fun s(): S { f(); S {} }
+}
+
+#[deprecated(note = b"use 0x2::X instead")]
+module Y {
+    struct T has drop {}
+
+    public fun h(): u64 { 0 }
+}
+
+module M {
+    use 0x2::X;
+    use 0x2::Y;
+
+    fun uses(_s: X::S, _t: Y::T): u64 {
+        X::f() + X::g() + X::C + Y::h()
+    }
+}
+
+}
+
+script {
+    fun main() {
+        0x2::X::f();
+        0x2::X::s();
+    }
+}
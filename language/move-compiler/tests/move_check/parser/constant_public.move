@@ -1,4 +1,6 @@
 module 0x42::M {
-    // visibility modifiers on constants fail during parsing
+    // 'public' and 'public(friend)' constants are allowed, but 'public(script)' fails in parsing
     public const Foo: u64 = 0;
+    public(friend) const Bar: u64 = 0;
+    public(script) const Baz: u64 = 0;
 }
@@ -0,0 +1,4 @@
+module 0x42::M {
+    // native modifiers on enums fail during parsing
+    native enum E { A }
+}
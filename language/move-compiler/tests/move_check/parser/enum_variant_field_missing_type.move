@@ -0,0 +1,3 @@
+module 0x42::M {
+    enum E { A { f } } // Each variant field must specify a type
+}
@@ -0,0 +1,7 @@
+module 0x42::M {
+    enum E has drop { A, B }
+
+    fun f(e: E): u64 {
+        match (e) { E::A 0, E::B => 1 }
+    }
+}
@@ -20,4 +20,18 @@ module 0x8675309::M {
         let _: u64 = (340282366920938463463374607431768211455u128 as u64);
         let _: u128 = (340282366920938463463374607431768211455u128 as u128);
     }
+
+    fun t1(x16: u16, x32: u32, x256: u256) {
+        let _: u16 = (0 as u16);
+        let _: u32 = (0 as u32);
+        let _: u256 = (0 as u256);
+
+        let _: u16 = (x256 as u16);
+        let _: u32 = (x16 as u32);
+        let _: u256 = (x32 as u256);
+
+        let _: u8 = (x16 as u8);
+        let _: u64 = (x32 as u64);
+        let _: u128 = (x256 as u128);
+    }
 }
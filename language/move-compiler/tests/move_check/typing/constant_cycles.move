@@ -0,0 +1,13 @@
+module 0x42::M {
+    const A: u64 = B + 1;
+    const B: u64 = C * 2;
+    const C: u64 = A;
+    const D: u64 = D;
+    const E: u64 = C;
+}
+
+script {
+    const A: u64 = B;
+    const B: u64 = A;
+    fun main() {}
+}
@@ -0,0 +1,31 @@
+address 0x2 {
+
+module X {
+    friend 0x2::M;
+
+    const INTERNAL: u64 = 0;
+    public(friend) const FRIEND: u64 = INTERNAL + 1;
+    public const PUBLIC: u64 = FRIEND + 1;
+
+    public inline fun internal_plus(x: u64): u64 { INTERNAL + x }
+}
+
+module M {
+    use 0x2::X;
+
+    const LOCAL: u64 = X::PUBLIC + X::FRIEND;
+
+    fun foo(): u64 {
+        X::PUBLIC + X::FRIEND + LOCAL + X::internal_plus(1)
+    }
+}
+
+module N {
+    use 0x2::X;
+
+    fun foo(): u64 {
+        X::PUBLIC + X::FRIEND + X::INTERNAL
+    }
+}
+
+}
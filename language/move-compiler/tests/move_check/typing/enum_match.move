@@ -0,0 +1,43 @@
+module 0x42::M {
+    enum Shape has copy, drop {
+        Circle { radius: u64 },
+        Rect { width: u64, height: u64 },
+        Empty,
+    }
+
+    enum Box<T> {
+        Full { value: T },
+        Empty,
+    }
+
+    fun area(s: Shape): u64 {
+        match (s) {
+            Shape::Circle { radius } => 3 * radius * radius,
+            Shape::Rect { width, height } => width * height,
+            Shape::Empty => 0,
+        }
+    }
+
+    fun is_empty(s: &Shape): bool {
+        match (s) {
+            Shape::Empty => true,
+            _ => false,
+        }
+    }
+
+    fun unwrap<T: drop>(b: Box<T>, default: T): T {
+        match (b) {
+            Box::Full { value } => {
+                let _ = default;
+                value
+            },
+            Box::Empty => default,
+        }
+    }
+
+    fun t(): u64 {
+        let s = Shape::Rect { width: 2, height: 3 };
+        if (is_empty(&s)) return 0;
+        area(s) + unwrap(Box::Full { value: 1 }, 0) + area(Shape::Empty)
+    }
+}
@@ -0,0 +1,11 @@
+module 0x42::M {
+    enum E has drop { A, B }
+}
+
+module 0x42::N {
+    use 0x42::M::E;
+
+    fun is_a(e: E): bool {
+        match (e) { E::A => true, _ => false }
+    }
+}
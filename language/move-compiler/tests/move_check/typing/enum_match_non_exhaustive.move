@@ -0,0 +1,11 @@
+module 0x42::M {
+    enum E has drop { A, B { x: u64 }, C }
+
+    fun missing(e: E): u64 {
+        match (e) { E::A => 0, E::B { x } => x }
+    }
+
+    fun unreachable(e: E): u64 {
+        match (e) { E::A => 0, _ => 1, E::C => 2 }
+    }
+}
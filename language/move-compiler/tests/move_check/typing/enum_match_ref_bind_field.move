@@ -0,0 +1,11 @@
+module 0x42::M {
+    enum E has drop { A { x: u64 }, B }
+
+    fun bind_by_ref(e: &E): u64 {
+        match (e) { E::A { x } => *x, E::B => 0 }
+    }
+
+    fun ignore_by_ref(e: &E): bool {
+        match (e) { E::A { x: _ } => true, _ => false }
+    }
+}
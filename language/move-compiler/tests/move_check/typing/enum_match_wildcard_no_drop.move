@@ -0,0 +1,7 @@
+module 0x42::M {
+    enum E { A, B { x: u64 } }
+
+    fun wildcard(e: E): u64 {
+        match (e) { E::A => 0, _ => 1 }
+    }
+}
@@ -0,0 +1,37 @@
+module 0x42::M {
+    enum E has drop { A, B }
+
+    public fun a(): E {
+        E::A
+    }
+
+    public fun is_a(e: E): bool {
+        match (e) {
+            E::A => true,
+            E::B => false,
+        }
+    }
+}
+
+module 0x42::N {
+    use 0x42::M;
+
+    enum F has drop { X { v: u64 }, Y }
+
+    fun value(f: F): u64 {
+        match (f) {
+            F::X { v } => v,
+            F::Y => 0,
+        }
+    }
+
+    public fun f(): u64 {
+        if (M::is_a(M::a())) value(F::X { v: 1 }) else value(F::Y)
+    }
+}
+
+module 0x42::O {
+    public fun g(): u64 {
+        0
+    }
+}
@@ -0,0 +1,44 @@
+module 0x42::M {
+    struct S has copy, drop { x: u64 }
+
+    const TEN: u64 = 10;
+
+    public inline fun apply(x: u64, f: |u64|u64): u64 {
+        f(x)
+    }
+
+    inline fun apply_twice<T>(x: T, f: |T|T): T {
+        f(f(x))
+    }
+
+    inline fun for_each(n: u64, f: |u64|()) {
+        let i = 0;
+        while (i < n) {
+            f(i);
+            i = i + 1;
+        }
+    }
+
+    inline fun x_of(s: &S): &u64 {
+        &s.x
+    }
+
+    public inline fun pass_on(x: u64, f: |u64|u64): u64 {
+        apply(x, f) + TEN
+    }
+
+    public fun test(): u64 {
+        let y = 2;
+        let sum = 0;
+        for_each(10, |i| sum = sum + i * y);
+        let s = apply_twice(S { x: sum }, |s| S { x: apply(s.x, |x| x + 1) });
+        let i = *x_of(&s);
+        pass_on(i, |x| x * i)
+    }
+}
+
+module 0x42::N {
+    public fun test(): u64 {
+        0x42::M::pass_on(1, |x| 0x42::M::apply(x, |y| y + 1))
+    }
+}
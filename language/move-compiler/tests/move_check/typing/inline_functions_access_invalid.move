@@ -0,0 +1,36 @@
+module 0x42::M {
+    struct S has drop { x: u64 }
+
+    public inline fun make(x: u64): S {
+        S { x }
+    }
+
+    public inline fun get(s: &S): u64 {
+        s.x
+    }
+
+    fun secret(): u64 { 0 }
+
+    public inline fun call_secret(): u64 {
+        secret()
+    }
+
+    public inline fun rec(x: u64): u64 {
+        rec(x)
+    }
+
+    public fun test(): u64 {
+        get(&make(1)) + call_secret()
+    }
+}
+
+module 0x42::N {
+    fun test(): u64 {
+        let s = 0x42::M::make(1);
+        0x42::M::get(&s) + 0x42::M::call_secret()
+    }
+
+    fun test_rec(): u64 {
+        0x42::M::rec(1)
+    }
+}
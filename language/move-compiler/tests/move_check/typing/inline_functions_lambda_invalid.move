@@ -0,0 +1,29 @@
+module 0x42::M {
+    inline fun apply(x: u64, f: |u64|u64): u64 {
+        f(x)
+    }
+
+    inline fun ignore(f: |u64|u64): u64 {
+        let _ = f;
+        0
+    }
+
+    inline fun shadowed(f: |u64|u64): u64 {
+        let f: u64 = 1;
+        f(2)
+    }
+
+    inline fun early(x: u64): u64 {
+        if (x == 0) return 1;
+        x
+    }
+
+    fun not_inline(x: u64): u64 {
+        let _ = |y| y + x;
+        x
+    }
+
+    fun return_in_lambda(x: u64): u64 {
+        apply(x, |y| return y)
+    }
+}
@@ -0,0 +1,23 @@
+// Invalid uses of #[random_test]
+module 0x1::M {
+    struct S has drop {}
+
+    // signers cannot be generated
+    #[random_test]
+    fun unassigned_signer(_s: signer, _x: u64) { }
+
+    // neither can structs or vectors of signers
+    #[random_test]
+    fun unsupported_types(_s: S, _v: vector<signer>) { }
+
+    #[test]
+    #[random_test]
+    fun both_test_and_random_test(_x: u64) { }
+
+    #[random_test]
+    #[test_cases(a(_x=1))]
+    fun random_test_cases(_x: u64) { }
+
+    #[random_test(_x=@0x1)]
+    fun invalid_assignment(_x: u64) { }
+}
@@ -0,0 +1,25 @@
+// Invalid uses of #[test_cases(...)]
+module 0x1::M {
+    #[test_cases(a(_x=1))]
+    fun not_a_test(_x: u64) { }
+
+    #[test]
+    #[test_cases]
+    fun no_cases() { }
+
+    #[test]
+    #[test_cases(_x=1)]
+    fun unnamed_case(_x: u64) { }
+
+    #[test(_x=1)]
+    #[test_cases(a(_x=2))]
+    fun assigned_twice(_x: u64) { }
+
+    #[test]
+    #[test_cases(a(_x=1), b(_y=2))]
+    fun missing_param(_x: u64) { }
+
+    #[test]
+    #[test_cases(a(_x=1, nested(_y=2)))]
+    fun nested_case(_x: u64) { }
+}
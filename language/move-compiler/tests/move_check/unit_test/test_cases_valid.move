@@ -0,0 +1,20 @@
+// Make sure that legal usage of parameterized tests and test cases is allowed
+module 0x1::M {
+    #[test(_x=1, _y=2u8, _z=340282366920938463463374607431768211455, _b=false)]
+    fun primitives(_x: u64, _y: u8, _z: u128, _b: bool) { }
+
+    #[test(_xs=vector[1, 2], _ys=vector[], _bytes=b"", _nested=vector[vector[@0x1], vector[]])]
+    fun vectors(_xs: vector<u16>, _ys: vector<u32>, _bytes: vector<u8>, _nested: vector<vector<address>>) { }
+
+    #[test]
+    #[test_cases(a, b)]
+    fun cases_without_parameters() { }
+
+    #[test(_s=@0x1)]
+    #[test_cases(a(_x=1u256), b(_x=0))]
+    #[expected_failure]
+    fun shared_parameters(_s: signer, _x: u256) { }
+
+    #[test, test_cases(a(_x=vector[true]))]
+    fun same_annotation(_x: vector<bool>) { }
+}
@@ -0,0 +1,22 @@
+// Values assigned to test parameters must match the types of the parameters
+module 0x1::M {
+    struct S has drop {}
+
+    #[test(_x=256)]
+    fun out_of_range(_x: u8) { }
+
+    #[test(_x=1u64)]
+    fun wrong_literal_type(_x: u8) { }
+
+    #[test(_b=0)]
+    fun not_a_bool(_b: bool) { }
+
+    #[test(_a=@0x1, _xs=vector[1, @0x2])]
+    fun bad_vector_element(_a: address, _xs: vector<u64>) { }
+
+    #[test(_xs=b"abc")]
+    fun bytes_not_u8(_xs: vector<u64>) { }
+
+    #[test(_s=0)]
+    fun struct_param(_s: S) { }
+}
@@ -0,0 +1,28 @@
+module 0x42::M {
+    #[allow(unused_variable)]
+    fun allowed(a: u64) {}
+
+    fun not_allowed(a: u64) {}
+
+    #[allow(unused_item)]
+    fun allowed_category(a: u64) {
+        let x = 0;
+    }
+
+    #[allow(unused_assignment)]
+    fun allowed_code(a: u64) {
+        let x = 0;
+    }
+
+    #[allow(unused_type_parameter)]
+    struct S<T> {}
+
+    struct R<T> {}
+}
+
+#[allow(unused_alias)]
+module 0x42::N {
+    use 0x42::M;
+
+    fun f(a: u64) {}
+}
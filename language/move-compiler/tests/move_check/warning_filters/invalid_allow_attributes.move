@@ -0,0 +1,15 @@
+module 0x42::M {
+    #[allow(not_a_warning, dead_code, not_a_category)]
+    fun unknown() {}
+
+    #[allow]
+    fun missing_list() {}
+
+    #[allow(unused_variable = 0)]
+    fun assigned() {}
+
+    #[allow(unused_alias)]
+    use 0x42::N;
+}
+
+module 0x42::N {}
@@ -0,0 +1,10 @@
+module 0x42::M {
+    use 0x42::N;
+
+    fun f(a: u64) {}
+
+    #[allow(unused_variable)]
+    fun g(a: u64) {}
+}
+
+module 0x42::N {}
@@ -21,6 +21,15 @@ const TEST_EXT: &str = "unit_test";
 /// Root of tests which require to set flavor flags.
 const FLAVOR_PATH: &str = "flavors/";
 
+/// Root of tests which require the linters to run.
+const LINTER_PATH: &str = "linter/";
+
+/// Root of tests which report warnings as errors, except for unused aliases.
+const WARNINGS_ARE_ERRORS_PATH: &str = "warnings_are_errors/";
+
+/// Root of tests which report their diagnostics in the format named by their directory.
+const DIAGNOSTICS_FORMAT_PATH: &str = "diagnostics_format/";
+
 fn default_testing_addresses() -> BTreeMap<String, NumericalAddress> {
     let mapping = [
         ("std", "0x1"),
@@ -74,6 +83,23 @@ fn move_check_testsuite(path: &Path) -> datatest_stable::Result<()> {
                 .to_string();
             flags = flags.set_flavor(flavor)
         }
+        Some(p) if p.contains(DIAGNOSTICS_FORMAT_PATH) => {
+            let format = path
+                .parent()
+                .expect("has parent")
+                .file_name()
+                .expect("has name")
+                .to_string_lossy()
+                .parse()
+                .map_err(anyhow::Error::msg)?;
+            flags = flags.set_diagnostics_format(format)
+        }
+        Some(p) if p.contains(LINTER_PATH) => flags = flags.set_lint(true),
+        Some(p) if p.contains(WARNINGS_ARE_ERRORS_PATH) => {
+            flags = flags
+                .set_warnings_are_errors(true)
+                .set_allowed_warnings(vec!["unused_alias".parse().unwrap()])
+        }
         _ => {}
     };
     run_test(path, &exp_path, &out_path, flags)?;
@@ -84,6 +110,7 @@ fn move_check_testsuite(path: &Path) -> datatest_stable::Result<()> {
 fn run_test(path: &Path, exp_path: &Path, out_path: &Path, flags: Flags) -> anyhow::Result<()> {
     let targets: Vec<String> = vec![path.to_str().unwrap().to_owned()];
 
+    let format = flags.diagnostics_format();
     let (files, comments_and_compiler_res) = Compiler::from_files(
         targets,
         move_stdlib::move_stdlib_files(),
@@ -95,7 +122,7 @@ fn run_test(path: &Path, exp_path: &Path, out_path: &Path, flags: Flags) -> anyh
 
     let has_diags = !diags.is_empty();
     let diag_buffer = if has_diags {
-        move_compiler::diagnostics::report_diagnostics_to_buffer(&files, diags)
+        move_compiler::diagnostics::format_diagnostics(&files, diags, format).into_bytes()
     } else {
         vec![]
     };
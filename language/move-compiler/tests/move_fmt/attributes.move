@@ -0,0 +1,9 @@
+#[test_only]
+module 0x42::Attributes {
+    #[test]
+    fun t() {}
+
+    #[test(a=@0x1,b = @0x2)]
+    #[expected_failure(abort_code=1)]
+    fun t2(a: signer, b: signer) { let _ = a; let _ = b; abort 1 }
+}
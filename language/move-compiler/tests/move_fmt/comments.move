@@ -0,0 +1,18 @@
+// A leading comment
+/* A block comment
+   spanning two lines */
+module 0x42::Comments {
+    /// A documented function.
+    /// Doc comments stay attached to the function.
+    public fun f(): u64 { // trailing comment after an opener
+        let x = 1; // trailing comment
+           // indented comment
+        let y = /* inline */ 2;
+
+
+        x + y
+    }
+
+    /** A block doc comment */
+    fun g() {}
+}
@@ -0,0 +1,36 @@
+address 0x42 {
+module   M {
+  use std::vector;
+      use std::option::{Self,Option};
+
+
+
+  struct S<T: copy+drop> has copy , drop { f: u64 , g: vector<T> }
+  struct Empty {}
+
+  const MAX : u64 = 100 ;
+
+public fun new<T: copy + drop>( f : u64 ) : S<T> {
+  S{ f , g : vector::empty<T>() }
+}
+
+  public(friend) fun get<T: copy+drop>(s: &S<T>): u64 {   s.f   }
+
+  fun arith(x: u64, y: u64): u64 {
+      let z = x+y * 2 ;
+      if (z > MAX) { z = MAX } else { z = z - 1 };
+      while (z <MAX) {
+              z = z + 1;
+      };
+      let v = vector[1,2,3];
+      *vector::borrow(&v, 0) + (z as u64)
+  }
+
+  fun refs(s: &mut S<u8>, o: Option<u64>) {
+      let r = &mut s.f;
+      *r = 0;
+      assert!(option::is_none(&o), 1);
+      if (!vector::is_empty(&s.g)) abort 42
+  }
+}
+}
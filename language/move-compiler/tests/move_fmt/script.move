@@ -0,0 +1,8 @@
+script {
+use std::vector;
+fun main(v: vector<u8>) {
+if (vector::length(&v) > 0) {
+    let _ = v;
+}
+}
+}
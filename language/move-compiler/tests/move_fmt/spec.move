@@ -0,0 +1,21 @@
+module 0x42::Spec {
+    fun add(x: u64, y: u64): u64 { x + y }
+    spec add {
+            aborts_if x + y > MAX_U64;
+        ensures result == x+y;
+    }
+
+    spec module {
+        pragma verify = true;
+    }
+
+    spec schema AddsOne {
+        x: u64;
+        ensures x==old(x)+1;
+    }
+
+    fun with_spec(x: u64): u64 {
+        spec { assume x > 0; };
+        x
+    }
+}
@@ -0,0 +1,58 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use move_command_line_common::{
+    files::FileHash,
+    testing::{add_update_baseline_fix, format_diff, read_env_update_baseline, EXP_EXT},
+};
+use move_compiler::{
+    diagnostics::report_diagnostics_to_buffer,
+    formatter::{format_string, FormatterConfig},
+};
+use std::{collections::HashMap, fs, path::Path};
+
+fn move_fmt_testsuite(path: &Path) -> datatest_stable::Result<()> {
+    run_test(path, &path.with_extension(EXP_EXT))?;
+    Ok(())
+}
+
+// Formats each file under the tests/move_fmt directory, and checks the output against the expected
+// output. Formatting the output again must not change it.
+fn run_test(path: &Path, exp_path: &Path) -> anyhow::Result<()> {
+    let input = fs::read_to_string(path)?;
+    let file_hash = FileHash::new(&input);
+    let config = FormatterConfig::default();
+    let output = match format_string(file_hash, &input, &config) {
+        Ok(output) => output,
+        Err(diags) => {
+            let name = path.to_string_lossy().as_ref().into();
+            let files = HashMap::from([(file_hash, (name, input))]);
+            let rendered = String::from_utf8(report_diagnostics_to_buffer(&files, diags))?;
+            anyhow::bail!("Unable to format the file:\n{}", rendered)
+        }
+    };
+
+    if read_env_update_baseline() {
+        fs::write(exp_path, &output)?;
+        return Ok(());
+    }
+
+    let expected = fs::read_to_string(exp_path).unwrap_or_default();
+    if output != expected {
+        let msg = format!(
+            "Expected output differs from actual output:\n{}",
+            format_diff(expected, &output),
+        );
+        anyhow::bail!(add_update_baseline_fix(msg))
+    }
+    match format_string(FileHash::new(&output), &output, &config) {
+        Ok(reformatted) if reformatted == output => Ok(()),
+        Ok(reformatted) => anyhow::bail!(
+            "Formatting is not idempotent:\n{}",
+            format_diff(&output, reformatted)
+        ),
+        Err(_) => anyhow::bail!("Unable to format the formatted file"),
+    }
+}
+
+datatest_stable::harness!(move_fmt_testsuite, "tests/move_fmt", r".*\.move$");
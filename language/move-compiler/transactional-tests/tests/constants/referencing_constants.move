@@ -0,0 +1,39 @@
+//# publish
+module 0x42::M {
+    const BASE: u64 = 10;
+    const DOUBLE: u64 = BASE * 2;
+    public const SUM: u64 = BASE + DOUBLE;
+    public const LIST: vector<u64> = vector[BASE, DOUBLE, SUM + 1];
+    public const NAME: vector<u8> = b"move";
+    public const NAMES: vector<vector<u8>> = vector[NAME, x"00"];
+
+    public fun sum(): u64 { SUM }
+}
+
+//# publish
+module 0x42::N {
+    use 0x42::M;
+
+    const TRIPLE_SUM: u64 = M::SUM * 3;
+
+    public fun check() {
+        assert!(M::SUM == 30, 42);
+        assert!(M::sum() == M::SUM, 42);
+        assert!(TRIPLE_SUM == 90, 42);
+        assert!(M::LIST == vector[10, 20, 31], 42);
+        assert!(M::NAMES == vector[b"move", x"00"], 42);
+    }
+}
+
+//# run
+script {
+    use 0x42::M;
+
+    const LEN: u64 = M::SUM - 27;
+
+    fun main() {
+        0x42::N::check();
+        assert!(std::vector::length(&M::NAME) == 4, 42);
+        assert!(LEN == 3, 42);
+    }
+}
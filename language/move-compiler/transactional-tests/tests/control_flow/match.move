@@ -0,0 +1,77 @@
+//# publish
+module 0x42::Shapes {
+    enum Shape has copy, drop {
+        Circle { radius: u64 },
+        Rect { width: u64, height: u64 },
+        Empty,
+    }
+
+    public fun circle(radius: u64): Shape {
+        Shape::Circle { radius }
+    }
+
+    public fun rect(width: u64, height: u64): Shape {
+        Shape::Rect { width, height }
+    }
+
+    public fun empty(): Shape {
+        Shape::Empty
+    }
+
+    public fun area(s: Shape): u64 {
+        match (s) {
+            Shape::Circle { radius } => 3 * radius * radius,
+            Shape::Rect { width, height } => width * height,
+            Shape::Empty => 0,
+        }
+    }
+
+    public fun is_empty(s: &Shape): bool {
+        match (s) {
+            Shape::Empty => true,
+            _ => false,
+        }
+    }
+
+    public fun is_rect(s: &mut Shape): bool {
+        match (s) {
+            Shape::Rect { width: _, height: _ } => true,
+            Shape::Circle { radius: _ } => false,
+            Shape::Empty => false,
+        }
+    }
+
+    public fun scale(s: Shape, k: u64): Shape {
+        match (s) {
+            Shape::Circle { radius } => Shape::Circle { radius: radius * k },
+            Shape::Rect { width, height } => Shape::Rect { width: width * k, height: height * k },
+            _ => Shape::Empty,
+        }
+    }
+}
+
+//# run
+script {
+use 0x42::Shapes;
+fun main() {
+    assert!(Shapes::area(Shapes::circle(2)) == 12, 1);
+    assert!(Shapes::area(Shapes::rect(2, 3)) == 6, 2);
+    assert!(Shapes::area(Shapes::empty()) == 0, 3);
+}
+}
+
+//# run
+script {
+use 0x42::Shapes;
+fun main() {
+    let s = Shapes::rect(2, 3);
+    assert!(!Shapes::is_empty(&s), 4);
+    assert!(Shapes::is_rect(&mut s), 5);
+    assert!(Shapes::area(Shapes::scale(s, 2)) == 24, 6);
+
+    let s = Shapes::empty();
+    assert!(Shapes::is_empty(&s), 7);
+    assert!(!Shapes::is_rect(&mut s), 8);
+    assert!(Shapes::is_empty(&Shapes::scale(s, 2)), 9);
+}
+}
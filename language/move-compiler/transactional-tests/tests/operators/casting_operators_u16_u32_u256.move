@@ -0,0 +1,155 @@
+//# run
+// Casting to u16.
+script {
+fun main() {
+    // 0 remains unchanged.
+    assert!((0u8 as u16) == 0u16, 1000);
+    assert!((0u16 as u16) == 0u16, 1001);
+    assert!((0u32 as u16) == 0u16, 1002);
+    assert!((0u64 as u16) == 0u16, 1003);
+    assert!((0u128 as u16) == 0u16, 1004);
+    assert!((0u256 as u16) == 0u16, 1005);
+
+    // Random small number unchanged.
+    assert!((21u8 as u16) == 21u16, 1100);
+    assert!((21u16 as u16) == 21u16, 1101);
+    assert!((21u32 as u16) == 21u16, 1102);
+    assert!((21u64 as u16) == 21u16, 1103);
+    assert!((21u128 as u16) == 21u16, 1104);
+    assert!((21u256 as u16) == 21u16, 1105);
+
+    // Max representable values remain unchanged.
+    assert!((255u8 as u16) == 255u16, 1200);
+    assert!((65535u16 as u16) == 65535u16, 1201);
+    assert!((65535u32 as u16) == 65535u16, 1202);
+    assert!((65535u64 as u16) == 65535u16, 1203);
+    assert!((65535u128 as u16) == 65535u16, 1204);
+    assert!((65535u256 as u16) == 65535u16, 1205);
+}
+}
+
+//# run
+// Casting to u32.
+script {
+fun main() {
+    // 0 remains unchanged.
+    assert!((0u8 as u32) == 0u32, 2000);
+    assert!((0u16 as u32) == 0u32, 2001);
+    assert!((0u32 as u32) == 0u32, 2002);
+    assert!((0u64 as u32) == 0u32, 2003);
+    assert!((0u128 as u32) == 0u32, 2004);
+    assert!((0u256 as u32) == 0u32, 2005);
+
+    // Random small number unchanged.
+    assert!((21u8 as u32) == 21u32, 2100);
+    assert!((21u16 as u32) == 21u32, 2101);
+    assert!((21u32 as u32) == 21u32, 2102);
+    assert!((21u64 as u32) == 21u32, 2103);
+    assert!((21u128 as u32) == 21u32, 2104);
+    assert!((21u256 as u32) == 21u32, 2105);
+
+    // Max representable values remain unchanged.
+    assert!((255u8 as u32) == 255u32, 2200);
+    assert!((65535u16 as u32) == 65535u32, 2201);
+    assert!((4294967295u32 as u32) == 4294967295u32, 2202);
+    assert!((4294967295u64 as u32) == 4294967295u32, 2203);
+    assert!((4294967295u128 as u32) == 4294967295u32, 2204);
+    assert!((4294967295u256 as u32) == 4294967295u32, 2205);
+}
+}
+
+//# run
+// Casting to u256.
+script {
+fun main() {
+    // 0 remains unchanged.
+    assert!((0u8 as u256) == 0u256, 3000);
+    assert!((0u16 as u256) == 0u256, 3001);
+    assert!((0u32 as u256) == 0u256, 3002);
+    assert!((0u64 as u256) == 0u256, 3003);
+    assert!((0u128 as u256) == 0u256, 3004);
+    assert!((0u256 as u256) == 0u256, 3005);
+
+    // Random small number unchanged.
+    assert!((21u8 as u256) == 21u256, 3100);
+    assert!((21u16 as u256) == 21u256, 3101);
+    assert!((21u32 as u256) == 21u256, 3102);
+    assert!((21u64 as u256) == 21u256, 3103);
+    assert!((21u128 as u256) == 21u256, 3104);
+    assert!((21u256 as u256) == 21u256, 3105);
+
+    // Max representable values remain unchanged.
+    assert!((255u8 as u256) == 255u256, 3200);
+    assert!((65535u16 as u256) == 65535u256, 3201);
+    assert!((4294967295u32 as u256) == 4294967295u256, 3202);
+    assert!((18446744073709551615u64 as u256) == 18446744073709551615u256, 3203);
+    assert!((340282366920938463463374607431768211455u128 as u256) == 340282366920938463463374607431768211455u256, 3204);
+    assert!((115792089237316195423570985008687907853269984665640564039457584007913129639935u256 as u256) == 115792089237316195423570985008687907853269984665640564039457584007913129639935u256, 3205);
+}
+}
+
+//# run
+// Casting from u256 to narrower types.
+script {
+fun main() {
+    assert!((255u256 as u8) == 255u8, 4000);
+    assert!((18446744073709551615u256 as u64) == 18446744073709551615u64, 4001);
+    assert!((340282366920938463463374607431768211455u256 as u128) == 340282366920938463463374607431768211455u128, 4002);
+}
+}
+
+//# run
+script {
+fun main() {
+    // should fail
+    (256u16 as u8);
+}
+}
+
+//# run
+script {
+fun main() {
+    // should fail
+    (65536u32 as u16);
+}
+}
+
+//# run
+script {
+fun main() {
+    // should fail
+    (65536u256 as u16);
+}
+}
+
+//# run
+script {
+fun main() {
+    // should fail
+    (4294967296u64 as u32);
+}
+}
+
+//# run
+script {
+fun main() {
+    // should fail
+    (340282366920938463463374607431768211455u128 as u32);
+}
+}
+
+//# run
+script {
+fun main() {
+    // should fail
+    (18446744073709551616u256 as u64);
+}
+}
+
+//# run
+script {
+fun main() {
+    // should fail
+    (115792089237316195423570985008687907853269984665640564039457584007913129639935u256 as u128);
+}
+}
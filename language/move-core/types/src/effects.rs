@@ -5,15 +5,21 @@ use crate::{
     account_address::AccountAddress,
     identifier::Identifier,
     language_storage::{ModuleId, StructTag, TypeTag},
+    resolver::MoveResolver,
 };
 use anyhow::{format_err, Error, Result};
+use sha3::{Digest, Sha3_256};
 use std::collections::btree_map::{self, BTreeMap};
 
 /// A collection of changes to modules and resources under a Move account.
+///
+/// Changes to resources stored in a resource group are kept per group, as changes to the
+/// individual members of the group.
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
 pub struct AccountChangeSet {
     modules: BTreeMap<Identifier, Option<Vec<u8>>>,
     resources: BTreeMap<StructTag, Option<Vec<u8>>>,
+    resource_groups: BTreeMap<StructTag, BTreeMap<StructTag, Option<Vec<u8>>>>,
 }
 
 fn publish_checked<K, V, F>(map: &mut BTreeMap<K, Option<V>>, k: K, v: V, make_err: F) -> Result<()>
@@ -61,27 +67,69 @@ impl AccountChangeSet {
         modules: BTreeMap<Identifier, Option<Vec<u8>>>,
         resources: BTreeMap<StructTag, Option<Vec<u8>>>,
     ) -> Self {
-        Self { modules, resources }
+        Self {
+            modules,
+            resources,
+            resource_groups: BTreeMap::new(),
+        }
+    }
+
+    pub fn from_modules_resources_and_groups(
+        modules: BTreeMap<Identifier, Option<Vec<u8>>>,
+        resources: BTreeMap<StructTag, Option<Vec<u8>>>,
+        resource_groups: BTreeMap<StructTag, BTreeMap<StructTag, Option<Vec<u8>>>>,
+    ) -> Self {
+        Self {
+            modules,
+            resources,
+            resource_groups,
+        }
     }
 
     pub fn new() -> Self {
         Self {
             modules: BTreeMap::new(),
             resources: BTreeMap::new(),
+            resource_groups: BTreeMap::new(),
         }
     }
 
+    /// The changes to modules and resources. Returns an error if resource groups changed, use
+    /// `into_inner_with_groups` when resource groups are in use.
+    #[allow(clippy::type_complexity)]
     pub fn into_inner(
         self,
+    ) -> Result<(
+        BTreeMap<Identifier, Option<Vec<u8>>>,
+        BTreeMap<StructTag, Option<Vec<u8>>>,
+    )> {
+        self.check_no_resource_groups()?;
+        Ok((self.modules, self.resources))
+    }
+
+    #[allow(clippy::type_complexity)]
+    pub fn into_inner_with_groups(
+        self,
     ) -> (
         BTreeMap<Identifier, Option<Vec<u8>>>,
         BTreeMap<StructTag, Option<Vec<u8>>>,
+        BTreeMap<StructTag, BTreeMap<StructTag, Option<Vec<u8>>>>,
     ) {
-        (self.modules, self.resources)
+        (self.modules, self.resources, self.resource_groups)
     }
 
-    pub fn into_resources(self) -> BTreeMap<StructTag, Option<Vec<u8>>> {
-        self.resources
+    /// The changes to resources. Returns an error if resource groups changed.
+    pub fn into_resources(self) -> Result<BTreeMap<StructTag, Option<Vec<u8>>>> {
+        self.check_no_resource_groups()?;
+        Ok(self.resources)
+    }
+
+    fn check_no_resource_groups(&self) -> Result<()> {
+        if self.resource_groups.is_empty() {
+            Ok(())
+        } else {
+            Err(format_err!("changes to resource groups would be dropped"))
+        }
     }
 
     pub fn into_modules(self) -> BTreeMap<Identifier, Option<Vec<u8>>> {
@@ -96,8 +144,12 @@ impl AccountChangeSet {
         &self.resources
     }
 
+    pub fn resource_groups(&self) -> &BTreeMap<StructTag, BTreeMap<StructTag, Option<Vec<u8>>>> {
+        &self.resource_groups
+    }
+
     pub fn is_empty(&self) -> bool {
-        self.modules.is_empty() && self.resources.is_empty()
+        self.modules.is_empty() && self.resources.is_empty() && self.resource_groups.is_empty()
     }
 
     pub fn squash(&mut self, other: Self) -> Result<()> {
@@ -113,6 +165,14 @@ impl AccountChangeSet {
                 None => self.unpublish_resource(struct_tag)?,
             }
         }
+        for (group, members) in other.resource_groups {
+            for (struct_tag, blob_opt) in members {
+                match blob_opt {
+                    Some(blob) => self.publish_group_member(group.clone(), struct_tag, blob)?,
+                    None => self.unpublish_group_member(group.clone(), struct_tag)?,
+                }
+            }
+        }
         Ok(())
     }
 
@@ -147,6 +207,32 @@ impl AccountChangeSet {
             format_err!("resource already unpublished")
         })
     }
+
+    pub fn publish_group_member(
+        &mut self,
+        group: StructTag,
+        struct_tag: StructTag,
+        blob: Vec<u8>,
+    ) -> Result<()> {
+        publish_checked(
+            self.resource_groups.entry(group).or_default(),
+            struct_tag,
+            blob,
+            || format_err!("resource already published"),
+        )
+    }
+
+    pub fn unpublish_group_member(
+        &mut self,
+        group: StructTag,
+        struct_tag: StructTag,
+    ) -> Result<()> {
+        unpublish_checked(
+            self.resource_groups.entry(group).or_default(),
+            struct_tag,
+            || format_err!("resource already unpublished"),
+        )
+    }
 }
 
 /// A collection of changes to a Move state. Each AccountChangeSet in the domain of `accounts`
@@ -275,6 +361,118 @@ impl ChangeSet {
             })
         })
     }
+
+    /// The changes to the members of resource groups, as (address, group, member, blob).
+    pub fn resource_group_members(
+        &self,
+    ) -> impl Iterator<Item = (AccountAddress, &StructTag, &StructTag, Option<&[u8]>)> {
+        self.accounts.iter().flat_map(|(addr, account)| {
+            let addr = *addr;
+            account
+                .resource_groups
+                .iter()
+                .flat_map(move |(group, members)| {
+                    members.iter().map(move |(struct_tag, blob_opt)| {
+                        (addr, group, struct_tag, blob_opt.as_deref())
+                    })
+                })
+        })
+    }
+}
+
+/// The hash of a serialized module, as recorded in a `ReadSet`.
+pub type ModuleHash = [u8; 32];
+
+/// Returns the SHA3-256 hash of the serialized module `blob`.
+pub fn module_hash(blob: &[u8]) -> ModuleHash {
+    Sha3_256::digest(blob).into()
+}
+
+/// The modules and resources a Move session fetched from storage, with the values observed when
+/// each of them was first fetched. Modules are recorded by the hash of their bytes, see
+/// `module_hash`.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct ReadSet {
+    modules: BTreeMap<ModuleId, Option<ModuleHash>>,
+    resources: BTreeMap<(AccountAddress, StructTag), Option<Vec<u8>>>,
+    resource_groups: BTreeMap<(AccountAddress, StructTag), Option<Vec<u8>>>,
+}
+
+impl ReadSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.modules.is_empty() && self.resources.is_empty() && self.resource_groups.is_empty()
+    }
+
+    /// Record a read of a module, keeping the value of the first read if it was already read.
+    pub fn record_module(&mut self, module_id: ModuleId, hash: Option<ModuleHash>) {
+        self.modules.entry(module_id).or_insert(hash);
+    }
+
+    /// Record a read of a resource, keeping the value of the first read if it was already read.
+    pub fn record_resource(
+        &mut self,
+        addr: AccountAddress,
+        struct_tag: StructTag,
+        blob: Option<Vec<u8>>,
+    ) {
+        self.resources.entry((addr, struct_tag)).or_insert(blob);
+    }
+
+    /// Record a read of a resource group, keeping the value of the first read if it was already
+    /// read.
+    pub fn record_resource_group(
+        &mut self,
+        addr: AccountAddress,
+        group: StructTag,
+        blob: Option<Vec<u8>>,
+    ) {
+        self.resource_groups.entry((addr, group)).or_insert(blob);
+    }
+
+    pub fn modules(&self) -> impl Iterator<Item = (&ModuleId, Option<&ModuleHash>)> {
+        self.modules
+            .iter()
+            .map(|(module_id, hash_opt)| (module_id, hash_opt.as_ref()))
+    }
+
+    pub fn resources(&self) -> impl Iterator<Item = (AccountAddress, &StructTag, Option<&[u8]>)> {
+        self.resources
+            .iter()
+            .map(|((addr, struct_tag), blob_opt)| (*addr, struct_tag, blob_opt.as_deref()))
+    }
+
+    pub fn resource_groups(
+        &self,
+    ) -> impl Iterator<Item = (AccountAddress, &StructTag, Option<&[u8]>)> {
+        self.resource_groups
+            .iter()
+            .map(|((addr, group), blob_opt)| (*addr, group, blob_opt.as_deref()))
+    }
+
+    /// Check whether `state` still holds the values which were read, i.e. whether an execution
+    /// which observed this read set would behave the same on top of `state`.
+    pub fn is_valid<S: MoveResolver>(&self, state: &S) -> Result<bool, S::Err> {
+        for (module_id, hash_opt) in &self.modules {
+            if &state.get_module(module_id)?.map(|blob| module_hash(&blob)) != hash_opt {
+                return Ok(false);
+            }
+        }
+        for ((addr, struct_tag), blob_opt) in &self.resources {
+            if &state.get_resource(addr, struct_tag)? != blob_opt {
+                return Ok(false);
+            }
+        }
+        for ((addr, group), blob_opt) in &self.resource_groups {
+            if &state.get_resource_group(addr, group)? != blob_opt {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
 }
 
 pub type Event = (Vec<u8>, u64, TypeTag, Vec<u8>);
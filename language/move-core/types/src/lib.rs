@@ -16,7 +16,9 @@ pub mod parser;
 #[cfg(any(test, feature = "fuzzing"))]
 pub mod proptest_types;
 pub mod resolver;
+pub mod resource_group;
 pub mod transaction_argument;
+pub mod u256;
 #[cfg(test)]
 mod unit_tests;
 pub mod value;
@@ -13,8 +13,11 @@ use std::iter::Peekable;
 #[derive(Eq, PartialEq, Debug)]
 enum Token {
     U8Type,
+    U16Type,
+    U32Type,
     U64Type,
     U128Type,
+    U256Type,
     BoolType,
     AddressType,
     VectorType,
@@ -23,8 +26,11 @@ enum Token {
     Name(String),
     Address(String),
     U8(String),
+    U16(String),
+    U32(String),
     U64(String),
     U128(String),
+    U256(String),
     Bytes(String),
     True,
     False,
@@ -44,8 +50,11 @@ impl Token {
 fn name_token(s: String) -> Token {
     match s.as_str() {
         "u8" => Token::U8Type,
+        "u16" => Token::U16Type,
+        "u32" => Token::U32Type,
         "u64" => Token::U64Type,
         "u128" => Token::U128Type,
+        "u256" => Token::U256Type,
         "bool" => Token::BoolType,
         "address" => Token::AddressType,
         "vector" => Token::VectorType,
@@ -72,8 +81,11 @@ fn next_number(initial: char, mut it: impl Iterator<Item = char>) -> Result<(Tok
                             let len = num.len() + suffix.len();
                             let tok = match suffix.as_str() {
                                 "u8" => Token::U8(num),
+                                "u16" => Token::U16(num),
+                                "u32" => Token::U32(num),
                                 "u64" => Token::U64(num),
                                 "u128" => Token::U128(num),
+                                "u256" => Token::U256(num),
                                 _ => bail!("invalid suffix"),
                             };
                             return Ok((tok, len));
@@ -256,8 +268,11 @@ impl<I: Iterator<Item = Token>> Parser<I> {
     fn parse_type_tag(&mut self) -> Result<TypeTag> {
         Ok(match self.next()? {
             Token::U8Type => TypeTag::U8,
+            Token::U16Type => TypeTag::U16,
+            Token::U32Type => TypeTag::U32,
             Token::U64Type => TypeTag::U64,
             Token::U128Type => TypeTag::U128,
+            Token::U256Type => TypeTag::U256,
             Token::BoolType => TypeTag::Bool,
             Token::AddressType => TypeTag::Address,
             Token::SignerType => TypeTag::Signer,
@@ -306,8 +321,11 @@ impl<I: Iterator<Item = Token>> Parser<I> {
     fn parse_transaction_argument(&mut self) -> Result<TransactionArgument> {
         Ok(match self.next()? {
             Token::U8(s) => TransactionArgument::U8(s.parse()?),
+            Token::U16(s) => TransactionArgument::U16(s.parse()?),
+            Token::U32(s) => TransactionArgument::U32(s.parse()?),
             Token::U64(s) => TransactionArgument::U64(s.parse()?),
             Token::U128(s) => TransactionArgument::U128(s.parse()?),
+            Token::U256(s) => TransactionArgument::U256(s.parse()?),
             Token::True => TransactionArgument::Bool(true),
             Token::False => TransactionArgument::Bool(false),
             Token::Address(addr) => {
@@ -380,6 +398,7 @@ mod tests {
         account_address::AccountAddress,
         parser::{parse_struct_tag, parse_transaction_argument, parse_type_tag},
         transaction_argument::TransactionArgument,
+        u256::U256,
     };
 
     #[allow(clippy::unreadable_literal)]
@@ -401,6 +420,13 @@ mod tests {
                 "340282366920938463463374607431768211455u128",
                 T::U128(340282366920938463463374607431768211455),
             ),
+            ("65535u16", T::U16(65535)),
+            ("4294967295u32", T::U32(4294967295)),
+            ("0u256", T::U256(U256::zero())),
+            (
+                "115792089237316195423570985008687907853269984665640564039457584007913129639935u256",
+                T::U256(U256::max_value()),
+            ),
             ("true", T::Bool(true)),
             ("false", T::Bool(false)),
             (
@@ -437,6 +463,9 @@ mod tests {
             "18446744073709551616",
             "18446744073709551616u64",
             "340282366920938463463374607431768211456u128",
+            "65536u16",
+            "4294967296u32",
+            "115792089237316195423570985008687907853269984665640564039457584007913129639936u256",
             "0xg",
             "0x00g0",
             "0x",
@@ -460,7 +489,10 @@ mod tests {
     #[test]
     fn test_type_tag() {
         for s in &[
+            "u16",
+            "u32",
             "u64",
+            "u256",
             "bool",
             "vector<u8>",
             "vector<vector<u64>>",
@@ -41,6 +41,17 @@ pub trait ResourceResolver {
         address: &AccountAddress,
         typ: &StructTag,
     ) -> Result<Option<Vec<u8>>, Self::Error>;
+
+    /// Get the serialized resource group `group` (see `resource_group`), with the same
+    /// conventions as `get_resource`. By default, groups are stored like resources, under the
+    /// tag of the group.
+    fn get_resource_group(
+        &self,
+        address: &AccountAddress,
+        group: &StructTag,
+    ) -> Result<Option<Vec<u8>>, Self::Error> {
+        self.get_resource(address, group)
+    }
 }
 
 /// A persistent storage implementation that can resolve both resources and modules
@@ -66,6 +77,14 @@ impl<T: ResourceResolver + ?Sized> ResourceResolver for &T {
     ) -> Result<Option<Vec<u8>>, Self::Error> {
         (**self).get_resource(address, tag)
     }
+
+    fn get_resource_group(
+        &self,
+        address: &AccountAddress,
+        group: &StructTag,
+    ) -> Result<Option<Vec<u8>>, Self::Error> {
+        (**self).get_resource_group(address, group)
+    }
 }
 
 impl<T: ModuleResolver + ?Sized> ModuleResolver for &T {
@@ -0,0 +1,91 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Resource groups.
+//!
+//! A module can declare that some of its resources are stored together in a group instead of
+//! each under its own key. A group is identified by a struct tag and stored as a single blob:
+//! the BCS serialization of a map from the struct tag of each member to the member's blob.
+//! Changes to the members of a group are reported per member in the `ChangeSet`, so that the
+//! storage only needs to rewrite the members which changed.
+//!
+//! Groups are declared through a module metadata entry with key `RESOURCE_GROUP_METADATA_KEY`
+//! whose value is the BCS serialization of a `ResourceGroupMetadata`.
+
+use crate::{identifier::Identifier, language_storage::StructTag, metadata::Metadata};
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The key of the module metadata entry declaring resource groups.
+pub const RESOURCE_GROUP_METADATA_KEY: &[u8] = b"move_resource_groups";
+
+/// The members of a resource group, keyed by their struct tags.
+pub type ResourceGroup = BTreeMap<StructTag, Vec<u8>>;
+
+/// The resource groups the resources of a module are stored in.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResourceGroupMetadata {
+    /// Maps the name of a struct declared in the module to the tag of its group.
+    pub members: BTreeMap<Identifier, StructTag>,
+}
+
+impl ResourceGroupMetadata {
+    /// Find and deserialize the resource group declarations in the metadata of a module.
+    pub fn from_module_metadata(metadata: &[Metadata]) -> Result<Option<Self>> {
+        match metadata
+            .iter()
+            .find(|entry| entry.key == RESOURCE_GROUP_METADATA_KEY)
+        {
+            Some(entry) => Ok(Some(bcs::from_bytes(&entry.value)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub fn to_module_metadata(&self) -> Result<Metadata> {
+        Ok(Metadata {
+            key: RESOURCE_GROUP_METADATA_KEY.to_vec(),
+            value: bcs::to_bytes(self)?,
+        })
+    }
+}
+
+pub fn deserialize_resource_group(blob: &[u8]) -> Result<ResourceGroup> {
+    Ok(bcs::from_bytes(blob)?)
+}
+
+pub fn serialize_resource_group(group: &ResourceGroup) -> Result<Vec<u8>> {
+    Ok(bcs::to_bytes(group)?)
+}
+
+/// Apply the changes to the members of a group to its serialized form, returning the new
+/// serialized group, or `None` if no member is left.
+pub fn apply_resource_group_changes(
+    blob: Option<&[u8]>,
+    changes: BTreeMap<StructTag, Option<Vec<u8>>>,
+) -> Result<Option<Vec<u8>>> {
+    let mut group = match blob {
+        Some(blob) => deserialize_resource_group(blob)?,
+        None => ResourceGroup::new(),
+    };
+    for (struct_tag, blob_opt) in changes {
+        match blob_opt {
+            Some(blob) => {
+                group.insert(struct_tag, blob);
+            }
+            None => {
+                if group.remove(&struct_tag).is_none() {
+                    bail!(
+                        "Failed to delete resource {}: resource does not exist.",
+                        struct_tag
+                    )
+                }
+            }
+        }
+    }
+    if group.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(serialize_resource_group(&group)?))
+    }
+}
@@ -1,7 +1,7 @@
 // Copyright (c) The Diem Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{account_address::AccountAddress, value::MoveValue};
+use crate::{account_address::AccountAddress, u256::U256, value::MoveValue};
 use anyhow::{anyhow, Error, Result};
 use serde::{Deserialize, Serialize};
 use std::{convert::TryFrom, fmt};
@@ -14,14 +14,21 @@ pub enum TransactionArgument {
     Address(AccountAddress),
     U8Vector(#[serde(with = "serde_bytes")] Vec<u8>),
     Bool(bool),
+    // NOTE: Added in bytecode version 6, do not reorder!
+    U16(u16),
+    U32(u32),
+    U256(U256),
 }
 
 impl fmt::Debug for TransactionArgument {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             TransactionArgument::U8(value) => write!(f, "{{U8: {}}}", value),
+            TransactionArgument::U16(value) => write!(f, "{{U16: {}}}", value),
+            TransactionArgument::U32(value) => write!(f, "{{U32: {}}}", value),
             TransactionArgument::U64(value) => write!(f, "{{U64: {}}}", value),
             TransactionArgument::U128(value) => write!(f, "{{U128: {}}}", value),
+            TransactionArgument::U256(value) => write!(f, "{{U256: {}}}", value),
             TransactionArgument::Bool(boolean) => write!(f, "{{BOOL: {}}}", boolean),
             TransactionArgument::Address(address) => write!(f, "{{ADDRESS: {:?}}}", address),
             TransactionArgument::U8Vector(vector) => {
@@ -35,8 +42,11 @@ impl From<TransactionArgument> for MoveValue {
     fn from(val: TransactionArgument) -> Self {
         match val {
             TransactionArgument::U8(i) => MoveValue::U8(i),
+            TransactionArgument::U16(i) => MoveValue::U16(i),
+            TransactionArgument::U32(i) => MoveValue::U32(i),
             TransactionArgument::U64(i) => MoveValue::U64(i),
             TransactionArgument::U128(i) => MoveValue::U128(i),
+            TransactionArgument::U256(i) => MoveValue::U256(i),
             TransactionArgument::Address(a) => MoveValue::Address(a),
             TransactionArgument::Bool(b) => MoveValue::Bool(b),
             TransactionArgument::U8Vector(v) => MoveValue::vector_u8(v),
@@ -49,8 +59,11 @@ impl TryFrom<MoveValue> for TransactionArgument {
     fn try_from(val: MoveValue) -> Result<Self> {
         Ok(match val {
             MoveValue::U8(i) => TransactionArgument::U8(i),
+            MoveValue::U16(i) => TransactionArgument::U16(i),
+            MoveValue::U32(i) => TransactionArgument::U32(i),
             MoveValue::U64(i) => TransactionArgument::U64(i),
             MoveValue::U128(i) => TransactionArgument::U128(i),
+            MoveValue::U256(i) => TransactionArgument::U256(i),
             MoveValue::Address(a) => TransactionArgument::Address(a),
             MoveValue::Bool(b) => TransactionArgument::Bool(b),
             MoveValue::Vector(v) => TransactionArgument::U8Vector(
@@ -109,7 +122,7 @@ mod tests {
     use std::convert::{From, TryInto};
 
     use crate::{
-        account_address::AccountAddress, transaction_argument::TransactionArgument,
+        account_address::AccountAddress, transaction_argument::TransactionArgument, u256::U256,
         value::MoveValue,
     };
 
@@ -119,6 +132,9 @@ mod tests {
             TransactionArgument::U8(1),
             TransactionArgument::U64(u64::MAX),
             TransactionArgument::U128(u128::MAX),
+            TransactionArgument::U16(u16::MAX),
+            TransactionArgument::U32(u32::MAX),
+            TransactionArgument::U256(U256::max_value()),
             TransactionArgument::Bool(true),
             TransactionArgument::Address(AccountAddress::from_hex_literal("0x1").unwrap()),
             TransactionArgument::U8Vector(vec![1, 2, 3, 4]),
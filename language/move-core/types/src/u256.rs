@@ -0,0 +1,294 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! The 256-bit unsigned integer of Move's `u256` type.
+//!
+//! `U256` serializes as 32 little-endian bytes in binary formats such as BCS, and as a decimal
+//! string in human-readable formats such as JSON, where numbers that large are not portable.
+
+use primitive_types::U256 as PrimitiveU256;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    convert::TryFrom,
+    fmt,
+    ops::{BitAnd, BitOr, BitXor, Shl, Shr},
+    str::FromStr,
+};
+
+const NUM_BYTES: usize = 32;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct U256(PrimitiveU256);
+
+/// The error returned when converting a `U256` to a smaller integer type which cannot represent
+/// its value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct U256CastError {
+    pub value: U256,
+    pub target: &'static str,
+}
+
+impl fmt::Display for U256CastError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "cast of {} to {} overflows", self.value, self.target)
+    }
+}
+
+impl std::error::Error for U256CastError {}
+
+/// The error returned when parsing a `U256` from a string fails.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct U256FromStrError(String);
+
+impl fmt::Display for U256FromStrError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid u256 literal: {}", self.0)
+    }
+}
+
+impl std::error::Error for U256FromStrError {}
+
+impl U256 {
+    pub const fn zero() -> Self {
+        Self(PrimitiveU256::zero())
+    }
+
+    pub fn one() -> Self {
+        Self(PrimitiveU256::one())
+    }
+
+    pub const fn max_value() -> Self {
+        Self(PrimitiveU256::MAX)
+    }
+
+    pub fn from_le_bytes(bytes: &[u8; NUM_BYTES]) -> Self {
+        Self(PrimitiveU256::from_little_endian(bytes))
+    }
+
+    pub fn to_le_bytes(self) -> [u8; NUM_BYTES] {
+        let mut bytes = [0u8; NUM_BYTES];
+        self.0.to_little_endian(&mut bytes);
+        bytes
+    }
+
+    /// Parse a number in the given radix, which must be 10 or 16, without any prefix.
+    pub fn from_str_radix(src: &str, radix: u32) -> Result<Self, U256FromStrError> {
+        let result = match radix {
+            10 => PrimitiveU256::from_dec_str(src).ok(),
+            // Leading zeros do not count towards the maximum number of digits
+            16 => {
+                let digits = src.trim_start_matches('0');
+                let digits = if digits.is_empty() { "0" } else { digits };
+                PrimitiveU256::from_str_radix(digits, 16).ok()
+            }
+            _ => None,
+        };
+        result
+            .map(Self)
+            .ok_or_else(|| U256FromStrError(src.to_string()))
+    }
+
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.0.checked_add(rhs.0).map(Self)
+    }
+
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.0.checked_sub(rhs.0).map(Self)
+    }
+
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        self.0.checked_mul(rhs.0).map(Self)
+    }
+
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        self.0.checked_div(rhs.0).map(Self)
+    }
+
+    pub fn checked_rem(self, rhs: Self) -> Option<Self> {
+        self.0.checked_rem(rhs.0).map(Self)
+    }
+
+    /// Shift left by `n` bits, discarding the bits shifted out. Unlike the other integer types,
+    /// every shift amount a `u8` can hold is valid for `u256`.
+    pub fn shl_u8(self, n: u8) -> Self {
+        Self(self.0 << n as usize)
+    }
+
+    /// Shift right by `n` bits.
+    pub fn shr_u8(self, n: u8) -> Self {
+        Self(self.0 >> n as usize)
+    }
+
+    /// The number of bits needed to represent the value.
+    pub fn bits(&self) -> usize {
+        self.0.bits()
+    }
+}
+
+impl fmt::Display for U256 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl fmt::LowerHex for U256 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::LowerHex::fmt(&self.0, f)
+    }
+}
+
+impl FromStr for U256 {
+    type Err = U256FromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_str_radix(s, 10)
+    }
+}
+
+impl Serialize for U256 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            self.to_string().serialize(serializer)
+        } else {
+            self.to_le_bytes().serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for U256 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            let s = String::deserialize(deserializer)?;
+            s.parse().map_err(serde::de::Error::custom)
+        } else {
+            <[u8; NUM_BYTES]>::deserialize(deserializer).map(|bytes| Self::from_le_bytes(&bytes))
+        }
+    }
+}
+
+impl BitAnd for U256 {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        Self(self.0 & rhs.0)
+    }
+}
+
+impl BitOr for U256 {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitXor for U256 {
+    type Output = Self;
+
+    fn bitxor(self, rhs: Self) -> Self {
+        Self(self.0 ^ rhs.0)
+    }
+}
+
+impl Shl<u8> for U256 {
+    type Output = Self;
+
+    fn shl(self, n: u8) -> Self {
+        self.shl_u8(n)
+    }
+}
+
+impl Shr<u8> for U256 {
+    type Output = Self;
+
+    fn shr(self, n: u8) -> Self {
+        self.shr_u8(n)
+    }
+}
+
+macro_rules! impl_from_unsigned {
+    ($($t:ty),*) => {$(
+        impl From<$t> for U256 {
+            fn from(n: $t) -> Self {
+                Self(PrimitiveU256::from(n))
+            }
+        }
+    )*};
+}
+
+impl_from_unsigned!(u8, u16, u32, u64, u128);
+
+macro_rules! impl_try_into_unsigned {
+    ($($t:ty),*) => {$(
+        impl TryFrom<U256> for $t {
+            type Error = U256CastError;
+
+            fn try_from(n: U256) -> Result<Self, Self::Error> {
+                if n.0 > PrimitiveU256::from(<$t>::MAX) {
+                    return Err(U256CastError {
+                        value: n,
+                        target: stringify!($t),
+                    });
+                }
+                Ok(n.0.low_u128() as $t)
+            }
+        }
+    )*};
+}
+
+impl_try_into_unsigned!(u8, u16, u32, u64, u128);
+
+#[cfg(any(test, feature = "fuzzing"))]
+impl proptest::arbitrary::Arbitrary for U256 {
+    type Parameters = ();
+    type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        use proptest::strategy::Strategy;
+        proptest::arbitrary::any::<[u8; NUM_BYTES]>()
+            .prop_map(|bytes| U256::from_le_bytes(&bytes))
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::U256;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn bcs_round_trip() {
+        let n = U256::from(u128::MAX).checked_add(U256::one()).unwrap();
+        let bytes = bcs::to_bytes(&n).unwrap();
+        assert_eq!(bytes.len(), 32);
+        assert_eq!(bytes[16], 1);
+        assert_eq!(bcs::from_bytes::<U256>(&bytes).unwrap(), n);
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let n = U256::max_value();
+        let json = serde_json::to_string(&n).unwrap();
+        assert_eq!(
+            json,
+            "\"115792089237316195423570985008687907853269984665640564039457584007913129639935\""
+        );
+        assert_eq!(serde_json::from_str::<U256>(&json).unwrap(), n);
+    }
+
+    #[test]
+    fn checked_arithmetic() {
+        assert_eq!(U256::max_value().checked_add(U256::one()), None);
+        assert_eq!(U256::zero().checked_sub(U256::one()), None);
+        assert_eq!(U256::one().checked_div(U256::zero()), None);
+        assert_eq!(U256::one().shl_u8(255).shr_u8(255), U256::one());
+        assert_eq!(U256::max_value().shl_u8(255), U256::one().shl_u8(255));
+    }
+
+    #[test]
+    fn casts() {
+        assert_eq!(u64::try_from(U256::from(u64::MAX)).unwrap(), u64::MAX);
+        assert!(u64::try_from(U256::from(u64::MAX as u128 + 1)).is_err());
+        assert_eq!(u128::try_from(U256::from(u128::MAX)).unwrap(), u128::MAX);
+    }
+}
@@ -6,8 +6,11 @@ use crate::{
     ident_str,
     identifier::Identifier,
     language_storage::{StructTag, TypeTag},
-    value::{MoveStruct, MoveValue},
+    value::{
+        MoveFieldLayout, MoveStruct, MoveStructLayout, MoveTypeLayout, MoveValue, MoveVariantLayout,
+    },
 };
+use serde::Serialize;
 use serde_json::json;
 
 #[test]
@@ -135,3 +138,67 @@ fn nested_typed_struct_deserialization() {
         })
     );
 }
+
+/// A test which verifies that the BCS representation of a value of a Move enum is the
+/// representation of the equivalent Rust enum: the index of the variant followed by its fields.
+#[test]
+fn enum_bcs_equiv_rust_enum() {
+    #[derive(Serialize)]
+    enum Shape {
+        #[allow(dead_code)]
+        Empty,
+        Rect {
+            w: u64,
+            h: bool,
+        },
+    }
+
+    let layout = MoveTypeLayout::Struct(MoveStructLayout::new_variants(vec![
+        vec![],
+        vec![MoveTypeLayout::U64, MoveTypeLayout::Bool],
+    ]));
+    let value = MoveValue::Struct(MoveStruct::RuntimeVariant(
+        1,
+        vec![MoveValue::U64(7), MoveValue::Bool(true)],
+    ));
+    let blob = value.simple_serialize().unwrap();
+    assert_eq!(blob, bcs::to_bytes(&Shape::Rect { w: 7, h: true }).unwrap());
+    assert_eq!(
+        MoveValue::simple_deserialize(&blob, &layout).unwrap(),
+        value
+    );
+
+    // An out of range variant index is rejected.
+    let mut bad_blob = blob;
+    bad_blob[0] = 2;
+    assert!(MoveValue::simple_deserialize(&bad_blob, &layout).is_err());
+}
+
+#[test]
+fn enum_decoration() {
+    let layout = MoveStructLayout::with_variants(vec![
+        MoveVariantLayout::new(ident_str!("Empty").to_owned(), vec![]),
+        MoveVariantLayout::new(
+            ident_str!("Circle").to_owned(),
+            vec![MoveFieldLayout::new(
+                ident_str!("radius").to_owned(),
+                MoveTypeLayout::U64,
+            )],
+        ),
+    ]);
+    let runtime_value = MoveStruct::RuntimeVariant(1, vec![MoveValue::U64(3)]);
+    let decorated_value = runtime_value.clone().decorate(&layout);
+    assert_eq!(
+        decorated_value,
+        MoveStruct::WithVariantFields(
+            ident_str!("Circle").to_owned(),
+            1,
+            vec![(ident_str!("radius").to_owned(), MoveValue::U64(3))]
+        )
+    );
+    assert_eq!(
+        serde_json::to_value(&decorated_value).unwrap(),
+        json!({ "variant": "Circle", "fields": { "radius": 3 } })
+    );
+    assert_eq!(decorated_value.undecorate(), runtime_value);
+}
@@ -5,11 +5,12 @@ use crate::{
     account_address::AccountAddress,
     identifier::Identifier,
     language_storage::{StructTag, TypeTag},
+    u256::U256,
 };
 use anyhow::{bail, Result as AResult};
 use serde::{
-    de::Error as DeError,
-    ser::{SerializeMap, SerializeSeq, SerializeStruct, SerializeTuple},
+    de::{Error as DeError, VariantAccess},
+    ser::{SerializeMap, SerializeSeq, SerializeStruct, SerializeTuple, SerializeTupleVariant},
     Deserialize, Serialize,
 };
 use std::{
@@ -26,6 +27,12 @@ pub const MOVE_STRUCT_TYPE: &str = "type";
 /// In the `WithTypes` configuration, a Move struct gets serialized into a Serde struct with this as the second field
 pub const MOVE_STRUCT_FIELDS: &str = "fields";
 
+/// A Move enum gets serialized into a Serde enum with this name
+pub const MOVE_ENUM_NAME: &str = "enum";
+
+/// In the `WithVariantFields` configuration, a Move enum gets serialized into a Serde struct with this as the first field
+pub const MOVE_ENUM_VARIANT: &str = "variant";
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum MoveStruct {
     /// The representation used by the MoveVM
@@ -37,13 +44,22 @@ pub enum MoveStruct {
         type_: StructTag,
         fields: Vec<(Identifier, MoveValue)>,
     },
+    /// The representation of a value of an enum used by the MoveVM: the index of its variant and
+    /// the values of the fields of that variant
+    RuntimeVariant(u16, Vec<MoveValue>),
+    /// A decorated representation of a value of an enum with the name of its variant and
+    /// human-readable field names
+    WithVariantFields(Identifier, u16, Vec<(Identifier, MoveValue)>),
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum MoveValue {
     U8(u8),
+    U16(u16),
+    U32(u32),
     U64(u64),
     U128(u128),
+    U256(U256),
     Bool(bool),
     Address(AccountAddress),
     Vector(Vec<MoveValue>),
@@ -64,6 +80,19 @@ impl MoveFieldLayout {
     }
 }
 
+/// A layout associated with a named variant of an enum
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MoveVariantLayout {
+    pub name: Identifier,
+    pub fields: Vec<MoveFieldLayout>,
+}
+
+impl MoveVariantLayout {
+    pub fn new(name: Identifier, fields: Vec<MoveFieldLayout>) -> Self {
+        Self { name, fields }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum MoveStructLayout {
     /// The representation used by the MoveVM
@@ -75,6 +104,13 @@ pub enum MoveStructLayout {
         type_: StructTag,
         fields: Vec<MoveFieldLayout>,
     },
+
+    // NOTE: Added in bytecode version 7, do not reorder!
+    /// The representation of an enum used by the MoveVM: the field layouts of each variant, in
+    /// the order the variants are declared
+    RuntimeVariants(Vec<Vec<MoveTypeLayout>>),
+    /// A decorated representation of an enum with variant and field names
+    WithVariants(Vec<MoveVariantLayout>),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -95,6 +131,14 @@ pub enum MoveTypeLayout {
     Struct(MoveStructLayout),
     #[serde(rename(serialize = "signer", deserialize = "signer"))]
     Signer,
+
+    // NOTE: Added in bytecode version 6, do not reorder!
+    #[serde(rename(serialize = "u16", deserialize = "u16"))]
+    U16,
+    #[serde(rename(serialize = "u32", deserialize = "u32"))]
+    U32,
+    #[serde(rename(serialize = "u256", deserialize = "u256"))]
+    U256,
 }
 
 impl MoveValue {
@@ -152,6 +196,10 @@ impl MoveStruct {
         Self::Runtime(value)
     }
 
+    pub fn new_variant(tag: u16, fields: Vec<MoveValue>) -> Self {
+        Self::RuntimeVariant(tag, fields)
+    }
+
     pub fn with_fields(values: Vec<(Identifier, MoveValue)>) -> Self {
         Self::WithFields(values)
     }
@@ -194,14 +242,27 @@ impl MoveStruct {
                         .collect(),
                 }
             }
+            (MoveStruct::RuntimeVariant(tag, vals), MoveStructLayout::WithVariants(variants)) => {
+                match variants.get(tag as usize) {
+                    Some(variant) => MoveStruct::WithVariantFields(
+                        variant.name.clone(),
+                        tag,
+                        vals.into_iter()
+                            .zip(&variant.fields)
+                            .map(|(v, l)| (l.name.clone(), v.decorate(&l.layout)))
+                            .collect(),
+                    ),
+                    None => MoveStruct::RuntimeVariant(tag, vals),
+                }
+            }
             (v, _) => v, // already decorated
         }
     }
 
     pub fn fields(&self) -> &[MoveValue] {
         match self {
-            Self::Runtime(vals) => vals,
-            Self::WithFields(_) | Self::WithTypes { .. } => {
+            Self::Runtime(vals) | Self::RuntimeVariant(_, vals) => vals,
+            Self::WithFields(_) | Self::WithTypes { .. } | Self::WithVariantFields(..) => {
                 // It's not possible to implement this without changing the return type, and thus
                 // panicking is the best move
                 panic!("Getting fields for decorated representation")
@@ -211,20 +272,32 @@ impl MoveStruct {
 
     pub fn into_fields(self) -> Vec<MoveValue> {
         match self {
-            Self::Runtime(vals) => vals,
-            Self::WithFields(fields) | Self::WithTypes { fields, .. } => {
-                fields.into_iter().map(|(_, f)| f).collect()
-            }
+            Self::Runtime(vals) | Self::RuntimeVariant(_, vals) => vals,
+            Self::WithFields(fields)
+            | Self::WithTypes { fields, .. }
+            | Self::WithVariantFields(_, _, fields) => fields.into_iter().map(|(_, f)| f).collect(),
+        }
+    }
+
+    /// Returns the index of the variant if this is a value of an enum.
+    pub fn variant_tag(&self) -> Option<u16> {
+        match self {
+            Self::RuntimeVariant(tag, _) | Self::WithVariantFields(_, tag, _) => Some(*tag),
+            Self::Runtime(_) | Self::WithFields(_) | Self::WithTypes { .. } => None,
         }
     }
 
     pub fn undecorate(self) -> Self {
-        Self::Runtime(
-            self.into_fields()
-                .into_iter()
-                .map(MoveValue::undecorate)
-                .collect(),
-        )
+        let tag = self.variant_tag();
+        let fields = self
+            .into_fields()
+            .into_iter()
+            .map(MoveValue::undecorate)
+            .collect();
+        match tag {
+            Some(tag) => Self::RuntimeVariant(tag, fields),
+            None => Self::Runtime(fields),
+        }
     }
 }
 
@@ -241,6 +314,14 @@ impl MoveStructLayout {
         Self::WithTypes { type_, fields }
     }
 
+    pub fn new_variants(variants: Vec<Vec<MoveTypeLayout>>) -> Self {
+        Self::RuntimeVariants(variants)
+    }
+
+    pub fn with_variants(variants: Vec<MoveVariantLayout>) -> Self {
+        Self::WithVariants(variants)
+    }
+
     pub fn fields(&self) -> &[MoveTypeLayout] {
         match self {
             Self::Runtime(vals) => vals,
@@ -250,6 +331,9 @@ impl MoveStructLayout {
                 // panicking is the best move
                 panic!("Getting fields for decorated representation")
             }
+            Self::RuntimeVariants(_) | Self::WithVariants(_) => {
+                panic!("Getting fields for the layout of an enum")
+            }
         }
     }
 
@@ -259,6 +343,9 @@ impl MoveStructLayout {
             Self::WithFields(fields) | Self::WithTypes { fields, .. } => {
                 fields.into_iter().map(|f| f.layout).collect()
             }
+            Self::RuntimeVariants(_) | Self::WithVariants(_) => {
+                panic!("Getting fields for the layout of an enum")
+            }
         }
     }
 }
@@ -272,8 +359,11 @@ impl<'d> serde::de::DeserializeSeed<'d> for &MoveTypeLayout {
         match self {
             MoveTypeLayout::Bool => bool::deserialize(deserializer).map(MoveValue::Bool),
             MoveTypeLayout::U8 => u8::deserialize(deserializer).map(MoveValue::U8),
+            MoveTypeLayout::U16 => u16::deserialize(deserializer).map(MoveValue::U16),
+            MoveTypeLayout::U32 => u32::deserialize(deserializer).map(MoveValue::U32),
             MoveTypeLayout::U64 => u64::deserialize(deserializer).map(MoveValue::U64),
             MoveTypeLayout::U128 => u128::deserialize(deserializer).map(MoveValue::U128),
+            MoveTypeLayout::U256 => U256::deserialize(deserializer).map(MoveValue::U256),
             MoveTypeLayout::Address => {
                 AccountAddress::deserialize(deserializer).map(MoveValue::Address)
             }
@@ -357,6 +447,61 @@ impl<'d, 'a> serde::de::Visitor<'d> for StructFieldVisitor<'a> {
     }
 }
 
+/// Deserializes the index of a variant followed by the fields of that variant, which is how Serde
+/// data formats such as BCS encode enums.
+struct StructVariantVisitor<'a>(&'a [Vec<MoveTypeLayout>]);
+
+impl<'d, 'a> serde::de::Visitor<'d> for StructVariantVisitor<'a> {
+    type Value = (u16, Vec<MoveValue>);
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("Enum")
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::EnumAccess<'d>,
+    {
+        let (tag, variant) = data.variant::<u32>()?;
+        let layout = variant_at(self.0, tag, &self)?;
+        let fields = variant.tuple_variant(layout.len(), StructFieldVisitor(layout))?;
+        Ok((tag as u16, fields))
+    }
+}
+
+struct DecoratedStructVariantVisitor<'a>(&'a [MoveVariantLayout]);
+
+impl<'d, 'a> serde::de::Visitor<'d> for DecoratedStructVariantVisitor<'a> {
+    type Value = (Identifier, u16, Vec<(Identifier, MoveValue)>);
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("Enum")
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::EnumAccess<'d>,
+    {
+        let (tag, variant) = data.variant::<u32>()?;
+        let layout = variant_at(self.0, tag, &self)?;
+        let fields = variant.tuple_variant(
+            layout.fields.len(),
+            DecoratedStructFieldVisitor(&layout.fields),
+        )?;
+        Ok((layout.name.clone(), tag as u16, fields))
+    }
+}
+
+fn variant_at<'a, T, E: DeError>(
+    variants: &'a [T],
+    tag: u32,
+    expected: &dyn serde::de::Expected,
+) -> Result<&'a T, E> {
+    variants
+        .get(tag as usize)
+        .ok_or_else(|| E::invalid_value(serde::de::Unexpected::Unsigned(tag as u64), expected))
+}
+
 impl<'d> serde::de::DeserializeSeed<'d> for &MoveFieldLayout {
     type Value = (Identifier, MoveValue);
 
@@ -397,6 +542,22 @@ impl<'d> serde::de::DeserializeSeed<'d> for &MoveStructLayout {
                     fields,
                 })
             }
+            MoveStructLayout::RuntimeVariants(variants) => {
+                let (tag, fields) = deserializer.deserialize_enum(
+                    MOVE_ENUM_NAME,
+                    &[],
+                    StructVariantVisitor(variants),
+                )?;
+                Ok(MoveStruct::RuntimeVariant(tag, fields))
+            }
+            MoveStructLayout::WithVariants(variants) => {
+                let (name, tag, fields) = deserializer.deserialize_enum(
+                    MOVE_ENUM_NAME,
+                    &[],
+                    DecoratedStructVariantVisitor(variants),
+                )?;
+                Ok(MoveStruct::WithVariantFields(name, tag, fields))
+            }
         }
     }
 }
@@ -407,8 +568,11 @@ impl serde::Serialize for MoveValue {
             MoveValue::Struct(s) => s.serialize(serializer),
             MoveValue::Bool(b) => serializer.serialize_bool(*b),
             MoveValue::U8(i) => serializer.serialize_u8(*i),
+            MoveValue::U16(i) => serializer.serialize_u16(*i),
+            MoveValue::U32(i) => serializer.serialize_u32(*i),
             MoveValue::U64(i) => serializer.serialize_u64(*i),
             MoveValue::U128(i) => serializer.serialize_u128(*i),
+            MoveValue::U256(i) => i.serialize(serializer),
             MoveValue::Address(a) => a.serialize(serializer),
             MoveValue::Signer(a) => a.serialize(serializer),
             MoveValue::Vector(v) => {
@@ -457,6 +621,29 @@ impl serde::Serialize for MoveStruct {
                 t.serialize_field(MOVE_STRUCT_FIELDS, &MoveFields(fields))?;
                 t.end()
             }
+            Self::RuntimeVariant(tag, fields) => {
+                // Serialize a Move enum as a Serde tuple variant, which binary formats such as BCS
+                // encode as the index of the variant followed by its fields. The variant names are
+                // not known statically, so a placeholder is used for them.
+                let mut t = serializer.serialize_tuple_variant(
+                    MOVE_ENUM_NAME,
+                    *tag as u32,
+                    MOVE_ENUM_VARIANT,
+                    fields.len(),
+                )?;
+                for v in fields.iter() {
+                    t.serialize_field(v)?;
+                }
+                t.end()
+            }
+            Self::WithVariantFields(name, _, fields) => {
+                // Serialize a decorated Move enum as a Serde struct named `enum` with two fields
+                // named `variant` and `fields`, like `WithTypes` above.
+                let mut t = serializer.serialize_struct(MOVE_ENUM_NAME, 2)?;
+                t.serialize_field(MOVE_ENUM_VARIANT, name.as_str())?;
+                t.serialize_field(MOVE_STRUCT_FIELDS, &MoveFields(fields))?;
+                t.end()
+            }
         }
     }
 }
@@ -473,8 +660,11 @@ impl fmt::Display for MoveTypeLayout {
         match self {
             Bool => write!(f, "bool"),
             U8 => write!(f, "u8"),
+            U16 => write!(f, "u16"),
+            U32 => write!(f, "u32"),
             U64 => write!(f, "u64"),
             U128 => write!(f, "u128"),
+            U256 => write!(f, "u256"),
             Address => write!(f, "address"),
             Vector(typ) => write!(f, "vector<{}>", typ),
             Struct(s) => write!(f, "{}", s),
@@ -504,6 +694,24 @@ impl fmt::Display for MoveStructLayout {
                     write!(f, "{}, ", field)?
                 }
             }
+            Self::RuntimeVariants(variants) => {
+                for (i, layouts) in variants.iter().enumerate() {
+                    write!(f, "{}: {{ ", i)?;
+                    for (j, l) in layouts.iter().enumerate() {
+                        write!(f, "{}: {}, ", j, l)?
+                    }
+                    write!(f, "}}, ")?
+                }
+            }
+            Self::WithVariants(variants) => {
+                for variant in variants {
+                    write!(f, "{}: {{ ", variant.name)?;
+                    for field in &variant.fields {
+                        write!(f, "{}, ", field)?
+                    }
+                    write!(f, "}}, ")?
+                }
+            }
         }
         write!(f, "}}")
     }
@@ -517,8 +725,11 @@ impl TryInto<TypeTag> for &MoveTypeLayout {
             MoveTypeLayout::Address => TypeTag::Address,
             MoveTypeLayout::Bool => TypeTag::Bool,
             MoveTypeLayout::U8 => TypeTag::U8,
+            MoveTypeLayout::U16 => TypeTag::U16,
+            MoveTypeLayout::U32 => TypeTag::U32,
             MoveTypeLayout::U64 => TypeTag::U64,
             MoveTypeLayout::U128 => TypeTag::U128,
+            MoveTypeLayout::U256 => TypeTag::U256,
             MoveTypeLayout::Signer => TypeTag::Signer,
             MoveTypeLayout::Vector(v) => {
                 let inner_type = &**v;
@@ -535,7 +746,7 @@ impl TryInto<StructTag> for &MoveStructLayout {
     fn try_into(self) -> Result<StructTag, Self::Error> {
         use MoveStructLayout::*;
         match self {
-            Runtime(..) | WithFields(..) => bail!(
+            Runtime(..) | WithFields(..) | RuntimeVariants(..) | WithVariants(..) => bail!(
                 "Invalid MoveTypeLayout -> StructTag conversion--needed MoveLayoutType::WithTypes"
             ),
             WithTypes { type_, .. } => Ok(type_.clone()),
@@ -547,8 +758,11 @@ impl fmt::Display for MoveValue {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             MoveValue::U8(u) => write!(f, "{}u8", u),
+            MoveValue::U16(u) => write!(f, "{}u16", u),
+            MoveValue::U32(u) => write!(f, "{}u32", u),
             MoveValue::U64(u) => write!(f, "{}u64", u),
             MoveValue::U128(u) => write!(f, "{}u128", u),
+            MoveValue::U256(u) => write!(f, "{}u256", u),
             MoveValue::Bool(false) => write!(f, "false"),
             MoveValue::Bool(true) => write!(f, "true"),
             MoveValue::Address(a) => write!(f, "{}", a.to_hex_literal()),
@@ -570,6 +784,14 @@ impl fmt::Display for MoveStruct {
                 fmt::Display::fmt(type_, f)?;
                 fmt_list(f, " {", fields.iter().map(DisplayFieldBinding), "}")
             }
+            MoveStruct::RuntimeVariant(tag, v) => {
+                write!(f, "variant#{}", tag)?;
+                fmt_list(f, "[", v, "]")
+            }
+            MoveStruct::WithVariantFields(name, _, fields) => {
+                fmt::Display::fmt(name, f)?;
+                fmt_list(f, " {", fields.iter().map(DisplayFieldBinding), "}")
+            }
         }
     }
 }
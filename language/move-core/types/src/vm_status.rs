@@ -598,6 +598,24 @@ pub enum StatusCode {
     INVALID_PHANTOM_TYPE_PARAM_POSITION = 1108,
     VEC_UPDATE_EXISTS_MUTABLE_BORROW_ERROR = 1109,
     VEC_BORROW_ELEMENT_EXISTS_MUTABLE_BORROW_ERROR = 1110,
+    // The resource group declarations in the metadata of a module are malformed or refer to
+    // structs which are not resources of the module.
+    INVALID_RESOURCE_GROUP_DECLARATION = 1111,
+    // A function has more basic blocks than the verifier is configured to accept.
+    TOO_MANY_BASIC_BLOCKS = 1112,
+    // A signature has more type nodes than the verifier is configured to accept.
+    TOO_MANY_TYPE_NODES = 1113,
+    // Loops are nested deeper than the verifier is configured to accept.
+    LOOP_MAX_DEPTH_REACHED = 1114,
+    // A function or module has more back edges than the verifier is configured to accept.
+    TOO_MANY_BACK_EDGES = 1115,
+    // Verifying a function or module exceeded the verifier's meter budget.
+    PROGRAM_TOO_COMPLEX = 1116,
+    // An instruction operating on the fields of a struct, such as Pack or a field borrow, is
+    // applied to an enum.
+    STRUCT_ENUM_OPCODE_MISMATCH = 1117,
+    // The operand of TestVariant is not a reference to a value of the enum.
+    TEST_VARIANT_TYPE_MISMATCH_ERROR = 1118,
 
     // These are errors that the VM might raise if a violation of internal
     // invariants takes place.
@@ -642,6 +660,9 @@ pub enum StatusCode {
     VALUE_DESERIALIZATION_ERROR = 3023,
     CODE_DESERIALIZATION_ERROR = 3024,
     INVALID_FLAG_BITS = 3025,
+    BAD_U16 = 3026,
+    BAD_U32 = 3027,
+    BAD_U256 = 3028,
 
     // Errors that can arise at runtime
     // Runtime Errors: 4000-4999
@@ -663,6 +684,12 @@ pub enum StatusCode {
     VM_MAX_TYPE_DEPTH_REACHED = 4024,
     VM_MAX_VALUE_DEPTH_REACHED = 4025,
     VM_EXTENSION_ERROR = 4026,
+    // A vector would have more elements than the VM is configured to allow.
+    VM_MAX_VECTOR_LENGTH_REACHED = 4027,
+    // The type arguments of a call are larger than the VM is configured to allow.
+    VM_MAX_TYPE_INSTANTIATION_SIZE_REACHED = 4028,
+    // The value of an enum is not of the variant an instruction expects.
+    STRUCT_VARIANT_MISMATCH = 4029,
 
 
     // A reserved status to represent an unknown vm status.
@@ -9,7 +9,7 @@ use move_binary_format::{
         Constant, FieldDefinition, FunctionDefinition, FunctionSignature, ModuleHandle, Signature,
         SignatureToken, StructDefinition, StructDefinitionIndex, StructFieldInformation,
         StructHandleIndex, StructTypeParameter, TableIndex, TypeParameterIndex, TypeSignature,
-        Visibility,
+        VariantDefinition, VariantIndex, Visibility,
     },
     file_format_common::VERSION_MAX,
 };
@@ -649,8 +649,11 @@ fn compile_type(
         Type::Address => SignatureToken::Address,
         Type::Signer => SignatureToken::Signer,
         Type::U8 => SignatureToken::U8,
+        Type::U16 => SignatureToken::U16,
+        Type::U32 => SignatureToken::U32,
         Type::U64 => SignatureToken::U64,
         Type::U128 => SignatureToken::U128,
+        Type::U256 => SignatureToken::U256,
         Type::Bool => SignatureToken::Bool,
         Type::Vector(inner_type) => SignatureToken::Vector(Box::new(compile_type(
             context,
@@ -723,8 +726,9 @@ fn compile_structs(
         record_src_loc!(struct_decl: context, s.loc);
         record_src_loc!(struct_type_formals: context, &s.value.type_formals);
         let m = type_parameter_indexes(s.value.type_formals.iter().map(|formal| &formal.1))?;
-        let sd_idx = context.declare_struct_definition_index(s.value.name)?;
-        let field_information = compile_fields(context, &m, sh_idx, sd_idx, s.value.fields)?;
+        let sd_idx = context.declare_struct_definition_index(s.value.name.clone())?;
+        let field_information =
+            compile_fields(context, &m, sh_idx, sd_idx, &s.value.name, s.value.fields)?;
         struct_defs.push(StructDefinition {
             struct_handle: sh_idx,
             field_information,
@@ -738,10 +742,34 @@ fn compile_fields(
     type_parameters: &HashMap<TypeVar_, TypeParameterIndex>,
     sh_idx: StructHandleIndex,
     sd_idx: StructDefinitionIndex,
+    name: &StructName,
     sfields: StructDefinitionFields,
 ) -> Result<StructFieldInformation> {
     Ok(match sfields {
         StructDefinitionFields::Native => StructFieldInformation::Native,
+        StructDefinitionFields::Variants { variants } => {
+            let mut decl_variants = vec![];
+            for (idx, (v, fields)) in variants.into_iter().enumerate() {
+                if idx > TABLE_MAX_SIZE {
+                    bail!("too many variants {}", name)
+                }
+                context.declare_variant(name.clone(), v.clone(), idx as VariantIndex);
+                let fields = fields
+                    .into_iter()
+                    .map(|(f, ty)| {
+                        Ok(FieldDefinition {
+                            name: context.identifier_index(f.value.0)?,
+                            signature: TypeSignature(compile_type(context, type_parameters, &ty)?),
+                        })
+                    })
+                    .collect::<Result<_>>()?;
+                decl_variants.push(VariantDefinition {
+                    name: context.identifier_index(v.0)?,
+                    fields,
+                });
+            }
+            StructFieldInformation::DeclaredVariants(decl_variants)
+        }
         StructDefinitionFields::Move { fields } => {
             let mut decl_fields = vec![];
             for (decl_order, (f, ty)) in fields.into_iter().enumerate() {
@@ -1103,6 +1131,14 @@ fn compile_expression(
                 push_instr!(exp.loc, Bytecode::LdU8(i));
                 function_frame.push()?;
             }
+            CopyableVal_::U16(i) => {
+                push_instr!(exp.loc, Bytecode::LdU16(i));
+                function_frame.push()?;
+            }
+            CopyableVal_::U32(i) => {
+                push_instr!(exp.loc, Bytecode::LdU32(i));
+                function_frame.push()?;
+            }
             CopyableVal_::U64(i) => {
                 push_instr!(exp.loc, Bytecode::LdU64(i));
                 function_frame.push()?;
@@ -1111,6 +1147,10 @@ fn compile_expression(
                 push_instr!(exp.loc, Bytecode::LdU128(i));
                 function_frame.push()?;
             }
+            CopyableVal_::U256(i) => {
+                push_instr!(exp.loc, Bytecode::LdU256(i));
+                function_frame.push()?;
+            }
             CopyableVal_::ByteArray(buf) => {
                 let vec_value = MoveValue::vector_u8(buf);
                 let ty = Type::Vector(Box::new(Type::U8));
@@ -1476,6 +1516,16 @@ fn compile_call(
                     function_frame.pop()?;
                     function_frame.push()?;
                 }
+                Builtin::ToU16 => {
+                    push_instr!(call.loc, Bytecode::CastU16);
+                    function_frame.pop()?;
+                    function_frame.push()?;
+                }
+                Builtin::ToU32 => {
+                    push_instr!(call.loc, Bytecode::CastU32);
+                    function_frame.pop()?;
+                    function_frame.push()?;
+                }
                 Builtin::ToU64 => {
                     push_instr!(call.loc, Bytecode::CastU64);
                     function_frame.pop()?;
@@ -1486,6 +1536,11 @@ fn compile_call(
                     function_frame.pop()?;
                     function_frame.push()?;
                 }
+                Builtin::ToU256 => {
+                    push_instr!(call.loc, Bytecode::CastU256);
+                    function_frame.pop()?;
+                    function_frame.push()?;
+                }
             }
         }
         FunctionCall_::ModuleFunctionCall {
@@ -1520,8 +1575,11 @@ fn compile_constant(_context: &mut Context, ty: Type, value: MoveValue) -> Resul
             Type::Address => MoveTypeLayout::Address,
             Type::Signer => MoveTypeLayout::Signer,
             Type::U8 => MoveTypeLayout::U8,
+            Type::U16 => MoveTypeLayout::U16,
+            Type::U32 => MoveTypeLayout::U32,
             Type::U64 => MoveTypeLayout::U64,
             Type::U128 => MoveTypeLayout::U128,
+            Type::U256 => MoveTypeLayout::U256,
             Type::Bool => MoveTypeLayout::Bool,
             Type::Vector(inner_type) => MoveTypeLayout::Vector(Box::new(type_layout(*inner_type)?)),
             Type::Reference(_, _) => bail!("References are not supported in constant type layouts"),
@@ -1611,11 +1669,17 @@ fn compile_bytecode(
         IRBytecode_::BrFalse(lbl) => Bytecode::BrFalse(context.label_index(lbl)?),
         IRBytecode_::Branch(lbl) => Bytecode::Branch(context.label_index(lbl)?),
         IRBytecode_::LdU8(u) => Bytecode::LdU8(u),
+        IRBytecode_::LdU16(u) => Bytecode::LdU16(u),
+        IRBytecode_::LdU32(u) => Bytecode::LdU32(u),
         IRBytecode_::LdU64(u) => Bytecode::LdU64(u),
         IRBytecode_::LdU128(u) => Bytecode::LdU128(u),
+        IRBytecode_::LdU256(u) => Bytecode::LdU256(u),
         IRBytecode_::CastU8 => Bytecode::CastU8,
+        IRBytecode_::CastU16 => Bytecode::CastU16,
+        IRBytecode_::CastU32 => Bytecode::CastU32,
         IRBytecode_::CastU64 => Bytecode::CastU64,
         IRBytecode_::CastU128 => Bytecode::CastU128,
+        IRBytecode_::CastU256 => Bytecode::CastU256,
         IRBytecode_::LdTrue => Bytecode::LdTrue,
         IRBytecode_::LdFalse => Bytecode::LdFalse,
         IRBytecode_::LdConst(ty, v) => {
@@ -1671,6 +1735,54 @@ fn compile_bytecode(
                 Bytecode::UnpackGeneric(si_idx)
             }
         }
+        IRBytecode_::PackVariant(n, v, tys) => {
+            let tokens = Signature(compile_types(
+                context,
+                function_frame.type_parameters(),
+                &tys,
+            )?);
+            let type_actuals_id = context.signature_index(tokens)?;
+            let def_idx = context.struct_definition_index(&n)?;
+            let variant = context.variant_index(&n, &v)?;
+            if tys.is_empty() {
+                Bytecode::PackVariant(def_idx, variant)
+            } else {
+                let si_idx = context.struct_instantiation_index(def_idx, type_actuals_id)?;
+                Bytecode::PackVariantGeneric(si_idx, variant)
+            }
+        }
+        IRBytecode_::UnpackVariant(n, v, tys) => {
+            let tokens = Signature(compile_types(
+                context,
+                function_frame.type_parameters(),
+                &tys,
+            )?);
+            let type_actuals_id = context.signature_index(tokens)?;
+            let def_idx = context.struct_definition_index(&n)?;
+            let variant = context.variant_index(&n, &v)?;
+            if tys.is_empty() {
+                Bytecode::UnpackVariant(def_idx, variant)
+            } else {
+                let si_idx = context.struct_instantiation_index(def_idx, type_actuals_id)?;
+                Bytecode::UnpackVariantGeneric(si_idx, variant)
+            }
+        }
+        IRBytecode_::TestVariant(n, v, tys) => {
+            let tokens = Signature(compile_types(
+                context,
+                function_frame.type_parameters(),
+                &tys,
+            )?);
+            let type_actuals_id = context.signature_index(tokens)?;
+            let def_idx = context.struct_definition_index(&n)?;
+            let variant = context.variant_index(&n, &v)?;
+            if tys.is_empty() {
+                Bytecode::TestVariant(def_idx, variant)
+            } else {
+                let si_idx = context.struct_instantiation_index(def_idx, type_actuals_id)?;
+                Bytecode::TestVariantGeneric(si_idx, variant)
+            }
+        }
         IRBytecode_::ReadRef => Bytecode::ReadRef,
         IRBytecode_::WriteRef => Bytecode::WriteRef,
         IRBytecode_::FreezeRef => Bytecode::FreezeRef,
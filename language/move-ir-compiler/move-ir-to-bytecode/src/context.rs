@@ -11,6 +11,7 @@ use move_binary_format::{
         FunctionSignature, IdentifierIndex, ModuleHandle, ModuleHandleIndex, Signature,
         SignatureIndex, SignatureToken, StructDefInstantiation, StructDefInstantiationIndex,
         StructDefinitionIndex, StructHandle, StructHandleIndex, StructTypeParameter, TableIndex,
+        VariantIndex,
     },
     CompiledModule,
 };
@@ -22,7 +23,7 @@ use move_core_types::{
 use move_ir_types::{
     ast::{
         BlockLabel_, ConstantName, Field_, FunctionName, ModuleIdent, ModuleName,
-        QualifiedStructIdent, StructName,
+        QualifiedStructIdent, StructName, VariantName,
     },
     location::Loc,
 };
@@ -243,6 +244,7 @@ pub(crate) struct Context<'a> {
     modules: HashMap<ModuleName, (ModuleIdent, ModuleHandle)>,
     structs: HashMap<QualifiedStructIdent, StructHandle>,
     struct_defs: HashMap<StructName, TableIndex>,
+    variants: HashMap<(StructName, VariantName), VariantIndex>,
     named_constants: HashMap<ConstantName, TableIndex>,
     labels: HashMap<BlockLabel_, u16>,
 
@@ -287,6 +289,7 @@ impl<'a> Context<'a> {
             modules: HashMap::new(),
             structs: HashMap::new(),
             struct_defs: HashMap::new(),
+            variants: HashMap::new(),
             named_constants: HashMap::new(),
             labels: HashMap::new(),
             fields: HashMap::new(),
@@ -540,6 +543,14 @@ impl<'a> Context<'a> {
         }
     }
 
+    /// Get the index of a variant of an enum, fails if it is not bound.
+    pub fn variant_index(&self, s: &StructName, v: &VariantName) -> Result<VariantIndex> {
+        match self.variants.get(&(s.clone(), v.clone())) {
+            None => bail!("Unbound variant {}::{}", s, v),
+            Some(idx) => Ok(*idx),
+        }
+    }
+
     /// Get the signature pool index, adds it if missing.
     pub fn signature_index(&mut self, sig: Signature) -> Result<SignatureIndex> {
         Ok(SignatureIndex(get_or_add_item(&mut self.signatures, sig)?))
@@ -706,6 +717,11 @@ impl<'a> Context<'a> {
             .or_insert((sd_idx, token, decl_order));
     }
 
+    /// Given an enum and one of its variants, records the index of the variant.
+    pub fn declare_variant(&mut self, s: StructName, v: VariantName, idx: VariantIndex) {
+        self.variants.entry((s, v)).or_insert(idx);
+    }
+
     //**********************************************************************************************
     // Dependency Resolution
     //**********************************************************************************************
@@ -757,8 +773,11 @@ impl<'a> Context<'a> {
         Ok(match orig {
             x @ SignatureToken::Bool
             | x @ SignatureToken::U8
+            | x @ SignatureToken::U16
+            | x @ SignatureToken::U32
             | x @ SignatureToken::U64
             | x @ SignatureToken::U128
+            | x @ SignatureToken::U256
             | x @ SignatureToken::Address
             | x @ SignatureToken::Signer
             | x @ SignatureToken::TypeParameter(_) => x,
@@ -6,7 +6,7 @@ use std::{collections::BTreeSet, fmt, str::FromStr};
 
 use crate::lexer::*;
 use move_command_line_common::files::FileHash;
-use move_core_types::account_address::AccountAddress;
+use move_core_types::{account_address::AccountAddress, u256::U256};
 use move_ir_types::{ast::*, location::*, spec_language_ast::*};
 use move_symbol_pool::Symbol;
 
@@ -278,6 +278,24 @@ fn parse_copyable_val(tokens: &mut Lexer) -> Result<CopyableVal, ParseError<Loc,
             tokens.advance()?;
             CopyableVal_::U8(i)
         }
+        Tok::U16Value => {
+            let mut s = tokens.content();
+            if s.ends_with("u16") {
+                s = &s[..s.len() - 3]
+            }
+            let i = u16::from_str(s).unwrap();
+            tokens.advance()?;
+            CopyableVal_::U16(i)
+        }
+        Tok::U32Value => {
+            let mut s = tokens.content();
+            if s.ends_with("u32") {
+                s = &s[..s.len() - 3]
+            }
+            let i = u32::from_str(s).unwrap();
+            tokens.advance()?;
+            CopyableVal_::U32(i)
+        }
         Tok::U64Value => {
             let mut s = tokens.content();
             if s.ends_with("u64") {
@@ -296,6 +314,15 @@ fn parse_copyable_val(tokens: &mut Lexer) -> Result<CopyableVal, ParseError<Loc,
             tokens.advance()?;
             CopyableVal_::U128(i)
         }
+        Tok::U256Value => {
+            let mut s = tokens.content();
+            if s.ends_with("u256") {
+                s = &s[..s.len() - 4]
+            }
+            let i = U256::from_str(s).unwrap();
+            tokens.advance()?;
+            CopyableVal_::U256(i)
+        }
         Tok::ByteArrayValue => {
             let s = tokens.content();
             let buf = hex::decode(&s[2..s.len() - 1]).unwrap_or_else(|_| {
@@ -440,8 +467,11 @@ fn parse_qualified_function_name(
         | Tok::VecSwap
         | Tok::Freeze
         | Tok::ToU8
+        | Tok::ToU16
+        | Tok::ToU32
         | Tok::ToU64
-        | Tok::ToU128 => {
+        | Tok::ToU128
+        | Tok::ToU256 => {
             let f = parse_builtin(tokens)?;
             FunctionCall_::Builtin(f)
         }
@@ -584,8 +614,11 @@ fn parse_call_or_term_(tokens: &mut Lexer) -> Result<Exp_, ParseError<Loc, anyho
         | Tok::Freeze
         | Tok::DotNameValue
         | Tok::ToU8
+        | Tok::ToU16
+        | Tok::ToU32
         | Tok::ToU64
-        | Tok::ToU128 => {
+        | Tok::ToU128
+        | Tok::ToU256 => {
             let f = parse_qualified_function_name(tokens)?;
             let exp = parse_call_or_term(tokens)?;
             Ok(Exp_::FunctionCall(f, Box::new(exp)))
@@ -665,8 +698,11 @@ fn parse_term_(tokens: &mut Lexer) -> Result<Exp_, ParseError<Loc, anyhow::Error
         | Tok::True
         | Tok::False
         | Tok::U8Value
+        | Tok::U16Value
+        | Tok::U32Value
         | Tok::U64Value
         | Tok::U128Value
+        | Tok::U256Value
         | Tok::ByteArrayValue => Ok(Exp_::Value(parse_copyable_val(tokens)?)),
         Tok::NameValue | Tok::NameBeginTyValue => {
             let (name, type_actuals) = parse_name_and_type_actuals(tokens)?;
@@ -821,6 +857,14 @@ fn parse_builtin(tokens: &mut Lexer) -> Result<Builtin, ParseError<Loc, anyhow::
             tokens.advance()?;
             Ok(Builtin::ToU8)
         }
+        Tok::ToU16 => {
+            tokens.advance()?;
+            Ok(Builtin::ToU16)
+        }
+        Tok::ToU32 => {
+            tokens.advance()?;
+            Ok(Builtin::ToU32)
+        }
         Tok::ToU64 => {
             tokens.advance()?;
             Ok(Builtin::ToU64)
@@ -829,6 +873,10 @@ fn parse_builtin(tokens: &mut Lexer) -> Result<Builtin, ParseError<Loc, anyhow::
             tokens.advance()?;
             Ok(Builtin::ToU128)
         }
+        Tok::ToU256 => {
+            tokens.advance()?;
+            Ok(Builtin::ToU256)
+        }
         t => Err(ParseError::InvalidToken {
             location: current_token_loc(tokens),
             message: format!("unrecognized token kind for builtin {:?}", t),
@@ -1025,8 +1073,11 @@ fn parse_statement_(tokens: &mut Lexer) -> Result<Statement_, ParseError<Loc, an
         | Tok::Freeze
         | Tok::DotNameValue
         | Tok::ToU8
+        | Tok::ToU16
+        | Tok::ToU32
         | Tok::ToU64
-        | Tok::ToU128 => Ok(Statement_::Exp(Box::new(parse_call(tokens)?))),
+        | Tok::ToU128
+        | Tok::ToU256 => Ok(Statement_::Exp(Box::new(parse_call(tokens)?))),
         Tok::LParen => {
             tokens.advance()?;
             let start = tokens.start_loc();
@@ -1194,6 +1245,14 @@ fn parse_type(tokens: &mut Lexer) -> Result<Type, ParseError<Loc, anyhow::Error>
             tokens.advance()?;
             Type::U8
         }
+        Tok::NameValue if matches!(tokens.content(), "u16") => {
+            tokens.advance()?;
+            Type::U16
+        }
+        Tok::NameValue if matches!(tokens.content(), "u32") => {
+            tokens.advance()?;
+            Type::U32
+        }
         Tok::NameValue if matches!(tokens.content(), "u64") => {
             tokens.advance()?;
             Type::U64
@@ -1202,6 +1261,10 @@ fn parse_type(tokens: &mut Lexer) -> Result<Type, ParseError<Loc, anyhow::Error>
             tokens.advance()?;
             Type::U128
         }
+        Tok::NameValue if matches!(tokens.content(), "u256") => {
+            tokens.advance()?;
+            Type::U256
+        }
         Tok::NameValue if matches!(tokens.content(), "bool") => {
             tokens.advance()?;
             Type::Bool
@@ -1512,8 +1575,11 @@ fn parse_unary_spec_exp(tokens: &mut Lexer) -> Result<SpecExp, ParseError<Loc, a
         | Tok::True
         | Tok::False
         | Tok::U8Value
+        | Tok::U16Value
+        | Tok::U32Value
         | Tok::U64Value
         | Tok::U128Value
+        | Tok::U256Value
         | Tok::ByteArrayValue => SpecExp::Constant(parse_copyable_val(tokens)?.value),
         Tok::GlobalExists => {
             consume_token(tokens, Tok::GlobalExists)?;
@@ -7,7 +7,7 @@ use crate::{
 };
 use move_core_types::{
     account_address::AccountAddress, identifier::Identifier, language_storage::ModuleId,
-    value::MoveValue,
+    u256::U256, value::MoveValue,
 };
 use move_symbol_pool::Symbol;
 use once_cell::sync::Lazy;
@@ -180,10 +180,16 @@ pub enum Type {
     Signer,
     /// `u8`
     U8,
+    /// `u16`
+    U16,
+    /// `u32`
+    U32,
     /// `u64`
     U64,
     /// `u128`
     U128,
+    /// `u256`
+    U256,
     /// `bool`
     Bool,
     /// `vector`
@@ -244,6 +250,10 @@ pub type Fields<T> = Vec<(Field, T)>;
 #[derive(Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
 pub struct StructName(pub Symbol);
 
+/// Newtype for the name of a variant of an enum
+#[derive(Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub struct VariantName(pub Symbol);
+
 /// A struct type parameter with its constraints and whether it's declared as phantom.
 pub type StructTypeParameter = (bool, TypeVar, BTreeSet<Ability>);
 
@@ -282,6 +292,10 @@ pub enum StructDefinitionFields {
     Move { fields: Fields<Type> },
     /// The struct is a type provided by the VM
     Native,
+    /// The struct is an enum with the declared variants, each with its own fields
+    Variants {
+        variants: Vec<(VariantName, Fields<Type>)>,
+    },
 }
 
 //**************************************************************************************************
@@ -428,10 +442,16 @@ pub enum Builtin {
 
     /// Cast an integer into u8.
     ToU8,
+    /// Cast an integer into u16.
+    ToU16,
+    /// Cast an integer into u32.
+    ToU32,
     /// Cast an integer into u64.
     ToU64,
     /// Cast an integer into u128.
     ToU128,
+    /// Cast an integer into u256.
+    ToU256,
 }
 
 /// Enum for different function calls
@@ -511,10 +531,16 @@ pub enum CopyableVal_ {
     Address(AccountAddress),
     /// An unsigned 8-bit integer
     U8(u8),
+    /// An unsigned 16-bit integer
+    U16(u16),
+    /// An unsigned 32-bit integer
+    U32(u32),
     /// An unsigned 64-bit integer
     U64(u64),
     /// An unsigned 128-bit integer
     U128(u128),
+    /// An unsigned 256-bit integer
+    U256(U256),
     /// true or false
     Bool(bool),
     /// `b"<bytes>"`
@@ -646,11 +672,17 @@ pub enum Bytecode_ {
     BrFalse(BlockLabel_),
     Branch(BlockLabel_),
     LdU8(u8),
+    LdU16(u16),
+    LdU32(u32),
     LdU64(u64),
     LdU128(u128),
+    LdU256(U256),
     CastU8,
+    CastU16,
+    CastU32,
     CastU64,
     CastU128,
+    CastU256,
     LdTrue,
     LdFalse,
     LdConst(Type, MoveValue),
@@ -661,6 +693,9 @@ pub enum Bytecode_ {
     Call(ModuleName, FunctionName, Vec<Type>),
     Pack(StructName, Vec<Type>),
     Unpack(StructName, Vec<Type>),
+    PackVariant(StructName, VariantName, Vec<Type>),
+    UnpackVariant(StructName, VariantName, Vec<Type>),
+    TestVariant(StructName, VariantName, Vec<Type>),
     ReadRef,
     WriteRef,
     FreezeRef,
@@ -1273,6 +1308,11 @@ impl fmt::Display for StructDefinition_ {
         match &self.fields {
             StructDefinitionFields::Move { fields } => writeln!(f, "{}", format_fields(fields))?,
             StructDefinitionFields::Native => writeln!(f, "{{native}}")?,
+            StructDefinitionFields::Variants { variants } => {
+                for (variant, fields) in variants {
+                    writeln!(f, "{} {{{}}}", variant, format_fields(fields))?
+                }
+            }
         }
         write!(f, ")")
     }
@@ -1314,6 +1354,12 @@ impl fmt::Display for StructName {
     }
 }
 
+impl fmt::Display for VariantName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 impl fmt::Display for FunctionName {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.0)
@@ -1441,8 +1487,11 @@ impl fmt::Display for Type {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Type::U8 => write!(f, "u8"),
+            Type::U16 => write!(f, "u16"),
+            Type::U32 => write!(f, "u32"),
             Type::U64 => write!(f, "u64"),
             Type::U128 => write!(f, "u128"),
+            Type::U256 => write!(f, "u256"),
             Type::Bool => write!(f, "bool"),
             Type::Address => write!(f, "address"),
             Type::Signer => write!(f, "signer"),
@@ -1490,8 +1539,11 @@ impl fmt::Display for Builtin {
             Builtin::VecSwap(tys) => write!(f, "vec_swap{}", format_type_actuals(tys)),
             Builtin::Freeze => write!(f, "freeze"),
             Builtin::ToU8 => write!(f, "to_u8"),
+            Builtin::ToU16 => write!(f, "to_u16"),
+            Builtin::ToU32 => write!(f, "to_u32"),
             Builtin::ToU64 => write!(f, "to_u64"),
             Builtin::ToU128 => write!(f, "to_u128"),
+            Builtin::ToU256 => write!(f, "to_u256"),
         }
     }
 }
@@ -1574,8 +1626,11 @@ impl fmt::Display for CopyableVal_ {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             CopyableVal_::U8(v) => write!(f, "{}u8", v),
+            CopyableVal_::U16(v) => write!(f, "{}u16", v),
+            CopyableVal_::U32(v) => write!(f, "{}u32", v),
             CopyableVal_::U64(v) => write!(f, "{}", v),
             CopyableVal_::U128(v) => write!(f, "{}u128", v),
+            CopyableVal_::U256(v) => write!(f, "{}u256", v),
             CopyableVal_::Bool(v) => write!(f, "{}", v),
             CopyableVal_::ByteArray(v) => write!(f, "0b{}", hex::encode(v)),
             CopyableVal_::Address(v) => write!(f, "0x{}", hex::encode(v)),
@@ -1685,11 +1740,17 @@ impl fmt::Display for Bytecode_ {
             Bytecode_::BrFalse(lbl) => write!(f, "BrFalse {}", &lbl.0),
             Bytecode_::Branch(lbl) => write!(f, "Branch {}", &lbl.0),
             Bytecode_::LdU8(u) => write!(f, "LdU8 {}", u),
+            Bytecode_::LdU16(u) => write!(f, "LdU16 {}", u),
+            Bytecode_::LdU32(u) => write!(f, "LdU32 {}", u),
             Bytecode_::LdU64(u) => write!(f, "LdU64 {}", u),
             Bytecode_::LdU128(u) => write!(f, "LdU128 {}", u),
+            Bytecode_::LdU256(u) => write!(f, "LdU256 {}", u),
             Bytecode_::CastU8 => write!(f, "CastU8"),
+            Bytecode_::CastU16 => write!(f, "CastU16"),
+            Bytecode_::CastU32 => write!(f, "CastU32"),
             Bytecode_::CastU64 => write!(f, "CastU64"),
             Bytecode_::CastU128 => write!(f, "CastU128"),
+            Bytecode_::CastU256 => write!(f, "CastU256"),
             Bytecode_::LdTrue => write!(f, "LdTrue"),
             Bytecode_::LdFalse => write!(f, "LdFalse"),
             Bytecode_::LdConst(ty, v) => write!(f, "LdConst<{}> {}", ty, format_move_value(v)),
@@ -1700,6 +1761,15 @@ impl fmt::Display for Bytecode_ {
             Bytecode_::Call(m, n, tys) => write!(f, "Call {}.{}{}", m, n, format_type_actuals(tys)),
             Bytecode_::Pack(n, tys) => write!(f, "Pack {}{}", n, format_type_actuals(tys)),
             Bytecode_::Unpack(n, tys) => write!(f, "Unpack {}{}", n, format_type_actuals(tys)),
+            Bytecode_::PackVariant(n, v, tys) => {
+                write!(f, "PackVariant {}{}::{}", n, format_type_actuals(tys), v)
+            }
+            Bytecode_::UnpackVariant(n, v, tys) => {
+                write!(f, "UnpackVariant {}{}::{}", n, format_type_actuals(tys), v)
+            }
+            Bytecode_::TestVariant(n, v, tys) => {
+                write!(f, "TestVariant {}{}::{}", n, format_type_actuals(tys), v)
+            }
             Bytecode_::ReadRef => write!(f, "ReadRef"),
             Bytecode_::WriteRef => write!(f, "WriteRef"),
             Bytecode_::FreezeRef => write!(f, "FreezeRef"),
@@ -1763,8 +1833,11 @@ impl fmt::Display for Bytecode_ {
 fn format_move_value(v: &MoveValue) -> String {
     match v {
         MoveValue::U8(u) => format!("{}u8", u),
+        MoveValue::U16(u) => format!("{}u16", u),
+        MoveValue::U32(u) => format!("{}u32", u),
         MoveValue::U64(u) => format!("{}u64", u),
         MoveValue::U128(u) => format!("{}u128", u),
+        MoveValue::U256(u) => format!("{}u256", u),
         MoveValue::Bool(true) => "true".to_owned(),
         MoveValue::Bool(false) => "false".to_owned(),
         MoveValue::Address(a) => format!("0x{}", a.short_str_lossless()),
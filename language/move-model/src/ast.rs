@@ -66,6 +66,7 @@ pub struct SpecFunDecl {
 #[derive(Debug, Clone)]
 pub enum AttributeValue {
     Value(NodeId, Value),
+    Vector(NodeId, Vec<AttributeValue>),
     Name(NodeId, Option<ModuleName>, Symbol),
 }
 
@@ -899,8 +900,11 @@ pub enum Operation {
     InRangeVec,
     RangeVec,
     MaxU8,
+    MaxU16,
+    MaxU32,
     MaxU64,
     MaxU128,
+    MaxU256,
 
     // Functions which support the transformation and translation process.
     AbortFlag,
@@ -7,7 +7,7 @@ use std::{
 };
 
 use itertools::Itertools;
-use num::{BigInt, BigUint, FromPrimitive, Zero};
+use num::{bigint::Sign, BigInt, BigUint, FromPrimitive, Zero};
 
 use move_compiler::{
     expansion::ast as EA, hlir::ast as HA, naming::ast as NA, parser::ast as PA, shared::Name,
@@ -547,8 +547,11 @@ impl<'env, 'translator, 'module_translator> ExpTranslator<'env, 'translator, 'mo
                         Address => Type::new_prim(PrimitiveType::Address),
                         Signer => Type::new_prim(PrimitiveType::Signer),
                         U8 => Type::new_prim(PrimitiveType::U8),
+                        U16 => Type::new_prim(PrimitiveType::U16),
+                        U32 => Type::new_prim(PrimitiveType::U32),
                         U64 => Type::new_prim(PrimitiveType::U64),
                         U128 => Type::new_prim(PrimitiveType::U128),
+                        U256 => Type::new_prim(PrimitiveType::U256),
                         Vector => Type::Vector(Box::new(self.translate_hlir_base_type(&args[0]))),
                         Bool => Type::new_prim(PrimitiveType::Bool),
                     },
@@ -612,10 +615,15 @@ impl<'env, 'translator, 'module_translator> ExpTranslator<'env, 'translator, 'mo
                             return check_zero_args(self, Type::new_prim(PrimitiveType::Bool));
                         }
                         "u8" => return check_zero_args(self, Type::new_prim(PrimitiveType::U8)),
+                        "u16" => return check_zero_args(self, Type::new_prim(PrimitiveType::U16)),
+                        "u32" => return check_zero_args(self, Type::new_prim(PrimitiveType::U32)),
                         "u64" => return check_zero_args(self, Type::new_prim(PrimitiveType::U64)),
                         "u128" => {
                             return check_zero_args(self, Type::new_prim(PrimitiveType::U128));
                         }
+                        "u256" => {
+                            return check_zero_args(self, Type::new_prim(PrimitiveType::U256));
+                        }
                         "num" => return check_zero_args(self, Type::new_prim(PrimitiveType::Num)),
                         "range" => {
                             return check_zero_args(self, Type::new_prim(PrimitiveType::Range));
@@ -831,14 +839,26 @@ impl<'env, 'translator, 'module_translator> ExpTranslator<'env, 'translator, 'mo
                 Value::Number(BigInt::from_u8(*x).unwrap()),
                 Type::new_prim(PrimitiveType::U8),
             )),
+            EA::Value_::U16(x) => Some((
+                Value::Number(BigInt::from_u16(*x).unwrap()),
+                Type::new_prim(PrimitiveType::U16),
+            )),
+            EA::Value_::U32(x) => Some((
+                Value::Number(BigInt::from_u32(*x).unwrap()),
+                Type::new_prim(PrimitiveType::U32),
+            )),
             EA::Value_::U64(x) => Some((
                 Value::Number(BigInt::from_u64(*x).unwrap()),
                 Type::new_prim(PrimitiveType::U64),
             )),
-            EA::Value_::InferredNum(x) | EA::Value_::U128(x) => Some((
+            EA::Value_::U128(x) => Some((
                 Value::Number(BigInt::from_u128(*x).unwrap()),
                 Type::new_prim(PrimitiveType::U128),
             )),
+            EA::Value_::InferredNum(x) | EA::Value_::U256(x) => Some((
+                Value::Number(BigInt::from_bytes_le(Sign::Plus, &x.to_le_bytes())),
+                Type::new_prim(PrimitiveType::U256),
+            )),
             EA::Value_::Bool(x) => Some((Value::Bool(*x), Type::new_prim(PrimitiveType::Bool))),
             EA::Value_::Bytearray(x) => {
                 let ty = Type::Vector(Box::new(Type::new_prim(PrimitiveType::U8)));
@@ -1908,8 +1928,13 @@ impl<'env, 'translator, 'module_translator> ExpTranslator<'env, 'translator, 'mo
     pub fn translate_from_move_value(&self, loc: &Loc, value: &MoveValue) -> Value {
         match value {
             MoveValue::U8(n) => Value::Number(BigInt::from_u8(*n).unwrap()),
+            MoveValue::U16(n) => Value::Number(BigInt::from_u16(*n).unwrap()),
+            MoveValue::U32(n) => Value::Number(BigInt::from_u32(*n).unwrap()),
             MoveValue::U64(n) => Value::Number(BigInt::from_u64(*n).unwrap()),
             MoveValue::U128(n) => Value::Number(BigInt::from_u128(*n).unwrap()),
+            MoveValue::U256(n) => {
+                Value::Number(BigInt::from_bytes_le(Sign::Plus, &n.to_le_bytes()))
+            }
             MoveValue::Bool(b) => Value::Bool(*b),
             MoveValue::Address(a) => Value::Address(crate::addr_to_big_uint(a)),
             MoveValue::Signer(a) => Value::Address(crate::addr_to_big_uint(a)),
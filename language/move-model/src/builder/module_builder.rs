@@ -270,49 +270,57 @@ impl<'env, 'translator> ModuleBuilder<'env, 'translator> {
                 Attribute::Apply(node_id, sym, self.translate_attributes(vs))
             }
             EA::Attribute_::Assigned(n, v) => {
-                let value_node_id = self
-                    .parent
-                    .env
-                    .new_node(self.parent.to_loc(&v.loc), Type::Tuple(vec![]));
-                let v = match &v.value {
-                    EA::AttributeValue_::Value(val) => {
-                        let val =
-                            if let Some((val, _)) = ExpTranslator::new(self).translate_value(val) {
-                                val
-                            } else {
-                                // Error reported
-                                Value::Bool(false)
-                            };
-                        AttributeValue::Value(value_node_id, val)
-                    }
-                    EA::AttributeValue_::ModuleAccess(macc) => match macc.value {
-                        EA::ModuleAccess_::Name(n) => AttributeValue::Name(
-                            value_node_id,
-                            None,
-                            self.symbol_pool().make(n.value.as_str()),
-                        ),
-                        EA::ModuleAccess_::ModuleAccess(mident, n) => {
-                            let addr_bytes = self.parent.resolve_address(
-                                &self.parent.to_loc(&macc.loc),
-                                &mident.value.address,
-                            );
-                            let module_name = ModuleName::from_address_bytes_and_name(
-                                addr_bytes,
-                                self.symbol_pool()
-                                    .make(mident.value.module.0.value.as_str()),
-                            );
-                            AttributeValue::Name(
-                                value_node_id,
-                                Some(module_name),
-                                self.symbol_pool().make(n.value.as_str()),
-                            )
-                        }
-                    },
-                };
+                let v = self.translate_attribute_value(v);
                 Attribute::Assign(node_id, self.symbol_pool().make(n.value.as_str()), v)
             }
         }
     }
+
+    fn translate_attribute_value(&mut self, v: &EA::AttributeValue) -> AttributeValue {
+        let value_node_id = self
+            .parent
+            .env
+            .new_node(self.parent.to_loc(&v.loc), Type::Tuple(vec![]));
+        match &v.value {
+            EA::AttributeValue_::Value(val) => {
+                let val = if let Some((val, _)) = ExpTranslator::new(self).translate_value(val) {
+                    val
+                } else {
+                    // Error reported
+                    Value::Bool(false)
+                };
+                AttributeValue::Value(value_node_id, val)
+            }
+            EA::AttributeValue_::Vector(vs) => AttributeValue::Vector(
+                value_node_id,
+                vs.iter()
+                    .map(|v| self.translate_attribute_value(v))
+                    .collect(),
+            ),
+            EA::AttributeValue_::ModuleAccess(macc) => match macc.value {
+                EA::ModuleAccess_::Name(n) => AttributeValue::Name(
+                    value_node_id,
+                    None,
+                    self.symbol_pool().make(n.value.as_str()),
+                ),
+                EA::ModuleAccess_::ModuleAccess(mident, n) => {
+                    let addr_bytes = self
+                        .parent
+                        .resolve_address(&self.parent.to_loc(&macc.loc), &mident.value.address);
+                    let module_name = ModuleName::from_address_bytes_and_name(
+                        addr_bytes,
+                        self.symbol_pool()
+                            .make(mident.value.module.0.value.as_str()),
+                    );
+                    AttributeValue::Name(
+                        value_node_id,
+                        Some(module_name),
+                        self.symbol_pool().make(n.value.as_str()),
+                    )
+                }
+            },
+        }
+    }
 }
 
 /// # Declaration Analysis
@@ -327,7 +335,12 @@ impl<'env, 'translator> ModuleBuilder<'env, 'translator> {
         for (name, struct_def) in module_def.structs.key_cloned_iter() {
             self.decl_ana_struct(&name, struct_def);
         }
-        for (name, fun_def) in module_def.functions.key_cloned_iter() {
+        // Inline functions are expanded at their call sites and have no bytecode of their own
+        for (name, fun_def) in module_def
+            .functions
+            .key_cloned_iter()
+            .filter(|(_, f)| !f.inline)
+        {
             self.decl_ana_fun(&name, fun_def);
         }
         for (name, const_def) in module_def.constants.key_cloned_iter() {
@@ -359,9 +372,16 @@ impl<'env, 'translator> ModuleBuilder<'env, 'translator> {
         let loc = et.to_loc(&def.loc);
         let value = et.translate_from_move_value(&loc, &move_value);
         let ty = et.translate_type(&def.signature);
-        et.parent
-            .parent
-            .define_const(qsym, ConstEntry { loc, ty, value });
+        let attributes = et.parent.translate_attributes(&def.attributes);
+        et.parent.parent.define_const(
+            qsym,
+            ConstEntry {
+                loc,
+                ty,
+                value,
+                attributes,
+            },
+        );
     }
 
     fn decl_ana_struct(&mut self, name: &PA::StructName, def: &EA::StructDefinition) {
@@ -649,14 +669,20 @@ impl<'env, 'translator> ModuleBuilder<'env, 'translator> {
         }
 
         // Analyze all functions.
-        for (idx, (name, fun_def)) in module_def.functions.key_cloned_iter().enumerate() {
+        let functions = || {
+            module_def
+                .functions
+                .key_cloned_iter()
+                .filter(|(_, f)| !f.inline)
+        };
+        for (idx, (name, fun_def)) in functions().enumerate() {
             self.def_ana_fun(&name, &fun_def.body, idx);
         }
 
         // Propagate the impurity of functions: a Move function which calls an
         // impure Move function is also considered impure.
         let mut visited = BTreeMap::new();
-        for (idx, (name, _)) in module_def.functions.key_cloned_iter().enumerate() {
+        for (idx, (name, _)) in functions().enumerate() {
             let is_pure = self.propagate_function_impurity(&mut visited, SpecFunId::new(idx));
             let full_name = self.qualified_by_module_from_name(&name.0);
             if is_pure {
@@ -723,7 +749,7 @@ impl<'env, 'translator> ModuleBuilder<'env, 'translator> {
         }
 
         // Analyze in-function spec blocks.
-        for (name, fun_def) in module_def.functions.key_cloned_iter() {
+        for (name, fun_def) in functions() {
             let fun_spec_info = &function_infos.get(&name).unwrap().spec_info;
             let qsym = self.qualified_by_module_from_name(&name.0);
             for (spec_id, spec_block) in fun_def.specs.iter() {
@@ -840,7 +866,8 @@ impl<'env, 'translator> ModuleBuilder<'env, 'translator> {
                 }
                 Some(field_map)
             }
-            EA::StructFields::Native(_) => None,
+            // Enums can only be inspected by a `match`, which specifications do not support
+            EA::StructFields::Native(_) | EA::StructFields::Variants(_) => None,
         };
         self.parent
             .struct_table
@@ -3151,12 +3178,21 @@ impl<'env, 'translator> ModuleBuilder<'env, 'translator> {
             .iter()
             .filter(|(name, _)| name.module_name == self.module_name)
             .map(|(name, const_entry)| {
-                let ConstEntry { loc, value, ty } = const_entry.clone();
+                let ConstEntry {
+                    loc,
+                    value,
+                    ty,
+                    attributes,
+                } = const_entry.clone();
                 (
                     NamedConstantId::new(name.symbol),
-                    self.parent
-                        .env
-                        .create_named_constant_data(name.symbol, loc, ty, value),
+                    self.parent.env.create_named_constant_data(
+                        name.symbol,
+                        loc,
+                        ty,
+                        value,
+                        attributes,
+                    ),
                 )
             })
             .collect();
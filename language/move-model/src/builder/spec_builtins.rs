@@ -25,6 +25,7 @@ pub(crate) fn declare_spec_builtins(trans: &mut ModelBuilder<'_>) {
         loc: loc.clone(),
         ty: num_t.clone(),
         value: Value::Number(value),
+        attributes: vec![],
     };
 
     {
@@ -33,6 +34,14 @@ pub(crate) fn declare_spec_builtins(trans: &mut ModelBuilder<'_>) {
             trans.builtin_qualified_symbol("MAX_U8"),
             mk_num_const(BigInt::from(u8::MAX)),
         );
+        trans.define_const(
+            trans.builtin_qualified_symbol("MAX_U16"),
+            mk_num_const(BigInt::from(u16::MAX)),
+        );
+        trans.define_const(
+            trans.builtin_qualified_symbol("MAX_U32"),
+            mk_num_const(BigInt::from(u32::MAX)),
+        );
         trans.define_const(
             trans.builtin_qualified_symbol("MAX_U64"),
             mk_num_const(BigInt::from(u64::MAX)),
@@ -41,6 +50,10 @@ pub(crate) fn declare_spec_builtins(trans: &mut ModelBuilder<'_>) {
             trans.builtin_qualified_symbol("MAX_U128"),
             mk_num_const(BigInt::from(u128::MAX)),
         );
+        trans.define_const(
+            trans.builtin_qualified_symbol("MAX_U256"),
+            mk_num_const((BigInt::from(1) << 256) - 1),
+        );
         trans.define_const(
             trans.builtin_qualified_symbol("EXECUTION_FAILURE"),
             mk_num_const(BigInt::from(-1)),
@@ -138,6 +151,28 @@ pub(crate) fn declare_spec_builtins(trans: &mut ModelBuilder<'_>) {
             },
         );
 
+        trans.define_spec_fun(
+            trans.builtin_qualified_symbol("max_u16"),
+            SpecFunEntry {
+                loc: loc.clone(),
+                oper: Operation::MaxU16,
+                type_params: vec![],
+                arg_types: vec![],
+                result_type: num_t.clone(),
+            },
+        );
+
+        trans.define_spec_fun(
+            trans.builtin_qualified_symbol("max_u32"),
+            SpecFunEntry {
+                loc: loc.clone(),
+                oper: Operation::MaxU32,
+                type_params: vec![],
+                arg_types: vec![],
+                result_type: num_t.clone(),
+            },
+        );
+
         trans.define_spec_fun(
             trans.builtin_qualified_symbol("max_u64"),
             SpecFunEntry {
@@ -160,6 +195,17 @@ pub(crate) fn declare_spec_builtins(trans: &mut ModelBuilder<'_>) {
             },
         );
 
+        trans.define_spec_fun(
+            trans.builtin_qualified_symbol("max_u256"),
+            SpecFunEntry {
+                loc: loc.clone(),
+                oper: Operation::MaxU256,
+                type_params: vec![],
+                arg_types: vec![],
+                result_type: num_t.clone(),
+            },
+        );
+
         // Vectors
         trans.define_spec_fun(
             trans.builtin_qualified_symbol("len"),
@@ -40,8 +40,9 @@ use move_binary_format::{
     binary_views::BinaryIndexedView,
     file_format::{
         AddressIdentifierIndex, Bytecode, Constant as VMConstant, ConstantPoolIndex,
-        FunctionDefinitionIndex, FunctionHandleIndex, SignatureIndex, SignatureToken,
-        StructDefinitionIndex, StructFieldInformation, StructHandleIndex, Visibility,
+        FieldDefinition, FunctionDefinitionIndex, FunctionHandleIndex, SignatureIndex,
+        SignatureToken, StructDefinitionIndex, StructFieldInformation, StructHandleIndex,
+        Visibility,
     },
     normalized::Type as MType,
     views::{
@@ -61,8 +62,8 @@ use move_disassembler::disassembler::{Disassembler, DisassemblerOptions};
 
 use crate::{
     ast::{
-        ConditionKind, Exp, ExpData, GlobalInvariant, ModuleName, PropertyBag, PropertyValue, Spec,
-        SpecBlockInfo, SpecFunDecl, SpecVarDecl, Value,
+        AttributeValue, ConditionKind, Exp, ExpData, GlobalInvariant, ModuleName, PropertyBag,
+        PropertyValue, Spec, SpecBlockInfo, SpecFunDecl, SpecVarDecl, Value,
     },
     pragmas::{
         DELEGATE_INVARIANTS_TO_CALLER_PRAGMA, DISABLE_INVARIANTS_IN_BODY_PRAGMA, FRIEND_PRAGMA,
@@ -77,6 +78,7 @@ use crate::ast::Attribute;
 use move_binary_format::file_format::CodeOffset;
 pub use move_binary_format::file_format::{AbilitySet, Visibility as FunctionVisibility};
 use move_command_line_common::address::NumericalAddress;
+use move_compiler::shared::known_attributes::DeprecationAttribute;
 
 // =================================================================================================
 /// # Constants
@@ -1138,12 +1140,14 @@ impl GlobalEnv {
         loc: Loc,
         typ: Type,
         value: Value,
+        attributes: Vec<Attribute>,
     ) -> NamedConstantData {
         NamedConstantData {
             name,
             loc,
             typ,
             value,
+            attributes,
         }
     }
 
@@ -1188,20 +1192,34 @@ impl GlobalEnv {
         spec: Spec,
     ) -> StructData {
         let handle_idx = module.struct_def_at(def_idx).struct_handle;
-        let field_data = if let StructFieldInformation::Declared(fields) =
-            &module.struct_def_at(def_idx).field_information
-        {
+        let make_field_data = |fields: &[FieldDefinition], variant: Option<usize>| {
             let mut map = BTreeMap::new();
             for (offset, field) in fields.iter().enumerate() {
                 let name = self
                     .symbol_pool
                     .make(module.identifier_at(field.name).as_str());
-                let info = FieldInfo::Declared { def_idx };
+                let info = FieldInfo::Declared { def_idx, variant };
                 map.insert(FieldId(name), FieldData { name, offset, info });
             }
             map
-        } else {
-            BTreeMap::new()
+        };
+        let (field_data, variant_data) = match &module.struct_def_at(def_idx).field_information {
+            StructFieldInformation::Native => (BTreeMap::new(), vec![]),
+            StructFieldInformation::Declared(fields) => (make_field_data(fields, None), vec![]),
+            StructFieldInformation::DeclaredVariants(variants) => {
+                let variant_data = variants
+                    .iter()
+                    .enumerate()
+                    .map(|(index, variant)| VariantData {
+                        name: self
+                            .symbol_pool
+                            .make(module.identifier_at(variant.name).as_str()),
+                        index,
+                        field_data: make_field_data(&variant.fields, Some(index)),
+                    })
+                    .collect();
+                (BTreeMap::new(), variant_data)
+            }
         };
         let info = StructInfo::Declared {
             def_idx,
@@ -1213,6 +1231,7 @@ impl GlobalEnv {
             attributes,
             info,
             field_data,
+            variant_data,
             spec,
         }
     }
@@ -1251,6 +1270,7 @@ impl GlobalEnv {
             attributes: Default::default(),
             info: StructInfo::Generated { spec_var: var_id },
             field_data,
+            variant_data: vec![],
             spec: Spec::default(),
         }
     }
@@ -1447,6 +1467,36 @@ impl GlobalEnv {
             .unwrap_or("")
     }
 
+    /// Returns the `deprecated` attribute among `attributes`, if any.
+    fn get_deprecated_attribute<'a>(&self, attributes: &'a [Attribute]) -> Option<&'a Attribute> {
+        attributes.iter().find(|attr| match attr {
+            Attribute::Apply(_, name, _) | Attribute::Assign(_, name, _) => {
+                self.symbol_pool().string(*name).as_str() == DeprecationAttribute::DEPRECATED
+            }
+        })
+    }
+
+    /// Returns true if an item with `attributes` is annotated with `#[deprecated]`.
+    pub fn is_deprecated(&self, attributes: &[Attribute]) -> bool {
+        self.get_deprecated_attribute(attributes).is_some()
+    }
+
+    /// Returns the note given with `#[deprecated(note = b"...")]` among `attributes`, if any.
+    pub fn get_deprecation_note(&self, attributes: &[Attribute]) -> Option<String> {
+        let args = match self.get_deprecated_attribute(attributes)? {
+            Attribute::Apply(_, _, args) => args,
+            Attribute::Assign(..) => return None,
+        };
+        args.iter().find_map(|arg| match arg {
+            Attribute::Assign(_, name, AttributeValue::Value(_, Value::ByteArray(bytes)))
+                if self.symbol_pool().string(*name).as_str() == DeprecationAttribute::NOTE =>
+            {
+                Some(String::from_utf8_lossy(bytes).into_owned())
+            }
+            _ => None,
+        })
+    }
+
     /// Returns true if the boolean property is true.
     pub fn is_property_true(&self, properties: &PropertyBag, name: &str) -> Option<bool> {
         let sym = &self.symbol_pool().make(name);
@@ -2186,8 +2236,11 @@ impl<'env> ModuleEnv<'env> {
         match sig {
             SignatureToken::Bool => Type::Primitive(PrimitiveType::Bool),
             SignatureToken::U8 => Type::Primitive(PrimitiveType::U8),
+            SignatureToken::U16 => Type::Primitive(PrimitiveType::U16),
+            SignatureToken::U32 => Type::Primitive(PrimitiveType::U32),
             SignatureToken::U64 => Type::Primitive(PrimitiveType::U64),
             SignatureToken::U128 => Type::Primitive(PrimitiveType::U128),
+            SignatureToken::U256 => Type::Primitive(PrimitiveType::U256),
             SignatureToken::Address => Type::Primitive(PrimitiveType::Address),
             SignatureToken::Signer => Type::Primitive(PrimitiveType::Signer),
             SignatureToken::Reference(t) => {
@@ -2392,6 +2445,10 @@ pub struct StructData {
     /// Field definitions.
     field_data: BTreeMap<FieldId, FieldData>,
 
+    /// Variant definitions, ordered by index. Only enums have variants, and they have no fields
+    /// besides those of their variants.
+    variant_data: Vec<VariantData>,
+
     // Associated specification.
     spec: Spec,
 }
@@ -2615,6 +2672,32 @@ impl<'env> StructEnv<'env> {
         unreachable!("invalid field lookup")
     }
 
+    /// Determines whether this struct is an enum.
+    pub fn is_enum(&self) -> bool {
+        !self.data.variant_data.is_empty()
+    }
+
+    /// Get an iterator for the variants of an enum, ordered by index.
+    pub fn get_variants(&'env self) -> impl Iterator<Item = VariantEnv<'env>> {
+        self.data.variant_data.iter().map(move |data| VariantEnv {
+            struct_env: self.clone(),
+            data,
+        })
+    }
+
+    /// Gets a variant of an enum by its index.
+    pub fn get_variant(&'env self, index: usize) -> VariantEnv<'env> {
+        let data = self
+            .data
+            .variant_data
+            .get(index)
+            .expect("invalid variant lookup");
+        VariantEnv {
+            struct_env: self.clone(),
+            data,
+        }
+    }
+
     /// Whether the type parameter at position `idx` is declared as phantom.
     pub fn is_phantom_parameter(&self, idx: usize) -> bool {
         match &self.data.info {
@@ -2746,6 +2829,8 @@ enum FieldInfo {
     Declared {
         /// The struct definition index of this field in its VM module.
         def_idx: StructDefinitionIndex,
+        /// The index of the variant declaring this field, if the struct is an enum.
+        variant: Option<usize>,
     },
     /// The field is generated by the prover.
     Generated { type_: Type },
@@ -2771,25 +2856,44 @@ impl<'env> FieldEnv<'env> {
         FieldId(self.data.name)
     }
 
-    /// Returns the VM identifier for this field
-    pub fn get_identifier(&'env self) -> Option<Identifier> {
-        if let FieldInfo::Declared { def_idx } = &self.data.info {
-            let m = &self.struct_env.module_env.data.module;
-            let def = m.struct_def_at(*def_idx);
-            let offset = self.data.offset;
-            Some(
-                FieldDefinitionView::new(m, def.field(offset).expect("Bad field offset"))
-                    .name()
-                    .to_owned(),
-            )
+    /// Returns the VM definition of this field, if it is declared in Move.
+    fn get_definition(&self) -> Option<&'env FieldDefinition> {
+        if let FieldInfo::Declared { def_idx, variant } = &self.data.info {
+            let def = self
+                .struct_env
+                .module_env
+                .data
+                .module
+                .struct_def_at(*def_idx);
+            let fields = match (&def.field_information, variant) {
+                (StructFieldInformation::Declared(fields), None) => fields,
+                (StructFieldInformation::DeclaredVariants(variants), Some(variant)) => {
+                    &variants[*variant].fields
+                }
+                _ => unreachable!("invalid field lookup"),
+            };
+            Some(&fields[self.data.offset])
         } else {
             None
         }
     }
 
+    /// Returns the VM identifier for this field
+    pub fn get_identifier(&'env self) -> Option<Identifier> {
+        self.get_definition().map(|field| {
+            FieldDefinitionView::new(&self.struct_env.module_env.data.module, field)
+                .name()
+                .to_owned()
+        })
+    }
+
     /// Get documentation associated with this field.
     pub fn get_doc(&self) -> &str {
-        if let FieldInfo::Declared { def_idx } = &self.data.info {
+        if let FieldInfo::Declared {
+            def_idx,
+            variant: None,
+        } = &self.data.info
+        {
             if let Ok(smap) = self
                 .struct_env
                 .module_env
@@ -2813,23 +2917,13 @@ impl<'env> FieldEnv<'env> {
 
     /// Gets the type of this field.
     pub fn get_type(&self) -> Type {
-        match &self.data.info {
-            FieldInfo::Declared { def_idx } => {
-                let struct_def = self
-                    .struct_env
-                    .module_env
-                    .data
-                    .module
-                    .struct_def_at(*def_idx);
-                let field = match &struct_def.field_information {
-                    StructFieldInformation::Declared(fields) => &fields[self.data.offset],
-                    StructFieldInformation::Native => unreachable!(),
-                };
-                self.struct_env
-                    .module_env
-                    .globalize_signature(&field.signature.0)
-            }
-            FieldInfo::Generated { type_ } => type_.clone(),
+        match (&self.data.info, self.get_definition()) {
+            (FieldInfo::Declared { .. }, Some(field)) => self
+                .struct_env
+                .module_env
+                .globalize_signature(&field.signature.0),
+            (FieldInfo::Generated { type_ }, _) => type_.clone(),
+            (FieldInfo::Declared { .. }, None) => unreachable!(),
         }
     }
 
@@ -2839,6 +2933,59 @@ impl<'env> FieldEnv<'env> {
     }
 }
 
+// =================================================================================================
+/// # Variant Environment
+
+#[derive(Debug)]
+pub struct VariantData {
+    /// The name of this variant.
+    name: Symbol,
+
+    /// The index of this variant in its enum.
+    index: usize,
+
+    /// Field definitions.
+    field_data: BTreeMap<FieldId, FieldData>,
+}
+
+#[derive(Debug)]
+pub struct VariantEnv<'env> {
+    /// Reference to enclosing enum.
+    pub struct_env: StructEnv<'env>,
+
+    /// Reference to the variant data.
+    data: &'env VariantData,
+}
+
+impl<'env> VariantEnv<'env> {
+    /// Gets the name of this variant.
+    pub fn get_name(&self) -> Symbol {
+        self.data.name
+    }
+
+    /// Gets the index of this variant in its enum.
+    pub fn get_index(&self) -> usize {
+        self.data.index
+    }
+
+    /// Get an iterator for the fields of this variant, ordered by offset.
+    pub fn get_fields(&self) -> impl Iterator<Item = FieldEnv<'env>> + '_ {
+        self.data
+            .field_data
+            .values()
+            .sorted_by_key(|data| data.offset)
+            .map(move |data| FieldEnv {
+                struct_env: self.struct_env.clone(),
+                data,
+            })
+    }
+
+    /// Return the number of fields of this variant.
+    pub fn get_field_count(&self) -> usize {
+        self.data.field_data.len()
+    }
+}
+
 // =================================================================================================
 /// # Named Constant Environment
 
@@ -2855,6 +3002,9 @@ pub struct NamedConstantData {
 
     /// The value of this constant
     value: Value,
+
+    /// Attributes attached to this constant
+    attributes: Vec<Attribute>,
 }
 
 #[derive(Debug)]
@@ -2895,6 +3045,11 @@ impl<'env> NamedConstantEnv<'env> {
     pub fn get_value(&self) -> Value {
         self.data.value.clone()
     }
+
+    /// Returns the attributes of this constant.
+    pub fn get_attributes(&self) -> &[Attribute] {
+        &self.data.attributes
+    }
 }
 
 // =================================================================================================
@@ -9,7 +9,7 @@ use move_model::{
     ast::{MemoryLabel, TempIndex},
     model::{
         FieldEnv, FunctionEnv, GlobalEnv, ModuleEnv, QualifiedInstId, SpecFunId, StructEnv,
-        StructId, SCRIPT_MODULE_NAME,
+        StructId, VariantEnv, SCRIPT_MODULE_NAME,
     },
     symbol::Symbol,
     ty::{PrimitiveType, Type},
@@ -60,6 +60,36 @@ pub fn boogie_field_sel(field_env: &FieldEnv<'_>, inst: &[Type]) -> String {
     )
 }
 
+/// Return boogie name of the constructor of given enum variant.
+pub fn boogie_variant_name(variant_env: &VariantEnv<'_>, inst: &[Type]) -> String {
+    let struct_env = &variant_env.struct_env;
+    format!(
+        "{}${}",
+        boogie_struct_name(struct_env, inst),
+        variant_env.get_name().display(struct_env.symbol_pool())
+    )
+}
+
+/// Return boogie tester for given enum variant.
+pub fn boogie_variant_test(variant_env: &VariantEnv<'_>, inst: &[Type]) -> String {
+    format!("is#{}", boogie_variant_name(variant_env, inst))
+}
+
+/// Return field selector for given field of given enum variant.
+pub fn boogie_variant_field_sel(
+    variant_env: &VariantEnv<'_>,
+    field_env: &FieldEnv<'_>,
+    inst: &[Type],
+) -> String {
+    format!(
+        "${}#{}",
+        field_env
+            .get_name()
+            .display(variant_env.struct_env.symbol_pool()),
+        boogie_variant_name(variant_env, inst)
+    )
+}
+
 /// Return field selector for given field.
 pub fn boogie_field_update(field_env: &FieldEnv<'_>, inst: &[Type]) -> String {
     let struct_env = &field_env.struct_env;
@@ -183,7 +213,7 @@ pub fn boogie_type(env: &GlobalEnv, ty: &Type) -> String {
     use Type::*;
     match ty {
         Primitive(p) => match p {
-            U8 | U64 | U128 | Num | Address => "int".to_string(),
+            U8 | U16 | U32 | U64 | U128 | U256 | Num | Address => "int".to_string(),
             Signer => "$signer".to_string(),
             Bool => "bool".to_string(),
             _ => panic!("unexpected type"),
@@ -217,8 +247,11 @@ pub fn boogie_type_suffix(env: &GlobalEnv, ty: &Type) -> String {
     match ty {
         Primitive(p) => match p {
             U8 => "u8".to_string(),
+            U16 => "u16".to_string(),
+            U32 => "u32".to_string(),
             U64 => "u64".to_string(),
             U128 => "u128".to_string(),
+            U256 => "u256".to_string(),
             Num => "num".to_string(),
             Address => "address".to_string(),
             Signer => "signer".to_string(),
@@ -21,6 +21,7 @@ use pretty::RcDoc;
 use regex::Regex;
 
 use move_binary_format::file_format::FunctionDefinitionIndex;
+use move_core_types::u256::U256;
 use move_model::{
     ast::TempIndex,
     code_writer::CodeWriter,
@@ -1346,6 +1347,14 @@ impl ModelValue {
                 "{}u8",
                 self.extract_literal().and_then(|s| s.parse::<u8>().ok())?
             ))),
+            Type::Primitive(PrimitiveType::U16) => Some(PrettyDoc::text(format!(
+                "{}u16",
+                self.extract_literal().and_then(|s| s.parse::<u16>().ok())?
+            ))),
+            Type::Primitive(PrimitiveType::U32) => Some(PrettyDoc::text(format!(
+                "{}u32",
+                self.extract_literal().and_then(|s| s.parse::<u32>().ok())?
+            ))),
             Type::Primitive(PrimitiveType::U64) => Some(PrettyDoc::text(
                 self.extract_literal()
                     .and_then(|s| s.parse::<u64>().ok())?
@@ -1356,6 +1365,11 @@ impl ModelValue {
                 self.extract_literal()
                     .and_then(|s| s.parse::<u128>().ok())?
             ))),
+            Type::Primitive(PrimitiveType::U256) => Some(PrettyDoc::text(format!(
+                "{}u256",
+                self.extract_literal()
+                    .and_then(|s| s.parse::<U256>().ok())?
+            ))),
             Type::Primitive(PrimitiveType::Num) => Some(PrettyDoc::text(format!(
                 "{}num",
                 self.extract_literal()
@@ -12,7 +12,7 @@ use log::{debug, info, log, warn, Level};
 use move_model::{
     code_writer::CodeWriter,
     emit, emitln,
-    model::{GlobalEnv, QualifiedInstId, StructEnv, StructId},
+    model::{FieldEnv, GlobalEnv, QualifiedInstId, StructEnv, StructId},
     pragmas::{ADDITION_OVERFLOW_UNCHECKED_PRAGMA, SEED_PRAGMA, TIMEOUT_PRAGMA},
     ty::{PrimitiveType, Type},
 };
@@ -30,6 +30,7 @@ use crate::{
         boogie_function_name, boogie_make_vec_from_strings, boogie_modifies_memory_name,
         boogie_resource_memory_name, boogie_struct_name, boogie_temp, boogie_type,
         boogie_type_param, boogie_type_suffix, boogie_type_suffix_for_struct,
+        boogie_variant_field_sel, boogie_variant_name, boogie_variant_test,
         boogie_well_formed_check, boogie_well_formed_expr,
     },
     options::BoogieOptions,
@@ -254,24 +255,38 @@ impl<'env> StructTranslator<'env> {
         let struct_name = boogie_struct_name(struct_env, self.type_inst);
         emitln!(writer, "type {{:datatype}} {};", struct_name);
 
-        // Emit constructor
-        let fields = struct_env
-            .get_fields()
-            .map(|field| {
-                format!(
-                    "${}: {}",
-                    field.get_name().display(env.symbol_pool()),
-                    boogie_type(env, &self.inst(&field.get_type()))
-                )
-            })
-            .join(", ");
-        emitln!(
-            writer,
-            "function {{:constructor}} {}({}): {};",
-            struct_name,
-            fields,
-            struct_name
-        );
+        // Emit constructors, one per variant for an enum
+        let constructor_params = |fields: Vec<FieldEnv<'_>>| {
+            fields
+                .iter()
+                .map(|field| {
+                    format!(
+                        "${}: {}",
+                        field.get_name().display(env.symbol_pool()),
+                        boogie_type(env, &self.inst(&field.get_type()))
+                    )
+                })
+                .join(", ")
+        };
+        if struct_env.is_enum() {
+            for variant in struct_env.get_variants() {
+                emitln!(
+                    writer,
+                    "function {{:constructor}} {}({}): {};",
+                    boogie_variant_name(&variant, self.type_inst),
+                    constructor_params(variant.get_fields().collect_vec()),
+                    struct_name
+                );
+            }
+        } else {
+            emitln!(
+                writer,
+                "function {{:constructor}} {}({}): {};",
+                struct_name,
+                constructor_params(struct_env.get_fields().collect_vec()),
+                struct_name
+            );
+        }
 
         let suffix = boogie_type_suffix_for_struct(struct_env, self.type_inst);
 
@@ -312,6 +327,35 @@ impl<'env> StructTranslator<'env> {
             || {
                 if struct_env.is_native_or_intrinsic() {
                     emitln!(writer, "true")
+                } else if struct_env.is_enum() {
+                    // The fields of a variant are valid if the value is of that variant
+                    let mut sep = "";
+                    for variant in struct_env.get_variants() {
+                        let valid = variant
+                            .get_fields()
+                            .map(|field| {
+                                let sel = format!(
+                                    "{}(s)",
+                                    boogie_variant_field_sel(&variant, &field, self.type_inst)
+                                );
+                                let ty = &field.get_type().instantiate(self.type_inst);
+                                boogie_well_formed_expr(env, &sel, ty)
+                            })
+                            .join(" && ");
+                        if !valid.is_empty() {
+                            emitln!(
+                                writer,
+                                "{}({}(s) ==> {})",
+                                sep,
+                                boogie_variant_test(&variant, self.type_inst),
+                                valid
+                            );
+                            sep = "  && ";
+                        }
+                    }
+                    if sep.is_empty() {
+                        emitln!(writer, "true")
+                    }
                 } else {
                     let mut sep = "";
                     for field in struct_env.get_fields() {
@@ -333,6 +377,26 @@ impl<'env> StructTranslator<'env> {
             || {
                 if struct_has_native_equality(struct_env, self.type_inst, self.parent.options) {
                     emitln!(writer, "s1 == s2")
+                } else if struct_env.is_enum() {
+                    // Values are equal if they are of the same variant, with equal fields
+                    let mut sep = "";
+                    for variant in struct_env.get_variants() {
+                        let test = boogie_variant_test(&variant, self.type_inst);
+                        let fields_equal = variant.get_fields().map(|field| {
+                            let sel_fun =
+                                boogie_variant_field_sel(&variant, &field, self.type_inst);
+                            let field_suffix =
+                                boogie_type_suffix(env, &self.inst(&field.get_type()));
+                            format!(
+                                "$IsEqual'{}'({}(s1), {}(s2))",
+                                field_suffix, sel_fun, sel_fun
+                            )
+                        });
+                        let mut conjuncts = vec![format!("{}(s1)", test), format!("{}(s2)", test)];
+                        conjuncts.extend(fields_equal);
+                        emit!(writer, "{}({})", sep, conjuncts.join(" && "));
+                        sep = "\n|| ";
+                    }
                 } else {
                     let mut sep = "";
                     for field in &fields {
@@ -850,6 +914,8 @@ impl<'env> FunctionTranslator<'env> {
                     Constant::Bool(true) => "true".to_string(),
                     Constant::Bool(false) => "false".to_string(),
                     Constant::U8(num) => num.to_string(),
+                    Constant::U16(num) => num.to_string(),
+                    Constant::U32(num) => num.to_string(),
                     Constant::U64(num) => num.to_string(),
                     Constant::U128(num) => num.to_string(),
                     Constant::U256(num) => num.to_string(),
@@ -1010,6 +1076,62 @@ impl<'env> FunctionTranslator<'env> {
                             emitln!(writer, "{} := {};", str_local(dests[i]), field_sel);
                         }
                     }
+                    PackVariant(mid, sid, variant, inst) => {
+                        let inst = &self.inst_slice(inst);
+                        let struct_env = env.get_module(*mid).into_struct(*sid);
+                        let args = srcs.iter().cloned().map(str_local).join(", ");
+                        let dest_str = str_local(dests[0]);
+                        emitln!(
+                            writer,
+                            "{} := {}({});",
+                            dest_str,
+                            boogie_variant_name(&struct_env.get_variant(*variant), inst),
+                            args
+                        );
+                    }
+                    UnpackVariant(mid, sid, variant, inst) => {
+                        let inst = &self.inst_slice(inst);
+                        let struct_env = env.get_module(*mid).into_struct(*sid);
+                        let variant_env = struct_env.get_variant(*variant);
+                        let src_str = str_local(srcs[0]);
+                        // Unpacking a value of another variant fails
+                        emitln!(
+                            writer,
+                            "if (!{}({})) {{",
+                            boogie_variant_test(&variant_env, inst),
+                            src_str
+                        );
+                        writer.with_indent(|| emitln!(writer, "call $ExecFailureAbort();"));
+                        emitln!(writer, "} else {");
+                        writer.with_indent(|| {
+                            for (i, ref field_env) in variant_env.get_fields().enumerate() {
+                                emitln!(
+                                    writer,
+                                    "{} := {}({});",
+                                    str_local(dests[i]),
+                                    boogie_variant_field_sel(&variant_env, field_env, inst),
+                                    src_str
+                                );
+                            }
+                        });
+                        emitln!(writer, "}");
+                    }
+                    TestVariant(mid, sid, variant, inst) => {
+                        let inst = &self.inst_slice(inst);
+                        let struct_env = env.get_module(*mid).into_struct(*sid);
+                        let src_str = if self.get_local_type(srcs[0]).is_mutable_reference() {
+                            format!("$Dereference({})", str_local(srcs[0]))
+                        } else {
+                            str_local(srcs[0])
+                        };
+                        emitln!(
+                            writer,
+                            "{} := {}({});",
+                            str_local(dests[0]),
+                            boogie_variant_test(&struct_env.get_variant(*variant), inst),
+                            src_str
+                        );
+                    }
                     BorrowField(mid, sid, inst, field_offset) => {
                         let inst = &self.inst_slice(inst);
                         let src_str = str_local(srcs[0]);
@@ -1206,6 +1328,26 @@ impl<'env> FunctionTranslator<'env> {
                             str_local(src)
                         );
                     }
+                    CastU16 => {
+                        let src = srcs[0];
+                        let dest = dests[0];
+                        emitln!(
+                            writer,
+                            "call {} := $CastU16({});",
+                            str_local(dest),
+                            str_local(src)
+                        );
+                    }
+                    CastU32 => {
+                        let src = srcs[0];
+                        let dest = dests[0];
+                        emitln!(
+                            writer,
+                            "call {} := $CastU32({});",
+                            str_local(dest),
+                            str_local(src)
+                        );
+                    }
                     CastU64 => {
                         let src = srcs[0];
                         let dest = dests[0];
@@ -1226,6 +1368,16 @@ impl<'env> FunctionTranslator<'env> {
                             str_local(src)
                         );
                     }
+                    CastU256 => {
+                        let src = srcs[0];
+                        let dest = dests[0];
+                        emitln!(
+                            writer,
+                            "call {} := $CastU256({});",
+                            str_local(dest),
+                            str_local(src)
+                        );
+                    }
                     Not => {
                         let src = srcs[0];
                         let dest = dests[0];
@@ -1249,8 +1401,11 @@ impl<'env> FunctionTranslator<'env> {
                         };
                         let add_type = match &self.get_local_type(dest) {
                             Type::Primitive(PrimitiveType::U8) => "U8".to_string(),
+                            Type::Primitive(PrimitiveType::U16) => "U16".to_string(),
+                            Type::Primitive(PrimitiveType::U32) => "U32".to_string(),
                             Type::Primitive(PrimitiveType::U64) => format!("U64{}", unchecked),
                             Type::Primitive(PrimitiveType::U128) => format!("U128{}", unchecked),
+                            Type::Primitive(PrimitiveType::U256) => format!("U256{}", unchecked),
                             _ => unreachable!(),
                         };
                         emitln!(
@@ -1280,8 +1435,11 @@ impl<'env> FunctionTranslator<'env> {
                         let op2 = srcs[1];
                         let mul_type = match &self.get_local_type(dest) {
                             Type::Primitive(PrimitiveType::U8) => "U8",
+                            Type::Primitive(PrimitiveType::U16) => "U16",
+                            Type::Primitive(PrimitiveType::U32) => "U32",
                             Type::Primitive(PrimitiveType::U64) => "U64",
                             Type::Primitive(PrimitiveType::U128) => "U128",
+                            Type::Primitive(PrimitiveType::U256) => "U256",
                             _ => unreachable!(),
                         };
                         emitln!(
@@ -1323,8 +1481,11 @@ impl<'env> FunctionTranslator<'env> {
                         let op2 = srcs[1];
                         let sh_type = match &self.get_local_type(dest) {
                             Type::Primitive(PrimitiveType::U8) => "U8",
+                            Type::Primitive(PrimitiveType::U16) => "U16",
+                            Type::Primitive(PrimitiveType::U32) => "U32",
                             Type::Primitive(PrimitiveType::U64) => "U64",
                             Type::Primitive(PrimitiveType::U128) => "U128",
+                            Type::Primitive(PrimitiveType::U256) => "U256",
                             _ => unreachable!(),
                         };
                         emitln!(
@@ -1484,7 +1645,7 @@ impl<'env> FunctionTranslator<'env> {
                         let node_id = env.new_node(env.unknown_loc(), mem.to_type());
                         self.track_global_mem(mem, node_id);
                     }
-                    CastU256 => unimplemented!(),
+                    CastU256FromWords => unimplemented!(),
                 }
                 if let Some(AbortAction(target, code)) = aa {
                     emitln!(writer, "if ($abort_flag) {");
@@ -1833,6 +1994,17 @@ fn struct_has_native_equality(
             return false;
         }
     }
+    for variant in struct_env.get_variants() {
+        for field in variant.get_fields() {
+            if !has_native_equality(
+                struct_env.module_env.env,
+                options,
+                &field.get_type().instantiate(inst),
+            ) {
+                return false;
+            }
+        }
+    }
     true
 }
 
@@ -759,8 +759,11 @@ impl<'env> SpecTranslator<'env> {
             Operation::InRangeVec => self.translate_primitive_call("InRangeVec", args),
             Operation::InRangeRange => self.translate_primitive_call("$InRange", args),
             Operation::MaxU8 => emit!(self.writer, "$MAX_U8"),
+            Operation::MaxU16 => emit!(self.writer, "$MAX_U16"),
+            Operation::MaxU32 => emit!(self.writer, "$MAX_U32"),
             Operation::MaxU64 => emit!(self.writer, "$MAX_U64"),
             Operation::MaxU128 => emit!(self.writer, "$MAX_U128"),
+            Operation::MaxU256 => emit!(self.writer, "$MAX_U256"),
             Operation::WellFormed => self.translate_well_formed(&args[0]),
             Operation::AbortCode => emit!(self.writer, "$abort_code"),
             Operation::AbortFlag => emit!(self.writer, "$abort_flag"),
@@ -238,8 +238,9 @@ impl<'a> TransferFunctions for EscapeAnalysis<'a> {
                 BorrowGlobal(_mid, _sid, _types) => {
                     state.insert(rets[0], AbsValue::InternalRef);
                 }
-                ReadRef | MoveFrom(..) | Exists(..) | Pack(..) | Eq | Neq | CastU8 | CastU64
-                | CastU128 | Not | Add | Sub | Mul | Div | Mod | BitOr | BitAnd | Xor | Shl
+                ReadRef | MoveFrom(..) | Exists(..) | Pack(..) | PackVariant(..)
+                | TestVariant(..) | Eq | Neq | CastU8 | CastU16 | CastU32 | CastU64 | CastU128
+                | CastU256 | Not | Add | Sub | Mul | Div | Mod | BitOr | BitAnd | Xor | Shl
                 | Shr | Lt | Gt | Le | Ge | Or | And => {
                     // These operations all produce a non-reference value
                     state.insert(rets[0], AbsValue::NonRef);
@@ -285,7 +286,7 @@ impl<'a> TransferFunctions for EscapeAnalysis<'a> {
                         state.call(rets, args, &callee_fun_env)
                     }
                 }
-                Unpack(..) => {
+                Unpack(..) | UnpackVariant(..) => {
                     for ret_index in rets {
                         state.insert(*ret_index, AbsValue::NonRef);
                     }
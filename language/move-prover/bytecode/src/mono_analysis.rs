@@ -467,6 +467,11 @@ impl<'a> Analyzer<'a> {
             for field in struct_.get_fields() {
                 self.add_type(&field.get_type().instantiate(targs));
             }
+            for variant in struct_.get_variants() {
+                for field in variant.get_fields() {
+                    self.add_type(&field.get_type().instantiate(targs));
+                }
+            }
         }
     }
 }
@@ -104,7 +104,7 @@ impl<'a> TransferFunctions for PackedTypesAnalysis<'a> {
 
         if let Call(_, _, oper, ..) = instr {
             match oper {
-                Pack(mid, sid, types) => {
+                Pack(mid, sid, types) | PackVariant(mid, sid, _, types) => {
                     let env = self.cache.global_env();
                     match env.get_struct_tag(*mid, *sid, types) {
                         Some(tag) => {
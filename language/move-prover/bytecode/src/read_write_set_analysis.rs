@@ -578,8 +578,59 @@ impl<'a> TransferFunctions for ReadWriteSetAnalysis<'a> {
                         }
                     }
                 }
-                CastU8 | CastU64 | CastU128 | Not | Add | Sub | Mul | Div | Mod | BitOr
-                | BitAnd | Xor | Shl | Shr | Lt | Gt | Le | Ge | Or | And => {
+                // The fields of the variants of an enum share offsets, which over-approximates
+                // the access paths of a field as those of every field at its offset
+                PackVariant(_mid, sid, variant, _types) => {
+                    // rets[0] = PackVariant<mid::sid<types>>(args)
+                    let struct_env = func_env.module_env.get_struct(*sid);
+                    for (arg_index, fld) in
+                        struct_env.get_variant(*variant).get_fields().enumerate()
+                    {
+                        let ty = fld.get_type();
+                        if ty.is_address() || ty.is_struct() || ty.is_vector() {
+                            if let Some(rhs) = state
+                                .locals
+                                .get_local_node(args[arg_index], func_env)
+                                .cloned()
+                            {
+                                // rets[0]/fld = args[arg_index]
+                                let mut ap = AccessPath::from_index(rets[0], func_env);
+                                ap.add_offset(Offset::field(fld.get_offset()));
+                                state.locals.join_access_path(ap, rhs);
+                            }
+                        }
+                    }
+                }
+                UnpackVariant(_mid, sid, variant, _types) => {
+                    // rets = UnpackVariant<mid::sid<types>>(args[0])
+                    if state.locals.local_exists(args[0], func_env) {
+                        let struct_env = func_env.module_env.get_struct(*sid);
+                        for (ret_index, fld) in
+                            struct_env.get_variant(*variant).get_fields().enumerate()
+                        {
+                            let ty = fld.get_type();
+                            if ty.is_address() || ty.is_struct() || ty.is_vector() {
+                                // rets[ret_index] = args[0]/fld
+                                state.assign_offset(
+                                    rets[ret_index],
+                                    args[0],
+                                    Offset::field(fld.get_offset()),
+                                    None,
+                                    func_env,
+                                );
+                            }
+                        }
+                    }
+                }
+                TestVariant(..) => {
+                    // Reads the variant of the enum behind the reference
+                    if state.locals.local_exists(args[0], func_env) {
+                        state.record_access(args[0], Access::Read, func_env)
+                    }
+                }
+                CastU8 | CastU16 | CastU32 | CastU64 | CastU128 | CastU256 | Not | Add | Sub
+                | Mul | Div | Mod | BitOr | BitAnd | Xor | Shl | Shr | Lt | Gt | Le | Ge | Or
+                | And => {
                     // These operations touch non-reference values; nothing to do
                 }
                 oper => unimplemented!("unsupported oper {:?}", oper),
@@ -87,6 +87,8 @@ pub enum HavocKind {
 pub enum Constant {
     Bool(bool),
     U8(u8),
+    U16(u16),
+    U32(u32),
     U64(u64),
     U128(u128),
     U256(U256),
@@ -112,6 +114,11 @@ pub enum Operation {
     Pack(ModuleId, StructId, Vec<Type>),
     Unpack(ModuleId, StructId, Vec<Type>),
 
+    // Variants of enums, identified by their index
+    PackVariant(ModuleId, StructId, usize, Vec<Type>),
+    UnpackVariant(ModuleId, StructId, usize, Vec<Type>),
+    TestVariant(ModuleId, StructId, usize, Vec<Type>),
+
     // Resources
     MoveTo(ModuleId, StructId, Vec<Type>),
     MoveFrom(ModuleId, StructId, Vec<Type>),
@@ -144,8 +151,11 @@ pub enum Operation {
 
     // Unary
     CastU8,
+    CastU16,
+    CastU32,
     CastU64,
     CastU128,
+    CastU256,
     Not,
 
     // Binary
@@ -167,7 +177,7 @@ pub enum Operation {
     And,
     Eq,
     Neq,
-    CastU256,
+    CastU256FromWords,
 
     // Debugging
     TraceLocal(TempIndex),
@@ -190,6 +200,9 @@ impl Operation {
             Operation::OpaqueCallEnd(_, _, _) => false,
             Operation::Pack(_, _, _) => false,
             Operation::Unpack(_, _, _) => false,
+            Operation::PackVariant(_, _, _, _) => false,
+            Operation::UnpackVariant(_, _, _, _) => true,
+            Operation::TestVariant(_, _, _, _) => false,
             Operation::MoveTo(_, _, _) => true,
             Operation::MoveFrom(_, _, _) => true,
             Operation::Exists(_, _, _) => false,
@@ -211,9 +224,12 @@ impl Operation {
             Operation::UnpackRefDeep => false,
             Operation::PackRefDeep => false,
             Operation::CastU8 => true,
+            Operation::CastU16 => true,
+            Operation::CastU32 => true,
             Operation::CastU64 => true,
             Operation::CastU128 => true,
             Operation::CastU256 => true,
+            Operation::CastU256FromWords => true,
             Operation::Not => false,
             Operation::Add => true,
             Operation::Sub => true,
@@ -566,6 +582,15 @@ impl Bytecode {
                     Unpack(mid, sid, tys) => {
                         Unpack(*mid, *sid, Type::instantiate_slice(tys, params))
                     }
+                    PackVariant(mid, sid, variant, tys) => {
+                        PackVariant(*mid, *sid, *variant, Type::instantiate_slice(tys, params))
+                    }
+                    UnpackVariant(mid, sid, variant, tys) => {
+                        UnpackVariant(*mid, *sid, *variant, Type::instantiate_slice(tys, params))
+                    }
+                    TestVariant(mid, sid, variant, tys) => {
+                        TestVariant(*mid, *sid, *variant, Type::instantiate_slice(tys, params))
+                    }
                     BorrowField(mid, sid, tys, field_num) => {
                         BorrowField(*mid, *sid, Type::instantiate_slice(tys, params), *field_num)
                     }
@@ -916,6 +941,19 @@ impl<'env> fmt::Display for OperationDisplay<'env> {
             Unpack(mid, sid, targs) => {
                 write!(f, "unpack {}", self.struct_str(*mid, *sid, targs))?;
             }
+            PackVariant(mid, sid, variant, targs) => {
+                write!(f, "pack {}", self.variant_str(*mid, *sid, *variant, targs))?;
+            }
+            UnpackVariant(mid, sid, variant, targs) => {
+                write!(
+                    f,
+                    "unpack {}",
+                    self.variant_str(*mid, *sid, *variant, targs)
+                )?;
+            }
+            TestVariant(mid, sid, variant, targs) => {
+                write!(f, "test {}", self.variant_str(*mid, *sid, *variant, targs))?;
+            }
 
             // Borrow
             BorrowLoc => {
@@ -1023,9 +1061,12 @@ impl<'env> fmt::Display for OperationDisplay<'env> {
             }
             // Unary
             CastU8 => write!(f, "(u8)")?,
+            CastU16 => write!(f, "(u16)")?,
+            CastU32 => write!(f, "(u32)")?,
             CastU64 => write!(f, "(u64)")?,
             CastU128 => write!(f, "(u128)")?,
             CastU256 => write!(f, "(u256)")?,
+            CastU256FromWords => write!(f, "(u256)")?,
             Not => write!(f, "!")?,
 
             // Binary
@@ -1103,6 +1144,22 @@ impl<'env> OperationDisplay<'env> {
         };
         format!("{}", ty.display(&tctx))
     }
+
+    fn variant_str(&self, mid: ModuleId, sid: StructId, variant: usize, targs: &[Type]) -> String {
+        let struct_env = self
+            .func_target
+            .global_env()
+            .get_module(mid)
+            .into_struct(sid);
+        let variant_env = struct_env.get_variant(variant);
+        format!(
+            "{}::{}",
+            self.struct_str(mid, sid, targs),
+            variant_env
+                .get_name()
+                .display(self.func_target.global_env().symbol_pool())
+        )
+    }
 }
 
 impl fmt::Display for Constant {
@@ -1111,6 +1168,8 @@ impl fmt::Display for Constant {
         match self {
             Bool(x) => write!(f, "{}", x)?,
             U8(x) => write!(f, "{}", x)?,
+            U16(x) => write!(f, "{}", x)?,
+            U32(x) => write!(f, "{}", x)?,
             U64(x) => write!(f, "{}", x)?,
             U128(x) => write!(f, "{}", x)?,
             U256(x) => write!(f, "{}", x)?,
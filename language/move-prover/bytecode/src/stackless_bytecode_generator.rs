@@ -9,6 +9,7 @@ use crate::{
         Constant, Label, Operation, PropKind,
     },
 };
+use ethnum::U256;
 use itertools::Itertools;
 use move_binary_format::{
     access::ModuleAccess,
@@ -156,6 +157,25 @@ impl<'a> StacklessBytecodeGenerator<'a> {
             .get_type_actuals(Some(type_params_index))
     }
 
+    /// Returns the enum, the variant index and the type arguments of a variant instruction.
+    fn get_variant_info(&self, bytecode: &MoveBytecode) -> (StructId, usize, Vec<Type>) {
+        let (def_idx, variant, actuals) = match bytecode {
+            MoveBytecode::PackVariant(idx, variant)
+            | MoveBytecode::UnpackVariant(idx, variant)
+            | MoveBytecode::TestVariant(idx, variant) => (*idx, *variant, vec![]),
+            MoveBytecode::PackVariantGeneric(idx, variant)
+            | MoveBytecode::UnpackVariantGeneric(idx, variant)
+            | MoveBytecode::TestVariantGeneric(idx, variant) => {
+                let struct_instantiation = self.module.struct_instantiation_at(*idx);
+                let actuals = self.get_type_params(struct_instantiation.type_parameters);
+                (struct_instantiation.def, *variant, actuals)
+            }
+            _ => unreachable!("not a variant instruction"),
+        };
+        let struct_id = self.func_env.module_env.get_struct_id(def_idx);
+        (struct_id, variant as usize, actuals)
+    }
+
     #[allow(clippy::cognitive_complexity)]
     pub fn generate_bytecode(
         &mut self,
@@ -380,6 +400,24 @@ impl<'a> StacklessBytecodeGenerator<'a> {
                 self.temp_count += 1;
             }
 
+            MoveBytecode::LdU16(number) => {
+                let temp_index = self.temp_count;
+                self.temp_stack.push(temp_index);
+                self.local_types.push(Type::Primitive(PrimitiveType::U16));
+                self.code
+                    .push(Bytecode::Load(attr_id, temp_index, Constant::U16(*number)));
+                self.temp_count += 1;
+            }
+
+            MoveBytecode::LdU32(number) => {
+                let temp_index = self.temp_count;
+                self.temp_stack.push(temp_index);
+                self.local_types.push(Type::Primitive(PrimitiveType::U32));
+                self.code
+                    .push(Bytecode::Load(attr_id, temp_index, Constant::U32(*number)));
+                self.temp_count += 1;
+            }
+
             MoveBytecode::LdU64(number) => {
                 let temp_index = self.temp_count;
                 self.temp_stack.push(temp_index);
@@ -398,6 +436,18 @@ impl<'a> StacklessBytecodeGenerator<'a> {
                 self.temp_count += 1;
             }
 
+            MoveBytecode::LdU256(number) => {
+                let temp_index = self.temp_count;
+                self.temp_stack.push(temp_index);
+                self.local_types.push(Type::Primitive(PrimitiveType::U256));
+                self.code.push(Bytecode::Load(
+                    attr_id,
+                    temp_index,
+                    Constant::U256(U256::from_le_bytes(number.to_le_bytes())),
+                ));
+                self.temp_count += 1;
+            }
+
             MoveBytecode::CastU8 => {
                 let operand_index = self.temp_stack.pop().unwrap();
                 let temp_index = self.temp_count;
@@ -408,6 +458,26 @@ impl<'a> StacklessBytecodeGenerator<'a> {
                 self.temp_count += 1;
             }
 
+            MoveBytecode::CastU16 => {
+                let operand_index = self.temp_stack.pop().unwrap();
+                let temp_index = self.temp_count;
+                self.temp_stack.push(temp_index);
+                self.local_types.push(Type::Primitive(PrimitiveType::U16));
+                self.code
+                    .push(mk_unary(Operation::CastU16, temp_index, operand_index));
+                self.temp_count += 1;
+            }
+
+            MoveBytecode::CastU32 => {
+                let operand_index = self.temp_stack.pop().unwrap();
+                let temp_index = self.temp_count;
+                self.temp_stack.push(temp_index);
+                self.local_types.push(Type::Primitive(PrimitiveType::U32));
+                self.code
+                    .push(mk_unary(Operation::CastU32, temp_index, operand_index));
+                self.temp_count += 1;
+            }
+
             MoveBytecode::CastU64 => {
                 let operand_index = self.temp_stack.pop().unwrap();
                 let temp_index = self.temp_count;
@@ -428,6 +498,16 @@ impl<'a> StacklessBytecodeGenerator<'a> {
                 self.temp_count += 1;
             }
 
+            MoveBytecode::CastU256 => {
+                let operand_index = self.temp_stack.pop().unwrap();
+                let temp_index = self.temp_count;
+                self.temp_stack.push(temp_index);
+                self.local_types.push(Type::Primitive(PrimitiveType::U256));
+                self.code
+                    .push(mk_unary(Operation::CastU256, temp_index, operand_index));
+                self.temp_count += 1;
+            }
+
             MoveBytecode::LdConst(idx) => {
                 let temp_index = self.temp_count;
                 self.temp_stack.push(temp_index);
@@ -687,6 +767,64 @@ impl<'a> StacklessBytecodeGenerator<'a> {
                 ));
             }
 
+            MoveBytecode::PackVariant(..) | MoveBytecode::PackVariantGeneric(..) => {
+                let (struct_id, variant, actuals) = self.get_variant_info(bytecode);
+                let module_id = self.func_env.module_env.get_id();
+                let struct_env = self.func_env.module_env.get_struct(struct_id);
+                let mut field_temp_indices = vec![];
+                let struct_temp_index = self.temp_count;
+                for _ in struct_env.get_variant(variant).get_fields() {
+                    let field_temp_index = self.temp_stack.pop().unwrap();
+                    field_temp_indices.push(field_temp_index);
+                }
+                self.local_types
+                    .push(Type::Struct(module_id, struct_id, actuals.clone()));
+                self.temp_stack.push(struct_temp_index);
+                field_temp_indices.reverse();
+                self.code.push(mk_call(
+                    Operation::PackVariant(module_id, struct_id, variant, actuals),
+                    vec![struct_temp_index],
+                    field_temp_indices,
+                ));
+                self.temp_count += 1;
+            }
+
+            MoveBytecode::UnpackVariant(..) | MoveBytecode::UnpackVariantGeneric(..) => {
+                let (struct_id, variant, actuals) = self.get_variant_info(bytecode);
+                let module_id = self.func_env.module_env.get_id();
+                let struct_env = self.func_env.module_env.get_struct(struct_id);
+                let mut field_temp_indices = vec![];
+                let struct_temp_index = self.temp_stack.pop().unwrap();
+                for field_env in struct_env.get_variant(variant).get_fields() {
+                    let field_type = field_env.get_type().instantiate(&actuals);
+                    let field_temp_index = self.temp_count;
+                    field_temp_indices.push(field_temp_index);
+                    self.temp_stack.push(field_temp_index);
+                    self.local_types.push(field_type);
+                    self.temp_count += 1;
+                }
+                self.code.push(mk_call(
+                    Operation::UnpackVariant(module_id, struct_id, variant, actuals),
+                    field_temp_indices,
+                    vec![struct_temp_index],
+                ));
+            }
+
+            MoveBytecode::TestVariant(..) | MoveBytecode::TestVariantGeneric(..) => {
+                let (struct_id, variant, actuals) = self.get_variant_info(bytecode);
+                let module_id = self.func_env.module_env.get_id();
+                let operand_index = self.temp_stack.pop().unwrap();
+                let temp_index = self.temp_count;
+                self.local_types.push(Type::Primitive(PrimitiveType::Bool));
+                self.temp_count += 1;
+                self.temp_stack.push(temp_index);
+                self.code.push(mk_call(
+                    Operation::TestVariant(module_id, struct_id, variant, actuals),
+                    vec![temp_index],
+                    vec![operand_index],
+                ));
+            }
+
             MoveBytecode::ReadRef => {
                 let operand_index = self.temp_stack.pop().unwrap();
                 let operand_sig = self.local_types[operand_index].clone();
@@ -1277,8 +1415,13 @@ impl<'a> StacklessBytecodeGenerator<'a> {
             }
             (Type::Primitive(PrimitiveType::Bool), MoveValue::Bool(b)) => Constant::Bool(*b),
             (Type::Primitive(PrimitiveType::U8), MoveValue::U8(b)) => Constant::U8(*b),
+            (Type::Primitive(PrimitiveType::U16), MoveValue::U16(b)) => Constant::U16(*b),
+            (Type::Primitive(PrimitiveType::U32), MoveValue::U32(b)) => Constant::U32(*b),
             (Type::Primitive(PrimitiveType::U64), MoveValue::U64(b)) => Constant::U64(*b),
             (Type::Primitive(PrimitiveType::U128), MoveValue::U128(b)) => Constant::U128(*b),
+            (Type::Primitive(PrimitiveType::U256), MoveValue::U256(b)) => {
+                Constant::U256(U256::from_le_bytes(b.to_le_bytes()))
+            }
             (Type::Primitive(PrimitiveType::Address), MoveValue::Address(a)) => {
                 Constant::Address(move_model::addr_to_big_uint(a))
             }
@@ -464,6 +464,18 @@ impl<'env> Evaluator<'env> {
                 }
                 BaseValue::mk_num(BigInt::from(u8::MAX))
             }
+            Operation::MaxU16 => {
+                if cfg!(debug_assertions) {
+                    assert!(arg_vals.is_empty());
+                }
+                BaseValue::mk_num(BigInt::from(u16::MAX))
+            }
+            Operation::MaxU32 => {
+                if cfg!(debug_assertions) {
+                    assert!(arg_vals.is_empty());
+                }
+                BaseValue::mk_num(BigInt::from(u32::MAX))
+            }
             Operation::MaxU64 => {
                 if cfg!(debug_assertions) {
                     assert!(arg_vals.is_empty());
@@ -476,6 +488,12 @@ impl<'env> Evaluator<'env> {
                 }
                 BaseValue::mk_num(BigInt::from(u128::MAX))
             }
+            Operation::MaxU256 => {
+                if cfg!(debug_assertions) {
+                    assert!(arg_vals.is_empty());
+                }
+                BaseValue::mk_num((BigInt::from(1) << 256) - 1)
+            }
             Operation::AbortFlag => {
                 if cfg!(debug_assertions) {
                     assert!(arg_vals.is_empty());
@@ -14,6 +14,7 @@ use move_binary_format::errors::Location;
 use move_core_types::{
     account_address::AccountAddress,
     language_storage::CORE_CODE_ADDRESS,
+    u256::U256,
     vm_status::{sub_status, StatusCode},
 };
 use move_model::{
@@ -400,9 +401,11 @@ impl<'env> FunctionContext<'env> {
         let val = match constant {
             Constant::Bool(v) => TypedValue::mk_bool(*v),
             Constant::U8(v) => TypedValue::mk_u8(*v),
+            Constant::U16(v) => TypedValue::mk_u16(*v),
+            Constant::U32(v) => TypedValue::mk_u32(*v),
             Constant::U64(v) => TypedValue::mk_u64(*v),
             Constant::U128(v) => TypedValue::mk_u128(*v),
-            Constant::U256(_) => unimplemented!(),
+            Constant::U256(v) => TypedValue::mk_u256(U256::from_le_bytes(&v.to_le_bytes())),
             Constant::Address(v) => TypedValue::mk_address(
                 AccountAddress::from_hex_literal(&format!("{:#x}", v)).unwrap(),
             ),
@@ -536,6 +539,31 @@ impl<'env> FunctionContext<'env> {
                     self.handle_unpack(*module_id, *struct_id, ty_args, typed_args.remove(0));
                 Ok(unpacked)
             }
+            // enum
+            Operation::PackVariant(module_id, struct_id, variant, ty_args) => {
+                let packed =
+                    self.handle_pack_variant(*module_id, *struct_id, *variant, ty_args, typed_args);
+                Ok(vec![packed])
+            }
+            Operation::UnpackVariant(module_id, struct_id, variant, ty_args) => {
+                if cfg!(debug_assertions) {
+                    assert_eq!(typed_args.len(), 1);
+                }
+                self.handle_unpack_variant(
+                    *module_id,
+                    *struct_id,
+                    *variant,
+                    ty_args,
+                    typed_args.remove(0),
+                )
+            }
+            Operation::TestVariant(_, _, variant, _) => {
+                if cfg!(debug_assertions) {
+                    assert_eq!(typed_args.len(), 1);
+                }
+                let tag = typed_args.remove(0).get_ref_variant_tag();
+                Ok(vec![TypedValue::mk_bool(tag as usize == *variant)])
+            }
             Operation::GetField(module_id, struct_id, ty_args, field_num) => {
                 if cfg!(debug_assertions) {
                     assert_eq!(typed_args.len(), 1);
@@ -761,18 +789,18 @@ impl<'env> FunctionContext<'env> {
                 unreachable!()
             }
             // cast
-            Operation::CastU8 | Operation::CastU64 | Operation::CastU128 => {
+            Operation::CastU8
+            | Operation::CastU16
+            | Operation::CastU32
+            | Operation::CastU64
+            | Operation::CastU128
+            | Operation::CastU256 => {
                 if cfg!(debug_assertions) {
                     assert_eq!(typed_args.len(), 1);
                 }
                 let val = typed_args.remove(0);
-                match op {
-                    Operation::CastU8 => self.handle_cast_u8(val),
-                    Operation::CastU64 => self.handle_cast_u64(val),
-                    Operation::CastU128 => self.handle_cast_u128(val),
-                    _ => unreachable!(),
-                }
-                .map(|casted| vec![casted])
+                self.handle_cast(val, local_state.get_type(dsts[0]))
+                    .map(|casted| vec![casted])
             }
             // binary arithmetic
             Operation::Add | Operation::Sub | Operation::Mul | Operation::Div | Operation::Mod => {
@@ -857,7 +885,7 @@ impl<'env> FunctionContext<'env> {
             | Operation::TraceAbort
             | Operation::TraceExp(..)
             | Operation::TraceGlobalMem(..)
-            | Operation::CastU256 => {
+            | Operation::CastU256FromWords => {
                 unreachable!();
             }
         };
@@ -1011,6 +1039,39 @@ impl<'env> FunctionContext<'env> {
         op_struct.unpack_struct()
     }
 
+    fn handle_pack_variant(
+        &self,
+        module_id: ModuleId,
+        struct_id: StructId,
+        variant: usize,
+        ty_args: &[MT::Type],
+        op_fields: Vec<TypedValue>,
+    ) -> TypedValue {
+        let env = self.target.global_env();
+        let inst = convert_model_struct_type(env, module_id, struct_id, ty_args, &self.ty_args);
+        TypedValue::mk_variant(inst, variant as u16, op_fields)
+    }
+
+    fn handle_unpack_variant(
+        &self,
+        module_id: ModuleId,
+        struct_id: StructId,
+        variant: usize,
+        ty_args: &[MT::Type],
+        op_struct: TypedValue,
+    ) -> Result<Vec<TypedValue>, AbortInfo> {
+        if cfg!(debug_assertions) {
+            let env = self.target.global_env();
+            let inst = convert_model_struct_type(env, module_id, struct_id, ty_args, &self.ty_args);
+            assert_eq!(&inst, op_struct.get_ty().get_struct_inst());
+        }
+        let (tag, fields) = op_struct.unpack_variant();
+        if tag as usize != variant {
+            return Err(self.sys_abort(StatusCode::STRUCT_VARIANT_MISMATCH));
+        }
+        Ok(fields)
+    }
+
     fn handle_get_field(
         &self,
         module_id: ModuleId,
@@ -1475,76 +1536,9 @@ impl<'env> FunctionContext<'env> {
         }
     }
 
-    fn handle_cast_u8(&self, val: TypedValue) -> Result<TypedValue, AbortInfo> {
-        let (ty, val, _) = val.decompose();
-        let v = if ty.is_u8() {
-            val.into_u8()
-        } else if ty.is_u64() {
-            let v = val.into_u64();
-            if v > (u8::MAX as u64) {
-                return Err(self.sys_abort(StatusCode::ARITHMETIC_ERROR));
-            }
-            v as u8
-        } else if ty.is_u128() {
-            let v = val.into_u128();
-            if v > (u8::MAX as u128) {
-                return Err(self.sys_abort(StatusCode::ARITHMETIC_ERROR));
-            }
-            v as u8
-        } else {
-            let n = val.into_num();
-            match n.to_u8() {
-                None => {
-                    return Err(self.sys_abort(StatusCode::ARITHMETIC_ERROR));
-                }
-                Some(v) => v,
-            }
-        };
-        Ok(TypedValue::mk_u8(v))
-    }
-
-    fn handle_cast_u64(&self, val: TypedValue) -> Result<TypedValue, AbortInfo> {
-        let (ty, val, _) = val.decompose();
-        let v = if ty.is_u8() {
-            val.into_u8() as u64
-        } else if ty.is_u64() {
-            val.into_u64()
-        } else if ty.is_u128() {
-            let v = val.into_u128();
-            if v > (u64::MAX as u128) {
-                return Err(self.sys_abort(StatusCode::ARITHMETIC_ERROR));
-            }
-            v as u64
-        } else {
-            let n = val.into_num();
-            match n.to_u64() {
-                None => {
-                    return Err(self.sys_abort(StatusCode::ARITHMETIC_ERROR));
-                }
-                Some(v) => v,
-            }
-        };
-        Ok(TypedValue::mk_u64(v))
-    }
-
-    fn handle_cast_u128(&self, val: TypedValue) -> Result<TypedValue, AbortInfo> {
-        let (ty, val, _) = val.decompose();
-        let v = if ty.is_u8() {
-            val.into_u8() as u128
-        } else if ty.is_u64() {
-            val.into_u64() as u128
-        } else if ty.is_u128() {
-            val.into_u128()
-        } else {
-            let n = val.into_num();
-            match n.to_u128() {
-                None => {
-                    return Err(self.sys_abort(StatusCode::ARITHMETIC_ERROR));
-                }
-                Some(v) => v,
-            }
-        };
-        Ok(TypedValue::mk_u128(v))
+    fn handle_cast(&self, val: TypedValue, res: &Type) -> Result<TypedValue, AbortInfo> {
+        TypedValue::mk_int(res, val.into_int())
+            .ok_or_else(|| self.sys_abort(StatusCode::ARITHMETIC_ERROR))
     }
 
     fn handle_binary_arithmetic(
@@ -1579,33 +1573,8 @@ impl<'env> FunctionContext<'env> {
             _ => unreachable!(),
         };
 
-        let res_val = if res.is_u8() {
-            match result.to_u8() {
-                None => {
-                    return Err(self.sys_abort(StatusCode::ARITHMETIC_ERROR));
-                }
-                Some(v) => TypedValue::mk_u8(v),
-            }
-        } else if res.is_u64() {
-            match result.to_u64() {
-                None => {
-                    return Err(self.sys_abort(StatusCode::ARITHMETIC_ERROR));
-                }
-                Some(v) => TypedValue::mk_u64(v),
-            }
-        } else if res.is_u128() {
-            match result.to_u128() {
-                None => {
-                    return Err(self.sys_abort(StatusCode::ARITHMETIC_ERROR));
-                }
-                Some(v) => TypedValue::mk_u128(v),
-            }
-        } else {
-            if cfg!(debug_assertions) {
-                assert!(res.is_num());
-            }
-            TypedValue::mk_num(result)
-        };
+        let res_val = TypedValue::mk_int(res, result)
+            .ok_or_else(|| self.sys_abort(StatusCode::ARITHMETIC_ERROR))?;
         Ok(res_val)
     }
 
@@ -1629,16 +1598,7 @@ impl<'env> FunctionContext<'env> {
             _ => unreachable!(),
         };
 
-        if res.is_u8() {
-            TypedValue::mk_u8(result.to_u8().unwrap())
-        } else if res.is_u64() {
-            TypedValue::mk_u64(result.to_u64().unwrap())
-        } else {
-            if cfg!(debug_assertions) {
-                assert!(res.is_u128());
-            }
-            TypedValue::mk_u128(result.to_u128().unwrap())
-        }
+        TypedValue::mk_int(res, result).unwrap()
     }
 
     fn handle_binary_bitshift(
@@ -1661,6 +1621,22 @@ impl<'env> FunctionContext<'env> {
                 _ => unreachable!(),
             };
             TypedValue::mk_u8(result)
+        } else if lhs.get_ty().is_u16() {
+            let lval = lhs.into_u16();
+            let result = match op {
+                Operation::Shl => lval << rval,
+                Operation::Shr => lval >> rval,
+                _ => unreachable!(),
+            };
+            TypedValue::mk_u16(result)
+        } else if lhs.get_ty().is_u32() {
+            let lval = lhs.into_u32();
+            let result = match op {
+                Operation::Shl => lval << rval,
+                Operation::Shr => lval >> rval,
+                _ => unreachable!(),
+            };
+            TypedValue::mk_u32(result)
         } else if lhs.get_ty().is_u64() {
             let lval = lhs.into_u64();
             let result = match op {
@@ -1669,8 +1645,7 @@ impl<'env> FunctionContext<'env> {
                 _ => unreachable!(),
             };
             TypedValue::mk_u64(result)
-        } else {
-            assert!(lhs.get_ty().is_u128());
+        } else if lhs.get_ty().is_u128() {
             let lval = lhs.into_u128();
             let result = match op {
                 Operation::Shl => lval << rval,
@@ -1678,6 +1653,15 @@ impl<'env> FunctionContext<'env> {
                 _ => unreachable!(),
             };
             TypedValue::mk_u128(result)
+        } else {
+            assert!(lhs.get_ty().is_u256());
+            let lval = lhs.into_u256();
+            let result = match op {
+                Operation::Shl => lval.shl_u8(rval),
+                Operation::Shr => lval.shr_u8(rval),
+                _ => unreachable!(),
+            };
+            TypedValue::mk_u256(result)
         }
     }
 
@@ -25,7 +25,7 @@ use crate::{
         player,
         settings::InterpreterSettings,
         ty::{
-            convert_model_base_type, BaseType, IntType, PrimitiveType, StructField,
+            convert_model_base_type, convert_model_struct_fields, BaseType, IntType, PrimitiveType,
             StructInstantiation,
         },
         value::{GlobalState, TypedValue},
@@ -162,8 +162,11 @@ pub fn convert_move_type_tag(env: &GlobalEnv, tag: &TypeTag) -> PartialVMResult<
     let converted = match tag {
         TypeTag::Bool => BaseType::mk_bool(),
         TypeTag::U8 => BaseType::mk_u8(),
+        TypeTag::U16 => BaseType::mk_u16(),
+        TypeTag::U32 => BaseType::mk_u32(),
         TypeTag::U64 => BaseType::mk_u64(),
         TypeTag::U128 => BaseType::mk_u128(),
+        TypeTag::U256 => BaseType::mk_u256(),
         TypeTag::Address => BaseType::mk_address(),
         TypeTag::Signer => BaseType::mk_signer(),
         TypeTag::Vector(elem_tag) => BaseType::mk_vector(convert_move_type_tag(env, elem_tag)?),
@@ -203,22 +206,13 @@ pub fn convert_move_struct_tag(
         .collect::<PartialVMResult<Vec<_>>>()?;
 
     // collect fields
-    let fields = struct_env
-        .get_fields()
-        .map(|field_env| {
-            let field_name = env.symbol_pool().string(field_env.get_name()).to_string();
-            let field_ty = convert_model_base_type(env, &field_env.get_type(), &insts);
-            StructField {
-                name: field_name,
-                ty: field_ty,
-            }
-        })
-        .collect();
+    let (fields, variants) = convert_model_struct_fields(env, &struct_env, &insts);
 
     Ok(StructInstantiation {
         ident,
         insts,
         fields,
+        variants,
     })
 }
 
@@ -232,12 +226,21 @@ pub fn convert_move_value(
         (MoveValue::U8(v), BaseType::Primitive(PrimitiveType::Int(IntType::U8))) => {
             TypedValue::mk_u8(*v)
         }
+        (MoveValue::U16(v), BaseType::Primitive(PrimitiveType::Int(IntType::U16))) => {
+            TypedValue::mk_u16(*v)
+        }
+        (MoveValue::U32(v), BaseType::Primitive(PrimitiveType::Int(IntType::U32))) => {
+            TypedValue::mk_u32(*v)
+        }
         (MoveValue::U64(v), BaseType::Primitive(PrimitiveType::Int(IntType::U64))) => {
             TypedValue::mk_u64(*v)
         }
         (MoveValue::U128(v), BaseType::Primitive(PrimitiveType::Int(IntType::U128))) => {
             TypedValue::mk_u128(*v)
         }
+        (MoveValue::U256(v), BaseType::Primitive(PrimitiveType::Int(IntType::U256))) => {
+            TypedValue::mk_u256(*v)
+        }
         (MoveValue::Address(v), BaseType::Primitive(PrimitiveType::Address)) => {
             TypedValue::mk_address(*v)
         }
@@ -251,6 +254,25 @@ pub fn convert_move_value(
                 .collect::<PartialVMResult<Vec<_>>>()?;
             TypedValue::mk_vector(*elem.clone(), converted)
         }
+        (MoveValue::Struct(v), BaseType::Struct(inst)) if !inst.variants.is_empty() => {
+            let tag = v
+                .variant_tag()
+                .ok_or_else(|| PartialVMError::new(StatusCode::TYPE_MISMATCH))?;
+            let variant = inst
+                .variants
+                .get(tag as usize)
+                .ok_or_else(|| PartialVMError::new(StatusCode::TYPE_MISMATCH))?;
+            let fields = v.fields();
+            if fields.len() != variant.fields.len() {
+                return Err(PartialVMError::new(StatusCode::TYPE_MISMATCH));
+            }
+            let converted = fields
+                .iter()
+                .zip(variant.fields.iter())
+                .map(|(f, info)| convert_move_value(env, f, &info.ty))
+                .collect::<PartialVMResult<Vec<_>>>()?;
+            TypedValue::mk_variant(inst.clone(), tag, converted)
+        }
         (MoveValue::Struct(v), BaseType::Struct(inst)) => {
             let fields = v.fields();
             if fields.len() != inst.fields.len() {
@@ -290,9 +312,14 @@ fn check_type_instantiation(
 
 fn get_abilities(env: &GlobalEnv, ty: &TypeTag) -> PartialVMResult<AbilitySet> {
     match ty {
-        TypeTag::Bool | TypeTag::U8 | TypeTag::U64 | TypeTag::U128 | TypeTag::Address => {
-            Ok(AbilitySet::PRIMITIVES)
-        }
+        TypeTag::Bool
+        | TypeTag::U8
+        | TypeTag::U16
+        | TypeTag::U32
+        | TypeTag::U64
+        | TypeTag::U128
+        | TypeTag::U256
+        | TypeTag::Address => Ok(AbilitySet::PRIMITIVES),
         TypeTag::Signer => Ok(AbilitySet::SIGNER),
         TypeTag::Vector(elem_ty) => AbilitySet::polymorphic_abilities(
             AbilitySet::VECTOR,
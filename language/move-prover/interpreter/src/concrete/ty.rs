@@ -21,7 +21,7 @@ use move_core_types::{
     value::{MoveStructLayout, MoveTypeLayout},
 };
 use move_model::{
-    model::{GlobalEnv, ModuleId, StructId},
+    model::{FieldEnv, GlobalEnv, ModuleId, StructEnv, StructId},
     ty as MT,
 };
 use move_stackless_bytecode::stackless_bytecode::Constant;
@@ -36,8 +36,11 @@ pub type CodeOffset = u16;
 pub enum IntType {
     Num,
     U8,
+    U16,
+    U32,
     U64,
     U128,
+    U256,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
@@ -54,11 +57,19 @@ pub struct StructField {
     pub ty: BaseType,
 }
 
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub struct StructVariant {
+    pub name: String,
+    pub fields: Vec<StructField>,
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
 pub struct StructInstantiation {
     pub ident: StructIdent,
     pub insts: Vec<BaseType>,
     pub fields: Vec<StructField>,
+    /// The variants of an enum, which has no fields besides those of its variants
+    pub variants: Vec<StructVariant>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
@@ -104,8 +115,11 @@ impl fmt::Display for IntType {
         let repr = match self {
             Self::Num => "num",
             Self::U8 => "u8",
+            Self::U16 => "u16",
+            Self::U32 => "u32",
             Self::U64 => "u64",
             Self::U128 => "u128",
+            Self::U256 => "u256",
         };
         f.write_str(repr)
     }
@@ -130,13 +144,35 @@ impl fmt::Display for StructInstantiation {
             .iter()
             .map(|f| format!("{}: {}", f.name, f.ty))
             .collect();
-        write!(
-            f,
-            "struct {}<{}> {{{}}}",
-            self.ident,
-            inst_tokens.join(", "),
-            field_tokens.join(",")
-        )
+        let variant_tokens: Vec<_> = self
+            .variants
+            .iter()
+            .map(|v| {
+                let field_tokens: Vec<_> = v
+                    .fields
+                    .iter()
+                    .map(|f| format!("{}: {}", f.name, f.ty))
+                    .collect();
+                format!("{} {{{}}}", v.name, field_tokens.join(","))
+            })
+            .collect();
+        if self.variants.is_empty() {
+            write!(
+                f,
+                "struct {}<{}> {{{}}}",
+                self.ident,
+                inst_tokens.join(", "),
+                field_tokens.join(",")
+            )
+        } else {
+            write!(
+                f,
+                "enum {}<{}> {{{}}}",
+                self.ident,
+                inst_tokens.join(", "),
+                variant_tokens.join(",")
+            )
+        }
     }
 }
 
@@ -205,12 +241,26 @@ impl StructInstantiation {
     }
 
     pub fn to_move_struct_layout(&self) -> MoveStructLayout {
-        MoveStructLayout::new(
-            self.fields
-                .iter()
-                .map(|e| e.ty.to_move_type_layout())
-                .collect(),
-        )
+        if self.variants.is_empty() {
+            MoveStructLayout::new(
+                self.fields
+                    .iter()
+                    .map(|e| e.ty.to_move_type_layout())
+                    .collect(),
+            )
+        } else {
+            MoveStructLayout::new_variants(
+                self.variants
+                    .iter()
+                    .map(|v| {
+                        v.fields
+                            .iter()
+                            .map(|e| e.ty.to_move_type_layout())
+                            .collect()
+                    })
+                    .collect(),
+            )
+        }
     }
 }
 
@@ -227,6 +277,14 @@ impl BaseType {
         BaseType::Primitive(PrimitiveType::Int(IntType::U8))
     }
 
+    pub fn mk_u16() -> Self {
+        BaseType::Primitive(PrimitiveType::Int(IntType::U16))
+    }
+
+    pub fn mk_u32() -> Self {
+        BaseType::Primitive(PrimitiveType::Int(IntType::U32))
+    }
+
     pub fn mk_u64() -> Self {
         BaseType::Primitive(PrimitiveType::Int(IntType::U64))
     }
@@ -235,6 +293,10 @@ impl BaseType {
         BaseType::Primitive(PrimitiveType::Int(IntType::U128))
     }
 
+    pub fn mk_u256() -> Self {
+        BaseType::Primitive(PrimitiveType::Int(IntType::U256))
+    }
+
     pub fn mk_num() -> Self {
         BaseType::Primitive(PrimitiveType::Int(IntType::Num))
     }
@@ -271,6 +333,14 @@ impl BaseType {
         matches!(self, BaseType::Primitive(PrimitiveType::Int(IntType::U8)))
     }
 
+    pub fn is_u16(&self) -> bool {
+        matches!(self, BaseType::Primitive(PrimitiveType::Int(IntType::U16)))
+    }
+
+    pub fn is_u32(&self) -> bool {
+        matches!(self, BaseType::Primitive(PrimitiveType::Int(IntType::U32)))
+    }
+
     pub fn is_u64(&self) -> bool {
         matches!(self, BaseType::Primitive(PrimitiveType::Int(IntType::U64)))
     }
@@ -279,6 +349,10 @@ impl BaseType {
         matches!(self, BaseType::Primitive(PrimitiveType::Int(IntType::U128)))
     }
 
+    pub fn is_u256(&self) -> bool {
+        matches!(self, BaseType::Primitive(PrimitiveType::Int(IntType::U256)))
+    }
+
     pub fn is_num(&self) -> bool {
         matches!(self, BaseType::Primitive(PrimitiveType::Int(IntType::Num)))
     }
@@ -400,11 +474,17 @@ impl BaseType {
         match (self, value) {
             (BaseType::Primitive(PrimitiveType::Bool), Constant::Bool(_)) => true,
             (BaseType::Primitive(PrimitiveType::Int(IntType::U8)), Constant::U8(_)) => true,
+            (BaseType::Primitive(PrimitiveType::Int(IntType::U16)), Constant::U16(_)) => true,
+            (BaseType::Primitive(PrimitiveType::Int(IntType::U32)), Constant::U32(_)) => true,
             (BaseType::Primitive(PrimitiveType::Int(IntType::U64)), Constant::U64(_)) => true,
             (BaseType::Primitive(PrimitiveType::Int(IntType::U128)), Constant::U128(_)) => true,
+            (BaseType::Primitive(PrimitiveType::Int(IntType::U256)), Constant::U256(_)) => true,
             (BaseType::Primitive(PrimitiveType::Int(IntType::Num)), Constant::U8(_)) => true,
+            (BaseType::Primitive(PrimitiveType::Int(IntType::Num)), Constant::U16(_)) => true,
+            (BaseType::Primitive(PrimitiveType::Int(IntType::Num)), Constant::U32(_)) => true,
             (BaseType::Primitive(PrimitiveType::Int(IntType::Num)), Constant::U64(_)) => true,
             (BaseType::Primitive(PrimitiveType::Int(IntType::Num)), Constant::U128(_)) => true,
+            (BaseType::Primitive(PrimitiveType::Int(IntType::Num)), Constant::U256(_)) => true,
             (BaseType::Primitive(PrimitiveType::Address), Constant::Address(_)) => true,
             (BaseType::Vector(elem_ty), Constant::ByteArray(_)) => {
                 elem_ty.as_ref() == &BaseType::mk_u8()
@@ -421,8 +501,11 @@ impl BaseType {
         match self {
             BaseType::Primitive(PrimitiveType::Bool) => TypeTag::Bool,
             BaseType::Primitive(PrimitiveType::Int(IntType::U8)) => TypeTag::U8,
+            BaseType::Primitive(PrimitiveType::Int(IntType::U16)) => TypeTag::U16,
+            BaseType::Primitive(PrimitiveType::Int(IntType::U32)) => TypeTag::U32,
             BaseType::Primitive(PrimitiveType::Int(IntType::U64)) => TypeTag::U64,
             BaseType::Primitive(PrimitiveType::Int(IntType::U128)) => TypeTag::U128,
+            BaseType::Primitive(PrimitiveType::Int(IntType::U256)) => TypeTag::U256,
             BaseType::Primitive(PrimitiveType::Int(IntType::Num)) => unreachable!(),
             BaseType::Primitive(PrimitiveType::Address) => TypeTag::Address,
             BaseType::Primitive(PrimitiveType::Signer) => TypeTag::Signer,
@@ -435,8 +518,11 @@ impl BaseType {
         match self {
             BaseType::Primitive(PrimitiveType::Bool) => MoveTypeLayout::Bool,
             BaseType::Primitive(PrimitiveType::Int(IntType::U8)) => MoveTypeLayout::U8,
+            BaseType::Primitive(PrimitiveType::Int(IntType::U16)) => MoveTypeLayout::U16,
+            BaseType::Primitive(PrimitiveType::Int(IntType::U32)) => MoveTypeLayout::U32,
             BaseType::Primitive(PrimitiveType::Int(IntType::U64)) => MoveTypeLayout::U64,
             BaseType::Primitive(PrimitiveType::Int(IntType::U128)) => MoveTypeLayout::U128,
+            BaseType::Primitive(PrimitiveType::Int(IntType::U256)) => MoveTypeLayout::U256,
             BaseType::Primitive(PrimitiveType::Int(IntType::Num)) => unreachable!(),
             BaseType::Primitive(PrimitiveType::Address) => MoveTypeLayout::Address,
             BaseType::Primitive(PrimitiveType::Signer) => MoveTypeLayout::Signer,
@@ -536,8 +622,11 @@ impl Type {
 
     gen!(mk_bool, mk_ref_bool, is_bool, is_ref_bool);
     gen!(mk_u8, mk_ref_u8, is_u8, is_ref_u8);
+    gen!(mk_u16, mk_ref_u16, is_u16, is_ref_u16);
+    gen!(mk_u32, mk_ref_u32, is_u32, is_ref_u32);
     gen!(mk_u64, mk_ref_u64, is_u64, is_ref_u64);
     gen!(mk_u128, mk_ref_u128, is_u128, is_ref_u128);
+    gen!(mk_u256, mk_ref_u256, is_u256, is_ref_u256);
     gen!(mk_num, mk_ref_num, is_num, is_ref_num);
     gen!(mk_address, mk_ref_address, is_address, is_ref_address);
     gen!(mk_signer, mk_ref_signer, is_signer, is_ref_signer);
@@ -711,6 +800,14 @@ impl PartialBaseType {
         Self::Primitive(PrimitiveType::Int(IntType::U8))
     }
 
+    pub fn mk_u16() -> Self {
+        Self::Primitive(PrimitiveType::Int(IntType::U16))
+    }
+
+    pub fn mk_u32() -> Self {
+        Self::Primitive(PrimitiveType::Int(IntType::U32))
+    }
+
     pub fn mk_u64() -> Self {
         Self::Primitive(PrimitiveType::Int(IntType::U64))
     }
@@ -719,6 +816,10 @@ impl PartialBaseType {
         Self::Primitive(PrimitiveType::Int(IntType::U128))
     }
 
+    pub fn mk_u256() -> Self {
+        Self::Primitive(PrimitiveType::Int(IntType::U256))
+    }
+
     pub fn mk_num() -> Self {
         Self::Primitive(PrimitiveType::Int(IntType::Num))
     }
@@ -765,8 +866,11 @@ pub fn convert_model_base_type(env: &GlobalEnv, ty: &MT::Type, subst: &[BaseType
     match ty {
         MT::Type::Primitive(MT::PrimitiveType::Bool) => BaseType::mk_bool(),
         MT::Type::Primitive(MT::PrimitiveType::U8) => BaseType::mk_u8(),
+        MT::Type::Primitive(MT::PrimitiveType::U16) => BaseType::mk_u16(),
+        MT::Type::Primitive(MT::PrimitiveType::U32) => BaseType::mk_u32(),
         MT::Type::Primitive(MT::PrimitiveType::U64) => BaseType::mk_u64(),
         MT::Type::Primitive(MT::PrimitiveType::U128) => BaseType::mk_u128(),
+        MT::Type::Primitive(MT::PrimitiveType::U256) => BaseType::mk_u256(),
         MT::Type::Primitive(MT::PrimitiveType::Num) => BaseType::mk_num(),
         MT::Type::Primitive(MT::PrimitiveType::Address) => BaseType::mk_address(),
         MT::Type::Primitive(MT::PrimitiveType::Signer) => BaseType::mk_signer(),
@@ -803,32 +907,51 @@ pub fn convert_model_struct_type(
         .collect();
 
     // collect fields
-    let fields = struct_env
-        .get_fields()
-        .map(|field_env| {
-            let field_name = env.symbol_pool().string(field_env.get_name()).to_string();
-            let field_ty = convert_model_base_type(env, &field_env.get_type(), &insts);
-            StructField {
-                name: field_name,
-                ty: field_ty,
-            }
-        })
-        .collect();
+    let (fields, variants) = convert_model_struct_fields(env, &struct_env, &insts);
 
     // return the information for constructing the struct type
     StructInstantiation {
         ident,
         insts,
         fields,
+        variants,
     }
 }
 
+/// Convert the fields of a struct, or those of the variants of an enum, instantiated by `insts`
+pub fn convert_model_struct_fields(
+    env: &GlobalEnv,
+    struct_env: &StructEnv<'_>,
+    insts: &[BaseType],
+) -> (Vec<StructField>, Vec<StructVariant>) {
+    let convert_field = |field_env: FieldEnv<'_>| {
+        let field_name = env.symbol_pool().string(field_env.get_name()).to_string();
+        let field_ty = convert_model_base_type(env, &field_env.get_type(), insts);
+        StructField {
+            name: field_name,
+            ty: field_ty,
+        }
+    };
+    let fields = struct_env.get_fields().map(convert_field).collect();
+    let variants = struct_env
+        .get_variants()
+        .map(|variant_env| StructVariant {
+            name: env.symbol_pool().string(variant_env.get_name()).to_string(),
+            fields: variant_env.get_fields().map(convert_field).collect(),
+        })
+        .collect();
+    (fields, variants)
+}
+
 pub fn convert_model_partial_base_type(env: &GlobalEnv, ty: &MT::Type) -> PartialBaseType {
     match ty {
         MT::Type::Primitive(MT::PrimitiveType::Bool) => PartialBaseType::mk_bool(),
         MT::Type::Primitive(MT::PrimitiveType::U8) => PartialBaseType::mk_u8(),
+        MT::Type::Primitive(MT::PrimitiveType::U16) => PartialBaseType::mk_u16(),
+        MT::Type::Primitive(MT::PrimitiveType::U32) => PartialBaseType::mk_u32(),
         MT::Type::Primitive(MT::PrimitiveType::U64) => PartialBaseType::mk_u64(),
         MT::Type::Primitive(MT::PrimitiveType::U128) => PartialBaseType::mk_u128(),
+        MT::Type::Primitive(MT::PrimitiveType::U256) => PartialBaseType::mk_u256(),
         MT::Type::Primitive(MT::PrimitiveType::Num) => PartialBaseType::mk_num(),
         MT::Type::Primitive(MT::PrimitiveType::Address) => PartialBaseType::mk_address(),
         MT::Type::Primitive(MT::PrimitiveType::Signer) => PartialBaseType::mk_signer(),
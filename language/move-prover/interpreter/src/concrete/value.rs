@@ -7,12 +7,13 @@
 //! and `TypedValue` must match `Type` (by match, we mean each value must have a way to type it,
 //! and each type must also have a way to construct a value of this type).
 
-use num::{BigInt, ToPrimitive};
+use num::{bigint::Sign, BigInt, ToPrimitive};
 use std::collections::{BTreeMap, BTreeSet};
 
 use move_core_types::{
     account_address::AccountAddress,
     effects::ChangeSet,
+    u256::U256,
     value::{MoveStruct, MoveValue},
 };
 use move_model::ast::{MemoryLabel, TempIndex};
@@ -36,6 +37,8 @@ pub enum BaseValue {
     Signer(AccountAddress),
     Vector(Vec<BaseValue>),
     Struct(Vec<BaseValue>),
+    /// A value of an enum: the index of its variant and the values of the fields of that variant
+    Variant(u16, Vec<BaseValue>),
 }
 
 impl BaseValue {
@@ -45,12 +48,21 @@ impl BaseValue {
     pub fn mk_u8(v: u8) -> Self {
         Self::Int(BigInt::from(v))
     }
+    pub fn mk_u16(v: u16) -> Self {
+        Self::Int(BigInt::from(v))
+    }
+    pub fn mk_u32(v: u32) -> Self {
+        Self::Int(BigInt::from(v))
+    }
     pub fn mk_u64(v: u64) -> Self {
         Self::Int(BigInt::from(v))
     }
     pub fn mk_u128(v: u128) -> Self {
         Self::Int(BigInt::from(v))
     }
+    pub fn mk_u256(v: U256) -> Self {
+        Self::Int(BigInt::from_bytes_le(Sign::Plus, &v.to_le_bytes()))
+    }
     pub fn mk_num(v: BigInt) -> Self {
         Self::Int(v)
     }
@@ -66,6 +78,9 @@ impl BaseValue {
     pub fn mk_struct(v: Vec<BaseValue>) -> Self {
         Self::Struct(v)
     }
+    pub fn mk_variant(tag: u16, v: Vec<BaseValue>) -> Self {
+        Self::Variant(tag, v)
+    }
 
     pub fn into_bool(self) -> bool {
         match self {
@@ -79,6 +94,18 @@ impl BaseValue {
             _ => unreachable!(),
         }
     }
+    pub fn into_u16(self) -> u16 {
+        match self {
+            Self::Int(v) => v.to_u16().unwrap(),
+            _ => unreachable!(),
+        }
+    }
+    pub fn into_u32(self) -> u32 {
+        match self {
+            Self::Int(v) => v.to_u32().unwrap(),
+            _ => unreachable!(),
+        }
+    }
     pub fn into_u64(self) -> u64 {
         match self {
             Self::Int(v) => v.to_u64().unwrap(),
@@ -91,6 +118,12 @@ impl BaseValue {
             _ => unreachable!(),
         }
     }
+    pub fn into_u256(self) -> U256 {
+        match self {
+            Self::Int(v) => big_int_to_u256(&v).unwrap(),
+            _ => unreachable!(),
+        }
+    }
     pub fn into_num(self) -> BigInt {
         match self {
             Self::Int(v) => v,
@@ -127,6 +160,12 @@ impl BaseValue {
             _ => unreachable!(),
         }
     }
+    pub fn into_variant(self) -> (u16, Vec<BaseValue>) {
+        match self {
+            Self::Variant(tag, v) => (tag, v),
+            _ => unreachable!(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
@@ -166,6 +205,14 @@ impl TypedValue {
                             .zip(inst.fields.iter())
                             .all(|(field_val, field_info)| type_match(&field_info.ty, field_val))
                 }
+                (BaseType::Struct(inst), BaseValue::Variant(tag, field_vals)) => {
+                    inst.variants.get(*tag as usize).map_or(false, |variant| {
+                        field_vals.len() == variant.fields.len()
+                            && field_vals.iter().zip(variant.fields.iter()).all(
+                                |(field_val, field_info)| type_match(&field_info.ty, field_val),
+                            )
+                    })
+                }
                 _ => false,
             }
         }
@@ -198,6 +245,20 @@ impl TypedValue {
             ptr: Pointer::None,
         }
     }
+    pub fn mk_u16(v: u16) -> Self {
+        Self {
+            ty: Type::mk_u16(),
+            val: BaseValue::mk_u16(v),
+            ptr: Pointer::None,
+        }
+    }
+    pub fn mk_u32(v: u32) -> Self {
+        Self {
+            ty: Type::mk_u32(),
+            val: BaseValue::mk_u32(v),
+            ptr: Pointer::None,
+        }
+    }
     pub fn mk_u64(v: u64) -> Self {
         Self {
             ty: Type::mk_u64(),
@@ -212,6 +273,13 @@ impl TypedValue {
             ptr: Pointer::None,
         }
     }
+    pub fn mk_u256(v: U256) -> Self {
+        Self {
+            ty: Type::mk_u256(),
+            val: BaseValue::mk_u256(v),
+            ptr: Pointer::None,
+        }
+    }
     pub fn mk_num(v: BigInt) -> Self {
         Self {
             ty: Type::mk_num(),
@@ -219,6 +287,28 @@ impl TypedValue {
             ptr: Pointer::None,
         }
     }
+    /// Create a value of the integer type `ty`, or `None` if `v` is out of the range of `ty`.
+    pub fn mk_int(ty: &Type, v: BigInt) -> Option<Self> {
+        let val = if ty.is_u8() {
+            Self::mk_u8(v.to_u8()?)
+        } else if ty.is_u16() {
+            Self::mk_u16(v.to_u16()?)
+        } else if ty.is_u32() {
+            Self::mk_u32(v.to_u32()?)
+        } else if ty.is_u64() {
+            Self::mk_u64(v.to_u64()?)
+        } else if ty.is_u128() {
+            Self::mk_u128(v.to_u128()?)
+        } else if ty.is_u256() {
+            Self::mk_u256(big_int_to_u256(&v)?)
+        } else {
+            if cfg!(debug_assertions) {
+                assert!(ty.is_num());
+            }
+            Self::mk_num(v)
+        };
+        Some(val)
+    }
     pub fn mk_address(v: AccountAddress) -> Self {
         Self {
             ty: Type::mk_address(),
@@ -258,6 +348,20 @@ impl TypedValue {
             ptr: Pointer::None,
         }
     }
+    pub fn mk_variant(inst: StructInstantiation, tag: u16, v: Vec<TypedValue>) -> Self {
+        if cfg!(debug_assertions) {
+            let variant = inst.variants.get(tag as usize).unwrap();
+            assert_eq!(variant.fields.len(), v.len());
+            for (e, field) in v.iter().zip(variant.fields.iter()) {
+                assert_eq!(e.ty.get_base_type(), &field.ty);
+            }
+        }
+        Self {
+            ty: Type::mk_struct(inst),
+            val: BaseValue::mk_variant(tag, v.into_iter().map(|e| e.val).collect()),
+            ptr: Pointer::None,
+        }
+    }
 
     pub fn mk_ref_bool(v: bool, is_mut: bool, ptr: Pointer) -> Self {
         Self {
@@ -273,6 +377,20 @@ impl TypedValue {
             ptr,
         }
     }
+    pub fn mk_ref_u16(v: u16, is_mut: bool, ptr: Pointer) -> Self {
+        Self {
+            ty: Type::mk_ref_u16(is_mut),
+            val: BaseValue::mk_u16(v),
+            ptr,
+        }
+    }
+    pub fn mk_ref_u32(v: u32, is_mut: bool, ptr: Pointer) -> Self {
+        Self {
+            ty: Type::mk_ref_u32(is_mut),
+            val: BaseValue::mk_u32(v),
+            ptr,
+        }
+    }
     pub fn mk_ref_u64(v: u64, is_mut: bool, ptr: Pointer) -> Self {
         Self {
             ty: Type::mk_ref_u64(is_mut),
@@ -287,6 +405,13 @@ impl TypedValue {
             ptr,
         }
     }
+    pub fn mk_ref_u256(v: U256, is_mut: bool, ptr: Pointer) -> Self {
+        Self {
+            ty: Type::mk_ref_u256(is_mut),
+            val: BaseValue::mk_u256(v),
+            ptr,
+        }
+    }
     pub fn mk_ref_num(v: BigInt, is_mut: bool, ptr: Pointer) -> Self {
         Self {
             ty: Type::mk_ref_num(is_mut),
@@ -355,6 +480,18 @@ impl TypedValue {
         }
         self.val.into_u8()
     }
+    pub fn into_u16(self) -> u16 {
+        if cfg!(debug_assertions) {
+            assert!(self.ty.is_u16());
+        }
+        self.val.into_u16()
+    }
+    pub fn into_u32(self) -> u32 {
+        if cfg!(debug_assertions) {
+            assert!(self.ty.is_u32());
+        }
+        self.val.into_u32()
+    }
     pub fn into_u64(self) -> u64 {
         if cfg!(debug_assertions) {
             assert!(self.ty.is_u64());
@@ -367,6 +504,12 @@ impl TypedValue {
         }
         self.val.into_u128()
     }
+    pub fn into_u256(self) -> U256 {
+        if cfg!(debug_assertions) {
+            assert!(self.ty.is_u256());
+        }
+        self.val.into_u256()
+    }
     pub fn into_num(self) -> BigInt {
         if cfg!(debug_assertions) {
             assert!(self.ty.is_num());
@@ -416,6 +559,18 @@ impl TypedValue {
         }
         (self.val.into_u8(), self.ty.into_ref_type().0, self.ptr)
     }
+    pub fn into_ref_u16(self) -> (u16, bool, Pointer) {
+        if cfg!(debug_assertions) {
+            assert!(self.ty.is_ref_u16(None));
+        }
+        (self.val.into_u16(), self.ty.into_ref_type().0, self.ptr)
+    }
+    pub fn into_ref_u32(self) -> (u32, bool, Pointer) {
+        if cfg!(debug_assertions) {
+            assert!(self.ty.is_ref_u32(None));
+        }
+        (self.val.into_u32(), self.ty.into_ref_type().0, self.ptr)
+    }
     pub fn into_ref_u64(self) -> (u64, bool, Pointer) {
         if cfg!(debug_assertions) {
             assert!(self.ty.is_ref_u64(None));
@@ -428,6 +583,12 @@ impl TypedValue {
         }
         (self.val.into_u128(), self.ty.into_ref_type().0, self.ptr)
     }
+    pub fn into_ref_u256(self) -> (U256, bool, Pointer) {
+        if cfg!(debug_assertions) {
+            assert!(self.ty.is_ref_u256(None));
+        }
+        (self.val.into_u256(), self.ty.into_ref_type().0, self.ptr)
+    }
     pub fn into_ref_num(self) -> (BigInt, bool, Pointer) {
         if cfg!(debug_assertions) {
             assert!(self.ty.is_ref_num(None));
@@ -762,6 +923,35 @@ impl TypedValue {
         }
     }
 
+    /// Unpack a value of an enum, returning the index of its variant and the fields of that variant
+    pub fn unpack_variant(self) -> (u16, Vec<TypedValue>) {
+        let (tag, v) = self.val.into_variant();
+        let fields = self
+            .ty
+            .into_struct_inst()
+            .variants
+            .swap_remove(tag as usize)
+            .fields;
+        let fields = v
+            .into_iter()
+            .zip(fields)
+            .map(|(field_val, field_info)| TypedValue {
+                ty: Type::Base(field_info.ty),
+                val: field_val,
+                ptr: Pointer::None,
+            })
+            .collect();
+        (tag, fields)
+    }
+
+    /// Get the index of the variant of an enum value behind a reference
+    pub fn get_ref_variant_tag(&self) -> u16 {
+        match &self.val {
+            BaseValue::Variant(tag, _) => *tag,
+            _ => unreachable!(),
+        }
+    }
+
     /// Unpack one specific field from a struct value
     pub fn unpack_struct_field(self, field_num: usize) -> TypedValue {
         let field = self.ty.into_struct_inst().fields.remove(field_num);
@@ -840,13 +1030,19 @@ impl TypedValue {
             BaseValue::Int(v) => {
                 if self.ty.is_u8() {
                     MoveValue::U8(v.to_u8().unwrap())
+                } else if self.ty.is_u16() {
+                    MoveValue::U16(v.to_u16().unwrap())
+                } else if self.ty.is_u32() {
+                    MoveValue::U32(v.to_u32().unwrap())
                 } else if self.ty.is_u64() {
                     MoveValue::U64(v.to_u64().unwrap())
+                } else if self.ty.is_u128() {
+                    MoveValue::U128(v.to_u128().unwrap())
                 } else {
                     if cfg!(debug_assertions) {
-                        assert!(self.ty.is_u128());
+                        assert!(self.ty.is_u256());
                     }
-                    MoveValue::U128(v.to_u128().unwrap())
+                    MoveValue::U256(big_int_to_u256(&v).unwrap())
                 }
             }
             BaseValue::Address(v) => MoveValue::Address(v),
@@ -881,6 +1077,27 @@ impl TypedValue {
                     .collect();
                 MoveValue::Struct(MoveStruct::new(move_fields))
             }
+            BaseValue::Variant(tag, v) => {
+                let move_fields = v
+                    .into_iter()
+                    .zip(
+                        self.ty
+                            .into_struct_inst()
+                            .variants
+                            .swap_remove(tag as usize)
+                            .fields,
+                    )
+                    .map(|(field_val, field_info)| {
+                        let full_field = TypedValue {
+                            ty: Type::Base(field_info.ty),
+                            val: field_val,
+                            ptr: Pointer::None,
+                        };
+                        full_field.into_move_value()
+                    })
+                    .collect();
+                MoveValue::Struct(MoveStruct::new_variant(tag, move_fields))
+            }
         }
     }
 
@@ -890,6 +1107,17 @@ impl TypedValue {
     }
 }
 
+/// Convert `v` into a `U256`, or return `None` if it is negative or does not fit into 256 bits.
+fn big_int_to_u256(v: &BigInt) -> Option<U256> {
+    let (sign, bytes) = v.to_bytes_le();
+    if sign == Sign::Minus || bytes.len() > 32 {
+        return None;
+    }
+    let mut le_bytes = [0u8; 32];
+    le_bytes[..bytes.len()].copy_from_slice(&bytes);
+    Some(U256::from_le_bytes(&le_bytes))
+}
+
 //**************************************************************************************************
 // Local state
 //**************************************************************************************************
@@ -117,8 +117,11 @@ pub fn interpret_with_options(
         .chain(options.args.into_iter().map(|arg| match arg {
             TransactionArgument::Bool(v) => MoveValue::Bool(v),
             TransactionArgument::U8(v) => MoveValue::U8(v),
+            TransactionArgument::U16(v) => MoveValue::U16(v),
+            TransactionArgument::U32(v) => MoveValue::U32(v),
             TransactionArgument::U64(v) => MoveValue::U64(v),
             TransactionArgument::U128(v) => MoveValue::U128(v),
+            TransactionArgument::U256(v) => MoveValue::U256(v),
             TransactionArgument::Address(v) => MoveValue::Address(v),
             TransactionArgument::U8Vector(v) => {
                 MoveValue::Vector(v.into_iter().map(MoveValue::U8).collect())
@@ -370,8 +373,11 @@ fn convert_typed_value_to_move_value(ty: &BaseType, val: BaseValue) -> MoveValue
     match ty {
         BaseType::Primitive(PrimitiveType::Bool) => MoveValue::Bool(val.into_bool()),
         BaseType::Primitive(PrimitiveType::Int(IntType::U8)) => MoveValue::U8(val.into_u8()),
+        BaseType::Primitive(PrimitiveType::Int(IntType::U16)) => MoveValue::U16(val.into_u16()),
+        BaseType::Primitive(PrimitiveType::Int(IntType::U32)) => MoveValue::U32(val.into_u32()),
         BaseType::Primitive(PrimitiveType::Int(IntType::U64)) => MoveValue::U64(val.into_u64()),
         BaseType::Primitive(PrimitiveType::Int(IntType::U128)) => MoveValue::U128(val.into_u128()),
+        BaseType::Primitive(PrimitiveType::Int(IntType::U256)) => MoveValue::U256(val.into_u256()),
         BaseType::Primitive(PrimitiveType::Int(IntType::Num)) => unreachable!(),
         BaseType::Primitive(PrimitiveType::Address) => MoveValue::Address(val.into_address()),
         BaseType::Primitive(PrimitiveType::Signer) => MoveValue::Signer(val.into_signer()),
@@ -381,6 +387,19 @@ fn convert_typed_value_to_move_value(ty: &BaseType, val: BaseValue) -> MoveValue
                 .map(|e| convert_typed_value_to_move_value(elem, e))
                 .collect(),
         ),
+        BaseType::Struct(inst) if !inst.variants.is_empty() => {
+            let (tag, field_vals) = val.into_variant();
+            MoveValue::Struct(MoveStruct::new_variant(
+                tag,
+                field_vals
+                    .into_iter()
+                    .zip(inst.variants[tag as usize].fields.iter())
+                    .map(|(field_val, field_info)| {
+                        convert_typed_value_to_move_value(&field_info.ty, field_val)
+                    })
+                    .collect(),
+            ))
+        }
         BaseType::Struct(inst) => MoveValue::Struct(MoveStruct::new(
             val.into_struct()
                 .into_iter()
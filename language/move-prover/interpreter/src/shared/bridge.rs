@@ -2,7 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use move_binary_format::errors::{PartialVMError, VMResult};
-use move_core_types::{effects::ChangeSet, language_storage::ModuleId, resolver::MoveResolver};
+use move_core_types::{
+    effects::{AccountChangeSet, ChangeSet},
+    resolver::MoveResolver,
+};
 
 /// The result returned by the stackless VM does not contain code offsets and indices. In order to
 /// do cross-vm comparison, we need to adapt the Move VM result by removing these fields.
@@ -36,33 +39,45 @@ fn adapt_move_vm_change_set_internal<S: MoveResolver>(
 ) -> ChangeSet {
     let mut adapted = ChangeSet::new();
     for (addr, state) in change_set.into_inner() {
-        let (modules, resources) = state.into_inner();
+        let (modules, resources, resource_groups) = state.into_inner_with_groups();
+        let mut account = AccountChangeSet::new();
         for (tag, val) in resources {
             match val {
                 // deletion
-                None => adapted.unpublish_resource(addr, tag).unwrap(),
+                None => account.unpublish_resource(tag).unwrap(),
                 // addition / modification
                 Some(new_val) => match old_storage.get_resource(&addr, &tag).unwrap() {
                     // addition
-                    None => adapted.publish_resource(addr, tag, new_val).unwrap(),
+                    None => account.publish_resource(tag, new_val).unwrap(),
                     // modification is only added to change_set if the values actually change
                     Some(old_val) => {
                         if new_val != old_val {
-                            adapted.publish_resource(addr, tag, new_val).unwrap();
+                            account.publish_resource(tag, new_val).unwrap();
                         }
                     }
                 },
             }
         }
+        // the stackless VM has no notion of resource groups, their changes are kept as-is
+        for (group, members) in resource_groups {
+            for (tag, val) in members {
+                match val {
+                    None => account.unpublish_group_member(group.clone(), tag).unwrap(),
+                    Some(blob) => account
+                        .publish_group_member(group.clone(), tag, blob)
+                        .unwrap(),
+                }
+            }
+        }
         for (module_name, blob_opt) in modules {
-            let module_id = ModuleId::new(addr, module_name);
             match blob_opt {
                 // deletion
-                None => adapted.unpublish_module(module_id).unwrap(),
+                None => account.unpublish_module(module_name).unwrap(),
                 // addition
-                Some(blob) => adapted.publish_module(module_id, blob).unwrap(),
+                Some(blob) => account.publish_module(module_name, blob).unwrap(),
             }
         }
+        adapted.publish_or_overwrite_account_change_set(addr, account);
     }
     adapted
 }
@@ -8,7 +8,7 @@ use codespan::{ByteIndex, Span};
 use itertools::Itertools;
 use move_compiler::parser::keywords::{BUILTINS, CONTEXTUAL_KEYWORDS, KEYWORDS};
 use move_model::{
-    ast::{ModuleName, SpecBlockInfo, SpecBlockTarget},
+    ast::{Attribute, ModuleName, SpecBlockInfo, SpecBlockTarget},
     code_writer::{CodeWriter, CodeWriterLabel},
     emit, emitln,
     model::{
@@ -536,6 +536,7 @@ impl<'env> Docgen<'env> {
         self.increment_section_nest();
 
         // Document module overview.
+        self.deprecation_notice(module_env.get_attributes());
         self.doc_text(module_env.get_doc());
 
         // If this is a standalone doc, generate TOC header.
@@ -895,6 +896,7 @@ impl<'env> Docgen<'env> {
         self.increment_section_nest();
         for const_env in self.current_module.as_ref().unwrap().get_named_constants() {
             self.label(&self.label_for_module_item(&const_env.module_env, const_env.get_name()));
+            self.deprecation_notice(const_env.get_attributes());
             self.doc_text(const_env.get_doc());
             self.code_block(&self.named_constant_display(&const_env));
         }
@@ -910,6 +912,7 @@ impl<'env> Docgen<'env> {
             &self.label_for_module_item(&struct_env.module_env, name),
         );
         self.increment_section_nest();
+        self.deprecation_notice(struct_env.get_attributes());
         self.doc_text(struct_env.get_doc());
         self.code_block(&self.struct_header_display(struct_env));
 
@@ -1007,6 +1010,7 @@ impl<'env> Docgen<'env> {
             );
             self.increment_section_nest();
         }
+        self.deprecation_notice(func_env.get_attributes());
         self.doc_text(func_env.get_doc());
         let sig = self.function_header_display(func_env);
         self.code_block(&sig);
@@ -1403,6 +1407,18 @@ impl<'env> Docgen<'env> {
         }
     }
 
+    /// Outputs a notice if the item with `attributes` is deprecated, along with the note given
+    /// with the deprecation.
+    fn deprecation_notice(&self, attributes: &[Attribute]) {
+        if !self.env.is_deprecated(attributes) {
+            return;
+        }
+        match self.env.get_deprecation_note(attributes) {
+            Some(note) => self.doc_text(&format!("**Deprecated**: {}", note)),
+            None => self.doc_text("**Deprecated**"),
+        }
+    }
+
     /// Outputs documentation text.
     fn doc_text_general(&self, for_root: bool, text: &str) {
         for line in self.decorate_text(text).lines() {
@@ -0,0 +1,24 @@
+#[deprecated(note = b"use `0x42::NewModule` instead")]
+/// A module with deprecated items.
+module 0x42::DeprecatedItems {
+    #[deprecated]
+    /// A deprecated constant.
+    const OLD_LIMIT: u64 = 10;
+
+    /// A constant.
+    const LIMIT: u64 = 20;
+
+    #[deprecated(note = b"use `NewStruct` instead")]
+    /// A deprecated struct.
+    struct OldStruct has drop {}
+
+    /// A struct.
+    struct NewStruct has drop {}
+
+    #[deprecated(note = b"use `new_fun` instead")]
+    /// A deprecated function.
+    public fun old_fun(): u64 { OLD_LIMIT }
+
+    /// A function.
+    public fun new_fun(): u64 { LIMIT }
+}
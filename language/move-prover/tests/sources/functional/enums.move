@@ -0,0 +1,51 @@
+module 0x42::TestEnums {
+
+    spec module {
+        pragma verify = true;
+    }
+
+    enum Shape has copy, drop {
+        Circle { radius: u64 },
+        Rect { width: u64, height: u64 },
+        Empty,
+    }
+
+    fun area(s: Shape): u64 {
+        match (s) {
+            Shape::Circle { radius } => 3 * radius * radius,
+            Shape::Rect { width, height } => width * height,
+            Shape::Empty => 0,
+        }
+    }
+
+    fun is_empty(s: &Shape): bool {
+        match (s) {
+            Shape::Empty => true,
+            _ => false,
+        }
+    }
+
+    fun empty_area(): u64 {
+        area(Shape::Empty)
+    }
+    spec empty_area {
+        aborts_if false;
+        ensures result == 0;
+    }
+
+    fun rect_area(width: u64, height: u64): u64 {
+        area(Shape::Rect { width, height })
+    }
+    spec rect_area {
+        aborts_if width * height > MAX_U64;
+        ensures result == width * height;
+    }
+
+    fun circle_not_empty(radius: u64): bool {
+        is_empty(&Shape::Circle { radius })
+    }
+    spec circle_not_empty {
+        aborts_if false;
+        ensures result == false;
+    }
+}
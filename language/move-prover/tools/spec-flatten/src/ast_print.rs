@@ -55,8 +55,11 @@ impl SpecPrinter<'_> {
         match ty {
             Type::Primitive(PrimitiveType::Bool) => Self::doc("bool"),
             Type::Primitive(PrimitiveType::U8) => Self::doc("u8"),
+            Type::Primitive(PrimitiveType::U16) => Self::doc("u16"),
+            Type::Primitive(PrimitiveType::U32) => Self::doc("u32"),
             Type::Primitive(PrimitiveType::U64) => Self::doc("u64"),
             Type::Primitive(PrimitiveType::U128) => Self::doc("u128"),
+            Type::Primitive(PrimitiveType::U256) => Self::doc("u256"),
             Type::Primitive(PrimitiveType::Address) => Self::doc("address"),
             Type::Primitive(PrimitiveType::Signer) => Self::doc("signer"),
             Type::Primitive(PrimitiveType::Num) => Self::doc("num"),
@@ -322,8 +325,11 @@ impl SpecPrinter<'_> {
                     TypeDomain => self.print_type(&self.env.get_node_type(*node_id), ty_params),
                     ResourceDomain => self.print_type(&self.env.get_node_type(*node_id), ty_params),
                     MaxU8 => Self::doc("MAX_U8"),
+                    MaxU16 => Self::doc("MAX_U16"),
+                    MaxU32 => Self::doc("MAX_U32"),
                     MaxU64 => Self::doc("MAX_U64"),
                     MaxU128 => Self::doc("MAX_U128"),
+                    MaxU256 => Self::doc("MAX_U256"),
                     // unable to be specified by users
                     CanModify | AbortFlag | AbortCode | WellFormed | BoxValue | UnboxValue
                     | EmptyEventStore | ExtendEventStore | EventStoreIncludes
@@ -54,6 +54,11 @@ pub fn native_push_back(
         NativeCostIndex::PUSH_BACK,
         e.size().get() as usize,
     );
+    if r.len(&ty_args[0])?.value_as::<u64>()? >= context.vm_config().max_vector_length {
+        return Err(PartialVMError::new(
+            StatusCode::VM_MAX_VECTOR_LENGTH_REACHED,
+        ));
+    }
     NativeResult::map_partial_vm_result_empty(cost, r.push_back(e, &ty_args[0]))
 }
 
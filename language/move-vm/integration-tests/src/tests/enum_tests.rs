@@ -0,0 +1,238 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::compiler::{as_module, compile_units};
+use move_binary_format::errors::VMResult;
+use move_core_types::{
+    account_address::AccountAddress,
+    identifier::Identifier,
+    language_storage::ModuleId,
+    value::{MoveStruct, MoveStructLayout, MoveTypeLayout, MoveValue},
+    vm_status::StatusCode,
+};
+use move_vm_runtime::{move_vm::MoveVM, session::SerializedReturnValues};
+use move_vm_test_utils::InMemoryStorage;
+use move_vm_types::gas_schedule::GasStatus;
+
+const TEST_ADDR: AccountAddress = AccountAddress::new([42; AccountAddress::LENGTH]);
+
+const CODE: &str = r#"
+    module {{ADDR}}::M {
+        enum Shape has copy, drop {
+            Circle { radius: u64 },
+            Rect { width: u64, height: u64 },
+            Empty,
+        }
+
+        fun make(kind: u8): Shape {
+            if (kind == 0) Shape::Circle { radius: 2 }
+            else if (kind == 1) Shape::Rect { width: 2, height: 3 }
+            else Shape::Empty
+        }
+
+        fun area(s: Shape): u64 {
+            match (s) {
+                Shape::Circle { radius } => 3 * radius * radius,
+                Shape::Rect { width, height } => width * height,
+                Shape::Empty => 0,
+            }
+        }
+
+        fun is_empty(s: &Shape): bool {
+            match (s) {
+                Shape::Empty => true,
+                _ => false,
+            }
+        }
+
+        fun area_of_rect(s: Shape): u64 {
+            match (s) {
+                Shape::Rect { width, height } => width * height,
+                _ => abort 42,
+            }
+        }
+    }
+"#;
+
+fn run(fun_name: &str, args: Vec<MoveValue>) -> VMResult<Vec<Vec<u8>>> {
+    let code = CODE.replace("{{ADDR}}", &format!("0x{}", TEST_ADDR));
+    let m = as_module(compile_units(&code).unwrap().pop().unwrap());
+    let mut blob = vec![];
+    m.serialize(&mut blob).unwrap();
+
+    let mut storage = InMemoryStorage::new();
+    let module_id = ModuleId::new(TEST_ADDR, Identifier::new("M").unwrap());
+    storage.publish_or_overwrite_module(module_id.clone(), blob);
+
+    let vm = MoveVM::new(vec![]).unwrap();
+    let mut sess = vm.new_session(&storage);
+    let fun_name = Identifier::new(fun_name).unwrap();
+    let mut gas_status = GasStatus::new_unmetered();
+    let args = args
+        .into_iter()
+        .map(|val| val.simple_serialize().unwrap())
+        .collect();
+
+    let SerializedReturnValues {
+        return_values,
+        mutable_reference_outputs: _,
+    } = sess.execute_function_bypass_visibility(
+        &module_id,
+        &fun_name,
+        vec![],
+        args,
+        &mut gas_status,
+    )?;
+    Ok(return_values
+        .into_iter()
+        .map(|(bytes, _layout)| bytes)
+        .collect())
+}
+
+fn shape_layout() -> MoveTypeLayout {
+    MoveTypeLayout::Struct(MoveStructLayout::new_variants(vec![
+        vec![MoveTypeLayout::U64],
+        vec![MoveTypeLayout::U64, MoveTypeLayout::U64],
+        vec![],
+    ]))
+}
+
+fn shape(tag: u16, fields: Vec<u64>) -> MoveValue {
+    MoveValue::Struct(MoveStruct::new_variant(
+        tag,
+        fields.into_iter().map(MoveValue::U64).collect(),
+    ))
+}
+
+fn return_value(fun_name: &str, arg: MoveValue, layout: &MoveTypeLayout) -> MoveValue {
+    let bytes = run(fun_name, vec![arg]).unwrap().pop().unwrap();
+    MoveValue::simple_deserialize(&bytes, layout).unwrap()
+}
+
+fn return_u64(fun_name: &str, arg: MoveValue) -> MoveValue {
+    return_value(fun_name, arg, &MoveTypeLayout::U64)
+}
+
+#[test]
+fn pack_variants() {
+    let expected = vec![shape(0, vec![2]), shape(1, vec![2, 3]), shape(2, vec![])];
+    for (kind, expected) in expected.into_iter().enumerate() {
+        let bytes = run("make", vec![MoveValue::U8(kind as u8)])
+            .unwrap()
+            .pop()
+            .unwrap();
+        // The variant tag is serialized before the fields.
+        assert_eq!(bytes[0], kind as u8);
+        assert_eq!(
+            MoveValue::simple_deserialize(&bytes, &shape_layout()).unwrap(),
+            expected
+        );
+    }
+}
+
+#[test]
+fn match_variants() {
+    assert_eq!(return_u64("area", shape(0, vec![2])), MoveValue::U64(12));
+    assert_eq!(return_u64("area", shape(1, vec![2, 3])), MoveValue::U64(6));
+    assert_eq!(return_u64("area", shape(2, vec![])), MoveValue::U64(0));
+
+    let is_empty = |s| return_value("is_empty", s, &MoveTypeLayout::Bool);
+    assert_eq!(is_empty(shape(2, vec![])), MoveValue::Bool(true));
+    assert_eq!(is_empty(shape(1, vec![2, 3])), MoveValue::Bool(false));
+}
+
+#[test]
+fn wildcard_arm_aborts() {
+    assert_eq!(
+        return_u64("area_of_rect", shape(1, vec![4, 5])),
+        MoveValue::U64(20)
+    );
+    let err = run("area_of_rect", vec![shape(0, vec![1])]).unwrap_err();
+    assert_eq!(err.major_status(), StatusCode::ABORTED);
+    assert_eq!(err.sub_status(), Some(42));
+}
+
+#[test]
+fn invalid_variant_tag_argument() {
+    let err = run("area", vec![shape(3, vec![])]).unwrap_err();
+    assert_eq!(
+        err.major_status(),
+        StatusCode::FAILED_TO_DESERIALIZE_ARGUMENT
+    );
+}
+
+#[test]
+fn enums_in_multiple_modules() {
+    let code = r#"
+        module {{ADDR}}::M {
+            enum Shape has drop {
+                Circle { radius: u64 },
+                Rect { width: u64, height: u64 },
+            }
+
+            public fun rect(width: u64, height: u64): Shape {
+                Shape::Rect { width, height }
+            }
+
+            public fun area(s: Shape): u64 {
+                match (s) {
+                    Shape::Circle { radius } => 3 * radius * radius,
+                    Shape::Rect { width, height } => width * height,
+                }
+            }
+        }
+
+        module {{ADDR}}::N {
+            use {{ADDR}}::M;
+
+            enum Scale has drop { Single, Double }
+
+            fun factor(s: Scale): u64 {
+                match (s) {
+                    Scale::Single => 1,
+                    Scale::Double => 2,
+                }
+            }
+
+            public fun scaled_area(width: u64, height: u64): u64 {
+                factor(Scale::Double) * M::area(M::rect(width, height))
+            }
+        }
+    "#;
+    let code = code.replace("{{ADDR}}", &format!("0x{}", TEST_ADDR));
+    let units = compile_units(&code).unwrap();
+
+    let mut storage = InMemoryStorage::new();
+    for unit in units {
+        let m = as_module(unit);
+        let mut blob = vec![];
+        m.serialize(&mut blob).unwrap();
+        storage.publish_or_overwrite_module(m.self_id(), blob);
+    }
+
+    let vm = MoveVM::new(vec![]).unwrap();
+    let mut sess = vm.new_session(&storage);
+    let module_id = ModuleId::new(TEST_ADDR, Identifier::new("N").unwrap());
+    let fun_name = Identifier::new("scaled_area").unwrap();
+    let args = vec![MoveValue::U64(2), MoveValue::U64(3)]
+        .into_iter()
+        .map(|val| val.simple_serialize().unwrap())
+        .collect();
+    let SerializedReturnValues {
+        mut return_values,
+        mutable_reference_outputs: _,
+    } = sess
+        .execute_function_bypass_visibility(
+            &module_id,
+            &fun_name,
+            vec![],
+            args,
+            &mut GasStatus::new_unmetered(),
+        )
+        .unwrap();
+    let (bytes, _layout) = return_values.pop().unwrap();
+    assert_eq!(
+        MoveValue::simple_deserialize(&bytes, &MoveTypeLayout::U64).unwrap(),
+        MoveValue::U64(12)
+    );
+}
@@ -0,0 +1,171 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::compiler::{as_module, compile_units};
+use move_binary_format::{errors::PartialVMResult, file_format_common::Opcodes};
+use move_core_types::{
+    account_address::AccountAddress,
+    gas_schedule::{
+        AbstractMemorySize, CostTable, GasAlgebra, GasCarrier, GasUnits, InternalGasUnits,
+    },
+    identifier::Identifier,
+    language_storage::ModuleId,
+    value::{serialize_values, MoveValue},
+};
+use move_vm_runtime::move_vm::MoveVM;
+use move_vm_test_utils::InMemoryStorage;
+use move_vm_types::{
+    gas_schedule::{zero_cost_schedule, GasMeter},
+    loaded_data::runtime_types::Type,
+    values::Value,
+};
+use std::collections::VecDeque;
+
+const TEST_ADDR: AccountAddress = AccountAddress::new([42; AccountAddress::LENGTH]);
+
+/// A meter that does not charge anything but records what the VM reported.
+struct RecordingMeter {
+    cost_table: CostTable,
+    instructions: usize,
+    calls: Vec<(u8, usize, usize, usize)>,
+    resource_loads: Vec<Option<Option<usize>>>,
+}
+
+impl RecordingMeter {
+    fn new() -> Self {
+        Self {
+            cost_table: zero_cost_schedule(0),
+            instructions: 0,
+            calls: vec![],
+            resource_loads: vec![],
+        }
+    }
+}
+
+impl GasMeter for RecordingMeter {
+    fn charge_instr(&mut self, _opcode: Opcodes) -> PartialVMResult<()> {
+        self.instructions += 1;
+        Ok(())
+    }
+
+    fn charge_instr_with_size(
+        &mut self,
+        _opcode: Opcodes,
+        _size: AbstractMemorySize<GasCarrier>,
+    ) -> PartialVMResult<()> {
+        self.instructions += 1;
+        Ok(())
+    }
+
+    fn charge_call(
+        &mut self,
+        opcode: Opcodes,
+        ty_args: &[Type],
+        arg_count: usize,
+        call_depth: usize,
+    ) -> PartialVMResult<()> {
+        self.calls
+            .push((opcode as u8, ty_args.len(), arg_count, call_depth));
+        Ok(())
+    }
+
+    fn charge_load_resource(&mut self, loaded: Option<Option<usize>>) -> PartialVMResult<()> {
+        self.resource_loads.push(loaded);
+        Ok(())
+    }
+
+    fn charge_native_function_before_execution(
+        &mut self,
+        _ty_args: &[Type],
+        _args: &VecDeque<Value>,
+    ) -> PartialVMResult<()> {
+        Ok(())
+    }
+
+    fn charge_native_function(
+        &mut self,
+        _amount: InternalGasUnits<GasCarrier>,
+    ) -> PartialVMResult<()> {
+        Ok(())
+    }
+
+    fn cost_table(&self) -> &CostTable {
+        &self.cost_table
+    }
+
+    fn remaining_gas(&self) -> GasUnits<GasCarrier> {
+        GasUnits::new(0)
+    }
+}
+
+#[test]
+fn custom_gas_meter() {
+    let code = r#"
+        module {{ADDR}}::M {
+            struct Foo has key { a: u64 }
+            struct Bar<T: store> has key { t: T }
+
+            public fun publish(s: &signer) {
+                move_to(s, Foo { a: 1 })
+            }
+            public fun check(addr: address): bool {
+                id<u64>(1);
+                exists<Foo>(addr) && exists<Foo>(addr) && !exists<Bar<u64>>(addr)
+            }
+            fun id<T>(t: T): T {
+                t
+            }
+        }
+    "#;
+
+    let code = code.replace("{{ADDR}}", &format!("0x{}", TEST_ADDR));
+    let mut units = compile_units(&code).unwrap();
+    let m = as_module(units.pop().unwrap());
+    let mut blob = vec![];
+    m.serialize(&mut blob).unwrap();
+
+    let mut storage = InMemoryStorage::new();
+    let module_id = ModuleId::new(TEST_ADDR, Identifier::new("M").unwrap());
+    storage.publish_or_overwrite_module(module_id.clone(), blob);
+
+    let vm = MoveVM::new(vec![]).unwrap();
+    let account = AccountAddress::random();
+
+    let mut sess = vm.new_session(&storage);
+    let mut gas_meter = RecordingMeter::new();
+    sess.execute_function_bypass_visibility(
+        &module_id,
+        &Identifier::new("publish").unwrap(),
+        vec![],
+        serialize_values(&vec![MoveValue::Signer(account)]),
+        &mut gas_meter,
+    )
+    .unwrap();
+    // `move_to` looks up a resource that does not exist yet.
+    assert_eq!(gas_meter.resource_loads, vec![Some(None)]);
+    let (changes, _) = sess.finish().unwrap();
+    storage.apply(changes).unwrap();
+
+    let mut sess = vm.new_session(&storage);
+    let mut gas_meter = RecordingMeter::new();
+    sess.execute_function_bypass_visibility(
+        &module_id,
+        &Identifier::new("check").unwrap(),
+        vec![],
+        serialize_values(&vec![MoveValue::Address(account)]),
+        &mut gas_meter,
+    )
+    .unwrap();
+
+    assert!(gas_meter.instructions > 0);
+    assert_eq!(
+        gas_meter.calls,
+        vec![(Opcodes::CALL_GENERIC as u8, 1, 1, 0)]
+    );
+    // The first access reads `Foo` from storage, the second one hits the data cache and the
+    // lookup of `Bar<u64>` does not find anything.
+    assert_eq!(gas_meter.resource_loads.len(), 3);
+    assert!(matches!(gas_meter.resource_loads[0], Some(Some(n)) if n > 0));
+    assert_eq!(gas_meter.resource_loads[1], None);
+    assert_eq!(gas_meter.resource_loads[2], Some(None));
+}
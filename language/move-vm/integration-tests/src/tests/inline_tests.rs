@@ -0,0 +1,100 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::compiler::{as_module, compile_units};
+use move_binary_format::{access::ModuleAccess, file_format::CompiledModule};
+use move_core_types::{
+    account_address::AccountAddress,
+    identifier::Identifier,
+    language_storage::ModuleId,
+    value::{MoveTypeLayout, MoveValue},
+};
+use move_vm_runtime::{move_vm::MoveVM, session::SerializedReturnValues};
+use move_vm_test_utils::InMemoryStorage;
+use move_vm_types::gas_schedule::GasStatus;
+
+const TEST_ADDR: AccountAddress = AccountAddress::new([42; AccountAddress::LENGTH]);
+
+const CODE: &str = r#"
+    module {{ADDR}}::M {
+        inline fun for_each(n: u64, f: |u64|()) {
+            let i = 0;
+            while (i < n) {
+                f(i);
+                i = i + 1;
+            }
+        }
+
+        inline fun apply_twice(x: u64, f: |u64|u64): u64 {
+            let i = f(x);
+            i + f(i)
+        }
+
+        fun sum_to(n: u64): u64 {
+            let sum = 0;
+            for_each(n, |i| sum = sum + i);
+            sum
+        }
+
+        fun captures(i: u64): u64 {
+            apply_twice(1, |x| x + i)
+        }
+    }
+"#;
+
+fn compile() -> CompiledModule {
+    let code = CODE.replace("{{ADDR}}", &format!("0x{}", TEST_ADDR));
+    as_module(compile_units(&code).unwrap().pop().unwrap())
+}
+
+fn run_u64(fun_name: &str, arg: u64) -> MoveValue {
+    let mut blob = vec![];
+    compile().serialize(&mut blob).unwrap();
+
+    let mut storage = InMemoryStorage::new();
+    let module_id = ModuleId::new(TEST_ADDR, Identifier::new("M").unwrap());
+    storage.publish_or_overwrite_module(module_id.clone(), blob);
+
+    let vm = MoveVM::new(vec![]).unwrap();
+    let mut sess = vm.new_session(&storage);
+    let fun_name = Identifier::new(fun_name).unwrap();
+    let mut gas_status = GasStatus::new_unmetered();
+    let args = vec![MoveValue::U64(arg).simple_serialize().unwrap()];
+
+    let SerializedReturnValues {
+        mut return_values,
+        mutable_reference_outputs: _,
+    } = sess
+        .execute_function_bypass_visibility(&module_id, &fun_name, vec![], args, &mut gas_status)
+        .unwrap();
+    let (bytes, _layout) = return_values.pop().unwrap();
+    MoveValue::simple_deserialize(&bytes, &MoveTypeLayout::U64).unwrap()
+}
+
+#[test]
+fn inline_functions_are_not_compiled() {
+    let m = compile();
+    let mut names = m
+        .function_defs()
+        .iter()
+        .map(|def| {
+            m.identifier_at(m.function_handle_at(def.function).name)
+                .as_str()
+        })
+        .collect::<Vec<_>>();
+    names.sort_unstable();
+    assert_eq!(names, vec!["captures", "sum_to"]);
+}
+
+#[test]
+fn lambda_updates_caller_local() {
+    assert_eq!(run_u64("sum_to", 5), MoveValue::U64(10));
+    assert_eq!(run_u64("sum_to", 0), MoveValue::U64(0));
+}
+
+#[test]
+fn lambda_captures_caller_local() {
+    // The local `i` of `apply_twice` does not capture the `i` the lambda refers to:
+    // f(1) = 11 and f(11) = 21.
+    assert_eq!(run_u64("captures", 10), MoveValue::U64(32));
+}
@@ -3,8 +3,16 @@
 
 mod bad_entry_point_tests;
 mod bad_storage_tests;
+mod enum_tests;
 mod exec_func_effects_tests;
 mod function_arg_tests;
+mod gas_meter_tests;
+mod inline_tests;
 mod loader_tests;
+mod module_cache_tests;
 mod mutated_accounts_tests;
+mod read_set_tests;
+mod resource_group_tests;
 mod return_value_tests;
+mod trace_sink_tests;
+mod vm_config_tests;
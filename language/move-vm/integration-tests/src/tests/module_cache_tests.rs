@@ -0,0 +1,146 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::compiler::{as_module, as_script, compile_units};
+use move_binary_format::CompiledModule;
+use move_core_types::{
+    account_address::AccountAddress, identifier::Identifier, language_storage::ModuleId,
+    value::MoveValue,
+};
+use move_vm_runtime::move_vm::MoveVM;
+use move_vm_test_utils::InMemoryStorage;
+use move_vm_types::gas_schedule::GasStatus;
+
+const TEST_ADDR: AccountAddress = AccountAddress::new([42; AccountAddress::LENGTH]);
+
+fn compile_module(code: &str) -> CompiledModule {
+    let code = code.replace("{{ADDR}}", &format!("0x{}", TEST_ADDR));
+    let mut units = compile_units(&code).unwrap();
+    as_module(units.pop().unwrap())
+}
+
+fn publish(storage: &mut InMemoryStorage, module: &CompiledModule) {
+    let mut blob = vec![];
+    module.serialize(&mut blob).unwrap();
+    storage.publish_or_overwrite_module(module.self_id(), blob);
+}
+
+fn call_get(vm: &MoveVM, storage: &InMemoryStorage, module_id: &ModuleId) -> Vec<u8> {
+    let mut sess = vm.new_session(storage);
+    let mut gas_status = GasStatus::new_unmetered();
+    let mut return_values = sess
+        .execute_function_bypass_visibility(
+            module_id,
+            &Identifier::new("get").unwrap(),
+            vec![],
+            Vec::<Vec<u8>>::new(),
+            &mut gas_status,
+        )
+        .unwrap()
+        .return_values;
+    return_values.pop().unwrap().0
+}
+
+#[test]
+fn flush_upgraded_module() {
+    let a_v1 = compile_module(
+        r#"
+        module {{ADDR}}::A {
+            public fun value(): u64 { 1 }
+        }
+    "#,
+    );
+    let a_v2 = compile_module(
+        r#"
+        module {{ADDR}}::A {
+            public fun value(): u64 { 2 }
+        }
+    "#,
+    );
+    let b = compile_module(
+        r#"
+        module {{ADDR}}::A {
+            public fun value(): u64 { 1 }
+        }
+        module {{ADDR}}::B {
+            use {{ADDR}}::A;
+            public fun get(): u64 { A::value() }
+        }
+    "#,
+    );
+
+    let mut storage = InMemoryStorage::new();
+    publish(&mut storage, &a_v1);
+    publish(&mut storage, &b);
+    let a_id = a_v1.self_id();
+    let b_id = b.self_id();
+
+    let vm = MoveVM::new(vec![]).unwrap();
+    let one = MoveValue::U64(1).simple_serialize().unwrap();
+    let two = MoveValue::U64(2).simple_serialize().unwrap();
+    assert_eq!(call_get(&vm, &storage, &b_id), one);
+    let metrics = vm.cache_metrics();
+    assert_eq!(metrics.module_hits, 0);
+    assert_eq!(metrics.module_misses, 1);
+
+    // The upgrade is not visible until the module is flushed.
+    publish(&mut storage, &a_v2);
+    assert_eq!(call_get(&vm, &storage, &b_id), one);
+    assert_eq!(vm.cache_metrics().module_hits, 1);
+
+    // Flushing `A` flushes `B` as well, which depends on it.
+    assert_eq!(vm.flush_module(&a_id), 2);
+    assert_eq!(call_get(&vm, &storage, &b_id), two);
+    let metrics = vm.cache_metrics();
+    assert_eq!(metrics.modules_flushed, 2);
+    assert_eq!(metrics.module_misses, 2);
+
+    // Flushing a module which is not cached does nothing.
+    let unknown = ModuleId::new(TEST_ADDR, Identifier::new("C").unwrap());
+    assert_eq!(vm.mark_modules_stale(vec![unknown]), 0);
+}
+
+#[test]
+fn bounded_script_cache() {
+    let code = r#"
+        script {
+            fun main() { }
+        }
+        script {
+            fun main() { let _x = 1; }
+        }
+    "#;
+    let mut units = compile_units(code).unwrap();
+    let scripts: Vec<_> = vec![as_script(units.remove(0)), as_script(units.remove(0))]
+        .into_iter()
+        .map(|script| {
+            let mut blob = vec![];
+            script.serialize(&mut blob).unwrap();
+            blob
+        })
+        .collect();
+
+    let storage = InMemoryStorage::new();
+    let vm = MoveVM::new(vec![]).unwrap();
+    vm.set_script_cache_capacity(Some(1));
+    let run = |script: &Vec<u8>| {
+        let mut sess = vm.new_session(&storage);
+        let mut gas_status = GasStatus::new_unmetered();
+        sess.execute_script(
+            script.clone(),
+            vec![],
+            Vec::<Vec<u8>>::new(),
+            &mut gas_status,
+        )
+        .unwrap();
+    };
+
+    run(&scripts[0]);
+    run(&scripts[0]);
+    run(&scripts[1]);
+    run(&scripts[0]);
+    let metrics = vm.cache_metrics();
+    assert_eq!(metrics.script_hits, 1);
+    assert_eq!(metrics.script_misses, 3);
+    assert_eq!(metrics.scripts_evicted, 2);
+}
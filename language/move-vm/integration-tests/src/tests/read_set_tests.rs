@@ -0,0 +1,133 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::compiler::{as_module, compile_units};
+use move_core_types::{
+    account_address::AccountAddress,
+    effects::module_hash,
+    identifier::Identifier,
+    language_storage::{ModuleId, StructTag},
+    value::{serialize_values, MoveValue},
+};
+use move_vm_runtime::move_vm::MoveVM;
+use move_vm_test_utils::InMemoryStorage;
+use move_vm_types::gas_schedule::GasStatus;
+
+const TEST_ADDR: AccountAddress = AccountAddress::new([42; AccountAddress::LENGTH]);
+
+fn struct_tag(name: &str) -> StructTag {
+    StructTag {
+        address: TEST_ADDR,
+        module: Identifier::new("M").unwrap(),
+        name: Identifier::new(name).unwrap(),
+        type_params: vec![],
+    }
+}
+
+#[test]
+fn read_set() {
+    let code = r#"
+        module {{ADDR}}::M {
+            struct Foo has key { a: u64 }
+            struct Bar has key { b: u64 }
+            struct Baz has key { c: u64 }
+
+            public fun publish(s: &signer) {
+                move_to(s, Foo { a: 1 })
+            }
+            public fun read(s: &signer, addr: address): u64 acquires Foo, Baz {
+                move_to(s, Baz { c: 2 });
+                let c = borrow_global<Baz>(addr).c;
+                if (exists<Bar>(addr)) abort 0;
+                borrow_global<Foo>(addr).a + c
+            }
+        }
+    "#;
+    let code = code.replace("{{ADDR}}", &format!("0x{}", TEST_ADDR));
+    let mut units = compile_units(&code).unwrap();
+    let m = as_module(units.pop().unwrap());
+    let mut module_blob = vec![];
+    m.serialize(&mut module_blob).unwrap();
+
+    let mut storage = InMemoryStorage::new();
+    let module_id = ModuleId::new(TEST_ADDR, Identifier::new("M").unwrap());
+    storage.publish_or_overwrite_module(module_id.clone(), module_blob.clone());
+    let hash = module_hash(&module_blob);
+
+    let vm = MoveVM::new(vec![]).unwrap();
+    let account = AccountAddress::random();
+    let mut gas_status = GasStatus::new_unmetered();
+
+    let mut sess = vm.new_session(&storage);
+    sess.record_reads();
+    sess.execute_function_bypass_visibility(
+        &module_id,
+        &Identifier::new("publish").unwrap(),
+        vec![],
+        serialize_values(&vec![MoveValue::Signer(account)]),
+        &mut gas_status,
+    )
+    .unwrap();
+    let (changes, _, reads) = sess.finish_with_read_set().unwrap();
+    // The module is fetched from storage when it is loaded for the first time.
+    assert_eq!(
+        reads.modules().collect::<Vec<_>>(),
+        vec![(&module_id, Some(&hash))]
+    );
+    assert_eq!(
+        reads.resources().collect::<Vec<_>>(),
+        vec![(account, &struct_tag("Foo"), None)]
+    );
+    assert!(reads.is_valid(&storage).unwrap());
+    storage.apply(changes).unwrap();
+    assert!(!reads.is_valid(&storage).unwrap());
+
+    let mut sess = vm.new_session(&storage);
+    sess.record_reads();
+    sess.execute_function_bypass_visibility(
+        &module_id,
+        &Identifier::new("read").unwrap(),
+        vec![],
+        serialize_values(&vec![
+            MoveValue::Signer(account),
+            MoveValue::Address(account),
+        ]),
+        &mut gas_status,
+    )
+    .unwrap();
+    let (_, _, reads, _) = sess.finish_with_extensions_and_read_set().unwrap();
+    // The module is in the code cache now, and recorded with the bytes it was loaded from.
+    // Publishing `Baz` depends on it not existing yet.
+    let foo_blob = MoveValue::U64(1).simple_serialize().unwrap();
+    assert_eq!(
+        reads.modules().collect::<Vec<_>>(),
+        vec![(&module_id, Some(&hash))]
+    );
+    assert_eq!(
+        reads.resources().collect::<Vec<_>>(),
+        vec![
+            (account, &struct_tag("Bar"), None),
+            (account, &struct_tag("Baz"), None),
+            (account, &struct_tag("Foo"), Some(foo_blob.as_slice())),
+        ]
+    );
+    assert!(reads.is_valid(&storage).unwrap());
+
+    // Upgrading the module invalidates the session, which ran the cached version
+    let upgraded_code = code.replace("struct Baz", "struct Qux has key { d: u64 }\n struct Baz");
+    let mut units = compile_units(&upgraded_code).unwrap();
+    let upgraded = as_module(units.pop().unwrap());
+    let mut upgraded_blob = vec![];
+    upgraded.serialize(&mut upgraded_blob).unwrap();
+    storage.publish_or_overwrite_module(module_id, upgraded_blob);
+    assert!(!reads.is_valid(&storage).unwrap());
+}
+
+#[test]
+fn no_read_set_by_default() {
+    let storage = InMemoryStorage::new();
+    let vm = MoveVM::new(vec![]).unwrap();
+    let sess = vm.new_session(&storage);
+    let (_, _, reads) = sess.finish_with_read_set().unwrap();
+    assert!(reads.is_empty());
+}
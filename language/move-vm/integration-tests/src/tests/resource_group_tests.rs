@@ -0,0 +1,190 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::compiler::{as_module, compile_units};
+use move_binary_format::CompiledModule;
+use move_core_types::{
+    account_address::AccountAddress,
+    identifier::Identifier,
+    language_storage::StructTag,
+    resolver::ResourceResolver,
+    resource_group::{deserialize_resource_group, ResourceGroupMetadata},
+    value::{serialize_values, MoveValue},
+    vm_status::StatusCode,
+};
+use move_vm_runtime::move_vm::MoveVM;
+use move_vm_test_utils::InMemoryStorage;
+use move_vm_types::gas_schedule::GasStatus;
+use std::collections::BTreeMap;
+
+const TEST_ADDR: AccountAddress = AccountAddress::new([42; AccountAddress::LENGTH]);
+
+fn struct_tag(name: &str) -> StructTag {
+    module_struct_tag("M", name)
+}
+
+fn module_struct_tag(module: &str, name: &str) -> StructTag {
+    StructTag {
+        address: TEST_ADDR,
+        module: Identifier::new(module).unwrap(),
+        name: Identifier::new(name).unwrap(),
+        type_params: vec![],
+    }
+}
+
+fn compile_with_groups(members: &[&str]) -> CompiledModule {
+    compile_with_group(struct_tag("Group"), members)
+}
+
+fn compile_with_group(group: StructTag, members: &[&str]) -> CompiledModule {
+    let code = r#"
+        module {{ADDR}}::M {
+            struct Foo has key { a: u64 }
+            struct Bar has key { b: u64 }
+            struct Baz has key { c: u64 }
+            struct NotAResource has drop { d: u64 }
+            struct Group { e: bool }
+
+            public fun publish(s: &signer) {
+                move_to(s, Foo { a: 1 });
+                move_to(s, Bar { b: 2 });
+                move_to(s, Baz { c: 3 });
+            }
+            public fun bump_bar(addr: address) acquires Foo, Bar {
+                let a = borrow_global<Foo>(addr).a;
+                let bar = borrow_global_mut<Bar>(addr);
+                bar.b = bar.b + a;
+            }
+        }
+    "#;
+    let code = code.replace("{{ADDR}}", &format!("0x{}", TEST_ADDR));
+    let mut units = compile_units(&code).unwrap();
+    let mut m = as_module(units.pop().unwrap());
+    let metadata = ResourceGroupMetadata {
+        members: members
+            .iter()
+            .map(|name| (Identifier::new(*name).unwrap(), group.clone()))
+            .collect(),
+    };
+    m.metadata.push(metadata.to_module_metadata().unwrap());
+    m
+}
+
+fn serialize(m: &CompiledModule) -> Vec<u8> {
+    let mut blob = vec![];
+    m.serialize(&mut blob).unwrap();
+    blob
+}
+
+#[test]
+fn resource_group_effects() {
+    let m = compile_with_groups(&["Foo", "Bar"]);
+    let module_id = m.self_id();
+    let mut storage = InMemoryStorage::new();
+    storage.publish_or_overwrite_module(module_id.clone(), serialize(&m));
+
+    let vm = MoveVM::new(vec![]).unwrap();
+    let account = AccountAddress::random();
+    let mut gas_status = GasStatus::new_unmetered();
+
+    let mut sess = vm.new_session(&storage);
+    sess.execute_function_bypass_visibility(
+        &module_id,
+        &Identifier::new("publish").unwrap(),
+        vec![],
+        serialize_values(&vec![MoveValue::Signer(account)]),
+        &mut gas_status,
+    )
+    .unwrap();
+    let (changes, _) = sess.finish().unwrap();
+    let account_changes = &changes.accounts()[&account];
+    assert_eq!(
+        account_changes.resources().keys().collect::<Vec<_>>(),
+        vec![&struct_tag("Baz")]
+    );
+    assert_eq!(
+        changes
+            .resource_group_members()
+            .map(|(_, group, member, _)| (group.clone(), member.clone()))
+            .collect::<Vec<_>>(),
+        vec![
+            (struct_tag("Group"), struct_tag("Bar")),
+            (struct_tag("Group"), struct_tag("Foo")),
+        ]
+    );
+    storage.apply(changes).unwrap();
+
+    // The group is stored as a whole, under its own tag.
+    let group = storage
+        .get_resource(&account, &struct_tag("Group"))
+        .unwrap()
+        .unwrap();
+    let group = deserialize_resource_group(&group).unwrap();
+    assert_eq!(group.len(), 2);
+    assert_eq!(
+        group[&struct_tag("Foo")],
+        MoveValue::U64(1).simple_serialize().unwrap()
+    );
+
+    // Only the members which changed are part of the change set.
+    let mut sess = vm.new_session(&storage);
+    sess.execute_function_bypass_visibility(
+        &module_id,
+        &Identifier::new("bump_bar").unwrap(),
+        vec![],
+        serialize_values(&vec![MoveValue::Address(account)]),
+        &mut gas_status,
+    )
+    .unwrap();
+    let (changes, _) = sess.finish().unwrap();
+    let bar = MoveValue::U64(3).simple_serialize().unwrap();
+    let mut expected = BTreeMap::new();
+    expected.insert(struct_tag("Bar"), Some(bar.clone()));
+    assert_eq!(
+        changes.accounts()[&account].resource_groups()[&struct_tag("Group")],
+        expected
+    );
+    storage.apply(changes).unwrap();
+    let group = storage
+        .get_resource(&account, &struct_tag("Group"))
+        .unwrap()
+        .unwrap();
+    let group = deserialize_resource_group(&group).unwrap();
+    assert_eq!(group.len(), 2);
+    assert_eq!(group[&struct_tag("Bar")], bar);
+}
+
+fn assert_invalid_declaration(m: CompiledModule) {
+    let storage = InMemoryStorage::new();
+    let vm = MoveVM::new(vec![]).unwrap();
+    let mut sess = vm.new_session(&storage);
+    let err = sess
+        .publish_module(serialize(&m), TEST_ADDR, &mut GasStatus::new_unmetered())
+        .unwrap_err();
+    assert_eq!(
+        err.major_status(),
+        StatusCode::INVALID_RESOURCE_GROUP_DECLARATION
+    );
+}
+
+#[test]
+fn invalid_resource_group_declaration() {
+    assert_invalid_declaration(compile_with_groups(&["NotAResource"]));
+}
+
+#[test]
+fn invalid_resource_group_tag() {
+    // A resource of the module itself cannot be a group
+    assert_invalid_declaration(compile_with_group(struct_tag("Baz"), &["Foo"]));
+    // Neither can a struct which does not exist
+    assert_invalid_declaration(compile_with_group(struct_tag("Missing"), &["Foo"]));
+}
+
+#[test]
+fn foreign_resource_group_tag() {
+    // Storing a group under the tag of a resource of another module would overwrite it
+    assert_invalid_declaration(compile_with_group(
+        module_struct_tag("N", "Store"),
+        &["Foo"],
+    ));
+}
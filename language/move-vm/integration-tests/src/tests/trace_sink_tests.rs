@@ -0,0 +1,149 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::compiler::{as_module, compile_units};
+use move_core_types::{
+    account_address::AccountAddress,
+    identifier::Identifier,
+    language_storage::ModuleId,
+    value::{serialize_values, MoveValue},
+    vm_status::StatusCode,
+};
+use move_vm_runtime::{
+    move_vm::MoveVM,
+    trace_sink::{TraceEvent, TraceSink},
+};
+use move_vm_test_utils::InMemoryStorage;
+use move_vm_types::gas_schedule::GasStatus;
+
+const TEST_ADDR: AccountAddress = AccountAddress::new([42; AccountAddress::LENGTH]);
+
+/// Records a condensed, owned version of every event.
+#[derive(Default)]
+struct RecordingSink {
+    events: Vec<String>,
+    instructions: usize,
+}
+
+impl TraceSink for RecordingSink {
+    fn on_event(&mut self, event: TraceEvent) {
+        match event {
+            TraceEvent::EnterFunction {
+                function,
+                ty_args,
+                depth,
+            } => self.events.push(format!(
+                "enter {} {} {}",
+                function.name,
+                ty_args.len(),
+                depth
+            )),
+            TraceEvent::ExitFunction {
+                function,
+                ty_args,
+                depth,
+            } => self.events.push(format!(
+                "exit {} {} {}",
+                function.name,
+                ty_args.len(),
+                depth
+            )),
+            TraceEvent::Instruction { .. } => self.instructions += 1,
+            TraceEvent::GlobalAccess {
+                function,
+                kind,
+                address,
+                ..
+            } => self.events.push(format!(
+                "global {} {:?} {} {}",
+                function.name,
+                kind,
+                kind.is_write(),
+                address
+            )),
+            TraceEvent::NativeCall { function, .. } => {
+                self.events.push(format!("native {}", function.name))
+            }
+            TraceEvent::Abort { function, code, .. } => self
+                .events
+                .push(format!("abort {} {}", function.name, code)),
+        }
+    }
+}
+
+#[test]
+fn trace_sink() {
+    let code = r#"
+        module {{ADDR}}::M {
+            struct Foo has key { a: u64 }
+
+            public fun publish(s: &signer, addr: address) acquires Foo {
+                move_to(s, Foo { a: 1 });
+                bump<u8>(addr);
+            }
+            public fun fail(addr: address) acquires Foo {
+                assert!(borrow_global<Foo>(addr).a == 0, 7);
+            }
+            fun bump<T>(addr: address) acquires Foo {
+                let foo = borrow_global_mut<Foo>(addr);
+                foo.a = foo.a + 1;
+            }
+        }
+    "#;
+
+    let code = code.replace("{{ADDR}}", &format!("0x{}", TEST_ADDR));
+    let mut units = compile_units(&code).unwrap();
+    let m = as_module(units.pop().unwrap());
+    let mut blob = vec![];
+    m.serialize(&mut blob).unwrap();
+
+    let mut storage = InMemoryStorage::new();
+    let module_id = ModuleId::new(TEST_ADDR, Identifier::new("M").unwrap());
+    storage.publish_or_overwrite_module(module_id.clone(), blob);
+
+    let vm = MoveVM::new(vec![]).unwrap();
+    let account = AccountAddress::random();
+    let mut sink = RecordingSink::default();
+
+    let mut sess = vm.new_session(&storage);
+    sess.set_trace_sink(&mut sink);
+    let mut gas_status = GasStatus::new_unmetered();
+    sess.execute_function_bypass_visibility(
+        &module_id,
+        &Identifier::new("publish").unwrap(),
+        vec![],
+        serialize_values(&vec![
+            MoveValue::Signer(account),
+            MoveValue::Address(account),
+        ]),
+        &mut gas_status,
+    )
+    .unwrap();
+    let err = sess
+        .execute_function_bypass_visibility(
+            &module_id,
+            &Identifier::new("fail").unwrap(),
+            vec![],
+            serialize_values(&vec![MoveValue::Address(account)]),
+            &mut gas_status,
+        )
+        .unwrap_err();
+    assert_eq!(err.major_status(), StatusCode::ABORTED);
+    drop(sess);
+
+    assert!(sink.instructions > 0);
+    assert_eq!(
+        sink.events,
+        vec![
+            "enter publish 0 0".to_string(),
+            format!("global publish MoveTo true {}", account),
+            "enter bump 1 1".to_string(),
+            format!("global bump MutBorrow true {}", account),
+            "exit bump 1 1".to_string(),
+            "exit publish 0 0".to_string(),
+            "enter fail 0 0".to_string(),
+            format!("global fail ImmBorrow false {}", account),
+            "abort fail 7".to_string(),
+        ]
+    );
+}
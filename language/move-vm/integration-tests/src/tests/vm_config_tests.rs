@@ -0,0 +1,219 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::compiler::{as_module, compile_units};
+use move_binary_format::errors::VMResult;
+use move_bytecode_verifier::VerifierConfig;
+use move_core_types::{
+    account_address::AccountAddress,
+    identifier::Identifier,
+    language_storage::{ModuleId, TypeTag},
+    value::{serialize_values, MoveValue},
+    vm_status::StatusCode,
+};
+use move_vm_runtime::{config::VMConfig, move_vm::MoveVM};
+use move_vm_test_utils::InMemoryStorage;
+use move_vm_types::gas_schedule::GasStatus;
+
+const TEST_ADDR: AccountAddress = AccountAddress::new([42; AccountAddress::LENGTH]);
+
+fn run(
+    vm_config: VMConfig,
+    code: &str,
+    ty_args: Vec<TypeTag>,
+    args: Vec<MoveValue>,
+) -> VMResult<()> {
+    let code = code.replace("{{ADDR}}", &format!("0x{}", TEST_ADDR));
+    let mut storage = InMemoryStorage::new();
+    for unit in compile_units(&code).unwrap() {
+        let m = as_module(unit);
+        let mut blob = vec![];
+        m.serialize(&mut blob).unwrap();
+        storage.publish_or_overwrite_module(m.self_id(), blob);
+    }
+    let module_id = ModuleId::new(TEST_ADDR, Identifier::new("M").unwrap());
+
+    let vm = MoveVM::new_with_config(vec![], vm_config).unwrap();
+    let mut sess = vm.new_session(&storage);
+    let fun_name = Identifier::new("foo").unwrap();
+    let mut gas_status = GasStatus::new_unmetered();
+
+    sess.execute_function_bypass_visibility(
+        &module_id,
+        &fun_name,
+        ty_args,
+        serialize_values(&args),
+        &mut gas_status,
+    )?;
+    Ok(())
+}
+
+fn expect_status(result: VMResult<()>, status: StatusCode) {
+    assert_eq!(result.unwrap_err().major_status(), status);
+}
+
+#[test]
+fn max_call_depth() {
+    let code = r#"
+        module {{ADDR}}::M {
+            fun foo(n: u64) {
+                if (n > 0) foo(n - 1)
+            }
+        }
+    "#;
+    let vm_config = || VMConfig {
+        max_call_depth: 10,
+        ..VMConfig::default()
+    };
+
+    run(vm_config(), code, vec![], vec![MoveValue::U64(5)]).unwrap();
+    expect_status(
+        run(vm_config(), code, vec![], vec![MoveValue::U64(20)]),
+        StatusCode::CALL_STACK_OVERFLOW,
+    );
+}
+
+#[test]
+fn max_vector_length() {
+    let code = r#"
+        module {{ADDR}}::M {
+            fun foo(v: vector<u64>) {
+                let i = 0;
+                while (i < 2) {
+                    std::vector::push_back(&mut v, i);
+                    i = i + 1;
+                }
+            }
+        }
+        module std::vector {
+            #[bytecode_instruction]
+            native public fun push_back<Element>(v: &mut vector<Element>, e: Element);
+        }
+    "#;
+    let vm_config = || VMConfig {
+        max_vector_length: 4,
+        ..VMConfig::default()
+    };
+    let vector_arg = |len| MoveValue::Vector(vec![MoveValue::U64(0); len]);
+
+    run(vm_config(), code, vec![], vec![vector_arg(2)]).unwrap();
+    // Pushing the fifth element fails.
+    expect_status(
+        run(vm_config(), code, vec![], vec![vector_arg(3)]),
+        StatusCode::VM_MAX_VECTOR_LENGTH_REACHED,
+    );
+    // So does passing a vector which is already too long.
+    expect_status(
+        run(vm_config(), code, vec![], vec![vector_arg(5)]),
+        StatusCode::VM_MAX_VECTOR_LENGTH_REACHED,
+    );
+}
+
+#[test]
+fn max_value_depth() {
+    let code = r#"
+        module {{ADDR}}::M {
+            struct A { b: B }
+            struct B { c: C }
+            struct C { x: u64 }
+
+            fun foo(deep: bool) {
+                let c = C { x: 0 };
+                let b = B { c };
+                if (deep) {
+                    let A { b } = A { b };
+                    let B { c } = b;
+                    let C { x: _ } = c;
+                } else {
+                    let B { c } = b;
+                    let C { x: _ } = c;
+                }
+            }
+        }
+    "#;
+    let vm_config = || VMConfig {
+        max_value_depth: 3,
+        check_value_depth_on_pack: true,
+        ..VMConfig::default()
+    };
+
+    run(vm_config(), code, vec![], vec![MoveValue::Bool(false)]).unwrap();
+    expect_status(
+        run(vm_config(), code, vec![], vec![MoveValue::Bool(true)]),
+        StatusCode::VM_MAX_VALUE_DEPTH_REACHED,
+    );
+    // By default, only serializing the value fails.
+    run(
+        VMConfig {
+            max_value_depth: 3,
+            ..VMConfig::default()
+        },
+        code,
+        vec![],
+        vec![MoveValue::Bool(true)],
+    )
+    .unwrap();
+}
+
+#[test]
+fn max_type_instantiation_size() {
+    let code = r#"
+        module {{ADDR}}::M {
+            fun foo<T>() {}
+        }
+    "#;
+    let vm_config = || VMConfig {
+        max_type_instantiation_size: 3,
+        ..VMConfig::default()
+    };
+    let nested_vector = |depth| {
+        let mut ty = TypeTag::U8;
+        for _ in 0..depth {
+            ty = TypeTag::Vector(Box::new(ty));
+        }
+        ty
+    };
+
+    run(vm_config(), code, vec![nested_vector(2)], vec![]).unwrap();
+    expect_status(
+        run(vm_config(), code, vec![nested_vector(3)], vec![]),
+        StatusCode::VM_MAX_TYPE_INSTANTIATION_SIZE_REACHED,
+    );
+}
+
+#[test]
+fn verifier_limits_on_publish() {
+    let code = r#"
+        module {{ADDR}}::M {
+            fun foo(n: u64) {
+                let i = 0;
+                while (i < n) {
+                    let j = 0;
+                    while (j < n) j = j + 1;
+                    i = i + 1;
+                }
+            }
+        }
+    "#;
+    let code = code.replace("{{ADDR}}", &format!("0x{}", TEST_ADDR));
+    let m = as_module(compile_units(&code).unwrap().pop().unwrap());
+    let mut blob = vec![];
+    m.serialize(&mut blob).unwrap();
+
+    let publish = |max_loop_depth| {
+        let vm_config = VMConfig {
+            verifier: VerifierConfig {
+                max_loop_depth: Some(max_loop_depth),
+                ..VerifierConfig::default()
+            },
+            ..VMConfig::default()
+        };
+        let vm = MoveVM::new_with_config(vec![], vm_config).unwrap();
+        let storage = InMemoryStorage::new();
+        let mut sess = vm.new_session(&storage);
+        sess.publish_module(blob.clone(), TEST_ADDR, &mut GasStatus::new_unmetered())
+    };
+
+    publish(2).unwrap();
+    expect_status(publish(1), StatusCode::LOOP_MAX_DEPTH_REACHED);
+}
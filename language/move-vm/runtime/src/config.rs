@@ -0,0 +1,48 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use move_bytecode_verifier::VerifierConfig;
+
+/// Limits the VM enforces on the code it loads and during execution, on top of gas.
+///
+/// Exceeding a limit fails verification or execution with a dedicated `StatusCode`. The limits do
+/// not depend on the environment the VM runs in, so every VM configured the same way fails in the
+/// same place.
+#[derive(Clone, Debug)]
+pub struct VMConfig {
+    /// The limits the bytecode verifier enforces on published modules and on scripts.
+    pub verifier: VerifierConfig,
+    /// The maximum number of nested calls of Move functions. Exceeding it fails with
+    /// `CALL_STACK_OVERFLOW`.
+    pub max_call_depth: usize,
+    /// The maximum number of values on the operand stack. Exceeding it fails with
+    /// `EXECUTION_STACK_OVERFLOW`.
+    pub max_operand_stack_size: usize,
+    /// The maximum nesting depth of values, where a primitive value has depth 1 and a struct or
+    /// vector is one deeper than its deepest field or element. Deserializing or serializing a
+    /// value of a type allowing deeper values fails with `VM_MAX_VALUE_DEPTH_REACHED`.
+    pub max_value_depth: u64,
+    /// Whether creating a value of a type allowing values deeper than `max_value_depth` also
+    /// fails with `VM_MAX_VALUE_DEPTH_REACHED`, rather than only serializing it.
+    pub check_value_depth_on_pack: bool,
+    /// The maximum number of nodes in the type arguments of a function call, e.g. 3 for
+    /// `<u64, vector<u8>>`. Exceeding it fails with `VM_MAX_TYPE_INSTANTIATION_SIZE_REACHED`.
+    pub max_type_instantiation_size: u64,
+    /// The maximum number of elements of a vector, whether it is created by the program or
+    /// deserialized. Exceeding it fails with `VM_MAX_VECTOR_LENGTH_REACHED`.
+    pub max_vector_length: u64,
+}
+
+impl Default for VMConfig {
+    fn default() -> Self {
+        Self {
+            verifier: VerifierConfig::default(),
+            max_call_depth: 1024,
+            max_operand_stack_size: 1024,
+            max_value_depth: 128,
+            check_value_depth_on_pack: false,
+            max_type_instantiation_size: u64::MAX,
+            max_vector_length: u64::MAX,
+        }
+    }
+}
@@ -6,10 +6,11 @@ use crate::loader::Loader;
 use move_binary_format::errors::*;
 use move_core_types::{
     account_address::AccountAddress,
-    effects::{AccountChangeSet, ChangeSet, Event},
+    effects::{module_hash, AccountChangeSet, ChangeSet, Event, ModuleHash, ReadSet},
     identifier::Identifier,
-    language_storage::{ModuleId, TypeTag},
+    language_storage::{ModuleId, StructTag, TypeTag},
     resolver::MoveResolver,
+    resource_group::{deserialize_resource_group, ResourceGroup},
     value::MoveTypeLayout,
     vm_status::StatusCode,
 };
@@ -18,11 +19,13 @@ use move_vm_types::{
     loaded_data::runtime_types::Type,
     values::{GlobalValue, GlobalValueEffect, Value},
 };
-use std::collections::btree_map::BTreeMap;
+use std::{cell::RefCell, collections::btree_map::BTreeMap};
 
 pub struct AccountDataCache {
     data_map: BTreeMap<Type, (MoveTypeLayout, GlobalValue)>,
     module_map: BTreeMap<Identifier, Vec<u8>>,
+    // The resource groups fetched from the remote cache, as they are in the remote cache.
+    group_map: BTreeMap<StructTag, ResourceGroup>,
 }
 
 impl AccountDataCache {
@@ -30,6 +33,7 @@ impl AccountDataCache {
         Self {
             data_map: BTreeMap::new(),
             module_map: BTreeMap::new(),
+            group_map: BTreeMap::new(),
         }
     }
 }
@@ -47,11 +51,14 @@ impl AccountDataCache {
 /// The Move VM takes a `DataStore` in input and this is the default and correct implementation
 /// for a data store related to a transaction. Clients should create an instance of this type
 /// and pass it to the Move VM.
+///
+/// Optionally, the values fetched from the remote cache are recorded into a `ReadSet`.
 pub(crate) struct TransactionDataCache<'r, 'l, S> {
     remote: &'r S,
     loader: &'l Loader,
     account_map: BTreeMap<AccountAddress, AccountDataCache>,
     event_data: Vec<(Vec<u8>, u64, Type, MoveTypeLayout, Value)>,
+    read_set: Option<RefCell<ReadSet>>,
 }
 
 impl<'r, 'l, S: MoveResolver> TransactionDataCache<'r, 'l, S> {
@@ -63,9 +70,37 @@ impl<'r, 'l, S: MoveResolver> TransactionDataCache<'r, 'l, S> {
             loader,
             account_map: BTreeMap::new(),
             event_data: vec![],
+            read_set: None,
+        }
+    }
+
+    /// Start recording the values fetched from the remote cache. Data fetched before is not
+    /// recorded.
+    pub(crate) fn record_reads(&mut self) {
+        if self.read_set.is_none() {
+            self.read_set = Some(RefCell::new(ReadSet::new()));
         }
     }
 
+    fn record_read(&self, record: impl FnOnce(&mut ReadSet)) {
+        if let Some(read_set) = &self.read_set {
+            record(&mut read_set.borrow_mut())
+        }
+    }
+
+    /// Same as `into_effects`, but also returns the values read from the remote cache.
+    pub(crate) fn into_effects_and_read_set(
+        mut self,
+    ) -> PartialVMResult<(ChangeSet, Vec<Event>, ReadSet)> {
+        let read_set = self
+            .read_set
+            .take()
+            .map(RefCell::into_inner)
+            .unwrap_or_default();
+        let (change_set, events) = self.into_effects()?;
+        Ok((change_set, events, read_set))
+    }
+
     /// Make a write set from the updated (dirty, deleted) global resources along with
     /// published modules.
     ///
@@ -79,31 +114,39 @@ impl<'r, 'l, S: MoveResolver> TransactionDataCache<'r, 'l, S> {
             }
 
             let mut resources = BTreeMap::new();
+            let mut resource_groups = BTreeMap::new();
             for (ty, (layout, gv)) in account_data_cache.data_map {
-                match gv.into_effect()? {
-                    GlobalValueEffect::None => (),
-                    GlobalValueEffect::Deleted => {
-                        let struct_tag = match self.loader.type_to_type_tag(&ty)? {
-                            TypeTag::Struct(struct_tag) => struct_tag,
-                            _ => return Err(PartialVMError::new(StatusCode::INTERNAL_TYPE_ERROR)),
-                        };
-                        resources.insert(struct_tag, None);
+                let blob_opt = match gv.into_effect()? {
+                    GlobalValueEffect::None => continue,
+                    GlobalValueEffect::Deleted => None,
+                    GlobalValueEffect::Changed(val) => Some(
+                        val.simple_serialize(&layout)
+                            .ok_or_else(|| PartialVMError::new(StatusCode::INTERNAL_TYPE_ERROR))?,
+                    ),
+                };
+                let struct_tag = match self.loader.type_to_type_tag(&ty)? {
+                    TypeTag::Struct(struct_tag) => struct_tag,
+                    _ => return Err(PartialVMError::new(StatusCode::INTERNAL_TYPE_ERROR)),
+                };
+                match self.loader.resource_group_of(&ty)? {
+                    Some(group) => {
+                        resource_groups
+                            .entry(group)
+                            .or_insert_with(BTreeMap::new)
+                            .insert(struct_tag, blob_opt);
                     }
-                    GlobalValueEffect::Changed(val) => {
-                        let struct_tag = match self.loader.type_to_type_tag(&ty)? {
-                            TypeTag::Struct(struct_tag) => struct_tag,
-                            _ => return Err(PartialVMError::new(StatusCode::INTERNAL_TYPE_ERROR)),
-                        };
-                        let resource_blob = val
-                            .simple_serialize(&layout)
-                            .ok_or_else(|| PartialVMError::new(StatusCode::INTERNAL_TYPE_ERROR))?;
-                        resources.insert(struct_tag, Some(resource_blob));
+                    None => {
+                        resources.insert(struct_tag, blob_opt);
                     }
                 }
             }
             change_set.publish_or_overwrite_account_change_set(
                 addr,
-                AccountChangeSet::from_modules_resources(modules, resources),
+                AccountChangeSet::from_modules_resources_and_groups(
+                    modules,
+                    resources,
+                    resource_groups,
+                ),
             );
         }
 
@@ -152,11 +195,12 @@ impl<'r, 'l, S: MoveResolver> DataStore for TransactionDataCache<'r, 'l, S> {
         &mut self,
         addr: AccountAddress,
         ty: &Type,
-    ) -> PartialVMResult<&mut GlobalValue> {
+    ) -> PartialVMResult<(&mut GlobalValue, Option<Option<usize>>)> {
         let account_cache = Self::get_mut_or_insert_with(&mut self.account_map, &addr, || {
             (addr, AccountDataCache::new())
         });
 
+        let mut loaded = None;
         if !account_cache.data_map.contains_key(ty) {
             let ty_tag = match self.loader.type_to_type_tag(ty)? {
                 TypeTag::Struct(s_tag) => s_tag,
@@ -168,10 +212,65 @@ impl<'r, 'l, S: MoveResolver> DataStore for TransactionDataCache<'r, 'l, S> {
             };
             let ty_layout = self.loader.type_to_type_layout(ty)?;
 
-            let gv = match self.remote.get_resource(&addr, &ty_tag) {
+            let resource = match self.loader.resource_group_of(ty)? {
+                // Resources in a group are fetched with the whole group, the first time one
+                // of its members is accessed.
+                Some(group) => {
+                    if !account_cache.group_map.contains_key(&group) {
+                        let group_blob =
+                            self.remote
+                                .get_resource_group(&addr, &group)
+                                .map_err(|err| {
+                                    let msg = format!("Unexpected storage error: {:?}", err);
+                                    PartialVMError::new(
+                                        StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR,
+                                    )
+                                    .with_message(msg)
+                                })?;
+                        if let Some(read_set) = &self.read_set {
+                            read_set.borrow_mut().record_resource_group(
+                                addr,
+                                group.clone(),
+                                group_blob.clone(),
+                            );
+                        }
+                        let members = match group_blob {
+                            Some(blob) => deserialize_resource_group(&blob).map_err(|err| {
+                                let msg = format!(
+                                    "Failed to deserialize resource group {} at {}: {}",
+                                    group, addr, err
+                                );
+                                PartialVMError::new(StatusCode::FAILED_TO_DESERIALIZE_RESOURCE)
+                                    .with_message(msg)
+                            })?,
+                            None => ResourceGroup::new(),
+                        };
+                        account_cache.group_map.insert(group.clone(), members);
+                    }
+                    Ok(account_cache.group_map[&group].get(&ty_tag).cloned())
+                }
+                None => {
+                    let resource = self.remote.get_resource(&addr, &ty_tag);
+                    if let Some(read_set) = &self.read_set {
+                        if let Ok(blob_opt) = &resource {
+                            read_set.borrow_mut().record_resource(
+                                addr,
+                                ty_tag.clone(),
+                                blob_opt.clone(),
+                            );
+                        }
+                    }
+                    resource
+                }
+            };
+            let gv = match resource {
                 Ok(Some(blob)) => {
+                    loaded = Some(Some(blob.len()));
                     let val = match Value::simple_deserialize(&blob, &ty_layout) {
-                        Some(val) => val,
+                        Some(val) => {
+                            val.check_vector_lengths(self.loader.vm_config().max_vector_length)?;
+                            val
+                        }
                         None => {
                             let msg =
                                 format!("Failed to deserialize resource {} at {}!", ty_tag, addr);
@@ -184,7 +283,10 @@ impl<'r, 'l, S: MoveResolver> DataStore for TransactionDataCache<'r, 'l, S> {
 
                     GlobalValue::cached(val)?
                 }
-                Ok(None) => GlobalValue::none(),
+                Ok(None) => {
+                    loaded = Some(None);
+                    GlobalValue::none()
+                }
                 Err(err) => {
                     let msg = format!("Unexpected storage error: {:?}", err);
                     return Err(
@@ -197,11 +299,14 @@ impl<'r, 'l, S: MoveResolver> DataStore for TransactionDataCache<'r, 'l, S> {
             account_cache.data_map.insert(ty.clone(), (ty_layout, gv));
         }
 
-        Ok(account_cache
-            .data_map
-            .get_mut(ty)
-            .map(|(_ty_layout, gv)| gv)
-            .expect("global value must exist"))
+        Ok((
+            account_cache
+                .data_map
+                .get_mut(ty)
+                .map(|(_ty_layout, gv)| gv)
+                .expect("global value must exist"),
+            loaded,
+        ))
     }
 
     fn load_module(&self, module_id: &ModuleId) -> VMResult<Vec<u8>> {
@@ -210,7 +315,13 @@ impl<'r, 'l, S: MoveResolver> DataStore for TransactionDataCache<'r, 'l, S> {
                 return Ok(blob.clone());
             }
         }
-        match self.remote.get_module(module_id) {
+        let module = self.remote.get_module(module_id);
+        if let Ok(blob_opt) = &module {
+            self.record_read(|read_set| {
+                read_set.record_module(module_id.clone(), blob_opt.as_deref().map(module_hash))
+            });
+        }
+        match module {
             Ok(Some(bytes)) => Ok(bytes),
             Ok(None) => Err(PartialVMError::new(StatusCode::LINKER_ERROR)
                 .with_message(format!("Cannot find {:?} in data cache", module_id))
@@ -245,13 +356,28 @@ impl<'r, 'l, S: MoveResolver> DataStore for TransactionDataCache<'r, 'l, S> {
                 return Ok(true);
             }
         }
-        Ok(self
-            .remote
-            .get_module(module_id)
-            .map_err(|_| {
-                PartialVMError::new(StatusCode::STORAGE_ERROR).finish(Location::Undefined)
-            })?
-            .is_some())
+        let blob_opt = self.remote.get_module(module_id).map_err(|_| {
+            PartialVMError::new(StatusCode::STORAGE_ERROR).finish(Location::Undefined)
+        })?;
+        let exists = blob_opt.is_some();
+        self.record_read(|read_set| {
+            read_set.record_module(module_id.clone(), blob_opt.as_deref().map(module_hash))
+        });
+        Ok(exists)
+    }
+
+    fn is_recording_reads(&self) -> bool {
+        self.read_set.is_some()
+    }
+
+    fn record_cached_module(&self, module_id: &ModuleId, hash: &ModuleHash) {
+        // modules published by this session are not read from the remote cache
+        if let Some(account_cache) = self.account_map.get(module_id.address()) {
+            if account_cache.module_map.contains_key(module_id.name()) {
+                return;
+            }
+        }
+        self.record_read(|read_set| read_set.record_module(module_id.clone(), Some(*hash)));
     }
 
     fn emit_event(
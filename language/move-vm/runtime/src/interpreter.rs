@@ -5,6 +5,7 @@ use crate::{
     loader::{Function, Loader, Resolver},
     native_functions::NativeContext,
     trace,
+    trace_sink::{GlobalAccessKind, TraceEvent, TracedFunction, Tracer},
 };
 use fail::fail_point;
 use move_binary_format::{
@@ -19,7 +20,7 @@ use move_core_types::{
 };
 use move_vm_types::{
     data_store::DataStore,
-    gas_schedule::GasStatus,
+    gas_schedule::GasMeter,
     loaded_data::runtime_types::Type,
     values::{
         self, GlobalValue, IntegerValue, Locals, Reference, Struct, StructRef, VMValueCast, Value,
@@ -75,24 +76,26 @@ impl Interpreter {
         ty_args: Vec<Type>,
         args: Vec<Value>,
         data_store: &mut impl DataStore,
-        gas_status: &mut GasStatus,
+        gas_meter: &mut impl GasMeter,
         extensions: &mut NativeContextExtensions,
+        tracer: &mut Tracer,
         loader: &Loader,
     ) -> VMResult<Vec<Value>> {
         // We count the intrinsic cost of the transaction here, since that needs to also cover the
         // setup of the function.
-        let mut interp = Self::new();
+        let mut interp = Self::new(loader);
         interp.execute(
-            loader, data_store, gas_status, extensions, function, ty_args, args,
+            loader, data_store, gas_meter, extensions, tracer, function, ty_args, args,
         )
     }
 
-    /// Create a new instance of an `Interpreter` in the context of a transaction with a
-    /// given module cache and gas schedule.
-    fn new() -> Self {
+    /// Create a new instance of an `Interpreter` in the context of a transaction, with the
+    /// stack limits configured for the loader's VM.
+    fn new(loader: &Loader) -> Self {
+        let vm_config = loader.vm_config();
         Interpreter {
-            operand_stack: Stack::new(),
-            call_stack: CallStack::new(),
+            operand_stack: Stack::new(vm_config.max_operand_stack_size),
+            call_stack: CallStack::new(vm_config.max_call_depth),
         }
     }
 
@@ -101,8 +104,9 @@ impl Interpreter {
         &mut self,
         loader: &Loader,
         data_store: &mut impl DataStore,
-        gas_status: &mut GasStatus,
+        gas_meter: &mut impl GasMeter,
         extensions: &mut NativeContextExtensions,
+        tracer: &mut Tracer,
         function: Arc<Function>,
         ty_args: Vec<Type>,
         args: Vec<Value>,
@@ -110,7 +114,7 @@ impl Interpreter {
         // No unwinding of the call stack and value stack need to be done here -- the context will
         // take care of that.
         self.execute_main(
-            loader, data_store, gas_status, extensions, function, ty_args, args,
+            loader, data_store, gas_meter, extensions, tracer, function, ty_args, args,
         )
     }
 
@@ -126,8 +130,9 @@ impl Interpreter {
         &mut self,
         loader: &Loader,
         data_store: &mut impl DataStore,
-        gas_status: &mut GasStatus,
+        gas_meter: &mut impl GasMeter,
         extensions: &mut NativeContextExtensions,
+        tracer: &mut Tracer,
         function: Arc<Function>,
         ty_args: Vec<Type>,
         args: Vec<Value>,
@@ -140,37 +145,49 @@ impl Interpreter {
         }
 
         let mut current_frame = Frame::new(function, ty_args, locals);
+        tracer.emit(TraceEvent::EnterFunction {
+            function: TracedFunction::new(&current_frame.function),
+            ty_args: current_frame.ty_args(),
+            depth: 0,
+        });
         loop {
             let resolver = current_frame.resolver(loader);
-            let exit_code = current_frame //self
-                .execute_code(&resolver, self, data_store, gas_status)
-                .map_err(|err| self.maybe_core_dump(err, &current_frame))?;
+            let exit_code =
+                current_frame //self
+                    .execute_code(&resolver, self, data_store, gas_meter, tracer)
+                    .map_err(|err| self.maybe_core_dump(err, &current_frame))?;
             match exit_code {
                 ExitCode::Return => {
+                    tracer.emit(TraceEvent::ExitFunction {
+                        function: TracedFunction::new(&current_frame.function),
+                        ty_args: current_frame.ty_args(),
+                        depth: self.call_stack.frames.len(),
+                    });
                     if let Some(frame) = self.call_stack.pop() {
                         current_frame = frame;
                         current_frame.pc += 1; // advance past the Call instruction in the caller
                     } else {
-                        return Ok(mem::take(&mut self.operand_stack.0));
+                        return Ok(mem::take(&mut self.operand_stack.values));
                     }
                 }
                 ExitCode::Call(fh_idx) => {
-                    gas_status
-                        .charge_instr_with_size(Opcodes::CALL, AbstractMemorySize::new(1))
-                        .map_err(|e| set_err_info!(current_frame, e))?;
                     let func = resolver.function_from_handle(fh_idx);
-                    gas_status
-                        .charge_instr_with_size(
+                    gas_meter
+                        .charge_call(
                             Opcodes::CALL,
-                            AbstractMemorySize::new(func.arg_count() as GasCarrier),
+                            &[],
+                            func.arg_count(),
+                            self.call_stack.frames.len(),
                         )
                         .map_err(|e| set_err_info!(current_frame, e))?;
                     if func.is_native() {
                         self.call_native(
                             &resolver,
                             data_store,
-                            gas_status,
+                            gas_meter,
                             extensions,
+                            tracer,
+                            &current_frame,
                             func,
                             vec![],
                         )?;
@@ -185,29 +202,40 @@ impl Interpreter {
                         let err = set_err_info!(frame, err);
                         self.maybe_core_dump(err, &frame)
                     })?;
+                    tracer.emit(TraceEvent::EnterFunction {
+                        function: TracedFunction::new(&frame.function),
+                        ty_args: frame.ty_args(),
+                        depth: self.call_stack.frames.len(),
+                    });
                     current_frame = frame;
                 }
                 ExitCode::CallGeneric(idx) => {
-                    let arity = resolver.type_params_count(idx);
-                    gas_status
-                        .charge_instr_with_size(
-                            Opcodes::CALL_GENERIC,
-                            AbstractMemorySize::new((arity + 1) as GasCarrier),
-                        )
-                        .map_err(|e| set_err_info!(current_frame, e))?;
                     let ty_args = resolver
                         .instantiate_generic_function(idx, current_frame.ty_args())
                         .map_err(|e| set_err_info!(current_frame, e))?;
+                    resolver
+                        .loader()
+                        .check_type_instantiation_size(&ty_args)
+                        .map_err(|e| set_err_info!(current_frame, e))?;
                     let func = resolver.function_from_instantiation(idx);
-                    gas_status
-                        .charge_instr_with_size(
+                    gas_meter
+                        .charge_call(
                             Opcodes::CALL_GENERIC,
-                            AbstractMemorySize::new(func.arg_count() as GasCarrier),
+                            &ty_args,
+                            func.arg_count(),
+                            self.call_stack.frames.len(),
                         )
                         .map_err(|e| set_err_info!(current_frame, e))?;
                     if func.is_native() {
                         self.call_native(
-                            &resolver, data_store, gas_status, extensions, func, ty_args,
+                            &resolver,
+                            data_store,
+                            gas_meter,
+                            extensions,
+                            tracer,
+                            &current_frame,
+                            func,
+                            ty_args,
                         )?;
                         current_frame.pc += 1; // advance past the Call instruction in the caller
                         continue;
@@ -220,6 +248,11 @@ impl Interpreter {
                         let err = set_err_info!(frame, err);
                         self.maybe_core_dump(err, &frame)
                     })?;
+                    tracer.emit(TraceEvent::EnterFunction {
+                        function: TracedFunction::new(&frame.function),
+                        ty_args: frame.ty_args(),
+                        depth: self.call_stack.frames.len(),
+                    });
                     current_frame = frame;
                 }
             }
@@ -250,8 +283,10 @@ impl Interpreter {
         &mut self,
         resolver: &Resolver,
         data_store: &mut dyn DataStore,
-        gas_status: &mut GasStatus,
+        gas_meter: &mut impl GasMeter,
         extensions: &mut NativeContextExtensions,
+        tracer: &mut Tracer,
+        caller: &Frame,
         function: Arc<Function>,
         ty_args: Vec<Type>,
     ) -> VMResult<()> {
@@ -259,8 +294,10 @@ impl Interpreter {
         self.call_native_impl(
             resolver,
             data_store,
-            gas_status,
+            gas_meter,
             extensions,
+            tracer,
+            caller,
             function.clone(),
             ty_args,
         )
@@ -280,8 +317,10 @@ impl Interpreter {
         &mut self,
         resolver: &Resolver,
         data_store: &mut dyn DataStore,
-        gas_status: &mut GasStatus,
+        gas_meter: &mut impl GasMeter,
         extensions: &mut NativeContextExtensions,
+        tracer: &mut Tracer,
+        caller: &Frame,
         function: Arc<Function>,
         ty_args: Vec<Type>,
     ) -> PartialVMResult<()> {
@@ -290,14 +329,25 @@ impl Interpreter {
         for _ in 0..expected_args {
             arguments.push_front(self.operand_stack.pop()?);
         }
+        tracer.emit(TraceEvent::NativeCall {
+            function: TracedFunction::new(&function),
+            ty_args: &ty_args,
+            depth: self.call_stack.frames.len() + 1,
+        });
+        gas_meter.charge_native_function_before_execution(&ty_args, &arguments)?;
         let mut native_context =
-            NativeContext::new(self, data_store, gas_status, resolver, extensions);
+            NativeContext::new(self, data_store, gas_meter, resolver, extensions);
         let native_function = function.get_native()?;
         let result = native_function(&mut native_context, ty_args, arguments)?;
-        gas_status.deduct_gas(result.cost)?;
-        let return_values = result
-            .result
-            .map_err(|code| PartialVMError::new(StatusCode::ABORTED).with_sub_status(code))?;
+        gas_meter.charge_native_function(result.cost)?;
+        let return_values = result.result.map_err(|code| {
+            tracer.emit(TraceEvent::Abort {
+                function: TracedFunction::new(&caller.function),
+                pc: caller.pc,
+                code,
+            });
+            PartialVMError::new(StatusCode::ABORTED).with_sub_status(code)
+        })?;
         for value in return_values {
             self.operand_stack.push(value)?;
         }
@@ -324,8 +374,11 @@ impl Interpreter {
         self.binop(|lhs, rhs| {
             Ok(match f(lhs, rhs)? {
                 IntegerValue::U8(x) => Value::u8(x),
+                IntegerValue::U16(x) => Value::u16(x),
+                IntegerValue::U32(x) => Value::u32(x),
                 IntegerValue::U64(x) => Value::u64(x),
                 IntegerValue::U128(x) => Value::u128(x),
+                IntegerValue::U256(x) => Value::u256(x),
             })
         })
     }
@@ -342,11 +395,15 @@ impl Interpreter {
     /// Load a resource from the data store.
     fn load_resource<'b>(
         data_store: &'b mut impl DataStore,
+        gas_meter: &mut impl GasMeter,
         addr: AccountAddress,
         ty: &Type,
     ) -> PartialVMResult<&'b mut GlobalValue> {
         match data_store.load_resource(addr, ty) {
-            Ok(gv) => Ok(gv),
+            Ok((gv, loaded)) => {
+                gas_meter.charge_load_resource(loaded)?;
+                Ok(gv)
+            }
             Err(e) => {
                 error!(
                     "[VM] error loading resource at ({}, {:?}): {:?} from data store",
@@ -361,10 +418,11 @@ impl Interpreter {
     fn borrow_global(
         &mut self,
         data_store: &mut impl DataStore,
+        gas_meter: &mut impl GasMeter,
         addr: AccountAddress,
         ty: &Type,
     ) -> PartialVMResult<AbstractMemorySize<GasCarrier>> {
-        let g = Self::load_resource(data_store, addr, ty)?.borrow_global()?;
+        let g = Self::load_resource(data_store, gas_meter, addr, ty)?.borrow_global()?;
         let size = g.size();
         self.operand_stack.push(g)?;
         Ok(size)
@@ -374,10 +432,11 @@ impl Interpreter {
     fn exists(
         &mut self,
         data_store: &mut impl DataStore,
+        gas_meter: &mut impl GasMeter,
         addr: AccountAddress,
         ty: &Type,
     ) -> PartialVMResult<AbstractMemorySize<GasCarrier>> {
-        let gv = Self::load_resource(data_store, addr, ty)?;
+        let gv = Self::load_resource(data_store, gas_meter, addr, ty)?;
         let mem_size = gv.size();
         let exists = gv.exists()?;
         self.operand_stack.push(Value::bool(exists))?;
@@ -388,10 +447,11 @@ impl Interpreter {
     fn move_from(
         &mut self,
         data_store: &mut impl DataStore,
+        gas_meter: &mut impl GasMeter,
         addr: AccountAddress,
         ty: &Type,
     ) -> PartialVMResult<AbstractMemorySize<GasCarrier>> {
-        let resource = Self::load_resource(data_store, addr, ty)?.move_from()?;
+        let resource = Self::load_resource(data_store, gas_meter, addr, ty)?.move_from()?;
         let size = resource.size();
         self.operand_stack.push(resource)?;
         Ok(size)
@@ -401,12 +461,13 @@ impl Interpreter {
     fn move_to(
         &mut self,
         data_store: &mut impl DataStore,
+        gas_meter: &mut impl GasMeter,
         addr: AccountAddress,
         ty: &Type,
         resource: Value,
     ) -> PartialVMResult<AbstractMemorySize<GasCarrier>> {
         let size = resource.size();
-        Self::load_resource(data_store, addr, ty)?.move_to(resource)?;
+        Self::load_resource(data_store, gas_meter, addr, ty)?.move_to(resource)?;
         Ok(size)
     }
 
@@ -508,11 +569,11 @@ impl Interpreter {
         loader: &Loader,
     ) -> PartialVMResult<()> {
         debug_writeln!(buf, "Call Stack:")?;
-        for (i, frame) in self.call_stack.0.iter().enumerate() {
+        for (i, frame) in self.call_stack.frames.iter().enumerate() {
             self.debug_print_frame(buf, loader, i, frame)?;
         }
         debug_writeln!(buf, "Operand Stack:")?;
-        for (idx, val) in self.operand_stack.0.iter().enumerate() {
+        for (idx, val) in self.operand_stack.values.iter().enumerate() {
             // TODO: Currently we do not know the types of the values on the operand stack.
             // Revisit.
             debug_write!(buf, "    [{}] ", idx)?;
@@ -530,7 +591,7 @@ impl Interpreter {
     /// of an execution.
     fn internal_state_str(&self, current_frame: &Frame) -> String {
         let mut internal_state = "Call stack:\n".to_string();
-        for (i, frame) in self.call_stack.0.iter().enumerate() {
+        for (i, frame) in self.call_stack.frames.iter().enumerate() {
             internal_state.push_str(
                 format!(
                     " frame #{}: {} [pc = {}]\n",
@@ -544,7 +605,7 @@ impl Interpreter {
         internal_state.push_str(
             format!(
                 "*frame #{}: {} [pc = {}]:\n",
-                self.call_stack.0.len(),
+                self.call_stack.frames.len(),
                 current_frame.function.pretty_string(),
                 current_frame.pc,
             )
@@ -562,7 +623,7 @@ impl Interpreter {
         }
         internal_state.push_str(format!("Locals:\n{}\n", current_frame.locals).as_str());
         internal_state.push_str("Operand Stack:\n");
-        for value in &self.operand_stack.0 {
+        for value in &self.operand_stack.values {
             internal_state.push_str(format!("{}\n", value).as_str());
         }
         internal_state
@@ -583,7 +644,7 @@ impl Interpreter {
         // is the last one)
         let stack_trace = self
             .call_stack
-            .0
+            .frames
             .iter()
             .rev()
             .take(count)
@@ -599,24 +660,26 @@ impl Interpreter {
     }
 }
 
-// TODO Determine stack size limits based on gas limit
-const OPERAND_STACK_SIZE_LIMIT: usize = 1024;
-const CALL_STACK_SIZE_LIMIT: usize = 1024;
-
 /// The operand stack.
-struct Stack(Vec<Value>);
+struct Stack {
+    values: Vec<Value>,
+    size_limit: usize,
+}
 
 impl Stack {
-    /// Create a new empty operand stack.
-    fn new() -> Self {
-        Stack(vec![])
+    /// Create a new empty operand stack holding at most `size_limit` values.
+    fn new(size_limit: usize) -> Self {
+        Stack {
+            values: vec![],
+            size_limit,
+        }
     }
 
     /// Push a `Value` on the stack if the max stack size has not been reached. Abort execution
     /// otherwise.
     fn push(&mut self, value: Value) -> PartialVMResult<()> {
-        if self.0.len() < OPERAND_STACK_SIZE_LIMIT {
-            self.0.push(value);
+        if self.values.len() < self.size_limit {
+            self.values.push(value);
             Ok(())
         } else {
             Err(PartialVMError::new(StatusCode::EXECUTION_STACK_OVERFLOW))
@@ -625,7 +688,7 @@ impl Stack {
 
     /// Pop a `Value` off the stack or abort execution if the stack is empty.
     fn pop(&mut self) -> PartialVMResult<Value> {
-        self.0
+        self.values
             .pop()
             .ok_or_else(|| PartialVMError::new(StatusCode::EMPTY_VALUE_STACK))
     }
@@ -642,29 +705,35 @@ impl Stack {
     /// Pop n values off the stack.
     fn popn(&mut self, n: u16) -> PartialVMResult<Vec<Value>> {
         let remaining_stack_size = self
-            .0
+            .values
             .len()
             .checked_sub(n as usize)
             .ok_or_else(|| PartialVMError::new(StatusCode::EMPTY_VALUE_STACK))?;
-        let args = self.0.split_off(remaining_stack_size);
+        let args = self.values.split_off(remaining_stack_size);
         Ok(args)
     }
 }
 
 /// A call stack.
 // #[derive(Debug)]
-struct CallStack(Vec<Frame>);
+struct CallStack {
+    frames: Vec<Frame>,
+    size_limit: usize,
+}
 
 impl CallStack {
-    /// Create a new empty call stack.
-    fn new() -> Self {
-        CallStack(vec![])
+    /// Create a new empty call stack holding at most `size_limit` frames.
+    fn new(size_limit: usize) -> Self {
+        CallStack {
+            frames: vec![],
+            size_limit,
+        }
     }
 
     /// Push a `Frame` on the call stack.
     fn push(&mut self, frame: Frame) -> ::std::result::Result<(), Frame> {
-        if self.0.len() < CALL_STACK_SIZE_LIMIT {
-            self.0.push(frame);
+        if self.frames.len() < self.size_limit {
+            self.frames.push(frame);
             Ok(())
         } else {
             Err(frame)
@@ -673,11 +742,11 @@ impl CallStack {
 
     /// Pop a `Frame` off the call stack.
     fn pop(&mut self) -> Option<Frame> {
-        self.0.pop()
+        self.frames.pop()
     }
 
     fn current_location(&self) -> Location {
-        let location_opt = self.0.last().map(|frame| frame.location());
+        let location_opt = self.frames.last().map(|frame| frame.location());
         location_opt.unwrap_or(Location::Undefined)
     }
 }
@@ -713,15 +782,32 @@ impl Frame {
         }
     }
 
+    fn trace_global_access(
+        &self,
+        tracer: &mut Tracer,
+        kind: GlobalAccessKind,
+        address: AccountAddress,
+        ty: &Type,
+    ) {
+        tracer.emit(TraceEvent::GlobalAccess {
+            function: TracedFunction::new(&self.function),
+            pc: self.pc,
+            kind,
+            address,
+            ty,
+        })
+    }
+
     /// Execute a Move function until a return or a call opcode is found.
     fn execute_code(
         &mut self,
         resolver: &Resolver,
         interpreter: &mut Interpreter,
         data_store: &mut impl DataStore,
-        gas_status: &mut GasStatus,
+        gas_meter: &mut impl GasMeter,
+        tracer: &mut Tracer,
     ) -> VMResult<ExitCode> {
-        self.execute_code_impl(resolver, interpreter, data_store, gas_status)
+        self.execute_code_impl(resolver, interpreter, data_store, gas_meter, tracer)
             .map_err(|e| {
                 e.at_code_offset(self.function.index(), self.pc)
                     .finish(self.location())
@@ -733,11 +819,18 @@ impl Frame {
         resolver: &Resolver,
         interpreter: &mut Interpreter,
         data_store: &mut impl DataStore,
-        gas_status: &mut GasStatus,
+        gas_meter: &mut impl GasMeter,
+        tracer: &mut Tracer,
     ) -> PartialVMResult<ExitCode> {
         let code = self.function.code();
         loop {
             for instruction in &code[self.pc as usize..] {
+                tracer.emit(TraceEvent::Instruction {
+                    function: TracedFunction::new(&self.function),
+                    pc: self.pc,
+                    instruction,
+                    locals: &self.locals,
+                });
                 trace!(
                     &self.function,
                     &self.locals,
@@ -757,47 +850,59 @@ impl Frame {
 
                 match instruction {
                     Bytecode::Pop => {
-                        gas_status.charge_instr(Opcodes::POP)?;
+                        gas_meter.charge_instr(Opcodes::POP)?;
                         interpreter.operand_stack.pop()?;
                     }
                     Bytecode::Ret => {
-                        gas_status.charge_instr(Opcodes::RET)?;
+                        gas_meter.charge_instr(Opcodes::RET)?;
                         return Ok(ExitCode::Return);
                     }
                     Bytecode::BrTrue(offset) => {
-                        gas_status.charge_instr(Opcodes::BR_TRUE)?;
+                        gas_meter.charge_instr(Opcodes::BR_TRUE)?;
                         if interpreter.operand_stack.pop_as::<bool>()? {
                             self.pc = *offset;
                             break;
                         }
                     }
                     Bytecode::BrFalse(offset) => {
-                        gas_status.charge_instr(Opcodes::BR_FALSE)?;
+                        gas_meter.charge_instr(Opcodes::BR_FALSE)?;
                         if !interpreter.operand_stack.pop_as::<bool>()? {
                             self.pc = *offset;
                             break;
                         }
                     }
                     Bytecode::Branch(offset) => {
-                        gas_status.charge_instr(Opcodes::BRANCH)?;
+                        gas_meter.charge_instr(Opcodes::BRANCH)?;
                         self.pc = *offset;
                         break;
                     }
                     Bytecode::LdU8(int_const) => {
-                        gas_status.charge_instr(Opcodes::LD_U8)?;
+                        gas_meter.charge_instr(Opcodes::LD_U8)?;
                         interpreter.operand_stack.push(Value::u8(*int_const))?;
                     }
+                    Bytecode::LdU16(int_const) => {
+                        gas_meter.charge_instr(Opcodes::LD_U16)?;
+                        interpreter.operand_stack.push(Value::u16(*int_const))?;
+                    }
+                    Bytecode::LdU32(int_const) => {
+                        gas_meter.charge_instr(Opcodes::LD_U32)?;
+                        interpreter.operand_stack.push(Value::u32(*int_const))?;
+                    }
                     Bytecode::LdU64(int_const) => {
-                        gas_status.charge_instr(Opcodes::LD_U64)?;
+                        gas_meter.charge_instr(Opcodes::LD_U64)?;
                         interpreter.operand_stack.push(Value::u64(*int_const))?;
                     }
                     Bytecode::LdU128(int_const) => {
-                        gas_status.charge_instr(Opcodes::LD_U128)?;
+                        gas_meter.charge_instr(Opcodes::LD_U128)?;
                         interpreter.operand_stack.push(Value::u128(*int_const))?;
                     }
+                    Bytecode::LdU256(int_const) => {
+                        gas_meter.charge_instr(Opcodes::LD_U256)?;
+                        interpreter.operand_stack.push(Value::u256(*int_const))?;
+                    }
                     Bytecode::LdConst(idx) => {
                         let constant = resolver.constant_at(*idx);
-                        gas_status.charge_instr_with_size(
+                        gas_meter.charge_instr_with_size(
                             Opcodes::LD_CONST,
                             AbstractMemorySize::new(constant.data.len() as GasCarrier),
                         )?;
@@ -812,28 +917,27 @@ impl Frame {
                         )?
                     }
                     Bytecode::LdTrue => {
-                        gas_status.charge_instr(Opcodes::LD_TRUE)?;
+                        gas_meter.charge_instr(Opcodes::LD_TRUE)?;
                         interpreter.operand_stack.push(Value::bool(true))?;
                     }
                     Bytecode::LdFalse => {
-                        gas_status.charge_instr(Opcodes::LD_FALSE)?;
+                        gas_meter.charge_instr(Opcodes::LD_FALSE)?;
                         interpreter.operand_stack.push(Value::bool(false))?;
                     }
                     Bytecode::CopyLoc(idx) => {
                         let local = self.locals.copy_loc(*idx as usize)?;
-                        gas_status.charge_instr_with_size(Opcodes::COPY_LOC, local.size())?;
+                        gas_meter.charge_instr_with_size(Opcodes::COPY_LOC, local.size())?;
                         interpreter.operand_stack.push(local)?;
                     }
                     Bytecode::MoveLoc(idx) => {
                         let local = self.locals.move_loc(*idx as usize)?;
-                        gas_status.charge_instr_with_size(Opcodes::MOVE_LOC, local.size())?;
+                        gas_meter.charge_instr_with_size(Opcodes::MOVE_LOC, local.size())?;
 
                         interpreter.operand_stack.push(local)?;
                     }
                     Bytecode::StLoc(idx) => {
                         let value_to_store = interpreter.operand_stack.pop()?;
-                        gas_status
-                            .charge_instr_with_size(Opcodes::ST_LOC, value_to_store.size())?;
+                        gas_meter.charge_instr_with_size(Opcodes::ST_LOC, value_to_store.size())?;
                         self.locals.store_loc(*idx as usize, value_to_store)?;
                     }
                     Bytecode::Call(idx) => {
@@ -847,7 +951,7 @@ impl Frame {
                             Bytecode::MutBorrowLoc(_) => Opcodes::MUT_BORROW_LOC,
                             _ => Opcodes::IMM_BORROW_LOC,
                         };
-                        gas_status.charge_instr(opcode)?;
+                        gas_meter.charge_instr(opcode)?;
                         interpreter
                             .operand_stack
                             .push(self.locals.borrow_loc(*idx as usize)?)?;
@@ -857,7 +961,7 @@ impl Frame {
                             Bytecode::MutBorrowField(_) => Opcodes::MUT_BORROW_FIELD,
                             _ => Opcodes::IMM_BORROW_FIELD,
                         };
-                        gas_status.charge_instr(opcode)?;
+                        gas_meter.charge_instr(opcode)?;
 
                         let reference = interpreter.operand_stack.pop_as::<StructRef>()?;
                         let offset = resolver.field_offset(*fh_idx);
@@ -870,7 +974,7 @@ impl Frame {
                             Bytecode::MutBorrowField(_) => Opcodes::MUT_BORROW_FIELD_GENERIC,
                             _ => Opcodes::IMM_BORROW_FIELD_GENERIC,
                         };
-                        gas_status.charge_instr(opcode)?;
+                        gas_meter.charge_instr(opcode)?;
 
                         let reference = interpreter.operand_stack.pop_as::<StructRef>()?;
                         let offset = resolver.field_instantiation_offset(*fi_idx);
@@ -884,7 +988,10 @@ impl Frame {
                             AbstractMemorySize::new(GasCarrier::from(field_count)),
                             |acc, v| acc.add(v.size()),
                         );
-                        gas_status.charge_instr_with_size(Opcodes::PACK, size)?;
+                        gas_meter.charge_instr_with_size(Opcodes::PACK, size)?;
+                        resolver
+                            .loader()
+                            .check_value_depth(&resolver.get_struct_type(*sd_idx))?;
                         interpreter
                             .operand_stack
                             .push(Value::struct_(Struct::pack(args)))?;
@@ -896,7 +1003,10 @@ impl Frame {
                             AbstractMemorySize::new(GasCarrier::from(field_count)),
                             |acc, v| acc.add(v.size()),
                         );
-                        gas_status.charge_instr_with_size(Opcodes::PACK_GENERIC, size)?;
+                        gas_meter.charge_instr_with_size(Opcodes::PACK_GENERIC, size)?;
+                        resolver.loader().check_value_depth(
+                            &resolver.instantiate_generic_type(*si_idx, self.ty_args())?,
+                        )?;
                         interpreter
                             .operand_stack
                             .push(Value::struct_(Struct::pack(args)))?;
@@ -904,7 +1014,7 @@ impl Frame {
                     Bytecode::Unpack(sd_idx) => {
                         let field_count = resolver.field_count(*sd_idx);
                         let struct_ = interpreter.operand_stack.pop_as::<Struct>()?;
-                        gas_status.charge_instr_with_size(
+                        gas_meter.charge_instr_with_size(
                             Opcodes::UNPACK,
                             AbstractMemorySize::new(GasCarrier::from(field_count)),
                         )?;
@@ -912,14 +1022,14 @@ impl Frame {
                         // questionable.  However, if we don't have it in the loop we could wind up
                         // doing a fair bit of work before charging for it.
                         for value in struct_.unpack()? {
-                            gas_status.charge_instr_with_size(Opcodes::UNPACK, value.size())?;
+                            gas_meter.charge_instr_with_size(Opcodes::UNPACK, value.size())?;
                             interpreter.operand_stack.push(value)?;
                         }
                     }
                     Bytecode::UnpackGeneric(si_idx) => {
                         let field_count = resolver.field_instantiation_count(*si_idx);
                         let struct_ = interpreter.operand_stack.pop_as::<Struct>()?;
-                        gas_status.charge_instr_with_size(
+                        gas_meter.charge_instr_with_size(
                             Opcodes::UNPACK_GENERIC,
                             AbstractMemorySize::new(GasCarrier::from(field_count)),
                         )?;
@@ -927,79 +1037,172 @@ impl Frame {
                         // questionable.  However, if we don't have it in the loop we could wind up
                         // doing a fair bit of work before charging for it.
                         for value in struct_.unpack()? {
-                            gas_status
+                            gas_meter
                                 .charge_instr_with_size(Opcodes::UNPACK_GENERIC, value.size())?;
                             interpreter.operand_stack.push(value)?;
                         }
                     }
+                    Bytecode::PackVariant(sd_idx, variant) => {
+                        let field_count = resolver.variant_field_count(*sd_idx, *variant);
+                        let args = interpreter.operand_stack.popn(field_count)?;
+                        let size = args.iter().fold(
+                            AbstractMemorySize::new(GasCarrier::from(field_count + 1)),
+                            |acc, v| acc.add(v.size()),
+                        );
+                        gas_meter.charge_instr_with_size(Opcodes::PACK_VARIANT, size)?;
+                        resolver
+                            .loader()
+                            .check_value_depth(&resolver.get_struct_type(*sd_idx))?;
+                        interpreter
+                            .operand_stack
+                            .push(Value::struct_(Struct::pack_variant(*variant, args)))?;
+                    }
+                    Bytecode::PackVariantGeneric(si_idx, variant) => {
+                        let field_count =
+                            resolver.variant_instantiation_field_count(*si_idx, *variant);
+                        let args = interpreter.operand_stack.popn(field_count)?;
+                        let size = args.iter().fold(
+                            AbstractMemorySize::new(GasCarrier::from(field_count + 1)),
+                            |acc, v| acc.add(v.size()),
+                        );
+                        gas_meter.charge_instr_with_size(Opcodes::PACK_VARIANT_GENERIC, size)?;
+                        resolver.loader().check_value_depth(
+                            &resolver.instantiate_generic_type(*si_idx, self.ty_args())?,
+                        )?;
+                        interpreter
+                            .operand_stack
+                            .push(Value::struct_(Struct::pack_variant(*variant, args)))?;
+                    }
+                    Bytecode::UnpackVariant(sd_idx, variant) => {
+                        let field_count = resolver.variant_field_count(*sd_idx, *variant);
+                        let struct_ = interpreter.operand_stack.pop_as::<Struct>()?;
+                        gas_meter.charge_instr_with_size(
+                            Opcodes::UNPACK_VARIANT,
+                            AbstractMemorySize::new(GasCarrier::from(field_count)),
+                        )?;
+                        for value in struct_.unpack_variant(*variant)? {
+                            gas_meter
+                                .charge_instr_with_size(Opcodes::UNPACK_VARIANT, value.size())?;
+                            interpreter.operand_stack.push(value)?;
+                        }
+                    }
+                    Bytecode::UnpackVariantGeneric(si_idx, variant) => {
+                        let field_count =
+                            resolver.variant_instantiation_field_count(*si_idx, *variant);
+                        let struct_ = interpreter.operand_stack.pop_as::<Struct>()?;
+                        gas_meter.charge_instr_with_size(
+                            Opcodes::UNPACK_VARIANT_GENERIC,
+                            AbstractMemorySize::new(GasCarrier::from(field_count)),
+                        )?;
+                        for value in struct_.unpack_variant(*variant)? {
+                            gas_meter.charge_instr_with_size(
+                                Opcodes::UNPACK_VARIANT_GENERIC,
+                                value.size(),
+                            )?;
+                            interpreter.operand_stack.push(value)?;
+                        }
+                    }
+                    Bytecode::TestVariant(_, variant)
+                    | Bytecode::TestVariantGeneric(_, variant) => {
+                        let opcode = match instruction {
+                            Bytecode::TestVariant(..) => Opcodes::TEST_VARIANT,
+                            _ => Opcodes::TEST_VARIANT_GENERIC,
+                        };
+                        gas_meter.charge_instr(opcode)?;
+                        let reference = interpreter.operand_stack.pop_as::<StructRef>()?;
+                        interpreter
+                            .operand_stack
+                            .push(reference.test_variant(*variant)?)?;
+                    }
                     Bytecode::ReadRef => {
                         let reference = interpreter.operand_stack.pop_as::<Reference>()?;
                         let value = reference.read_ref()?;
-                        gas_status.charge_instr_with_size(Opcodes::READ_REF, value.size())?;
+                        gas_meter.charge_instr_with_size(Opcodes::READ_REF, value.size())?;
                         interpreter.operand_stack.push(value)?;
                     }
                     Bytecode::WriteRef => {
                         let reference = interpreter.operand_stack.pop_as::<Reference>()?;
                         let value = interpreter.operand_stack.pop()?;
-                        gas_status.charge_instr_with_size(Opcodes::WRITE_REF, value.size())?;
+                        gas_meter.charge_instr_with_size(Opcodes::WRITE_REF, value.size())?;
                         reference.write_ref(value)?;
                     }
                     Bytecode::CastU8 => {
-                        gas_status.charge_instr(Opcodes::CAST_U8)?;
+                        gas_meter.charge_instr(Opcodes::CAST_U8)?;
                         let integer_value = interpreter.operand_stack.pop_as::<IntegerValue>()?;
                         interpreter
                             .operand_stack
                             .push(Value::u8(integer_value.cast_u8()?))?;
                     }
+                    Bytecode::CastU16 => {
+                        gas_meter.charge_instr(Opcodes::CAST_U16)?;
+                        let integer_value = interpreter.operand_stack.pop_as::<IntegerValue>()?;
+                        interpreter
+                            .operand_stack
+                            .push(Value::u16(integer_value.cast_u16()?))?;
+                    }
+                    Bytecode::CastU32 => {
+                        gas_meter.charge_instr(Opcodes::CAST_U32)?;
+                        let integer_value = interpreter.operand_stack.pop_as::<IntegerValue>()?;
+                        interpreter
+                            .operand_stack
+                            .push(Value::u32(integer_value.cast_u32()?))?;
+                    }
                     Bytecode::CastU64 => {
-                        gas_status.charge_instr(Opcodes::CAST_U64)?;
+                        gas_meter.charge_instr(Opcodes::CAST_U64)?;
                         let integer_value = interpreter.operand_stack.pop_as::<IntegerValue>()?;
                         interpreter
                             .operand_stack
                             .push(Value::u64(integer_value.cast_u64()?))?;
                     }
                     Bytecode::CastU128 => {
-                        gas_status.charge_instr(Opcodes::CAST_U128)?;
+                        gas_meter.charge_instr(Opcodes::CAST_U128)?;
                         let integer_value = interpreter.operand_stack.pop_as::<IntegerValue>()?;
                         interpreter
                             .operand_stack
                             .push(Value::u128(integer_value.cast_u128()?))?;
                     }
+                    Bytecode::CastU256 => {
+                        gas_meter.charge_instr(Opcodes::CAST_U256)?;
+                        let integer_value = interpreter.operand_stack.pop_as::<IntegerValue>()?;
+                        interpreter
+                            .operand_stack
+                            .push(Value::u256(integer_value.cast_u256()?))?;
+                    }
                     // Arithmetic Operations
                     Bytecode::Add => {
-                        gas_status.charge_instr(Opcodes::ADD)?;
+                        gas_meter.charge_instr(Opcodes::ADD)?;
                         interpreter.binop_int(IntegerValue::add_checked)?
                     }
                     Bytecode::Sub => {
-                        gas_status.charge_instr(Opcodes::SUB)?;
+                        gas_meter.charge_instr(Opcodes::SUB)?;
                         interpreter.binop_int(IntegerValue::sub_checked)?
                     }
                     Bytecode::Mul => {
-                        gas_status.charge_instr(Opcodes::MUL)?;
+                        gas_meter.charge_instr(Opcodes::MUL)?;
                         interpreter.binop_int(IntegerValue::mul_checked)?
                     }
                     Bytecode::Mod => {
-                        gas_status.charge_instr(Opcodes::MOD)?;
+                        gas_meter.charge_instr(Opcodes::MOD)?;
                         interpreter.binop_int(IntegerValue::rem_checked)?
                     }
                     Bytecode::Div => {
-                        gas_status.charge_instr(Opcodes::DIV)?;
+                        gas_meter.charge_instr(Opcodes::DIV)?;
                         interpreter.binop_int(IntegerValue::div_checked)?
                     }
                     Bytecode::BitOr => {
-                        gas_status.charge_instr(Opcodes::BIT_OR)?;
+                        gas_meter.charge_instr(Opcodes::BIT_OR)?;
                         interpreter.binop_int(IntegerValue::bit_or)?
                     }
                     Bytecode::BitAnd => {
-                        gas_status.charge_instr(Opcodes::BIT_AND)?;
+                        gas_meter.charge_instr(Opcodes::BIT_AND)?;
                         interpreter.binop_int(IntegerValue::bit_and)?
                     }
                     Bytecode::Xor => {
-                        gas_status.charge_instr(Opcodes::XOR)?;
+                        gas_meter.charge_instr(Opcodes::XOR)?;
                         interpreter.binop_int(IntegerValue::bit_xor)?
                     }
                     Bytecode::Shl => {
-                        gas_status.charge_instr(Opcodes::SHL)?;
+                        gas_meter.charge_instr(Opcodes::SHL)?;
                         let rhs = interpreter.operand_stack.pop_as::<u8>()?;
                         let lhs = interpreter.operand_stack.pop_as::<IntegerValue>()?;
                         interpreter
@@ -1007,7 +1210,7 @@ impl Frame {
                             .push(lhs.shl_checked(rhs)?.into_value())?;
                     }
                     Bytecode::Shr => {
-                        gas_status.charge_instr(Opcodes::SHR)?;
+                        gas_meter.charge_instr(Opcodes::SHR)?;
                         let rhs = interpreter.operand_stack.pop_as::<u8>()?;
                         let lhs = interpreter.operand_stack.pop_as::<IntegerValue>()?;
                         interpreter
@@ -1015,32 +1218,37 @@ impl Frame {
                             .push(lhs.shr_checked(rhs)?.into_value())?;
                     }
                     Bytecode::Or => {
-                        gas_status.charge_instr(Opcodes::OR)?;
+                        gas_meter.charge_instr(Opcodes::OR)?;
                         interpreter.binop_bool(|l, r| Ok(l || r))?
                     }
                     Bytecode::And => {
-                        gas_status.charge_instr(Opcodes::AND)?;
+                        gas_meter.charge_instr(Opcodes::AND)?;
                         interpreter.binop_bool(|l, r| Ok(l && r))?
                     }
                     Bytecode::Lt => {
-                        gas_status.charge_instr(Opcodes::LT)?;
+                        gas_meter.charge_instr(Opcodes::LT)?;
                         interpreter.binop_bool(IntegerValue::lt)?
                     }
                     Bytecode::Gt => {
-                        gas_status.charge_instr(Opcodes::GT)?;
+                        gas_meter.charge_instr(Opcodes::GT)?;
                         interpreter.binop_bool(IntegerValue::gt)?
                     }
                     Bytecode::Le => {
-                        gas_status.charge_instr(Opcodes::LE)?;
+                        gas_meter.charge_instr(Opcodes::LE)?;
                         interpreter.binop_bool(IntegerValue::le)?
                     }
                     Bytecode::Ge => {
-                        gas_status.charge_instr(Opcodes::GE)?;
+                        gas_meter.charge_instr(Opcodes::GE)?;
                         interpreter.binop_bool(IntegerValue::ge)?
                     }
                     Bytecode::Abort => {
-                        gas_status.charge_instr(Opcodes::ABORT)?;
+                        gas_meter.charge_instr(Opcodes::ABORT)?;
                         let error_code = interpreter.operand_stack.pop_as::<u64>()?;
+                        tracer.emit(TraceEvent::Abort {
+                            function: TracedFunction::new(&self.function),
+                            pc: self.pc,
+                            code: error_code,
+                        });
                         let error = PartialVMError::new(StatusCode::ABORTED)
                             .with_sub_status(error_code)
                             .with_message(format!(
@@ -1057,7 +1265,7 @@ impl Frame {
                     Bytecode::Eq => {
                         let lhs = interpreter.operand_stack.pop()?;
                         let rhs = interpreter.operand_stack.pop()?;
-                        gas_status
+                        gas_meter
                             .charge_instr_with_size(Opcodes::EQ, lhs.size().add(rhs.size()))?;
                         interpreter
                             .operand_stack
@@ -1066,7 +1274,7 @@ impl Frame {
                     Bytecode::Neq => {
                         let lhs = interpreter.operand_stack.pop()?;
                         let rhs = interpreter.operand_stack.pop()?;
-                        gas_status
+                        gas_meter
                             .charge_instr_with_size(Opcodes::NEQ, lhs.size().add(rhs.size()))?;
                         interpreter
                             .operand_stack
@@ -1075,44 +1283,68 @@ impl Frame {
                     Bytecode::MutBorrowGlobal(sd_idx) | Bytecode::ImmBorrowGlobal(sd_idx) => {
                         let addr = interpreter.operand_stack.pop_as::<AccountAddress>()?;
                         let ty = resolver.get_struct_type(*sd_idx);
-                        let size = interpreter.borrow_global(data_store, addr, &ty)?;
-                        gas_status.charge_instr_with_size(Opcodes::MUT_BORROW_GLOBAL, size)?;
+                        self.trace_global_access(
+                            tracer,
+                            if matches!(instruction, Bytecode::MutBorrowGlobal(_)) {
+                                GlobalAccessKind::MutBorrow
+                            } else {
+                                GlobalAccessKind::ImmBorrow
+                            },
+                            addr,
+                            &ty,
+                        );
+                        let size = interpreter.borrow_global(data_store, gas_meter, addr, &ty)?;
+                        gas_meter.charge_instr_with_size(Opcodes::MUT_BORROW_GLOBAL, size)?;
                     }
                     Bytecode::MutBorrowGlobalGeneric(si_idx)
                     | Bytecode::ImmBorrowGlobalGeneric(si_idx) => {
                         let addr = interpreter.operand_stack.pop_as::<AccountAddress>()?;
                         let ty = resolver.instantiate_generic_type(*si_idx, self.ty_args())?;
-                        let size = interpreter.borrow_global(data_store, addr, &ty)?;
-                        gas_status
+                        self.trace_global_access(
+                            tracer,
+                            if matches!(instruction, Bytecode::MutBorrowGlobalGeneric(_)) {
+                                GlobalAccessKind::MutBorrow
+                            } else {
+                                GlobalAccessKind::ImmBorrow
+                            },
+                            addr,
+                            &ty,
+                        );
+                        let size = interpreter.borrow_global(data_store, gas_meter, addr, &ty)?;
+                        gas_meter
                             .charge_instr_with_size(Opcodes::MUT_BORROW_GLOBAL_GENERIC, size)?;
                     }
                     Bytecode::Exists(sd_idx) => {
                         let addr = interpreter.operand_stack.pop_as::<AccountAddress>()?;
                         let ty = resolver.get_struct_type(*sd_idx);
-                        let size = interpreter.exists(data_store, addr, &ty)?;
-                        gas_status.charge_instr_with_size(Opcodes::EXISTS, size)?;
+                        self.trace_global_access(tracer, GlobalAccessKind::Exists, addr, &ty);
+                        let size = interpreter.exists(data_store, gas_meter, addr, &ty)?;
+                        gas_meter.charge_instr_with_size(Opcodes::EXISTS, size)?;
                     }
                     Bytecode::ExistsGeneric(si_idx) => {
                         let addr = interpreter.operand_stack.pop_as::<AccountAddress>()?;
                         let ty = resolver.instantiate_generic_type(*si_idx, self.ty_args())?;
-                        let size = interpreter.exists(data_store, addr, &ty)?;
-                        gas_status.charge_instr_with_size(Opcodes::EXISTS_GENERIC, size)?;
+                        self.trace_global_access(tracer, GlobalAccessKind::Exists, addr, &ty);
+                        let size = interpreter.exists(data_store, gas_meter, addr, &ty)?;
+                        gas_meter.charge_instr_with_size(Opcodes::EXISTS_GENERIC, size)?;
                     }
                     Bytecode::MoveFrom(sd_idx) => {
                         let addr = interpreter.operand_stack.pop_as::<AccountAddress>()?;
                         let ty = resolver.get_struct_type(*sd_idx);
-                        let size = interpreter.move_from(data_store, addr, &ty)?;
+                        self.trace_global_access(tracer, GlobalAccessKind::MoveFrom, addr, &ty);
+                        let size = interpreter.move_from(data_store, gas_meter, addr, &ty)?;
                         // TODO: Have this calculate before pulling in the data based upon
                         // the size of the data that we are about to read in.
-                        gas_status.charge_instr_with_size(Opcodes::MOVE_FROM, size)?;
+                        gas_meter.charge_instr_with_size(Opcodes::MOVE_FROM, size)?;
                     }
                     Bytecode::MoveFromGeneric(si_idx) => {
                         let addr = interpreter.operand_stack.pop_as::<AccountAddress>()?;
                         let ty = resolver.instantiate_generic_type(*si_idx, self.ty_args())?;
-                        let size = interpreter.move_from(data_store, addr, &ty)?;
+                        self.trace_global_access(tracer, GlobalAccessKind::MoveFrom, addr, &ty);
+                        let size = interpreter.move_from(data_store, gas_meter, addr, &ty)?;
                         // TODO: Have this calculate before pulling in the data based upon
                         // the size of the data that we are about to read in.
-                        gas_status.charge_instr_with_size(Opcodes::MOVE_FROM_GENERIC, size)?;
+                        gas_meter.charge_instr_with_size(Opcodes::MOVE_FROM_GENERIC, size)?;
                     }
                     Bytecode::MoveTo(sd_idx) => {
                         let resource = interpreter.operand_stack.pop()?;
@@ -1123,9 +1355,11 @@ impl Frame {
                             .read_ref()?
                             .value_as::<AccountAddress>()?;
                         let ty = resolver.get_struct_type(*sd_idx);
+                        self.trace_global_access(tracer, GlobalAccessKind::MoveTo, addr, &ty);
                         // REVIEW: Can we simplify Interpreter::move_to?
-                        let size = interpreter.move_to(data_store, addr, &ty, resource)?;
-                        gas_status.charge_instr_with_size(Opcodes::MOVE_TO, size)?;
+                        let size =
+                            interpreter.move_to(data_store, gas_meter, addr, &ty, resource)?;
+                        gas_meter.charge_instr_with_size(Opcodes::MOVE_TO, size)?;
                     }
                     Bytecode::MoveToGeneric(si_idx) => {
                         let resource = interpreter.operand_stack.pop()?;
@@ -1136,35 +1370,43 @@ impl Frame {
                             .read_ref()?
                             .value_as::<AccountAddress>()?;
                         let ty = resolver.instantiate_generic_type(*si_idx, self.ty_args())?;
-                        let size = interpreter.move_to(data_store, addr, &ty, resource)?;
-                        gas_status.charge_instr_with_size(Opcodes::MOVE_TO_GENERIC, size)?;
+                        self.trace_global_access(tracer, GlobalAccessKind::MoveTo, addr, &ty);
+                        let size =
+                            interpreter.move_to(data_store, gas_meter, addr, &ty, resource)?;
+                        gas_meter.charge_instr_with_size(Opcodes::MOVE_TO_GENERIC, size)?;
                     }
                     Bytecode::FreezeRef => {
-                        gas_status.charge_instr(Opcodes::FREEZE_REF)?;
+                        gas_meter.charge_instr(Opcodes::FREEZE_REF)?;
                         // FreezeRef should just be a null op as we don't distinguish between mut
                         // and immut ref at runtime.
                     }
                     Bytecode::Not => {
-                        gas_status.charge_instr(Opcodes::NOT)?;
+                        gas_meter.charge_instr(Opcodes::NOT)?;
                         let value = !interpreter.operand_stack.pop_as::<bool>()?;
                         interpreter.operand_stack.push(Value::bool(value))?;
                     }
                     Bytecode::Nop => {
-                        gas_status.charge_instr(Opcodes::NOP)?;
+                        gas_meter.charge_instr(Opcodes::NOP)?;
                     }
                     Bytecode::VecPack(si, num) => {
                         let elements = interpreter.operand_stack.popn(*num as u16)?;
                         let size = AbstractMemorySize::new(*num);
-                        gas_status.charge_instr_with_size(Opcodes::VEC_PACK, size)?;
-                        let value = Vector::pack(
-                            &resolver.instantiate_single_type(*si, self.ty_args())?,
-                            elements,
-                        )?;
+                        gas_meter.charge_instr_with_size(Opcodes::VEC_PACK, size)?;
+                        if *num > resolver.loader().vm_config().max_vector_length {
+                            return Err(PartialVMError::new(
+                                StatusCode::VM_MAX_VECTOR_LENGTH_REACHED,
+                            ));
+                        }
+                        let elem_ty = resolver.instantiate_single_type(*si, self.ty_args())?;
+                        resolver
+                            .loader()
+                            .check_value_depth(&Type::Vector(Box::new(elem_ty.clone())))?;
+                        let value = Vector::pack(&elem_ty, elements)?;
                         interpreter.operand_stack.push(value)?;
                     }
                     Bytecode::VecLen(si) => {
                         let vec_ref = interpreter.operand_stack.pop_as::<VectorRef>()?;
-                        gas_status.charge_instr(Opcodes::VEC_LEN)?;
+                        gas_meter.charge_instr(Opcodes::VEC_LEN)?;
                         let vec_ty_arg = &resolver.instantiate_single_type(*si, self.ty_args())?;
                         let value = vec_ref.len(vec_ty_arg)?;
                         interpreter.operand_stack.push(value)?;
@@ -1172,7 +1414,7 @@ impl Frame {
                     Bytecode::VecImmBorrow(si) => {
                         let idx = interpreter.operand_stack.pop_as::<u64>()? as usize;
                         let vec_ref = interpreter.operand_stack.pop_as::<VectorRef>()?;
-                        gas_status.charge_instr(Opcodes::VEC_IMM_BORROW)?;
+                        gas_meter.charge_instr(Opcodes::VEC_IMM_BORROW)?;
                         let vec_ty_arg = &resolver.instantiate_single_type(*si, self.ty_args())?;
                         let value = vec_ref.borrow_elem(idx, vec_ty_arg)?;
                         interpreter.operand_stack.push(value)?;
@@ -1180,7 +1422,7 @@ impl Frame {
                     Bytecode::VecMutBorrow(si) => {
                         let idx = interpreter.operand_stack.pop_as::<u64>()? as usize;
                         let vec_ref = interpreter.operand_stack.pop_as::<VectorRef>()?;
-                        gas_status.charge_instr(Opcodes::VEC_MUT_BORROW)?;
+                        gas_meter.charge_instr(Opcodes::VEC_MUT_BORROW)?;
                         let vec_ty_arg = &resolver.instantiate_single_type(*si, self.ty_args())?;
                         let value = vec_ref.borrow_elem(idx, vec_ty_arg)?;
                         interpreter.operand_stack.push(value)?;
@@ -1188,13 +1430,20 @@ impl Frame {
                     Bytecode::VecPushBack(si) => {
                         let elem = interpreter.operand_stack.pop()?;
                         let vec_ref = interpreter.operand_stack.pop_as::<VectorRef>()?;
-                        gas_status.charge_instr_with_size(Opcodes::VEC_PUSH_BACK, elem.size())?;
+                        gas_meter.charge_instr_with_size(Opcodes::VEC_PUSH_BACK, elem.size())?;
                         let vec_ty_arg = &resolver.instantiate_single_type(*si, self.ty_args())?;
+                        if vec_ref.len(vec_ty_arg)?.value_as::<u64>()?
+                            >= resolver.loader().vm_config().max_vector_length
+                        {
+                            return Err(PartialVMError::new(
+                                StatusCode::VM_MAX_VECTOR_LENGTH_REACHED,
+                            ));
+                        }
                         vec_ref.push_back(elem, vec_ty_arg)?;
                     }
                     Bytecode::VecPopBack(si) => {
                         let vec_ref = interpreter.operand_stack.pop_as::<VectorRef>()?;
-                        gas_status.charge_instr(Opcodes::VEC_POP_BACK)?;
+                        gas_meter.charge_instr(Opcodes::VEC_POP_BACK)?;
                         let vec_ty_arg = &resolver.instantiate_single_type(*si, self.ty_args())?;
                         let value = vec_ref.pop(vec_ty_arg)?;
                         interpreter.operand_stack.push(value)?;
@@ -1202,7 +1451,7 @@ impl Frame {
                     Bytecode::VecUnpack(si, num) => {
                         let vec_val = interpreter.operand_stack.pop_as::<Vector>()?;
                         let size = AbstractMemorySize::new(*num);
-                        gas_status.charge_instr_with_size(Opcodes::VEC_UNPACK, size)?;
+                        gas_meter.charge_instr_with_size(Opcodes::VEC_UNPACK, size)?;
                         let vec_ty_arg = &resolver.instantiate_single_type(*si, self.ty_args())?;
                         let elements = vec_val.unpack(vec_ty_arg, *num)?;
                         for value in elements {
@@ -1213,7 +1462,7 @@ impl Frame {
                         let idx2 = interpreter.operand_stack.pop_as::<u64>()? as usize;
                         let idx1 = interpreter.operand_stack.pop_as::<u64>()? as usize;
                         let vec_ref = interpreter.operand_stack.pop_as::<VectorRef>()?;
-                        gas_status.charge_instr(Opcodes::VEC_SWAP)?;
+                        gas_meter.charge_instr(Opcodes::VEC_SWAP)?;
                         let vec_ty_arg = &resolver.instantiate_single_type(*si, self.ty_args())?;
                         vec_ref.swap(idx1, idx2, vec_ty_arg)?;
                     }
@@ -9,6 +9,7 @@
 //! other blockchains can use it as well. The VM isn't there yet, but hopefully will be there
 //! soon.
 
+pub mod config;
 pub mod data_cache;
 mod interpreter;
 mod loader;
@@ -18,6 +19,7 @@ pub mod native_extensions;
 pub mod native_functions;
 mod runtime;
 pub mod session;
+pub mod trace_sink;
 #[macro_use]
 mod tracing;
 
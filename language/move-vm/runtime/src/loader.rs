@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    config::VMConfig,
     logging::expect_no_verification_errors,
     native_functions::{NativeFunction, NativeFunctions},
     session::LoadedFunctionInstantiation,
@@ -12,17 +13,19 @@ use move_binary_format::{
     errors::{verification_error, Location, PartialVMError, PartialVMResult, VMResult},
     file_format::{
         AbilitySet, Bytecode, CompiledModule, CompiledScript, Constant, ConstantPoolIndex,
-        FieldHandleIndex, FieldInstantiationIndex, FunctionDefinition, FunctionDefinitionIndex,
-        FunctionHandleIndex, FunctionInstantiationIndex, Signature, SignatureIndex, SignatureToken,
-        StructDefInstantiationIndex, StructDefinition, StructDefinitionIndex,
-        StructFieldInformation, TableIndex,
+        FieldDefinition, FieldHandleIndex, FieldInstantiationIndex, FunctionDefinition,
+        FunctionDefinitionIndex, FunctionHandleIndex, FunctionInstantiationIndex, Signature,
+        SignatureIndex, SignatureToken, StructDefInstantiationIndex, StructDefinition,
+        StructDefinitionIndex, StructFieldInformation, TableIndex, VariantIndex,
     },
     IndexKind,
 };
 use move_bytecode_verifier::{self, cyclic_dependencies, dependencies};
 use move_core_types::{
+    effects::{module_hash, ModuleHash},
     identifier::{IdentStr, Identifier},
     language_storage::{ModuleId, StructTag, TypeTag},
+    resource_group::ResourceGroupMetadata,
     value::{MoveStructLayout, MoveTypeLayout},
     vm_status::StatusCode,
 };
@@ -36,7 +39,10 @@ use std::{
     collections::{BTreeMap, BTreeSet, HashMap},
     fmt::Debug,
     hash::Hash,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
 };
 use tracing::error;
 
@@ -73,30 +79,120 @@ where
     fn get(&self, key: &K) -> Option<&Arc<V>> {
         self.id_map.get(key).and_then(|idx| self.binaries.get(*idx))
     }
+
+    // Remove a binary, moving the last binary into its slot.
+    fn remove(&mut self, key: &K) -> Option<Arc<V>> {
+        let idx = self.id_map.remove(key)?;
+        let last_idx = self.binaries.len() - 1;
+        if idx != last_idx {
+            if let Some(moved_idx) = self.id_map.values_mut().find(|i| **i == last_idx) {
+                *moved_idx = idx;
+            }
+        }
+        Some(self.binaries.swap_remove(idx))
+    }
+
+    fn iter(&self) -> impl Iterator<Item = (&K, &Arc<V>)> {
+        self.id_map
+            .iter()
+            .map(move |(key, idx)| (key, &self.binaries[*idx]))
+    }
+
+    fn len(&self) -> usize {
+        self.id_map.len()
+    }
+}
+
+// Counters for the use of the code caches, shared by all sessions of a VM.
+#[derive(Default)]
+struct CacheCounters {
+    module_hits: AtomicU64,
+    module_misses: AtomicU64,
+    script_hits: AtomicU64,
+    script_misses: AtomicU64,
+    scripts_evicted: AtomicU64,
+    modules_flushed: AtomicU64,
+}
+
+impl CacheCounters {
+    fn record(counter: &AtomicU64, count: u64) {
+        counter.fetch_add(count, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> CacheMetrics {
+        CacheMetrics {
+            module_hits: self.module_hits.load(Ordering::Relaxed),
+            module_misses: self.module_misses.load(Ordering::Relaxed),
+            script_hits: self.script_hits.load(Ordering::Relaxed),
+            script_misses: self.script_misses.load(Ordering::Relaxed),
+            scripts_evicted: self.scripts_evicted.load(Ordering::Relaxed),
+            modules_flushed: self.modules_flushed.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// A snapshot of the code cache metrics of a `MoveVM`, counted since the VM was created.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CacheMetrics {
+    /// Module loads served from the module cache.
+    pub module_hits: u64,
+    /// Module loads that had to fetch the module from storage.
+    pub module_misses: u64,
+    /// Script loads served from the script cache.
+    pub script_hits: u64,
+    /// Script loads that had to deserialize and verify the script.
+    pub script_misses: u64,
+    /// Scripts evicted from the script cache to stay within its capacity.
+    pub scripts_evicted: u64,
+    /// Modules removed from the module cache by `MoveVM::flush_module` and
+    /// `MoveVM::mark_modules_stale`, including their dependents.
+    pub modules_flushed: u64,
 }
 
 // A script cache is a map from the hash value of a script and the `Script` itself.
 // Script are added in the cache once verified and so getting a script out the cache
-// does not require further verification (except for parameters and type parameters)
+// does not require further verification (except for parameters and type parameters).
+// The cache may be bounded, in which case the least recently used scripts are evicted.
 struct ScriptCache {
     scripts: BinaryCache<ScriptHash, Script>,
+    last_used: HashMap<ScriptHash, u64>,
+    clock: u64,
+    capacity: Option<usize>,
 }
 
 impl ScriptCache {
     fn new() -> Self {
         Self {
             scripts: BinaryCache::new(),
+            last_used: HashMap::new(),
+            clock: 0,
+            capacity: None,
         }
     }
 
-    fn get(&self, hash: &ScriptHash) -> Option<(Arc<Function>, Vec<Type>, Vec<Type>)> {
-        self.scripts.get(hash).map(|script| {
+    fn touch(&mut self, hash: &ScriptHash) {
+        self.clock += 1;
+        self.last_used.insert(*hash, self.clock);
+    }
+
+    fn get(&mut self, hash: &ScriptHash) -> Option<(Arc<Function>, Vec<Type>, Vec<Type>)> {
+        let cached = self.scripts.get(hash).map(|script| {
             (
                 script.entry_point(),
                 script.parameter_tys.clone(),
                 script.return_tys.clone(),
             )
-        })
+        })?;
+        self.touch(hash);
+        Some(cached)
+    }
+
+    // The modules the script with the given hash depends on
+    fn dependencies(&self, hash: &ScriptHash) -> Vec<ModuleId> {
+        self.scripts
+            .get(hash)
+            .map(|script| script.script.immediate_dependencies())
+            .unwrap_or_default()
     }
 
     fn insert(
@@ -108,14 +204,67 @@ impl ScriptCache {
             Some(cached) => cached,
             None => {
                 let script = self.scripts.insert(hash, script);
-                (
+                let cached = (
                     script.entry_point(),
                     script.parameter_tys.clone(),
                     script.return_tys.clone(),
-                )
+                );
+                self.touch(&hash);
+                cached
             }
         }
     }
+
+    fn remove(&mut self, hash: &ScriptHash) {
+        self.scripts.remove(hash);
+        self.last_used.remove(hash);
+    }
+
+    // Evict the least recently used scripts until the cache is within its capacity and
+    // return how many were evicted.
+    // Scripts which are being executed are never evicted: the interpreter resolves their
+    // entities through the cache. A script is executing if anything but the script itself
+    // holds its entry point.
+    fn evict(&mut self) -> u64 {
+        let capacity = match self.capacity {
+            Some(capacity) => capacity,
+            None => return 0,
+        };
+        let mut evicted = 0;
+        while self.scripts.len() > capacity {
+            let lru = self
+                .scripts
+                .iter()
+                .filter(|(_, script)| Arc::strong_count(&script.main) == 1)
+                .min_by_key(|(hash, _)| self.last_used.get(*hash))
+                .map(|(hash, _)| *hash);
+            match lru {
+                Some(hash) => self.remove(&hash),
+                None => break,
+            }
+            evicted += 1;
+        }
+        evicted
+    }
+
+    // Remove all scripts depending on any of the given modules.
+    fn remove_dependents(&mut self, modules: &BTreeSet<ModuleId>) {
+        let dependents: Vec<_> = self
+            .scripts
+            .iter()
+            .filter(|(_, script)| {
+                script
+                    .script
+                    .immediate_dependencies()
+                    .iter()
+                    .any(|dep| modules.contains(dep))
+            })
+            .map(|(hash, _)| *hash)
+            .collect();
+        for hash in dependents {
+            self.remove(&hash);
+        }
+    }
 }
 
 // A ModuleCache is the core structure in the Loader.
@@ -167,6 +316,7 @@ impl ModuleCache {
         natives: &NativeFunctions,
         id: ModuleId,
         module: CompiledModule,
+        hash: ModuleHash,
     ) -> VMResult<Arc<Module>> {
         if let Some(cached) = self.module_at(&id) {
             return Ok(cached);
@@ -175,7 +325,7 @@ impl ModuleCache {
         // we need this operation to be transactional, if an error occurs we must
         // leave a clean state
         self.add_module(natives, &module)?;
-        match Module::new(module, self) {
+        match Module::new(module, hash, self) {
             Ok(module) => Ok(Arc::clone(self.modules.insert(id, module))),
             Err((err, module)) => {
                 // remove all structs and functions that have been pushed
@@ -221,6 +371,7 @@ impl ModuleCache {
         let module = module.self_id();
         StructType {
             fields: vec![],
+            variants: vec![],
             abilities,
             type_parameters,
             name,
@@ -236,24 +387,29 @@ impl ModuleCache {
     ) -> PartialVMResult<()> {
         let mut field_types = vec![];
         for struct_def in module.struct_defs() {
-            let fields = match &struct_def.field_information {
+            let (fields, variants) = match &struct_def.field_information {
                 StructFieldInformation::Native => unreachable!("native structs have been removed"),
-                StructFieldInformation::Declared(fields) => fields,
+                StructFieldInformation::Declared(fields) => {
+                    (self.make_field_types(module, fields)?, vec![])
+                }
+                StructFieldInformation::DeclaredVariants(variants) => {
+                    let mut variant_tys = vec![];
+                    for variant in variants {
+                        variant_tys.push(self.make_field_types(module, &variant.fields)?);
+                    }
+                    (vec![], variant_tys)
+                }
             };
 
-            let mut field_tys = vec![];
-            for field in fields {
-                let ty = self.make_type_while_loading(module, &field.signature.0)?;
-                debug_assert!(field_tys.len() < usize::max_value());
-                field_tys.push(ty);
-            }
-
-            field_types.push(field_tys);
+            field_types.push((fields, variants));
         }
         let mut struct_idx = starting_idx;
-        for fields in field_types {
+        for (fields, variants) in field_types {
             match Arc::get_mut(&mut self.structs[struct_idx]) {
-                Some(struct_type) => struct_type.fields = fields,
+                Some(struct_type) => {
+                    struct_type.fields = fields;
+                    struct_type.variants = variants;
+                }
                 None => {
                     // we have pending references to the `Arc` which is impossible,
                     // given the code that adds the `Arc` is above and no reference to
@@ -263,6 +419,7 @@ impl ModuleCache {
                     error!("Arc<StructType> cannot have any live reference while publishing");
                     let mut struct_type = (*self.structs[struct_idx]).clone();
                     struct_type.fields = fields;
+                    struct_type.variants = variants;
                     self.structs[struct_idx] = Arc::new(struct_type);
                 }
             }
@@ -271,6 +428,20 @@ impl ModuleCache {
         Ok(())
     }
 
+    fn make_field_types(
+        &self,
+        module: &CompiledModule,
+        fields: &[FieldDefinition],
+    ) -> PartialVMResult<Vec<Type>> {
+        let mut field_tys = vec![];
+        for field in fields {
+            let ty = self.make_type_while_loading(module, &field.signature.0)?;
+            debug_assert!(field_tys.len() < usize::max_value());
+            field_tys.push(ty);
+        }
+        Ok(field_tys)
+    }
+
     // `make_type` is the entry point to "translate" a `SignatureToken` to a `Type`
     fn make_type(&self, module: BinaryIndexedView, tok: &SignatureToken) -> PartialVMResult<Type> {
         self.make_type_internal(module, tok, &|struct_name, module_id| {
@@ -330,8 +501,11 @@ impl ModuleCache {
         let res = match tok {
             SignatureToken::Bool => Type::Bool,
             SignatureToken::U8 => Type::U8,
+            SignatureToken::U16 => Type::U16,
+            SignatureToken::U32 => Type::U32,
             SignatureToken::U64 => Type::U64,
             SignatureToken::U128 => Type::U128,
+            SignatureToken::U256 => Type::U256,
             SignatureToken::Address => Type::Address,
             SignatureToken::Signer => Type::Signer,
             SignatureToken::TypeParameter(idx) => Type::TyParam(*idx as usize),
@@ -382,6 +556,40 @@ impl ModuleCache {
         self.modules.id_map.contains_key(module_id)
     }
 
+    // Remove the given modules and, transitively, all the modules depending on them from the
+    // cache, and return the ids and the struct indices of all the modules removed.
+    // The structs and functions of the removed modules stay in the cache: types and functions
+    // refer to them by index and values of those types may still be alive.
+    fn remove_modules_and_dependents(
+        &mut self,
+        ids: impl IntoIterator<Item = ModuleId>,
+    ) -> (BTreeSet<ModuleId>, Vec<CachedStructIndex>) {
+        let mut removed = BTreeSet::new();
+        let mut struct_idxs = vec![];
+        let mut pending: Vec<_> = ids.into_iter().collect();
+        while !pending.is_empty() {
+            for id in pending.drain(..) {
+                if let Some(module) = self.modules.remove(&id) {
+                    struct_idxs.extend(module.struct_map.values().copied());
+                    removed.insert(id);
+                }
+            }
+            pending = self
+                .modules
+                .iter()
+                .filter(|(_, module)| {
+                    module
+                        .module()
+                        .immediate_dependencies()
+                        .iter()
+                        .any(|dep| removed.contains(dep))
+                })
+                .map(|(id, _)| id.clone())
+                .collect();
+        }
+        (removed, struct_idxs)
+    }
+
     // Given a ModuleId::struct_name, retrieve the `StructType` and the index associated.
     // Return and error if the type has not been loaded
     fn resolve_struct_by_name(
@@ -440,16 +648,61 @@ pub(crate) struct Loader {
     module_cache: RwLock<ModuleCache>,
     type_cache: RwLock<TypeCache>,
     natives: NativeFunctions,
+    counters: CacheCounters,
+    vm_config: VMConfig,
 }
 
 impl Loader {
-    pub(crate) fn new(natives: NativeFunctions) -> Self {
+    pub(crate) fn new(natives: NativeFunctions, vm_config: VMConfig) -> Self {
         Self {
             scripts: RwLock::new(ScriptCache::new()),
             module_cache: RwLock::new(ModuleCache::new()),
             type_cache: RwLock::new(TypeCache::new()),
             natives,
+            counters: CacheCounters::default(),
+            vm_config,
+        }
+    }
+
+    pub(crate) fn vm_config(&self) -> &VMConfig {
+        &self.vm_config
+    }
+
+    //
+    // Cache management
+    //
+
+    pub(crate) fn cache_metrics(&self) -> CacheMetrics {
+        self.counters.snapshot()
+    }
+
+    // Bound the number of scripts in the script cache, `None` meaning unbounded.
+    pub(crate) fn set_script_cache_capacity(&self, capacity: Option<usize>) {
+        let mut scripts = self.scripts.write();
+        scripts.capacity = capacity;
+        let evicted = scripts.evict();
+        CacheCounters::record(&self.counters.scripts_evicted, evicted);
+    }
+
+    // Remove the given modules from the code cache, together with every module and script that
+    // depends on them, so that they are loaded again from storage the next time they are used.
+    // Returns the number of modules removed.
+    //
+    // Each cache is locked on its own, one after the other, so this never waits on a lock while
+    // holding another one.
+    pub(crate) fn flush_modules(&self, ids: impl IntoIterator<Item = ModuleId>) -> usize {
+        let (removed, struct_idxs) = self.module_cache.write().remove_modules_and_dependents(ids);
+        if removed.is_empty() {
+            return 0;
         }
+        self.scripts.write().remove_dependents(&removed);
+        let mut type_cache = self.type_cache.write();
+        for idx in struct_idxs {
+            type_cache.structs.remove(&idx);
+        }
+        drop(type_cache);
+        CacheCounters::record(&self.counters.modules_flushed, removed.len() as u64);
+        removed.len()
     }
 
     //
@@ -477,13 +730,26 @@ impl Loader {
 
         let mut scripts = self.scripts.write();
         let (main, parameters, return_) = match scripts.get(&hash_value) {
-            Some(cached) => cached,
+            Some(cached) => {
+                CacheCounters::record(&self.counters.script_hits, 1);
+                // the dependencies of the script are not loaded again
+                if data_store.is_recording_reads() {
+                    for module_id in scripts.dependencies(&hash_value) {
+                        self.record_cached_module(&module_id, data_store);
+                    }
+                }
+                cached
+            }
             None => {
+                CacheCounters::record(&self.counters.script_misses, 1);
                 let ver_script = self.deserialize_and_verify_script(script_blob, data_store)?;
                 let script = Script::new(ver_script, &hash_value, &self.module_cache.read())?;
-                scripts.insert(hash_value, script)
+                let loaded = scripts.insert(hash_value, script);
+                CacheCounters::record(&self.counters.scripts_evicted, scripts.evict());
+                loaded
             }
         };
+        drop(scripts); // explicit unlock
 
         // verify type arguments
         let mut type_arguments = vec![];
@@ -492,6 +758,8 @@ impl Loader {
         }
         self.verify_ty_args(main.type_parameters(), &type_arguments)
             .map_err(|e| e.finish(Location::Script))?;
+        self.check_type_instantiation_size(&type_arguments)
+            .map_err(|e| e.finish(Location::Script))?;
         let instantiation = LoadedFunctionInstantiation {
             type_arguments,
             parameters,
@@ -544,7 +812,7 @@ impl Loader {
     // Script verification steps.
     // See `verify_module()` for module verification steps.
     fn verify_script(&self, script: &CompiledScript) -> VMResult<()> {
-        move_bytecode_verifier::verify_script(script)
+        move_bytecode_verifier::verify_script_with_config(&self.vm_config.verifier, script)
     }
 
     fn verify_script_dependencies(
@@ -612,6 +880,8 @@ impl Loader {
             .collect::<VMResult<Vec<_>>>()?;
         self.verify_ty_args(func.type_parameters(), &type_arguments)
             .map_err(|e| e.finish(Location::Module(module_id.clone())))?;
+        self.check_type_instantiation_size(&type_arguments)
+            .map_err(|e| e.finish(Location::Module(module_id.clone())))?;
 
         let loaded = LoadedFunctionInstantiation {
             type_arguments,
@@ -669,8 +939,9 @@ impl Loader {
         // module will NOT show up in `module_cache`. In the module republishing case, it means
         // that the old module is still in the `module_cache`, unless a new Loader is created,
         // which means that a new MoveVM instance needs to be created.
-        move_bytecode_verifier::verify_module(module)?;
+        move_bytecode_verifier::verify_module_with_config(&self.vm_config.verifier, module)?;
         self.check_natives(module)?;
+        self.check_resource_groups(module)?;
 
         let mut visited = BTreeSet::new();
         let mut friends_discovered = BTreeSet::new();
@@ -739,6 +1010,51 @@ impl Loader {
         )
     }
 
+    // Resource group declarations must be well formed and only contain resources of the module
+    fn check_resource_groups(&self, module: &CompiledModule) -> VMResult<()> {
+        let error = |msg: String| {
+            PartialVMError::new(StatusCode::INVALID_RESOURCE_GROUP_DECLARATION)
+                .with_message(msg)
+                .finish(Location::Module(module.self_id()))
+        };
+        let metadata = match ResourceGroupMetadata::from_module_metadata(&module.metadata) {
+            Ok(Some(metadata)) => metadata,
+            Ok(None) => return Ok(()),
+            Err(err) => return Err(error(format!("Malformed resource groups: {}", err))),
+        };
+        let self_id = module.self_id();
+        let declared_abilities = |name: &IdentStr| {
+            module.struct_defs().iter().find_map(|struct_def| {
+                let handle = module.struct_handle_at(struct_def.struct_handle);
+                (module.identifier_at(handle.name) == name).then(|| handle.abilities)
+            })
+        };
+        for (name, group) in &metadata.members {
+            let is_resource = declared_abilities(name.as_ident_str())
+                .map_or(false, |abilities| abilities.has_key());
+            if !is_resource {
+                return Err(error(format!(
+                    "{} is not a resource declared in the module",
+                    name
+                )));
+            }
+            // A group is stored under its own tag, which must therefore be owned by the module
+            // and not be a resource: otherwise a module could overwrite another one's resources
+            let is_own_group = group.address == *self_id.address()
+                && group.module.as_ident_str() == self_id.name()
+                && group.type_params.is_empty()
+                && declared_abilities(group.name.as_ident_str())
+                    .map_or(false, |abilities| !abilities.has_key());
+            if !is_own_group {
+                return Err(error(format!(
+                    "{} is not a non-resource struct declared in the module",
+                    group
+                )));
+            }
+        }
+        Ok(())
+    }
+
     // All native functions must be known to the loader
     fn check_natives(&self, module: &CompiledModule) -> VMResult<()> {
         fn check_natives_impl(loader: &Loader, module: &CompiledModule) -> PartialVMResult<()> {
@@ -793,8 +1109,11 @@ impl Loader {
         Ok(match type_tag {
             TypeTag::Bool => Type::Bool,
             TypeTag::U8 => Type::U8,
+            TypeTag::U16 => Type::U16,
+            TypeTag::U32 => Type::U32,
             TypeTag::U64 => Type::U64,
             TypeTag::U128 => Type::U128,
+            TypeTag::U256 => Type::U256,
             TypeTag::Address => Type::Address,
             TypeTag::Signer => Type::Signer,
             TypeTag::Vector(tt) => Type::Vector(Box::new(self.load_type(tt, data_store)?)),
@@ -842,9 +1161,13 @@ impl Loader {
         data_store: &impl DataStore,
     ) -> VMResult<Arc<Module>> {
         // if the module is already in the code cache, load the cached version
-        if let Some(cached) = self.module_cache.read().module_at(id) {
+        let cached = self.module_cache.read().module_at(id);
+        if let Some(cached) = cached {
+            CacheCounters::record(&self.counters.module_hits, 1);
+            self.record_cached_module(id, data_store);
             return Ok(cached);
         }
+        CacheCounters::record(&self.counters.module_misses, 1);
 
         // otherwise, load the transitive closure of the target module
         let module_ref = self.load_and_verify_module_and_dependencies_and_friends(
@@ -865,13 +1188,35 @@ impl Loader {
         Ok(module_ref)
     }
 
-    // Load, deserialize, and check the module with the bytecode verifier, without linking
+    // Modules found in the code cache are not loaded from the data store. Their use, and that of
+    // their transitive dependencies, is recorded in the data store with the hash of the bytes they
+    // were loaded from, so that the read set of a session covers all the code it ran.
+    fn record_cached_module(&self, id: &ModuleId, data_store: &impl DataStore) {
+        if !data_store.is_recording_reads() {
+            return;
+        }
+        let module_cache = self.module_cache.read();
+        let mut visited = BTreeSet::new();
+        let mut pending = vec![id.clone()];
+        while let Some(id) = pending.pop() {
+            if !visited.insert(id.clone()) {
+                continue;
+            }
+            if let Some(module) = module_cache.module_at(&id) {
+                data_store.record_cached_module(&id, &module.hash);
+                pending.extend(module.module().immediate_dependencies());
+            }
+        }
+    }
+
+    // Load, deserialize, and check the module with the bytecode verifier, without linking.
+    // Returns the module along with the bytes it was deserialized from.
     fn load_and_verify_module(
         &self,
         id: &ModuleId,
         data_store: &impl DataStore,
         allow_loading_failure: bool,
-    ) -> VMResult<CompiledModule> {
+    ) -> VMResult<(CompiledModule, ModuleHash)> {
         // bytes fetching, allow loading to fail if the flag is set
         let bytes = match data_store.load_module(id) {
             Ok(bytes) => bytes,
@@ -893,11 +1238,15 @@ impl Loader {
             })
             .map_err(expect_no_verification_errors)?;
 
-        // bytecode verifier checks that can be performed with the module itself
+        // bytecode verifier checks that can be performed with the module itself. The limits of
+        // the verifier config are not enforced: they were when the module was published, and
+        // modules already in storage must remain loadable if the limits are lowered.
         move_bytecode_verifier::verify_module(&module).map_err(expect_no_verification_errors)?;
         self.check_natives(&module)
             .map_err(expect_no_verification_errors)?;
-        Ok(module)
+        self.check_resource_groups(&module)
+            .map_err(expect_no_verification_errors)?;
+        Ok((module, module_hash(&bytes)))
     }
 
     // Everything in `load_and_verify_module` and also recursively load and verify all the
@@ -918,7 +1267,8 @@ impl Loader {
         }
 
         // module self-check
-        let module = self.load_and_verify_module(id, data_store, allow_module_loading_failure)?;
+        let (module, hash) =
+            self.load_and_verify_module(id, data_store, allow_module_loading_failure)?;
         visited.insert(id.clone());
         friends_discovered.extend(module.immediate_friends());
 
@@ -935,7 +1285,7 @@ impl Loader {
 
         // if linking goes well, insert the module to the code cache
         let mut locked_cache = self.module_cache.write();
-        let module_ref = locked_cache.insert(&self.natives, id.clone(), module)?;
+        let module_ref = locked_cache.insert(&self.natives, id.clone(), module, hash)?;
         drop(locked_cache); // explicit unlock
 
         Ok(module_ref)
@@ -974,7 +1324,11 @@ impl Loader {
                             allow_dependency_loading_failure,
                         )?
                     }
-                    Some(cached) => cached,
+                    Some(cached) => {
+                        drop(locked_cache); // explicit unlock
+                        self.record_cached_module(&module_id, data_store);
+                        cached
+                    }
                 };
                 cached_deps.push(loaded);
             }
@@ -1132,9 +1486,14 @@ impl Loader {
 
     pub(crate) fn abilities(&self, ty: &Type) -> PartialVMResult<AbilitySet> {
         match ty {
-            Type::Bool | Type::U8 | Type::U64 | Type::U128 | Type::Address => {
-                Ok(AbilitySet::PRIMITIVES)
-            }
+            Type::Bool
+            | Type::U8
+            | Type::U16
+            | Type::U32
+            | Type::U64
+            | Type::U128
+            | Type::U256
+            | Type::Address => Ok(AbilitySet::PRIMITIVES),
 
             // Technically unreachable but, no point in erroring if we don't have to
             Type::Reference(_) | Type::MutableReference(_) => Ok(AbilitySet::REFERENCES),
@@ -1249,14 +1608,6 @@ impl<'a> Resolver<'a> {
         Ok(instantiation)
     }
 
-    pub(crate) fn type_params_count(&self, idx: FunctionInstantiationIndex) -> usize {
-        let func_inst = match &self.binary {
-            BinaryType::Module(module) => module.function_instantiation_at(idx.0),
-            BinaryType::Script(script) => script.function_instantiation_at(idx.0),
-        };
-        func_inst.instantiation.len()
-    }
-
     //
     // Type resolution
     //
@@ -1336,6 +1687,28 @@ impl<'a> Resolver<'a> {
         }
     }
 
+    pub(crate) fn variant_field_count(
+        &self,
+        idx: StructDefinitionIndex,
+        variant: VariantIndex,
+    ) -> u16 {
+        match &self.binary {
+            BinaryType::Module(module) => module.variant_field_count(idx.0, variant),
+            BinaryType::Script(_) => unreachable!("Scripts cannot have type instructions"),
+        }
+    }
+
+    pub(crate) fn variant_instantiation_field_count(
+        &self,
+        idx: StructDefInstantiationIndex,
+        variant: VariantIndex,
+    ) -> u16 {
+        match &self.binary {
+            BinaryType::Module(module) => module.variant_instantiation_field_count(idx.0, variant),
+            BinaryType::Script(_) => unreachable!("Scripts cannot have type instructions"),
+        }
+    }
+
     pub(crate) fn type_to_type_layout(&self, ty: &Type) -> PartialVMResult<MoveTypeLayout> {
         self.loader.type_to_type_layout(ty)
     }
@@ -1357,6 +1730,8 @@ pub(crate) struct Module {
     id: ModuleId,
     // primitive pools
     module: Arc<CompiledModule>,
+    // the hash of the serialized module, as loaded from the data store
+    hash: ModuleHash,
 
     //
     // types as indexes into the Loader type list
@@ -1393,6 +1768,9 @@ pub(crate) struct Module {
     // This allows a direct access from struct name to `Struct`
     struct_map: HashMap<Identifier, CachedStructIndex>,
 
+    // struct name to the tag of the resource group the struct is stored in, if any
+    resource_groups: HashMap<Identifier, StructTag>,
+
     // a map of single-token signature indices to type.
     // Single-token signatures are usually indexed by the `SignatureIndex` in bytecode. For example,
     // `VecMutBorrow(SignatureIndex)`, the `SignatureIndex` maps to a single `SignatureToken`, and
@@ -1403,6 +1781,7 @@ pub(crate) struct Module {
 impl Module {
     fn new(
         module: CompiledModule,
+        hash: ModuleHash,
         cache: &ModuleCache,
     ) -> Result<Self, (PartialVMError, CompiledModule)> {
         let id = module.self_id();
@@ -1417,6 +1796,12 @@ impl Module {
         let mut function_map = HashMap::new();
         let mut struct_map = HashMap::new();
         let mut single_signature_token_map = BTreeMap::new();
+        // declarations have been checked when the module was verified
+        let resource_groups = ResourceGroupMetadata::from_module_metadata(&module.metadata)
+            .ok()
+            .flatten()
+            .map(|metadata| metadata.members.into_iter().collect())
+            .unwrap_or_default();
 
         let mut create = || {
             for struct_handle in module.struct_handles() {
@@ -1449,8 +1834,18 @@ impl Module {
 
             for struct_def in module.struct_defs() {
                 let idx = struct_refs[struct_def.struct_handle.0 as usize];
-                let field_count = cache.structs[idx.0].fields.len() as u16;
-                structs.push(StructDef { field_count, idx });
+                let struct_type = &cache.structs[idx.0];
+                let field_count = struct_type.fields.len() as u16;
+                let variant_field_counts = struct_type
+                    .variants
+                    .iter()
+                    .map(|fields| fields.len() as u16)
+                    .collect();
+                structs.push(StructDef {
+                    field_count,
+                    variant_field_counts,
+                    idx,
+                });
                 let name =
                     module.identifier_at(module.struct_handle_at(struct_def.struct_handle).name);
                 struct_map.insert(name.to_owned(), idx);
@@ -1460,12 +1855,14 @@ impl Module {
                 let def = struct_inst.def.0 as usize;
                 let struct_def = &structs[def];
                 let field_count = struct_def.field_count;
+                let variant_field_counts = struct_def.variant_field_counts.clone();
                 let mut instantiation = vec![];
                 for ty in &module.signature_at(struct_inst.type_parameters).0 {
                     instantiation.push(cache.make_type_while_loading(&module, ty)?);
                 }
                 struct_instantiations.push(StructInstantiation {
                     field_count,
+                    variant_field_counts,
                     def: struct_def.idx,
                     instantiation,
                 });
@@ -1570,6 +1967,7 @@ impl Module {
             Ok(_) => Ok(Self {
                 id,
                 module: Arc::new(module),
+                hash,
                 struct_refs,
                 structs,
                 struct_instantiations,
@@ -1579,6 +1977,7 @@ impl Module {
                 field_instantiations,
                 function_map,
                 struct_map,
+                resource_groups,
                 single_signature_token_map,
             }),
             Err(err) => Err((err, module)),
@@ -1609,6 +2008,14 @@ impl Module {
         self.struct_instantiations[idx as usize].field_count
     }
 
+    fn variant_field_count(&self, idx: u16, variant: VariantIndex) -> u16 {
+        self.structs[idx as usize].variant_field_counts[variant as usize]
+    }
+
+    fn variant_instantiation_field_count(&self, idx: u16, variant: VariantIndex) -> u16 {
+        self.struct_instantiations[idx as usize].variant_field_counts[variant as usize]
+    }
+
     pub(crate) fn module(&self) -> &CompiledModule {
         &self.module
     }
@@ -1999,6 +2406,8 @@ struct FunctionInstantiation {
 struct StructDef {
     // struct field count
     field_count: u16,
+    // field count of each variant of an enum
+    variant_field_counts: Vec<u16>,
     // `ModuelCache::structs` global table index
     idx: CachedStructIndex,
 }
@@ -2007,6 +2416,8 @@ struct StructDef {
 struct StructInstantiation {
     // struct field count
     field_count: u16,
+    // field count of each variant of an enum
+    variant_field_counts: Vec<u16>,
     // `ModuelCache::structs` global table index. It is the generic type.
     def: CachedStructIndex,
     instantiation: Vec<Type>,
@@ -2037,6 +2448,7 @@ struct FieldInstantiation {
 struct StructInfo {
     struct_tag: Option<StructTag>,
     struct_layout: Option<MoveStructLayout>,
+    depth: Option<u64>,
 }
 
 impl StructInfo {
@@ -2044,6 +2456,7 @@ impl StructInfo {
         Self {
             struct_tag: None,
             struct_layout: None,
+            depth: None,
         }
     }
 }
@@ -2060,8 +2473,6 @@ impl TypeCache {
     }
 }
 
-const VALUE_DEPTH_MAX: usize = 128;
-
 impl Loader {
     fn struct_gidx_to_type_tag(
         &self,
@@ -2104,8 +2515,11 @@ impl Loader {
         Ok(match ty {
             Type::Bool => TypeTag::Bool,
             Type::U8 => TypeTag::U8,
+            Type::U16 => TypeTag::U16,
+            Type::U32 => TypeTag::U32,
             Type::U64 => TypeTag::U64,
             Type::U128 => TypeTag::U128,
+            Type::U256 => TypeTag::U256,
             Type::Address => TypeTag::Address,
             Type::Signer => TypeTag::Signer,
             Type::Vector(ty) => TypeTag::Vector(Box::new(self.type_to_type_tag(ty)?)),
@@ -2126,7 +2540,7 @@ impl Loader {
         &self,
         gidx: CachedStructIndex,
         ty_args: &[Type],
-        depth: usize,
+        depth: u64,
     ) -> PartialVMResult<MoveStructLayout> {
         if let Some(struct_map) = self.type_cache.read().structs.get(&gidx) {
             if let Some(struct_info) = struct_map.get(ty_args) {
@@ -2137,16 +2551,20 @@ impl Loader {
         }
 
         let struct_type = self.module_cache.read().struct_at(gidx);
-        let field_tys = struct_type
-            .fields
-            .iter()
-            .map(|ty| ty.subst(ty_args))
-            .collect::<PartialVMResult<Vec<_>>>()?;
-        let field_layouts = field_tys
-            .iter()
-            .map(|ty| self.type_to_type_layout_impl(ty, depth + 1))
-            .collect::<PartialVMResult<Vec<_>>>()?;
-        let struct_layout = MoveStructLayout::new(field_layouts);
+        let struct_layout = if struct_type.is_enum() {
+            let variant_layouts = struct_type
+                .variants
+                .iter()
+                .map(|fields| self.field_types_to_type_layouts(fields, ty_args, depth))
+                .collect::<PartialVMResult<Vec<_>>>()?;
+            MoveStructLayout::new_variants(variant_layouts)
+        } else {
+            MoveStructLayout::new(self.field_types_to_type_layouts(
+                &struct_type.fields,
+                ty_args,
+                depth,
+            )?)
+        };
 
         self.type_cache
             .write()
@@ -2160,15 +2578,30 @@ impl Loader {
         Ok(struct_layout)
     }
 
-    fn type_to_type_layout_impl(&self, ty: &Type, depth: usize) -> PartialVMResult<MoveTypeLayout> {
-        if depth > VALUE_DEPTH_MAX {
+    fn field_types_to_type_layouts(
+        &self,
+        field_tys: &[Type],
+        ty_args: &[Type],
+        depth: u64,
+    ) -> PartialVMResult<Vec<MoveTypeLayout>> {
+        field_tys
+            .iter()
+            .map(|ty| self.type_to_type_layout_impl(&ty.subst(ty_args)?, depth + 1))
+            .collect()
+    }
+
+    fn type_to_type_layout_impl(&self, ty: &Type, depth: u64) -> PartialVMResult<MoveTypeLayout> {
+        if depth > self.vm_config.max_value_depth {
             return Err(PartialVMError::new(StatusCode::VM_MAX_VALUE_DEPTH_REACHED));
         }
         Ok(match ty {
             Type::Bool => MoveTypeLayout::Bool,
             Type::U8 => MoveTypeLayout::U8,
+            Type::U16 => MoveTypeLayout::U16,
+            Type::U32 => MoveTypeLayout::U32,
             Type::U64 => MoveTypeLayout::U64,
             Type::U128 => MoveTypeLayout::U128,
+            Type::U256 => MoveTypeLayout::U256,
             Type::Address => MoveTypeLayout::Address,
             Type::Signer => MoveTypeLayout::Signer,
             Type::Vector(ty) => {
@@ -2195,6 +2628,121 @@ impl Loader {
     pub(crate) fn type_to_type_layout(&self, ty: &Type) -> PartialVMResult<MoveTypeLayout> {
         self.type_to_type_layout_impl(ty, 1)
     }
+
+    // The maximum nesting depth of values of type `ty`, see `VMConfig::max_value_depth`.
+    fn calculate_depth_of_type(&self, ty: &Type) -> PartialVMResult<u64> {
+        Ok(match ty {
+            Type::Bool
+            | Type::U8
+            | Type::U16
+            | Type::U32
+            | Type::U64
+            | Type::U128
+            | Type::U256
+            | Type::Address
+            | Type::Signer => 1,
+            Type::Vector(ty) => self.calculate_depth_of_type(ty)? + 1,
+            Type::Struct(gidx) => self.calculate_depth_of_struct(*gidx, &[])?,
+            Type::StructInstantiation(gidx, ty_args) => {
+                self.calculate_depth_of_struct(*gidx, ty_args)?
+            }
+            Type::Reference(_) | Type::MutableReference(_) | Type::TyParam(_) => {
+                return Err(
+                    PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
+                        .with_message(format!("no value depth for {:?}", ty)),
+                )
+            }
+        })
+    }
+
+    fn calculate_depth_of_struct(
+        &self,
+        gidx: CachedStructIndex,
+        ty_args: &[Type],
+    ) -> PartialVMResult<u64> {
+        if let Some(struct_map) = self.type_cache.read().structs.get(&gidx) {
+            if let Some(struct_info) = struct_map.get(ty_args) {
+                if let Some(depth) = struct_info.depth {
+                    return Ok(depth);
+                }
+            }
+        }
+
+        let struct_type = self.module_cache.read().struct_at(gidx);
+        let mut field_depth = 0;
+        // the variants of an enum are as deep as their deepest field
+        for field_ty in struct_type
+            .fields
+            .iter()
+            .chain(struct_type.variants.iter().flatten())
+        {
+            let field_depth_ty = self.calculate_depth_of_type(&field_ty.subst(ty_args)?)?;
+            field_depth = std::cmp::max(field_depth, field_depth_ty);
+        }
+        let depth = field_depth + 1;
+
+        self.type_cache
+            .write()
+            .structs
+            .entry(gidx)
+            .or_insert_with(HashMap::new)
+            .entry(ty_args.to_vec())
+            .or_insert_with(StructInfo::new)
+            .depth = Some(depth);
+
+        Ok(depth)
+    }
+
+    // Fails with `VM_MAX_VALUE_DEPTH_REACHED` if values of type `ty` can be nested deeper than
+    // the VM allows, when the VM checks the depth of the values it packs.
+    pub(crate) fn check_value_depth(&self, ty: &Type) -> PartialVMResult<()> {
+        if !self.vm_config.check_value_depth_on_pack {
+            return Ok(());
+        }
+        if self.calculate_depth_of_type(ty)? > self.vm_config.max_value_depth {
+            return Err(PartialVMError::new(StatusCode::VM_MAX_VALUE_DEPTH_REACHED));
+        }
+        Ok(())
+    }
+
+    // Fails with `VM_MAX_TYPE_INSTANTIATION_SIZE_REACHED` if the type arguments of a call have
+    // more nodes than the VM allows.
+    pub(crate) fn check_type_instantiation_size(&self, ty_args: &[Type]) -> PartialVMResult<()> {
+        fn num_nodes(ty: &Type) -> u64 {
+            match ty {
+                Type::Vector(ty) | Type::Reference(ty) | Type::MutableReference(ty) => {
+                    num_nodes(ty) + 1
+                }
+                Type::StructInstantiation(_, ty_args) => {
+                    ty_args.iter().map(num_nodes).sum::<u64>() + 1
+                }
+                _ => 1,
+            }
+        }
+        let size: u64 = ty_args.iter().map(num_nodes).sum();
+        if size > self.vm_config.max_type_instantiation_size {
+            return Err(
+                PartialVMError::new(StatusCode::VM_MAX_TYPE_INSTANTIATION_SIZE_REACHED)
+                    .with_message(format!("type instantiation of size {}", size)),
+            );
+        }
+        Ok(())
+    }
+
+    // The tag of the resource group a resource of type `ty` is stored in, if any.
+    pub(crate) fn resource_group_of(&self, ty: &Type) -> PartialVMResult<Option<StructTag>> {
+        let gidx = match ty {
+            Type::Struct(gidx) | Type::StructInstantiation(gidx, _) => *gidx,
+            _ => return Err(PartialVMError::new(StatusCode::INTERNAL_TYPE_ERROR)),
+        };
+        let module_cache = self.module_cache.read();
+        let struct_type = module_cache.struct_at(gidx);
+        Ok(module_cache
+            .modules
+            .get(&struct_type.module)
+            .and_then(|module| module.resource_groups.get(&struct_type.name))
+            .cloned())
+    }
 }
 
 // Public APIs for external uses.
@@ -3,8 +3,9 @@
 
 use std::sync::Arc;
 
+pub use crate::loader::CacheMetrics;
 use crate::{
-    data_cache::TransactionDataCache, native_extensions::NativeContextExtensions,
+    config::VMConfig, data_cache::TransactionDataCache, native_extensions::NativeContextExtensions,
     native_functions::NativeFunction, runtime::VMRuntime, session::Session,
 };
 use move_binary_format::{
@@ -23,9 +24,18 @@ pub struct MoveVM {
 impl MoveVM {
     pub fn new(
         natives: impl IntoIterator<Item = (AccountAddress, Identifier, Identifier, NativeFunction)>,
+    ) -> VMResult<Self> {
+        Self::new_with_config(natives, VMConfig::default())
+    }
+
+    /// Create a new Move VM enforcing the limits of `vm_config`.
+    pub fn new_with_config(
+        natives: impl IntoIterator<Item = (AccountAddress, Identifier, Identifier, NativeFunction)>,
+        vm_config: VMConfig,
     ) -> VMResult<Self> {
         Ok(Self {
-            runtime: VMRuntime::new(natives).map_err(|err| err.finish(Location::Undefined))?,
+            runtime: VMRuntime::new(natives, vm_config)
+                .map_err(|err| err.finish(Location::Undefined))?,
         })
     }
 
@@ -43,6 +53,9 @@ impl MoveVM {
     ///     cases where this may not be necessary, with the most notable one being the common module
     ///     publishing flow: you can keep using the same Move VM if you publish some modules in a Session
     ///     and apply the effects to the storage when the Session ends.
+    ///   - When modules already in the code cache are changed in the storage, e.g. upgraded through
+    ///     `publish_module_bundle`, call `mark_modules_stale` with their ids once the effects are
+    ///     applied instead of creating a new Move VM.
     pub fn new_session<'r, S: MoveResolver>(&self, remote: &'r S) -> Session<'r, '_, S> {
         self.runtime.new_session(remote)
     }
@@ -70,4 +83,31 @@ impl MoveVM {
             )
             .map(|arc_module| arc_module.arc_module())
     }
+
+    /// Remove a module from the VM's code cache, so that it is loaded again from storage the next
+    /// time it is used. See `mark_modules_stale`.
+    pub fn flush_module(&self, module_id: &ModuleId) -> usize {
+        self.mark_modules_stale(std::iter::once(module_id.clone()))
+    }
+
+    /// Remove modules which have been changed in storage from the VM's code cache, so that the
+    /// new versions are loaded the next time they are used. All cached modules and scripts
+    /// depending on them, directly or transitively, are removed as well. Returns the number of
+    /// modules removed.
+    ///
+    /// This must not be called while a Session using any of those modules is alive.
+    pub fn mark_modules_stale(&self, module_ids: impl IntoIterator<Item = ModuleId>) -> usize {
+        self.runtime.loader().flush_modules(module_ids)
+    }
+
+    /// Bound the number of verified scripts kept in the VM's code cache, evicting the least
+    /// recently used ones when the bound is exceeded. `None`, the default, means unbounded.
+    pub fn set_script_cache_capacity(&self, capacity: Option<usize>) {
+        self.runtime.loader().set_script_cache_capacity(capacity)
+    }
+
+    /// Hit and miss counts of the VM's code caches.
+    pub fn cache_metrics(&self) -> CacheMetrics {
+        self.runtime.loader().cache_metrics()
+    }
 }
@@ -2,7 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    interpreter::Interpreter, loader::Resolver, native_extensions::NativeContextExtensions,
+    config::VMConfig, interpreter::Interpreter, loader::Resolver,
+    native_extensions::NativeContextExtensions,
 };
 use move_binary_format::errors::{ExecutionState, PartialVMError, PartialVMResult};
 use move_core_types::{
@@ -14,7 +15,7 @@ use move_core_types::{
     vm_status::{StatusCode, StatusType},
 };
 use move_vm_types::{
-    data_store::DataStore, gas_schedule::GasStatus, loaded_data::runtime_types::Type,
+    data_store::DataStore, gas_schedule::GasMeter, loaded_data::runtime_types::Type,
     natives::function::NativeResult, values::Value,
 };
 use std::{
@@ -87,7 +88,7 @@ impl NativeFunctions {
 pub struct NativeContext<'a, 'b> {
     interpreter: &'a mut Interpreter,
     data_store: &'a mut dyn DataStore,
-    gas_status: &'a GasStatus<'a>,
+    gas_meter: &'a dyn GasMeter,
     resolver: &'a Resolver<'a>,
     extensions: &'a mut NativeContextExtensions<'b>,
 }
@@ -96,14 +97,14 @@ impl<'a, 'b> NativeContext<'a, 'b> {
     pub(crate) fn new(
         interpreter: &'a mut Interpreter,
         data_store: &'a mut dyn DataStore,
-        gas_status: &'a mut GasStatus,
+        gas_meter: &'a mut impl GasMeter,
         resolver: &'a Resolver<'a>,
         extensions: &'a mut NativeContextExtensions<'b>,
     ) -> Self {
         Self {
             interpreter,
             data_store,
-            gas_status,
+            gas_meter,
             resolver,
             extensions,
         }
@@ -117,7 +118,11 @@ impl<'a, 'b> NativeContext<'a, 'b> {
     }
 
     pub fn cost_table(&self) -> &CostTable {
-        self.gas_status.cost_table()
+        self.gas_meter.cost_table()
+    }
+
+    pub fn vm_config(&self) -> &VMConfig {
+        self.resolver.loader().vm_config()
     }
 
     pub fn save_event(
@@ -2,12 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    config::VMConfig,
     data_cache::TransactionDataCache,
     interpreter::Interpreter,
     loader::{Function, Loader},
     native_extensions::NativeContextExtensions,
     native_functions::{NativeFunction, NativeFunctions},
     session::{LoadedFunctionInstantiation, SerializedReturnValues, Session},
+    trace_sink::Tracer,
 };
 use move_binary_format::{
     access::ModuleAccess,
@@ -27,7 +29,7 @@ use move_core_types::{
 };
 use move_vm_types::{
     data_store::DataStore,
-    gas_schedule::GasStatus,
+    gas_schedule::GasMeter,
     loaded_data::runtime_types::Type,
     values::{Locals, Reference, VMValueCast, Value},
 };
@@ -42,9 +44,10 @@ pub(crate) struct VMRuntime {
 impl VMRuntime {
     pub(crate) fn new(
         natives: impl IntoIterator<Item = (AccountAddress, Identifier, Identifier, NativeFunction)>,
+        vm_config: VMConfig,
     ) -> PartialVMResult<Self> {
         Ok(VMRuntime {
-            loader: Loader::new(NativeFunctions::new(natives)?),
+            loader: Loader::new(NativeFunctions::new(natives)?, vm_config),
         })
     }
 
@@ -53,6 +56,7 @@ impl VMRuntime {
             runtime: self,
             data_cache: TransactionDataCache::new(remote, &self.loader),
             native_extensions: NativeContextExtensions::default(),
+            trace_sink: None,
         }
     }
 
@@ -65,6 +69,7 @@ impl VMRuntime {
             runtime: self,
             data_cache: TransactionDataCache::new(remote, &self.loader),
             native_extensions,
+            trace_sink: None,
         }
     }
 
@@ -73,7 +78,7 @@ impl VMRuntime {
         modules: Vec<Vec<u8>>,
         sender: AccountAddress,
         data_store: &mut impl DataStore,
-        _gas_status: &mut GasStatus,
+        _gas_meter: &mut impl GasMeter,
         compat_check: bool,
     ) -> VMResult<()> {
         // deserialize the modules. Perform bounds check. After this indexes can be
@@ -209,7 +214,10 @@ impl VMRuntime {
         };
 
         match Value::simple_deserialize(arg.borrow(), &layout) {
-            Some(val) => Ok(val),
+            Some(val) => {
+                val.check_vector_lengths(self.loader.vm_config().max_vector_length)?;
+                Ok(val)
+            }
             None => {
                 warn!("[VM] failed to deserialize argument");
                 Err(PartialVMError::new(
@@ -317,8 +325,9 @@ impl VMRuntime {
         return_types: Vec<Type>,
         serialized_args: Vec<impl Borrow<[u8]>>,
         data_store: &mut impl DataStore,
-        gas_status: &mut GasStatus,
+        gas_meter: &mut impl GasMeter,
         extensions: &mut NativeContextExtensions,
+        tracer: &mut Tracer,
     ) -> VMResult<SerializedReturnValues> {
         let arg_types = param_types
             .into_iter()
@@ -347,8 +356,9 @@ impl VMRuntime {
             ty_args,
             deserialized_args,
             data_store,
-            gas_status,
+            gas_meter,
             extensions,
+            tracer,
             &self.loader,
         )?;
 
@@ -382,8 +392,9 @@ impl VMRuntime {
         ty_args: Vec<TypeTag>,
         serialized_args: Vec<impl Borrow<[u8]>>,
         data_store: &mut impl DataStore,
-        gas_status: &mut GasStatus,
+        gas_meter: &mut impl GasMeter,
         extensions: &mut NativeContextExtensions,
+        tracer: &mut Tracer,
         bypass_declared_entry_check: bool,
     ) -> VMResult<SerializedReturnValues> {
         use move_binary_format::{binary_views::BinaryIndexedView, file_format::SignatureIndex};
@@ -434,8 +445,9 @@ impl VMRuntime {
             return_,
             serialized_args,
             data_store,
-            gas_status,
+            gas_meter,
             extensions,
+            tracer,
         )
     }
 
@@ -446,8 +458,9 @@ impl VMRuntime {
         ty_args: Vec<TypeTag>,
         serialized_args: Vec<impl Borrow<[u8]>>,
         data_store: &mut impl DataStore,
-        gas_status: &mut GasStatus,
+        gas_meter: &mut impl GasMeter,
         extensions: &mut NativeContextExtensions,
+        tracer: &mut Tracer,
     ) -> VMResult<SerializedReturnValues> {
         // load the script, perform verification
         let (
@@ -468,8 +481,9 @@ impl VMRuntime {
             return_,
             serialized_args,
             data_store,
-            gas_status,
+            gas_meter,
             extensions,
+            tracer,
         )
     }
 
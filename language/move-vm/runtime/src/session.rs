@@ -2,8 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    data_cache::TransactionDataCache, native_extensions::NativeContextExtensions,
+    data_cache::TransactionDataCache,
+    native_extensions::NativeContextExtensions,
     runtime::VMRuntime,
+    trace_sink::{TraceSink, Tracer},
 };
 use move_binary_format::{
     errors::*,
@@ -11,7 +13,7 @@ use move_binary_format::{
 };
 use move_core_types::{
     account_address::AccountAddress,
-    effects::{ChangeSet, Event},
+    effects::{ChangeSet, Event, ReadSet},
     identifier::IdentStr,
     language_storage::{ModuleId, TypeTag},
     resolver::MoveResolver,
@@ -19,7 +21,7 @@ use move_core_types::{
 };
 use move_vm_types::{
     data_store::DataStore,
-    gas_schedule::GasStatus,
+    gas_schedule::GasMeter,
     loaded_data::runtime_types::{CachedStructIndex, StructType, Type},
 };
 use std::{borrow::Borrow, sync::Arc};
@@ -28,6 +30,7 @@ pub struct Session<'r, 'l, S> {
     pub(crate) runtime: &'l VMRuntime,
     pub(crate) data_cache: TransactionDataCache<'r, 'l, S>,
     pub(crate) native_extensions: NativeContextExtensions<'r>,
+    pub(crate) trace_sink: Option<Box<dyn TraceSink + 'r>>,
 }
 
 /// Serialized return values from function/script execution
@@ -73,7 +76,7 @@ impl<'r, 'l, S: MoveResolver> Session<'r, 'l, S> {
         function_name: &IdentStr,
         ty_args: Vec<TypeTag>,
         args: Vec<impl Borrow<[u8]>>,
-        gas_status: &mut GasStatus,
+        gas_meter: &mut impl GasMeter,
     ) -> VMResult<SerializedReturnValues> {
         let bypass_declared_entry_check = false;
         self.runtime.execute_function(
@@ -82,8 +85,9 @@ impl<'r, 'l, S: MoveResolver> Session<'r, 'l, S> {
             ty_args,
             args,
             &mut self.data_cache,
-            gas_status,
+            gas_meter,
             &mut self.native_extensions,
+            &mut Tracer::new(&mut self.trace_sink),
             bypass_declared_entry_check,
         )
     }
@@ -95,7 +99,7 @@ impl<'r, 'l, S: MoveResolver> Session<'r, 'l, S> {
         function_name: &IdentStr,
         ty_args: Vec<TypeTag>,
         args: Vec<impl Borrow<[u8]>>,
-        gas_status: &mut GasStatus,
+        gas_meter: &mut impl GasMeter,
     ) -> VMResult<SerializedReturnValues> {
         let bypass_declared_entry_check = true;
         self.runtime.execute_function(
@@ -104,8 +108,9 @@ impl<'r, 'l, S: MoveResolver> Session<'r, 'l, S> {
             ty_args,
             args,
             &mut self.data_cache,
-            gas_status,
+            gas_meter,
             &mut self.native_extensions,
+            &mut Tracer::new(&mut self.trace_sink),
             bypass_declared_entry_check,
         )
     }
@@ -131,15 +136,16 @@ impl<'r, 'l, S: MoveResolver> Session<'r, 'l, S> {
         script: impl Borrow<[u8]>,
         ty_args: Vec<TypeTag>,
         args: Vec<impl Borrow<[u8]>>,
-        gas_status: &mut GasStatus,
+        gas_meter: &mut impl GasMeter,
     ) -> VMResult<SerializedReturnValues> {
         self.runtime.execute_script(
             script,
             ty_args,
             args,
             &mut self.data_cache,
-            gas_status,
+            gas_meter,
             &mut self.native_extensions,
+            &mut Tracer::new(&mut self.trace_sink),
         )
     }
 
@@ -160,9 +166,9 @@ impl<'r, 'l, S: MoveResolver> Session<'r, 'l, S> {
         &mut self,
         module: Vec<u8>,
         sender: AccountAddress,
-        gas_status: &mut GasStatus,
+        gas_meter: &mut impl GasMeter,
     ) -> VMResult<()> {
-        self.publish_module_bundle(vec![module], sender, gas_status)
+        self.publish_module_bundle(vec![module], sender, gas_meter)
     }
 
     /// Publish a series of modules.
@@ -184,10 +190,10 @@ impl<'r, 'l, S: MoveResolver> Session<'r, 'l, S> {
         &mut self,
         modules: Vec<Vec<u8>>,
         sender: AccountAddress,
-        gas_status: &mut GasStatus,
+        gas_meter: &mut impl GasMeter,
     ) -> VMResult<()> {
         self.runtime
-            .publish_module_bundle(modules, sender, &mut self.data_cache, gas_status, true)
+            .publish_module_bundle(modules, sender, &mut self.data_cache, gas_meter, true)
     }
 
     /// Same like `publish_module_bundle` but relaxes compatibility checks.
@@ -195,10 +201,10 @@ impl<'r, 'l, S: MoveResolver> Session<'r, 'l, S> {
         &mut self,
         modules: Vec<Vec<u8>>,
         sender: AccountAddress,
-        gas_status: &mut GasStatus,
+        gas_meter: &mut impl GasMeter,
     ) -> VMResult<()> {
         self.runtime
-            .publish_module_bundle(modules, sender, &mut self.data_cache, gas_status, false)
+            .publish_module_bundle(modules, sender, &mut self.data_cache, gas_meter, false)
     }
 
     pub fn num_mutated_accounts(&self, sender: &AccountAddress) -> u64 {
@@ -231,6 +237,39 @@ impl<'r, 'l, S: MoveResolver> Session<'r, 'l, S> {
         Ok((change_set, events, native_extensions))
     }
 
+    /// Same like `finish`, but also returns the values this session read from storage, once
+    /// `record_reads` has been called.
+    ///
+    /// Modules taken from the code cache of the VM are part of the read set as well, with the
+    /// bytes the cached module was loaded from, along with their transitive dependencies.
+    pub fn finish_with_read_set(self) -> VMResult<(ChangeSet, Vec<Event>, ReadSet)> {
+        self.data_cache
+            .into_effects_and_read_set()
+            .map_err(|e| e.finish(Location::Undefined))
+    }
+
+    /// Same like `finish_with_read_set`, but also extracts the native context extensions from the
+    /// session.
+    pub fn finish_with_extensions_and_read_set(
+        self,
+    ) -> VMResult<(ChangeSet, Vec<Event>, ReadSet, NativeContextExtensions<'r>)> {
+        let Session {
+            data_cache,
+            native_extensions,
+            ..
+        } = self;
+        let (change_set, events, read_set) = data_cache
+            .into_effects_and_read_set()
+            .map_err(|e| e.finish(Location::Undefined))?;
+        Ok((change_set, events, read_set, native_extensions))
+    }
+
+    /// Record the modules and resources read from storage from now on, together with the values
+    /// observed, so that `finish_with_read_set` can return them.
+    pub fn record_reads(&mut self) {
+        self.data_cache.record_reads()
+    }
+
     /// Load a script and all of its types into cache
     pub fn load_script(
         &self,
@@ -293,6 +332,19 @@ impl<'r, 'l, S: MoveResolver> Session<'r, 'l, S> {
     pub fn get_native_extensions(&mut self) -> &mut NativeContextExtensions<'r> {
         &mut self.native_extensions
     }
+
+    /// Attach a `TraceSink` which receives the execution events of all subsequent function and
+    /// script executions in this session, replacing any previously attached sink.
+    ///
+    /// To inspect the collected trace afterwards, attach a mutable reference to the sink.
+    pub fn set_trace_sink(&mut self, sink: impl TraceSink + 'r) {
+        self.trace_sink = Some(Box::new(sink));
+    }
+
+    /// Detach the current `TraceSink`, if any, and return it.
+    pub fn remove_trace_sink(&mut self) -> Option<Box<dyn TraceSink + 'r>> {
+        self.trace_sink.take()
+    }
 }
 
 pub struct LoadedFunctionInstantiation {
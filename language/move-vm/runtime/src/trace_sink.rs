@@ -0,0 +1,130 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Structured execution tracing.
+//!
+//! Embedders can attach a `TraceSink` to a `Session` to observe the execution of Move code: the
+//! interpreter reports function entries and exits, every executed instruction, accesses to global
+//! storage, native calls and aborts as `TraceEvent`s. Unlike the `MOVE_VM_TRACE` text trace, this
+//! is available in all builds and only costs a branch per event when no sink is attached.
+
+use crate::loader::Function;
+use move_binary_format::file_format::{Bytecode, CodeOffset, FunctionDefinitionIndex};
+use move_core_types::{account_address::AccountAddress, language_storage::ModuleId};
+use move_vm_types::{loaded_data::runtime_types::Type, values::Locals};
+
+/// The function an event happened in.
+#[derive(Clone, Copy, Debug)]
+pub struct TracedFunction<'a> {
+    /// The module defining the function, `None` for scripts.
+    pub module_id: Option<&'a ModuleId>,
+    pub name: &'a str,
+    pub index: FunctionDefinitionIndex,
+}
+
+impl<'a> TracedFunction<'a> {
+    pub(crate) fn new(function: &'a Function) -> Self {
+        Self {
+            module_id: function.module_id(),
+            name: function.name(),
+            index: function.index(),
+        }
+    }
+}
+
+/// The kind of access to global storage performed by an instruction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GlobalAccessKind {
+    Exists,
+    ImmBorrow,
+    MutBorrow,
+    MoveFrom,
+    MoveTo,
+}
+
+impl GlobalAccessKind {
+    /// Whether the access may modify the resource.
+    pub fn is_write(self) -> bool {
+        matches!(
+            self,
+            GlobalAccessKind::MutBorrow | GlobalAccessKind::MoveFrom | GlobalAccessKind::MoveTo
+        )
+    }
+}
+
+/// An event reported by the interpreter to a `TraceSink`.
+///
+/// `depth` is the number of Move frames below the function, i.e. `0` for the function the
+/// session was asked to execute.
+#[derive(Clone, Copy, Debug)]
+pub enum TraceEvent<'a> {
+    /// A Move function is entered, after its arguments have been moved into its locals.
+    EnterFunction {
+        function: TracedFunction<'a>,
+        ty_args: &'a [Type],
+        depth: usize,
+    },
+    /// A Move function returns to its caller.
+    ExitFunction {
+        function: TracedFunction<'a>,
+        ty_args: &'a [Type],
+        depth: usize,
+    },
+    /// An instruction is about to be executed, with the current values of the function's locals.
+    Instruction {
+        function: TracedFunction<'a>,
+        pc: CodeOffset,
+        instruction: &'a Bytecode,
+        locals: &'a Locals,
+    },
+    /// An instruction accesses the resource of type `ty` stored under `address`.
+    GlobalAccess {
+        function: TracedFunction<'a>,
+        pc: CodeOffset,
+        kind: GlobalAccessKind,
+        address: AccountAddress,
+        ty: &'a Type,
+    },
+    /// A native function is called.
+    NativeCall {
+        function: TracedFunction<'a>,
+        ty_args: &'a [Type],
+        depth: usize,
+    },
+    /// Execution aborts, either through the `Abort` instruction or from within a native function.
+    Abort {
+        function: TracedFunction<'a>,
+        pc: CodeOffset,
+        code: u64,
+    },
+}
+
+/// A receiver of `TraceEvent`s.
+pub trait TraceSink {
+    fn on_event(&mut self, event: TraceEvent);
+}
+
+impl<T: TraceSink + ?Sized> TraceSink for &mut T {
+    fn on_event(&mut self, event: TraceEvent) {
+        (**self).on_event(event)
+    }
+}
+
+/// The optional sink the interpreter reports to.
+pub(crate) struct Tracer<'a>(Option<&'a mut dyn TraceSink>);
+
+impl<'a> Tracer<'a> {
+    pub(crate) fn new(sink: &'a mut Option<Box<dyn TraceSink + '_>>) -> Self {
+        Self(
+            sink.as_mut()
+                .map(|sink| sink.as_mut() as &mut dyn TraceSink),
+        )
+    }
+
+    #[inline]
+    pub(crate) fn emit(&mut self, event: TraceEvent) {
+        if let Some(sink) = &mut self.0 {
+            sink.on_event(event)
+        }
+    }
+}
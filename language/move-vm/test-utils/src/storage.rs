@@ -11,9 +11,14 @@ use move_core_types::{
     identifier::Identifier,
     language_storage::{ModuleId, StructTag},
     resolver::{ModuleResolver, MoveResolver, ResourceResolver},
+    resource_group::apply_resource_group_changes,
 };
 use std::collections::{btree_map, BTreeMap};
+#[cfg(feature = "table-extension")]
+use std::ops::Bound;
 
+#[cfg(feature = "aggregator-extension")]
+use move_aggregator_extension::{AggregatorHandle, AggregatorOperation, AggregatorResolver};
 #[cfg(feature = "table-extension")]
 use move_table_extension::{TableChangeSet, TableHandle, TableOperation, TableResolver};
 
@@ -67,6 +72,17 @@ impl TableResolver for BlankStorage {
     }
 }
 
+#[cfg(feature = "aggregator-extension")]
+impl AggregatorResolver for BlankStorage {
+    fn resolve_aggregator_value(&self, _handle: &AggregatorHandle) -> Result<Option<u128>, Error> {
+        Ok(None)
+    }
+
+    fn operation_cost(&self, _op: AggregatorOperation) -> InternalGasUnits<GasCarrier> {
+        InternalGasUnits::new(1)
+    }
+}
+
 // A storage adapter created by stacking a change set on top of an existing storage backend.
 /// The new storage can be used for additional computations without modifying the base.
 #[derive(Debug, Clone)]
@@ -105,6 +121,15 @@ impl<'a, 'b, S: ResourceResolver> ResourceResolver for DeltaStorage<'a, 'b, S> {
 
         self.base.get_resource(address, tag)
     }
+
+    fn get_resource_group(
+        &self,
+        address: &AccountAddress,
+        group: &StructTag,
+    ) -> Result<Option<Vec<u8>>, S::Error> {
+        // TODO: No support for resource group deltas
+        self.base.get_resource_group(address, group)
+    }
 }
 
 #[cfg(feature = "table-extension")]
@@ -127,6 +152,17 @@ impl<'a, 'b, S: TableResolver> TableResolver for DeltaStorage<'a, 'b, S> {
         // TODO: No support for table deltas
         self.base.operation_cost(op, key_size, val_size)
     }
+
+    fn resolve_table_keys(
+        &self,
+        handle: &TableHandle,
+        start: &[u8],
+        end: Option<&[u8]>,
+        limit: usize,
+    ) -> std::result::Result<Vec<Vec<u8>>, Error> {
+        // TODO: No support for table deltas
+        self.base.resolve_table_keys(handle, start, end, limit)
+    }
 }
 
 impl<'a, 'b, S: MoveResolver> DeltaStorage<'a, 'b, S> {
@@ -178,7 +214,7 @@ where
 
 impl InMemoryAccountStorage {
     fn apply(&mut self, account_changeset: AccountChangeSet) -> Result<()> {
-        let (modules, resources) = account_changeset.into_inner();
+        let (modules, resources, resource_groups) = account_changeset.into_inner_with_groups();
         apply_changes(&mut self.modules, modules, |module_name| {
             format_err!(
                 "Failed to delete module {}: module does not exist.",
@@ -193,6 +229,19 @@ impl InMemoryAccountStorage {
             )
         })?;
 
+        // resource groups are stored like resources, under the tag of the group
+        for (group, changes) in resource_groups {
+            let blob = self.resources.get(&group).map(|blob| blob.as_slice());
+            match apply_resource_group_changes(blob, changes)? {
+                Some(blob) => {
+                    self.resources.insert(group, blob);
+                }
+                None => {
+                    self.resources.remove(&group);
+                }
+            }
+        }
+
         Ok(())
     }
 
@@ -347,4 +396,26 @@ impl TableResolver for InMemoryStorage {
     ) -> InternalGasUnits<GasCarrier> {
         InternalGasUnits::new(1)
     }
+
+    fn resolve_table_keys(
+        &self,
+        handle: &TableHandle,
+        start: &[u8],
+        end: Option<&[u8]>,
+        limit: usize,
+    ) -> std::result::Result<Vec<Vec<u8>>, Error> {
+        let table = match self.tables.get(handle) {
+            Some(table) => table,
+            None => return Ok(vec![]),
+        };
+        let end = match end {
+            Some(end) => Bound::Excluded(end.max(start)),
+            None => Bound::Unbounded,
+        };
+        Ok(table
+            .range::<[u8], _>((Bound::Included(start), end))
+            .take(limit)
+            .map(|(key, _)| key.clone())
+            .collect())
+    }
 }
@@ -7,7 +7,8 @@ use crate::{
 };
 use move_binary_format::errors::{PartialVMResult, VMResult};
 use move_core_types::{
-    account_address::AccountAddress, language_storage::ModuleId, value::MoveTypeLayout,
+    account_address::AccountAddress, effects::ModuleHash, language_storage::ModuleId,
+    value::MoveTypeLayout,
 };
 
 /// Provide an implementation for bytecodes related to data with a given data store.
@@ -23,11 +24,15 @@ pub trait DataStore {
 
     /// Try to load a resource from remote storage and create a corresponding GlobalValue
     /// that is owned by the data store.
+    ///
+    /// Besides the `GlobalValue`, this returns what was read from remote storage: `None` if the
+    /// resource was already cached, `Some(None)` if it does not exist in storage and
+    /// `Some(Some(n))` if a blob of `n` bytes was loaded.
     fn load_resource(
         &mut self,
         addr: AccountAddress,
         ty: &Type,
-    ) -> PartialVMResult<&mut GlobalValue>;
+    ) -> PartialVMResult<(&mut GlobalValue, Option<Option<usize>>)>;
 
     /// Get the serialized format of a `CompiledModule` given a `ModuleId`.
     fn load_module(&self, module_id: &ModuleId) -> VMResult<Vec<u8>>;
@@ -38,6 +43,15 @@ pub trait DataStore {
     /// Check if this module exists.
     fn exists_module(&self, module_id: &ModuleId) -> VMResult<bool>;
 
+    /// Whether the reads of the data store are being recorded, see `record_cached_module`.
+    fn is_recording_reads(&self) -> bool {
+        false
+    }
+
+    /// Record a read of the module `module_id`, whose bytes hash to `hash`, which the VM took from
+    /// its code cache instead of loading it with `load_module`.
+    fn record_cached_module(&self, _module_id: &ModuleId, _hash: &ModuleHash) {}
+
     // ---
     // EventStore operations
     // ---
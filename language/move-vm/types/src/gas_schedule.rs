@@ -6,6 +6,7 @@
 //! It is important to note that the cost schedule defined in this file does not track hashing
 //! operations or other native operations; the cost of each native operation will be returned by the
 //! native function itself.
+use crate::{loaded_data::runtime_types::Type, values::Value};
 use move_binary_format::{
     errors::{Location, PartialVMError, PartialVMResult, VMResult},
     file_format::{
@@ -20,20 +21,77 @@ use move_core_types::{
         AbstractMemorySize, CostTable, GasAlgebra, GasCarrier, GasConstants, GasCost, GasUnits,
         InternalGasUnits,
     },
+    u256::U256,
     vm_status::StatusCode,
 };
 use once_cell::sync::Lazy;
-use std::cmp::max;
+use std::{cmp::max, collections::VecDeque};
 
 static ZERO_COST_SCHEDULE: Lazy<CostTable> =
     Lazy::new(|| zero_cost_schedule(NUMBER_OF_NATIVE_FUNCTIONS));
 
+/// Trait that defines a generic gas meter interface, allowing clients of the Move VM to implement
+/// their own metering scheme.
+///
+/// The interpreter calls into the meter for every instruction it executes, for every access to
+/// global storage, for every function call and around every native function call.
+/// `GasStatus` is the default, `CostTable` based implementation.
+pub trait GasMeter {
+    /// Charge an instruction and fail if not enough gas units are left.
+    fn charge_instr(&mut self, opcode: Opcodes) -> PartialVMResult<()>;
+
+    /// Charge an instruction over data with a given size and fail if not enough gas units are left.
+    fn charge_instr_with_size(
+        &mut self,
+        opcode: Opcodes,
+        size: AbstractMemorySize<GasCarrier>,
+    ) -> PartialVMResult<()>;
+
+    /// Charge a `Call` or `CallGeneric` instruction before the callee is invoked.
+    ///
+    /// `ty_args` is the instantiation of the callee, `arg_count` the number of arguments passed
+    /// to it and `call_depth` the number of frames on the call stack at the time of the call.
+    fn charge_call(
+        &mut self,
+        opcode: Opcodes,
+        ty_args: &[Type],
+        arg_count: usize,
+        call_depth: usize,
+    ) -> PartialVMResult<()>;
+
+    /// Charge for loading a resource as part of a global storage access.
+    ///
+    /// `loaded` is `None` if the resource was already cached by the data store, `Some(None)` if
+    /// it was looked up in storage but does not exist, and `Some(Some(n))` if `n` bytes were read
+    /// from storage.
+    fn charge_load_resource(&mut self, loaded: Option<Option<usize>>) -> PartialVMResult<()>;
+
+    /// Charge for a native function call before it is executed, given its arguments.
+    fn charge_native_function_before_execution(
+        &mut self,
+        ty_args: &[Type],
+        args: &VecDeque<Value>,
+    ) -> PartialVMResult<()>;
+
+    /// Charge the cost reported by a native function once it has been executed.
+    fn charge_native_function(
+        &mut self,
+        amount: InternalGasUnits<GasCarrier>,
+    ) -> PartialVMResult<()>;
+
+    /// Return the `CostTable` native functions use to compute their own cost.
+    fn cost_table(&self) -> &CostTable;
+
+    /// Return the gas left.
+    fn remaining_gas(&self) -> GasUnits<GasCarrier>;
+}
+
 /// The Move VM implementation of state for gas metering.
 ///
 /// Initialize with a `CostTable` and the gas provided to the transaction.
 /// Provide all the proper guarantees about gas metering in the Move VM.
 ///
-/// Every client must use an instance of this type to interact with the Move VM.
+/// This is the default `GasMeter` used by clients of the Move VM.
 pub struct GasStatus<'a> {
     cost_table: &'a CostTable,
     gas_left: InternalGasUnits<GasCarrier>,
@@ -95,8 +153,28 @@ impl<'a> GasStatus<'a> {
         }
     }
 
-    /// Charge an instruction over data with a given size and fail if not enough gas units are left.
-    pub fn charge_instr_with_size(
+    /// Charge gas related to the overall size of a transaction and fail if not enough
+    /// gas units are left.
+    pub fn charge_intrinsic_gas(
+        &mut self,
+        intrinsic_cost: AbstractMemorySize<GasCarrier>,
+    ) -> VMResult<()> {
+        let cost = calculate_intrinsic_gas(intrinsic_cost, &self.cost_table.gas_constants);
+        self.deduct_gas(cost)
+            .map_err(|e| e.finish(Location::Undefined))
+    }
+
+    pub fn set_metering(&mut self, enabled: bool) {
+        self.charge = enabled
+    }
+}
+
+impl<'a> GasMeter for GasStatus<'a> {
+    fn charge_instr(&mut self, opcode: Opcodes) -> PartialVMResult<()> {
+        self.deduct_gas(self.cost_table.instruction_cost(opcode as u8).total())
+    }
+
+    fn charge_instr_with_size(
         &mut self,
         opcode: Opcodes,
         size: AbstractMemorySize<GasCarrier>,
@@ -112,24 +190,48 @@ impl<'a> GasStatus<'a> {
         )
     }
 
-    /// Charge an instruction and fail if not enough gas units are left.
-    pub fn charge_instr(&mut self, opcode: Opcodes) -> PartialVMResult<()> {
-        self.deduct_gas(self.cost_table.instruction_cost(opcode as u8).total())
+    fn charge_call(
+        &mut self,
+        opcode: Opcodes,
+        ty_args: &[Type],
+        arg_count: usize,
+        _call_depth: usize,
+    ) -> PartialVMResult<()> {
+        let size = match opcode {
+            Opcodes::CALL_GENERIC => ty_args.len() + 1,
+            _ => 1,
+        };
+        self.charge_instr_with_size(opcode, AbstractMemorySize::new(size as GasCarrier))?;
+        self.charge_instr_with_size(opcode, AbstractMemorySize::new(arg_count as GasCarrier))
     }
 
-    /// Charge gas related to the overall size of a transaction and fail if not enough
-    /// gas units are left.
-    pub fn charge_intrinsic_gas(
+    fn charge_load_resource(&mut self, _loaded: Option<Option<usize>>) -> PartialVMResult<()> {
+        // Storage reads are covered by the size based cost of the global access instructions.
+        Ok(())
+    }
+
+    fn charge_native_function_before_execution(
         &mut self,
-        intrinsic_cost: AbstractMemorySize<GasCarrier>,
-    ) -> VMResult<()> {
-        let cost = calculate_intrinsic_gas(intrinsic_cost, &self.cost_table.gas_constants);
-        self.deduct_gas(cost)
-            .map_err(|e| e.finish(Location::Undefined))
+        _ty_args: &[Type],
+        _args: &VecDeque<Value>,
+    ) -> PartialVMResult<()> {
+        // Native functions report their own cost, which is charged after execution.
+        Ok(())
     }
 
-    pub fn set_metering(&mut self, enabled: bool) {
-        self.charge = enabled
+    fn charge_native_function(
+        &mut self,
+        amount: InternalGasUnits<GasCarrier>,
+    ) -> PartialVMResult<()> {
+        self.deduct_gas(amount)
+    }
+
+    fn cost_table(&self) -> &CostTable {
+        GasStatus::cost_table(self)
+    }
+
+    fn remaining_gas(&self) -> GasUnits<GasCarrier> {
+        GasStatus::remaining_gas(self)
     }
 }
 
@@ -202,11 +304,17 @@ pub fn zero_cost_instruction_table() -> Vec<(Bytecode, GasCost)> {
         (Ret, GasCost::new(0, 0)),
         (Lt, GasCost::new(0, 0)),
         (LdU8(0), GasCost::new(0, 0)),
+        (LdU16(0), GasCost::new(0, 0)),
+        (LdU32(0), GasCost::new(0, 0)),
         (LdU64(0), GasCost::new(0, 0)),
         (LdU128(0), GasCost::new(0, 0)),
+        (LdU256(U256::zero()), GasCost::new(0, 0)),
         (CastU8, GasCost::new(0, 0)),
+        (CastU16, GasCost::new(0, 0)),
+        (CastU32, GasCost::new(0, 0)),
         (CastU64, GasCost::new(0, 0)),
         (CastU128, GasCost::new(0, 0)),
+        (CastU256, GasCost::new(0, 0)),
         (Abort, GasCost::new(0, 0)),
         (MutBorrowLoc(0), GasCost::new(0, 0)),
         (ImmBorrowLoc(0), GasCost::new(0, 0)),
@@ -274,6 +382,30 @@ pub fn zero_cost_instruction_table() -> Vec<(Bytecode, GasCost)> {
         (VecPopBack(SignatureIndex::new(0)), GasCost::new(0, 0)),
         (VecUnpack(SignatureIndex::new(0), 0), GasCost::new(0, 0)),
         (VecSwap(SignatureIndex::new(0)), GasCost::new(0, 0)),
+        (
+            PackVariant(StructDefinitionIndex::new(0), 0),
+            GasCost::new(0, 0),
+        ),
+        (
+            PackVariantGeneric(StructDefInstantiationIndex::new(0), 0),
+            GasCost::new(0, 0),
+        ),
+        (
+            UnpackVariant(StructDefinitionIndex::new(0), 0),
+            GasCost::new(0, 0),
+        ),
+        (
+            UnpackVariantGeneric(StructDefInstantiationIndex::new(0), 0),
+            GasCost::new(0, 0),
+        ),
+        (
+            TestVariant(StructDefinitionIndex::new(0), 0),
+            GasCost::new(0, 0),
+        ),
+        (
+            TestVariantGeneric(StructDefInstantiationIndex::new(0), 0),
+            GasCost::new(0, 0),
+        ),
     ]
 }
 
@@ -335,11 +467,17 @@ pub fn bytecode_instruction_costs() -> Vec<(Bytecode, GasCost)> {
         (Ret, GasCost::new(638, 1)),
         (Lt, GasCost::new(1, 1)),
         (LdU8(0), GasCost::new(1, 1)),
+        (LdU16(0), GasCost::new(1, 1)),
+        (LdU32(0), GasCost::new(1, 1)),
         (LdU64(0), GasCost::new(1, 1)),
         (LdU128(0), GasCost::new(1, 1)),
+        (LdU256(U256::zero()), GasCost::new(1, 1)),
         (CastU8, GasCost::new(2, 1)),
+        (CastU16, GasCost::new(1, 1)),
+        (CastU32, GasCost::new(1, 1)),
         (CastU64, GasCost::new(1, 1)),
         (CastU128, GasCost::new(1, 1)),
+        (CastU256, GasCost::new(1, 1)),
         (Abort, GasCost::new(1, 1)),
         (MutBorrowLoc(0), GasCost::new(2, 1)),
         (ImmBorrowLoc(0), GasCost::new(1, 1)),
@@ -407,6 +545,30 @@ pub fn bytecode_instruction_costs() -> Vec<(Bytecode, GasCost)> {
         (VecPopBack(SignatureIndex::new(0)), GasCost::new(227, 1)),
         (VecUnpack(SignatureIndex::new(0), 0), GasCost::new(572, 1)),
         (VecSwap(SignatureIndex::new(0)), GasCost::new(1436, 1)),
+        (
+            PackVariant(StructDefinitionIndex::new(0), 0),
+            GasCost::new(2, 1),
+        ),
+        (
+            PackVariantGeneric(StructDefInstantiationIndex::new(0), 0),
+            GasCost::new(2, 1),
+        ),
+        (
+            UnpackVariant(StructDefinitionIndex::new(0), 0),
+            GasCost::new(2, 1),
+        ),
+        (
+            UnpackVariantGeneric(StructDefInstantiationIndex::new(0), 0),
+            GasCost::new(2, 1),
+        ),
+        (
+            TestVariant(StructDefinitionIndex::new(0), 0),
+            GasCost::new(2, 1),
+        ),
+        (
+            TestVariantGeneric(StructDefInstantiationIndex::new(0), 0),
+            GasCost::new(2, 1),
+        ),
     ];
 }
 
@@ -12,6 +12,8 @@ pub const TYPE_DEPTH_MAX: usize = 256;
 #[derive(Debug, Clone, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct StructType {
     pub fields: Vec<Type>,
+    /// The field types of each variant if the type is an enum, in which case `fields` is empty.
+    pub variants: Vec<Vec<Type>>,
     pub abilities: AbilitySet,
     pub type_parameters: Vec<StructTypeParameter>,
     pub name: Identifier,
@@ -23,6 +25,10 @@ impl StructType {
     pub fn type_param_constraints(&self) -> impl ExactSizeIterator<Item = &AbilitySet> {
         self.type_parameters.iter().map(|param| &param.constraints)
     }
+
+    pub fn is_enum(&self) -> bool {
+        !self.variants.is_empty()
+    }
 }
 
 #[derive(Debug, Copy, Clone, Eq, Hash, Ord, PartialEq, PartialOrd)]
@@ -32,8 +38,11 @@ pub struct CachedStructIndex(pub usize);
 pub enum Type {
     Bool,
     U8,
+    U16,
+    U32,
     U64,
     U128,
+    U256,
     Address,
     Signer,
     Vector(Box<Type>),
@@ -60,8 +69,11 @@ impl Type {
             Type::TyParam(idx) => subst(*idx, depth)?,
             Type::Bool => Type::Bool,
             Type::U8 => Type::U8,
+            Type::U16 => Type::U16,
+            Type::U32 => Type::U32,
             Type::U64 => Type::U64,
             Type::U128 => Type::U128,
+            Type::U256 => Type::U256,
             Type::Address => Type::Address,
             Type::Signer => Type::Signer,
             Type::Vector(ty) => Type::Vector(Box::new(ty.apply_subst(subst, depth + 1)?)),
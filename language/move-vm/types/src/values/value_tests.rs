@@ -3,6 +3,7 @@
 
 use crate::values::*;
 use move_binary_format::errors::*;
+use move_core_types::u256::U256;
 
 #[test]
 fn locals() -> PartialVMResult<()> {
@@ -120,3 +121,22 @@ fn global_value_non_struct() -> PartialVMResult<()> {
 
     Ok(())
 }
+
+#[test]
+fn integer_casts_and_shifts() -> PartialVMResult<()> {
+    let int = |v: Value| v.value_as::<IntegerValue>();
+
+    assert_eq!(int(Value::u16(u16::MAX))?.cast_u32()?, u16::MAX as u32);
+    assert_eq!(int(Value::u32(255))?.cast_u8()?, 255);
+    assert!(int(Value::u32(256))?.cast_u8().is_err());
+    assert!(int(Value::u256(U256::from(u128::MAX)))?.cast_u128().is_ok());
+    assert!(int(Value::u256(U256::max_value()))?.cast_u128().is_err());
+
+    assert!(int(Value::u16(1))?.shl_checked(16).is_err());
+    let x = int(Value::u256(U256::one()))?.shl_checked(255)?;
+    assert!(x
+        .into_value()
+        .equals(&Value::u256(U256::one().shl_u8(255)))?);
+
+    Ok(())
+}
@@ -12,11 +12,13 @@ use move_core_types::{
         AbstractMemorySize, GasAlgebra, GasCarrier, CONST_SIZE, MIN_EXISTS_DATA_SIZE,
         REFERENCE_SIZE, STRUCT_SIZE,
     },
-    value::{MoveStructLayout, MoveTypeLayout},
+    u256::{self, U256},
+    value::{MoveStructLayout, MoveTypeLayout, MOVE_ENUM_NAME, MOVE_ENUM_VARIANT},
     vm_status::{sub_status::NFE_VECTOR_ERROR_BASE, StatusCode},
 };
 use std::{
     cell::RefCell,
+    convert::TryFrom,
     fmt::{self, Debug, Display},
     iter,
     mem::size_of,
@@ -40,8 +42,11 @@ enum ValueImpl {
     Invalid,
 
     U8(u8),
+    U16(u16),
+    U32(u32),
     U64(u64),
     U128(u128),
+    U256(U256),
     Bool(bool),
     Address(AccountAddress),
 
@@ -66,8 +71,11 @@ enum Container {
     Vec(Rc<RefCell<Vec<ValueImpl>>>),
     Struct(Rc<RefCell<Vec<ValueImpl>>>),
     VecU8(Rc<RefCell<Vec<u8>>>),
+    VecU16(Rc<RefCell<Vec<u16>>>),
+    VecU32(Rc<RefCell<Vec<u32>>>),
     VecU64(Rc<RefCell<Vec<u64>>>),
     VecU128(Rc<RefCell<Vec<u128>>>),
+    VecU256(Rc<RefCell<Vec<U256>>>),
     VecBool(Rc<RefCell<Vec<bool>>>),
     VecAddress(Rc<RefCell<Vec<AccountAddress>>>),
 }
@@ -165,8 +173,11 @@ pub struct Locals(Rc<RefCell<Vec<ValueImpl>>>);
 #[derive(Debug)]
 pub enum IntegerValue {
     U8(u8),
+    U16(u16),
+    U32(u32),
     U64(u64),
     U128(u128),
+    U256(U256),
 }
 
 /// A Move struct.
@@ -217,13 +228,56 @@ pub enum GlobalValueEffect<T> {
  *
  **************************************************************************************/
 
+impl ValueImpl {
+    fn check_vector_lengths(&self, max_vector_length: u64) -> PartialVMResult<()> {
+        match self {
+            ValueImpl::Container(c) => c.check_vector_lengths(max_vector_length),
+            _ => Ok(()),
+        }
+    }
+}
+
 impl Container {
+    fn check_vector_lengths(&self, max_vector_length: u64) -> PartialVMResult<()> {
+        match self {
+            Self::Struct(r) => {
+                for v in r.borrow().iter() {
+                    v.check_vector_lengths(max_vector_length)?;
+                }
+            }
+            Self::Vec(r) => {
+                for v in r.borrow().iter() {
+                    v.check_vector_lengths(max_vector_length)?;
+                }
+            }
+            Self::Locals(_) => (),
+            Self::VecU8(_)
+            | Self::VecU16(_)
+            | Self::VecU32(_)
+            | Self::VecU64(_)
+            | Self::VecU128(_)
+            | Self::VecU256(_)
+            | Self::VecBool(_)
+            | Self::VecAddress(_) => (),
+        }
+        match self {
+            Self::Locals(_) | Self::Struct(_) => Ok(()),
+            _ if self.len() as u64 > max_vector_length => Err(PartialVMError::new(
+                StatusCode::VM_MAX_VECTOR_LENGTH_REACHED,
+            )),
+            _ => Ok(()),
+        }
+    }
+
     fn len(&self) -> usize {
         match self {
             Self::Locals(r) | Self::Struct(r) | Self::Vec(r) => r.borrow().len(),
             Self::VecU8(r) => r.borrow().len(),
+            Self::VecU16(r) => r.borrow().len(),
+            Self::VecU32(r) => r.borrow().len(),
             Self::VecU64(r) => r.borrow().len(),
             Self::VecU128(r) => r.borrow().len(),
+            Self::VecU256(r) => r.borrow().len(),
             Self::VecBool(r) => r.borrow().len(),
             Self::VecAddress(r) => r.borrow().len(),
         }
@@ -233,8 +287,11 @@ impl Container {
         match self {
             Self::Locals(r) | Self::Struct(r) | Self::Vec(r) => Rc::strong_count(r),
             Self::VecU8(r) => Rc::strong_count(r),
+            Self::VecU16(r) => Rc::strong_count(r),
+            Self::VecU32(r) => Rc::strong_count(r),
             Self::VecU64(r) => Rc::strong_count(r),
             Self::VecU128(r) => Rc::strong_count(r),
+            Self::VecU256(r) => Rc::strong_count(r),
             Self::VecBool(r) => Rc::strong_count(r),
             Self::VecAddress(r) => Rc::strong_count(r),
         }
@@ -305,8 +362,11 @@ macro_rules! impl_vm_value_ref {
 }
 
 impl_vm_value_ref!(u8, U8);
+impl_vm_value_ref!(u16, U16);
+impl_vm_value_ref!(u32, U32);
 impl_vm_value_ref!(u64, U64);
 impl_vm_value_ref!(u128, U128);
+impl_vm_value_ref!(U256, U256);
 impl_vm_value_ref!(bool, Bool);
 impl_vm_value_ref!(AccountAddress, Address);
 
@@ -336,8 +396,11 @@ impl ValueImpl {
             Invalid => Invalid,
 
             U8(x) => U8(*x),
+            U16(x) => U16(*x),
+            U32(x) => U32(*x),
             U64(x) => U64(*x),
             U128(x) => U128(*x),
+            U256(x) => U256(*x),
             Bool(x) => Bool(*x),
             Address(x) => Address(*x),
 
@@ -367,8 +430,11 @@ impl Container {
             Self::Struct(r) => Self::Struct(copy_rc_ref_vec_val(r)?),
 
             Self::VecU8(r) => Self::VecU8(Rc::new(RefCell::new(r.borrow().clone()))),
+            Self::VecU16(r) => Self::VecU16(Rc::new(RefCell::new(r.borrow().clone()))),
+            Self::VecU32(r) => Self::VecU32(Rc::new(RefCell::new(r.borrow().clone()))),
             Self::VecU64(r) => Self::VecU64(Rc::new(RefCell::new(r.borrow().clone()))),
             Self::VecU128(r) => Self::VecU128(Rc::new(RefCell::new(r.borrow().clone()))),
+            Self::VecU256(r) => Self::VecU256(Rc::new(RefCell::new(r.borrow().clone()))),
             Self::VecBool(r) => Self::VecBool(Rc::new(RefCell::new(r.borrow().clone()))),
             Self::VecAddress(r) => Self::VecAddress(Rc::new(RefCell::new(r.borrow().clone()))),
 
@@ -386,8 +452,11 @@ impl Container {
             Self::Vec(r) => Self::Vec(Rc::clone(r)),
             Self::Struct(r) => Self::Struct(Rc::clone(r)),
             Self::VecU8(r) => Self::VecU8(Rc::clone(r)),
+            Self::VecU16(r) => Self::VecU16(Rc::clone(r)),
+            Self::VecU32(r) => Self::VecU32(Rc::clone(r)),
             Self::VecU64(r) => Self::VecU64(Rc::clone(r)),
             Self::VecU128(r) => Self::VecU128(Rc::clone(r)),
+            Self::VecU256(r) => Self::VecU256(Rc::clone(r)),
             Self::VecBool(r) => Self::VecBool(Rc::clone(r)),
             Self::VecAddress(r) => Self::VecAddress(Rc::clone(r)),
             Self::Locals(r) => Self::Locals(Rc::clone(r)),
@@ -445,8 +514,11 @@ impl ValueImpl {
 
         let res = match (self, other) {
             (U8(l), U8(r)) => l == r,
+            (U16(l), U16(r)) => l == r,
+            (U32(l), U32(r)) => l == r,
             (U64(l), U64(r)) => l == r,
             (U128(l), U128(r)) => l == r,
+            (U256(l), U256(r)) => l == r,
             (Bool(l), Bool(r)) => l == r,
             (Address(l), Address(r)) => l == r,
 
@@ -485,8 +557,11 @@ impl Container {
                 true
             }
             (VecU8(l), VecU8(r)) => l.borrow().eq(&*r.borrow()),
+            (VecU16(l), VecU16(r)) => l.borrow().eq(&*r.borrow()),
+            (VecU32(l), VecU32(r)) => l.borrow().eq(&*r.borrow()),
             (VecU64(l), VecU64(r)) => l.borrow().eq(&*r.borrow()),
             (VecU128(l), VecU128(r)) => l.borrow().eq(&*r.borrow()),
+            (VecU256(l), VecU256(r)) => l.borrow().eq(&*r.borrow()),
             (VecBool(l), VecBool(r)) => l.borrow().eq(&*r.borrow()),
             (VecAddress(l), VecAddress(r)) => l.borrow().eq(&*r.borrow()),
 
@@ -494,8 +569,11 @@ impl Container {
             | (Vec(_), _)
             | (Struct(_), _)
             | (VecU8(_), _)
+            | (VecU16(_), _)
+            | (VecU32(_), _)
             | (VecU64(_), _)
             | (VecU128(_), _)
+            | (VecU256(_), _)
             | (VecBool(_), _)
             | (VecAddress(_), _) => {
                 return Err(
@@ -537,8 +615,11 @@ impl IndexedRef {
             | (Locals(r1), Locals(r2)) => r1.borrow()[self.idx].equals(&r2.borrow()[other.idx])?,
 
             (VecU8(r1), VecU8(r2)) => r1.borrow()[self.idx] == r2.borrow()[other.idx],
+            (VecU16(r1), VecU16(r2)) => r1.borrow()[self.idx] == r2.borrow()[other.idx],
+            (VecU32(r1), VecU32(r2)) => r1.borrow()[self.idx] == r2.borrow()[other.idx],
             (VecU64(r1), VecU64(r2)) => r1.borrow()[self.idx] == r2.borrow()[other.idx],
             (VecU128(r1), VecU128(r2)) => r1.borrow()[self.idx] == r2.borrow()[other.idx],
+            (VecU256(r1), VecU256(r2)) => r1.borrow()[self.idx] == r2.borrow()[other.idx],
             (VecBool(r1), VecBool(r2)) => r1.borrow()[self.idx] == r2.borrow()[other.idx],
             (VecAddress(r1), VecAddress(r2)) => r1.borrow()[self.idx] == r2.borrow()[other.idx],
 
@@ -550,6 +631,20 @@ impl IndexedRef {
                 r1.borrow()[self.idx] == *r2.borrow()[other.idx].as_value_ref::<u8>()?
             }
 
+            (Locals(r1), VecU16(r2)) | (Struct(r1), VecU16(r2)) => {
+                *r1.borrow()[self.idx].as_value_ref::<u16>()? == r2.borrow()[other.idx]
+            }
+            (VecU16(r1), Locals(r2)) | (VecU16(r1), Struct(r2)) => {
+                r1.borrow()[self.idx] == *r2.borrow()[other.idx].as_value_ref::<u16>()?
+            }
+
+            (Locals(r1), VecU32(r2)) | (Struct(r1), VecU32(r2)) => {
+                *r1.borrow()[self.idx].as_value_ref::<u32>()? == r2.borrow()[other.idx]
+            }
+            (VecU32(r1), Locals(r2)) | (VecU32(r1), Struct(r2)) => {
+                r1.borrow()[self.idx] == *r2.borrow()[other.idx].as_value_ref::<u32>()?
+            }
+
             (Locals(r1), VecU64(r2)) | (Struct(r1), VecU64(r2)) => {
                 *r1.borrow()[self.idx].as_value_ref::<u64>()? == r2.borrow()[other.idx]
             }
@@ -564,6 +659,13 @@ impl IndexedRef {
                 r1.borrow()[self.idx] == *r2.borrow()[other.idx].as_value_ref::<u128>()?
             }
 
+            (Locals(r1), VecU256(r2)) | (Struct(r1), VecU256(r2)) => {
+                *r1.borrow()[self.idx].as_value_ref::<U256>()? == r2.borrow()[other.idx]
+            }
+            (VecU256(r1), Locals(r2)) | (VecU256(r1), Struct(r2)) => {
+                r1.borrow()[self.idx] == *r2.borrow()[other.idx].as_value_ref::<U256>()?
+            }
+
             (Locals(r1), VecBool(r2)) | (Struct(r1), VecBool(r2)) => {
                 *r1.borrow()[self.idx].as_value_ref::<bool>()? == r2.borrow()[other.idx]
             }
@@ -581,8 +683,11 @@ impl IndexedRef {
             // All other combinations are illegal.
             (Vec(_), _)
             | (VecU8(_), _)
+            | (VecU16(_), _)
+            | (VecU32(_), _)
             | (VecU64(_), _)
             | (VecU128(_), _)
+            | (VecU256(_), _)
             | (VecBool(_), _)
             | (VecAddress(_), _) => {
                 return Err(PartialVMError::new(StatusCode::INTERNAL_TYPE_ERROR)
@@ -620,8 +725,11 @@ impl IndexedRef {
         let res = match &*self.container_ref.container() {
             Locals(r) | Vec(r) | Struct(r) => r.borrow()[self.idx].copy_value()?,
             VecU8(r) => ValueImpl::U8(r.borrow()[self.idx]),
+            VecU16(r) => ValueImpl::U16(r.borrow()[self.idx]),
+            VecU32(r) => ValueImpl::U32(r.borrow()[self.idx]),
             VecU64(r) => ValueImpl::U64(r.borrow()[self.idx]),
             VecU128(r) => ValueImpl::U128(r.borrow()[self.idx]),
+            VecU256(r) => ValueImpl::U256(r.borrow()[self.idx]),
             VecBool(r) => ValueImpl::Bool(r.borrow()[self.idx]),
             VecAddress(r) => ValueImpl::Address(r.borrow()[self.idx]),
         };
@@ -684,8 +792,11 @@ impl ContainerRef {
                     Container::Struct(r) => assign!(r, Struct),
                     Container::Vec(r) => assign!(r, Vec),
                     Container::VecU8(r) => assign!(r, VecU8),
+                    Container::VecU16(r) => assign!(r, VecU16),
+                    Container::VecU32(r) => assign!(r, VecU32),
                     Container::VecU64(r) => assign!(r, VecU64),
                     Container::VecU128(r) => assign!(r, VecU128),
+                    Container::VecU256(r) => assign!(r, VecU256),
                     Container::VecBool(r) => assign!(r, VecBool),
                     Container::VecAddress(r) => assign!(r, VecAddress),
                     Container::Locals(_) => {
@@ -735,14 +846,20 @@ impl IndexedRef {
                 v[self.idx] = x.0;
             }
             (Container::VecU8(r), ValueImpl::U8(x)) => r.borrow_mut()[self.idx] = *x,
+            (Container::VecU16(r), ValueImpl::U16(x)) => r.borrow_mut()[self.idx] = *x,
+            (Container::VecU32(r), ValueImpl::U32(x)) => r.borrow_mut()[self.idx] = *x,
             (Container::VecU64(r), ValueImpl::U64(x)) => r.borrow_mut()[self.idx] = *x,
             (Container::VecU128(r), ValueImpl::U128(x)) => r.borrow_mut()[self.idx] = *x,
+            (Container::VecU256(r), ValueImpl::U256(x)) => r.borrow_mut()[self.idx] = *x,
             (Container::VecBool(r), ValueImpl::Bool(x)) => r.borrow_mut()[self.idx] = *x,
             (Container::VecAddress(r), ValueImpl::Address(x)) => r.borrow_mut()[self.idx] = *x,
 
             (Container::VecU8(_), _)
+            | (Container::VecU16(_), _)
+            | (Container::VecU32(_), _)
             | (Container::VecU64(_), _)
             | (Container::VecU128(_), _)
+            | (Container::VecU256(_), _)
             | (Container::VecBool(_), _)
             | (Container::VecAddress(_), _) => {
                 return Err(
@@ -819,8 +936,11 @@ impl ContainerRef {
             }
 
             Container::VecU8(_)
+            | Container::VecU16(_)
+            | Container::VecU32(_)
             | Container::VecU64(_)
             | Container::VecU128(_)
+            | Container::VecU256(_)
             | Container::VecAddress(_)
             | Container::VecBool(_) => ValueImpl::IndexedRef(IndexedRef {
                 idx,
@@ -836,6 +956,19 @@ impl StructRef {
     pub fn borrow_field(&self, idx: usize) -> PartialVMResult<Value> {
         Ok(Value(self.0.borrow_elem(idx)?))
     }
+
+    /// Test whether the referenced value of an enum is of the variant `tag`.
+    pub fn test_variant(&self, tag: u16) -> PartialVMResult<Value> {
+        if let Container::Struct(r) = self.0.container() {
+            if let Some(ValueImpl::U16(actual)) = r.borrow().first() {
+                return Ok(Value::bool(*actual == tag));
+            }
+        }
+        Err(
+            PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
+                .with_message("enum value without a variant tag".to_string()),
+        )
+    }
 }
 
 impl Locals {
@@ -862,8 +995,11 @@ impl Locals {
             )))),
 
             ValueImpl::U8(_)
+            | ValueImpl::U16(_)
+            | ValueImpl::U32(_)
             | ValueImpl::U64(_)
             | ValueImpl::U128(_)
+            | ValueImpl::U256(_)
             | ValueImpl::Bool(_)
             | ValueImpl::Address(_) => Ok(Value(ValueImpl::IndexedRef(IndexedRef {
                 container_ref: ContainerRef::Local(Container::Locals(Rc::clone(&self.0))),
@@ -964,6 +1100,14 @@ impl Value {
         Self(ValueImpl::U8(x))
     }
 
+    pub fn u16(x: u16) -> Self {
+        Self(ValueImpl::U16(x))
+    }
+
+    pub fn u32(x: u32) -> Self {
+        Self(ValueImpl::U32(x))
+    }
+
     pub fn u64(x: u64) -> Self {
         Self(ValueImpl::U64(x))
     }
@@ -972,6 +1116,10 @@ impl Value {
         Self(ValueImpl::U128(x))
     }
 
+    pub fn u256(x: U256) -> Self {
+        Self(ValueImpl::U256(x))
+    }
+
     pub fn bool(x: bool) -> Self {
         Self(ValueImpl::Bool(x))
     }
@@ -1005,6 +1153,18 @@ impl Value {
         ))))
     }
 
+    pub fn vector_u16(it: impl IntoIterator<Item = u16>) -> Self {
+        Self(ValueImpl::Container(Container::VecU16(Rc::new(
+            RefCell::new(it.into_iter().collect()),
+        ))))
+    }
+
+    pub fn vector_u32(it: impl IntoIterator<Item = u32>) -> Self {
+        Self(ValueImpl::Container(Container::VecU32(Rc::new(
+            RefCell::new(it.into_iter().collect()),
+        ))))
+    }
+
     pub fn vector_u64(it: impl IntoIterator<Item = u64>) -> Self {
         Self(ValueImpl::Container(Container::VecU64(Rc::new(
             RefCell::new(it.into_iter().collect()),
@@ -1017,6 +1177,12 @@ impl Value {
         ))))
     }
 
+    pub fn vector_u256(it: impl IntoIterator<Item = U256>) -> Self {
+        Self(ValueImpl::Container(Container::VecU256(Rc::new(
+            RefCell::new(it.into_iter().collect()),
+        ))))
+    }
+
     pub fn vector_bool(it: impl IntoIterator<Item = bool>) -> Self {
         Self(ValueImpl::Container(Container::VecBool(Rc::new(
             RefCell::new(it.into_iter().collect()),
@@ -1069,8 +1235,11 @@ macro_rules! impl_vm_value_cast {
 }
 
 impl_vm_value_cast!(u8, U8);
+impl_vm_value_cast!(u16, U16);
+impl_vm_value_cast!(u32, U32);
 impl_vm_value_cast!(u64, U64);
 impl_vm_value_cast!(u128, U128);
+impl_vm_value_cast!(U256, U256);
 impl_vm_value_cast!(bool, Bool);
 impl_vm_value_cast!(AccountAddress, Address);
 impl_vm_value_cast!(ContainerRef, ContainerRef);
@@ -1080,8 +1249,11 @@ impl VMValueCast<IntegerValue> for Value {
     fn cast(self) -> PartialVMResult<IntegerValue> {
         match self.0 {
             ValueImpl::U8(x) => Ok(IntegerValue::U8(x)),
+            ValueImpl::U16(x) => Ok(IntegerValue::U16(x)),
+            ValueImpl::U32(x) => Ok(IntegerValue::U32(x)),
             ValueImpl::U64(x) => Ok(IntegerValue::U64(x)),
             ValueImpl::U128(x) => Ok(IntegerValue::U128(x)),
+            ValueImpl::U256(x) => Ok(IntegerValue::U256(x)),
             v => Err(PartialVMError::new(StatusCode::INTERNAL_TYPE_ERROR)
                 .with_message(format!("cannot cast {:?} to integer", v,))),
         }
@@ -1146,8 +1318,11 @@ impl VMValueCast<Vec<Value>> for Value {
             ValueImpl::Address(_)
             | ValueImpl::Bool(_)
             | ValueImpl::U8(_)
+            | ValueImpl::U16(_)
+            | ValueImpl::U32(_)
             | ValueImpl::U64(_)
-            | ValueImpl::U128(_) => Err(PartialVMError::new(StatusCode::INTERNAL_TYPE_ERROR)
+            | ValueImpl::U128(_)
+            | ValueImpl::U256(_) => Err(PartialVMError::new(StatusCode::INTERNAL_TYPE_ERROR)
                 .with_message(
                     "cannot cast a specialized vector into a non-specialized one".to_string(),
                 )),
@@ -1210,6 +1385,26 @@ impl VMValueCast<u8> for IntegerValue {
     }
 }
 
+impl VMValueCast<u16> for IntegerValue {
+    fn cast(self) -> PartialVMResult<u16> {
+        match self {
+            Self::U16(x) => Ok(x),
+            v => Err(PartialVMError::new(StatusCode::INTERNAL_TYPE_ERROR)
+                .with_message(format!("cannot cast {:?} to u16", v,))),
+        }
+    }
+}
+
+impl VMValueCast<u32> for IntegerValue {
+    fn cast(self) -> PartialVMResult<u32> {
+        match self {
+            Self::U32(x) => Ok(x),
+            v => Err(PartialVMError::new(StatusCode::INTERNAL_TYPE_ERROR)
+                .with_message(format!("cannot cast {:?} to u32", v,))),
+        }
+    }
+}
+
 impl VMValueCast<u64> for IntegerValue {
     fn cast(self) -> PartialVMResult<u64> {
         match self {
@@ -1230,6 +1425,16 @@ impl VMValueCast<u128> for IntegerValue {
     }
 }
 
+impl VMValueCast<U256> for IntegerValue {
+    fn cast(self) -> PartialVMResult<U256> {
+        match self {
+            Self::U256(x) => Ok(x),
+            v => Err(PartialVMError::new(StatusCode::INTERNAL_TYPE_ERROR)
+                .with_message(format!("cannot cast {:?} to u256", v,))),
+        }
+    }
+}
+
 impl IntegerValue {
     pub fn value_as<T>(self) -> PartialVMResult<T>
     where
@@ -1251,8 +1456,11 @@ impl IntegerValue {
         use IntegerValue::*;
         let res = match (self, other) {
             (U8(l), U8(r)) => u8::checked_add(l, r).map(IntegerValue::U8),
+            (U16(l), U16(r)) => u16::checked_add(l, r).map(IntegerValue::U16),
+            (U32(l), U32(r)) => u32::checked_add(l, r).map(IntegerValue::U32),
             (U64(l), U64(r)) => u64::checked_add(l, r).map(IntegerValue::U64),
             (U128(l), U128(r)) => u128::checked_add(l, r).map(IntegerValue::U128),
+            (U256(l), U256(r)) => u256::U256::checked_add(l, r).map(IntegerValue::U256),
             (l, r) => {
                 let msg = format!("Cannot add {:?} and {:?}", l, r);
                 return Err(PartialVMError::new(StatusCode::INTERNAL_TYPE_ERROR).with_message(msg));
@@ -1265,8 +1473,11 @@ impl IntegerValue {
         use IntegerValue::*;
         let res = match (self, other) {
             (U8(l), U8(r)) => u8::checked_sub(l, r).map(IntegerValue::U8),
+            (U16(l), U16(r)) => u16::checked_sub(l, r).map(IntegerValue::U16),
+            (U32(l), U32(r)) => u32::checked_sub(l, r).map(IntegerValue::U32),
             (U64(l), U64(r)) => u64::checked_sub(l, r).map(IntegerValue::U64),
             (U128(l), U128(r)) => u128::checked_sub(l, r).map(IntegerValue::U128),
+            (U256(l), U256(r)) => u256::U256::checked_sub(l, r).map(IntegerValue::U256),
             (l, r) => {
                 let msg = format!("Cannot sub {:?} from {:?}", r, l);
                 return Err(PartialVMError::new(StatusCode::INTERNAL_TYPE_ERROR).with_message(msg));
@@ -1279,8 +1490,11 @@ impl IntegerValue {
         use IntegerValue::*;
         let res = match (self, other) {
             (U8(l), U8(r)) => u8::checked_mul(l, r).map(IntegerValue::U8),
+            (U16(l), U16(r)) => u16::checked_mul(l, r).map(IntegerValue::U16),
+            (U32(l), U32(r)) => u32::checked_mul(l, r).map(IntegerValue::U32),
             (U64(l), U64(r)) => u64::checked_mul(l, r).map(IntegerValue::U64),
             (U128(l), U128(r)) => u128::checked_mul(l, r).map(IntegerValue::U128),
+            (U256(l), U256(r)) => u256::U256::checked_mul(l, r).map(IntegerValue::U256),
             (l, r) => {
                 let msg = format!("Cannot mul {:?} and {:?}", l, r);
                 return Err(PartialVMError::new(StatusCode::INTERNAL_TYPE_ERROR).with_message(msg));
@@ -1293,8 +1507,11 @@ impl IntegerValue {
         use IntegerValue::*;
         let res = match (self, other) {
             (U8(l), U8(r)) => u8::checked_div(l, r).map(IntegerValue::U8),
+            (U16(l), U16(r)) => u16::checked_div(l, r).map(IntegerValue::U16),
+            (U32(l), U32(r)) => u32::checked_div(l, r).map(IntegerValue::U32),
             (U64(l), U64(r)) => u64::checked_div(l, r).map(IntegerValue::U64),
             (U128(l), U128(r)) => u128::checked_div(l, r).map(IntegerValue::U128),
+            (U256(l), U256(r)) => u256::U256::checked_div(l, r).map(IntegerValue::U256),
             (l, r) => {
                 let msg = format!("Cannot div {:?} by {:?}", l, r);
                 return Err(PartialVMError::new(StatusCode::INTERNAL_TYPE_ERROR).with_message(msg));
@@ -1307,8 +1524,11 @@ impl IntegerValue {
         use IntegerValue::*;
         let res = match (self, other) {
             (U8(l), U8(r)) => u8::checked_rem(l, r).map(IntegerValue::U8),
+            (U16(l), U16(r)) => u16::checked_rem(l, r).map(IntegerValue::U16),
+            (U32(l), U32(r)) => u32::checked_rem(l, r).map(IntegerValue::U32),
             (U64(l), U64(r)) => u64::checked_rem(l, r).map(IntegerValue::U64),
             (U128(l), U128(r)) => u128::checked_rem(l, r).map(IntegerValue::U128),
+            (U256(l), U256(r)) => u256::U256::checked_rem(l, r).map(IntegerValue::U256),
             (l, r) => {
                 let msg = format!("Cannot rem {:?} by {:?}", l, r);
                 return Err(PartialVMError::new(StatusCode::INTERNAL_TYPE_ERROR).with_message(msg));
@@ -1321,8 +1541,11 @@ impl IntegerValue {
         use IntegerValue::*;
         Ok(match (self, other) {
             (U8(l), U8(r)) => IntegerValue::U8(l | r),
+            (U16(l), U16(r)) => IntegerValue::U16(l | r),
+            (U32(l), U32(r)) => IntegerValue::U32(l | r),
             (U64(l), U64(r)) => IntegerValue::U64(l | r),
             (U128(l), U128(r)) => IntegerValue::U128(l | r),
+            (U256(l), U256(r)) => IntegerValue::U256(l | r),
             (l, r) => {
                 let msg = format!("Cannot bit_or {:?} and {:?}", l, r);
                 return Err(PartialVMError::new(StatusCode::INTERNAL_TYPE_ERROR).with_message(msg));
@@ -1334,8 +1557,11 @@ impl IntegerValue {
         use IntegerValue::*;
         Ok(match (self, other) {
             (U8(l), U8(r)) => IntegerValue::U8(l & r),
+            (U16(l), U16(r)) => IntegerValue::U16(l & r),
+            (U32(l), U32(r)) => IntegerValue::U32(l & r),
             (U64(l), U64(r)) => IntegerValue::U64(l & r),
             (U128(l), U128(r)) => IntegerValue::U128(l & r),
+            (U256(l), U256(r)) => IntegerValue::U256(l & r),
             (l, r) => {
                 let msg = format!("Cannot bit_and {:?} and {:?}", l, r);
                 return Err(PartialVMError::new(StatusCode::INTERNAL_TYPE_ERROR).with_message(msg));
@@ -1347,8 +1573,11 @@ impl IntegerValue {
         use IntegerValue::*;
         Ok(match (self, other) {
             (U8(l), U8(r)) => IntegerValue::U8(l ^ r),
+            (U16(l), U16(r)) => IntegerValue::U16(l ^ r),
+            (U32(l), U32(r)) => IntegerValue::U32(l ^ r),
             (U64(l), U64(r)) => IntegerValue::U64(l ^ r),
             (U128(l), U128(r)) => IntegerValue::U128(l ^ r),
+            (U256(l), U256(r)) => IntegerValue::U256(l ^ r),
             (l, r) => {
                 let msg = format!("Cannot bit_xor {:?} and {:?}", l, r);
                 return Err(PartialVMError::new(StatusCode::INTERNAL_TYPE_ERROR).with_message(msg));
@@ -1366,6 +1595,18 @@ impl IntegerValue {
                 }
                 IntegerValue::U8(x << n_bits)
             }
+            U16(x) => {
+                if n_bits >= 16 {
+                    return Err(PartialVMError::new(StatusCode::ARITHMETIC_ERROR));
+                }
+                IntegerValue::U16(x << n_bits)
+            }
+            U32(x) => {
+                if n_bits >= 32 {
+                    return Err(PartialVMError::new(StatusCode::ARITHMETIC_ERROR));
+                }
+                IntegerValue::U32(x << n_bits)
+            }
             U64(x) => {
                 if n_bits >= 64 {
                     return Err(PartialVMError::new(StatusCode::ARITHMETIC_ERROR));
@@ -1378,6 +1619,7 @@ impl IntegerValue {
                 }
                 IntegerValue::U128(x << n_bits)
             }
+            U256(x) => IntegerValue::U256(x << n_bits),
         })
     }
 
@@ -1391,6 +1633,18 @@ impl IntegerValue {
                 }
                 IntegerValue::U8(x >> n_bits)
             }
+            U16(x) => {
+                if n_bits >= 16 {
+                    return Err(PartialVMError::new(StatusCode::ARITHMETIC_ERROR));
+                }
+                IntegerValue::U16(x >> n_bits)
+            }
+            U32(x) => {
+                if n_bits >= 32 {
+                    return Err(PartialVMError::new(StatusCode::ARITHMETIC_ERROR));
+                }
+                IntegerValue::U32(x >> n_bits)
+            }
             U64(x) => {
                 if n_bits >= 64 {
                     return Err(PartialVMError::new(StatusCode::ARITHMETIC_ERROR));
@@ -1403,6 +1657,7 @@ impl IntegerValue {
                 }
                 IntegerValue::U128(x >> n_bits)
             }
+            U256(x) => IntegerValue::U256(x >> n_bits),
         })
     }
 
@@ -1411,8 +1666,11 @@ impl IntegerValue {
 
         Ok(match (self, other) {
             (U8(l), U8(r)) => l < r,
+            (U16(l), U16(r)) => l < r,
+            (U32(l), U32(r)) => l < r,
             (U64(l), U64(r)) => l < r,
             (U128(l), U128(r)) => l < r,
+            (U256(l), U256(r)) => l < r,
             (l, r) => {
                 let msg = format!(
                     "Cannot compare {:?} and {:?}: incompatible integer types",
@@ -1428,8 +1686,11 @@ impl IntegerValue {
 
         Ok(match (self, other) {
             (U8(l), U8(r)) => l <= r,
+            (U16(l), U16(r)) => l <= r,
+            (U32(l), U32(r)) => l <= r,
             (U64(l), U64(r)) => l <= r,
             (U128(l), U128(r)) => l <= r,
+            (U256(l), U256(r)) => l <= r,
             (l, r) => {
                 let msg = format!(
                     "Cannot compare {:?} and {:?}: incompatible integer types",
@@ -1445,8 +1706,11 @@ impl IntegerValue {
 
         Ok(match (self, other) {
             (U8(l), U8(r)) => l > r,
+            (U16(l), U16(r)) => l > r,
+            (U32(l), U32(r)) => l > r,
             (U64(l), U64(r)) => l > r,
             (U128(l), U128(r)) => l > r,
+            (U256(l), U256(r)) => l > r,
             (l, r) => {
                 let msg = format!(
                     "Cannot compare {:?} and {:?}: incompatible integer types",
@@ -1462,8 +1726,11 @@ impl IntegerValue {
 
         Ok(match (self, other) {
             (U8(l), U8(r)) => l >= r,
+            (U16(l), U16(r)) => l >= r,
+            (U32(l), U32(r)) => l >= r,
             (U64(l), U64(r)) => l >= r,
             (U128(l), U128(r)) => l >= r,
+            (U256(l), U256(r)) => l >= r,
             (l, r) => {
                 let msg = format!(
                     "Cannot compare {:?} and {:?}: incompatible integer types",
@@ -1479,62 +1746,84 @@ impl IntegerValue {
 
         match self {
             U8(x) => Value::u8(x),
+            U16(x) => Value::u16(x),
+            U32(x) => Value::u32(x),
             U64(x) => Value::u64(x),
             U128(x) => Value::u128(x),
+            U256(x) => Value::u256(x),
         }
     }
 }
 
 impl IntegerValue {
     pub fn cast_u8(self) -> PartialVMResult<u8> {
-        use IntegerValue::*;
+        self.cast_checked("u8")
+    }
 
-        match self {
-            U8(x) => Ok(x),
-            U64(x) => {
-                if x > (std::u8::MAX as u64) {
-                    Err(PartialVMError::new(StatusCode::ARITHMETIC_ERROR)
-                        .with_message(format!("Cannot cast u64({}) to u8", x)))
-                } else {
-                    Ok(x as u8)
-                }
-            }
-            U128(x) => {
-                if x > (std::u8::MAX as u128) {
-                    Err(PartialVMError::new(StatusCode::ARITHMETIC_ERROR)
-                        .with_message(format!("Cannot cast u128({}) to u8", x)))
-                } else {
-                    Ok(x as u8)
-                }
-            }
-        }
+    pub fn cast_u16(self) -> PartialVMResult<u16> {
+        self.cast_checked("u16")
+    }
+
+    pub fn cast_u32(self) -> PartialVMResult<u32> {
+        self.cast_checked("u32")
     }
 
     pub fn cast_u64(self) -> PartialVMResult<u64> {
+        self.cast_checked("u64")
+    }
+
+    pub fn cast_u128(self) -> PartialVMResult<u128> {
+        self.cast_checked("u128")
+    }
+
+    pub fn cast_u256(self) -> PartialVMResult<U256> {
         use IntegerValue::*;
 
+        Ok(match self {
+            U8(x) => u256::U256::from(x),
+            U16(x) => u256::U256::from(x),
+            U32(x) => u256::U256::from(x),
+            U64(x) => u256::U256::from(x),
+            U128(x) => u256::U256::from(x),
+            U256(x) => x,
+        })
+    }
+
+    /// Casts the value to the integer type `T`, named `to` in error messages, failing with
+    /// `ARITHMETIC_ERROR` if `T` cannot represent the value. Each source type is converted
+    /// directly, so only casts from a u256 involve 256-bit arithmetic.
+    fn cast_checked<T>(self, to: &str) -> PartialVMResult<T>
+    where
+        T: TryFrom<u8> + TryFrom<u16> + TryFrom<u32> + TryFrom<u64> + TryFrom<u128> + TryFrom<U256>,
+    {
+        use IntegerValue::*;
+
+        let from = self.type_name();
+        let err = |x: &dyn fmt::Display| {
+            PartialVMError::new(StatusCode::ARITHMETIC_ERROR)
+                .with_message(format!("Cannot cast {}({}) to {}", from, x, to))
+        };
         match self {
-            U8(x) => Ok(x as u64),
-            U64(x) => Ok(x),
-            U128(x) => {
-                if x > (std::u64::MAX as u128) {
-                    Err(PartialVMError::new(StatusCode::ARITHMETIC_ERROR)
-                        .with_message(format!("Cannot cast u128({}) to u64", x)))
-                } else {
-                    Ok(x as u64)
-                }
-            }
+            U8(x) => <T as TryFrom<u8>>::try_from(x).map_err(|_| err(&x)),
+            U16(x) => <T as TryFrom<u16>>::try_from(x).map_err(|_| err(&x)),
+            U32(x) => <T as TryFrom<u32>>::try_from(x).map_err(|_| err(&x)),
+            U64(x) => <T as TryFrom<u64>>::try_from(x).map_err(|_| err(&x)),
+            U128(x) => <T as TryFrom<u128>>::try_from(x).map_err(|_| err(&x)),
+            U256(x) => <T as TryFrom<u256::U256>>::try_from(x).map_err(|_| err(&x)),
         }
     }
 
-    pub fn cast_u128(self) -> PartialVMResult<u128> {
+    fn type_name(&self) -> &'static str {
         use IntegerValue::*;
 
-        Ok(match self {
-            U8(x) => x as u128,
-            U64(x) => x as u128,
-            U128(x) => x,
-        })
+        match self {
+            U8(_) => "u8",
+            U16(_) => "u16",
+            U32(_) => "u32",
+            U64(_) => "u64",
+            U128(_) => "u128",
+            U256(_) => "u256",
+        }
     }
 }
 
@@ -1553,8 +1842,11 @@ pub const VEC_UNPACK_PARITY_MISMATCH: u64 = NFE_VECTOR_ERROR_BASE + 3;
 fn check_elem_layout(ty: &Type, v: &Container) -> PartialVMResult<()> {
     match (ty, v) {
         (Type::U8, Container::VecU8(_))
+        | (Type::U16, Container::VecU16(_))
+        | (Type::U32, Container::VecU32(_))
         | (Type::U64, Container::VecU64(_))
         | (Type::U128, Container::VecU128(_))
+        | (Type::U256, Container::VecU256(_))
         | (Type::Bool, Container::VecBool(_))
         | (Type::Address, Container::VecAddress(_))
         | (Type::Signer, Container::Struct(_)) => Ok(()),
@@ -1571,8 +1863,11 @@ fn check_elem_layout(ty: &Type, v: &Container) -> PartialVMResult<()> {
         ),
 
         (Type::U8, _)
+        | (Type::U16, _)
+        | (Type::U32, _)
         | (Type::U64, _)
         | (Type::U128, _)
+        | (Type::U256, _)
         | (Type::Bool, _)
         | (Type::Address, _)
         | (Type::Signer, _)
@@ -1595,8 +1890,11 @@ impl VectorRef {
 
         let len = match c {
             Container::VecU8(r) => r.borrow().len(),
+            Container::VecU16(r) => r.borrow().len(),
+            Container::VecU32(r) => r.borrow().len(),
             Container::VecU64(r) => r.borrow().len(),
             Container::VecU128(r) => r.borrow().len(),
+            Container::VecU256(r) => r.borrow().len(),
             Container::VecBool(r) => r.borrow().len(),
             Container::VecAddress(r) => r.borrow().len(),
             Container::Vec(r) => r.borrow().len(),
@@ -1611,8 +1909,11 @@ impl VectorRef {
 
         match c {
             Container::VecU8(r) => r.borrow_mut().push(e.value_as()?),
+            Container::VecU16(r) => r.borrow_mut().push(e.value_as()?),
+            Container::VecU32(r) => r.borrow_mut().push(e.value_as()?),
             Container::VecU64(r) => r.borrow_mut().push(e.value_as()?),
             Container::VecU128(r) => r.borrow_mut().push(e.value_as()?),
+            Container::VecU256(r) => r.borrow_mut().push(e.value_as()?),
             Container::VecBool(r) => r.borrow_mut().push(e.value_as()?),
             Container::VecAddress(r) => r.borrow_mut().push(e.value_as()?),
             Container::Vec(r) => r.borrow_mut().push(e.0),
@@ -1658,6 +1959,14 @@ impl VectorRef {
                 Some(x) => Value::u8(x),
                 None => err_pop_empty_vec!(),
             },
+            Container::VecU16(r) => match r.borrow_mut().pop() {
+                Some(x) => Value::u16(x),
+                None => err_pop_empty_vec!(),
+            },
+            Container::VecU32(r) => match r.borrow_mut().pop() {
+                Some(x) => Value::u32(x),
+                None => err_pop_empty_vec!(),
+            },
             Container::VecU64(r) => match r.borrow_mut().pop() {
                 Some(x) => Value::u64(x),
                 None => err_pop_empty_vec!(),
@@ -1666,6 +1975,10 @@ impl VectorRef {
                 Some(x) => Value::u128(x),
                 None => err_pop_empty_vec!(),
             },
+            Container::VecU256(r) => match r.borrow_mut().pop() {
+                Some(x) => Value::u256(x),
+                None => err_pop_empty_vec!(),
+            },
             Container::VecBool(r) => match r.borrow_mut().pop() {
                 Some(x) => Value::bool(x),
                 None => err_pop_empty_vec!(),
@@ -1702,8 +2015,11 @@ impl VectorRef {
 
         match c {
             Container::VecU8(r) => swap!(r),
+            Container::VecU16(r) => swap!(r),
+            Container::VecU32(r) => swap!(r),
             Container::VecU64(r) => swap!(r),
             Container::VecU128(r) => swap!(r),
+            Container::VecU256(r) => swap!(r),
             Container::VecBool(r) => swap!(r),
             Container::VecAddress(r) => swap!(r),
             Container::Vec(r) => swap!(r),
@@ -1724,6 +2040,18 @@ impl Vector {
                     .map(|v| v.value_as())
                     .collect::<PartialVMResult<Vec<_>>>()?,
             ),
+            Type::U16 => Value::vector_u16(
+                elements
+                    .into_iter()
+                    .map(|v| v.value_as())
+                    .collect::<PartialVMResult<Vec<_>>>()?,
+            ),
+            Type::U32 => Value::vector_u32(
+                elements
+                    .into_iter()
+                    .map(|v| v.value_as())
+                    .collect::<PartialVMResult<Vec<_>>>()?,
+            ),
             Type::U64 => Value::vector_u64(
                 elements
                     .into_iter()
@@ -1736,6 +2064,12 @@ impl Vector {
                     .map(|v| v.value_as())
                     .collect::<PartialVMResult<Vec<_>>>()?,
             ),
+            Type::U256 => Value::vector_u256(
+                elements
+                    .into_iter()
+                    .map(|v| v.value_as())
+                    .collect::<PartialVMResult<Vec<_>>>()?,
+            ),
             Type::Bool => Value::vector_bool(
                 elements
                     .into_iter()
@@ -1777,6 +2111,14 @@ impl Vector {
                 .into_iter()
                 .map(Value::u8)
                 .collect(),
+            Container::VecU16(r) => take_unique_ownership(r)?
+                .into_iter()
+                .map(Value::u16)
+                .collect(),
+            Container::VecU32(r) => take_unique_ownership(r)?
+                .into_iter()
+                .map(Value::u32)
+                .collect(),
             Container::VecU64(r) => take_unique_ownership(r)?
                 .into_iter()
                 .map(Value::u64)
@@ -1785,6 +2127,10 @@ impl Vector {
                 .into_iter()
                 .map(Value::u128)
                 .collect(),
+            Container::VecU256(r) => take_unique_ownership(r)?
+                .into_iter()
+                .map(Value::u256)
+                .collect(),
             Container::VecBool(r) => take_unique_ownership(r)?
                 .into_iter()
                 .map(Value::bool)
@@ -1835,12 +2181,21 @@ impl Container {
         match self {
             Self::Locals(r) | Self::Vec(r) | Self::Struct(r) => Struct::size_impl(&*r.borrow()),
             Self::VecU8(r) => AbstractMemorySize::new((r.borrow().len() * size_of::<u8>()) as u64),
+            Self::VecU16(r) => {
+                AbstractMemorySize::new((r.borrow().len() * size_of::<u16>()) as u64)
+            }
+            Self::VecU32(r) => {
+                AbstractMemorySize::new((r.borrow().len() * size_of::<u32>()) as u64)
+            }
             Self::VecU64(r) => {
                 AbstractMemorySize::new((r.borrow().len() * size_of::<u64>()) as u64)
             }
             Self::VecU128(r) => {
                 AbstractMemorySize::new((r.borrow().len() * size_of::<u128>()) as u64)
             }
+            Self::VecU256(r) => {
+                AbstractMemorySize::new((r.borrow().len() * size_of::<U256>()) as u64)
+            }
             Self::VecBool(r) => {
                 AbstractMemorySize::new((r.borrow().len() * size_of::<bool>()) as u64)
             }
@@ -1868,7 +2223,7 @@ impl ValueImpl {
         use ValueImpl::*;
 
         match self {
-            Invalid | U8(_) | U64(_) | U128(_) | Bool(_) => CONST_SIZE,
+            Invalid | U8(_) | U16(_) | U32(_) | U64(_) | U128(_) | U256(_) | Bool(_) => CONST_SIZE,
             Address(_) => AbstractMemorySize::new(AccountAddress::LENGTH as u64),
             ContainerRef(r) => r.size(),
             IndexedRef(r) => r.size(),
@@ -1939,6 +2294,29 @@ impl Struct {
     pub fn unpack(self) -> PartialVMResult<impl Iterator<Item = Value>> {
         Ok(self.fields.into_iter().map(Value))
     }
+
+    /// Create a value of the variant `tag` of an enum. The value holds the tag of the variant
+    /// followed by its fields.
+    pub fn pack_variant<I: IntoIterator<Item = Value>>(tag: u16, vals: I) -> Self {
+        Self::pack(iter::once(Value::u16(tag)).chain(vals))
+    }
+
+    /// Destroy a value of an enum, failing with `STRUCT_VARIANT_MISMATCH` if it is not of the
+    /// variant `tag`.
+    pub fn unpack_variant(self, tag: u16) -> PartialVMResult<impl Iterator<Item = Value>> {
+        let mut fields = self.fields.into_iter();
+        match fields.next() {
+            Some(ValueImpl::U16(actual)) if actual == tag => Ok(fields.map(Value)),
+            Some(ValueImpl::U16(actual)) => {
+                Err(PartialVMError::new(StatusCode::STRUCT_VARIANT_MISMATCH)
+                    .with_message(format!("expected variant {}, got {}", tag, actual)))
+            }
+            _ => Err(
+                PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
+                    .with_message("enum value without a variant tag".to_string()),
+            ),
+        }
+    }
 }
 
 /***************************************************************************************
@@ -2114,8 +2492,11 @@ impl Display for ValueImpl {
             Self::Invalid => write!(f, "Invalid"),
 
             Self::U8(x) => write!(f, "U8({})", x),
+            Self::U16(x) => write!(f, "U16({})", x),
+            Self::U32(x) => write!(f, "U32({})", x),
             Self::U64(x) => write!(f, "U64({})", x),
             Self::U128(x) => write!(f, "U128({})", x),
+            Self::U256(x) => write!(f, "U256({})", x),
             Self::Bool(x) => write!(f, "{}", x),
             Self::Address(addr) => write!(f, "Address({})", addr.short_str_lossless()),
 
@@ -2171,8 +2552,11 @@ impl Display for Container {
                 display_list_of_items(r.borrow().iter(), f)
             }
             Self::VecU8(r) => display_list_of_items(r.borrow().iter(), f),
+            Self::VecU16(r) => display_list_of_items(r.borrow().iter(), f),
+            Self::VecU32(r) => display_list_of_items(r.borrow().iter(), f),
             Self::VecU64(r) => display_list_of_items(r.borrow().iter(), f),
             Self::VecU128(r) => display_list_of_items(r.borrow().iter(), f),
+            Self::VecU256(r) => display_list_of_items(r.borrow().iter(), f),
             Self::VecBool(r) => display_list_of_items(r.borrow().iter(), f),
             Self::VecAddress(r) => display_list_of_items(r.borrow().iter(), f),
         }
@@ -2214,6 +2598,14 @@ pub mod debug {
         debug_write!(buf, "{}", x)
     }
 
+    fn print_u16<B: Write>(buf: &mut B, x: &u16) -> PartialVMResult<()> {
+        debug_write!(buf, "{}", x)
+    }
+
+    fn print_u32<B: Write>(buf: &mut B, x: &u32) -> PartialVMResult<()> {
+        debug_write!(buf, "{}", x)
+    }
+
     fn print_u64<B: Write>(buf: &mut B, x: &u64) -> PartialVMResult<()> {
         debug_write!(buf, "{}", x)
     }
@@ -2222,6 +2614,10 @@ pub mod debug {
         debug_write!(buf, "{}", x)
     }
 
+    fn print_u256<B: Write>(buf: &mut B, x: &U256) -> PartialVMResult<()> {
+        debug_write!(buf, "{}", x)
+    }
+
     fn print_bool<B: Write>(buf: &mut B, x: &bool) -> PartialVMResult<()> {
         debug_write!(buf, "{}", x)
     }
@@ -2235,8 +2631,11 @@ pub mod debug {
             ValueImpl::Invalid => print_invalid(buf),
 
             ValueImpl::U8(x) => print_u8(buf, x),
+            ValueImpl::U16(x) => print_u16(buf, x),
+            ValueImpl::U32(x) => print_u32(buf, x),
             ValueImpl::U64(x) => print_u64(buf, x),
             ValueImpl::U128(x) => print_u128(buf, x),
+            ValueImpl::U256(x) => print_u256(buf, x),
             ValueImpl::Bool(x) => print_bool(buf, x),
             ValueImpl::Address(x) => print_address(buf, x),
 
@@ -2282,8 +2681,11 @@ pub mod debug {
             }
 
             Container::VecU8(r) => print_list(buf, "[", r.borrow().iter(), print_u8, "]"),
+            Container::VecU16(r) => print_list(buf, "[", r.borrow().iter(), print_u16, "]"),
+            Container::VecU32(r) => print_list(buf, "[", r.borrow().iter(), print_u32, "]"),
             Container::VecU64(r) => print_list(buf, "[", r.borrow().iter(), print_u64, "]"),
             Container::VecU128(r) => print_list(buf, "[", r.borrow().iter(), print_u128, "]"),
+            Container::VecU256(r) => print_list(buf, "[", r.borrow().iter(), print_u256, "]"),
             Container::VecBool(r) => print_list(buf, "[", r.borrow().iter(), print_bool, "]"),
             Container::VecAddress(r) => print_list(buf, "[", r.borrow().iter(), print_address, "]"),
 
@@ -2321,8 +2723,11 @@ pub mod debug {
             }
 
             Container::VecU8(r) => print_slice_elem(buf, &*r.borrow(), idx, print_u8),
+            Container::VecU16(r) => print_slice_elem(buf, &*r.borrow(), idx, print_u16),
+            Container::VecU32(r) => print_slice_elem(buf, &*r.borrow(), idx, print_u32),
             Container::VecU64(r) => print_slice_elem(buf, &*r.borrow(), idx, print_u64),
             Container::VecU128(r) => print_slice_elem(buf, &*r.borrow(), idx, print_u128),
+            Container::VecU256(r) => print_slice_elem(buf, &*r.borrow(), idx, print_u256),
             Container::VecBool(r) => print_slice_elem(buf, &*r.borrow(), idx, print_bool),
             Container::VecAddress(r) => print_slice_elem(buf, &*r.borrow(), idx, print_address),
         }
@@ -2370,8 +2775,8 @@ pub mod debug {
  *
  **************************************************************************************/
 use serde::{
-    de::Error as DeError,
-    ser::{Error as SerError, SerializeSeq, SerializeTuple},
+    de::{Error as DeError, VariantAccess},
+    ser::{Error as SerError, SerializeSeq, SerializeTuple, SerializeTupleVariant},
     Deserialize,
 };
 
@@ -2380,6 +2785,17 @@ impl Value {
         bcs::from_bytes_seed(SeedWrapper { layout }, blob).ok()
     }
 
+    /// Check that no vector in the value has more than `max_vector_length` elements. Values
+    /// created by the VM are checked as they are built, this is meant for values deserialized
+    /// from outside of the VM. No vector can exceed a limit of `u64::MAX`, so the value is not
+    /// walked in that case.
+    pub fn check_vector_lengths(&self, max_vector_length: u64) -> PartialVMResult<()> {
+        if max_vector_length == u64::MAX {
+            return Ok(());
+        }
+        self.0.check_vector_lengths(max_vector_length)
+    }
+
     pub fn simple_serialize(&self, layout: &MoveTypeLayout) -> Option<Vec<u8>> {
         bcs::to_bytes(&AnnotatedValue {
             layout,
@@ -2418,8 +2834,11 @@ impl<'a, 'b> serde::Serialize for AnnotatedValue<'a, 'b, MoveTypeLayout, ValueIm
     fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
         match (self.layout, self.val) {
             (MoveTypeLayout::U8, ValueImpl::U8(x)) => serializer.serialize_u8(*x),
+            (MoveTypeLayout::U16, ValueImpl::U16(x)) => serializer.serialize_u16(*x),
+            (MoveTypeLayout::U32, ValueImpl::U32(x)) => serializer.serialize_u32(*x),
             (MoveTypeLayout::U64, ValueImpl::U64(x)) => serializer.serialize_u64(*x),
             (MoveTypeLayout::U128, ValueImpl::U128(x)) => serializer.serialize_u128(*x),
+            (MoveTypeLayout::U256, ValueImpl::U256(x)) => x.serialize(serializer),
             (MoveTypeLayout::Bool, ValueImpl::Bool(x)) => serializer.serialize_bool(*x),
             (MoveTypeLayout::Address, ValueImpl::Address(x)) => x.serialize(serializer),
 
@@ -2435,10 +2854,15 @@ impl<'a, 'b> serde::Serialize for AnnotatedValue<'a, 'b, MoveTypeLayout, ValueIm
                 let layout = &**layout;
                 match (layout, c) {
                     (MoveTypeLayout::U8, Container::VecU8(r)) => r.borrow().serialize(serializer),
+                    (MoveTypeLayout::U16, Container::VecU16(r)) => r.borrow().serialize(serializer),
+                    (MoveTypeLayout::U32, Container::VecU32(r)) => r.borrow().serialize(serializer),
                     (MoveTypeLayout::U64, Container::VecU64(r)) => r.borrow().serialize(serializer),
                     (MoveTypeLayout::U128, Container::VecU128(r)) => {
                         r.borrow().serialize(serializer)
                     }
+                    (MoveTypeLayout::U256, Container::VecU256(r)) => {
+                        r.borrow().serialize(serializer)
+                    }
                     (MoveTypeLayout::Bool, Container::VecBool(r)) => {
                         r.borrow().serialize(serializer)
                     }
@@ -2488,6 +2912,9 @@ impl<'a, 'b> serde::Serialize for AnnotatedValue<'a, 'b, MoveTypeLayout, ValueIm
 impl<'a, 'b> serde::Serialize for AnnotatedValue<'a, 'b, MoveStructLayout, Vec<ValueImpl>> {
     fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
         let values = &self.val;
+        if let MoveStructLayout::RuntimeVariants(variants) = self.layout {
+            return serialize_variant(serializer, variants, values);
+        }
         let fields = self.layout.fields();
         if fields.len() != values.len() {
             return Err(invariant_violation::<S>(format!(
@@ -2506,6 +2933,45 @@ impl<'a, 'b> serde::Serialize for AnnotatedValue<'a, 'b, MoveStructLayout, Vec<V
     }
 }
 
+// The value of an enum holds the tag of its variant followed by the fields of the variant, and is
+// serialized the way Serde data formats serialize the tuple variant of a Rust enum.
+fn serialize_variant<S: serde::Serializer>(
+    serializer: S,
+    variants: &[Vec<MoveTypeLayout>],
+    values: &[ValueImpl],
+) -> Result<S::Ok, S::Error> {
+    let (tag, field_layouts) = match values.split_first() {
+        Some((ValueImpl::U16(tag), fields)) => match variants.get(*tag as usize) {
+            Some(field_layouts) if field_layouts.len() == fields.len() => (*tag, field_layouts),
+            _ => {
+                return Err(invariant_violation::<S>(format!(
+                    "cannot serialize enum value {:?} as {:?} -- variant mismatch",
+                    values, variants
+                )))
+            }
+        },
+        _ => {
+            return Err(invariant_violation::<S>(format!(
+                "cannot serialize enum value {:?} as {:?} -- missing variant tag",
+                values, variants
+            )))
+        }
+    };
+    let mut t = serializer.serialize_tuple_variant(
+        MOVE_ENUM_NAME,
+        tag as u32,
+        MOVE_ENUM_VARIANT,
+        field_layouts.len(),
+    )?;
+    for (field_layout, val) in field_layouts.iter().zip(values[1..].iter()) {
+        t.serialize_field(&AnnotatedValue {
+            layout: field_layout,
+            val,
+        })?;
+    }
+    t.end()
+}
+
 #[derive(Clone)]
 struct SeedWrapper<L> {
     layout: L,
@@ -2523,8 +2989,11 @@ impl<'d> serde::de::DeserializeSeed<'d> for SeedWrapper<&MoveTypeLayout> {
         match self.layout {
             L::Bool => bool::deserialize(deserializer).map(Value::bool),
             L::U8 => u8::deserialize(deserializer).map(Value::u8),
+            L::U16 => u16::deserialize(deserializer).map(Value::u16),
+            L::U32 => u32::deserialize(deserializer).map(Value::u32),
             L::U64 => u64::deserialize(deserializer).map(Value::u64),
             L::U128 => u128::deserialize(deserializer).map(Value::u128),
+            L::U256 => U256::deserialize(deserializer).map(Value::u256),
             L::Address => AccountAddress::deserialize(deserializer).map(Value::address),
             L::Signer => AccountAddress::deserialize(deserializer).map(Value::signer),
 
@@ -2540,12 +3009,21 @@ impl<'d> serde::de::DeserializeSeed<'d> for SeedWrapper<&MoveTypeLayout> {
                     L::U8 => {
                         Container::VecU8(Rc::new(RefCell::new(Vec::deserialize(deserializer)?)))
                     }
+                    L::U16 => {
+                        Container::VecU16(Rc::new(RefCell::new(Vec::deserialize(deserializer)?)))
+                    }
+                    L::U32 => {
+                        Container::VecU32(Rc::new(RefCell::new(Vec::deserialize(deserializer)?)))
+                    }
                     L::U64 => {
                         Container::VecU64(Rc::new(RefCell::new(Vec::deserialize(deserializer)?)))
                     }
                     L::U128 => {
                         Container::VecU128(Rc::new(RefCell::new(Vec::deserialize(deserializer)?)))
                     }
+                    L::U256 => {
+                        Container::VecU256(Rc::new(RefCell::new(Vec::deserialize(deserializer)?)))
+                    }
                     L::Bool => {
                         Container::VecBool(Rc::new(RefCell::new(Vec::deserialize(deserializer)?)))
                     }
@@ -2571,6 +3049,13 @@ impl<'d> serde::de::DeserializeSeed<'d> for SeedWrapper<&MoveStructLayout> {
         self,
         deserializer: D,
     ) -> Result<Self::Value, D::Error> {
+        if let MoveStructLayout::RuntimeVariants(variants) = self.layout {
+            return deserializer.deserialize_enum(
+                MOVE_ENUM_NAME,
+                &[],
+                StructVariantVisitor(variants),
+            );
+        }
         let field_layouts = self.layout.fields();
         let fields = deserializer
             .deserialize_tuple(field_layouts.len(), StructFieldVisitor(field_layouts))?;
@@ -2578,6 +3063,35 @@ impl<'d> serde::de::DeserializeSeed<'d> for SeedWrapper<&MoveStructLayout> {
     }
 }
 
+struct StructVariantVisitor<'a>(&'a [Vec<MoveTypeLayout>]);
+
+impl<'d, 'a> serde::de::Visitor<'d> for StructVariantVisitor<'a> {
+    type Value = Struct;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("Enum")
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::EnumAccess<'d>,
+    {
+        let (tag, variant) = data.variant::<u32>()?;
+        let field_layouts = match self.0.get(tag as usize) {
+            Some(field_layouts) => field_layouts,
+            None => {
+                return Err(A::Error::invalid_value(
+                    serde::de::Unexpected::Unsigned(tag as u64),
+                    &self,
+                ))
+            }
+        };
+        let fields =
+            variant.tuple_variant(field_layouts.len(), StructFieldVisitor(field_layouts))?;
+        Ok(Struct::pack_variant(tag as u16, fields))
+    }
+}
+
 struct VectorElementVisitor<'a>(SeedWrapper<&'a MoveTypeLayout>);
 
 impl<'d, 'a> serde::de::Visitor<'d> for VectorElementVisitor<'a> {
@@ -2642,8 +3156,11 @@ impl Value {
         Some(match constant_signature {
             S::Bool => L::Bool,
             S::U8 => L::U8,
+            S::U16 => L::U16,
+            S::U32 => L::U32,
             S::U64 => L::U64,
             S::U128 => L::U128,
+            S::U256 => L::U256,
             S::Address => L::Address,
             S::Signer => return None,
             S::Vector(inner) => L::Vector(Box::new(Self::constant_sig_token_to_layout(inner)?)),
@@ -2678,8 +3195,11 @@ pub mod prop {
 
         match layout {
             L::U8 => any::<u8>().prop_map(Value::u8).boxed(),
+            L::U16 => any::<u16>().prop_map(Value::u16).boxed(),
+            L::U32 => any::<u32>().prop_map(Value::u32).boxed(),
             L::U64 => any::<u64>().prop_map(Value::u64).boxed(),
             L::U128 => any::<u128>().prop_map(Value::u128).boxed(),
+            L::U256 => any::<U256>().prop_map(Value::u256).boxed(),
             L::Bool => any::<bool>().prop_map(Value::bool).boxed(),
             L::Address => any::<AccountAddress>().prop_map(Value::address).boxed(),
             L::Signer => any::<AccountAddress>().prop_map(Value::signer).boxed(),
@@ -2692,6 +3212,20 @@ pub mod prop {
                         ))))
                     })
                     .boxed(),
+                L::U16 => vec(any::<u16>(), 0..10)
+                    .prop_map(|vals| {
+                        Value(ValueImpl::Container(Container::VecU16(Rc::new(
+                            RefCell::new(vals),
+                        ))))
+                    })
+                    .boxed(),
+                L::U32 => vec(any::<u32>(), 0..10)
+                    .prop_map(|vals| {
+                        Value(ValueImpl::Container(Container::VecU32(Rc::new(
+                            RefCell::new(vals),
+                        ))))
+                    })
+                    .boxed(),
                 L::U64 => vec(any::<u64>(), 0..10)
                     .prop_map(|vals| {
                         Value(ValueImpl::Container(Container::VecU64(Rc::new(
@@ -2706,6 +3240,13 @@ pub mod prop {
                         ))))
                     })
                     .boxed(),
+                L::U256 => vec(any::<U256>(), 0..10)
+                    .prop_map(|vals| {
+                        Value(ValueImpl::Container(Container::VecU256(Rc::new(
+                            RefCell::new(vals),
+                        ))))
+                    })
+                    .boxed(),
                 L::Bool => vec(any::<bool>(), 0..10)
                     .prop_map(|vals| {
                         Value(ValueImpl::Container(Container::VecBool(Rc::new(
@@ -2744,8 +3285,11 @@ pub mod prop {
 
         let leaf = prop_oneof![
             1 => Just(L::U8),
+            1 => Just(L::U16),
+            1 => Just(L::U32),
             1 => Just(L::U64),
             1 => Just(L::U128),
+            1 => Just(L::U256),
             1 => Just(L::Bool),
             1 => Just(L::Address),
             1 => Just(L::Signer),
@@ -2773,11 +3317,30 @@ pub mod prop {
 
             match (layout, &self) {
                 (L::U8, ValueImpl::U8(x)) => MoveValue::U8(*x),
+                (L::U16, ValueImpl::U16(x)) => MoveValue::U16(*x),
+                (L::U32, ValueImpl::U32(x)) => MoveValue::U32(*x),
                 (L::U64, ValueImpl::U64(x)) => MoveValue::U64(*x),
                 (L::U128, ValueImpl::U128(x)) => MoveValue::U128(*x),
+                (L::U256, ValueImpl::U256(x)) => MoveValue::U256(*x),
                 (L::Bool, ValueImpl::Bool(x)) => MoveValue::Bool(*x),
                 (L::Address, ValueImpl::Address(x)) => MoveValue::Address(*x),
 
+                (
+                    L::Struct(MoveStructLayout::RuntimeVariants(variants)),
+                    ValueImpl::Container(Container::Struct(r)),
+                ) => {
+                    let r = r.borrow();
+                    let tag = match &r[0] {
+                        ValueImpl::U16(tag) => *tag,
+                        v => panic!("got {:?} as the variant tag of an enum", v),
+                    };
+                    let mut fields = vec![];
+                    for (v, field_layout) in r[1..].iter().zip(variants[tag as usize].iter()) {
+                        fields.push(v.as_move_value(field_layout));
+                    }
+                    MoveValue::Struct(MoveStruct::new_variant(tag, fields))
+                }
+
                 (L::Struct(struct_layout), ValueImpl::Container(Container::Struct(r))) => {
                     let mut fields = vec![];
                     for (v, field_layout) in r.borrow().iter().zip(struct_layout.fields().iter()) {
@@ -2788,10 +3351,15 @@ pub mod prop {
 
                 (L::Vector(inner_layout), ValueImpl::Container(c)) => MoveValue::Vector(match c {
                     Container::VecU8(r) => r.borrow().iter().map(|u| MoveValue::U8(*u)).collect(),
+                    Container::VecU16(r) => r.borrow().iter().map(|u| MoveValue::U16(*u)).collect(),
+                    Container::VecU32(r) => r.borrow().iter().map(|u| MoveValue::U32(*u)).collect(),
                     Container::VecU64(r) => r.borrow().iter().map(|u| MoveValue::U64(*u)).collect(),
                     Container::VecU128(r) => {
                         r.borrow().iter().map(|u| MoveValue::U128(*u)).collect()
                     }
+                    Container::VecU256(r) => {
+                        r.borrow().iter().map(|u| MoveValue::U256(*u)).collect()
+                    }
                     Container::VecBool(r) => {
                         r.borrow().iter().map(|u| MoveValue::Bool(*u)).collect()
                     }
@@ -105,7 +105,7 @@ impl<'a> ModuleGenerator<'a> {
             .cloned()
             .collect();
 
-        let mut end = 5;
+        let mut end = 8;
         if !ty_param_context.is_empty() {
             end += 1;
         };
@@ -116,10 +116,13 @@ impl<'a> ModuleGenerator<'a> {
         match self.index(end) {
             0 => Type::Address,
             1 => Type::U8,
-            2 => Type::U64,
-            3 => Type::U128,
-            4 => Type::Bool,
-            5 if !structs.is_empty() => {
+            2 => Type::U16,
+            3 => Type::U32,
+            4 => Type::U64,
+            5 => Type::U128,
+            6 => Type::U256,
+            7 => Type::Bool,
+            8 if !structs.is_empty() => {
                 let index = self.index(structs.len());
                 let struct_def = structs[index].value.clone();
                 let ty_instants = {
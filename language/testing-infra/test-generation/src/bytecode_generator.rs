@@ -19,6 +19,7 @@ use move_binary_format::{
         StructDefInstantiationIndex, StructDefinitionIndex, StructFieldInformation, TableIndex,
     },
 };
+use move_core_types::u256::U256;
 use rand::{rngs::StdRng, Rng};
 use tracing::{debug, error, warn};
 
@@ -31,12 +32,21 @@ type CodeOffsetToBytecode = fn(CodeOffset) -> Bytecode;
 /// This type represents bytecode instructions that take a `u8`
 type U8ToBytecode = fn(u8) -> Bytecode;
 
+/// This type represents bytecode instructions that take a `u16`
+type U16ToBytecode = fn(u16) -> Bytecode;
+
+/// This type represents bytecode instructions that take a `u32`
+type U32ToBytecode = fn(u32) -> Bytecode;
+
 /// This type represents bytecode instructions that take a `u64`
 type U64ToBytecode = fn(u64) -> Bytecode;
 
 /// This type represents bytecode instructions that take a `u128`
 type U128ToBytecode = fn(u128) -> Bytecode;
 
+/// This type represents bytecode instructions that take a `u256`
+type U256ToBytecode = fn(U256) -> Bytecode;
+
 /// This type represents bytecode instructions that take a `AddressPoolIndex`
 type ConstantPoolIndexToBytecode = fn(ConstantPoolIndex) -> Bytecode;
 
@@ -73,12 +83,21 @@ enum BytecodeType {
     /// Instructions that take a `u8`
     U8(U8ToBytecode),
 
+    /// Instructions that take a `u16`
+    U16(U16ToBytecode),
+
+    /// Instructions that take a `u32`
+    U32(U32ToBytecode),
+
     /// Instructions that take a `u64`
     U64(U64ToBytecode),
 
     /// Instructions that take a `u128`
     U128(U128ToBytecode),
 
+    /// Instructions that take a `u256`
+    U256(U256ToBytecode),
+
     /// Instructions that take an `ConstantPoolIndex`
     ConstantPoolIndex(ConstantPoolIndexToBytecode),
 
@@ -164,11 +183,17 @@ impl<'a> BytecodeGenerator<'a> {
         let instructions: Vec<(StackEffect, BytecodeType)> = vec![
             (StackEffect::Sub, BytecodeType::NoArg(Bytecode::Pop)),
             (StackEffect::Add, BytecodeType::U8(Bytecode::LdU8)),
+            (StackEffect::Add, BytecodeType::U16(Bytecode::LdU16)),
+            (StackEffect::Add, BytecodeType::U32(Bytecode::LdU32)),
             (StackEffect::Add, BytecodeType::U64(Bytecode::LdU64)),
             (StackEffect::Add, BytecodeType::U128(Bytecode::LdU128)),
+            (StackEffect::Add, BytecodeType::U256(Bytecode::LdU256)),
             (StackEffect::Nop, BytecodeType::NoArg(Bytecode::CastU8)),
+            (StackEffect::Nop, BytecodeType::NoArg(Bytecode::CastU16)),
+            (StackEffect::Nop, BytecodeType::NoArg(Bytecode::CastU32)),
             (StackEffect::Nop, BytecodeType::NoArg(Bytecode::CastU64)),
             (StackEffect::Nop, BytecodeType::NoArg(Bytecode::CastU128)),
+            (StackEffect::Nop, BytecodeType::NoArg(Bytecode::CastU256)),
             (
                 StackEffect::Add,
                 BytecodeType::ConstantPoolIndex(Bytecode::LdConst),
@@ -376,6 +401,14 @@ impl<'a> BytecodeGenerator<'a> {
                     // Generate a random u8 constant to load
                     Some(instruction(self.rng.gen_range(0..u8::max_value())))
                 }
+                BytecodeType::U16(instruction) => {
+                    // Generate a random u16 constant to load
+                    Some(instruction(self.rng.gen_range(0..u16::max_value())))
+                }
+                BytecodeType::U32(instruction) => {
+                    // Generate a random u32 constant to load
+                    Some(instruction(self.rng.gen_range(0..u32::max_value())))
+                }
                 BytecodeType::U64(instruction) => {
                     // Generate a random u64 constant to load
                     Some(instruction(self.rng.gen_range(0..u64::max_value())))
@@ -384,6 +417,10 @@ impl<'a> BytecodeGenerator<'a> {
                     // Generate a random u128 constant to load
                     Some(instruction(self.rng.gen_range(0..u128::max_value())))
                 }
+                BytecodeType::U256(instruction) => {
+                    // Generate a random u256 constant to load
+                    Some(instruction(U256::from_le_bytes(&self.rng.gen())))
+                }
                 BytecodeType::ConstantPoolIndex(instruction) => {
                     // Select a random address from the module's address pool
                     Self::index_or_none(&module.constant_pool, &mut self.rng)
@@ -869,7 +906,10 @@ impl<'a> BytecodeGenerator<'a> {
             SignatureToken::Address => vec![Bytecode::LdConst(ConstantPoolIndex(0))],
             SignatureToken::U64 => vec![Bytecode::LdU64(0)],
             SignatureToken::U8 => vec![Bytecode::LdU8(0)],
+            SignatureToken::U16 => vec![Bytecode::LdU16(0)],
+            SignatureToken::U32 => vec![Bytecode::LdU32(0)],
             SignatureToken::U128 => vec![Bytecode::LdU128(0)],
+            SignatureToken::U256 => vec![Bytecode::LdU256(U256::zero())],
             SignatureToken::Bool => vec![Bytecode::LdFalse],
             SignatureToken::Struct(handle_idx) => {
                 let struct_def_idx = module
@@ -886,6 +926,9 @@ impl<'a> BytecodeGenerator<'a> {
                 let fields = match &struct_def.field_information {
                     StructFieldInformation::Native => panic!("Can't inhabit native structs"),
                     StructFieldInformation::Declared(fields) => fields.clone(),
+                    StructFieldInformation::DeclaredVariants(_) => {
+                        panic!("Can't inhabit enums")
+                    }
                 };
                 let mut bytecodes: Vec<Bytecode> = fields
                     .iter()
@@ -914,6 +957,9 @@ impl<'a> BytecodeGenerator<'a> {
                 let fields = match &struct_def.field_information {
                     StructFieldInformation::Native => panic!("Can't inhabit native structs"),
                     StructFieldInformation::Declared(fields) => fields.clone(),
+                    StructFieldInformation::DeclaredVariants(_) => {
+                        panic!("Can't inhabit enums")
+                    }
                 };
                 let mut bytecodes: Vec<Bytecode> = fields
                     .iter()
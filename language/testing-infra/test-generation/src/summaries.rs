@@ -81,6 +81,18 @@ pub fn instruction_summary(instruction: Bytecode, exact: bool) -> Summary {
                 SignatureToken::U8
             ))]),
         },
+        Bytecode::LdU16(_) => Summary {
+            preconditions: vec![],
+            effects: Effects::NoTyParams(vec![state_stack_push!(AbstractValue::new_primitive(
+                SignatureToken::U16
+            ))]),
+        },
+        Bytecode::LdU32(_) => Summary {
+            preconditions: vec![],
+            effects: Effects::NoTyParams(vec![state_stack_push!(AbstractValue::new_primitive(
+                SignatureToken::U32
+            ))]),
+        },
         Bytecode::LdU64(_) => Summary {
             preconditions: vec![],
             effects: Effects::NoTyParams(vec![state_stack_push!(AbstractValue::new_primitive(
@@ -93,6 +105,12 @@ pub fn instruction_summary(instruction: Bytecode, exact: bool) -> Summary {
                 SignatureToken::U128
             ))]),
         },
+        Bytecode::LdU256(_) => Summary {
+            preconditions: vec![],
+            effects: Effects::NoTyParams(vec![state_stack_push!(AbstractValue::new_primitive(
+                SignatureToken::U256
+            ))]),
+        },
         Bytecode::CastU8 => Summary {
             preconditions: vec![state_stack_is_castable!(SignatureToken::U8)],
             effects: Effects::NoTyParams(vec![
@@ -100,6 +118,20 @@ pub fn instruction_summary(instruction: Bytecode, exact: bool) -> Summary {
                 state_stack_push!(AbstractValue::new_primitive(SignatureToken::U8)),
             ]),
         },
+        Bytecode::CastU16 => Summary {
+            preconditions: vec![state_stack_is_castable!(SignatureToken::U16)],
+            effects: Effects::NoTyParams(vec![
+                state_stack_pop!(),
+                state_stack_push!(AbstractValue::new_primitive(SignatureToken::U16)),
+            ]),
+        },
+        Bytecode::CastU32 => Summary {
+            preconditions: vec![state_stack_is_castable!(SignatureToken::U32)],
+            effects: Effects::NoTyParams(vec![
+                state_stack_pop!(),
+                state_stack_push!(AbstractValue::new_primitive(SignatureToken::U32)),
+            ]),
+        },
         Bytecode::CastU64 => Summary {
             preconditions: vec![state_stack_is_castable!(SignatureToken::U64)],
             effects: Effects::NoTyParams(vec![
@@ -114,6 +146,13 @@ pub fn instruction_summary(instruction: Bytecode, exact: bool) -> Summary {
                 state_stack_push!(AbstractValue::new_primitive(SignatureToken::U128)),
             ]),
         },
+        Bytecode::CastU256 => Summary {
+            preconditions: vec![state_stack_is_castable!(SignatureToken::U256)],
+            effects: Effects::NoTyParams(vec![
+                state_stack_pop!(),
+                state_stack_push!(AbstractValue::new_primitive(SignatureToken::U256)),
+            ]),
+        },
         // TODO actual constant generation
         Bytecode::LdConst(_) => Summary {
             preconditions: vec![],
@@ -577,5 +616,11 @@ pub fn instruction_summary(instruction: Bytecode, exact: bool) -> Summary {
         | Bytecode::VecPopBack(_)
         | Bytecode::VecUnpack(..)
         | Bytecode::VecSwap(_) => unimplemented!("Vector bytecode not supported yet"),
+        Bytecode::PackVariant(..)
+        | Bytecode::PackVariantGeneric(..)
+        | Bytecode::UnpackVariant(..)
+        | Bytecode::UnpackVariantGeneric(..)
+        | Bytecode::TestVariant(..)
+        | Bytecode::TestVariantGeneric(..) => unimplemented!("Enum bytecode not supported yet"),
     }
 }
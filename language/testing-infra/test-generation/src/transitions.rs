@@ -129,26 +129,15 @@ pub fn stack_has_integer(state: &AbstractState, index: usize) -> bool {
 }
 
 pub fn stack_top_is_castable_to(state: &AbstractState, typ: SignatureToken) -> bool {
+    use SignatureToken::*;
+    // The integer types ordered by width; a value is castable to a type at least as wide as its own.
+    let widths = [U8, U16, U32, U64, U128, U256];
     stack_has_integer(state, 0)
-        && match typ {
-            SignatureToken::U8 => stack_has(
-                state,
-                0,
-                Some(AbstractValue::new_primitive(SignatureToken::U8)),
-            ),
-            SignatureToken::U64 => {
-                stack_has(
-                    state,
-                    0,
-                    Some(AbstractValue::new_primitive(SignatureToken::U8)),
-                ) || stack_has(
-                    state,
-                    0,
-                    Some(AbstractValue::new_primitive(SignatureToken::U64)),
-                )
-            }
-            SignatureToken::U128 => true,
-            _ => false,
+        && match widths.iter().position(|ty| *ty == typ) {
+            Some(max) => widths[..=max]
+                .iter()
+                .any(|ty| stack_has(state, 0, Some(AbstractValue::new_primitive(ty.clone())))),
+            None => false,
         }
 }
 
@@ -701,6 +690,9 @@ pub fn stack_struct_borrow_field(
         StructFieldInformation::Native => {
             return Err(VMError::new("Borrow field on a native struct".to_string()));
         }
+        StructFieldInformation::DeclaredVariants(_) => {
+            return Err(VMError::new("Borrow field on an enum".to_string()));
+        }
         StructFieldInformation::Declared(fields) => {
             let field_def = &fields[field_handle.field as usize];
             &field_def.signature.0
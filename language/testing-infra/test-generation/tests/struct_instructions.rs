@@ -88,6 +88,7 @@ fn get_field_signature<'a>(module: &'a CompiledModule, handle: &FieldHandle) ->
     match &struct_def.field_information {
         StructFieldInformation::Native => panic!("borrow field on a native struct"),
         StructFieldInformation::Declared(fields) => &fields[handle.field as usize].signature.0,
+        StructFieldInformation::DeclaredVariants(_) => panic!("borrow field on an enum"),
     }
 }
 
@@ -5,17 +5,20 @@ use crate::module_cache::GetModule;
 use anyhow::{anyhow, bail, Result};
 use move_binary_format::{
     access::ModuleAccess,
-    file_format::{SignatureToken, StructDefinition, StructFieldInformation, StructHandleIndex},
-    normalized::{Struct, Type},
+    file_format::{
+        FieldDefinition, SignatureToken, StructDefinition, StructFieldInformation,
+        StructHandleIndex,
+    },
+    normalized::{Field, Struct, Type},
     CompiledModule,
 };
 use move_core_types::{
     account_address::AccountAddress,
     identifier::{IdentStr, Identifier},
     language_storage::{ModuleId, StructTag, TypeTag},
-    value::{MoveFieldLayout, MoveStructLayout, MoveTypeLayout},
+    value::{MoveFieldLayout, MoveStructLayout, MoveTypeLayout, MoveVariantLayout},
 };
-use serde_reflection::{ContainerFormat, Format, Named, Registry};
+use serde_reflection::{ContainerFormat, Format, Named, Registry, VariantFormat};
 use std::{borrow::Borrow, collections::BTreeMap, convert::TryInto, fmt::Debug};
 
 /// Name of the Move `address` type in the serde registry
@@ -114,8 +117,15 @@ impl<T: GetModule> SerdeLayoutBuilder<T> {
         Ok(match t {
             Bool => Format::Bool,
             U8 => Format::U8,
+            U16 => Format::U16,
+            U32 => Format::U32,
             U64 => Format::U64,
             U128 => Format::U128,
+            // u256 values are serialized as 32 little-endian bytes
+            U256 => Format::TupleArray {
+                content: Box::new(Format::U8),
+                size: 32,
+            },
             Address => Format::TypeName(ADDRESS.to_string()),
             Signer => Format::TypeName(SIGNER.to_string()),
             Struct {
@@ -181,32 +191,60 @@ impl<T: GetModule> SerdeLayoutBuilder<T> {
                 type_arguments.len(),
                 "Wrong number of type arguments for struct"
             );
-            let fields = normalized_struct
-                .fields
-                .iter()
-                .map(|f| {
-                    self.build_normalized_type_layout(&f.type_, type_arguments)
-                        .map(|value| Named {
-                            name: f.name.to_string(),
-                            value,
-                        })
-                })
-                .collect::<Result<Vec<Named<Format>>, T::Error>>()?;
-
-            let serde_struct = ContainerFormat::Struct(fields);
+            let serde_struct = if normalized_struct.variants.is_empty() {
+                ContainerFormat::Struct(
+                    self.build_normalized_fields_layout(&normalized_struct.fields, type_arguments)?,
+                )
+            } else {
+                let mut variants = BTreeMap::new();
+                for (tag, variant) in normalized_struct.variants.iter().enumerate() {
+                    let fields =
+                        self.build_normalized_fields_layout(&variant.fields, type_arguments)?;
+                    variants.insert(
+                        tag as u32,
+                        Named {
+                            name: variant.name.to_string(),
+                            value: VariantFormat::Struct(fields),
+                        },
+                    );
+                }
+                ContainerFormat::Enum(variants)
+            };
             // update cache
             self.registry.insert(struct_key.clone(), serde_struct);
         } // else, it's shallow mode or we already generated a layout for the type
         Ok(Format::TypeName(struct_key))
     }
 
+    fn build_normalized_fields_layout(
+        &mut self,
+        fields: &[Field],
+        type_arguments: &[Format],
+    ) -> Result<Vec<Named<Format>>, T::Error> {
+        fields
+            .iter()
+            .map(|f| {
+                self.build_normalized_type_layout(&f.type_, type_arguments)
+                    .map(|value| Named {
+                        name: f.name.to_string(),
+                        value,
+                    })
+            })
+            .collect()
+    }
+
     fn print_format_type(t: &Format) -> String {
         match t {
             Format::TypeName(s) => s.to_string(),
             Format::Bool => "bool".to_string(),
             Format::U8 => "u8".to_string(),
+            Format::U16 => "u16".to_string(),
+            Format::U32 => "u32".to_string(),
             Format::U64 => "u64".to_string(),
             Format::U128 => "u128".to_string(),
+            Format::TupleArray { content, size: 32 } if **content == Format::U8 => {
+                "u256".to_string()
+            }
             Format::Bytes => "vector<u8>".to_string(),
             Format::Seq(inner) => format!("vector<{}>", Self::print_format_type(inner)),
             v => unimplemented!("Printing format value {:?}", v),
@@ -255,8 +293,11 @@ impl TypeLayoutBuilder {
         Ok(match t {
             Bool => MoveTypeLayout::Bool,
             U8 => MoveTypeLayout::U8,
+            U16 => MoveTypeLayout::U16,
+            U32 => MoveTypeLayout::U32,
             U64 => MoveTypeLayout::U64,
             U128 => MoveTypeLayout::U128,
+            U256 => MoveTypeLayout::U256,
             Address => MoveTypeLayout::Address,
             Signer => bail!("Type layouts cannot contain signer"),
             Vector(elem_t) => {
@@ -315,8 +356,11 @@ impl TypeLayoutBuilder {
             TypeParameter(i) => type_arguments[*i as usize].clone(),
             Bool => MoveTypeLayout::Bool,
             U8 => MoveTypeLayout::U8,
+            U16 => MoveTypeLayout::U16,
+            U32 => MoveTypeLayout::U32,
             U64 => MoveTypeLayout::U64,
             U128 => MoveTypeLayout::U128,
+            U256 => MoveTypeLayout::U256,
             Address => MoveTypeLayout::Address,
             Signer => bail!("Type layouts cannot contain signer"),
             Reference(_) | MutableReference(_) => bail!("Type layouts cannot contain references"),
@@ -366,33 +410,59 @@ impl StructLayoutBuilder {
         if s_handle.type_parameters.len() != type_arguments.len() {
             bail!("Wrong number of type arguments for struct")
         }
+        let build_fields = |fields: &[FieldDefinition]| {
+            fields
+                .iter()
+                .map(|f| {
+                    TypeLayoutBuilder::build_from_signature_token(
+                        m,
+                        &f.signature.0,
+                        &type_arguments,
+                        resolver,
+                        layout_type,
+                    )
+                })
+                .collect::<Result<Vec<MoveTypeLayout>>>()
+        };
+        let with_names = |fields: &[FieldDefinition], layouts: Vec<MoveTypeLayout>| -> Vec<_> {
+            fields
+                .iter()
+                .map(|f| m.identifier_at(f.name).to_owned())
+                .zip(layouts)
+                .map(|(name, layout)| MoveFieldLayout::new(name, layout))
+                .collect()
+        };
         match &s.field_information {
             StructFieldInformation::Native => {
                 bail!("Can't extract fields for native struct")
             }
+            // The variants of an enum are not decorated with the type of the enum
+            StructFieldInformation::DeclaredVariants(variants) => Ok(match layout_type {
+                LayoutType::Runtime => MoveStructLayout::new_variants(
+                    variants
+                        .iter()
+                        .map(|v| build_fields(&v.fields))
+                        .collect::<Result<_>>()?,
+                ),
+                LayoutType::WithFields | LayoutType::WithTypes => MoveStructLayout::with_variants(
+                    variants
+                        .iter()
+                        .map(|v| {
+                            Ok(MoveVariantLayout::new(
+                                m.identifier_at(v.name).to_owned(),
+                                with_names(&v.fields, build_fields(&v.fields)?),
+                            ))
+                        })
+                        .collect::<Result<_>>()?,
+                ),
+            }),
             StructFieldInformation::Declared(fields) => {
-                let layouts = fields
-                    .iter()
-                    .map(|f| {
-                        TypeLayoutBuilder::build_from_signature_token(
-                            m,
-                            &f.signature.0,
-                            &type_arguments,
-                            resolver,
-                            layout_type,
-                        )
-                    })
-                    .collect::<Result<Vec<MoveTypeLayout>>>()?;
+                let layouts = build_fields(fields)?;
                 Ok(match layout_type {
                     LayoutType::Runtime => MoveStructLayout::Runtime(layouts),
-                    LayoutType::WithFields => MoveStructLayout::WithFields(
-                        fields
-                            .iter()
-                            .map(|f| m.identifier_at(f.name).to_owned())
-                            .zip(layouts)
-                            .map(|(name, layout)| MoveFieldLayout::new(name, layout))
-                            .collect(),
-                    ),
+                    LayoutType::WithFields => {
+                        MoveStructLayout::WithFields(with_names(fields, layouts))
+                    }
                     LayoutType::WithTypes => {
                         let mid = m.self_id();
                         let type_param_res: Result<Vec<TypeTag>> =
@@ -404,12 +474,7 @@ impl StructLayoutBuilder {
                             name: m.identifier_at(s_handle.name).to_owned(),
                             type_params,
                         };
-                        let fields = fields
-                            .iter()
-                            .map(|f| m.identifier_at(f.name).to_owned())
-                            .zip(layouts)
-                            .map(|(name, layout)| MoveFieldLayout::new(name, layout))
-                            .collect();
+                        let fields = with_names(fields, layouts);
                         MoveStructLayout::WithTypes { type_, fields }
                     }
                 })
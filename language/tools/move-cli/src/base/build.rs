@@ -6,7 +6,8 @@ use clap::*;
 use move_package::{Architecture, BuildConfig};
 use std::path::PathBuf;
 
-/// Build the package at `path`. If no path is provided defaults to current directory.
+/// Build the package at `path`, or all members of the workspace at `path`. If no path is provided
+/// defaults to current directory.
 #[derive(Parser)]
 #[clap(name = "build")]
 pub struct Build;
@@ -26,7 +27,7 @@ impl Build {
 
         match architecture {
             Architecture::Move | Architecture::AsyncMove => {
-                config.compile_package(&rerooted_path, &mut std::io::stderr())?;
+                config.compile_workspace(&rerooted_path, &mut std::io::stderr())?;
             }
 
             Architecture::Ethereum => {
@@ -0,0 +1,544 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    sandbox::{self, utils::PackageContext},
+    NativeFunctionRecord, DEFAULT_STORAGE_DIR,
+};
+use anyhow::{bail, Result};
+use clap::*;
+use move_bytecode_source_map::source_map::SourceMap;
+use move_command_line_common::files::{FileHash, MOVE_EXTENSION};
+use move_compiler::compiled_unit::{CompiledUnit, NamedCompiledModule};
+use move_core_types::{
+    errmap::ErrorMapping, gas_schedule::CostTable, language_storage::ModuleId, parser,
+    transaction_argument::TransactionArgument,
+};
+use move_package::{compilation::compiled_package::CompiledPackage, BuildConfig};
+use move_vm_runtime::trace_sink::{TraceEvent, TraceSink, TracedFunction};
+use move_vm_types::values::{self, Locals};
+use std::{
+    collections::BTreeMap,
+    fmt, fs,
+    io::{self, BufRead, BufReader, Write},
+    path::{Component, Path, PathBuf},
+    str::FromStr,
+};
+
+/// Run a Move script or entry function from the sandbox under an interactive source-level
+/// debugger. The effects of the execution are never committed to storage. Unit tests can be
+/// debugged with `move test --debug`.
+#[derive(Parser)]
+#[clap(name = "debug")]
+pub struct Debug {
+    /// Directory storing Move resources, events, and module bytecodes produced by module publishing
+    /// and script execution.
+    #[clap(long, default_value = DEFAULT_STORAGE_DIR, parse(from_os_str))]
+    pub storage_dir: PathBuf,
+    /// Path to .mv file containing either script or module bytecodes, or to a script in the
+    /// package. If the file is a module, the `script_name` parameter must be set.
+    #[clap(name = "script", parse(from_os_str))]
+    pub script_file: PathBuf,
+    /// Name of the script function inside `script_file` to call. Should only be set if
+    /// `script_file` points to a module.
+    #[clap(name = "name")]
+    pub script_name: Option<String>,
+    /// Possibly-empty list of signers for the current transaction.
+    #[clap(
+        long = "signers",
+        takes_value(true),
+        multiple_values(true),
+        multiple_occurrences(true)
+    )]
+    pub signers: Vec<String>,
+    /// Possibly-empty list of arguments passed to the transaction, in the same format as for
+    /// `sandbox run`.
+    #[clap(
+        long = "args",
+        parse(try_from_str = parser::parse_transaction_argument),
+        takes_value(true),
+        multiple_values(true),
+        multiple_occurrences(true)
+    )]
+    pub args: Vec<TransactionArgument>,
+    /// Possibly-empty list of type arguments passed to the transaction.
+    #[clap(
+        long = "type-args",
+        parse(try_from_str = parser::parse_type_tag),
+        takes_value(true),
+        multiple_values(true),
+        multiple_occurrences(true)
+    )]
+    pub type_args: Vec<move_core_types::language_storage::TypeTag>,
+    /// Maximum number of gas units to be consumed by execution.
+    #[clap(long = "gas-budget", short = 'g')]
+    pub gas_budget: Option<u64>,
+    /// Source lines to stop at, given as `<file>:<line>` where `<file>` is the path of a Move
+    /// source file in the package or its dependencies, or a suffix of it such as `M.move` or
+    /// `sources/M.move`. Without breakpoints, execution stops at the first line of the called
+    /// function.
+    #[clap(
+        long = "break",
+        short = 'b',
+        takes_value(true),
+        multiple_values(true),
+        multiple_occurrences(true)
+    )]
+    pub breakpoints: Vec<Breakpoint>,
+    /// Read debugger commands from this file instead of from standard input.
+    #[clap(long = "commands", parse(from_os_str))]
+    pub commands_file: Option<PathBuf>,
+}
+
+impl Debug {
+    pub fn execute(
+        self,
+        path: Option<PathBuf>,
+        config: BuildConfig,
+        natives: Vec<NativeFunctionRecord>,
+        cost_table: &CostTable,
+        error_descriptions: &ErrorMapping,
+        verbose: bool,
+    ) -> Result<()> {
+        let context = PackageContext::new(&path, &config)?;
+        let state = context.prepare_state(&self.storage_dir)?;
+        let mut debugger = SourceDebugger::new(
+            context.package(),
+            Some(self.script_file.as_path()),
+            self.breakpoints,
+            self.commands_file.as_deref(),
+        )?;
+        sandbox::commands::run(
+            natives,
+            cost_table,
+            error_descriptions,
+            &state,
+            context.package(),
+            &self.script_file,
+            &self.script_name,
+            &self.signers,
+            &self.args,
+            self.type_args,
+            self.gas_budget,
+            /* dry_run */ true,
+            verbose,
+            Some(&mut debugger),
+        )
+    }
+}
+
+/// A line breakpoint, identified by the path of the source file, or a suffix of it such as its
+/// name, and a 1-based line number.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Breakpoint {
+    pub file: String,
+    pub line: usize,
+}
+
+impl FromStr for Breakpoint {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (file, line) = match s.rsplit_once(':') {
+            Some(parts) => parts,
+            None => bail!(
+                "Expected a breakpoint of the form `<file>:<line>`, got `{}`",
+                s
+            ),
+        };
+        let file = Path::new(file)
+            .components()
+            .filter(|component| !matches!(component, Component::CurDir))
+            .collect::<PathBuf>();
+        if file.file_name().is_none() {
+            bail!("Missing file name in breakpoint `{}`", s)
+        }
+        Ok(Self {
+            file: file.to_string_lossy().to_string(),
+            line: line.trim().parse()?,
+        })
+    }
+}
+
+impl fmt::Display for Breakpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.file, self.line)
+    }
+}
+
+/// A source file of the package, split into lines.
+struct SourceFile {
+    path: PathBuf,
+    name: String,
+    line_starts: Vec<usize>,
+    lines: Vec<String>,
+}
+
+impl SourceFile {
+    fn new(path: PathBuf, contents: &str) -> Self {
+        let mut line_starts = vec![0];
+        line_starts.extend(contents.match_indices('\n').map(|(idx, _)| idx + 1));
+        Self {
+            name: path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            path,
+            line_starts,
+            lines: contents.lines().map(|line| line.to_string()).collect(),
+        }
+    }
+
+    /// The 1-based line containing the byte at `offset`.
+    fn line_of(&self, offset: usize) -> usize {
+        match self.line_starts.binary_search(&offset) {
+            Ok(idx) => idx + 1,
+            Err(idx) => idx,
+        }
+    }
+}
+
+/// The position of the debugger in the source, a file and a 1-based line.
+type SourcePosition = (FileHash, usize);
+
+/// What the debugger does when the next instruction is executed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Mode {
+    /// Stop at the next source line, entering calls.
+    StepInto,
+    /// Stop at the next source line of a function at or below the given depth.
+    StepOver(usize),
+    /// Stop once the function at the given depth has returned.
+    StepOut(usize),
+    /// Stop only at breakpoints.
+    Continue,
+    /// Never stop again.
+    Detached,
+}
+
+/// Without breakpoints, execution stops at the first line of the called function.
+fn initial_mode(breakpoints: &[Breakpoint]) -> Mode {
+    if breakpoints.is_empty() {
+        Mode::StepInto
+    } else {
+        Mode::Continue
+    }
+}
+
+struct DebugFrame {
+    name: String,
+    position: Option<SourcePosition>,
+}
+
+/// A `TraceSink` which maps the executed instructions back to Move source lines and interacts
+/// with the user whenever execution reaches a breakpoint or finishes a step.
+pub(crate) struct SourceDebugger {
+    modules: BTreeMap<ModuleId, SourceMap>,
+    script: Option<SourceMap>,
+    files: BTreeMap<FileHash, SourceFile>,
+    breakpoints: Vec<Breakpoint>,
+    mode: Mode,
+    frames: Vec<DebugFrame>,
+    input: Box<dyn BufRead + Send>,
+    echo_commands: bool,
+}
+
+const HELP: &str = "\
+Available commands:
+  step (s)                  step to the next source line, entering calls
+  next (n)                  step to the next source line, stepping over calls
+  finish (f)                run until the current function returns
+  continue (c)              run until the next breakpoint
+  break (b) <file>:<line>   add a breakpoint
+  delete (d) <file>:<line>  remove a breakpoint
+  breakpoints               list all breakpoints
+  locals (l)                print the locals of the current function
+  backtrace (bt)            print the call stack
+  quit (q)                  run to completion without stopping";
+
+impl SourceDebugger {
+    /// Creates a debugger for the code of `package`, including the script in `script_file` if it
+    /// is a Move source file. Commands are read from `commands_file`, or from standard input if
+    /// it is not set.
+    pub(crate) fn new(
+        package: &CompiledPackage,
+        script_file: Option<&Path>,
+        breakpoints: Vec<Breakpoint>,
+        commands_file: Option<&Path>,
+    ) -> Result<Self> {
+        let input: Box<dyn BufRead + Send> = match commands_file {
+            Some(commands_file) => Box::new(BufReader::new(fs::File::open(commands_file)?)),
+            None => Box::new(BufReader::new(io::stdin())),
+        };
+        let mut files = BTreeMap::new();
+        for unit in package.all_compiled_units_with_source() {
+            let contents = fs::read_to_string(&unit.source_path)?;
+            files.insert(
+                FileHash::new(&contents),
+                SourceFile::new(unit.source_path.clone(), &contents),
+            );
+        }
+        let modules = package
+            .all_modules()
+            .filter_map(|unit| match &unit.unit {
+                CompiledUnit::Module(NamedCompiledModule {
+                    module, source_map, ..
+                }) => Some((module.self_id(), source_map.clone())),
+                CompiledUnit::Script(_) => None,
+            })
+            .collect();
+        let script = if let Some(script_file) =
+            script_file.filter(|file| file.extension().map_or(false, |ext| ext == MOVE_EXTENSION))
+        {
+            let contents = fs::read_to_string(script_file)?;
+            package
+                .scripts()
+                .find(|unit| unit.unit.source_map().check(&contents))
+                .map(|unit| unit.unit.source_map().clone())
+        } else {
+            None
+        };
+        Ok(Self {
+            modules,
+            script,
+            files,
+            mode: initial_mode(&breakpoints),
+            breakpoints,
+            frames: vec![],
+            input,
+            echo_commands: commands_file.is_some(),
+        })
+    }
+
+    fn source_map(&self, function: &TracedFunction) -> Option<&SourceMap> {
+        match function.module_id {
+            Some(module_id) => self.modules.get(module_id),
+            None => self.script.as_ref(),
+        }
+    }
+
+    fn position(&self, function: &TracedFunction, pc: u16) -> Option<SourcePosition> {
+        let loc = self
+            .source_map(function)?
+            .get_code_location(function.index, pc)
+            .ok()?;
+        let file = self.files.get(&loc.file_hash())?;
+        Some((loc.file_hash(), file.line_of(loc.start() as usize)))
+    }
+
+    fn is_breakpoint(&self, (file_hash, line): SourcePosition) -> bool {
+        let file = &self.files[&file_hash];
+        self.breakpoints
+            .iter()
+            .any(|bp| bp.line == line && file.path.ends_with(&bp.file))
+    }
+
+    fn on_instruction(&mut self, function: TracedFunction, pc: u16, locals: &Locals) {
+        if self.mode == Mode::Detached {
+            return;
+        }
+        let position = match self.position(&function, pc) {
+            Some(position) => position,
+            None => return,
+        };
+        let depth = self.frames.len();
+        let frame = match self.frames.last_mut() {
+            Some(frame) => frame,
+            None => return,
+        };
+        let new_line = frame.position != Some(position);
+        frame.position = Some(position);
+        if !new_line {
+            return;
+        }
+        let stop = match self.mode {
+            Mode::StepInto => true,
+            Mode::StepOver(max_depth) => depth <= max_depth,
+            Mode::StepOut(max_depth) => depth < max_depth,
+            Mode::Continue | Mode::Detached => false,
+        } || self.is_breakpoint(position);
+        if stop {
+            self.print_position(position);
+            self.interact(&function, locals);
+        }
+    }
+
+    fn print_position(&self, (file_hash, line): SourcePosition) {
+        let file = &self.files[&file_hash];
+        let name = self
+            .frames
+            .last()
+            .map(|frame| frame.name.as_str())
+            .unwrap_or_default();
+        println!("[{}:{}] {}", file.name, line, name);
+        if let Some(text) = file.lines.get(line - 1) {
+            println!("{:>5} | {}", line, text);
+        }
+    }
+
+    fn print_locals(&self, function: &TracedFunction, locals: &Locals) {
+        let source_map = match self.source_map(function) {
+            Some(source_map) => source_map,
+            None => {
+                println!("No source information available");
+                return;
+            }
+        };
+        let mut idx = 0;
+        while let Ok((name, _)) = source_map.get_parameter_or_local_name(function.index, idx) {
+            idx += 1;
+            // Temporaries introduced by the compiler are not interesting to the user.
+            if name.starts_with('%') {
+                continue;
+            }
+            let name = &name[..name.find('#').unwrap_or(name.len())];
+            let mut value = String::new();
+            let printed = locals
+                .copy_loc(idx as usize - 1)
+                .and_then(|v| values::debug::print_value(&mut value, &v));
+            if printed.is_err() {
+                value = "<unavailable>".to_string();
+            }
+            println!("  {} = {}", name, value);
+        }
+    }
+
+    fn print_backtrace(&self) {
+        for (idx, frame) in self.frames.iter().rev().enumerate() {
+            match frame.position {
+                Some((file_hash, line)) => println!(
+                    "  #{} {} at {}:{}",
+                    idx, frame.name, self.files[&file_hash].name, line
+                ),
+                None => println!("  #{} {}", idx, frame.name),
+            }
+        }
+    }
+
+    fn read_command(&mut self) -> Option<String> {
+        print!("(debug) ");
+        io::stdout().flush().ok()?;
+        let mut line = String::new();
+        match self.input.read_line(&mut line) {
+            Ok(0) | Err(_) => {
+                println!();
+                None
+            }
+            Ok(_) => {
+                if self.echo_commands {
+                    println!("{}", line.trim_end());
+                }
+                Some(line.trim().to_string())
+            }
+        }
+    }
+
+    /// Read and execute commands until one of them resumes execution.
+    fn interact(&mut self, function: &TracedFunction, locals: &Locals) {
+        loop {
+            let command = match self.read_command() {
+                Some(command) => command,
+                None => {
+                    self.mode = Mode::Detached;
+                    return;
+                }
+            };
+            let (command, arg) = match command.split_once(char::is_whitespace) {
+                Some((command, arg)) => (command, arg.trim()),
+                None => (command.as_str(), ""),
+            };
+            let depth = self.frames.len();
+            match command {
+                "s" | "step" => {
+                    self.mode = Mode::StepInto;
+                    return;
+                }
+                "n" | "next" => {
+                    self.mode = Mode::StepOver(depth);
+                    return;
+                }
+                "f" | "finish" => {
+                    self.mode = Mode::StepOut(depth);
+                    return;
+                }
+                "c" | "continue" => {
+                    self.mode = Mode::Continue;
+                    return;
+                }
+                "q" | "quit" => {
+                    self.mode = Mode::Detached;
+                    return;
+                }
+                "b" | "break" => match arg.parse::<Breakpoint>() {
+                    Ok(bp) => {
+                        println!("Breakpoint set at {}", bp);
+                        if !self.breakpoints.contains(&bp) {
+                            self.breakpoints.push(bp);
+                        }
+                    }
+                    Err(err) => println!("{}", err),
+                },
+                "d" | "delete" => match arg.parse::<Breakpoint>() {
+                    Ok(bp) => match self.breakpoints.iter().position(|b| b == &bp) {
+                        Some(idx) => {
+                            self.breakpoints.remove(idx);
+                            println!("Breakpoint removed at {}", bp);
+                        }
+                        None => println!("No breakpoint at {}", bp),
+                    },
+                    Err(err) => println!("{}", err),
+                },
+                "breakpoints" => {
+                    for bp in &self.breakpoints {
+                        println!("  {}", bp);
+                    }
+                }
+                "l" | "locals" => self.print_locals(function, locals),
+                "bt" | "backtrace" => self.print_backtrace(),
+                "" => (),
+                _ => println!("Unrecognized command `{}`\n{}", command, HELP),
+            }
+        }
+    }
+}
+
+impl TraceSink for SourceDebugger {
+    fn on_event(&mut self, event: TraceEvent) {
+        match event {
+            TraceEvent::EnterFunction {
+                function, depth, ..
+            } => {
+                // A new execution starts, e.g. of the next unit test, after the last one aborted
+                if depth == 0 {
+                    self.frames.clear();
+                    if self.mode != Mode::Detached {
+                        self.mode = initial_mode(&self.breakpoints);
+                    }
+                }
+                let name = match function.module_id {
+                    Some(module_id) => format!("{}::{}", module_id.name(), function.name),
+                    None => function.name.to_string(),
+                };
+                self.frames.push(DebugFrame {
+                    name,
+                    position: None,
+                });
+            }
+            TraceEvent::ExitFunction { .. } => {
+                self.frames.pop();
+            }
+            TraceEvent::Instruction {
+                function,
+                pc,
+                locals,
+                ..
+            } => self.on_instruction(function, pc, locals),
+            TraceEvent::Abort { code, .. } => {
+                if self.mode != Mode::Detached {
+                    println!("Execution aborted with code {}", code);
+                }
+            }
+            TraceEvent::GlobalAccess { .. } | TraceEvent::NativeCall { .. } => (),
+        }
+    }
+}
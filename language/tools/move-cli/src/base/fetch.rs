@@ -0,0 +1,22 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use super::reroot_path;
+use clap::*;
+use move_package::BuildConfig;
+use std::path::PathBuf;
+
+/// Download the git and registry dependencies of the package to MOVE_HOME, without building it.
+#[derive(Parser)]
+#[clap(name = "fetch")]
+pub struct Fetch;
+
+impl Fetch {
+    pub fn execute(self, path: Option<PathBuf>, mut config: BuildConfig) -> anyhow::Result<()> {
+        let rerooted_path = reroot_path(path)?;
+        if config.test_mode {
+            config.dev_mode = true;
+        }
+        config.download_deps_for_package(&rerooted_path)
+    }
+}
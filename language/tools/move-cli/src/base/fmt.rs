@@ -0,0 +1,77 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use super::reroot_path;
+use anyhow::bail;
+use clap::*;
+use move_command_line_common::files::{find_move_filenames, FileHash};
+use move_compiler::{
+    diagnostics::report_diagnostics_to_buffer,
+    formatter::{format_string, FormatterConfig, DEFAULT_INDENT_WIDTH},
+};
+use move_package::source_package::layout::SourcePackageLayout;
+use std::{collections::HashMap, fs, io::Write, path::PathBuf};
+
+/// Format the Move source files of the package at `path`. If no path is provided defaults to
+/// current directory.
+#[derive(Parser)]
+#[clap(name = "fmt")]
+pub struct Fmt {
+    /// Do not rewrite any file, but fail if a file is not formatted
+    #[clap(long = "check")]
+    pub check: bool,
+    /// The number of spaces used for one level of indentation
+    #[clap(long = "indent-width", default_value_t = DEFAULT_INDENT_WIDTH)]
+    pub indent_width: usize,
+}
+
+impl Fmt {
+    pub fn execute(self, path: Option<PathBuf>) -> anyhow::Result<()> {
+        let rerooted_path = reroot_path(path)?;
+        let config = FormatterConfig {
+            indent_width: self.indent_width,
+        };
+        let source_dirs: Vec<_> = [
+            SourcePackageLayout::Sources,
+            SourcePackageLayout::Scripts,
+            SourcePackageLayout::Examples,
+            SourcePackageLayout::Tests,
+        ]
+        .iter()
+        .map(|layout| rerooted_path.join(layout.path()))
+        .filter(|dir| dir.is_dir())
+        .collect();
+
+        let mut unformatted = vec![];
+        let mut failed = vec![];
+        for file in find_move_filenames(&source_dirs, false)? {
+            let input = fs::read_to_string(&file)?;
+            let file_hash = FileHash::new(&input);
+            match format_string(file_hash, &input, &config) {
+                Ok(output) if output == input => (),
+                Ok(output) => {
+                    if !self.check {
+                        fs::write(&file, output)?;
+                    }
+                    unformatted.push(file)
+                }
+                Err(diags) => {
+                    let files = HashMap::from([(file_hash, (file.as_str().into(), input))]);
+                    std::io::stderr().write_all(&report_diagnostics_to_buffer(&files, diags))?;
+                    failed.push(file)
+                }
+            }
+        }
+
+        if !failed.is_empty() {
+            bail!("Unable to format {}", failed.join(", "))
+        }
+        if self.check && !unformatted.is_empty() {
+            bail!("Files are not formatted: {}", unformatted.join(", "))
+        }
+        for file in unformatted {
+            println!("FORMATTED {}", file)
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,27 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use super::reroot_path;
+use clap::*;
+use move_package::BuildConfig;
+use std::path::PathBuf;
+
+/// Run the lints of the compiler over the package at `path`, at the levels set in the `[lints]`
+/// section of its manifest. If no path is provided defaults to current directory.
+#[derive(Parser)]
+#[clap(name = "lint")]
+pub struct Lint;
+
+impl Lint {
+    pub fn execute(self, path: Option<PathBuf>, config: BuildConfig) -> anyhow::Result<()> {
+        let rerooted_path = reroot_path(path)?;
+        // Lints are only reported when compiling, so a cached build cannot be reused
+        let config = BuildConfig {
+            lint: true,
+            force_recompilation: true,
+            ..config
+        };
+        config.compile_package(&rerooted_path, &mut std::io::stderr())?;
+        Ok(())
+    }
+}
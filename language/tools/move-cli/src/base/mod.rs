@@ -3,12 +3,18 @@
 
 pub mod build;
 pub mod coverage;
+pub mod debug;
 pub mod disassemble;
 pub mod errmap;
+pub mod fetch;
+pub mod fmt;
 pub mod info;
+pub mod lint;
 pub mod new;
 pub mod prove;
+pub mod publish_package;
 pub mod test;
+pub mod update_deps;
 
 use move_package::source_package::layout::SourcePackageLayout;
 use std::path::PathBuf;
@@ -6,7 +6,7 @@ use anyhow::bail;
 use clap::Parser;
 use codespan_reporting::term::termcolor::{ColorChoice, StandardStream};
 use colored::Colorize;
-use move_package::{BuildConfig, ModelConfig};
+use move_package::{source_package::workspace::workspace_member_paths, BuildConfig, ModelConfig};
 use move_prover::run_move_prover_with_model;
 use std::{
     io::Write,
@@ -27,8 +27,9 @@ pub enum ProverOptions {
     Options(Vec<String>),
 }
 
-/// Run the Move Prover on the package at `path`. If no path is provided defaults to current
-/// directory. Use `.. prove .. -- <options>` to pass on options to the prover.
+/// Run the Move Prover on the package at `path`, or on all members of the workspace at `path`. If
+/// no path is provided defaults to current directory. Use `.. prove .. -- <options>` to pass on
+/// options to the prover.
 #[derive(Parser)]
 #[clap(name = "prove")]
 pub struct Prove {
@@ -56,7 +57,22 @@ impl Prove {
             Some(ProverOptions::Options(opts)) => opts,
             _ => vec![],
         };
-        run_move_prover(config, &rerooted_path, &target_filter, for_test, &opts)
+        // The members of a workspace are verified one after the other, each with its own settings
+        match workspace_member_paths(&rerooted_path)? {
+            Some(member_paths) => {
+                for member_path in member_paths {
+                    run_move_prover(
+                        config.clone(),
+                        &member_path,
+                        &target_filter,
+                        for_test,
+                        &opts,
+                    )?;
+                }
+                Ok(())
+            }
+            None => run_move_prover(config, &rerooted_path, &target_filter, for_test, &opts),
+        }
     }
 }
 
@@ -0,0 +1,36 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use super::reroot_path;
+use clap::*;
+use move_package::{
+    resolution::registry::{version_string, Registry},
+    BuildConfig,
+};
+use std::path::PathBuf;
+
+/// Publish the package to a registry under the version in its manifest, after checking that it
+/// builds.
+#[derive(Parser)]
+#[clap(name = "publish-package")]
+pub struct PublishPackage {
+    /// The registry to publish to, as an absolute path or a 'file://' url
+    #[clap(long = "registry")]
+    pub registry: String,
+}
+
+impl PublishPackage {
+    pub fn execute(self, path: Option<PathBuf>, config: BuildConfig) -> anyhow::Result<()> {
+        let rerooted_path = reroot_path(path)?;
+        let registry = Registry::new(&self.registry)?;
+        config.compile_package(&rerooted_path, &mut std::io::stderr())?;
+        let manifest = registry.publish(&rerooted_path)?;
+        println!(
+            "Published version {} of package '{}' to {}",
+            version_string(manifest.package.version),
+            manifest.package.name,
+            self.registry
+        );
+        Ok(())
+    }
+}
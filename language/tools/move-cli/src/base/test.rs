@@ -1,7 +1,10 @@
 // Copyright (c) The Diem Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use super::reroot_path;
+use super::{
+    debug::{Breakpoint, SourceDebugger},
+    reroot_path,
+};
 use crate::NativeFunctionRecord;
 use anyhow::Result;
 use clap::*;
@@ -13,8 +16,11 @@ use move_compiler::{
     PASS_CFGIR,
 };
 use move_coverage::coverage_map::{output_map_to_file, CoverageMap};
-use move_package::{compilation::build_plan::BuildPlan, BuildConfig};
+use move_package::{
+    compilation::build_plan::BuildPlan, resolution::resolution_graph::ResolvedGraph, BuildConfig,
+};
 use move_unit_test::UnitTestingConfig;
+use move_vm_runtime::trace_sink::TraceSink;
 use std::{
     collections::HashMap,
     fs,
@@ -32,7 +38,7 @@ use std::os::unix::prelude::ExitStatusExt;
 #[cfg(not(any(target_family = "windows", target_family = "unix")))]
 compile_error!("Unsupported OS, currently we only support windows and unix family");
 
-/// Run Move unit tests in this package.
+/// Run Move unit tests in this package, or in all members of this workspace.
 #[derive(Parser)]
 #[clap(name = "test")]
 pub struct Test {
@@ -75,6 +81,30 @@ pub struct Test {
     /// Collect coverage information for later use with the various `package coverage` subcommands
     #[clap(long = "coverage")]
     pub compute_coverage: bool,
+    /// Number of times each #[random_test] is run with generated arguments
+    #[clap(name = "random_iterations", default_value = "100", long = "iterations")]
+    pub random_iterations: u32,
+    /// Seed for generating the arguments of #[random_test]s. A random seed is used if not set
+    #[clap(name = "random_seed", long = "seed")]
+    pub random_seed: Option<u64>,
+    /// Run the unit tests whose name contains this string, one at a time, under the interactive
+    /// source-level debugger of `move debug`
+    #[clap(name = "debug", long = "debug")]
+    pub debug: Option<String>,
+    /// Source lines to stop at when debugging, given as `<file>:<line>`. Without breakpoints,
+    /// execution stops at the first line of each test.
+    #[clap(
+        long = "break",
+        short = 'b',
+        takes_value(true),
+        multiple_values(true),
+        multiple_occurrences(true),
+        requires = "debug"
+    )]
+    pub breakpoints: Vec<Breakpoint>,
+    /// Read debugger commands from this file instead of from standard input.
+    #[clap(long = "commands", parse(from_os_str), requires = "debug")]
+    pub commands_file: Option<PathBuf>,
 
     /// Use the EVM-based execution backend.
     /// Does not work with --stackless.
@@ -101,30 +131,43 @@ impl Test {
             check_stackless_vm,
             verbose_mode,
             compute_coverage,
+            random_iterations,
+            random_seed,
+            debug,
+            breakpoints,
+            commands_file,
             #[cfg(feature = "evm-backend")]
             evm,
         } = self;
+        let debugger = debug.as_ref().map(|_| DebuggerOptions {
+            breakpoints,
+            commands_file,
+        });
         let unit_test_config = UnitTestingConfig {
             instruction_execution_bound,
-            filter,
+            filter: debug.or(filter),
             list,
-            num_threads,
+            // Tests are debugged one at a time
+            num_threads: if debugger.is_some() { 1 } else { num_threads },
             report_statistics,
             report_storage_on_error,
             check_stackless_vm,
             verbose: verbose_mode,
+            random_iterations,
+            random_seed,
 
             #[cfg(feature = "evm-backend")]
             evm,
 
             ..UnitTestingConfig::default_with_bound(None)
         };
-        let result = run_move_unit_tests(
+        let result = run_move_unit_tests_with_debugger(
             &rerooted_path,
             config,
             unit_test_config,
             natives,
             compute_coverage,
+            debugger.as_ref(),
             &mut std::io::stdout(),
         )?;
 
@@ -143,21 +186,79 @@ pub enum UnitTestResult {
     Failure,
 }
 
+/// Where the debugger stops when running unit tests, and where it reads its commands from
+#[derive(Clone, Debug, Default)]
+pub struct DebuggerOptions {
+    pub breakpoints: Vec<Breakpoint>,
+    pub commands_file: Option<PathBuf>,
+}
+
+/// Runs the unit tests of the package at `pkg_path`, or of all members of the workspace if
+/// `pkg_path` is the root of a workspace.
 pub fn run_move_unit_tests<W: Write + Send>(
+    pkg_path: &Path,
+    build_config: move_package::BuildConfig,
+    unit_test_config: UnitTestingConfig,
+    natives: Vec<NativeFunctionRecord>,
+    compute_coverage: bool,
+    writer: &mut W,
+) -> Result<UnitTestResult> {
+    run_move_unit_tests_with_debugger(
+        pkg_path,
+        build_config,
+        unit_test_config,
+        natives,
+        compute_coverage,
+        None,
+        writer,
+    )
+}
+
+/// Like `run_move_unit_tests`, running the tests under the source-level debugger if `debugger` is
+/// set
+pub fn run_move_unit_tests_with_debugger<W: Write + Send>(
     pkg_path: &Path,
     mut build_config: move_package::BuildConfig,
-    mut unit_test_config: UnitTestingConfig,
+    unit_test_config: UnitTestingConfig,
     natives: Vec<NativeFunctionRecord>,
     compute_coverage: bool,
+    debugger: Option<&DebuggerOptions>,
     writer: &mut W,
 ) -> Result<UnitTestResult> {
-    let mut test_plan = None;
     build_config.test_mode = true;
     build_config.dev_mode = true;
 
     // Build the resolution graph
     let resolution_graph = build_config.resolution_graph_for_package(pkg_path)?;
 
+    let mut result = UnitTestResult::Success;
+    for member_graph in resolution_graph.member_graphs() {
+        if run_move_unit_tests_for_graph(
+            member_graph,
+            unit_test_config.clone(),
+            natives.clone(),
+            compute_coverage,
+            debugger,
+            writer,
+        )? == UnitTestResult::Failure
+        {
+            result = UnitTestResult::Failure;
+        }
+    }
+    Ok(result)
+}
+
+fn run_move_unit_tests_for_graph<W: Write + Send>(
+    resolution_graph: ResolvedGraph,
+    mut unit_test_config: UnitTestingConfig,
+    natives: Vec<NativeFunctionRecord>,
+    compute_coverage: bool,
+    debugger: Option<&DebuggerOptions>,
+    writer: &mut W,
+) -> Result<UnitTestResult> {
+    let mut test_plan = None;
+    let pkg_path = resolution_graph.root_package_path.clone();
+
     // Note: unit_test_config.named_address_values is always set to vec![] (the default value) before
     // being passed in.
     unit_test_config.named_address_values = resolution_graph
@@ -170,12 +271,16 @@ pub fn run_move_unit_tests<W: Write + Send>(
         })
         .collect();
 
-    // Get the source files for all modules. We need this in order to report source-mapped error
-    // messages.
-    let dep_file_map: HashMap<_, _> = resolution_graph
-        .package_table
-        .iter()
-        .flat_map(|(_, rpkg)| {
+    // Get the source files for all modules of the package and its dependencies. We need this in
+    // order to report source-mapped error messages.
+    let root_package = resolution_graph.root_package.package.name;
+    let root = resolution_graph.get_package(&root_package);
+    let dep_file_map: HashMap<_, _> = root
+        .transitive_dependencies(&resolution_graph)
+        .into_iter()
+        .map(|name| resolution_graph.get_package(&name))
+        .chain(std::iter::once(root))
+        .flat_map(|rpkg| {
             rpkg.get_sources(&resolution_graph.build_options)
                 .unwrap()
                 .iter()
@@ -187,13 +292,12 @@ pub fn run_move_unit_tests<W: Write + Send>(
                 .collect::<HashMap<_, _>>()
         })
         .collect();
-    let root_package = resolution_graph.root_package.package.name;
     let build_plan = BuildPlan::create(resolution_graph)?;
     // Compile the package. We need to intercede in the compilation, process being performed by the
     // Move package system, to first grab the compilation env, construct the test plan from it, and
     // then save it, before resuming the rest of the compilation and returning the results and
     // control back to the Move package system.
-    build_plan.compile_with_driver(writer, |compiler| {
+    let compiled_package = build_plan.compile_with_driver(writer, |compiler| {
         let (files, comments_and_compiler_res) = compiler.run::<PASS_CFGIR>().unwrap();
         let (_, compiler) =
             diagnostics::unwrap_or_report_diagnostics(&files, comments_and_compiler_res);
@@ -211,6 +315,19 @@ pub fn run_move_unit_tests<W: Write + Send>(
         Ok((files, units))
     })?;
 
+    let debugger = match debugger {
+        Some(DebuggerOptions {
+            breakpoints,
+            commands_file,
+        }) => Some(Box::new(SourceDebugger::new(
+            &compiled_package,
+            None,
+            breakpoints.clone(),
+            commands_file.as_deref(),
+        )?) as Box<dyn TraceSink + Send>),
+        None => None,
+    };
+
     let (test_plan, mut files, units) = test_plan.unwrap();
     files.extend(dep_file_map);
     let test_plan = test_plan.unwrap();
@@ -238,7 +355,7 @@ pub fn run_move_unit_tests<W: Write + Send>(
     // Run the tests. If any of the tests fail, then we don't produce a coverage report, so cleanup
     // the trace files.
     if !unit_test_config
-        .run_and_report_unit_tests(test_plan, Some(natives), writer)
+        .run_and_report_unit_tests_with_debugger(test_plan, Some(natives), debugger, writer)
         .unwrap()
         .1
     {
@@ -0,0 +1,21 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use super::reroot_path;
+use clap::*;
+use move_package::BuildConfig;
+use std::path::PathBuf;
+
+/// Fetch the latest commits of the git dependencies of the package, and record them in its
+/// lockfile Move.lock.
+#[derive(Parser)]
+#[clap(name = "update-deps")]
+pub struct UpdateDeps;
+
+impl UpdateDeps {
+    pub fn execute(self, path: Option<PathBuf>, config: BuildConfig) -> anyhow::Result<()> {
+        let rerooted_path = reroot_path(path)?;
+        config.update_dependencies(&rerooted_path)?;
+        Ok(())
+    }
+}
@@ -2,8 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use base::{
-    build::Build, coverage::Coverage, disassemble::Disassemble, errmap::Errmap, info::Info,
-    new::New, prove::Prove, test::Test,
+    build::Build, coverage::Coverage, debug::Debug, disassemble::Disassemble, errmap::Errmap,
+    fetch::Fetch, fmt::Fmt, info::Info, lint::Lint, new::New, prove::Prove,
+    publish_package::PublishPackage, test::Test, update_deps::UpdateDeps,
 };
 use move_package::BuildConfig;
 
@@ -63,12 +64,18 @@ pub struct MoveCLI {
 pub enum Command {
     Build(Build),
     Coverage(Coverage),
+    Debug(Debug),
     Disassemble(Disassemble),
     Errmap(Errmap),
+    Fetch(Fetch),
+    Fmt(Fmt),
     Info(Info),
+    Lint(Lint),
     New(New),
     Prove(Prove),
+    PublishPackage(PublishPackage),
     Test(Test),
+    UpdateDeps(UpdateDeps),
     /// Execute a sandbox command.
     #[clap(name = "sandbox")]
     Sandbox {
@@ -101,12 +108,25 @@ pub fn run_cli(
     match cmd {
         Command::Build(c) => c.execute(move_args.package_path, move_args.build_config),
         Command::Coverage(c) => c.execute(move_args.package_path, move_args.build_config),
+        Command::Debug(c) => c.execute(
+            move_args.package_path,
+            move_args.build_config,
+            natives,
+            cost_table,
+            error_descriptions,
+            move_args.verbose,
+        ),
         Command::Disassemble(c) => c.execute(move_args.package_path, move_args.build_config),
         Command::Errmap(c) => c.execute(move_args.package_path, move_args.build_config),
+        Command::Fetch(c) => c.execute(move_args.package_path, move_args.build_config),
+        Command::Fmt(c) => c.execute(move_args.package_path),
         Command::Info(c) => c.execute(move_args.package_path, move_args.build_config),
+        Command::Lint(c) => c.execute(move_args.package_path, move_args.build_config),
         Command::New(c) => c.execute_with_defaults(move_args.package_path),
         Command::Prove(c) => c.execute(move_args.package_path, move_args.build_config),
+        Command::PublishPackage(c) => c.execute(move_args.package_path, move_args.build_config),
         Command::Test(c) => c.execute(move_args.package_path, move_args.build_config, natives),
+        Command::UpdateDeps(c) => c.execute(move_args.package_path, move_args.build_config),
         Command::Sandbox { storage_dir, cmd } => cmd.handle_command(
             natives,
             cost_table,
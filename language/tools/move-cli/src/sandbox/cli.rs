@@ -236,6 +236,7 @@ impl SandboxCommand {
                     *gas_budget,
                     *dry_run,
                     move_args.verbose,
+                    None,
                 )
             }
             SandboxCommand::Test {
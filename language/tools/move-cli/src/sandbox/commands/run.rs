@@ -21,9 +21,10 @@ use move_core_types::{
     value::MoveValue,
 };
 use move_package::compilation::compiled_package::CompiledPackage;
-use move_vm_runtime::move_vm::MoveVM;
+use move_vm_runtime::{move_vm::MoveVM, trace_sink::TraceSink};
 use std::{fs, path::Path};
 
+#[allow(clippy::too_many_arguments)]
 pub fn run(
     natives: impl IntoIterator<Item = NativeFunctionRecord>,
     cost_table: &CostTable,
@@ -38,6 +39,7 @@ pub fn run(
     gas_budget: Option<u64>,
     dry_run: bool,
     verbose: bool,
+    trace_sink: Option<&mut dyn TraceSink>,
 ) -> Result<()> {
     if !script_path.exists() {
         bail!("Script file {:?} does not exist", script_path)
@@ -76,6 +78,9 @@ move run` must be applied to a module inside `storage/`",
     let vm = MoveVM::new(natives).unwrap();
     let mut gas_status = get_gas_status(cost_table, gas_budget)?;
     let mut session = vm.new_session(state);
+    if let Some(trace_sink) = trace_sink {
+        session.set_trace_sink(trace_sink);
+    }
 
     let script_type_parameters = vec![];
     let script_parameters = vec![];
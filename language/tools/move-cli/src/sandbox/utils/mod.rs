@@ -24,6 +24,7 @@ use move_core_types::{
     errmap::ErrorMapping,
     gas_schedule::{GasAlgebra, GasUnits},
     language_storage::{ModuleId, TypeTag},
+    resource_group::apply_resource_group_changes,
     transaction_argument::TransactionArgument,
     vm_status::{AbortLocation, StatusCode, VMStatus},
 };
@@ -253,18 +254,30 @@ pub(crate) fn maybe_commit_effects(
     // shouldn't contain modules
     if commit {
         for (addr, account) in changeset.into_inner() {
-            for (struct_tag, blob_opt) in account.into_resources() {
+            let (_, resources, resource_groups) = account.into_inner_with_groups();
+            for (struct_tag, blob_opt) in resources {
                 match blob_opt {
                     Some(blob) => state.save_resource(addr, struct_tag, &blob)?,
                     None => state.delete_resource(addr, struct_tag)?,
                 }
             }
+            // resource groups are stored like resources, under the tag of the group
+            for (group, changes) in resource_groups {
+                let blob = state.get_resource_bytes(addr, group.clone())?;
+                match apply_resource_group_changes(blob.as_deref(), changes)? {
+                    Some(blob) => state.save_resource(addr, group, &blob)?,
+                    None => state.delete_resource(addr, group)?,
+                }
+            }
         }
 
         for (event_key, event_sequence_number, event_type, event_data) in events {
             state.save_event(&event_key, event_sequence_number, event_type, event_data)?
         }
-    } else if !(changeset.resources().next().is_none() && events.is_empty()) {
+    } else if !(changeset.resources().next().is_none()
+        && changeset.resource_group_members().next().is_none()
+        && events.is_empty())
+    {
         println!("Discarding changes; re-run without --dry-run if you would like to keep them.")
     }
 
@@ -0,0 +1,6 @@
+module 0x42::M {
+  /// Adds one to `x`.
+  public fun inc(x:u64):u64 {
+      x+1 // no overflow check
+  }
+}
@@ -0,0 +1,16 @@
+module 0x1::M {
+    fun f(x: u64): u64 {
+        while (true) { break };
+        x = x;
+        x
+    }
+
+    fun g(): u64 {
+        let i = 0;
+        while (true) {
+            i = i + 1;
+            if (i == 10) break
+        };
+        *&i
+    }
+}
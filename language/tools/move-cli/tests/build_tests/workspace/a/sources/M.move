@@ -0,0 +1,3 @@
+module A::M {
+    public fun f(): u64 { 1 }
+}
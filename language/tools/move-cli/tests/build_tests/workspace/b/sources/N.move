@@ -0,0 +1,3 @@
+module B::N {
+    public fun g(): u64 { A::M::f() + 1 }
+}
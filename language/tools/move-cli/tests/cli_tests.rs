@@ -52,3 +52,28 @@ fn cross_process_locking_git_deps() {
         .expect("Package2 failed");
     handle.join().unwrap();
 }
+
+#[test]
+fn json_diagnostics_are_alone_on_stdout() {
+    let cli_exe = env!("CARGO_BIN_EXE_move");
+    let output = std::process::Command::new(cli_exe)
+        .current_dir("./tests/diagnostics_format_tests/App")
+        .args(["build", "--diagnostics-format", "json"])
+        .env("NO_COLOR", "1")
+        .output()
+        .expect("App failed");
+    assert!(output.status.success());
+
+    // The progress of the build is reported on stderr
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("INCLUDING DEPENDENCY Dep"), "{}", stderr);
+    assert!(stderr.contains("BUILDING App"), "{}", stderr);
+
+    let document: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let diagnostics = document["diagnostics"].as_array().unwrap();
+    assert_eq!(diagnostics.len(), 1, "{}", document);
+    assert!(diagnostics[0]["primary_label"]["location"]["file"]
+        .as_str()
+        .unwrap()
+        .ends_with("A.move"));
+}
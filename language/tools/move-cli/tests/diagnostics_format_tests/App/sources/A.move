@@ -0,0 +1,8 @@
+module App::A {
+    use Dep::D;
+
+    public fun two(): u64 {
+        let unused = 0;
+        D::one() + D::one()
+    }
+}
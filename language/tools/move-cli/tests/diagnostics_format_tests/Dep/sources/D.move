@@ -0,0 +1,3 @@
+module Dep::D {
+    public fun one(): u64 { 1 }
+}
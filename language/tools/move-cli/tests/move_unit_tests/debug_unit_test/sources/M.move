@@ -0,0 +1,14 @@
+module 0x2::M {
+    fun add_two(x: u64): u64 {
+        x + 2
+    }
+
+    #[test]
+    fun test_add_two() {
+        let y = add_two(1);
+        assert!(y == 3, 42);
+    }
+
+    #[test]
+    fun other() {}
+}
@@ -0,0 +1,5 @@
+module 0x2::A {
+    public fun one(): u64 {
+        1
+    }
+}
@@ -0,0 +1,12 @@
+module 0x2::B {
+    public fun two(): u64 {
+        2
+    }
+
+    #[test]
+    fun test_sum() {
+        let x = 0x2::A::one();
+        let y = two();
+        assert!(x + y == 3, 42);
+    }
+}
@@ -0,0 +1,8 @@
+module A::M {
+    public fun f(): u64 { 1 }
+
+    #[test]
+    fun test_f() {
+        assert!(f() == 1, 0)
+    }
+}
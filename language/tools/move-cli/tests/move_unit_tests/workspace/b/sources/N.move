@@ -0,0 +1,14 @@
+module B::N {
+    public fun g(): u64 { A::M::f() + 1 }
+
+    #[test]
+    fun test_g() {
+        assert!(g() == 2, 0)
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0)]
+    fun test_g_fails() {
+        assert!(g() == 1, 0)
+    }
+}
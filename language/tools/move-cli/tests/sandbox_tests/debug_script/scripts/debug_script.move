@@ -0,0 +1,7 @@
+script {
+    fun main() {
+        let x = 1;
+        let y = 0x2::Math::add_two(x);
+        assert!(y == 3, 42);
+    }
+}
@@ -0,0 +1,5 @@
+module 0x2::Math {
+    public fun add_two(x: u64): u64 {
+        x + 2
+    }
+}
@@ -8,10 +8,10 @@ use move_binary_format::{
     binary_views::BinaryIndexedView,
     control_flow_graph::{ControlFlowGraph, VMControlFlowGraph},
     file_format::{
-        Ability, AbilitySet, Bytecode, CodeUnit, FieldHandleIndex, FunctionDefinition,
-        FunctionDefinitionIndex, FunctionHandle, Signature, SignatureIndex, SignatureToken,
-        StructDefinition, StructDefinitionIndex, StructFieldInformation, StructTypeParameter,
-        TableIndex, TypeSignature, Visibility,
+        Ability, AbilitySet, Bytecode, CodeUnit, FieldDefinition, FieldHandleIndex,
+        FunctionDefinition, FunctionDefinitionIndex, FunctionHandle, Signature, SignatureIndex,
+        SignatureToken, StructDefInstantiationIndex, StructDefinition, StructDefinitionIndex,
+        StructFieldInformation, StructTypeParameter, TableIndex, VariantIndex, Visibility,
     },
 };
 use move_bytecode_source_map::{
@@ -220,6 +220,9 @@ impl<'a> Disassembler<'a> {
             StructFieldInformation::Declared(fields) => fields
                 .get(field_handle.field as usize)
                 .ok_or_else(|| format_err!("Bad field index"))?,
+            StructFieldInformation::DeclaredVariants(_) => {
+                return Err(format_err!("Attempt to access field on an enum"));
+            }
         };
         let field_name = self
             .source_mapper
@@ -251,6 +254,9 @@ impl<'a> Disassembler<'a> {
             StructFieldInformation::Declared(fields) => fields
                 .get(field_handle.field as usize)
                 .ok_or_else(|| format_err!("Bad field index"))?,
+            StructFieldInformation::DeclaredVariants(_) => {
+                return Err(format_err!("Attempt to access field on an enum"));
+            }
         };
         let struct_source_info = self
             .source_mapper
@@ -286,6 +292,42 @@ impl<'a> Disassembler<'a> {
         Ok((name, Self::format_type_params(&type_arguments)))
     }
 
+    fn variant_type_info(
+        &self,
+        struct_idx: StructDefinitionIndex,
+        variant: VariantIndex,
+        signature: &Signature,
+        type_param_context: &[SourceName],
+    ) -> Result<String> {
+        let (name, ty_params) = self.struct_type_info(struct_idx, signature, type_param_context)?;
+        let variant_definition = self
+            .get_struct_def(struct_idx)?
+            .variant(variant)
+            .ok_or_else(|| format_err!("Bad variant index"))?;
+        let variant_name = self
+            .source_mapper
+            .bytecode
+            .identifier_at(variant_definition.name);
+        Ok(format!("{}{}::{}", name, ty_params, variant_name))
+    }
+
+    fn variant_instantiation_type_info(
+        &self,
+        struct_idx: StructDefInstantiationIndex,
+        variant: VariantIndex,
+        type_param_context: &[SourceName],
+    ) -> Result<String> {
+        let struct_inst = self
+            .source_mapper
+            .bytecode
+            .struct_instantiation_at(struct_idx)?;
+        let type_params = self
+            .source_mapper
+            .bytecode
+            .signature_at(struct_inst.type_parameters);
+        self.variant_type_info(struct_inst.def, variant, type_params, type_param_context)
+    }
+
     fn name_for_parameter_or_local(
         &self,
         local_idx: usize,
@@ -386,8 +428,11 @@ impl<'a> Disassembler<'a> {
         Ok(match sig_tok {
             SignatureToken::Bool => "bool".to_string(),
             SignatureToken::U8 => "u8".to_string(),
+            SignatureToken::U16 => "u16".to_string(),
+            SignatureToken::U32 => "u32".to_string(),
             SignatureToken::U64 => "u64".to_string(),
             SignatureToken::U128 => "u128".to_string(),
+            SignatureToken::U256 => "u256".to_string(),
             SignatureToken::Address => "address".to_string(),
             SignatureToken::Signer => "signer".to_string(),
             SignatureToken::Struct(struct_handle_idx) => self
@@ -602,6 +647,75 @@ impl<'a> Disassembler<'a> {
                     struct_idx, name, ty_params
                 ))
             }
+            Bytecode::PackVariant(struct_idx, variant) => {
+                let name = self.variant_type_info(
+                    *struct_idx,
+                    *variant,
+                    &Signature(vec![]),
+                    &function_source_map.type_parameters,
+                )?;
+                Ok(format!(
+                    "PackVariant[{}, {}]({})",
+                    struct_idx, variant, name
+                ))
+            }
+            Bytecode::PackVariantGeneric(struct_idx, variant) => {
+                let name = self.variant_instantiation_type_info(
+                    *struct_idx,
+                    *variant,
+                    &function_source_map.type_parameters,
+                )?;
+                Ok(format!(
+                    "PackVariantGeneric[{}, {}]({})",
+                    struct_idx, variant, name
+                ))
+            }
+            Bytecode::UnpackVariant(struct_idx, variant) => {
+                let name = self.variant_type_info(
+                    *struct_idx,
+                    *variant,
+                    &Signature(vec![]),
+                    &function_source_map.type_parameters,
+                )?;
+                Ok(format!(
+                    "UnpackVariant[{}, {}]({})",
+                    struct_idx, variant, name
+                ))
+            }
+            Bytecode::UnpackVariantGeneric(struct_idx, variant) => {
+                let name = self.variant_instantiation_type_info(
+                    *struct_idx,
+                    *variant,
+                    &function_source_map.type_parameters,
+                )?;
+                Ok(format!(
+                    "UnpackVariantGeneric[{}, {}]({})",
+                    struct_idx, variant, name
+                ))
+            }
+            Bytecode::TestVariant(struct_idx, variant) => {
+                let name = self.variant_type_info(
+                    *struct_idx,
+                    *variant,
+                    &Signature(vec![]),
+                    &function_source_map.type_parameters,
+                )?;
+                Ok(format!(
+                    "TestVariant[{}, {}]({})",
+                    struct_idx, variant, name
+                ))
+            }
+            Bytecode::TestVariantGeneric(struct_idx, variant) => {
+                let name = self.variant_instantiation_type_info(
+                    *struct_idx,
+                    *variant,
+                    &function_source_map.type_parameters,
+                )?;
+                Ok(format!(
+                    "TestVariantGeneric[{}, {}]({})",
+                    struct_idx, variant, name
+                ))
+            }
             Bytecode::Exists(struct_idx) => {
                 let (name, ty_params) = self.struct_type_info(
                     *struct_idx,
@@ -1085,25 +1199,10 @@ impl<'a> Disassembler<'a> {
             .source_map
             .get_struct_source_map(struct_def_idx)?;
 
-        let field_info: Option<Vec<(&IdentStr, &TypeSignature)>> =
-            match &struct_definition.field_information {
-                StructFieldInformation::Native => None,
-                StructFieldInformation::Declared(fields) => Some(
-                    fields
-                        .iter()
-                        .map(|field_definition| {
-                            let type_sig = &field_definition.signature;
-                            let field_name = self
-                                .source_mapper
-                                .bytecode
-                                .identifier_at(field_definition.name);
-                            (field_name, type_sig)
-                        })
-                        .collect(),
-                ),
-            };
-
-        let native = if field_info.is_none() { "native " } else { "" };
+        let native = match &struct_definition.field_information {
+            StructFieldInformation::Native => "native ",
+            StructFieldInformation::Declared(_) | StructFieldInformation::DeclaredVariants(_) => "",
+        };
 
         let abilities = if struct_handle.abilities == AbilitySet::EMPTY {
             String::new()
@@ -1126,16 +1225,40 @@ impl<'a> Disassembler<'a> {
             &struct_source_map.type_parameters,
             &struct_handle.type_parameters,
         );
-        let mut fields = match field_info {
-            None => vec![],
-            Some(field_info) => field_info
+        let disassemble_fields = |fields: &[FieldDefinition]| {
+            fields
                 .iter()
-                .map(|(name, ty)| {
-                    let ty_str =
-                        self.disassemble_sig_tok(ty.0.clone(), &struct_source_map.type_parameters)?;
-                    Ok(format!("{}: {}", name, ty_str))
+                .map(|field_definition| {
+                    let field_name = self
+                        .source_mapper
+                        .bytecode
+                        .identifier_at(field_definition.name);
+                    let ty_str = self.disassemble_sig_tok(
+                        field_definition.signature.0.clone(),
+                        &struct_source_map.type_parameters,
+                    )?;
+                    Ok(format!("{}: {}", field_name, ty_str))
                 })
-                .collect::<Result<Vec<String>>>()?,
+                .collect::<Result<Vec<String>>>()
+        };
+        let (kind, mut fields) = match &struct_definition.field_information {
+            StructFieldInformation::Native => ("struct", vec![]),
+            StructFieldInformation::Declared(fields) => ("struct", disassemble_fields(fields)?),
+            StructFieldInformation::DeclaredVariants(variants) => (
+                "enum",
+                variants
+                    .iter()
+                    .map(|variant| {
+                        let variant_name = self.source_mapper.bytecode.identifier_at(variant.name);
+                        let fields = disassemble_fields(&variant.fields)?;
+                        Ok(if fields.is_empty() {
+                            variant_name.to_string()
+                        } else {
+                            format!("{} {{ {} }}", variant_name, fields.join(", "))
+                        })
+                    })
+                    .collect::<Result<Vec<String>>>()?,
+            ),
         };
 
         if let Some(first_elem) = fields.first_mut() {
@@ -1147,8 +1270,9 @@ impl<'a> Disassembler<'a> {
         }
 
         Ok(format!(
-            "{native}struct {name}{ty_params}{abilities} {fields}",
+            "{native}{kind} {name}{ty_params}{abilities} {fields}",
             native = native,
+            kind = kind,
             name = name,
             ty_params = ty_params,
             abilities = abilities,
@@ -2,13 +2,17 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    compilation::compiled_package::CompiledPackage, resolution::resolution_graph::ResolvedGraph,
+    compilation::{compiled_package::CompiledPackage, incremental::SharedUnits},
+    resolution::resolution_graph::ResolvedGraph,
     source_package::parsed_manifest::PackageName,
 };
 use anyhow::Result;
 use move_compiler::{
     compiled_unit::AnnotatedCompiledUnit,
-    diagnostics::{report_diagnostics_to_color_buffer, report_warnings, FilesSourceText},
+    diagnostics::{
+        format_diagnostics, report_diagnostics_to_color_buffer, report_warnings_with_format,
+        structured::DiagnosticsFormat, FilesSourceText,
+    },
     Compiler,
 };
 use petgraph::algo::toposort;
@@ -84,6 +88,12 @@ fn should_recompile(
 
 impl BuildPlan {
     pub fn create(resolution_graph: ResolvedGraph) -> Result<Self> {
+        if resolution_graph.is_workspace() {
+            anyhow::bail!(
+                "'{}' is a workspace; build each of its members instead",
+                resolution_graph.root_package_path.to_string_lossy()
+            )
+        }
         let mut sorted_deps = match toposort(&resolution_graph.graph, None) {
             Ok(nodes) => nodes,
             Err(err) => {
@@ -103,21 +113,42 @@ impl BuildPlan {
 
     /// Compilation results in the process exit upon warning/failure
     pub fn compile<W: Write>(&self, writer: &mut W) -> Result<CompiledPackage> {
-        self.compile_with_driver(writer, |compiler| compiler.build_and_report())
+        self.compile_with_driver_impl(writer, true, None, |compiler| compiler.build_and_report())
+    }
+
+    /// Compiles a member of a workspace like `compile`. The packages already compiled by the
+    /// builds of other members are taken from `shared_units`, and the packages compiled by this
+    /// build are added to it.
+    pub fn compile_with_shared_units<W: Write>(
+        &self,
+        writer: &mut W,
+        shared_units: &mut SharedUnits,
+    ) -> Result<CompiledPackage> {
+        self.compile_with_driver_impl(writer, true, Some(shared_units), |compiler| {
+            compiler.build_and_report()
+        })
     }
 
     /// Compilation process does not exit even if warnings/failures are encountered
     pub fn compile_no_exit<W: Write>(&self, writer: &mut W) -> Result<CompiledPackage> {
-        self.compile_with_driver(writer, |compiler| {
+        let format = self.resolution_graph.build_options.diagnostics_format;
+        self.compile_with_driver_impl(writer, true, None, |compiler| {
             let (files, units_res) = compiler.build()?;
             match units_res {
                 Ok((units, warning_diags)) => {
-                    report_warnings(&files, warning_diags);
+                    report_warnings_with_format(&files, warning_diags, format);
                     Ok((files, units))
                 }
                 Err(error_diags) => {
                     assert!(!error_diags.is_empty());
-                    let diags_buf = report_diagnostics_to_color_buffer(&files, error_diags);
+                    let diags_buf = match format {
+                        DiagnosticsFormat::Human => {
+                            report_diagnostics_to_color_buffer(&files, error_diags)
+                        }
+                        DiagnosticsFormat::Json | DiagnosticsFormat::Sarif => {
+                            format_diagnostics(&files, error_diags, format).into_bytes()
+                        }
+                    };
                     if let Err(err) = std::io::stdout().write_all(&diags_buf) {
                         anyhow::bail!("Cannot output compiler diagnostics: {}", err);
                     }
@@ -127,9 +158,26 @@ impl BuildPlan {
         })
     }
 
+    /// Compiles all packages with `compiler_driver`, which is given the whole program. Units from
+    /// previous builds are not reused.
     pub fn compile_with_driver<W: Write>(
         &self,
         writer: &mut W,
+        compiler_driver: impl FnMut(
+            Compiler,
+        )
+            -> anyhow::Result<(FilesSourceText, Vec<AnnotatedCompiledUnit>)>,
+    ) -> Result<CompiledPackage> {
+        self.compile_with_driver_impl(writer, false, None, compiler_driver)
+    }
+
+    /// If `incremental` is set, units compiled by a previous build are reused for the files that
+    /// are unaffected by changes since then, and `compiler_driver` is only given the others.
+    fn compile_with_driver_impl<W: Write>(
+        &self,
+        writer: &mut W,
+        incremental: bool,
+        shared_units: Option<&mut SharedUnits>,
         mut compiler_driver: impl FnMut(
             Compiler,
         )
@@ -169,6 +217,8 @@ impl BuildPlan {
             root_package.clone(),
             transitive_dependencies,
             &self.resolution_graph,
+            incremental,
+            shared_units,
             &mut compiler_driver,
         )?;
 
@@ -2,15 +2,18 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    compilation::package_layout::CompiledPackageLayout,
+    compilation::{
+        incremental::{self, IncrementalCache, SharedUnits},
+        package_layout::CompiledPackageLayout,
+    },
     resolution::resolution_graph::{Renaming, ResolvedGraph, ResolvedPackage, ResolvedTable},
     source_package::{
         layout::{SourcePackageLayout, REFERENCE_TEMPLATE_FILENAME},
-        parsed_manifest::{FileName, PackageDigest, PackageName},
+        parsed_manifest::{BuildInfo, FileName, PackageDigest, PackageName},
     },
     BuildConfig,
 };
-use anyhow::{ensure, Result};
+use anyhow::{ensure, format_err, Result};
 use colored::Colorize;
 use move_abigen::{Abigen, AbigenOptions};
 use move_binary_format::file_format::{CompiledModule, CompiledScript};
@@ -27,7 +30,8 @@ use move_compiler::{
     compiled_unit::{
         self, AnnotatedCompiledUnit, CompiledUnit, NamedCompiledModule, NamedCompiledScript,
     },
-    diagnostics::FilesSourceText,
+    diagnostics::{codes::WarningFilter, structured::DiagnosticsFormat, FilesSourceText},
+    linters::LintConfig,
     shared::{Flags, NamedAddressMap, NumericalAddress, PackagePaths},
     Compiler,
 };
@@ -202,7 +206,7 @@ impl OnDiskCompiledPackage {
         })
     }
 
-    fn decode_unit(
+    pub(crate) fn decode_unit(
         &self,
         package_name: Symbol,
         bytecode_path_str: &str,
@@ -318,33 +322,48 @@ impl OnDiskCompiledPackage {
         })
     }
 
-    fn save_compiled_unit(
+    /// Returns the path, relative to `root_path`, under which the bytecode of `compiled_unit` of
+    /// package `package_name` is saved.
+    pub(crate) fn compiled_unit_path(
         &self,
         package_name: Symbol,
-        compiled_unit: &CompiledUnitWithSource,
-    ) -> Result<()> {
-        let root_package = self.package.compiled_package_info.package_name;
-        assert!(self.root_path.ends_with(root_package.as_str()));
-        let category_dir = match &compiled_unit.unit {
+        compiled_unit: &CompiledUnit,
+    ) -> PathBuf {
+        let category_dir = match compiled_unit {
             CompiledUnit::Script(_) => CompiledPackageLayout::CompiledScripts.path(),
             CompiledUnit::Module(_) => CompiledPackageLayout::CompiledModules.path(),
         };
-        let file_path = if root_package == package_name {
+        category_dir
+            .join(self.unit_file_path(package_name, compiled_unit))
+            .with_extension(MOVE_COMPILED_EXTENSION)
+    }
+
+    fn unit_file_path(&self, package_name: Symbol, compiled_unit: &CompiledUnit) -> PathBuf {
+        let root_package = self.package.compiled_package_info.package_name;
+        if root_package == package_name {
             PathBuf::new()
         } else {
             CompiledPackageLayout::Dependencies
                 .path()
                 .join(package_name.as_str())
         }
-        .join(match &compiled_unit.unit {
+        .join(match compiled_unit {
             CompiledUnit::Script(named) => named.name.as_str(),
             CompiledUnit::Module(named) => named.name.as_str(),
-        });
+        })
+    }
+
+    fn save_compiled_unit(
+        &self,
+        package_name: Symbol,
+        compiled_unit: &CompiledUnitWithSource,
+    ) -> Result<()> {
+        let root_package = self.package.compiled_package_info.package_name;
+        assert!(self.root_path.ends_with(root_package.as_str()));
+        let file_path = self.unit_file_path(package_name, &compiled_unit.unit);
 
         self.save_under(
-            category_dir
-                .join(&file_path)
-                .with_extension(MOVE_COMPILED_EXTENSION),
+            self.compiled_unit_path(package_name, &compiled_unit.unit),
             compiled_unit
                 .unit
                 .serialize(get_bytecode_version_from_env())
@@ -494,6 +513,8 @@ impl CompiledPackage {
             /* address mapping */ &ResolvedTable,
         )>,
         resolution_graph: &ResolvedGraph,
+        incremental: bool,
+        mut shared_units: Option<&mut SharedUnits>,
         mut compiler_driver: impl FnMut(
             Compiler,
         )
@@ -510,16 +531,11 @@ impl CompiledPackage {
                 (name, source_paths, address_mapping)
             })
             .collect::<Vec<_>>();
-        for (dep_package_name, _, _) in &transitive_dependencies {
-            writeln!(
-                w,
-                "{} {}",
-                "INCLUDING DEPENDENCY".bold().green(),
-                dep_package_name
-            )?;
-        }
+        let dep_package_names = transitive_dependencies
+            .iter()
+            .map(|(name, _, _)| *name)
+            .collect::<Vec<_>>();
         let root_package_name = resolved_package.source_package.package.name;
-        writeln!(w, "{} {}", "BUILDING".bold().green(), root_package_name)?;
 
         // gather source/dep files with their address mappings
         let (sources_package_paths, deps_package_paths) = make_source_and_deps_for_compiler(
@@ -527,29 +543,118 @@ impl CompiledPackage {
             &resolved_package,
             transitive_dependencies,
         )?;
-        let flags = if resolution_graph.build_options.test_mode {
+        let mut paths = deps_package_paths.clone();
+        paths.push(sources_package_paths.clone());
+
+        // Packages already compiled by the build of another workspace member are not compiled
+        // again
+        let (compiled_paths, reused) = match shared_units.as_deref() {
+            Some(shared_units) => shared_units.partition(&paths),
+            None => (paths.clone(), vec![]),
+        };
+        let (shared_deps, shared_compiled_units) = match shared_units.as_deref() {
+            Some(shared_units) => (
+                shared_units.compiler_deps(&reused),
+                shared_units.units(&reused),
+            ),
+            None => (vec![], vec![]),
+        };
+        // The JSON and SARIF documents are written to stdout, which must not contain anything else
+        let mut stderr = std::io::stderr();
+        let progress: &mut dyn Write = match resolution_graph.build_options.diagnostics_format {
+            DiagnosticsFormat::Human => &mut *w,
+            DiagnosticsFormat::Json | DiagnosticsFormat::Sarif => &mut stderr,
+        };
+        for dep_package_name in &dep_package_names {
+            let status = if reused.contains(dep_package_name) {
+                "REUSING DEPENDENCY"
+            } else {
+                "INCLUDING DEPENDENCY"
+            };
+            writeln!(progress, "{} {}", status.bold().green(), dep_package_name)?;
+        }
+        writeln!(
+            progress,
+            "{} {}",
+            "BUILDING".bold().green(),
+            root_package_name
+        )?;
+
+        let mut flags = if resolution_graph.build_options.test_mode {
             Flags::testing()
         } else {
             Flags::empty()
         };
-        // invoke the compiler
-        let mut paths = deps_package_paths.clone();
-        paths.push(sources_package_paths.clone());
+        if resolution_graph.build_options.lint {
+            flags = flags.set_lint(true).set_lint_config(LintConfig {
+                levels: resolved_package.source_package.lints.clone(),
+                package: Some(root_package_name),
+            });
+        }
+        flags = set_warning_flags(
+            flags,
+            &resolution_graph.build_options,
+            resolved_package.source_package.build.as_ref(),
+        )?
+        .set_diagnostics_format(resolution_graph.build_options.diagnostics_format);
 
-        let compiler = Compiler::from_package_paths(paths, vec![]).set_flags(flags);
-        let (file_map, all_compiled_units) = compiler_driver(compiler)?;
+        // Units can only be reused from the last build if the whole program does not need to be
+        // checked, which is the case in test mode and when linting
+        let build_options = &resolution_graph.build_options;
+        let incremental = incremental && !build_options.test_mode && !build_options.lint;
+        let config_digest = incremental::config_digest(build_options, &flags, &paths);
+        let build_root = project_root
+            .join(CompiledPackageLayout::Root.path())
+            .join(root_package_name.as_str());
+        let incremental_units = if incremental && !build_options.force_recompilation {
+            incremental::build(
+                &build_root,
+                &config_digest,
+                &compiled_paths,
+                &shared_deps,
+                &shared_compiled_units,
+                flags.clone(),
+                &mut compiler_driver,
+            )?
+        } else {
+            None
+        };
+        let all_compiled_units = match incremental_units {
+            Some(units) => units,
+            None => {
+                // invoke the compiler
+                let compiler = Compiler::from_package_paths(compiled_paths.clone(), shared_deps)
+                    .set_flags(flags);
+                let (file_map, all_compiled_units) = compiler_driver(compiler)?;
+                all_compiled_units
+                    .into_iter()
+                    .map(|annot_unit| {
+                        let source_path =
+                            PathBuf::from(file_map[&annot_unit.loc().file_hash()].0.as_str());
+                        let package_name = match &annot_unit {
+                            compiled_unit::CompiledUnitEnum::Module(m) => {
+                                m.named_module.package_name.unwrap()
+                            }
+                            compiled_unit::CompiledUnitEnum::Script(s) => {
+                                s.named_script.package_name.unwrap()
+                            }
+                        };
+                        let unit = CompiledUnitWithSource {
+                            unit: annot_unit.into_compiled_unit(),
+                            source_path,
+                        };
+                        (package_name, unit)
+                    })
+                    .chain(shared_compiled_units)
+                    .collect::<Vec<_>>()
+            }
+        };
+        if let Some(shared_units) = shared_units.as_deref_mut() {
+            shared_units.record(&compiled_paths, &all_compiled_units)?;
+        }
         let mut root_compiled_units = vec![];
         let mut deps_compiled_units = vec![];
-        for annot_unit in all_compiled_units {
-            let source_path = PathBuf::from(file_map[&annot_unit.loc().file_hash()].0.as_str());
-            let package_name = match &annot_unit {
-                compiled_unit::CompiledUnitEnum::Module(m) => m.named_module.package_name.unwrap(),
-                compiled_unit::CompiledUnitEnum::Script(s) => s.named_script.package_name.unwrap(),
-            };
-            let unit = CompiledUnitWithSource {
-                unit: annot_unit.into_compiled_unit(),
-                source_path,
-            };
+        for (package_name, unit) in all_compiled_units {
             if package_name == root_package_name {
                 root_compiled_units.push(unit)
             } else {
@@ -600,7 +705,25 @@ impl CompiledPackage {
             compiled_abis,
         };
 
-        compiled_package.save_to_disk(project_root.join(CompiledPackageLayout::Root.path()))?;
+        let on_disk_package =
+            compiled_package.save_to_disk(project_root.join(CompiledPackageLayout::Root.path()))?;
+        if incremental {
+            IncrementalCache::save(
+                &on_disk_package,
+                config_digest,
+                &paths,
+                compiled_package
+                    .root_compiled_units
+                    .iter()
+                    .map(|unit| (root_package_name, unit))
+                    .chain(
+                        compiled_package
+                            .deps_compiled_units
+                            .iter()
+                            .map(|(package_name, unit)| (*package_name, unit)),
+                    ),
+            )?;
+        }
 
         Ok(compiled_package)
     }
@@ -807,6 +930,33 @@ impl CompiledPackage {
     }
 }
 
+/// Sets how warnings are reported from the `[build]` section of the root manifest, extended by the
+/// build options
+fn set_warning_flags(
+    flags: Flags,
+    build_options: &BuildConfig,
+    build_info: Option<&BuildInfo>,
+) -> Result<Flags> {
+    let parse_filters = |filters: &[String]| {
+        filters
+            .iter()
+            .map(|filter| filter.parse().map_err(|e: String| format_err!(e)))
+            .collect::<Result<Vec<WarningFilter>>>()
+    };
+    let mut allow = parse_filters(&build_options.allow)?;
+    let mut deny = parse_filters(&build_options.deny)?;
+    let mut warnings_are_errors = build_options.warnings_are_errors;
+    if let Some(build_info) = build_info {
+        allow.extend(build_info.allow.iter().copied());
+        deny.extend(build_info.deny.iter().copied());
+        warnings_are_errors |= build_info.warnings_are_errors;
+    }
+    Ok(flags
+        .set_warnings_are_errors(warnings_are_errors)
+        .set_allowed_warnings(allow)
+        .set_denied_warnings(deny))
+}
+
 pub(crate) fn named_address_mapping_for_compiler(
     resolution_table: &ResolvedTable,
 ) -> BTreeMap<Symbol, NumericalAddress> {
@@ -0,0 +1,664 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Incremental compilation of the packages in a build plan.
+//!
+//! Every source file compiled by a build is recorded in an `IncrementalCache` in the build
+//! directory of the root package, along with the hash of its contents, the units compiled from it,
+//! and the hashes of the public interfaces of the modules those units depend on. The interface of
+//! a module is its interface file, as generated by `move_compiler::interface_generator`.
+//!
+//! On the next build, a file is only given to the compiler as a target if its contents changed, or
+//! if the interface of one of its dependencies changed. Unchanged modules are given to the
+//! compiler as interface files generated from their cached bytecode, so their bodies are neither
+//! parsed nor type checked again. Files defining inline functions, public constants or deprecated
+//! items are given as source instead, as their interface files would lack those, and changing one
+//! of them rebuilds everything, as the other files may contain a copy of them or be warned about
+//! their use. Whenever something unexpected happens (e.g. the changed files do not compile), the
+//! packages are built from scratch instead, so that diagnostics are the same as for a full build.
+//!
+//! The members of a workspace are built one after the other, and the packages compiled by the
+//! build of a member are recorded in `SharedUnits`. The builds of the other members are given the
+//! interfaces of those packages in the same way, and reuse their units, so that the dependencies
+//! members have in common are only compiled once.
+
+use crate::{
+    compilation::{
+        compiled_package::{CompiledUnitWithSource, OnDiskCompiledPackage},
+        package_layout::CompiledPackageLayout,
+    },
+    source_package::parsed_manifest::PackageName,
+    BuildConfig,
+};
+use anyhow::Result;
+use move_binary_format::{
+    access::{ModuleAccess, ScriptAccess},
+    file_format::CompiledModule,
+};
+use move_command_line_common::files::{FileHash, MOVE_EXTENSION};
+use move_compiler::{
+    compiled_unit::{AnnotatedCompiledUnit, CompiledUnit},
+    diagnostics::{report_warnings_with_format, Diagnostics, FilesSourceText},
+    interface_generator::write_module_to_string,
+    parser::{
+        ast::INLINE_MODIFIER,
+        lexer::{Lexer, Tok},
+    },
+    shared::{known_attributes::DeprecationAttribute, Flags, PackagePaths},
+    Compiler,
+};
+use move_core_types::language_storage::ModuleId;
+use move_symbol_pool::Symbol;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
+};
+use tempfile::TempDir;
+
+/// What is known about the source files compiled by the last build of a package.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IncrementalCache {
+    /// The hash of the build flags and named address mappings used by the build
+    pub config_digest: String,
+    /// The compiled source files, by path
+    pub files: BTreeMap<String, CachedFile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedFile {
+    /// The package the file belongs to
+    pub package_name: PackageName,
+    /// The hash of the contents of the file
+    pub content_hash: String,
+    /// Whether the file must be given to the compiler as source when compiling other files, as it
+    /// defines inline functions, public constants or deprecated items, which are not part of its
+    /// interface.
+    pub requires_source: bool,
+    /// The units compiled from the file
+    pub units: Vec<CachedUnit>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedUnit {
+    /// The path of the bytecode of the unit, relative to the build directory of the root package
+    pub bytecode_path: PathBuf,
+    /// The hash of the interface of the unit. `None` for scripts.
+    pub interface_hash: Option<String>,
+    /// The hashes of the interfaces of the modules the unit depends on, at the time it was compiled
+    pub dependencies: BTreeMap<String, String>,
+}
+
+/// A cached source file whose units can be reused
+struct CleanFile {
+    package_name: PackageName,
+    requires_source: bool,
+    units: Vec<CompiledUnitWithSource>,
+    dependencies: BTreeMap<String, String>,
+}
+
+/// The units of the packages compiled so far while building the members of a workspace
+pub struct SharedUnits {
+    /// The directory of the interface files of the packages
+    interfaces_dir: TempDir,
+    packages: BTreeMap<PackageName, SharedPackage>,
+}
+
+struct SharedPackage {
+    /// The source files and named addresses the package was compiled with
+    paths: PackagePaths,
+    /// The package paths to give to the compiler to compile against the package
+    deps: PackagePaths,
+    units: Vec<CompiledUnitWithSource>,
+}
+
+impl SharedUnits {
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            interfaces_dir: tempfile::tempdir()?,
+            packages: BTreeMap::new(),
+        })
+    }
+
+    /// Returns the packages to compile among `packages`, and the names of those which were
+    /// already compiled with the same source files and named addresses.
+    pub(crate) fn partition(
+        &self,
+        packages: &[PackagePaths],
+    ) -> (Vec<PackagePaths>, Vec<PackageName>) {
+        let mut compiled = vec![];
+        let mut reused = vec![];
+        // The source files of a package are not listed in the same order when it is the root
+        let source_files =
+            |package: &PackagePaths| package.paths.iter().copied().collect::<BTreeSet<Symbol>>();
+        for package in packages {
+            match self.packages.get(&package.name.unwrap()) {
+                Some(shared)
+                    if source_files(&shared.paths) == source_files(package)
+                        && shared.paths.named_address_map == package.named_address_map =>
+                {
+                    reused.push(package.name.unwrap())
+                }
+                _ => compiled.push(package.clone()),
+            }
+        }
+        (compiled, reused)
+    }
+
+    /// Returns the package paths to give to the compiler as dependencies to compile against the
+    /// `reused` packages
+    pub(crate) fn compiler_deps(&self, reused: &[PackageName]) -> Vec<PackagePaths> {
+        reused
+            .iter()
+            .map(|name| self.packages[name].deps.clone())
+            .collect()
+    }
+
+    /// Returns the units of the `reused` packages
+    pub(crate) fn units(
+        &self,
+        reused: &[PackageName],
+    ) -> Vec<(PackageName, CompiledUnitWithSource)> {
+        reused
+            .iter()
+            .flat_map(|name| {
+                self.packages[name]
+                    .units
+                    .iter()
+                    .map(move |unit| (*name, unit.clone()))
+            })
+            .collect()
+    }
+
+    /// Records the units compiled for `packages`, unless a package was already recorded
+    pub(crate) fn record(
+        &mut self,
+        packages: &[PackagePaths],
+        compiled_units: &[(PackageName, CompiledUnitWithSource)],
+    ) -> Result<()> {
+        for package in packages {
+            let package_name = package.name.unwrap();
+            if self.packages.contains_key(&package_name) {
+                continue;
+            }
+            let mut files = BTreeMap::new();
+            for path in &package.paths {
+                let contents = std::fs::read_to_string(path.as_str())?;
+                let units = compiled_units
+                    .iter()
+                    .filter(|(name, unit)| {
+                        *name == package_name && unit.source_path.to_string_lossy() == path.as_str()
+                    })
+                    .map(|(_, unit)| unit.clone())
+                    .collect();
+                files.insert(
+                    *path,
+                    CleanFile {
+                        package_name,
+                        requires_source: requires_source(&contents),
+                        units,
+                        dependencies: BTreeMap::new(),
+                    },
+                );
+            }
+            let (_, mut deps) = compiler_paths(
+                std::slice::from_ref(package),
+                &BTreeSet::new(),
+                &files,
+                self.interfaces_dir.path(),
+            )?;
+            self.packages.insert(
+                package_name,
+                SharedPackage {
+                    paths: package.clone(),
+                    deps: deps.pop().unwrap(),
+                    units: files.into_values().flat_map(|file| file.units).collect(),
+                },
+            );
+        }
+        Ok(())
+    }
+}
+
+impl IncrementalCache {
+    fn load(build_root: &Path) -> Option<Self> {
+        let buf =
+            std::fs::read(build_root.join(CompiledPackageLayout::IncrementalCache.path())).ok()?;
+        serde_yaml::from_slice(&buf).ok()
+    }
+
+    /// Records the source files of `packages` along with the units compiled from them, in the build
+    /// directory of `package`.
+    pub(crate) fn save<'a>(
+        package: &OnDiskCompiledPackage,
+        config_digest: String,
+        packages: &[PackagePaths],
+        compiled_units: impl IntoIterator<Item = (PackageName, &'a CompiledUnitWithSource)>,
+    ) -> Result<()> {
+        let mut units_by_file = BTreeMap::new();
+        let mut interface_hashes = BTreeMap::new();
+        for (package_name, unit) in compiled_units {
+            if let CompiledUnit::Module(named) = &unit.unit {
+                interface_hashes.insert(
+                    named.module.self_id().to_string(),
+                    interface_hash(&named.module)?,
+                );
+            }
+            units_by_file
+                .entry(unit.source_path.to_string_lossy().to_string())
+                .or_insert_with(Vec::new)
+                .push((package_name, unit));
+        }
+
+        let mut files = BTreeMap::new();
+        for PackagePaths { name, paths, .. } in packages {
+            for path in paths {
+                let contents = std::fs::read_to_string(path.as_str())?;
+                let units = units_by_file
+                    .remove(path.as_str())
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|(package_name, unit)| CachedUnit {
+                        bytecode_path: package.compiled_unit_path(package_name, &unit.unit),
+                        interface_hash: match &unit.unit {
+                            CompiledUnit::Module(named) => interface_hashes
+                                .get(&named.module.self_id().to_string())
+                                .cloned(),
+                            CompiledUnit::Script(_) => None,
+                        },
+                        dependencies: unit_dependencies(&unit.unit)
+                            .into_iter()
+                            .filter_map(|id| {
+                                let id = id.to_string();
+                                let hash = interface_hashes.get(&id)?.clone();
+                                Some((id, hash))
+                            })
+                            .collect(),
+                    })
+                    .collect();
+                files.insert(
+                    path.to_string(),
+                    CachedFile {
+                        package_name: name.unwrap(),
+                        content_hash: content_hash(contents.as_bytes()),
+                        requires_source: requires_source(&contents),
+                        units,
+                    },
+                );
+            }
+        }
+
+        package.save_under(
+            CompiledPackageLayout::IncrementalCache.path(),
+            serde_yaml::to_string(&Self {
+                config_digest,
+                files,
+            })?
+            .as_bytes(),
+        )
+    }
+}
+
+/// Returns the hash of everything besides the source files that determines the output of a build
+pub(crate) fn config_digest(
+    build_options: &BuildConfig,
+    flags: &Flags,
+    packages: &[PackagePaths],
+) -> String {
+    // A forced build produces the same units, so its cache can be used by the next build
+    let build_options = BuildConfig {
+        force_recompilation: false,
+        ..build_options.clone()
+    };
+    let mut hasher = Sha256::new();
+    hasher.update(format!("{:?}", build_options).as_bytes());
+    hasher.update(format!("{:?}", flags).as_bytes());
+    for PackagePaths {
+        name,
+        named_address_map,
+        ..
+    } in packages
+    {
+        hasher.update(format!("{:?}", name).as_bytes());
+        for (named_address, address) in named_address_map {
+            hasher.update(format!("{}={}", named_address, address).as_bytes());
+        }
+    }
+    format!("{:X}", hasher.finalize())
+}
+
+/// Compiles `packages`, reusing the units cached in `build_root` for the source files that are
+/// unaffected by changes since the last build. The packages are compiled against `shared_deps`,
+/// whose units are `shared_units`, see `SharedUnits`. Returns `None` if the packages have to be
+/// built from scratch.
+pub(crate) fn build(
+    build_root: &Path,
+    config_digest: &str,
+    packages: &[PackagePaths],
+    shared_deps: &[PackagePaths],
+    shared_units: &[(PackageName, CompiledUnitWithSource)],
+    flags: Flags,
+    mut compiler_driver: impl FnMut(Compiler) -> Result<(FilesSourceText, Vec<AnnotatedCompiledUnit>)>,
+) -> Result<Option<Vec<(PackageName, CompiledUnitWithSource)>>> {
+    let cache = match IncrementalCache::load(build_root) {
+        Some(cache) if cache.config_digest == config_digest => cache,
+        _ => return Ok(None),
+    };
+    let on_disk_package = match OnDiskCompiledPackage::from_path(build_root) {
+        Ok(package) => package,
+        Err(_) => return Ok(None),
+    };
+
+    // Find the files that changed since the last build
+    let mut clean = BTreeMap::new();
+    let mut dirty = BTreeSet::new();
+    for PackagePaths { name, paths, .. } in packages {
+        for path in paths {
+            let cached = cache.files.get(path.as_str());
+            let contents = std::fs::read(path.as_str()).ok();
+            let unchanged = cached
+                .filter(|cached| Some(cached.package_name) == *name)
+                .filter(|cached| {
+                    matches!(
+                        &contents,
+                        Some(contents) if content_hash(contents) == cached.content_hash
+                    )
+                })
+                .is_some();
+            // The parts of the file that are not in its interface may have been copied into the
+            // units of other files, or be reported in them, which cannot be told apart from the
+            // interfaces
+            if !unchanged {
+                let requires_source = match &contents {
+                    Some(contents) => requires_source(&String::from_utf8_lossy(contents)),
+                    None => true,
+                };
+                if requires_source || matches!(cached, Some(cached) if cached.requires_source) {
+                    return Ok(None);
+                }
+            }
+            let clean_file = cached
+                .filter(|_| unchanged)
+                .and_then(|cached| reuse_file(&on_disk_package, path, cached));
+            match clean_file {
+                Some(clean_file) => {
+                    clean.insert(*path, clean_file);
+                }
+                None => {
+                    dirty.insert(*path);
+                }
+            }
+        }
+    }
+    if dirty.is_empty() {
+        // The compiler is not run, but the machine-readable formats always produce a document
+        report_warnings_with_format(
+            &FilesSourceText::new(),
+            Diagnostics::new(),
+            flags.diagnostics_format(),
+        );
+        let mut all_compiled_units = into_compiled_units(clean);
+        all_compiled_units.extend(shared_units.iter().cloned());
+        return Ok(Some(all_compiled_units));
+    }
+
+    // Files whose dependencies changed their interface need to be compiled again as well. The
+    // interfaces of the changed files are only known once they are compiled, so if any unchanged
+    // file depends on them, they are first compiled against the interfaces of the others to find
+    // out which public interfaces changed. Compiling a file again can change its interface in
+    // turn, so this is repeated for the files found to be affected until no more are.
+    let interfaces_dir = tempfile::tempdir()?;
+    let mut interface_hashes = BTreeMap::new();
+    for unit in clean
+        .values()
+        .flat_map(|file| &file.units)
+        .chain(shared_units.iter().map(|(_, unit)| unit))
+    {
+        insert_interface_hash(&mut interface_hashes, &unit.unit)?;
+    }
+    let mut unchecked = dirty.clone();
+    let mut checked = BTreeMap::new();
+    while !unchecked.is_empty() && clean.values().any(|file| is_stale(file, &interface_hashes)) {
+        let (targets, mut deps) = compiler_paths(
+            packages,
+            &unchecked,
+            clean.iter().chain(&checked),
+            interfaces_dir.path(),
+        )?;
+        deps.extend(shared_deps.iter().cloned());
+        let (files, units_res) = Compiler::from_package_paths(targets, deps)
+            .set_flags(flags.clone())
+            .build()?;
+        let units = match units_res {
+            Ok((units, _)) => units,
+            Err(_) => return Ok(None),
+        };
+        for annot_unit in units {
+            let (path, source) = &files[&annot_unit.loc().file_hash()];
+            let unit = annot_unit.into_compiled_unit();
+            let package_name = match &unit {
+                CompiledUnit::Module(named) => named.package_name,
+                CompiledUnit::Script(named) => named.package_name,
+            };
+            insert_interface_hash(&mut interface_hashes, &unit)?;
+            checked
+                .entry(*path)
+                .or_insert_with(|| CleanFile {
+                    package_name: package_name.unwrap(),
+                    requires_source: requires_source(source),
+                    units: vec![],
+                    dependencies: BTreeMap::new(),
+                })
+                .units
+                .push(CompiledUnitWithSource {
+                    unit,
+                    source_path: PathBuf::from(path.as_str()),
+                });
+        }
+
+        unchecked = clean
+            .iter()
+            .filter(|(_, file)| is_stale(file, &interface_hashes))
+            .map(|(path, _)| *path)
+            .collect();
+        for path in &unchecked {
+            let file = clean.remove(path).unwrap();
+            for unit in &file.units {
+                if let CompiledUnit::Module(named) = &unit.unit {
+                    interface_hashes.remove(&named.module.self_id().to_string());
+                }
+            }
+            dirty.insert(*path);
+        }
+    }
+
+    let (targets, mut deps) = compiler_paths(packages, &dirty, &clean, interfaces_dir.path())?;
+    deps.extend(shared_deps.iter().cloned());
+    let compiler = Compiler::from_package_paths(targets, deps).set_flags(flags);
+    let (file_map, compiled_units) = compiler_driver(compiler)?;
+    let mut all_compiled_units = into_compiled_units(clean);
+    all_compiled_units.extend(shared_units.iter().cloned());
+    for annot_unit in compiled_units {
+        let source_path = PathBuf::from(file_map[&annot_unit.loc().file_hash()].0.as_str());
+        let unit = annot_unit.into_compiled_unit();
+        let package_name = match &unit {
+            CompiledUnit::Module(named) => named.package_name,
+            CompiledUnit::Script(named) => named.package_name,
+        };
+        all_compiled_units.push((
+            package_name.unwrap(),
+            CompiledUnitWithSource { unit, source_path },
+        ));
+    }
+    Ok(Some(all_compiled_units))
+}
+
+/// Loads the units compiled from `path` in the last build
+fn reuse_file(
+    package: &OnDiskCompiledPackage,
+    path: &Symbol,
+    cached: &CachedFile,
+) -> Option<CleanFile> {
+    let mut units = vec![];
+    let mut dependencies = BTreeMap::new();
+    for unit in &cached.units {
+        let bytecode_path = package.root_path.join(&unit.bytecode_path);
+        let mut decoded = package
+            .decode_unit(cached.package_name, &bytecode_path.to_string_lossy())
+            .ok()?;
+        decoded.source_path = PathBuf::from(path.as_str());
+        units.push(decoded);
+        dependencies.extend(unit.dependencies.clone());
+    }
+    Some(CleanFile {
+        package_name: cached.package_name,
+        requires_source: cached.requires_source,
+        units,
+        dependencies,
+    })
+}
+
+/// Returns the package paths to give to the compiler to compile the `dirty` files. The `clean`
+/// files are given as dependencies, as interface files written to `interfaces_dir` unless they
+/// have to be given as source.
+fn compiler_paths<'a>(
+    packages: &[PackagePaths],
+    dirty: &BTreeSet<Symbol>,
+    clean: impl IntoIterator<Item = (&'a Symbol, &'a CleanFile)>,
+    interfaces_dir: &Path,
+) -> Result<(Vec<PackagePaths>, Vec<PackagePaths>)> {
+    let clean = clean.into_iter().collect::<Vec<_>>();
+    let mut targets = vec![];
+    let mut deps = vec![];
+    for package in packages {
+        let package_name = package.name.unwrap();
+        let target_paths = package
+            .paths
+            .iter()
+            .filter(|path| dirty.contains(path))
+            .copied()
+            .collect::<Vec<_>>();
+        let mut dep_paths = vec![];
+        for (path, file) in &clean {
+            if file.package_name != package_name {
+                continue;
+            }
+            if file.requires_source {
+                dep_paths.push(**path);
+                continue;
+            }
+            for unit in &file.units {
+                if let CompiledUnit::Module(named) = &unit.unit {
+                    let id = named.module.self_id();
+                    let interface = module_interface(&named.module)?;
+                    let interface_dir = interfaces_dir.join(package_name.as_str());
+                    std::fs::create_dir_all(&interface_dir)?;
+                    let interface_path = interface_dir
+                        .join(format!(
+                            "{}_{}",
+                            id.address().short_str_lossless(),
+                            id.name()
+                        ))
+                        .with_extension(MOVE_EXTENSION);
+                    std::fs::write(&interface_path, interface)?;
+                    dep_paths.push(Symbol::from(interface_path.to_string_lossy().as_ref()));
+                }
+            }
+        }
+        targets.push(PackagePaths {
+            name: package.name,
+            paths: target_paths,
+            named_address_map: package.named_address_map.clone(),
+        });
+        deps.push(PackagePaths {
+            name: package.name,
+            paths: dep_paths,
+            named_address_map: package.named_address_map.clone(),
+        });
+    }
+    Ok((targets, deps))
+}
+
+/// Returns whether any of the interfaces `file` was compiled against changed, or is unknown
+fn is_stale(file: &CleanFile, interface_hashes: &BTreeMap<String, String>) -> bool {
+    file.dependencies
+        .iter()
+        .any(|(id, hash)| interface_hashes.get(id) != Some(hash))
+}
+
+fn insert_interface_hash(
+    interface_hashes: &mut BTreeMap<String, String>,
+    unit: &CompiledUnit,
+) -> Result<()> {
+    if let CompiledUnit::Module(named) = unit {
+        interface_hashes.insert(
+            named.module.self_id().to_string(),
+            interface_hash(&named.module)?,
+        );
+    }
+    Ok(())
+}
+
+fn into_compiled_units(
+    clean: BTreeMap<Symbol, CleanFile>,
+) -> Vec<(PackageName, CompiledUnitWithSource)> {
+    clean
+        .into_values()
+        .flat_map(|file| {
+            let package_name = file.package_name;
+            file.units.into_iter().map(move |unit| (package_name, unit))
+        })
+        .collect()
+}
+
+fn unit_dependencies(unit: &CompiledUnit) -> Vec<ModuleId> {
+    match unit {
+        CompiledUnit::Module(named) => named.module.immediate_dependencies(),
+        CompiledUnit::Script(named) => named.script.immediate_dependencies(),
+    }
+}
+
+fn module_interface(module: &CompiledModule) -> Result<String> {
+    let (_, interface) = write_module_to_string(&BTreeMap::<ModuleId, String>::new(), module)?;
+    Ok(interface)
+}
+
+fn interface_hash(module: &CompiledModule) -> Result<String> {
+    Ok(content_hash(module_interface(module)?.as_bytes()))
+}
+
+fn content_hash(contents: &[u8]) -> String {
+    format!("{:X}", Sha256::digest(contents))
+}
+
+/// Returns whether `contents` declares any inline function, any constant visible outside of its
+/// module, i.e. `public` or `public(friend)`, or any `deprecated` attribute. Files that cannot be
+/// lexed are conservatively assumed to do so.
+fn requires_source(contents: &str) -> bool {
+    let mut lexer = Lexer::new(contents, FileHash::new(contents));
+    let mut previous = Tok::EOF;
+    loop {
+        if lexer.advance().is_err() {
+            return true;
+        }
+        match lexer.peek() {
+            Tok::EOF => return false,
+            Tok::Identifier
+                if lexer.content() == INLINE_MODIFIER
+                    && matches!(lexer.lookahead(), Ok(Tok::Fun)) =>
+            {
+                return true
+            }
+            Tok::Const if matches!(previous, Tok::Public | Tok::RParen) => return true,
+            Tok::Identifier
+                if lexer.content() == DeprecationAttribute::DEPRECATED
+                    && matches!(previous, Tok::LBracket | Tok::Comma) =>
+            {
+                return true
+            }
+            _ => (),
+        }
+        previous = lexer.peek();
+    }
+}
@@ -3,5 +3,6 @@
 
 pub mod build_plan;
 pub mod compiled_package;
+pub mod incremental;
 pub mod model_builder;
 pub mod package_layout;
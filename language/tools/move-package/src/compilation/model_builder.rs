@@ -29,6 +29,13 @@ impl ModelBuilder {
     // TODO: In the future we will need a better way to do this to support renaming in packages
     // where we want to support building a Move model.
     pub fn build_model(&self) -> Result<GlobalEnv> {
+        if self.resolution_graph.is_workspace() {
+            anyhow::bail!(
+                "'{}' is a workspace; build the Move model of each of its members instead",
+                self.resolution_graph.root_package_path.to_string_lossy()
+            )
+        }
+
         // Make sure no renamings have been performed
         for (pkg_name, pkg) in self.resolution_graph.package_table.iter() {
             if !pkg.renaming.is_empty() {
@@ -43,18 +50,13 @@ impl ModelBuilder {
         // Targets are all files in the root package
         let root_name = &self.resolution_graph.root_package.package.name;
         let root_package = self.resolution_graph.get_package(root_name).clone();
-        let deps_source_info = self
-            .resolution_graph
-            .package_table
-            .iter()
-            .filter_map(|(nm, pkg)| {
-                if nm == root_name {
-                    return None;
-                }
-                let dep_source_paths = pkg
-                    .get_sources(&self.resolution_graph.build_options)
-                    .unwrap();
-                Some(Ok((*nm, dep_source_paths, &pkg.resolution_table)))
+        let deps_source_info = root_package
+            .transitive_dependencies(&self.resolution_graph)
+            .into_iter()
+            .map(|nm| {
+                let pkg = self.resolution_graph.get_package(&nm);
+                let dep_source_paths = pkg.get_sources(&self.resolution_graph.build_options)?;
+                Ok((nm, dep_source_paths, &pkg.resolution_table))
             })
             .collect::<Result<Vec<_>>>()?;
 
@@ -6,6 +6,7 @@ use std::path::{Path, PathBuf};
 #[derive(Debug, Clone)]
 pub enum CompiledPackageLayout {
     BuildInfo,
+    IncrementalCache,
     Root,
     Dependencies,
     Sources,
@@ -20,6 +21,7 @@ impl CompiledPackageLayout {
     pub fn path(&self) -> &Path {
         let path = match self {
             Self::BuildInfo => "BuildInfo.yaml",
+            Self::IncrementalCache => "IncrementalCache.yaml",
             Self::Root => "build",
             Self::Dependencies => "dependencies",
             Self::Sources => "sources",
@@ -9,12 +9,15 @@ pub mod source_package;
 
 use anyhow::{bail, Result};
 use clap::*;
+use move_compiler::diagnostics::{codes::WarningFilter, structured::DiagnosticsFormat};
 use move_core_types::account_address::AccountAddress;
 use move_model::model::GlobalEnv;
+use petgraph::algo;
 use serde::{Deserialize, Serialize};
 use source_package::layout::SourcePackageLayout;
 use std::{
-    collections::BTreeMap,
+    cmp::Reverse,
+    collections::{BTreeMap, BTreeSet},
     fmt,
     io::Write,
     path::{Path, PathBuf},
@@ -22,11 +25,20 @@ use std::{
 
 use crate::{
     compilation::{
-        build_plan::BuildPlan, compiled_package::CompiledPackage, model_builder::ModelBuilder,
+        build_plan::BuildPlan, compiled_package::CompiledPackage, incremental::SharedUnits,
+        model_builder::ModelBuilder,
     },
     package_lock::PackageLock,
-    resolution::resolution_graph::{ResolutionGraph, ResolvedGraph},
-    source_package::manifest_parser,
+    resolution::{
+        lock_file::{self, LockFile},
+        registry::{self, RegistryPins},
+        resolution_graph::{RepoSync, ResolutionGraph, ResolvedGraph},
+    },
+    source_package::{
+        manifest_parser,
+        parsed_manifest::{PackageName, SourceManifest},
+        workspace,
+    },
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
@@ -88,6 +100,10 @@ impl Architecture {
     }
 }
 
+fn validate_warning_filter(filter: &str) -> std::result::Result<(), String> {
+    filter.parse::<WarningFilter>().map(|_| ())
+}
+
 #[derive(Debug, Parser, Clone, Serialize, Deserialize, Eq, PartialEq, PartialOrd)]
 #[clap(author, version, about)]
 pub struct BuildConfig {
@@ -128,6 +144,42 @@ pub struct BuildConfig {
     /// Only fetch dependency repos to MOVE_HOME
     #[clap(long = "fetch-deps-only", global = true)]
     pub fetch_deps_only: bool,
+
+    /// Fail if the lockfile Move.lock is missing or would need to be updated
+    #[clap(long = "locked", global = true)]
+    pub locked: bool,
+
+    /// Run the lints of the compiler over the root package, at the levels set in its manifest
+    #[clap(skip)]
+    pub lint: bool,
+
+    /// Report all warnings as errors
+    #[clap(long = "warnings-are-errors", global = true)]
+    pub warnings_are_errors: bool,
+
+    /// Do not report the selected warnings, e.g. 'unused_variable' or 'unused_item'. Adds to the
+    /// warnings allowed in the manifest
+    #[clap(
+        long = "allow",
+        value_name = "FILTER",
+        global = true,
+        validator = validate_warning_filter
+    )]
+    pub allow: Vec<String>,
+
+    /// Report the selected warnings as errors. Adds to the warnings denied in the manifest
+    #[clap(
+        long = "deny",
+        value_name = "FILTER",
+        global = true,
+        validator = validate_warning_filter
+    )]
+    pub deny: Vec<String>,
+
+    /// The format of the compiler diagnostics: 'human', 'json' or 'sarif'. Diagnostics in the
+    /// 'json' and 'sarif' formats are written to stdout
+    #[clap(long = "diagnostics-format", default_value = "human", global = true)]
+    pub diagnostics_format: DiagnosticsFormat,
 }
 
 impl Default for BuildConfig {
@@ -142,6 +194,12 @@ impl Default for BuildConfig {
             additional_named_addresses: BTreeMap::new(),
             architecture: None,
             fetch_deps_only: false,
+            locked: false,
+            lint: false,
+            warnings_are_errors: false,
+            allow: vec![],
+            deny: vec![],
+            diagnostics_format: DiagnosticsFormat::Human,
         }
     }
 }
@@ -180,6 +238,52 @@ impl BuildConfig {
         ret
     }
 
+    /// Compile the package at `path` or the containing Move package, or all members of the
+    /// workspace if `path` is the root of a workspace. Exit process on warning or failure.
+    pub fn compile_workspace<W: Write>(
+        self,
+        path: &Path,
+        writer: &mut W,
+    ) -> Result<Vec<CompiledPackage>> {
+        let resolved_graph = self.resolution_graph_for_package(path)?;
+        let mutx = PackageLock::lock();
+        let ret = Self::compile_members(resolved_graph.member_graphs(), writer);
+        mutx.unlock();
+        ret
+    }
+
+    /// Compiles the members of a workspace, in order. The packages compiled by the build of a
+    /// member are not compiled again by the builds of the others.
+    fn compile_members<W: Write>(
+        member_graphs: Vec<ResolvedGraph>,
+        writer: &mut W,
+    ) -> Result<Vec<CompiledPackage>> {
+        // A member is built after the members it depends on, so that it reuses their packages.
+        // All member graphs share the dependency graph of the workspace.
+        let mut members = member_graphs.into_iter().enumerate().collect::<Vec<_>>();
+        if let Some((_, member_graph)) = members.first() {
+            let sorted = match algo::toposort(&member_graph.graph, None) {
+                Ok(nodes) => nodes,
+                Err(err) => bail!("IPE: Cyclic dependency found after resolution {:?}", err),
+            };
+            // `toposort` puts packages before their dependencies
+            members.sort_by_key(|(_, member_graph)| {
+                let name = member_graph.root_package.package.name;
+                Reverse(sorted.iter().position(|node| *node == name))
+            });
+        }
+        let mut shared_units = SharedUnits::new()?;
+        let mut compiled = BTreeMap::new();
+        for (i, member_graph) in members {
+            let build_plan = BuildPlan::create(member_graph)?;
+            compiled.insert(
+                i,
+                build_plan.compile_with_shared_units(writer, &mut shared_units)?,
+            );
+        }
+        Ok(compiled.into_values().collect())
+    }
+
     #[cfg(feature = "evm-backend")]
     pub fn compile_package_evm<W: Write>(self, path: &Path, writer: &mut W) -> Result<()> {
         let resolved_graph = self.resolution_graph_for_package(path)?;
@@ -210,32 +314,118 @@ impl BuildConfig {
         let path = SourcePackageLayout::try_find_root(path)?;
         let toml_manifest =
             self.parse_toml_manifest(path.join(SourcePackageLayout::Manifest.path()))?;
+        let lock_file = LockFile::read(&path)?;
         let mutx = PackageLock::lock();
         // This should be locked as it inspects the environment for `MOVE_HOME` which could
         // possibly be set by a different process in parallel.
-        let manifest = manifest_parser::parse_source_manifest(toml_manifest)?;
-        ResolutionGraph::download_dependency_repos(&manifest, self, &path)?;
+        let (manifest, _) = Self::root_manifest(toml_manifest, &path)?;
+        let sync = match &lock_file {
+            None => RepoSync::Download,
+            Some(lock_file) => RepoSync::Pinned(lock_file.pinned_commits()),
+        };
+        ResolutionGraph::sync_dependency_repos(
+            &manifest,
+            self,
+            &path,
+            &sync,
+            &mut BTreeSet::new(),
+        )?;
+        let pins = lock_file
+            .map(|lock_file| lock_file.pinned_versions(self.dev_mode))
+            .unwrap_or_default();
+        registry::select_versions(&manifest, &path, self.dev_mode, &pins)?;
         mutx.unlock();
         Ok(())
     }
 
-    pub fn resolution_graph_for_package(mut self, path: &Path) -> Result<ResolvedGraph> {
+    /// Resolves the package at `path` or the containing Move package. If it is the root of a
+    /// workspace, the graph of the whole workspace is returned, and if it is a member of a
+    /// workspace, the graph of the workspace rooted at the member.
+    pub fn resolution_graph_for_package(self, path: &Path) -> Result<ResolvedGraph> {
+        self.resolve_and_lock(path, false)
+    }
+
+    /// Fetches the latest commits of the revisions of the git dependencies of the package at
+    /// `path` and selects the highest matching versions of its registry dependencies, and records
+    /// them along with the rest of the resolution of the package in its lockfile.
+    pub fn update_dependencies(self, path: &Path) -> Result<ResolvedGraph> {
+        if self.locked {
+            bail!("Cannot update the dependencies of a package when --locked is passed")
+        }
+        self.resolve_and_lock(path, true)
+    }
+
+    fn resolve_and_lock(mut self, path: &Path, refresh: bool) -> Result<ResolvedGraph> {
         if self.test_mode {
             self.dev_mode = true;
         }
         let path = SourcePackageLayout::try_find_root(path)?;
+        // The members of a workspace are resolved along with the rest of the workspace
+        let (path, member) = match workspace::find_enclosing_workspace(&path)? {
+            Some((workspace_path, member)) => (workspace_path, Some(member)),
+            None => (path, None),
+        };
         let toml_manifest =
             self.parse_toml_manifest(path.join(SourcePackageLayout::Manifest.path()))?;
+        let lock_file = LockFile::read(&path)?;
         let mutx = PackageLock::lock();
         // This should be locked as it inspects the environment for `MOVE_HOME` which could
         // possibly be set by a different process in parallel.
-        let manifest = manifest_parser::parse_source_manifest(toml_manifest)?;
-        let resolution_graph = ResolutionGraph::new(manifest, path, self)?;
-        let ret = resolution_graph.resolve();
+        let (manifest, workspace_members) = Self::root_manifest(toml_manifest, &path)?;
+        let sync = match &lock_file {
+            _ if refresh => Some(RepoSync::Update),
+            Some(lock_file) => Some(RepoSync::Pinned(lock_file.pinned_commits())),
+            None => None,
+        };
+        if let Some(sync) = sync {
+            ResolutionGraph::sync_dependency_repos(
+                &manifest,
+                &self,
+                &path,
+                &sync,
+                &mut BTreeSet::new(),
+            )?;
+        }
+        let pins = match &lock_file {
+            Some(lock_file) if !refresh => lock_file.pinned_versions(self.dev_mode),
+            _ => RegistryPins::new(),
+        };
+        let resolution_graph = ResolutionGraph::new_with_registry_pins(
+            manifest,
+            path,
+            self,
+            &pins,
+            workspace_members,
+        )?;
+        let ret = resolution_graph.resolve().and_then(|resolved_graph| {
+            lock_file::update_lock_file(&resolved_graph, lock_file, refresh)?;
+            Ok(match member {
+                Some(member) => resolved_graph.member_graph(&member),
+                None => resolved_graph,
+            })
+        });
         mutx.unlock();
         ret
     }
 
+    /// Parses the manifest of the package or workspace at `path`. A workspace is resolved from its
+    /// virtual root package, and its members are returned along with it.
+    fn root_manifest(
+        toml_manifest: toml::Value,
+        path: &Path,
+    ) -> Result<(SourceManifest, BTreeSet<PackageName>)> {
+        if !manifest_parser::is_workspace_manifest(&toml_manifest) {
+            return Ok((
+                manifest_parser::parse_source_manifest(toml_manifest)?,
+                BTreeSet::new(),
+            ));
+        }
+        let workspace = manifest_parser::parse_workspace_manifest(toml_manifest)?;
+        let manifest = workspace::workspace_package(workspace, path)?;
+        let members = manifest.dependencies.keys().copied().collect();
+        Ok((manifest, members))
+    }
+
     fn parse_toml_manifest(&self, path: PathBuf) -> Result<toml::Value> {
         let manifest_string = std::fs::read_to_string(path)?;
         manifest_parser::parse_move_manifest_string(manifest_string)
@@ -0,0 +1,301 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! The `Move.lock` file of a package records how each of its transitive dependencies was resolved:
+//! the commit that git dependencies were checked out at, the version selected for registry
+//! dependencies, the digest of the sources of every dependency, and the named address assignments
+//! that were in scope for it. Builds check out the commits pinned in the lockfile instead of the
+//! latest commit of the revision in the manifest, prefer the pinned versions of registry
+//! dependencies, and fail if the sources found at a pinned commit or version do not match the
+//! recorded digest.
+//!
+//! As the dependency graph, the digests and the address assignments differ between regular and
+//! dev/test builds, the lockfile keeps one list of packages for each.
+
+use crate::{
+    resolution::{
+        registry::{version_string, RegistryPins},
+        resolution_graph::{git_head, ResolvedGraph},
+    },
+    source_package::{
+        layout::SourcePackageLayout,
+        manifest_parser::parse_version_str,
+        parsed_manifest::{Dependency, PackageName},
+    },
+};
+use anyhow::{bail, Context, Result};
+use move_symbol_pool::Symbol;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, path::Path};
+
+const LOCK_FILE_HEADER: &str =
+    "# This file is generated by the Move package system. Do not edit it by hand.\n\n";
+
+#[derive(Debug, Default, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct LockFile {
+    /// The dependencies as resolved for regular builds
+    #[serde(default, rename = "package", skip_serializing_if = "Vec::is_empty")]
+    pub packages: Vec<LockedPackage>,
+    /// The dependencies as resolved for builds in dev or test mode
+    #[serde(default, rename = "dev-package", skip_serializing_if = "Vec::is_empty")]
+    pub dev_packages: Vec<LockedPackage>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct LockedPackage {
+    pub name: String,
+    /// The path of a local dependency, as given in the manifest that declares it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub local: Option<String>,
+    /// The url of a git dependency
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git: Option<String>,
+    /// The revision of a git dependency, as given in the manifest that declares it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rev: Option<String>,
+    /// The SHA of the commit that the revision of a git dependency was resolved to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    /// The path of a git dependency in its repository
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subdir: Option<String>,
+    /// The registry of a registry dependency
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registry: Option<String>,
+    /// The version that was selected for a registry dependency
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    pub digest: String,
+    #[serde(default)]
+    pub addresses: BTreeMap<String, String>,
+}
+
+impl LockFile {
+    /// Reads the lockfile of the package at `root_path`, if it has one.
+    pub fn read(root_path: &Path) -> Result<Option<Self>> {
+        let path = root_path.join(SourcePackageLayout::LockFile.path());
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let contents = fs::read_to_string(&path)?;
+        toml::from_str(&contents)
+            .map(Some)
+            .with_context(|| format!("Unable to parse lockfile {}", path.to_string_lossy()))
+    }
+
+    pub fn write(&self, root_path: &Path) -> Result<()> {
+        let contents = toml::to_string(self)?;
+        fs::write(
+            root_path.join(SourcePackageLayout::LockFile.path()),
+            format!("{}{}", LOCK_FILE_HEADER, contents),
+        )?;
+        Ok(())
+    }
+
+    pub fn packages(&self, dev_mode: bool) -> &[LockedPackage] {
+        if dev_mode {
+            &self.dev_packages
+        } else {
+            &self.packages
+        }
+    }
+
+    fn packages_mut(&mut self, dev_mode: bool) -> &mut Vec<LockedPackage> {
+        if dev_mode {
+            &mut self.dev_packages
+        } else {
+            &mut self.packages
+        }
+    }
+
+    /// Returns the commits of the git dependencies, by the url and revision of the dependency.
+    pub(crate) fn pinned_commits(&self) -> BTreeMap<(Symbol, Symbol), String> {
+        self.packages
+            .iter()
+            .chain(self.dev_packages.iter())
+            .filter_map(|pkg| Some((pkg.git_checkout()?, pkg.commit.clone()?)))
+            .collect()
+    }
+
+    /// Returns the versions selected for the registry dependencies, preferring those of dev mode
+    /// if `dev_mode` is set.
+    pub(crate) fn pinned_versions(&self, dev_mode: bool) -> RegistryPins {
+        self.packages(!dev_mode)
+            .iter()
+            .chain(self.packages(dev_mode).iter())
+            .filter_map(|pkg| {
+                let version = parse_version_str(pkg.version.as_deref()?).ok()?;
+                Some((
+                    PackageName::from(pkg.name.as_str()),
+                    (Symbol::from(pkg.registry.as_deref()?), version),
+                ))
+            })
+            .collect()
+    }
+}
+
+impl LockedPackage {
+    fn git_checkout(&self) -> Option<(Symbol, Symbol)> {
+        Some((
+            Symbol::from(self.git.as_deref()?),
+            Symbol::from(self.rev.as_deref()?),
+        ))
+    }
+}
+
+/// Checks the resolution of `graph` against the lockfile `lock_file` of its root package and
+/// records the resolution in it. Unless `refresh` is set, the sources of git dependencies at a
+/// pinned commit must match the digest in the lockfile.
+///
+/// A package without a lockfile only gets one if it has git or registry dependencies, as there is
+/// nothing to pin otherwise.
+pub(crate) fn update_lock_file(
+    graph: &ResolvedGraph,
+    lock_file: Option<LockFile>,
+    refresh: bool,
+) -> Result<()> {
+    let dev_mode = graph.build_options.dev_mode;
+    let packages = locked_packages(graph)?;
+    let mut new_lock_file = lock_file.clone().unwrap_or_default();
+    if !refresh {
+        verify_digests(new_lock_file.packages(dev_mode), &packages)?;
+    }
+    *new_lock_file.packages_mut(dev_mode) = packages;
+    if refresh {
+        // Entries of the other mode for a checkout that moved are stale
+        let pins = new_lock_file.pinned_commits();
+        new_lock_file.packages_mut(!dev_mode).retain(|pkg| {
+            match (pkg.git_checkout(), &pkg.commit) {
+                (Some(checkout), Some(commit)) => pins.get(&checkout) == Some(commit),
+                _ => true,
+            }
+        });
+    }
+
+    let has_pinned_packages = new_lock_file
+        .packages
+        .iter()
+        .chain(new_lock_file.dev_packages.iter())
+        .any(|pkg| pkg.git.is_some() || pkg.registry.is_some());
+    if lock_file.as_ref() == Some(&new_lock_file)
+        || (lock_file.is_none() && !refresh && !has_pinned_packages)
+    {
+        return Ok(());
+    }
+    if graph.build_options.locked {
+        bail!(
+            "The lockfile {} of package '{}' needs to be updated, but --locked was passed",
+            SourcePackageLayout::LockFile.location_str(),
+            graph.root_package.package.name
+        )
+    }
+    new_lock_file.write(&graph.root_package_path)
+}
+
+fn verify_digests(locked: &[LockedPackage], packages: &[LockedPackage]) -> Result<()> {
+    // The sources of a git dependency at a commit and of a published version of a package are
+    // expected to stay the same
+    let pinned_at = |pkg: &LockedPackage| match (&pkg.commit, &pkg.version) {
+        (Some(commit), _) => Some(format!("commit {}", commit)),
+        (None, Some(version)) => Some(format!("version {}", version)),
+        (None, None) => None,
+    };
+    for pkg in packages {
+        let pinned = match pinned_at(pkg) {
+            Some(pinned) => pinned,
+            None => continue,
+        };
+        let locked_pkg = locked.iter().find(|locked_pkg| {
+            locked_pkg.name == pkg.name
+                && locked_pkg.git == pkg.git
+                && locked_pkg.rev == pkg.rev
+                && locked_pkg.subdir == pkg.subdir
+                && locked_pkg.commit == pkg.commit
+                && locked_pkg.registry == pkg.registry
+                && locked_pkg.version == pkg.version
+        });
+        match locked_pkg {
+            Some(locked_pkg) if locked_pkg.digest != pkg.digest => bail!(
+                "Source digest mismatch in dependency '{}' at {}. Expected '{}' from the \
+                lockfile but got '{}'. Run `move update-deps` if this change is expected",
+                pkg.name,
+                pinned,
+                locked_pkg.digest,
+                pkg.digest
+            ),
+            _ => (),
+        }
+    }
+    Ok(())
+}
+
+/// Returns the lockfile entries for all packages in `graph` besides the root package.
+fn locked_packages(graph: &ResolvedGraph) -> Result<Vec<LockedPackage>> {
+    let dev_mode = graph.build_options.dev_mode;
+    let mut declarations: BTreeMap<PackageName, &Dependency> = BTreeMap::new();
+    for pkg in graph.package_table.values() {
+        let dev_dependencies = pkg
+            .source_package
+            .dev_dependencies
+            .iter()
+            .filter(|_| dev_mode);
+        for (dep_name, dep) in pkg
+            .source_package
+            .dependencies
+            .iter()
+            .chain(dev_dependencies)
+        {
+            declarations.entry(*dep_name).or_insert(dep);
+        }
+    }
+
+    let mut packages = vec![];
+    for (name, pkg) in &graph.package_table {
+        if *name == graph.root_package.package.name {
+            continue;
+        }
+        let dep = declarations[name];
+        let addresses = pkg
+            .resolution_table
+            .iter()
+            .map(|(addr_name, addr)| {
+                (
+                    addr_name.to_string(),
+                    format!("0x{}", addr.short_str_lossless()),
+                )
+            })
+            .collect();
+        let mut locked_pkg = LockedPackage {
+            name: name.to_string(),
+            local: None,
+            git: None,
+            rev: None,
+            commit: None,
+            subdir: None,
+            registry: None,
+            version: None,
+            digest: pkg.source_digest.to_string(),
+            addresses,
+        };
+        match (&dep.git_info, &dep.registry_info) {
+            (None, None) => locked_pkg.local = Some(dep.local.to_string_lossy().to_string()),
+            (_, Some(_)) => {
+                let registry_package = &graph.registry_packages[name];
+                locked_pkg.registry = Some(registry_package.registry.to_string());
+                locked_pkg.version = Some(version_string(registry_package.version));
+            }
+            (Some(git_info), None) => {
+                locked_pkg.git = Some(git_info.git_url.to_string());
+                locked_pkg.rev = Some(git_info.git_rev.to_string());
+                locked_pkg.commit = Some(git_head(git_info).with_context(|| {
+                    format!("Unable to find the commit of dependency '{}'", name)
+                })?);
+                if !git_info.subdir.as_os_str().is_empty() {
+                    locked_pkg.subdir = Some(git_info.subdir.to_string_lossy().to_string());
+                }
+            }
+        }
+        packages.push(locked_pkg);
+    }
+    Ok(packages)
+}
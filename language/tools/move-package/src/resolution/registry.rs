@@ -0,0 +1,400 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A registry holds the published versions of packages, which can then be used as dependencies of
+//! the form `{ registry = "<location>", version = "<requirement>" }`. Registries are directories in
+//! the file system, laid out as
+//!
+//! a_registry
+//! └── <package_name>
+//!     ├── index.toml  (the published versions of the package, with their digests)
+//!     └── <version>   (the sources of a version of the package)
+//!
+//! Packages are downloaded from their registry to MOVE_HOME before they are used.
+
+use crate::{
+    resolution::resolution_graph::{ResolvingGraph, ResolvingPackage},
+    source_package::{
+        layout::SourcePackageLayout,
+        manifest_parser::{self, move_home, parse_version_str, sanitize_url},
+        parsed_manifest::{PackageDigest, PackageName, SourceManifest, Version},
+        version_req::VersionReq,
+    },
+    BuildConfig,
+};
+use anyhow::{bail, Context, Result};
+use move_symbol_pool::Symbol;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::{Path, PathBuf},
+};
+
+const INDEX_FILE_NAME: &str = "index.toml";
+
+/// The number of rounds of version selection before giving up on finding a consistent selection
+const MAX_SELECTION_ROUNDS: usize = 100;
+
+/// The versions to prefer for packages from a registry, if they satisfy all requirements on them
+pub type RegistryPins = BTreeMap<PackageName, (Symbol, Version)>;
+
+/// A package from a registry that was selected for a package graph
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RegistryPackage {
+    /// The directory of the registry
+    pub registry: Symbol,
+    pub version: Version,
+    /// Where the package was downloaded to
+    pub package_path: PathBuf,
+}
+
+pub struct Registry {
+    root: PathBuf,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RegistryIndex {
+    #[serde(default, rename = "version")]
+    versions: Vec<PublishedVersion>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PublishedVersion {
+    version: String,
+    digest: String,
+}
+
+struct Requirement {
+    registry: Symbol,
+    version_req: VersionReq,
+    required_by: PackageName,
+}
+
+pub fn version_string((major, minor, patch): Version) -> String {
+    format!("{}.{}.{}", major, minor, patch)
+}
+
+impl Registry {
+    /// Opens the registry at `location`, an absolute path or a `file://` url.
+    pub fn new(location: &str) -> Result<Self> {
+        Ok(Self {
+            root: manifest_parser::registry_path(location)?,
+        })
+    }
+
+    fn index_path(&self, package_name: PackageName) -> PathBuf {
+        self.root.join(package_name.as_str()).join(INDEX_FILE_NAME)
+    }
+
+    fn read_index(&self, package_name: PackageName) -> Result<RegistryIndex> {
+        let path = self.index_path(package_name);
+        if !path.is_file() {
+            return Ok(RegistryIndex::default());
+        }
+        toml::from_str(&fs::read_to_string(&path)?)
+            .with_context(|| format!("Unable to parse registry index {}", path.to_string_lossy()))
+    }
+
+    /// Returns the published versions of a package along with their digests.
+    pub fn versions(&self, package_name: PackageName) -> Result<Vec<(Version, PackageDigest)>> {
+        self.read_index(package_name)?
+            .versions
+            .into_iter()
+            .map(|published| {
+                Ok((
+                    parse_version_str(&published.version)?,
+                    PackageDigest::from(published.digest),
+                ))
+            })
+            .collect()
+    }
+
+    /// Downloads a version of a package to MOVE_HOME, unless it has been downloaded already, and
+    /// returns where it was downloaded to.
+    pub fn fetch(&self, package_name: PackageName, version: Version) -> Result<PathBuf> {
+        let version_str = version_string(version);
+        let digest = match self
+            .versions(package_name)?
+            .into_iter()
+            .find(|(v, _)| *v == version)
+        {
+            Some((_, digest)) => digest,
+            None => bail!(
+                "Version {} of package '{}' is not published in registry '{}'",
+                version_str,
+                package_name,
+                self.root.to_string_lossy()
+            ),
+        };
+        let download_to = move_home()
+            .join("registry")
+            .join(sanitize_url(&self.root.to_string_lossy()))
+            .join(package_name.as_str())
+            .join(&version_str);
+        if !download_to.exists() {
+            copy_package(
+                &self.root.join(package_name.as_str()).join(&version_str),
+                &download_to,
+            )?;
+        }
+        // Published versions are immutable, so a different digest means that something changed
+        // the sources after publishing
+        let downloaded_digest = published_digest(&download_to)?;
+        if downloaded_digest != digest {
+            bail!(
+                "Source digest mismatch in version {} of package '{}' from registry '{}'. \
+                Expected '{}' but got '{}'",
+                version_str,
+                package_name,
+                self.root.to_string_lossy(),
+                digest,
+                downloaded_digest
+            )
+        }
+        Ok(download_to)
+    }
+
+    /// Publishes the package at `package_path` to the registry, under the version in its manifest.
+    pub fn publish(&self, package_path: &Path) -> Result<SourceManifest> {
+        let manifest = manifest_parser::parse_move_manifest_from_file(package_path)?;
+        let name = manifest.package.name;
+        let version_str = version_string(manifest.package.version);
+        for (dep_name, dep) in manifest
+            .dependencies
+            .iter()
+            .chain(manifest.dev_dependencies.iter())
+        {
+            if dep.git_info.is_none() && dep.registry_info.is_none() {
+                bail!(
+                    "Cannot publish package '{}' with the local dependency '{}'. Only git and \
+                    registry dependencies can be used by published packages",
+                    name,
+                    dep_name
+                )
+            }
+        }
+
+        let mut index = self.read_index(name)?;
+        if index.versions.iter().any(|v| v.version == version_str) {
+            bail!(
+                "Version {} of package '{}' is already published in registry '{}'",
+                version_str,
+                name,
+                self.root.to_string_lossy()
+            )
+        }
+        let publish_to = self.root.join(name.as_str()).join(&version_str);
+        copy_package(package_path, &publish_to)?;
+        index.versions.push(PublishedVersion {
+            version: version_str,
+            digest: published_digest(&publish_to)?.to_string(),
+        });
+        index
+            .versions
+            .sort_by_key(|v| parse_version_str(&v.version).ok());
+        fs::write(self.index_path(name), toml::to_string(&index)?)?;
+        Ok(manifest)
+    }
+}
+
+/// The digest of a published package, which covers the sources used by regular builds
+fn published_digest(package_path: &Path) -> Result<PackageDigest> {
+    ResolvingPackage::get_package_digest_for_config(package_path, &BuildConfig::default())
+}
+
+/// Copies the manifest and source directories of the package at `from` to `to`
+fn copy_package(from: &Path, to: &Path) -> Result<()> {
+    fs::create_dir_all(to)?;
+    fs::copy(
+        from.join(SourcePackageLayout::Manifest.path()),
+        to.join(SourcePackageLayout::Manifest.path()),
+    )
+    .with_context(|| {
+        format!(
+            "Unable to find package manifest in {}",
+            from.to_string_lossy()
+        )
+    })?;
+    for layout in [
+        SourcePackageLayout::Sources,
+        SourcePackageLayout::Scripts,
+        SourcePackageLayout::Examples,
+        SourcePackageLayout::Tests,
+        SourcePackageLayout::Specifications,
+        SourcePackageLayout::DocTemplates,
+    ] {
+        let dir = from.join(layout.path());
+        for entry in walkdir::WalkDir::new(&dir)
+            .follow_links(true)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            let dest = to.join(entry.path().strip_prefix(from)?);
+            fs::create_dir_all(dest.parent().unwrap())?;
+            fs::copy(entry.path(), dest)?;
+        }
+    }
+    Ok(())
+}
+
+/// Selects a version for each package that is required from a registry in the package graph of
+/// `root_package`, and downloads it. The selected version of a package is the pinned one if it
+/// satisfies all requirements on the package, or the highest version that does otherwise. As the
+/// requirements depend on the selected versions, selection is repeated until it no longer changes.
+pub(crate) fn select_versions(
+    root_package: &SourceManifest,
+    root_path: &Path,
+    dev_mode: bool,
+    pins: &RegistryPins,
+) -> Result<BTreeMap<PackageName, RegistryPackage>> {
+    let mut selected = BTreeMap::new();
+    for _ in 0..MAX_SELECTION_ROUNDS {
+        let mut requirements = BTreeMap::new();
+        collect_requirements(
+            root_package,
+            root_path,
+            dev_mode,
+            &selected,
+            &mut requirements,
+            &mut BTreeSet::new(),
+        );
+        let new_selected = requirements
+            .into_iter()
+            .map(|(name, reqs)| Ok((name, select_version(name, &reqs, pins)?)))
+            .collect::<Result<BTreeMap<_, _>>>()?;
+        if new_selected == selected {
+            return Ok(selected);
+        }
+        selected = new_selected;
+    }
+    bail!(
+        "Unable to select consistent versions for the registry dependencies of package '{}'",
+        root_package.package.name
+    )
+}
+
+fn collect_requirements(
+    package: &SourceManifest,
+    package_path: &Path,
+    dev_mode: bool,
+    selected: &BTreeMap<PackageName, RegistryPackage>,
+    requirements: &mut BTreeMap<PackageName, Vec<Requirement>>,
+    visited: &mut BTreeSet<PackageName>,
+) {
+    if !visited.insert(package.package.name) {
+        return;
+    }
+    let empty_deps = BTreeMap::new();
+    let dev_deps = if dev_mode {
+        &package.dev_dependencies
+    } else {
+        &empty_deps
+    };
+    for (dep_name, dep) in package.dependencies.iter().chain(dev_deps.iter()) {
+        // Problems with dependencies are reported when resolving the package graph
+        let dep_package = match &dep.registry_info {
+            Some(info) => {
+                requirements
+                    .entry(*dep_name)
+                    .or_default()
+                    .push(Requirement {
+                        registry: info.registry,
+                        version_req: info.version_req.clone(),
+                        required_by: package.package.name,
+                    });
+                selected.get(dep_name).and_then(|selected| {
+                    ResolvingGraph::read_package_manifest(dep_name, selected.package_path.clone())
+                        .ok()
+                })
+            }
+            None => ResolvingGraph::download_and_update_if_repo(*dep_name, dep)
+                .and_then(|_| {
+                    ResolvingGraph::parse_package_manifest(
+                        dep,
+                        dep_name,
+                        package_path.to_path_buf(),
+                    )
+                })
+                .ok(),
+        };
+        if let Some((dep_package, dep_path)) = dep_package {
+            collect_requirements(
+                &dep_package,
+                &dep_path,
+                dev_mode,
+                selected,
+                requirements,
+                visited,
+            );
+        }
+    }
+}
+
+fn select_version(
+    name: PackageName,
+    requirements: &[Requirement],
+    pins: &RegistryPins,
+) -> Result<RegistryPackage> {
+    let registry = requirements[0].registry;
+    if let Some(other) = requirements.iter().find(|req| req.registry != registry) {
+        bail!(
+            "Package '{}' is required from different registries: from '{}' by '{}' and from '{}' \
+            by '{}'",
+            name,
+            registry,
+            requirements[0].required_by,
+            other.registry,
+            other.required_by
+        )
+    }
+    let registry_dir = Registry::new(registry.as_str())?;
+    let versions = registry_dir.versions(name)?;
+    if versions.is_empty() {
+        bail!(
+            "Package '{}' is not published in registry '{}'",
+            name,
+            registry
+        )
+    }
+    let matching = versions
+        .iter()
+        .map(|(version, _)| *version)
+        .filter(|version| {
+            requirements
+                .iter()
+                .all(|req| req.version_req.matches(version))
+        })
+        .collect::<Vec<_>>();
+    let version = match pins.get(&name) {
+        Some((pinned_registry, pinned)) if *pinned_registry == registry && matching.contains(pinned) => {
+            *pinned
+        }
+        _ => match matching.iter().max() {
+            Some(version) => *version,
+            None => bail!(
+                "No version of package '{}' in registry '{}' satisfies all requirements on it:\n{}\n\
+                Published versions: {}",
+                name,
+                registry,
+                requirements
+                    .iter()
+                    .map(|req| format!("  {} (required by '{}')", req.version_req, req.required_by))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+                versions
+                    .iter()
+                    .map(|(version, _)| version_string(*version))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        },
+    };
+    let package_path = registry_dir.fetch(name, version)?;
+    Ok(RegistryPackage {
+        registry,
+        version,
+        package_path,
+    })
+}
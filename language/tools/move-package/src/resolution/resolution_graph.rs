@@ -2,12 +2,15 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    resolution::digest::compute_digest,
+    resolution::{
+        digest::compute_digest,
+        registry::{self, RegistryPackage, RegistryPins},
+    },
     source_package::{
         layout::SourcePackageLayout,
         manifest_parser::{parse_move_manifest_string, parse_source_manifest},
         parsed_manifest::{
-            Dependencies, Dependency, FileName, NamedAddress, PackageDigest, PackageName,
+            Dependencies, Dependency, FileName, GitInfo, NamedAddress, PackageDigest, PackageName,
             SourceManifest, SubstOrRename,
         },
     },
@@ -38,8 +41,8 @@ pub type GraphIndex = PackageName;
 
 type ResolutionTable<T> = BTreeMap<NamedAddress, T>;
 type ResolvingTable = ResolutionTable<ResolvingNamedAddress>;
-type ResolvingGraph = ResolutionGraph<ResolvingNamedAddress>;
-type ResolvingPackage = ResolutionPackage<ResolvingNamedAddress>;
+pub(crate) type ResolvingGraph = ResolutionGraph<ResolvingNamedAddress>;
+pub(crate) type ResolvingPackage = ResolutionPackage<ResolvingNamedAddress>;
 
 #[derive(Debug, Clone)]
 pub struct ResolvingNamedAddress {
@@ -69,6 +72,11 @@ pub struct ResolutionGraph<T> {
     pub graph: DiGraphMap<PackageName, ()>,
     /// A mapping of package name to its resolution
     pub package_table: BTreeMap<PackageName, ResolutionPackage<T>>,
+    /// The versions selected for the packages that are dependencies from a registry
+    pub registry_packages: BTreeMap<PackageName, RegistryPackage>,
+    /// The members of the workspace if the root package is the virtual root of a workspace, and
+    /// empty otherwise
+    pub workspace_members: BTreeSet<PackageName>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -89,21 +97,54 @@ pub struct ResolutionPackage<T> {
 
 impl ResolvingGraph {
     pub fn new(
+        root_package: SourceManifest,
+        root_package_path: PathBuf,
+        build_options: BuildConfig,
+    ) -> Result<ResolvingGraph> {
+        Self::new_with_registry_pins(
+            root_package,
+            root_package_path,
+            build_options,
+            &RegistryPins::new(),
+            BTreeSet::new(),
+        )
+    }
+
+    /// Creates the resolution graph, preferring the versions in `pins` for the packages that are
+    /// dependencies from a registry. If the root package is the virtual root of a workspace,
+    /// `workspace_members` are its members, whose dev addresses are used like those of the root.
+    pub fn new_with_registry_pins(
         root_package: SourceManifest,
         root_package_path: PathBuf,
         mut build_options: BuildConfig,
+        pins: &RegistryPins,
+        workspace_members: BTreeSet<PackageName>,
     ) -> Result<ResolvingGraph> {
         if build_options.architecture.is_none() {
             if let Some(info) = &root_package.build {
                 build_options.architecture = info.architecture;
             }
         }
+        let registry_packages = registry::select_versions(
+            &root_package,
+            &root_package_path,
+            build_options.dev_mode,
+            pins,
+        )
+        .with_context(|| {
+            format!(
+                "Unable to resolve registry dependencies for package '{}'",
+                root_package.package.name
+            )
+        })?;
         let mut resolution_graph = Self {
             root_package_path: root_package_path.clone(),
             build_options,
             root_package: root_package.clone(),
             graph: DiGraphMap::new(),
             package_table: BTreeMap::new(),
+            registry_packages,
+            workspace_members,
         };
 
         resolution_graph
@@ -124,6 +165,8 @@ impl ResolvingGraph {
             root_package,
             graph,
             package_table,
+            registry_packages,
+            workspace_members,
         } = self;
 
         let mut unresolved_addresses = Vec::new();
@@ -184,6 +227,8 @@ impl ResolvingGraph {
             root_package,
             graph,
             package_table: resolved_package_table,
+            registry_packages,
+            workspace_members,
         })
     }
 
@@ -274,6 +319,7 @@ impl ResolvingGraph {
             })?;
         }
 
+        let is_root_package = is_root_package || self.workspace_members.contains(&package_name);
         self.unify_addresses_in_package(&package, &mut resolution_table, is_root_package)?;
 
         let source_digest =
@@ -392,9 +438,20 @@ impl ResolvingGraph {
         root_path: PathBuf,
     ) -> Result<(Renaming, ResolvingTable)> {
         Self::download_and_update_if_repo(dep_name_in_pkg, &dep)?;
-        let (dep_package, dep_package_dir) =
-            Self::parse_package_manifest(&dep, &dep_name_in_pkg, root_path)
-                .with_context(|| format!("While processing dependency '{}'", dep_name_in_pkg))?;
+        let (dep_package, dep_package_dir) = match &dep.registry_info {
+            None => Self::parse_package_manifest(&dep, &dep_name_in_pkg, root_path),
+            Some(_) => match self.registry_packages.get(&dep_name_in_pkg) {
+                Some(registry_package) => Self::read_package_manifest(
+                    &dep_name_in_pkg,
+                    registry_package.package_path.clone(),
+                ),
+                None => Err(anyhow::format_err!(
+                    "No version selected for registry dependency '{}'",
+                    dep_name_in_pkg
+                )),
+            },
+        }
+        .with_context(|| format!("While processing dependency '{}'", dep_name_in_pkg))?;
         self.build_resolution_graph(dep_package.clone(), dep_package_dir, false)
             .with_context(|| {
                 format!("Unable to resolve package dependency '{}'", dep_name_in_pkg)
@@ -494,22 +551,29 @@ impl ResolvingGraph {
         }
     }
 
-    fn parse_package_manifest(
+    pub(crate) fn parse_package_manifest(
         dep: &Dependency,
         dep_name: &PackageName,
         mut root_path: PathBuf,
     ) -> Result<(SourceManifest, PathBuf)> {
         root_path.push(&dep.local);
-        match fs::read_to_string(&root_path.join(SourcePackageLayout::Manifest.path())) {
+        Self::read_package_manifest(dep_name, root_path)
+    }
+
+    pub(crate) fn read_package_manifest(
+        dep_name: &PackageName,
+        package_path: PathBuf,
+    ) -> Result<(SourceManifest, PathBuf)> {
+        match fs::read_to_string(package_path.join(SourcePackageLayout::Manifest.path())) {
             Ok(contents) => {
                 let source_package: SourceManifest =
                     parse_move_manifest_string(contents).and_then(parse_source_manifest)?;
-                Ok((source_package, root_path))
+                Ok((source_package, package_path))
             }
             Err(_) => Err(anyhow::format_err!(
                 "Unable to find package manifest for '{}' at {:?}",
                 dep_name,
-                SourcePackageLayout::Manifest.path().join(root_path),
+                SourcePackageLayout::Manifest.path().join(package_path),
             )),
         }
     }
@@ -518,6 +582,23 @@ impl ResolvingGraph {
         manifest: &SourceManifest,
         build_options: &BuildConfig,
         root_path: &Path,
+    ) -> Result<()> {
+        Self::sync_dependency_repos(
+            manifest,
+            build_options,
+            root_path,
+            &RepoSync::Download,
+            &mut BTreeSet::new(),
+        )
+    }
+
+    /// Brings the git checkouts of all transitive dependencies of `manifest` in line with `sync`.
+    pub(crate) fn sync_dependency_repos(
+        manifest: &SourceManifest,
+        build_options: &BuildConfig,
+        package_path: &Path,
+        sync: &RepoSync,
+        visited: &mut BTreeSet<PackageName>,
     ) -> Result<()> {
         // include dev dependencies if in dev mode
         let empty_deps;
@@ -529,18 +610,28 @@ impl ResolvingGraph {
         };
 
         for (dep_name, dep) in manifest.dependencies.iter().chain(additional_deps.iter()) {
+            // Packages from a registry are downloaded once their version is selected
+            if dep.registry_info.is_some() || !visited.insert(*dep_name) {
+                continue;
+            }
             Self::download_and_update_if_repo(*dep_name, dep)?;
+            if let Some(git_info) = &dep.git_info {
+                Self::sync_repo(*dep_name, git_info, sync)?;
+            }
 
-            let (dep_manifest, _) =
-                Self::parse_package_manifest(dep, dep_name, root_path.to_path_buf())
+            let (dep_manifest, dep_path) =
+                Self::parse_package_manifest(dep, dep_name, package_path.to_path_buf())
                     .with_context(|| format!("While processing dependency '{}'", *dep_name))?;
             // download dependencies of dependencies
-            Self::download_dependency_repos(&dep_manifest, &build_options, root_path)?;
+            Self::sync_dependency_repos(&dep_manifest, build_options, &dep_path, sync, visited)?;
         }
         Ok(())
     }
 
-    fn download_and_update_if_repo(dep_name: PackageName, dep: &Dependency) -> Result<()> {
+    pub(crate) fn download_and_update_if_repo(
+        dep_name: PackageName,
+        dep: &Dependency,
+    ) -> Result<()> {
         if let Some(git_info) = &dep.git_info {
             if !git_info.download_to.exists() {
                 Command::new("git")
@@ -572,6 +663,78 @@ impl ResolvingGraph {
         }
         Ok(())
     }
+
+    fn sync_repo(dep_name: PackageName, git_info: &GitInfo, sync: &RepoSync) -> Result<()> {
+        let target = match sync {
+            RepoSync::Download => return Ok(()),
+            RepoSync::Pinned(pins) => match pins.get(&(git_info.git_url, git_info.git_rev)) {
+                Some(commit) if commit != &git_head(git_info)? => commit.clone(),
+                _ => return Ok(()),
+            },
+            RepoSync::Update => {
+                run_git(git_info, &["fetch", "--quiet", "origin"])?;
+                // Branches have to be taken from the remote, tags and commits can be used as is
+                let remote_branch = format!("origin/{}", git_info.git_rev);
+                match run_git(
+                    git_info,
+                    &[
+                        "rev-parse",
+                        "--verify",
+                        &format!("{}^{{commit}}", remote_branch),
+                    ],
+                ) {
+                    Ok(_) => remote_branch,
+                    Err(_) => git_info.git_rev.to_string(),
+                }
+            }
+        };
+        // The commit may be newer than the download, in which case it needs to be fetched first
+        run_git(git_info, &["checkout", "--quiet", &target])
+            .or_else(|_| {
+                run_git(git_info, &["fetch", "--quiet", "origin"])?;
+                run_git(git_info, &["checkout", "--quiet", &target])
+            })
+            .with_context(|| {
+                format!(
+                    "Failed to checkout Git reference '{}' for package '{}'",
+                    target, dep_name
+                )
+            })?;
+        Ok(())
+    }
+}
+
+/// How the git checkouts of dependencies are brought up to date before resolution
+pub(crate) enum RepoSync {
+    /// Download missing repos at the revision given in the manifest, and keep existing ones as is
+    Download,
+    /// Check out the commits pinned for the (url, revision) pairs of the dependencies
+    Pinned(BTreeMap<(Symbol, Symbol), String>),
+    /// Fetch the repos and check out the latest commit of the revision given in the manifest
+    Update,
+}
+
+/// Returns the SHA of the commit that the git checkout of a dependency is at.
+pub(crate) fn git_head(git_info: &GitInfo) -> Result<String> {
+    run_git(git_info, &["rev-parse", "HEAD"])
+}
+
+fn run_git(git_info: &GitInfo, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(&git_info.download_to)
+        .args(args)
+        .output()
+        .with_context(|| format!("Failed to run 'git {}'", args.join(" ")))?;
+    if !output.status.success() {
+        bail!(
+            "'git {}' failed for the repository of '{}': {}",
+            args.join(" "),
+            git_info.git_url,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
 }
 
 impl ResolvingPackage {
@@ -658,7 +821,7 @@ impl ResolvingPackage {
         Ok(places_to_look)
     }
 
-    fn get_package_digest_for_config(
+    pub(crate) fn get_package_digest_for_config(
         package_path: &Path,
         config: &BuildConfig,
     ) -> Result<PackageDigest> {
@@ -737,6 +900,48 @@ impl ResolvedGraph {
             .map(|(name, addr)| (*name, *addr))
     }
 
+    /// Returns whether the root package is the virtual root of a workspace
+    pub fn is_workspace(&self) -> bool {
+        !self.workspace_members.is_empty()
+    }
+
+    /// Returns the graphs of the members of the workspace, each rooted at its member, or the graph
+    /// itself if it is not the graph of a workspace.
+    pub fn member_graphs(self) -> Vec<ResolvedGraph> {
+        if !self.is_workspace() {
+            return vec![self];
+        }
+        self.workspace_members
+            .iter()
+            .map(|member| self.member_graph(member))
+            .collect()
+    }
+
+    /// Returns the graph of the workspace rooted at its member `member`. The other members are kept
+    /// in the graph, and all members are built in the build directory of the workspace unless
+    /// another installation directory is set.
+    pub fn member_graph(&self, member: &PackageName) -> ResolvedGraph {
+        let workspace_name = self.root_package.package.name;
+        let mut graph = self.graph.clone();
+        graph.remove_node(workspace_name);
+        let mut package_table = self.package_table.clone();
+        package_table.remove(&workspace_name);
+        let member_package = &package_table[member];
+        let mut build_options = self.build_options.clone();
+        if build_options.install_dir.is_none() {
+            build_options.install_dir = Some(self.root_package_path.clone());
+        }
+        ResolvedGraph {
+            root_package_path: member_package.package_path.clone(),
+            build_options,
+            root_package: member_package.source_package.clone(),
+            graph,
+            package_table,
+            registry_packages: self.registry_packages.clone(),
+            workspace_members: BTreeSet::new(),
+        }
+    }
+
     pub fn file_sources(&self) -> BTreeMap<FileHash, (Symbol, String)> {
         self.package_table
             .iter()
@@ -16,6 +16,7 @@ pub enum SourcePackageLayout {
     Scripts,
     Examples,
     Manifest,
+    LockFile,
     DocTemplates,
 }
 
@@ -23,6 +24,7 @@ impl SourcePackageLayout {
     /// A Move source package is laid out on-disk as
     /// a_move_package
     /// ├── Move.toml      (required)
+    /// ├── Move.lock      (optional, generated)
     /// ├── sources        (required)
     /// ├── examples       (optional, dev mode)
     /// ├── scripts        (optional)
@@ -52,6 +54,7 @@ impl SourcePackageLayout {
         match self {
             Self::Sources => "sources",
             Self::Manifest => "Move.toml",
+            Self::LockFile => "Move.lock",
             Self::Tests => "tests",
             Self::Scripts => "scripts",
             Self::Examples => "examples",
@@ -65,6 +68,7 @@ impl SourcePackageLayout {
             Self::Sources | Self::Manifest => false,
             Self::Tests
             | Self::Scripts
+            | Self::LockFile
             | Self::Examples
             | Self::Specifications
             | Self::DocTemplates => true,
@@ -1,8 +1,12 @@
 // Copyright (c) The Diem Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{source_package::parsed_manifest as PM, Architecture};
+use crate::{
+    source_package::{parsed_manifest as PM, version_req::VersionReq},
+    Architecture,
+};
 use anyhow::{bail, format_err, Context, Result};
+use move_compiler::{diagnostics::codes::WarningFilter, linters};
 use move_core_types::account_address::{AccountAddress, AccountAddressParseError};
 use move_symbol_pool::symbol::Symbol;
 use std::{
@@ -17,14 +21,18 @@ const EMPTY_ADDR_STR: &str = "_";
 
 const PACKAGE_NAME: &str = "package";
 const BUILD_NAME: &str = "build";
+const LINTS_NAME: &str = "lints";
 const ADDRESSES_NAME: &str = "addresses";
 const DEV_ADDRESSES_NAME: &str = "dev-addresses";
 const DEPENDENCY_NAME: &str = "dependencies";
 const DEV_DEPENDENCY_NAME: &str = "dev-dependencies";
+const WORKSPACE_NAME: &str = "workspace";
+const MEMBERS_NAME: &str = "members";
 
 const KNOWN_NAMES: &[&str] = &[
     PACKAGE_NAME,
     BUILD_NAME,
+    LINTS_NAME,
     ADDRESSES_NAME,
     DEV_ADDRESSES_NAME,
     DEPENDENCY_NAME,
@@ -33,6 +41,18 @@ const KNOWN_NAMES: &[&str] = &[
 
 const REQUIRED_FIELDS: &[&str] = &[PACKAGE_NAME];
 
+const WORKSPACE_KNOWN_NAMES: &[&str] = &[WORKSPACE_NAME, ADDRESSES_NAME, DEV_ADDRESSES_NAME];
+
+// Sections that only apply to packages, and must be declared in the manifests of the members of
+// a workspace instead
+const WORKSPACE_DISALLOWED_NAMES: &[&str] = &[
+    PACKAGE_NAME,
+    BUILD_NAME,
+    LINTS_NAME,
+    DEPENDENCY_NAME,
+    DEV_DEPENDENCY_NAME,
+];
+
 pub fn parse_move_manifest_from_file(path: &Path) -> Result<PM::SourceManifest> {
     let file_contents = if path.is_file() {
         std::fs::read_to_string(path)?
@@ -73,6 +93,12 @@ pub fn parse_source_manifest(tval: TV) -> Result<PM::SourceManifest> {
                 .map(parse_build_info)
                 .transpose()
                 .context("Error parsing '[build]' section of manifest")?;
+            let lints = table
+                .remove(LINTS_NAME)
+                .map(parse_lints)
+                .transpose()
+                .context("Error parsing '[lints]' section of manifest")?
+                .unwrap_or_else(BTreeMap::new);
             let dependencies = table
                 .remove(DEPENDENCY_NAME)
                 .map(parse_dependencies)
@@ -90,6 +116,7 @@ pub fn parse_source_manifest(tval: TV) -> Result<PM::SourceManifest> {
                 addresses,
                 dev_address_assignments,
                 build,
+                lints,
                 dependencies,
                 dev_dependencies,
             })
@@ -104,6 +131,89 @@ pub fn parse_source_manifest(tval: TV) -> Result<PM::SourceManifest> {
     }
 }
 
+/// Returns whether `tval` is the manifest of a workspace rather than of a package
+pub fn is_workspace_manifest(tval: &TV) -> bool {
+    tval.get(WORKSPACE_NAME).is_some()
+}
+
+pub fn parse_workspace_manifest(tval: TV) -> Result<PM::WorkspaceManifest> {
+    match tval {
+        TV::Table(mut table) => {
+            if let Some(name) = WORKSPACE_DISALLOWED_NAMES
+                .iter()
+                .find(|name| table.contains_key(**name))
+            {
+                bail!(
+                    "Error parsing workspace manifest: a workspace cannot have a '[{}]' section, \
+                    it must be declared in the manifests of the members instead",
+                    name
+                )
+            }
+            warn_if_unknown_field_names(&table, WORKSPACE_KNOWN_NAMES);
+            let members = table
+                .remove(WORKSPACE_NAME)
+                .map(parse_workspace_members)
+                .transpose()
+                .context("Error parsing '[workspace]' section of manifest")?
+                .unwrap();
+            let addresses = table
+                .remove(ADDRESSES_NAME)
+                .map(parse_addresses)
+                .transpose()
+                .context("Error parsing '[addresses]' section of manifest")?;
+            let dev_address_assignments = table
+                .remove(DEV_ADDRESSES_NAME)
+                .map(parse_dev_addresses)
+                .transpose()
+                .context("Error parsing '[dev-addresses]' section of manifest")?;
+            Ok(PM::WorkspaceManifest {
+                members,
+                addresses,
+                dev_address_assignments,
+            })
+        }
+        x => {
+            bail!(
+                "Malformed workspace manifest {}. Expected a table at top level, but encountered a {}",
+                x,
+                x.type_str()
+            )
+        }
+    }
+}
+
+fn parse_workspace_members(tval: TV) -> Result<Vec<PathBuf>> {
+    match tval {
+        TV::Table(mut table) => {
+            check_for_required_field_names(&table, &[MEMBERS_NAME])?;
+            warn_if_unknown_field_names(&table, &[MEMBERS_NAME]);
+            let members = match table.remove(MEMBERS_NAME).unwrap() {
+                TV::Array(members) => members
+                    .into_iter()
+                    .map(|member| match member.as_str() {
+                        Some(path) => Ok(PathBuf::from(path)),
+                        None => bail!("Invalid member path {}, expected a string", member),
+                    })
+                    .collect::<Result<Vec<_>>>()?,
+                x => bail!(
+                    "Invalid '{}' field, expected an array of paths but encountered a {}",
+                    MEMBERS_NAME,
+                    x.type_str()
+                ),
+            };
+            if members.is_empty() {
+                bail!("A workspace must have at least one member")
+            }
+            Ok(members)
+        }
+        x => bail!(
+            "Malformed section in manifest {}. Expected a table, but encountered a {}",
+            x,
+            x.type_str()
+        ),
+    }
+}
+
 pub fn parse_package_info(tval: TV) -> Result<PM::PackageInfo> {
     match tval {
         TV::Table(mut table) => {
@@ -181,13 +291,42 @@ pub fn parse_dependencies(tval: TV) -> Result<PM::Dependencies> {
 pub fn parse_build_info(tval: TV) -> Result<PM::BuildInfo> {
     match tval {
         TV::Table(mut table) => {
-            warn_if_unknown_field_names(&table, &["language_version", "arch"]);
+            warn_if_unknown_field_names(
+                &table,
+                &[
+                    "language_version",
+                    "arch",
+                    "warnings_are_errors",
+                    "allow",
+                    "deny",
+                ],
+            );
+            let warnings_are_errors = match table.remove("warnings_are_errors") {
+                None => false,
+                Some(tval) => tval.as_bool().ok_or_else(|| {
+                    format_err!(
+                        "Invalid value {} for 'warnings_are_errors'. Expected a boolean",
+                        tval
+                    )
+                })?,
+            };
             Ok(PM::BuildInfo {
                 language_version: table
                     .remove("language_version")
                     .map(parse_version)
                     .transpose()?,
                 architecture: table.remove("arch").map(parse_architecture).transpose()?,
+                warnings_are_errors,
+                allow: table
+                    .remove("allow")
+                    .map(|tval| parse_warning_filters("allow", tval))
+                    .transpose()?
+                    .unwrap_or_default(),
+                deny: table
+                    .remove("deny")
+                    .map(|tval| parse_warning_filters("deny", tval))
+                    .transpose()?
+                    .unwrap_or_default(),
             })
         }
         x => bail!(
@@ -198,6 +337,65 @@ pub fn parse_build_info(tval: TV) -> Result<PM::BuildInfo> {
     }
 }
 
+fn parse_warning_filters(field_name: &str, tval: TV) -> Result<Vec<WarningFilter>> {
+    let filters = tval.as_array().ok_or_else(|| {
+        format_err!(
+            "Invalid value {} for '{}'. Expected a list of warnings",
+            tval,
+            field_name
+        )
+    })?;
+    filters
+        .iter()
+        .map(|filter| {
+            filter
+                .as_str()
+                .ok_or_else(|| {
+                    format_err!(
+                        "Invalid warning {} in '{}'. Expected a string but found a {}",
+                        filter,
+                        field_name,
+                        filter.type_str()
+                    )
+                })?
+                .parse()
+                .map_err(|e: String| format_err!(e))
+                .with_context(|| format!("Invalid warning in '{}'", field_name))
+        })
+        .collect()
+}
+
+pub fn parse_lints(tval: TV) -> Result<PM::LintLevels> {
+    match tval {
+        TV::Table(table) => {
+            warn_if_unknown_field_names(&table, &linters::lint_names().collect::<Vec<_>>());
+            let mut lints = BTreeMap::new();
+            for (lint_name, level) in table.into_iter() {
+                let level = level
+                    .as_str()
+                    .ok_or_else(|| {
+                        format_err!(
+                            "Invalid level {} for lint '{}'. Expected a string but found a {}",
+                            level,
+                            lint_name,
+                            level.type_str()
+                        )
+                    })?
+                    .parse()
+                    .map_err(|e: String| format_err!(e))
+                    .with_context(|| format!("Invalid level for lint '{}'", lint_name))?;
+                lints.insert(Symbol::from(lint_name), level);
+            }
+            Ok(lints)
+        }
+        x => bail!(
+            "Malformed section in manifest {}. Expected a table, but encountered a {}",
+            x,
+            x.type_str()
+        ),
+    }
+}
+
 pub fn parse_addresses(tval: TV) -> Result<PM::AddressDeclarations> {
     match tval {
         TV::Table(table) => {
@@ -302,14 +500,42 @@ fn parse_dependency(tval: TV) -> Result<PM::Dependency> {
                     "git",
                     "rev",
                     "subdir",
+                    "registry",
                 ],
             );
             let subst = table
                 .remove("addr_subst")
                 .map(parse_substitution)
                 .transpose()?;
-            let version = table.remove("version").map(parse_version).transpose()?;
             let digest = table.remove("digest").map(parse_digest).transpose()?;
+            if let Some(registry) = table.remove("registry") {
+                if table.contains_key("local") || table.contains_key("git") {
+                    bail!("'registry' cannot be specified along with 'local' or 'git' for dependency.")
+                }
+                let registry = registry
+                    .as_str()
+                    .ok_or_else(|| format_err!("Registry location not a string"))?;
+                let version_req = match table.remove("version") {
+                    None => bail!("Version requirement not supplied for registry dependency"),
+                    Some(v) => v
+                        .as_str()
+                        .ok_or_else(|| format_err!("Version requirement not a string"))?
+                        .parse::<VersionReq>()?,
+                };
+                // The package is only found in the registry once its version has been selected
+                return Ok(PM::Dependency {
+                    subst,
+                    version: None,
+                    digest,
+                    local: PathBuf::new(),
+                    git_info: None,
+                    registry_info: Some(PM::RegistryInfo {
+                        registry: Symbol::from(registry_path(registry)?.to_string_lossy().as_ref()),
+                        version_req,
+                    }),
+                });
+            }
+            let version = table.remove("version").map(parse_version).transpose()?;
             let mut git_info = None;
             match (table.remove("local"), table.remove("git")) {
                 (Some(local), None) => {
@@ -323,19 +549,11 @@ fn parse_dependency(tval: TV) -> Result<PM::Dependency> {
                         digest,
                         local: local_path,
                         git_info,
+                        registry_info: None,
                     })
                 }
                 (None, Some(git)) => {
-                    // Look to see if a MOVE_HOME has been set. Otherwise default to $HOME
-                    let move_home = std::env::var("MOVE_HOME").unwrap_or_else(|_| {
-                        format!(
-                            "{}/.move",
-                            dirs_next::home_dir()
-                                .expect("user's home directory not found")
-                                .to_str()
-                                .unwrap()
-                        )
-                    });
+                    let move_home = move_home();
                     let rev_name = match table.remove("rev") {
                         None => bail!("Git revision not supplied for dependency"),
                         Some(r) => Symbol::from(
@@ -344,12 +562,11 @@ fn parse_dependency(tval: TV) -> Result<PM::Dependency> {
                         ),
                     };
                     // Downloaded packages are of the form <sanitized_git_url>_<rev_name>
-                    let local_path = PathBuf::from(move_home).join(format!(
+                    let local_path = move_home.join(format!(
                         "{}_{}",
-                        regex::Regex::new(r"/|:|\.|@").unwrap().replace_all(
+                        sanitize_url(
                             git.as_str()
-                                .ok_or_else(|| anyhow::anyhow!("Git URL not a string"))?,
-                            "_"
+                                .ok_or_else(|| anyhow::anyhow!("Git URL not a string"))?
                         ),
                         rev_name.replace('/', "__")
                     ));
@@ -376,6 +593,7 @@ fn parse_dependency(tval: TV) -> Result<PM::Dependency> {
                         digest,
                         local: local_path.join(subdir),
                         git_info,
+                        registry_info: None,
                     })
                 }
                 (Some(_), Some(_)) => {
@@ -390,6 +608,49 @@ fn parse_dependency(tval: TV) -> Result<PM::Dependency> {
     }
 }
 
+/// Returns the directory that dependencies are downloaded to
+pub fn move_home() -> PathBuf {
+    // Look to see if a MOVE_HOME has been set. Otherwise default to $HOME
+    PathBuf::from(std::env::var("MOVE_HOME").unwrap_or_else(|_| {
+        format!(
+            "{}/.move",
+            dirs_next::home_dir()
+                .expect("user's home directory not found")
+                .to_str()
+                .unwrap()
+        )
+    }))
+}
+
+/// Turns a url into a name that can be used for a directory in MOVE_HOME
+pub fn sanitize_url(url: &str) -> String {
+    regex::Regex::new(r"/|:|\.|@")
+        .unwrap()
+        .replace_all(url, "_")
+        .to_string()
+}
+
+/// Returns the directory of a registry from its location in a manifest, which must be an absolute
+/// path or a `file://` url as only registries in the file system are supported.
+pub fn registry_path(location: &str) -> Result<PathBuf> {
+    let path = match location.strip_prefix("file://") {
+        Some(path) => path,
+        None if location.contains("://") => bail!(
+            "Unsupported registry '{}'. Only registries in the file system are supported",
+            location
+        ),
+        None => location,
+    };
+    let path = PathBuf::from(path);
+    if !path.is_absolute() {
+        bail!(
+            "Registry location '{}' is not an absolute path or a 'file://' url",
+            location
+        )
+    }
+    Ok(path)
+}
+
 fn parse_substitution(tval: TV) -> Result<PM::Substitution> {
     match tval {
         TV::Table(table) => {
@@ -423,7 +684,10 @@ fn parse_substitution(tval: TV) -> Result<PM::Substitution> {
 }
 
 fn parse_version(tval: TV) -> Result<PM::Version> {
-    let version_str = tval.as_str().unwrap();
+    parse_version_str(tval.as_str().unwrap())
+}
+
+pub fn parse_version_str(version_str: &str) -> Result<PM::Version> {
     let version_parts = version_str.split('.').collect::<Vec<_>>();
     if version_parts.len() != 3 {
         bail!(
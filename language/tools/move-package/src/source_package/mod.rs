@@ -4,3 +4,5 @@
 pub mod layout;
 pub mod manifest_parser;
 pub mod parsed_manifest;
+pub mod version_req;
+pub mod workspace;
@@ -1,7 +1,8 @@
 // Copyright (c) The Diem Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::Architecture;
+use crate::{source_package::version_req::VersionReq, Architecture};
+use move_compiler::{diagnostics::codes::WarningFilter, linters::LintLevel};
 use move_core_types::account_address::AccountAddress;
 use move_symbol_pool::symbol::Symbol;
 use std::{collections::BTreeMap, path::PathBuf};
@@ -16,6 +17,7 @@ pub type DevAddressDeclarations = BTreeMap<NamedAddress, AccountAddress>;
 pub type Version = (u64, u64, u64);
 pub type Dependencies = BTreeMap<PackageName, Dependency>;
 pub type Substitution = BTreeMap<NamedAddress, SubstOrRename>;
+pub type LintLevels = BTreeMap<Symbol, LintLevel>;
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct SourceManifest {
@@ -23,10 +25,21 @@ pub struct SourceManifest {
     pub addresses: Option<AddressDeclarations>,
     pub dev_address_assignments: Option<DevAddressDeclarations>,
     pub build: Option<BuildInfo>,
+    pub lints: LintLevels,
     pub dependencies: Dependencies,
     pub dev_dependencies: Dependencies,
 }
 
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct WorkspaceManifest {
+    /// The paths of the member packages, relative to the root of the workspace
+    pub members: Vec<PathBuf>,
+    /// Addresses shared by all members
+    pub addresses: Option<AddressDeclarations>,
+    /// Dev addresses shared by all members
+    pub dev_address_assignments: Option<DevAddressDeclarations>,
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct PackageInfo {
     pub name: PackageName,
@@ -42,6 +55,7 @@ pub struct Dependency {
     pub version: Option<Version>,
     pub digest: Option<PackageDigest>,
     pub git_info: Option<GitInfo>,
+    pub registry_info: Option<RegistryInfo>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -57,10 +71,21 @@ pub struct GitInfo {
     pub download_to: PathBuf,
 }
 
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RegistryInfo {
+    /// The directory of the registry that the package is published to
+    pub registry: Symbol,
+    /// The versions of the package that can be used
+    pub version_req: VersionReq,
+}
+
 #[derive(Default, Debug, Clone, Eq, PartialEq)]
 pub struct BuildInfo {
     pub language_version: Option<Version>,
     pub architecture: Option<Architecture>,
+    pub warnings_are_errors: bool,
+    pub allow: Vec<WarningFilter>,
+    pub deny: Vec<WarningFilter>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
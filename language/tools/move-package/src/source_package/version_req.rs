@@ -0,0 +1,40 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::source_package::parsed_manifest::Version;
+use anyhow::{Context, Result};
+use std::{fmt, str::FromStr};
+
+/// A semantic versioning requirement on the version of a registry dependency, e.g. `^1.2` or
+/// `>=1.2.0, <1.5.0`. A version matches the requirement if it matches all of its comparators.
+///
+/// Requirements are parsed and matched by the `semver` crate, following the conventions of
+/// Cargo: a bare version like `1.2` is the same as the caret requirement `^1.2`, which allows any
+/// version that does not change the left-most non-zero component of the version
+/// (`>=1.2.0, <2.0.0`). A tilde requirement like `~1.2` only allows changes to the patch version
+/// (`>=1.2.0, <1.3.0`), and `*` allows any version.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct VersionReq(semver::VersionReq);
+
+impl VersionReq {
+    pub fn matches(&self, version: &Version) -> bool {
+        let (major, minor, patch) = *version;
+        self.0.matches(&semver::Version::new(major, minor, patch))
+    }
+}
+
+impl FromStr for VersionReq {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let req = semver::VersionReq::parse(s)
+            .with_context(|| format!("Invalid version requirement '{}'", s))?;
+        Ok(Self(req))
+    }
+}
+
+impl fmt::Display for VersionReq {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
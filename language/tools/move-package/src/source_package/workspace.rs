@@ -0,0 +1,127 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::source_package::{
+    layout::SourcePackageLayout,
+    manifest_parser,
+    parsed_manifest::{Dependency, PackageInfo, PackageName, SourceManifest, WorkspaceManifest},
+};
+use anyhow::{bail, Context, Result};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// The name of the virtual root package of a workspace
+pub const WORKSPACE_PACKAGE_NAME: &str = "Workspace";
+
+/// Returns the virtual root package of the workspace at `workspace_path`. It has the addresses of
+/// the workspace and depends on all of its members, so that the members are resolved together into
+/// a single package graph.
+pub fn workspace_package(
+    workspace: WorkspaceManifest,
+    workspace_path: &Path,
+) -> Result<SourceManifest> {
+    let mut dependencies = BTreeMap::new();
+    for member_path in workspace.members {
+        let member =
+            manifest_parser::parse_move_manifest_from_file(&workspace_path.join(&member_path))
+                .with_context(|| {
+                    format!(
+                        "Unable to read the manifest of workspace member '{}'",
+                        member_path.to_string_lossy()
+                    )
+                })?;
+        let name = member.package.name;
+        if name.as_str() == WORKSPACE_PACKAGE_NAME {
+            bail!(
+                "Invalid workspace member '{}': the name '{}' is reserved for the root of the \
+                workspace",
+                member_path.to_string_lossy(),
+                WORKSPACE_PACKAGE_NAME
+            )
+        }
+        let dependency = Dependency {
+            local: member_path.clone(),
+            subst: None,
+            version: None,
+            digest: None,
+            git_info: None,
+            registry_info: None,
+        };
+        if let Some(other) = dependencies.insert(name, dependency) {
+            bail!(
+                "Workspace members '{}' and '{}' are both named '{}'",
+                other.local.to_string_lossy(),
+                member_path.to_string_lossy(),
+                name
+            )
+        }
+    }
+    Ok(SourceManifest {
+        package: PackageInfo {
+            name: PackageName::from(WORKSPACE_PACKAGE_NAME),
+            version: (0, 0, 0),
+            authors: vec![],
+            license: None,
+        },
+        addresses: workspace.addresses,
+        dev_address_assignments: workspace.dev_address_assignments,
+        build: None,
+        lints: BTreeMap::new(),
+        dependencies,
+        dev_dependencies: BTreeMap::new(),
+    })
+}
+
+/// If the package at `package_path` is a member of a workspace, returns the root of the workspace
+/// and the name of the member.
+pub fn find_enclosing_workspace(package_path: &Path) -> Result<Option<(PathBuf, PackageName)>> {
+    let package_path = match package_path.canonicalize() {
+        Ok(path) => path,
+        Err(_) => return Ok(None),
+    };
+    for workspace_path in package_path.ancestors().skip(1) {
+        let manifest_path = workspace_path.join(SourcePackageLayout::Manifest.path());
+        let toml_manifest = match fs::read_to_string(&manifest_path) {
+            Ok(contents) => manifest_parser::parse_move_manifest_string(contents)?,
+            Err(_) => continue,
+        };
+        if !manifest_parser::is_workspace_manifest(&toml_manifest) {
+            continue;
+        }
+        let workspace = manifest_parser::parse_workspace_manifest(toml_manifest)?;
+        let is_member = workspace.members.iter().any(|member_path| {
+            workspace_path
+                .join(member_path)
+                .canonicalize()
+                .ok()
+                .as_ref()
+                == Some(&package_path)
+        });
+        if is_member {
+            let member = manifest_parser::parse_move_manifest_from_file(&package_path)?;
+            return Ok(Some((workspace_path.to_path_buf(), member.package.name)));
+        }
+    }
+    Ok(None)
+}
+
+/// If `path` is the root of a workspace, returns the paths of its members.
+pub fn workspace_member_paths(path: &Path) -> Result<Option<Vec<PathBuf>>> {
+    let manifest_path = path.join(SourcePackageLayout::Manifest.path());
+    let toml_manifest =
+        manifest_parser::parse_move_manifest_string(fs::read_to_string(manifest_path)?)?;
+    if !manifest_parser::is_workspace_manifest(&toml_manifest) {
+        return Ok(None);
+    }
+    let workspace = manifest_parser::parse_workspace_manifest(toml_manifest)?;
+    Ok(Some(
+        workspace
+            .members
+            .iter()
+            .map(|member_path| path.join(member_path))
+            .collect(),
+    ))
+}
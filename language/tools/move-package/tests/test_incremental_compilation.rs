@@ -0,0 +1,315 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use move_binary_format::{
+    access::ModuleAccess,
+    file_format::{CompiledModule, SignatureToken},
+};
+use move_compiler::compiled_unit::CompiledUnit;
+use move_package::{
+    compilation::{
+        compiled_package::CompiledPackage, incremental::IncrementalCache,
+        package_layout::CompiledPackageLayout,
+    },
+    BuildConfig,
+};
+use sha2::{Digest, Sha256};
+use std::{collections::BTreeMap, path::Path};
+use tempfile::tempdir;
+
+const DEP_MANIFEST: &str = r#"
+[package]
+name = "Dep"
+version = "0.0.0"
+
+[addresses]
+Dep = "0x2"
+"#;
+
+const ROOT_MANIFEST: &str = r#"
+[package]
+name = "Root"
+version = "0.0.0"
+
+[addresses]
+Root = "0x3"
+
+[dependencies]
+Dep = { local = "../dep" }
+"#;
+
+const A: &str = "module Dep::A { public fun f(): u64 { 1 } }";
+const B: &str = "module Root::B { use Dep::A; public fun g(): u64 { (A::f() as u64) } }";
+const C: &str = "module Root::C { public fun h(): u64 { 3 } }";
+
+fn write_packages(root: &Path) {
+    for (path, contents) in [
+        ("dep/Move.toml", DEP_MANIFEST),
+        ("dep/sources/A.move", A),
+        ("root/Move.toml", ROOT_MANIFEST),
+        ("root/sources/B.move", B),
+        ("root/sources/C.move", C),
+    ] {
+        let path = root.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+}
+
+fn build(path: &Path, force_recompilation: bool) -> anyhow::Result<CompiledPackage> {
+    BuildConfig {
+        force_recompilation,
+        ..Default::default()
+    }
+    .compile_package_no_exit(path, &mut Vec::new())
+}
+
+fn modules(package: &CompiledPackage) -> BTreeMap<String, CompiledModule> {
+    package
+        .all_compiled_units()
+        .filter_map(|unit| match unit {
+            CompiledUnit::Module(named) => Some((named.name.to_string(), named.module.clone())),
+            CompiledUnit::Script(_) => None,
+        })
+        .collect()
+}
+
+/// Builds the root package incrementally, and checks the result against a build from scratch
+fn build_and_compare(root: &Path) -> BTreeMap<String, CompiledModule> {
+    let incremental = modules(&build(&root.join("root"), false).unwrap());
+    let full = modules(&build(&root.join("root"), true).unwrap());
+    assert_eq!(incremental, full);
+    incremental
+}
+
+#[test]
+fn unchanged_and_changed_bodies() {
+    let dir = tempdir().unwrap();
+    write_packages(dir.path());
+    build_and_compare(dir.path());
+    // Nothing changed
+    build_and_compare(dir.path());
+
+    std::fs::write(
+        dir.path().join("root/sources/C.move"),
+        "module Root::C { public fun h(): u64 { 4 } }",
+    )
+    .unwrap();
+    build_and_compare(dir.path());
+
+    std::fs::write(
+        dir.path().join("dep/sources/A.move"),
+        "module Dep::A { public fun f(): u64 { 2 } }",
+    )
+    .unwrap();
+    build_and_compare(dir.path());
+}
+
+#[test]
+fn changed_interface_recompiles_dependents() {
+    let dir = tempdir().unwrap();
+    write_packages(dir.path());
+    build_and_compare(dir.path());
+
+    std::fs::write(
+        dir.path().join("dep/sources/A.move"),
+        "module Dep::A { public fun f(): u8 { 1 } }",
+    )
+    .unwrap();
+    let modules = build_and_compare(dir.path());
+
+    // `B` must now call the new version of `f`
+    let b = &modules["B"];
+    let f = b
+        .function_handles()
+        .iter()
+        .find(|handle| b.identifier_at(handle.name).as_str() == "f")
+        .unwrap();
+    assert_eq!(b.signature_at(f.return_).0, vec![SignatureToken::U8]);
+}
+
+#[test]
+fn changed_transitive_interface_recompiles_dependents_of_dependents() {
+    let dir = tempdir().unwrap();
+    write_packages(dir.path());
+    std::fs::write(
+        dir.path().join("dep/sources/A.move"),
+        "module Dep::A { struct T has drop {} public fun f(): u64 { 1 } public fun t(): T { T {} } }",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("root/sources/B.move"),
+        "module Root::B { use Dep::A; public fun g(): A::T { A::t() } }",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("root/sources/D.move"),
+        "module Root::D { use Root::B; public fun i() { B::g(); } }",
+    )
+    .unwrap();
+    build_and_compare(dir.path());
+
+    // `D` only uses `A` through `B`, but its bytecode refers to `T` and its abilities
+    std::fs::write(
+        dir.path().join("dep/sources/A.move"),
+        "module Dep::A { struct T has copy, drop {} public fun f(): u64 { 1 } public fun t(): T { T {} } }",
+    )
+    .unwrap();
+    let modules = build_and_compare(dir.path());
+
+    let d = &modules["D"];
+    let t = d
+        .struct_handles()
+        .iter()
+        .find(|handle| d.identifier_at(handle.name).as_str() == "T")
+        .unwrap();
+    assert!(t.abilities.has_copy());
+}
+
+#[test]
+fn broken_dependents_are_reported() {
+    let dir = tempdir().unwrap();
+    write_packages(dir.path());
+    build_and_compare(dir.path());
+
+    std::fs::write(
+        dir.path().join("dep/sources/A.move"),
+        "module Dep::A { public fun k(): u64 { 1 } }",
+    )
+    .unwrap();
+    assert!(build(&dir.path().join("root"), false).is_err());
+
+    // Fixing the dependent makes the build succeed again
+    std::fs::write(
+        dir.path().join("root/sources/B.move"),
+        "module Root::B { use Dep::A; public fun g(): u64 { A::k() } }",
+    )
+    .unwrap();
+    build_and_compare(dir.path());
+}
+
+#[test]
+fn added_and_removed_files() {
+    let dir = tempdir().unwrap();
+    write_packages(dir.path());
+    build_and_compare(dir.path());
+
+    std::fs::write(
+        dir.path().join("root/sources/D.move"),
+        "module Root::D { use Root::C; public fun i(): u64 { C::h() } }",
+    )
+    .unwrap();
+    assert!(build_and_compare(dir.path()).contains_key("D"));
+
+    std::fs::remove_file(dir.path().join("root/sources/D.move")).unwrap();
+    assert!(!build_and_compare(dir.path()).contains_key("D"));
+}
+
+#[test]
+fn unchanged_files_are_reused() {
+    let dir = tempdir().unwrap();
+    write_packages(dir.path());
+    build(&dir.path().join("root"), false).unwrap();
+
+    // Make a file that does not compile look unchanged to the next build
+    let broken_c = "module Root::C { public fun h(): u64 { false } }";
+    let cache_path = dir
+        .path()
+        .join("root")
+        .join(CompiledPackageLayout::Root.path())
+        .join("Root")
+        .join(CompiledPackageLayout::IncrementalCache.path());
+    let mut cache: IncrementalCache =
+        serde_yaml::from_slice(&std::fs::read(&cache_path).unwrap()).unwrap();
+    let cached_c = cache
+        .files
+        .iter_mut()
+        .find(|(path, _)| path.ends_with("C.move"))
+        .unwrap()
+        .1;
+    cached_c.content_hash = format!("{:X}", Sha256::digest(broken_c.as_bytes()));
+    std::fs::write(&cache_path, serde_yaml::to_string(&cache).unwrap()).unwrap();
+    std::fs::write(dir.path().join("root/sources/C.move"), broken_c).unwrap();
+
+    assert!(build(&dir.path().join("root"), false).is_ok());
+    assert!(build(&dir.path().join("root"), true).is_err());
+}
+
+#[test]
+fn changed_inline_functions_recompile_callers() {
+    let dir = tempdir().unwrap();
+    write_packages(dir.path());
+    std::fs::write(
+        dir.path().join("dep/sources/A.move"),
+        "module Dep::A { public fun f(): u64 { 1 } public inline fun i(): u64 { 1 } }",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("root/sources/B.move"),
+        "module Root::B { use Dep::A; public fun g(): u64 { A::f() + A::i() } }",
+    )
+    .unwrap();
+    build_and_compare(dir.path());
+
+    // Only the body of the inline function changes, which is copied into `B`
+    std::fs::write(
+        dir.path().join("dep/sources/A.move"),
+        "module Dep::A { public fun f(): u64 { 1 } public inline fun i(): u64 { 2 } }",
+    )
+    .unwrap();
+    build_and_compare(dir.path());
+}
+
+#[test]
+fn changed_public_constants_recompile_users() {
+    let dir = tempdir().unwrap();
+    write_packages(dir.path());
+    std::fs::write(
+        dir.path().join("dep/sources/A.move"),
+        "module Dep::A { public const K: u64 = 1; public fun f(): u64 { K } }",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("root/sources/B.move"),
+        "module Root::B { use Dep::A; public fun g(): u64 { A::f() + A::K } }",
+    )
+    .unwrap();
+    build_and_compare(dir.path());
+
+    // Only the value of the constant changes, which is copied into `B`
+    std::fs::write(
+        dir.path().join("dep/sources/A.move"),
+        "module Dep::A { public const K: u64 = 2; public fun f(): u64 { K } }",
+    )
+    .unwrap();
+    build_and_compare(dir.path());
+}
+
+#[test]
+fn deprecated_items_are_reported_in_changed_users() {
+    let dir = tempdir().unwrap();
+    write_packages(dir.path());
+    std::fs::write(
+        dir.path().join("dep/sources/A.move"),
+        "module Dep::A { public fun f(): u64 { 1 } #[deprecated] public fun k(): u64 { 1 } }",
+    )
+    .unwrap();
+    let config = BuildConfig {
+        deny: vec!["deprecated_usage".to_string()],
+        ..Default::default()
+    };
+    config
+        .clone()
+        .compile_package_no_exit(&dir.path().join("root"), &mut Vec::new())
+        .unwrap();
+
+    // The `deprecated` attribute of `k` is not part of the interface of `A`
+    std::fs::write(
+        dir.path().join("root/sources/B.move"),
+        "module Root::B { use Dep::A; public fun g(): u64 { A::k() } }",
+    )
+    .unwrap();
+    assert!(config
+        .compile_package_no_exit(&dir.path().join("root"), &mut Vec::new())
+        .is_err());
+}
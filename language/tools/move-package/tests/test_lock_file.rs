@@ -0,0 +1,279 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use move_package::{
+    resolution::lock_file::{LockFile, LockedPackage},
+    BuildConfig,
+};
+use once_cell::sync::Lazy;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+use tempfile::{tempdir, TempDir};
+
+// Git dependencies are downloaded to MOVE_HOME, which is shared by all tests of this file
+static MOVE_HOME: Lazy<TempDir> = Lazy::new(|| {
+    let move_home = tempdir().unwrap();
+    std::env::set_var("MOVE_HOME", move_home.path());
+    move_home
+});
+
+const DEP_MANIFEST: &str = r#"
+[package]
+name = "Dep"
+version = "0.0.0"
+
+[addresses]
+Dep = "0x2"
+"#;
+
+const LOCAL_DEP_MANIFEST: &str = r#"
+[package]
+name = "LocalDep"
+version = "0.0.0"
+
+[addresses]
+LocalDep = "_"
+"#;
+
+fn root_manifest(repo: &Path) -> String {
+    format!(
+        r#"
+[package]
+name = "Root"
+version = "0.0.0"
+
+[addresses]
+Root = "0x3"
+LocalDep = "0x4"
+
+[dependencies]
+Dep = {{ git = "{}", rev = "main", subdir = "dep" }}
+LocalDep = {{ local = "../local_dep" }}
+"#,
+        repo.to_string_lossy()
+    )
+}
+
+fn git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    String::from_utf8(output.stdout).unwrap().trim().to_string()
+}
+
+fn write(path: PathBuf, contents: &str) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, contents).unwrap();
+}
+
+/// Commits a new version of the `Dep` package to the repository, and returns its commit
+fn commit_dep(repo: &Path, value: u64) -> String {
+    write(repo.join("dep/Move.toml"), DEP_MANIFEST);
+    write(
+        repo.join("dep/sources/A.move"),
+        &format!("module Dep::A {{ public fun f(): u64 {{ {} }} }}", value),
+    );
+    git(repo, &["add", "-A"]);
+    git(
+        repo,
+        &["commit", "--quiet", "-m", &format!("Version {}", value)],
+    );
+    git(repo, &["rev-parse", "HEAD"])
+}
+
+/// Creates a git repository with the `Dep` package, and a root package depending on it along with
+/// a local dependency. Returns the paths of the repository and the root package.
+fn create_packages(dir: &Path) -> (PathBuf, PathBuf) {
+    Lazy::force(&MOVE_HOME);
+    let repo = dir.join("repo");
+    fs::create_dir_all(&repo).unwrap();
+    git(&repo, &["init", "--quiet"]);
+    git(&repo, &["symbolic-ref", "HEAD", "refs/heads/main"]);
+    commit_dep(&repo, 1);
+
+    write(dir.join("local_dep/Move.toml"), LOCAL_DEP_MANIFEST);
+    write(
+        dir.join("local_dep/sources/B.move"),
+        "module LocalDep::B {}",
+    );
+    let root = dir.join("root");
+    write(root.join("Move.toml"), &root_manifest(&repo));
+    write(root.join("sources/C.move"), "module Root::C {}");
+    (repo, root)
+}
+
+fn resolve(root: &Path, locked: bool) -> anyhow::Result<()> {
+    BuildConfig {
+        locked,
+        ..Default::default()
+    }
+    .resolution_graph_for_package(root)?;
+    Ok(())
+}
+
+fn locked_package<'a>(lock_file: &'a LockFile, name: &str) -> &'a LockedPackage {
+    lock_file
+        .packages(false)
+        .iter()
+        .find(|pkg| pkg.name == name)
+        .unwrap()
+}
+
+#[test]
+fn lock_file_records_resolution() {
+    let dir = tempdir().unwrap();
+    let (repo, root) = create_packages(dir.path());
+    let commit = git(&repo, &["rev-parse", "HEAD"]);
+    resolve(&root, false).unwrap();
+
+    let lock_file = LockFile::read(&root).unwrap().unwrap();
+    assert!(lock_file.packages(true).is_empty());
+    let dep = locked_package(&lock_file, "Dep");
+    assert_eq!(dep.git.as_deref(), Some(repo.to_str().unwrap()));
+    assert_eq!(dep.rev.as_deref(), Some("main"));
+    assert_eq!(dep.commit.as_ref(), Some(&commit));
+    assert_eq!(dep.subdir.as_deref(), Some("dep"));
+    assert_eq!(dep.addresses["Dep"], "0x2");
+    let local_dep = locked_package(&lock_file, "LocalDep");
+    assert_eq!(local_dep.local.as_deref(), Some("../local_dep"));
+    assert_eq!(local_dep.commit, None);
+    assert_eq!(local_dep.addresses["LocalDep"], "0x4");
+
+    // Building in dev mode adds the entries for it
+    BuildConfig {
+        dev_mode: true,
+        ..Default::default()
+    }
+    .resolution_graph_for_package(&root)
+    .unwrap();
+    let dev_lock_file = LockFile::read(&root).unwrap().unwrap();
+    assert_eq!(dev_lock_file.packages(false), lock_file.packages(false));
+    assert_eq!(dev_lock_file.packages(true).len(), 2);
+}
+
+#[test]
+fn lock_file_pins_git_dependencies() {
+    let dir = tempdir().unwrap();
+    let (repo, root) = create_packages(dir.path());
+    let first_commit = git(&repo, &["rev-parse", "HEAD"]);
+    resolve(&root, false).unwrap();
+    let first_lock_file = fs::read_to_string(root.join("Move.lock")).unwrap();
+
+    // Updating moves the dependency to the latest commit of its revision
+    let second_commit = commit_dep(&repo, 2);
+    resolve(&root, false).unwrap();
+    assert_eq!(
+        fs::read_to_string(root.join("Move.lock")).unwrap(),
+        first_lock_file
+    );
+    let graph = BuildConfig::default().update_dependencies(&root).unwrap();
+    let checkout = graph.get_package(&"Dep".into()).package_path.clone();
+    assert_eq!(git(&checkout, &["rev-parse", "HEAD"]), second_commit);
+    let lock_file = LockFile::read(&root).unwrap().unwrap();
+    assert_eq!(
+        locked_package(&lock_file, "Dep").commit.as_ref(),
+        Some(&second_commit)
+    );
+
+    // Going back to the previous lockfile checks out the commit pinned there
+    fs::write(root.join("Move.lock"), &first_lock_file).unwrap();
+    resolve(&root, true).unwrap();
+    assert_eq!(git(&checkout, &["rev-parse", "HEAD"]), first_commit);
+}
+
+#[test]
+fn locked_builds_fail_if_lock_file_changes() {
+    let dir = tempdir().unwrap();
+    let (_, root) = create_packages(dir.path());
+    let err = resolve(&root, true).unwrap_err();
+    assert!(format!("{:#}", err).contains("--locked"), "{:#}", err);
+    assert!(!root.join("Move.lock").exists());
+
+    resolve(&root, false).unwrap();
+    resolve(&root, true).unwrap();
+
+    let manifest = fs::read_to_string(root.join("Move.toml")).unwrap();
+    fs::write(
+        root.join("Move.toml"),
+        manifest.replace("LocalDep = \"0x4\"", "LocalDep = \"0x5\""),
+    )
+    .unwrap();
+    let err = resolve(&root, true).unwrap_err();
+    assert!(format!("{:#}", err).contains("--locked"), "{:#}", err);
+    resolve(&root, false).unwrap();
+    let lock_file = LockFile::read(&root).unwrap().unwrap();
+    assert_eq!(
+        locked_package(&lock_file, "LocalDep").addresses["LocalDep"],
+        "0x5"
+    );
+}
+
+#[test]
+fn modified_git_dependencies_are_rejected() {
+    let dir = tempdir().unwrap();
+    let (_, root) = create_packages(dir.path());
+    resolve(&root, false).unwrap();
+
+    let lock_file = LockFile::read(&root).unwrap().unwrap();
+    let dep = locked_package(&lock_file, "Dep");
+    let checkout = BuildConfig::default()
+        .resolution_graph_for_package(&root)
+        .unwrap()
+        .get_package(&"Dep".into())
+        .package_path
+        .clone();
+    fs::write(
+        checkout.join("sources/A.move"),
+        "module Dep::A { public fun f(): u64 { 42 } }",
+    )
+    .unwrap();
+    let err = resolve(&root, false).unwrap_err();
+    let msg = format!("{:#}", err);
+    assert!(
+        msg.contains("Source digest mismatch in dependency 'Dep'"),
+        "{}",
+        msg
+    );
+    assert!(msg.contains(&dep.digest), "{}", msg);
+}
+
+#[test]
+fn local_dependencies_only_get_a_lock_file_on_update() {
+    let dir = tempdir().unwrap();
+    Lazy::force(&MOVE_HOME);
+    write(dir.path().join("local_dep/Move.toml"), LOCAL_DEP_MANIFEST);
+    write(
+        dir.path().join("local_dep/sources/B.move"),
+        "module LocalDep::B {}",
+    );
+    let root = dir.path().join("root");
+    write(
+        root.join("Move.toml"),
+        r#"
+[package]
+name = "Root"
+version = "0.0.0"
+
+[addresses]
+LocalDep = "0x4"
+
+[dependencies]
+LocalDep = { local = "../local_dep" }
+"#,
+    );
+    resolve(&root, false).unwrap();
+    assert!(!root.join("Move.lock").exists());
+
+    BuildConfig::default().update_dependencies(&root).unwrap();
+    let lock_file = LockFile::read(&root).unwrap().unwrap();
+    assert_eq!(lock_file.packages(false).len(), 1);
+    resolve(&root, true).unwrap();
+}
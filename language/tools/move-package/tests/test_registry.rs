@@ -0,0 +1,294 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use move_package::{
+    resolution::{
+        lock_file::LockFile,
+        registry::{version_string, Registry},
+        resolution_graph::ResolvedGraph,
+    },
+    source_package::version_req::VersionReq,
+    BuildConfig,
+};
+use once_cell::sync::Lazy;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use tempfile::{tempdir, TempDir};
+
+// Registry dependencies are downloaded to MOVE_HOME, which is shared by all tests of this file
+static MOVE_HOME: Lazy<TempDir> = Lazy::new(|| {
+    let move_home = tempdir().unwrap();
+    std::env::set_var("MOVE_HOME", move_home.path());
+    move_home
+});
+
+fn write(path: PathBuf, contents: &str) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, contents).unwrap();
+}
+
+/// Writes a package with the given version and dependencies to `dir`
+fn write_package(dir: &Path, name: &str, version: &str, dependencies: &[String]) {
+    write(
+        dir.join("Move.toml"),
+        &format!(
+            "[package]\nname = \"{}\"\nversion = \"{}\"\n\n[addresses]\n{} = \"0x{}\"\n\n\
+            [dependencies]\n{}\n",
+            name,
+            version,
+            name,
+            name.len(),
+            dependencies.join("\n")
+        ),
+    );
+    write(
+        dir.join("sources").join(format!("{}.move", name)),
+        &format!("module {}::M {{}}", name),
+    );
+}
+
+fn registry_dep(registry: &Path, name: &str, version_req: &str) -> String {
+    format!(
+        "{} = {{ registry = \"{}\", version = \"{}\" }}",
+        name,
+        registry.to_string_lossy(),
+        version_req
+    )
+}
+
+/// Publishes a version of a package to the registry
+fn publish(dir: &Path, name: &str, version: &str, dependencies: &[String]) {
+    let package_dir = dir.join("staging").join(name).join(version);
+    write_package(&package_dir, name, version, dependencies);
+    Registry::new(dir.join("registry").to_str().unwrap())
+        .unwrap()
+        .publish(&package_dir)
+        .unwrap();
+}
+
+fn setup() -> (TempDir, PathBuf) {
+    Lazy::force(&MOVE_HOME);
+    let dir = tempdir().unwrap();
+    let registry = dir.path().join("registry");
+    fs::create_dir_all(&registry).unwrap();
+    (dir, registry)
+}
+
+fn selected_version(graph: &ResolvedGraph, name: &str) -> String {
+    version_string(graph.registry_packages[&name.into()].version)
+}
+
+#[test]
+fn version_requirements() {
+    let matches =
+        |req: &str, version: (u64, u64, u64)| req.parse::<VersionReq>().unwrap().matches(&version);
+    assert!(matches("^1.2", (1, 9, 3)));
+    assert!(!matches("^1.2", (1, 1, 9)));
+    assert!(!matches("^1.2", (2, 0, 0)));
+    assert!(matches("1.2.3", (1, 2, 3)));
+    assert!(!matches("^0.2.3", (0, 3, 0)));
+    assert!(!matches("^0.0.3", (0, 0, 4)));
+    assert!(matches("~1.2.3", (1, 2, 9)));
+    assert!(!matches("~1.2.3", (1, 3, 0)));
+    assert!(matches("=1.2", (1, 2, 7)));
+    assert!(!matches(">1.2", (1, 2, 9)));
+    assert!(matches("<=1.2", (1, 2, 9)));
+    assert!(matches(">=1.2, <1.5", (1, 4, 9)));
+    assert!(!matches(">=1.2, <1.5", (1, 5, 0)));
+    assert!(matches("*", (3, 1, 4)));
+    assert!(matches("1.x", (1, 7, 0)));
+    assert!(!matches("1.*", (2, 0, 0)));
+    assert!(matches("^0.0", (0, 0, 5)));
+    assert!(!matches("^0.0", (0, 1, 0)));
+    assert!(matches("^0", (0, 9, 0)));
+    assert!(!matches("^0", (1, 0, 0)));
+    assert!(matches("~1", (1, 9, 9)));
+    assert!(!matches("~1", (2, 0, 0)));
+
+    // Requirements at the largest version components do not overflow
+    let max = u64::MAX;
+    assert!(matches("^18446744073709551615", (max, max, max)));
+    assert!(!matches("^18446744073709551615", (max - 1, 0, 0)));
+    assert!(matches("~1.18446744073709551615", (1, max, 7)));
+    assert!(matches("^0.0.18446744073709551615", (0, 0, max)));
+    assert!(matches("=1.2.18446744073709551615", (1, 2, max)));
+    assert!(!matches(">18446744073709551615", (max, max, max)));
+    assert!(matches("<=1.18446744073709551615", (1, max, max)));
+
+    assert_eq!("1.2".parse::<VersionReq>().unwrap().to_string(), "^1.2");
+    assert_eq!(
+        ">= 1.2.0,<2".parse::<VersionReq>().unwrap().to_string(),
+        ">=1.2.0, <2"
+    );
+    for req in ["", "1.y", "1.2.3.4", ">=", "^1,", "^18446744073709551616"] {
+        assert!(req.parse::<VersionReq>().is_err(), "{}", req);
+    }
+}
+
+#[test]
+fn highest_matching_version_is_selected_and_pinned() {
+    let (dir, registry) = setup();
+    for version in ["1.0.0", "1.2.0", "2.0.0"] {
+        publish(dir.path(), "Lib", version, &[]);
+    }
+    let root = dir.path().join("root");
+    write_package(
+        &root,
+        "Root",
+        "0.0.0",
+        &[registry_dep(&registry, "Lib", "^1.0")],
+    );
+
+    let graph = BuildConfig::default()
+        .resolution_graph_for_package(&root)
+        .unwrap();
+    assert_eq!(selected_version(&graph, "Lib"), "1.2.0");
+    let lock_file = LockFile::read(&root).unwrap().unwrap();
+    let locked = &lock_file.packages(false)[0];
+    assert_eq!(locked.registry.as_deref(), registry.to_str());
+    assert_eq!(locked.version.as_deref(), Some("1.2.0"));
+
+    // A newer version is only used once the dependencies are updated
+    publish(dir.path(), "Lib", "1.3.0", &[]);
+    let graph = BuildConfig::default()
+        .resolution_graph_for_package(&root)
+        .unwrap();
+    assert_eq!(selected_version(&graph, "Lib"), "1.2.0");
+    let graph = BuildConfig::default().update_dependencies(&root).unwrap();
+    assert_eq!(selected_version(&graph, "Lib"), "1.3.0");
+    BuildConfig {
+        locked: true,
+        ..Default::default()
+    }
+    .compile_package_no_exit(&root, &mut Vec::new())
+    .unwrap();
+}
+
+#[test]
+fn requirements_across_the_graph_are_combined() {
+    let (dir, registry) = setup();
+    for version in ["1.1.0", "1.2.0", "1.2.5", "1.3.0"] {
+        publish(dir.path(), "Lib", version, &[]);
+    }
+    publish(
+        dir.path(),
+        "Mid",
+        "1.0.0",
+        &[registry_dep(&registry, "Lib", "~1.2")],
+    );
+    let root = dir.path().join("root");
+    write_package(
+        &root,
+        "Root",
+        "0.0.0",
+        &[
+            registry_dep(&registry, "Lib", ">=1.2.1"),
+            registry_dep(&registry, "Mid", "1"),
+        ],
+    );
+
+    let graph = BuildConfig::default()
+        .resolution_graph_for_package(&root)
+        .unwrap();
+    assert_eq!(selected_version(&graph, "Lib"), "1.2.5");
+    assert_eq!(selected_version(&graph, "Mid"), "1.0.0");
+}
+
+#[test]
+fn version_conflicts_are_reported() {
+    let (dir, registry) = setup();
+    for version in ["1.2.0", "2.0.0"] {
+        publish(dir.path(), "Lib", version, &[]);
+    }
+    publish(
+        dir.path(),
+        "Mid",
+        "1.0.0",
+        &[registry_dep(&registry, "Lib", "~1.2")],
+    );
+    let root = dir.path().join("root");
+    write_package(
+        &root,
+        "Root",
+        "0.0.0",
+        &[
+            registry_dep(&registry, "Lib", "^2"),
+            registry_dep(&registry, "Mid", "^1"),
+        ],
+    );
+
+    let err = BuildConfig::default()
+        .resolution_graph_for_package(&root)
+        .unwrap_err();
+    let msg = format!("{:#}", err);
+    assert!(
+        msg.contains("No version of package 'Lib'"),
+        "unexpected error: {}",
+        msg
+    );
+    assert!(msg.contains("^2 (required by 'Root')"), "{}", msg);
+    assert!(msg.contains("~1.2 (required by 'Mid')"), "{}", msg);
+    assert!(msg.contains("Published versions: 1.2.0, 2.0.0"), "{}", msg);
+}
+
+#[test]
+fn invalid_publishing_is_rejected() {
+    let (dir, registry) = setup();
+    publish(dir.path(), "Lib", "1.0.0", &[]);
+    let registry = Registry::new(&format!("file://{}", registry.to_string_lossy())).unwrap();
+
+    let err = registry
+        .publish(&dir.path().join("staging/Lib/1.0.0"))
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("is already published"),
+        "unexpected error: {}",
+        err
+    );
+
+    let package_dir = dir.path().join("with_local_dep");
+    write_package(
+        &package_dir,
+        "Other",
+        "1.0.0",
+        &["Lib = { local = \"../staging/Lib/1.0.0\" }".to_string()],
+    );
+    let err = registry.publish(&package_dir).unwrap_err();
+    assert!(
+        err.to_string().contains("with the local dependency 'Lib'"),
+        "unexpected error: {}",
+        err
+    );
+
+    assert!(Registry::new("https://example.com/registry").is_err());
+    assert!(Registry::new("relative/registry").is_err());
+}
+
+#[test]
+fn modified_registry_packages_are_rejected() {
+    let (dir, registry) = setup();
+    publish(dir.path(), "Lib", "1.0.0", &[]);
+    write(
+        registry.join("Lib/1.0.0/sources/Lib.move"),
+        "module Lib::M { fun f() {} }",
+    );
+    let root = dir.path().join("root");
+    write_package(
+        &root,
+        "Root",
+        "0.0.0",
+        &[registry_dep(&registry, "Lib", "1")],
+    );
+
+    let err = BuildConfig::default()
+        .resolution_graph_for_package(&root)
+        .unwrap_err();
+    let msg = format!("{:#}", err);
+    assert!(
+        msg.contains("Source digest mismatch in version 1.0.0 of package 'Lib'"),
+        "unexpected error: {}",
+        msg
+    );
+}
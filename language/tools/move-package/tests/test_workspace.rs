@@ -0,0 +1,275 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use move_core_types::account_address::AccountAddress;
+use move_package::{
+    compilation::compiled_package::CompiledPackage, resolution::resolution_graph::ResolvedGraph,
+    BuildConfig,
+};
+use std::{fs, path::PathBuf};
+use tempfile::{tempdir, TempDir};
+
+fn write(path: PathBuf, contents: &str) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, contents).unwrap();
+}
+
+/// Creates a workspace with the members `Lib` and `App`, where `App` depends on `Lib` and the
+/// addresses of both are assigned by the workspace
+fn setup() -> TempDir {
+    let dir = tempdir().unwrap();
+    write(
+        dir.path().join("Move.toml"),
+        "[workspace]\nmembers = [\"lib\", \"app\"]\n\n\
+        [addresses]\nLib = \"0x11\"\nApp = \"_\"\n\n\
+        [dev-addresses]\nApp = \"0x22\"\n",
+    );
+    write(
+        dir.path().join("lib/Move.toml"),
+        "[package]\nname = \"Lib\"\nversion = \"0.0.0\"\n\n[addresses]\nLib = \"_\"\n",
+    );
+    write(
+        dir.path().join("lib/sources/Lib.move"),
+        "module Lib::L { public fun f(): u64 { 1 } }",
+    );
+    write(
+        dir.path().join("app/Move.toml"),
+        "[package]\nname = \"App\"\nversion = \"0.0.0\"\n\n\
+        [addresses]\nApp = \"_\"\nTest = \"_\"\n\n\
+        [dev-addresses]\nTest = \"0x33\"\n\n\
+        [dependencies]\nLib = { local = \"../lib\" }\n",
+    );
+    write(
+        dir.path().join("app/sources/App.move"),
+        "module App::A { public fun g(): u64 { Lib::L::f() } }",
+    );
+    dir
+}
+
+fn address(graph: &ResolvedGraph, name: &str) -> Option<AccountAddress> {
+    graph
+        .extract_named_address_mapping()
+        .find(|(addr_name, _)| addr_name.as_str() == name)
+        .map(|(_, addr)| addr)
+}
+
+#[test]
+fn members_are_resolved_together() {
+    let dir = setup();
+    let dev_config = BuildConfig {
+        dev_mode: true,
+        ..Default::default()
+    };
+    let graph = dev_config.resolution_graph_for_package(dir.path()).unwrap();
+    assert!(graph.is_workspace());
+    let members = graph.member_graphs();
+    assert_eq!(members.len(), 2);
+    let app = members
+        .iter()
+        .find(|graph| graph.root_package.package.name.as_str() == "App")
+        .unwrap();
+    assert!(!app.is_workspace());
+    assert_eq!(
+        address(app, "Lib"),
+        AccountAddress::from_hex_literal("0x11").ok()
+    );
+    assert_eq!(
+        address(app, "App"),
+        AccountAddress::from_hex_literal("0x22").ok()
+    );
+    // The dev addresses of the members are used as well
+    assert_eq!(
+        address(app, "Test"),
+        AccountAddress::from_hex_literal("0x33").ok()
+    );
+
+    // Without dev mode, the address of `App` is not assigned
+    let err = BuildConfig::default()
+        .resolution_graph_for_package(dir.path())
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("Named address 'App' in package 'App'"),
+        "unexpected error: {}",
+        err
+    );
+}
+
+#[test]
+fn members_share_build_directory_and_lock_file() {
+    let dir = setup();
+    let compiled = BuildConfig {
+        dev_mode: true,
+        ..Default::default()
+    }
+    .compile_workspace(dir.path(), &mut Vec::new())
+    .unwrap();
+    let names: Vec<_> = compiled
+        .iter()
+        .map(|package| package.compiled_package_info.package_name.to_string())
+        .collect();
+    assert_eq!(names, vec!["App", "Lib"]);
+    assert!(dir.path().join("build/App").is_dir());
+    assert!(dir.path().join("build/Lib").is_dir());
+    assert!(!dir.path().join("app/build").exists());
+
+    // The lockfile of a member is the one of its workspace
+    BuildConfig {
+        dev_mode: true,
+        ..Default::default()
+    }
+    .update_dependencies(&dir.path().join("app"))
+    .unwrap();
+    assert!(dir.path().join("Move.lock").is_file());
+    assert!(!dir.path().join("app/Move.lock").exists());
+
+    // A member is built as part of its workspace, and other members are kept in the build directory
+    let compiled = BuildConfig {
+        dev_mode: true,
+        ..Default::default()
+    }
+    .compile_package(&dir.path().join("lib"), &mut Vec::new())
+    .unwrap();
+    assert_eq!(compiled.compiled_package_info.package_name.as_str(), "Lib");
+    assert!(!dir.path().join("lib/build").exists());
+    assert!(dir.path().join("build/App").is_dir());
+
+    let err = BuildConfig {
+        dev_mode: true,
+        ..Default::default()
+    }
+    .compile_package(dir.path(), &mut Vec::new())
+    .unwrap_err();
+    assert!(
+        err.to_string().contains("is a workspace"),
+        "unexpected error: {}",
+        err
+    );
+}
+
+#[test]
+fn shared_dependencies_are_compiled_once() {
+    // Both members depend on `Util`, which is not a member
+    let dir = setup();
+    write(
+        dir.path().join("util/Move.toml"),
+        "[package]\nname = \"Util\"\nversion = \"0.0.0\"\n\n[addresses]\nUtil = \"0x44\"\n",
+    );
+    write(
+        dir.path().join("util/sources/Util.move"),
+        "module Util::U { public fun one(): u64 { 1 } }",
+    );
+    write(
+        dir.path().join("lib/Move.toml"),
+        "[package]\nname = \"Lib\"\nversion = \"0.0.0\"\n\n[addresses]\nLib = \"_\"\n\n\
+        [dependencies]\nUtil = { local = \"../util\" }\n",
+    );
+    write(
+        dir.path().join("lib/sources/Lib.move"),
+        "module Lib::L { public fun f(): u64 { Util::U::one() } }",
+    );
+    write(
+        dir.path().join("app/Move.toml"),
+        "[package]\nname = \"App\"\nversion = \"0.0.0\"\n\n\
+        [addresses]\nApp = \"_\"\nTest = \"_\"\n\n\
+        [dev-addresses]\nTest = \"0x33\"\n\n\
+        [dependencies]\nLib = { local = \"../lib\" }\nUtil = { local = \"../util\" }\n",
+    );
+    write(
+        dir.path().join("app/sources/App.move"),
+        "module App::A { public fun g(): u64 { Lib::L::f() + Util::U::one() } }",
+    );
+
+    let config = BuildConfig {
+        dev_mode: true,
+        force_recompilation: true,
+        ..Default::default()
+    };
+    let mut output = vec![];
+    let compiled = config
+        .clone()
+        .compile_workspace(dir.path(), &mut output)
+        .unwrap();
+    let output = String::from_utf8(output).unwrap();
+    let count = |status: &str, package: &str| {
+        output
+            .lines()
+            .filter(|line| line.contains(status) && line.ends_with(&format!(" {}", package)))
+            .count()
+    };
+    // `Lib` is built first and compiles `Util`, which `App` then reuses along with `Lib`
+    assert_eq!(count("INCLUDING DEPENDENCY", "Util"), 1, "{}", output);
+    assert_eq!(count("REUSING DEPENDENCY", "Util"), 1, "{}", output);
+    assert_eq!(count("INCLUDING DEPENDENCY", "Lib"), 0, "{}", output);
+    assert_eq!(count("REUSING DEPENDENCY", "Lib"), 1, "{}", output);
+
+    // The units are the same as when the member is built on its own
+    let names: Vec<_> = compiled
+        .iter()
+        .map(|package| package.compiled_package_info.package_name.to_string())
+        .collect();
+    assert_eq!(names, vec!["App", "Lib"]);
+    let app = config
+        .compile_package(&dir.path().join("app"), &mut Vec::new())
+        .unwrap();
+    let modules = |package: &CompiledPackage| package.all_modules_map().iter_modules_owned();
+    assert_eq!(modules(&compiled[0]), modules(&app));
+}
+
+#[test]
+fn invalid_workspaces_are_rejected() {
+    let dir = setup();
+    let manifest = dir.path().join("Move.toml");
+    let resolve_err = || {
+        let err = BuildConfig::default()
+            .resolution_graph_for_package(dir.path())
+            .unwrap_err();
+        format!("{:#}", err)
+    };
+
+    write(
+        manifest.clone(),
+        "[workspace]\nmembers = [\"lib\"]\n\n[dependencies]\nApp = { local = \"app\" }\n",
+    );
+    let msg = resolve_err();
+    assert!(
+        msg.contains("a workspace cannot have a '[dependencies]' section"),
+        "unexpected error: {}",
+        msg
+    );
+
+    write(manifest.clone(), "[workspace]\nmembers = []\n");
+    let msg = resolve_err();
+    assert!(
+        msg.contains("at least one member"),
+        "unexpected error: {}",
+        msg
+    );
+
+    write(
+        manifest.clone(),
+        "[workspace]\nmembers = [\"lib\", \"other\"]\n",
+    );
+    write(
+        dir.path().join("other/Move.toml"),
+        "[package]\nname = \"Lib\"\nversion = \"0.0.0\"\n",
+    );
+    let msg = resolve_err();
+    assert!(
+        msg.contains("Workspace members 'lib' and 'other' are both named 'Lib'"),
+        "unexpected error: {}",
+        msg
+    );
+
+    write(manifest, "[workspace]\nmembers = [\"other\"]\n");
+    write(
+        dir.path().join("other/Move.toml"),
+        "[package]\nname = \"Workspace\"\nversion = \"0.0.0\"\n",
+    );
+    let msg = resolve_err();
+    assert!(
+        msg.contains("the name 'Workspace' is reserved"),
+        "unexpected error: {}",
+        msg
+    );
+}
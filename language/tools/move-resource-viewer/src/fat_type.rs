@@ -49,14 +49,19 @@ pub(crate) struct FatStructType {
     pub abilities: WrappedAbilitySet,
     pub ty_args: Vec<FatType>,
     pub layout: Vec<FatType>,
+    /// The field types of each variant if this is an enum, in which case `layout` is empty.
+    pub variants: Vec<Vec<FatType>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) enum FatType {
     Bool,
     U8,
+    U16,
+    U32,
     U64,
     U128,
+    U256,
     Address,
     Signer,
     Vector(Box<FatType>),
@@ -83,6 +88,16 @@ impl FatStructType {
                 .iter()
                 .map(|ty| ty.subst(ty_args))
                 .collect::<PartialVMResult<_>>()?,
+            variants: self
+                .variants
+                .iter()
+                .map(|fields| {
+                    fields
+                        .iter()
+                        .map(|ty| ty.subst(ty_args))
+                        .collect::<PartialVMResult<_>>()
+                })
+                .collect::<PartialVMResult<_>>()?,
         })
     }
 
@@ -122,8 +137,11 @@ impl FatType {
 
             Bool => Bool,
             U8 => U8,
+            U16 => U16,
+            U32 => U32,
             U64 => U64,
             U128 => U128,
+            U256 => U256,
             Address => Address,
             Signer => Signer,
             Vector(ty) => Vector(Box::new(ty.subst(ty_args)?)),
@@ -142,8 +160,11 @@ impl FatType {
         let res = match self {
             Bool => TypeTag::Bool,
             U8 => TypeTag::U8,
+            U16 => TypeTag::U16,
+            U32 => TypeTag::U32,
             U64 => TypeTag::U64,
             U128 => TypeTag::U128,
+            U256 => TypeTag::U256,
             Address => TypeTag::Address,
             Signer => TypeTag::Signer,
             Vector(ty) => TypeTag::Vector(Box::new(ty.type_tag()?)),
@@ -165,6 +186,19 @@ impl TryInto<MoveStructLayout> for &FatStructType {
     type Error = PartialVMError;
 
     fn try_into(self) -> Result<MoveStructLayout, Self::Error> {
+        if !self.variants.is_empty() {
+            return Ok(MoveStructLayout::new_variants(
+                self.variants
+                    .iter()
+                    .map(|fields| {
+                        fields
+                            .iter()
+                            .map(|ty| ty.try_into())
+                            .collect::<PartialVMResult<Vec<_>>>()
+                    })
+                    .collect::<PartialVMResult<_>>()?,
+            ));
+        }
         Ok(MoveStructLayout::new(
             self.layout
                 .iter()
@@ -181,16 +215,14 @@ impl TryInto<MoveTypeLayout> for &FatType {
         Ok(match self {
             FatType::Address => MoveTypeLayout::Address,
             FatType::U8 => MoveTypeLayout::U8,
+            FatType::U16 => MoveTypeLayout::U16,
+            FatType::U32 => MoveTypeLayout::U32,
             FatType::U64 => MoveTypeLayout::U64,
             FatType::U128 => MoveTypeLayout::U128,
+            FatType::U256 => MoveTypeLayout::U256,
             FatType::Bool => MoveTypeLayout::Bool,
             FatType::Vector(v) => MoveTypeLayout::Vector(Box::new(v.as_ref().try_into()?)),
-            FatType::Struct(s) => MoveTypeLayout::Struct(MoveStructLayout::new(
-                s.layout
-                    .iter()
-                    .map(|ty| ty.try_into())
-                    .collect::<PartialVMResult<Vec<_>>>()?,
-            )),
+            FatType::Struct(s) => MoveTypeLayout::Struct(s.as_ref().try_into()?),
             FatType::Signer => MoveTypeLayout::Signer,
 
             _ => return Err(PartialVMError::new(StatusCode::ABORT_TYPE_MISMATCH_ERROR)),
@@ -17,6 +17,7 @@ use move_core_types::{
     identifier::{IdentStr, Identifier},
     language_storage::{ModuleId, StructTag, TypeTag},
     resolver::MoveResolver,
+    u256::U256,
     value::{MoveStruct, MoveTypeLayout, MoveValue},
     vm_status::VMStatus,
 };
@@ -35,6 +36,8 @@ mod resolver;
 pub struct AnnotatedMoveStruct {
     pub abilities: AbilitySet,
     pub type_: StructTag,
+    /// The name of the variant if this is a value of an enum.
+    pub variant: Option<Identifier>,
     pub value: Vec<(Identifier, AnnotatedMoveValue)>,
 }
 
@@ -45,8 +48,11 @@ pub struct AnnotatedMoveStruct {
 #[derive(Clone, Debug)]
 pub enum AnnotatedMoveValue {
     U8(u8),
+    U16(u16),
+    U32(u32),
     U64(u64),
     U128(u128),
+    U256(U256),
     Bool(bool),
     Address(AccountAddress),
     Vector(TypeTag, Vec<AnnotatedMoveValue>),
@@ -59,8 +65,11 @@ impl AnnotatedMoveValue {
         use AnnotatedMoveValue::*;
         match self {
             U8(_) => TypeTag::U8,
+            U16(_) => TypeTag::U16,
+            U32(_) => TypeTag::U32,
             U64(_) => TypeTag::U64,
             U128(_) => TypeTag::U128,
+            U256(_) => TypeTag::U256,
             Bool(_) => TypeTag::Bool,
             Address(_) => TypeTag::Address,
             Vector(t, _) => t.clone(),
@@ -151,7 +160,16 @@ impl<'a, T: MoveResolver + ?Sized> MoveValueAnnotator<'a, T> {
                 .into_iter()
                 .zip(runtime.into_iter())
                 .collect(),
-            MoveStruct::WithFields(fields) | MoveStruct::WithTypes { fields, .. } => fields,
+            MoveStruct::RuntimeVariant(tag, runtime) => self
+                .cache
+                .get_variant_names(&ty, tag)?
+                .1
+                .into_iter()
+                .zip(runtime.into_iter())
+                .collect(),
+            MoveStruct::WithFields(fields)
+            | MoveStruct::WithTypes { fields, .. }
+            | MoveStruct::WithVariantFields(_, _, fields) => fields,
         })
     }
 
@@ -174,14 +192,25 @@ impl<'a, T: MoveResolver + ?Sized> MoveValueAnnotator<'a, T> {
         let struct_tag = ty
             .struct_tag()
             .map_err(|e| e.finish(Location::Undefined).into_vm_status())?;
-        let field_names = self.cache.get_field_names(ty)?;
+        let (variant, field_names, field_types) = match move_struct.variant_tag() {
+            Some(tag) => {
+                let (variant, field_names) = self.cache.get_variant_names(ty, tag)?;
+                let field_types = ty
+                    .variants
+                    .get(tag as usize)
+                    .ok_or_else(|| anyhow!("Variant {} of {} not found", tag, ty.name))?;
+                (Some(variant), field_names, field_types)
+            }
+            None => (None, self.cache.get_field_names(ty)?, &ty.layout),
+        };
         let mut annotated_fields = vec![];
-        for (ty, v) in ty.layout.iter().zip(move_struct.fields().iter()) {
+        for (ty, v) in field_types.iter().zip(move_struct.fields().iter()) {
             annotated_fields.push(self.annotate_value(v, ty)?);
         }
         Ok(AnnotatedMoveStruct {
             abilities: ty.abilities.0,
             type_: struct_tag,
+            variant,
             value: field_names
                 .into_iter()
                 .zip(annotated_fields.into_iter())
@@ -193,8 +222,11 @@ impl<'a, T: MoveResolver + ?Sized> MoveValueAnnotator<'a, T> {
         Ok(match (value, ty) {
             (MoveValue::Bool(b), FatType::Bool) => AnnotatedMoveValue::Bool(*b),
             (MoveValue::U8(i), FatType::U8) => AnnotatedMoveValue::U8(*i),
+            (MoveValue::U16(i), FatType::U16) => AnnotatedMoveValue::U16(*i),
+            (MoveValue::U32(i), FatType::U32) => AnnotatedMoveValue::U32(*i),
             (MoveValue::U64(i), FatType::U64) => AnnotatedMoveValue::U64(*i),
             (MoveValue::U128(i), FatType::U128) => AnnotatedMoveValue::U128(*i),
+            (MoveValue::U256(i), FatType::U256) => AnnotatedMoveValue::U256(*i),
             (MoveValue::Address(a), FatType::Address) => AnnotatedMoveValue::Address(*a),
             (MoveValue::Vector(a), FatType::Vector(ty)) => match ty.as_ref() {
                 FatType::U8 => AnnotatedMoveValue::Bytes(
@@ -245,8 +277,11 @@ fn pretty_print_value(
     match value {
         AnnotatedMoveValue::Bool(b) => write!(f, "{}", b),
         AnnotatedMoveValue::U8(v) => write!(f, "{}u8", v),
+        AnnotatedMoveValue::U16(v) => write!(f, "{}u16", v),
+        AnnotatedMoveValue::U32(v) => write!(f, "{}u32", v),
         AnnotatedMoveValue::U64(v) => write!(f, "{}", v),
         AnnotatedMoveValue::U128(v) => write!(f, "{}u128", v),
+        AnnotatedMoveValue::U256(v) => write!(f, "{}u256", v),
         AnnotatedMoveValue::Address(a) => write!(f, "{}", a.short_str_lossless()),
         AnnotatedMoveValue::Vector(_, v) => {
             writeln!(f, "[")?;
@@ -269,7 +304,10 @@ fn pretty_print_struct(
     indent: u64,
 ) -> std::fmt::Result {
     pretty_print_ability_modifiers(f, value.abilities)?;
-    writeln!(f, "{} {{", value.type_)?;
+    match &value.variant {
+        Some(variant) => writeln!(f, "{}::{} {{", value.type_, variant)?,
+        None => writeln!(f, "{} {{", value.type_)?,
+    }
     for (field_name, v) in value.value.iter() {
         write_indent(f, indent + 4)?;
         write!(f, "{}: ", field_name)?;
@@ -292,13 +330,30 @@ fn pretty_print_ability_modifiers(f: &mut Formatter, abilities: AbilitySet) -> s
     Ok(())
 }
 
+/// Serializes the fields of a struct as a map. The fields of a value of an enum are nested in a
+/// map with the name of the variant as its single key.
 impl serde::Serialize for AnnotatedMoveStruct {
     fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-        let mut s = serializer.serialize_map(Some(self.value.len()))?;
-        for (f, v) in &self.value {
-            s.serialize_entry(f, v)?
+        struct Fields<'a>(&'a [(Identifier, AnnotatedMoveValue)]);
+
+        impl<'a> serde::Serialize for Fields<'a> {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let mut s = serializer.serialize_map(Some(self.0.len()))?;
+                for (f, v) in self.0 {
+                    s.serialize_entry(f, v)?
+                }
+                s.end()
+            }
+        }
+
+        match &self.variant {
+            Some(variant) => {
+                let mut s = serializer.serialize_map(Some(1))?;
+                s.serialize_entry(variant, &Fields(&self.value))?;
+                s.end()
+            }
+            None => Fields(&self.value).serialize(serializer),
         }
-        s.end()
     }
 }
 
@@ -307,6 +362,8 @@ impl serde::Serialize for AnnotatedMoveValue {
         use AnnotatedMoveValue::*;
         match self {
             U8(n) => serializer.serialize_u8(*n),
+            U16(n) => serializer.serialize_u16(*n),
+            U32(n) => serializer.serialize_u32(*n),
             U64(n) => serializer.serialize_u64(*n),
             U128(n) => {
                 // TODO: we could use serializer.serialize_u128 here, but it requires the serde_json
@@ -318,6 +375,7 @@ impl serde::Serialize for AnnotatedMoveValue {
                     serializer.serialize_bytes(&n.to_le_bytes())
                 }
             }
+            U256(n) => n.serialize(serializer),
             Bool(b) => serializer.serialize_bool(*b),
             Address(a) => a.short_str_lossless().serialize(serializer),
             Vector(t, vals) => {
@@ -10,7 +10,8 @@ use move_binary_format::{
     access::ModuleAccess,
     errors::PartialVMError,
     file_format::{
-        SignatureToken, StructDefinitionIndex, StructFieldInformation, StructHandleIndex,
+        FieldDefinition, SignatureToken, StructDefinitionIndex, StructFieldInformation,
+        StructHandleIndex,
     },
     views::FunctionHandleView,
     CompiledModule,
@@ -99,8 +100,11 @@ impl<'a, T: MoveResolver + ?Sized> Resolver<'a, T> {
             TypeTag::Bool => FatType::Bool,
             TypeTag::Struct(st) => FatType::Struct(Box::new(self.resolve_struct(st)?)),
             TypeTag::U8 => FatType::U8,
+            TypeTag::U16 => FatType::U16,
+            TypeTag::U32 => FatType::U32,
             TypeTag::U64 => FatType::U64,
             TypeTag::U128 => FatType::U128,
+            TypeTag::U256 => FatType::U256,
             TypeTag::Vector(ty) => FatType::Vector(Box::new(self.resolve_type(ty)?)),
         })
     }
@@ -126,13 +130,29 @@ impl<'a, T: MoveResolver + ?Sized> Resolver<'a, T> {
 
         match &struct_def.field_information {
             StructFieldInformation::Native => Err(anyhow!("Unexpected Native Struct")),
-            StructFieldInformation::Declared(defs) => Ok(defs
-                .iter()
-                .map(|field_def| module.identifier_at(field_def.name).to_owned())
-                .collect()),
+            StructFieldInformation::Declared(defs) => Ok(field_names(&module, defs)),
+            StructFieldInformation::DeclaredVariants(_) => Err(anyhow!("Unexpected Enum")),
         }
     }
 
+    /// Returns the name of the variant with index `tag` of an enum, and the names of its fields.
+    pub fn get_variant_names(
+        &self,
+        ty: &FatStructType,
+        tag: u16,
+    ) -> Result<(Identifier, Vec<Identifier>)> {
+        let module = self.get_module(&ty.address, ty.module.as_ident_str())?;
+        let struct_def_idx = find_struct_def_in_module(module.clone(), ty.name.as_ident_str())?;
+        let variant = module
+            .struct_def_at(struct_def_idx)
+            .variant(tag)
+            .ok_or_else(|| anyhow!("Variant {} of {} not found", tag, ty.name))?;
+        Ok((
+            module.identifier_at(variant.name).to_owned(),
+            field_names(&module, &variant.fields),
+        ))
+    }
+
     fn resolve_signature(
         &self,
         module: Rc<CompiledModule>,
@@ -141,8 +161,11 @@ impl<'a, T: MoveResolver + ?Sized> Resolver<'a, T> {
         Ok(match sig {
             SignatureToken::Bool => FatType::Bool,
             SignatureToken::U8 => FatType::U8,
+            SignatureToken::U16 => FatType::U16,
+            SignatureToken::U32 => FatType::U32,
             SignatureToken::U64 => FatType::U64,
             SignatureToken::U128 => FatType::U128,
+            SignatureToken::U256 => FatType::U256,
             SignatureToken::Address => FatType::Address,
             SignatureToken::Signer => FatType::Signer,
             SignatureToken::Vector(ty) => {
@@ -206,23 +229,40 @@ impl<'a, T: MoveResolver + ?Sized> Resolver<'a, T> {
         let ty_args = (0..struct_handle.type_parameters.len())
             .map(FatType::TyParam)
             .collect();
-        match &struct_def.field_information {
-            StructFieldInformation::Native => Err(anyhow!("Unexpected Native Struct")),
-            StructFieldInformation::Declared(defs) => Ok(FatStructType {
-                address,
-                module: module_name,
-                name,
-                abilities: WrappedAbilitySet(abilities),
-                ty_args,
-                layout: defs
+        let resolve_fields = |defs: &[FieldDefinition]| {
+            defs.iter()
+                .map(|field_def| self.resolve_signature(module.clone(), &field_def.signature.0))
+                .collect::<Result<Vec<_>>>()
+        };
+        let (layout, variants) = match &struct_def.field_information {
+            StructFieldInformation::Native => return Err(anyhow!("Unexpected Native Struct")),
+            StructFieldInformation::Declared(defs) => (resolve_fields(defs)?, vec![]),
+            StructFieldInformation::DeclaredVariants(variants) => (
+                vec![],
+                variants
                     .iter()
-                    .map(|field_def| self.resolve_signature(module.clone(), &field_def.signature.0))
+                    .map(|variant| resolve_fields(&variant.fields))
                     .collect::<Result<_>>()?,
-            }),
-        }
+            ),
+        };
+        Ok(FatStructType {
+            address,
+            module: module_name,
+            name,
+            abilities: WrappedAbilitySet(abilities),
+            ty_args,
+            layout,
+            variants,
+        })
     }
 }
 
+fn field_names(module: &CompiledModule, defs: &[FieldDefinition]) -> Vec<Identifier> {
+    defs.iter()
+        .map(|field_def| module.identifier_at(field_def.name).to_owned())
+        .collect()
+}
+
 fn find_struct_def_in_module(
     module: Rc<CompiledModule>,
     name: &IdentStr,
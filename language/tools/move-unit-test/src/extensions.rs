@@ -11,9 +11,11 @@ use std::{fmt::Write, sync::Mutex};
 
 #[cfg(feature = "table-extension")]
 use itertools::Itertools;
+#[cfg(feature = "aggregator-extension")]
+use move_aggregator_extension::{AggregatorChange, NativeAggregatorContext};
 #[cfg(feature = "table-extension")]
 use move_table_extension::NativeTableContext;
-#[cfg(feature = "table-extension")]
+#[cfg(any(feature = "table-extension", feature = "aggregator-extension"))]
 use move_vm_test_utils::BlankStorage;
 
 static EXTENSION_HOOK: Lazy<
@@ -30,7 +32,7 @@ static EXTENSION_HOOK: Lazy<
 /// (b) Before `cli::run_move_unit_tests` if unit tests are called programmatically from Rust.
 /// You may want to define a new function `my_cli::run_move_unit_tests` which does this.
 ///
-/// Note that the table extension is handled already internally, and does not need to added via
+/// Note that the table and aggregator extensions are handled already internally, and does not need to added via
 /// this hook.
 pub fn set_extension_hook(p: Box<dyn Fn(&mut NativeContextExtensions<'_>) + Send + Sync>) {
     *EXTENSION_HOOK.lock().unwrap() = Some(p)
@@ -45,6 +47,8 @@ pub(crate) fn new_extensions<'a>() -> NativeContextExtensions<'a> {
     }
     #[cfg(feature = "table-extension")]
     create_table_extension(&mut e);
+    #[cfg(feature = "aggregator-extension")]
+    create_aggregator_extension(&mut e);
     e
 }
 
@@ -53,6 +57,8 @@ pub(crate) fn new_extensions<'a>() -> NativeContextExtensions<'a> {
 pub(crate) fn print_change_sets<W: Write>(_w: &mut W, mut extensions: NativeContextExtensions) {
     #[cfg(feature = "table-extension")]
     print_table_extension(_w, &mut extensions);
+    #[cfg(feature = "aggregator-extension")]
+    print_aggregator_extension(_w, &mut extensions);
 }
 
 // =============================================================================================
@@ -95,7 +101,29 @@ fn print_table_extension<W: Write>(w: &mut W, extensions: &mut NativeContextExte
     }
 }
 
-#[cfg(feature = "table-extension")]
+// =============================================================================================
+// Aggregator Extensions
+
+#[cfg(feature = "aggregator-extension")]
+fn create_aggregator_extension(extensions: &mut NativeContextExtensions) {
+    extensions.add(NativeAggregatorContext::new(0, &*DUMMY_RESOLVER));
+}
+
+#[cfg(feature = "aggregator-extension")]
+fn print_aggregator_extension<W: Write>(w: &mut W, extensions: &mut NativeContextExtensions) {
+    let cs = extensions
+        .remove::<NativeAggregatorContext>()
+        .into_change_set();
+    for (h, c) in cs.changes {
+        match c {
+            AggregatorChange::Write(value) => writeln!(w, "{} := {}", h, value).unwrap(),
+            AggregatorChange::Merge(delta) => writeln!(w, "{} += {:?}", h, delta.update).unwrap(),
+            AggregatorChange::Delete => writeln!(w, "{} deleted", h).unwrap(),
+        }
+    }
+}
+
+#[cfg(any(feature = "table-extension", feature = "aggregator-extension"))]
 static DUMMY_RESOLVER: Lazy<BlankStorage> = Lazy::new(|| BlankStorage);
 
 #[cfg(test)]
@@ -17,7 +17,7 @@ use move_compiler::{
     Compiler, Flags, PASS_CFGIR,
 };
 use move_core_types::language_storage::ModuleId;
-use move_vm_runtime::native_functions::NativeFunctionTable;
+use move_vm_runtime::{native_functions::NativeFunctionTable, trace_sink::TraceSink};
 use std::{
     collections::BTreeMap,
     io::{Result, Write},
@@ -107,6 +107,14 @@ pub struct UnitTestingConfig {
     #[clap(short = 'v', long = "verbose")]
     pub verbose: bool,
 
+    /// Number of times each #[random_test] is run with generated arguments
+    #[clap(name = "random_iterations", default_value = "100", long = "iterations")]
+    pub random_iterations: u32,
+
+    /// Seed for generating the arguments of #[random_test]s. A random seed is used if not set
+    #[clap(name = "random_seed", long = "seed")]
+    pub random_seed: Option<u64>,
+
     /// Use the EVM-based execution backend.
     /// Does not work with --stackless.
     #[cfg(feature = "evm-backend")]
@@ -136,6 +144,8 @@ impl UnitTestingConfig {
             dep_files: vec![],
             check_stackless_vm: false,
             verbose: false,
+            random_iterations: 100,
+            random_seed: None,
             list: false,
             named_address_values: vec![],
 
@@ -205,6 +215,18 @@ impl UnitTestingConfig {
         test_plan: TestPlan,
         native_function_table: Option<NativeFunctionTable>,
         writer: W,
+    ) -> Result<(W, bool)> {
+        self.run_and_report_unit_tests_with_debugger(test_plan, native_function_table, None, writer)
+    }
+
+    /// Like `run_and_report_unit_tests`, with `debugger` receiving the execution events of the
+    /// tests, e.g. to step through the test selected by `filter`
+    pub fn run_and_report_unit_tests_with_debugger<W: Write + Send>(
+        &self,
+        test_plan: TestPlan,
+        native_function_table: Option<NativeFunctionTable>,
+        debugger: Option<Box<dyn TraceSink + Send>>,
+        writer: W,
     ) -> Result<(W, bool)> {
         let shared_writer = Mutex::new(writer);
 
@@ -230,6 +252,8 @@ impl UnitTestingConfig {
             self.verbose,
             self.report_storage_on_error,
             self.report_stacktrace_on_abort,
+            self.random_iterations,
+            self.random_seed,
             test_plan,
             native_function_table,
             verify_and_create_named_address_mapping(self.named_address_values.clone()).unwrap(),
@@ -241,6 +265,9 @@ impl UnitTestingConfig {
         if let Some(filter_str) = &self.filter {
             test_runner.filter(filter_str)
         }
+        if let Some(debugger) = debugger {
+            test_runner.set_debugger(debugger)
+        }
 
         let test_results = test_runner.run(&shared_writer).unwrap();
         if self.report_statistics {
@@ -56,6 +56,16 @@ pub struct TestFailure {
     pub vm_error: Option<VMError>,
     pub failure_reason: FailureReason,
     pub storage_state: Option<String>,
+    pub counterexample: Option<Counterexample>,
+}
+
+/// The minimized arguments a #[random_test] failed with
+#[derive(Debug, Clone, Ord, PartialOrd, PartialEq, Eq)]
+pub struct Counterexample {
+    /// The generated arguments, in the syntax of test attributes
+    pub arguments: String,
+    /// The seed the arguments were generated with
+    pub seed: u64,
 }
 
 #[derive(Debug, Clone, Ord, PartialOrd, PartialEq, Eq)]
@@ -148,9 +158,15 @@ impl TestFailure {
             vm_error,
             failure_reason,
             storage_state,
+            counterexample: None,
         }
     }
 
+    pub fn with_counterexample(mut self, counterexample: Counterexample) -> Self {
+        self.counterexample = Some(counterexample);
+        self
+    }
+
     pub fn render_error(&self, test_plan: &TestPlan) -> String {
         let error_string = match &self.failure_reason {
             FailureReason::NoAbort(message) => message.to_string(),
@@ -211,6 +227,14 @@ impl TestFailure {
             }
         };
 
+        let error_string = match &self.counterexample {
+            None => error_string,
+            Some(Counterexample { arguments, seed }) => format!(
+                "{}\n────── Minimized counterexample (seed {}) ──────\n{}",
+                error_string, seed, arguments
+            ),
+        };
+
         match &self.storage_state {
             None => error_string,
             Some(storage_state) => {
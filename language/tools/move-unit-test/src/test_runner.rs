@@ -3,7 +3,9 @@
 
 use crate::{
     extensions, format_module_id,
-    test_reporter::{FailureReason, TestFailure, TestResults, TestRunInfo, TestStatistics},
+    test_reporter::{
+        Counterexample, FailureReason, TestFailure, TestResults, TestRunInfo, TestStatistics,
+    },
 };
 use anyhow::Result;
 use colored::*;
@@ -12,14 +14,15 @@ use move_binary_format::{errors::VMResult, file_format::CompiledModule};
 use move_bytecode_utils::Modules;
 use move_compiler::{
     shared::{Flags, NumericalAddress, PackagePaths},
-    unit_test::{ExpectedFailure, ModuleTestPlan, TestCase, TestPlan},
+    unit_test::{ExpectedFailure, ModuleTestPlan, TestArgument, TestCase, TestPlan},
 };
 use move_core_types::{
     account_address::AccountAddress,
     effects::ChangeSet,
     gas_schedule::{CostTable, GasAlgebra, GasCost, GasUnits},
     identifier::IdentStr,
-    value::serialize_values,
+    u256,
+    value::{serialize_values, MoveTypeLayout, MoveValue},
     vm_status::StatusCode,
 };
 use move_model::{
@@ -32,11 +35,28 @@ use move_stackless_bytecode_interpreter::{
     shared::bridge::{adapt_move_vm_change_set, adapt_move_vm_result},
     StacklessBytecodeInterpreter,
 };
-use move_vm_runtime::{move_vm::MoveVM, native_functions::NativeFunctionTable};
+use move_vm_runtime::{
+    move_vm::MoveVM,
+    native_functions::NativeFunctionTable,
+    trace_sink::{TraceEvent, TraceSink},
+};
 use move_vm_test_utils::InMemoryStorage;
 use move_vm_types::gas_schedule::{zero_cost_schedule, GasStatus};
+use proptest::{
+    collection::vec,
+    prelude::*,
+    test_runner::{Config, RngAlgorithm, TestError, TestRng, TestRunner as PropTestRunner},
+};
 use rayon::prelude::*;
-use std::{collections::BTreeMap, io::Write, marker::Send, sync::Mutex, time::Instant};
+use std::{
+    cell::Cell,
+    collections::{hash_map::RandomState, BTreeMap},
+    hash::{BuildHasher, Hasher},
+    io::Write,
+    marker::Send,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
 
 use move_vm_runtime::native_extensions::NativeContextExtensions;
 #[cfg(feature = "evm-backend")]
@@ -61,6 +81,9 @@ pub struct SharedTestingConfig {
     named_address_values: BTreeMap<String, NumericalAddress>,
     check_stackless_vm: bool,
     verbose: bool,
+    random_iterations: u32,
+    random_seed: u64,
+    debugger: Option<Arc<Mutex<Box<dyn TraceSink + Send>>>>,
 
     #[cfg(feature = "evm-backend")]
     evm: bool,
@@ -72,6 +95,25 @@ pub struct TestRunner {
     tests: TestPlan,
 }
 
+/// The outcome of a single run of a test
+enum TestOutcome {
+    Pass(TestRunInfo),
+    Fail(TestFailure),
+    Timeout(TestFailure),
+}
+
+/// Forwards the execution events of a test to the debugger shared by all tests
+struct DebuggerSink(Arc<Mutex<Box<dyn TraceSink + Send>>>);
+
+impl TraceSink for DebuggerSink {
+    fn on_event(&mut self, event: TraceEvent) {
+        self.0.lock().unwrap().on_event(event)
+    }
+}
+
+/// The maximum length of the vectors generated as arguments of a #[random_test]
+const MAX_RANDOM_VECTOR_LENGTH: usize = 16;
+
 /// A gas schedule where every instruction has a cost of "1". This is used to bound execution of a
 /// test to a certain number of ticks.
 fn unit_cost_table(num_of_native_funcs: usize) -> CostTable {
@@ -104,6 +146,60 @@ fn setup_test_storage<'a>(
     Ok(storage)
 }
 
+/// A strategy generating values of `layout` for the arguments of a #[random_test]
+fn random_value_strategy(layout: &MoveTypeLayout) -> BoxedStrategy<MoveValue> {
+    match layout {
+        MoveTypeLayout::Bool => any::<bool>().prop_map(MoveValue::Bool).boxed(),
+        MoveTypeLayout::U8 => any::<u8>().prop_map(MoveValue::U8).boxed(),
+        MoveTypeLayout::U16 => any::<u16>().prop_map(MoveValue::U16).boxed(),
+        MoveTypeLayout::U32 => any::<u32>().prop_map(MoveValue::U32).boxed(),
+        MoveTypeLayout::U64 => any::<u64>().prop_map(MoveValue::U64).boxed(),
+        MoveTypeLayout::U128 => any::<u128>().prop_map(MoveValue::U128).boxed(),
+        MoveTypeLayout::U256 => any::<[u8; 32]>()
+            .prop_map(|bytes| MoveValue::U256(u256::U256::from_le_bytes(&bytes)))
+            .boxed(),
+        MoveTypeLayout::Address => any::<[u8; AccountAddress::LENGTH]>()
+            .prop_map(|bytes| MoveValue::Address(AccountAddress::new(bytes)))
+            .boxed(),
+        MoveTypeLayout::Vector(layout) => {
+            vec(random_value_strategy(layout), 0..=MAX_RANDOM_VECTOR_LENGTH)
+                .prop_map(MoveValue::Vector)
+                .boxed()
+        }
+        MoveTypeLayout::Signer | MoveTypeLayout::Struct(_) => {
+            unreachable!("ICE: values of {:?} cannot be generated", layout)
+        }
+    }
+}
+
+/// Formats the generated arguments of a #[random_test] in the syntax of test attributes, e.g.
+/// `x = 1u64, v = vector[@0x1]`
+fn format_random_arguments(random_arguments: &[TestArgument], arguments: &[MoveValue]) -> String {
+    fn format_value(value: &MoveValue) -> String {
+        match value {
+            MoveValue::Address(addr) => format!("@{}", addr.to_hex_literal()),
+            MoveValue::Vector(values) => format!(
+                "vector[{}]",
+                values
+                    .iter()
+                    .map(format_value)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            _ => value.to_string(),
+        }
+    }
+    random_arguments
+        .iter()
+        .zip(arguments)
+        .filter_map(|(argument, value)| match argument {
+            TestArgument::Random(name, _) => Some(format!("{} = {}", name, format_value(value))),
+            TestArgument::Value(_) => None,
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 /// Print the updates to storage represented by `cs` in the context of the starting storage state
 /// `storage`.
 fn print_resources_and_extensions(
@@ -140,6 +236,8 @@ impl TestRunner {
         verbose: bool,
         save_storage_state_on_failure: bool,
         report_stacktrace_on_abort: bool,
+        random_iterations: u32,
+        random_seed: Option<u64>,
         tests: TestPlan,
         native_function_table: Option<NativeFunctionTable>,
         named_address_values: BTreeMap<String, NumericalAddress>,
@@ -156,6 +254,7 @@ impl TestRunner {
             move_stdlib::natives::all_natives(AccountAddress::from_hex_literal("0x1").unwrap())
         });
         let num_of_native_funcs = native_function_table.len();
+        let random_seed = random_seed.unwrap_or_else(|| RandomState::new().build_hasher().finish());
         Ok(Self {
             testing_config: SharedTestingConfig {
                 save_storage_state_on_failure,
@@ -167,7 +266,10 @@ impl TestRunner {
                 source_files,
                 check_stackless_vm,
                 verbose,
+                random_iterations,
+                random_seed,
                 named_address_values,
+                debugger: None,
                 #[cfg(feature = "evm-backend")]
                 evm,
             },
@@ -193,6 +295,12 @@ impl TestRunner {
             })
     }
 
+    /// Attach `debugger` to the Move VM sessions executing the tests. Meant to be used together
+    /// with `filter`, to step through a single test.
+    pub fn set_debugger(&mut self, debugger: Box<dyn TraceSink + Send>) {
+        self.testing_config.debugger = Some(Arc::new(Mutex::new(debugger)));
+    }
+
     pub fn filter(&mut self, test_name_slice: &str) {
         for (module_id, module_test) in self.tests.module_tests.iter_mut() {
             if module_id.name().as_str().contains(test_name_slice) {
@@ -253,8 +361,9 @@ impl SharedTestingConfig {
     fn execute_via_move_vm(
         &self,
         test_plan: &ModuleTestPlan,
-        function_name: &str,
+        test_name: &str,
         test_info: &TestCase,
+        arguments: &[MoveValue],
     ) -> (
         VMResult<ChangeSet>,
         VMResult<NativeContextExtensions>,
@@ -265,15 +374,18 @@ impl SharedTestingConfig {
         let extensions = extensions::new_extensions();
         let mut session =
             move_vm.new_session_with_extensions(&self.starting_storage_state, extensions);
+        if let Some(debugger) = &self.debugger {
+            session.set_trace_sink(DebuggerSink(debugger.clone()));
+        }
         let mut gas_meter = GasStatus::new(&self.cost_table, GasUnits::new(self.execution_bound));
         // TODO: collect VM logs if the verbose flag (i.e, `self.verbose`) is set
 
         let now = Instant::now();
         let serialized_return_values_result = session.execute_function_bypass_visibility(
             &test_plan.module_id,
-            IdentStr::new(function_name).unwrap(),
+            IdentStr::new(&test_info.function_name).unwrap(),
             vec![], // no ty args, at least for now
-            serialize_values(test_info.arguments.iter()),
+            serialize_values(arguments.iter()),
             &mut gas_meter,
         );
         let mut return_result = serialized_return_values_result.map(|res| {
@@ -288,7 +400,7 @@ impl SharedTestingConfig {
             }
         }
         let test_run_info = TestRunInfo::new(
-            function_name.to_string(),
+            test_name.to_string(),
             now.elapsed(),
             self.execution_bound - gas_meter.remaining_gas().get(),
         );
@@ -302,8 +414,9 @@ impl SharedTestingConfig {
         &self,
         env: &GlobalEnv,
         test_plan: &ModuleTestPlan,
-        function_name: &str,
+        test_name: &str,
         test_info: &TestCase,
+        arguments: &[MoveValue],
     ) -> (
         VMResult<ChangeSet>,
         VMResult<Vec<Vec<u8>>>,
@@ -325,15 +438,15 @@ impl SharedTestingConfig {
         let global_state = GlobalState::default();
         let (return_result, change_set, _) = interpreter.interpret(
             &test_plan.module_id,
-            IdentStr::new(function_name).unwrap(),
+            IdentStr::new(&test_info.function_name).unwrap(),
             &[], // no ty args, at least for now
-            &test_info.arguments,
+            arguments,
             &global_state,
         );
         let prop_check_result = interpreter.report_property_checking_results();
 
         let test_run_info = TestRunInfo::new(
-            function_name.to_string(),
+            test_name.to_string(),
             now.elapsed(),
             // NOTE (mengxu) instruction counting on stackless VM might not be very useful because
             // gas is not charged against stackless VM instruction.
@@ -386,173 +499,244 @@ impl SharedTestingConfig {
 
         let mut stats = TestStatistics::new();
 
-        for (function_name, test_info) in &test_plan.tests {
-            let (cs_result, ext_result, exec_result, test_run_info) =
-                self.execute_via_move_vm(test_plan, function_name, test_info);
-            if self.check_stackless_vm {
-                let (stackless_vm_change_set, stackless_vm_result, _, prop_check_result) = self
-                    .execute_via_stackless_vm(
-                        stackless_model.as_ref().unwrap(),
-                        test_plan,
-                        function_name,
-                        test_info,
-                    );
-                let move_vm_result = adapt_move_vm_result(exec_result.clone());
-                let move_vm_change_set =
-                    adapt_move_vm_change_set(cs_result.clone(), &self.starting_storage_state);
-                if stackless_vm_result != move_vm_result
-                    || stackless_vm_change_set != move_vm_change_set
-                {
-                    output.fail(function_name);
-                    stats.test_failure(
-                        TestFailure::new(
-                            FailureReason::mismatch(
-                                move_vm_result,
-                                move_vm_change_set,
-                                stackless_vm_result,
-                                stackless_vm_change_set,
-                            ),
-                            test_run_info,
-                            None,
-                            None,
-                        ),
-                        test_plan,
-                    );
-                    continue;
+        for (test_name, test_info) in &test_plan.tests {
+            let outcome = match &test_info.random_arguments {
+                None => self.exec_test(
+                    test_plan,
+                    test_name,
+                    test_info,
+                    &test_info.arguments,
+                    stackless_model.as_ref(),
+                ),
+                Some(random_arguments) => self.exec_random_test(
+                    test_plan,
+                    test_name,
+                    test_info,
+                    random_arguments,
+                    stackless_model.as_ref(),
+                ),
+            };
+            match outcome {
+                TestOutcome::Pass(test_run_info) => {
+                    output.pass(test_name);
+                    stats.test_success(test_run_info, test_plan);
                 }
-                if let Some(prop_failure) = prop_check_result {
-                    output.fail(function_name);
-                    stats.test_failure(
-                        TestFailure::new(
-                            FailureReason::property(prop_failure),
-                            test_run_info,
-                            None,
-                            None,
-                        ),
-                        test_plan,
-                    );
-                    continue;
+                TestOutcome::Fail(failure) => {
+                    output.fail(test_name);
+                    stats.test_failure(failure, test_plan);
+                }
+                TestOutcome::Timeout(failure) => {
+                    output.timeout(test_name);
+                    stats.test_failure(failure, test_plan);
                 }
             }
+        }
+
+        stats
+    }
 
-            let save_session_state = || {
-                if self.save_storage_state_on_failure {
-                    cs_result.ok().and_then(|changeset| {
-                        ext_result.ok().and_then(|extensions| {
-                            print_resources_and_extensions(
-                                &changeset,
-                                extensions,
-                                &self.starting_storage_state,
-                            )
-                            .ok()
-                        })
+    /// Runs a test once with the given `arguments`
+    fn exec_test(
+        &self,
+        test_plan: &ModuleTestPlan,
+        test_name: &str,
+        test_info: &TestCase,
+        arguments: &[MoveValue],
+        stackless_model: Option<&GlobalEnv>,
+    ) -> TestOutcome {
+        let (cs_result, ext_result, exec_result, test_run_info) =
+            self.execute_via_move_vm(test_plan, test_name, test_info, arguments);
+        if self.check_stackless_vm {
+            let (stackless_vm_change_set, stackless_vm_result, _, prop_check_result) = self
+                .execute_via_stackless_vm(
+                    stackless_model.unwrap(),
+                    test_plan,
+                    test_name,
+                    test_info,
+                    arguments,
+                );
+            let move_vm_result = adapt_move_vm_result(exec_result.clone());
+            let move_vm_change_set =
+                adapt_move_vm_change_set(cs_result.clone(), &self.starting_storage_state);
+            if stackless_vm_result != move_vm_result
+                || stackless_vm_change_set != move_vm_change_set
+            {
+                return TestOutcome::Fail(TestFailure::new(
+                    FailureReason::mismatch(
+                        move_vm_result,
+                        move_vm_change_set,
+                        stackless_vm_result,
+                        stackless_vm_change_set,
+                    ),
+                    test_run_info,
+                    None,
+                    None,
+                ));
+            }
+            if let Some(prop_failure) = prop_check_result {
+                return TestOutcome::Fail(TestFailure::new(
+                    FailureReason::property(prop_failure),
+                    test_run_info,
+                    None,
+                    None,
+                ));
+            }
+        }
+
+        let save_session_state = || {
+            if self.save_storage_state_on_failure {
+                cs_result.ok().and_then(|changeset| {
+                    ext_result.ok().and_then(|extensions| {
+                        print_resources_and_extensions(
+                            &changeset,
+                            extensions,
+                            &self.starting_storage_state,
+                        )
+                        .ok()
                     })
+                })
+            } else {
+                None
+            }
+        };
+        match exec_result {
+            Err(err) => match (test_info.expected_failure.as_ref(), err.sub_status()) {
+                // Ran out of ticks, report a test timeout and log a test failure
+                _ if err.major_status() == StatusCode::OUT_OF_GAS => {
+                    TestOutcome::Timeout(TestFailure::new(
+                        FailureReason::timeout(),
+                        test_run_info,
+                        Some(err),
+                        save_session_state(),
+                    ))
+                }
+                // Expected the test to not abort, but it aborted with `code`
+                (None, Some(code)) => TestOutcome::Fail(TestFailure::new(
+                    FailureReason::aborted(code),
+                    test_run_info,
+                    Some(err),
+                    save_session_state(),
+                )),
+                // Expected the test the abort with a specific `code`, and it did abort with
+                // that abort code
+                (Some(ExpectedFailure::ExpectedWithCode(code)), Some(other_code))
+                    if matches!(
+                        err.major_status(),
+                        StatusCode::ABORTED | StatusCode::VECTOR_OPERATION_ERROR
+                    ) && *code == other_code =>
+                {
+                    TestOutcome::Pass(test_run_info)
+                }
+                // Expected the test to abort with a specific `code` but it aborted with a
+                // different `other_code`
+                (Some(ExpectedFailure::ExpectedWithCode(code)), Some(other_code)) => {
+                    TestOutcome::Fail(TestFailure::new(
+                        FailureReason::wrong_abort(*code, other_code),
+                        test_run_info,
+                        Some(err),
+                        save_session_state(),
+                    ))
+                }
+                // Expected the test to abort and it aborted, but we don't need to check the code
+                (Some(ExpectedFailure::Expected), Some(_)) => TestOutcome::Pass(test_run_info),
+                // Expected the test to abort and it aborted with internal error
+                (Some(ExpectedFailure::Expected), None)
+                    if err.major_status() != StatusCode::EXECUTED =>
+                {
+                    TestOutcome::Pass(test_run_info)
+                }
+                // Unexpected return status from the VM, signal that we hit an unknown error.
+                (_, None) => TestOutcome::Fail(TestFailure::new(
+                    FailureReason::unknown(),
+                    test_run_info,
+                    Some(err),
+                    save_session_state(),
+                )),
+            },
+            Ok(_) => {
+                // Expected the test to fail, but it executed
+                if test_info.expected_failure.is_some() {
+                    TestOutcome::Fail(TestFailure::new(
+                        FailureReason::no_abort(),
+                        test_run_info,
+                        None,
+                        save_session_state(),
+                    ))
                 } else {
-                    None
+                    // Expected the test to execute fully and it did
+                    TestOutcome::Pass(test_run_info)
                 }
-            };
-            match exec_result {
-                Err(err) => match (test_info.expected_failure.as_ref(), err.sub_status()) {
-                    // Ran out of ticks, report a test timeout and log a test failure
-                    _ if err.major_status() == StatusCode::OUT_OF_GAS => {
-                        output.timeout(function_name);
-                        stats.test_failure(
-                            TestFailure::new(
-                                FailureReason::timeout(),
-                                test_run_info,
-                                Some(err),
-                                save_session_state(),
-                            ),
-                            test_plan,
-                        )
-                    }
-                    // Expected the test to not abort, but it aborted with `code`
-                    (None, Some(code)) => {
-                        output.fail(function_name);
-                        stats.test_failure(
-                            TestFailure::new(
-                                FailureReason::aborted(code),
-                                test_run_info,
-                                Some(err),
-                                save_session_state(),
-                            ),
-                            test_plan,
-                        )
-                    }
-                    // Expected the test the abort with a specific `code`, and it did abort with
-                    // that abort code
-                    (Some(ExpectedFailure::ExpectedWithCode(code)), Some(other_code))
-                        if matches!(
-                            err.major_status(),
-                            StatusCode::ABORTED | StatusCode::VECTOR_OPERATION_ERROR
-                        ) && *code == other_code =>
-                    {
-                        output.pass(function_name);
-                        stats.test_success(test_run_info, test_plan);
-                    }
-                    // Expected the test to abort with a specific `code` but it aborted with a
-                    // different `other_code`
-                    (Some(ExpectedFailure::ExpectedWithCode(code)), Some(other_code)) => {
-                        output.fail(function_name);
-                        stats.test_failure(
-                            TestFailure::new(
-                                FailureReason::wrong_abort(*code, other_code),
-                                test_run_info,
-                                Some(err),
-                                save_session_state(),
-                            ),
-                            test_plan,
-                        )
-                    }
-                    // Expected the test to abort and it aborted, but we don't need to check the code
-                    (Some(ExpectedFailure::Expected), Some(_)) => {
-                        output.pass(function_name);
-                        stats.test_success(test_run_info, test_plan);
-                    }
-                    // Expected the test to abort and it aborted with internal error
-                    (Some(ExpectedFailure::Expected), None)
-                        if err.major_status() != StatusCode::EXECUTED =>
-                    {
-                        output.pass(function_name);
-                        stats.test_success(test_run_info, test_plan);
-                    }
-                    // Unexpected return status from the VM, signal that we hit an unknown error.
-                    (_, None) => {
-                        output.fail(function_name);
-                        stats.test_failure(
-                            TestFailure::new(
-                                FailureReason::unknown(),
-                                test_run_info,
-                                Some(err),
-                                save_session_state(),
-                            ),
-                            test_plan,
-                        )
+            }
+        }
+    }
+
+    /// Runs a #[random_test] with generated arguments until it fails or the number of iterations is
+    /// reached. On a failure, the generated arguments are shrunk to a minimal counterexample.
+    fn exec_random_test(
+        &self,
+        test_plan: &ModuleTestPlan,
+        test_name: &str,
+        test_info: &TestCase,
+        random_arguments: &[TestArgument],
+        stackless_model: Option<&GlobalEnv>,
+    ) -> TestOutcome {
+        let strategy: Vec<_> = random_arguments
+            .iter()
+            .map(|argument| match argument {
+                TestArgument::Value(value) => Just(value.clone()).boxed(),
+                TestArgument::Random(_, layout) => random_value_strategy(layout),
+            })
+            .collect();
+        let mut seed = [0u8; 32];
+        seed[..8].copy_from_slice(&self.random_seed.to_le_bytes());
+        let mut config = Config::with_cases(self.random_iterations);
+        config.failure_persistence = None;
+        let mut runner =
+            PropTestRunner::new_with_rng(config, TestRng::from_seed(RngAlgorithm::ChaCha, &seed));
+
+        let now = Instant::now();
+        let instructions_executed = Cell::new(0);
+        let result = runner.run(&strategy, |arguments| {
+            match self.exec_test(test_plan, test_name, test_info, &arguments, stackless_model) {
+                TestOutcome::Pass(test_run_info) => {
+                    instructions_executed
+                        .set(instructions_executed.get() + test_run_info.instructions_executed);
+                    Ok(())
+                }
+                TestOutcome::Fail(_) | TestOutcome::Timeout(_) => {
+                    Err(TestCaseError::fail("test failed"))
+                }
+            }
+        });
+        match result {
+            Ok(()) => TestOutcome::Pass(TestRunInfo::new(
+                test_name.to_string(),
+                now.elapsed(),
+                instructions_executed.get(),
+            )),
+            // Run the test again with the minimal counterexample to report its failure
+            Err(TestError::Fail(_, arguments)) => {
+                let counterexample = Counterexample {
+                    arguments: format_random_arguments(random_arguments, &arguments),
+                    seed: self.random_seed,
+                };
+                match self.exec_test(test_plan, test_name, test_info, &arguments, stackless_model) {
+                    TestOutcome::Fail(failure) => {
+                        TestOutcome::Fail(failure.with_counterexample(counterexample))
                     }
-                },
-                Ok(_) => {
-                    // Expected the test to fail, but it executed
-                    if test_info.expected_failure.is_some() {
-                        output.fail(function_name);
-                        stats.test_failure(
-                            TestFailure::new(
-                                FailureReason::no_abort(),
-                                test_run_info,
-                                None,
-                                save_session_state(),
-                            ),
-                            test_plan,
-                        )
-                    } else {
-                        // Expected the test to execute fully and it did
-                        output.pass(function_name);
-                        stats.test_success(test_run_info, test_plan);
+                    TestOutcome::Timeout(failure) => {
+                        TestOutcome::Timeout(failure.with_counterexample(counterexample))
                     }
+                    outcome => outcome,
                 }
             }
+            Err(TestError::Abort(_)) => TestOutcome::Fail(TestFailure::new(
+                FailureReason::unknown(),
+                TestRunInfo::new(test_name.to_string(), now.elapsed(), 0),
+                None,
+                None,
+            )),
         }
-
-        stats
     }
 
     #[cfg(feature = "evm-backend")]
@@ -619,11 +803,26 @@ impl SharedTestingConfig {
 
         let gen_options = move_to_yul::options::Options::default();
         for (function_name, test_info) in &test_plan.tests {
+            if test_info.random_arguments.is_some() {
+                output.fail(function_name);
+                stats.test_failure(
+                    TestFailure::new(
+                        FailureReason::move_to_evm_error(
+                            "Tests with random arguments are not supported".to_string(),
+                        ),
+                        TestRunInfo::new(function_name.to_string(), Duration::ZERO, 0),
+                        None,
+                        None,
+                    ),
+                    test_plan,
+                );
+                continue;
+            }
             let yul_code = match move_to_yul::generator::Generator::run_for_unit_test(
                 &gen_options,
                 &model,
                 &test_plan.module_id,
-                IdentStr::new(function_name).unwrap(),
+                IdentStr::new(&test_info.function_name).unwrap(),
                 &test_info.arguments,
             ) {
                 Ok(yul_code) => yul_code,
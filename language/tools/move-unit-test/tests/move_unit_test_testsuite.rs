@@ -82,6 +82,8 @@ fn run_test_impl(path: &Path) -> anyhow::Result<()> {
     let unit_test_config = UnitTestingConfig {
         num_threads: 1,
         instruction_execution_bound: 1000,
+        // Use a fixed seed so that the counterexamples of random tests are stable
+        random_seed: Some(0),
         source_files,
         dep_files: move_stdlib::move_stdlib_files(),
         named_address_values: move_stdlib::move_stdlib_named_addresses()
@@ -0,0 +1,41 @@
+address 0x1 {
+module M {
+    #[test(x=5, b=true, bytes=b"abc")]
+    fun primitive_args(x: u64, b: bool, bytes: vector<u8>) {
+        assert!(x == 5, 0);
+        assert!(b, 1);
+        assert!(bytes == vector[97, 98, 99], 2);
+    }
+
+    #[test(xs=vector[1, 2, 3], addrs=vector[@0x1, @0x2])]
+    fun vector_args(xs: vector<u128>, addrs: vector<address>) {
+        assert!(xs == vector[1, 2, 3], 0);
+        assert!(addrs == vector[@0x1, @0x2], 1);
+    }
+
+    #[test]
+    #[test_cases(zero(x=0, y=0), small(x=1, y=2), wrong(x=2, y=2))]
+    fun lt_or_zero(x: u8, y: u8) {
+        assert!(x == 0 || x < y, 0);
+    }
+
+    #[test_only]
+    use std::signer;
+
+    // arguments assigned in #[test(...)] are shared by all cases
+    #[test(s=@0x42)]
+    #[test_cases(first(n=1), second(n=2))]
+    fun shared_signer(s: signer, n: u16) {
+        assert!(signer::address_of(&s) == @0x42, 0);
+        assert!(n > 0, 1);
+    }
+
+    // an expected failure applies to each case
+    #[test]
+    #[test_cases(aborts(code=1), no_abort(code=0))]
+    #[expected_failure]
+    fun maybe_abort(code: u64) {
+        if (code > 0) abort code
+    }
+}
+}
@@ -0,0 +1,45 @@
+address 0x1 {
+module M {
+    #[test_only]
+    use std::signer;
+    #[test_only]
+    use std::vector;
+
+    #[random_test]
+    fun add_commutes(x: u64, y: u64) {
+        assert!((x as u128) + (y as u128) == (y as u128) + (x as u128), 0);
+    }
+
+    #[random_test]
+    fun reverse_twice(v: vector<u16>) {
+        let w = copy v;
+        vector::reverse(&mut w);
+        vector::reverse(&mut w);
+        assert!(v == w, 0);
+    }
+
+    // arguments assigned in the attribute are not generated
+    #[random_test(s=@0x42, n=7)]
+    fun assigned_args(s: signer, n: u8, _a: address, _b: bool) {
+        assert!(signer::address_of(&s) == @0x42, 0);
+        assert!(n == 7, 1);
+    }
+
+    #[random_test]
+    #[expected_failure(abort_code=0)]
+    fun always_aborts(_x: u256) {
+        abort 0
+    }
+
+    // fails, and the counterexample is shrunk to the smallest failing value
+    #[random_test]
+    fun less_than_ten(x: u64) {
+        assert!(x < 10, 0);
+    }
+
+    #[random_test(_s=@0x1)]
+    fun short_vectors(_s: signer, v: vector<vector<u8>>, b: bool) {
+        assert!(b || vector::length(&v) < 2, 0);
+    }
+}
+}